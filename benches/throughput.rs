@@ -0,0 +1,136 @@
+//! Throughput of the hot paths, so performance-motivated changes such as output
+//! buffering or zero-copy parsing have a baseline to compare with:
+//!
+//! - `round_trip`: requests per second answered by a [MockServer],
+//! - `put_image`: bytes per second of PutImage, serialized and sent,
+//! - `small_request`: fixed-size requests serialized on the stack and sent,
+//! - `events`: events per second parsed from a flood of MotionNotify.
+//!
+//! Run with `cargo bench --bench throughput`.
+
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion, Throughput};
+use mousetrap::event::Event;
+use mousetrap::protocol::XId;
+use mousetrap::protocol_state::ProtocolState;
+use mousetrap::reply::InputFocusReply;
+use mousetrap::request::{GetInputFocus, ImageFormat, MapWindow, PutImage};
+use mousetrap::testing::MockServer;
+use std::hint::black_box;
+
+/// Requests sent before waiting on their replies.
+const BATCH: usize = 100;
+/// Side of the square image of PutImage, small enough not to need BIG-REQUESTS.
+const IMAGE_SIDE: u16 = 128;
+/// Events in a flood.
+const FLOOD: usize = 1000;
+
+fn round_trip(c: &mut Criterion, server: &MockServer) {
+    let mut conn = server.connect().unwrap();
+    let request = GetInputFocus.serialize();
+    let mut group = c.benchmark_group("round_trip");
+    group.throughput(Throughput::Elements(BATCH as u64));
+    group.bench_function("get_input_focus", |b| {
+        b.iter(|| {
+            let cookies = (0..BATCH)
+                .map(|_| conn.send_request_with_reply::<InputFocusReply>(&request))
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap();
+            for cookie in cookies {
+                black_box(cookie.reply(&mut conn).unwrap());
+            }
+        })
+    });
+    group.finish();
+}
+
+fn put_image(c: &mut Criterion, server: &MockServer) {
+    let mut conn = server.connect().unwrap();
+    let data = vec![0x7fu8; IMAGE_SIDE as usize * IMAGE_SIDE as usize * 4];
+    let request = PutImage {
+        format: ImageFormat::Z_PIXMAP,
+        drawable: conn.default_root(),
+        gc: XId(0x0040_0001),
+        width: IMAGE_SIDE,
+        height: IMAGE_SIDE,
+        dst_x: 0,
+        dst_y: 0,
+        left_pad: 0,
+        depth: 24,
+        data: &data,
+    };
+    let mut group = c.benchmark_group("put_image");
+    group.throughput(Throughput::Bytes(data.len() as u64));
+    group.bench_function("serialize", |b| b.iter(|| black_box(request.serialize())));
+    group.bench_function("send", |b| b.iter(|| conn.put_image(&request).unwrap()));
+    group.finish();
+    conn.sync().unwrap();
+}
+
+fn small_request(c: &mut Criterion, server: &MockServer) {
+    let mut conn = server.connect().unwrap();
+    let request = MapWindow {
+        window: conn.default_root(),
+    };
+    let mut group = c.benchmark_group("small_request");
+    group.throughput(Throughput::Elements(1));
+    group.bench_function("serialize", |b| b.iter(|| black_box(request.serialize())));
+    group.bench_function("send", |b| {
+        b.iter(|| conn.send_request(&request.serialize()).unwrap())
+    });
+    group.finish();
+    conn.sync().unwrap();
+}
+
+/// A MotionNotify event as sent by the server.
+fn motion_notify(sequence: u16) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    bytes[0] = 6;
+    bytes[2..4].copy_from_slice(&sequence.to_le_bytes());
+    bytes[4..8].copy_from_slice(&1000u32.to_le_bytes()); // time
+    bytes[8..12].copy_from_slice(&0x100u32.to_le_bytes()); // root
+    bytes[12..16].copy_from_slice(&0x0040_0001u32.to_le_bytes()); // event
+    bytes[20..22].copy_from_slice(&640i16.to_le_bytes()); // root x
+    bytes[22..24].copy_from_slice(&480i16.to_le_bytes()); // root y
+    bytes[30] = 1; // same screen
+    bytes
+}
+
+fn events(c: &mut Criterion, server: &MockServer) {
+    let setup = server.connect().unwrap().setup().clone();
+    let flood: Vec<u8> = (0..FLOOD).flat_map(|_| motion_notify(0)).collect();
+    let mut group = c.benchmark_group("events");
+    group.throughput(Throughput::Elements(FLOOD as u64));
+    group.bench_function("from_bytes", |b| {
+        b.iter(|| {
+            for bytes in flood.chunks_exact(32) {
+                black_box(Event::from_bytes(bytes).unwrap());
+            }
+        })
+    });
+    // Splitting the stream into messages and queueing them, as a connection does.
+    group.bench_function("dispatch", |b| {
+        b.iter_batched(
+            || ProtocolState::new(setup.clone(), 0),
+            |mut state| {
+                state.bytes_received(&flood).unwrap();
+                while let Some(event) = state.take_event() {
+                    black_box(event);
+                }
+            },
+            BatchSize::SmallInput,
+        )
+    });
+    group.finish();
+}
+
+fn benches(c: &mut Criterion) {
+    // The server answers GetInputFocus and ignores the other requests.
+    let server = MockServer::builder().discard_requests().start().unwrap();
+    round_trip(c, &server);
+    put_image(c, &server);
+    small_request(c, &server);
+    events(c, &server);
+}
+
+criterion_group!(throughput, benches);
+criterion_main!(throughput);
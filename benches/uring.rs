@@ -0,0 +1,67 @@
+//! Compares the round-trip throughput of [mousetrap::uring::UringConnection]
+//! with the classic [mousetrap::connection::Connection], against a [MockServer].
+//!
+//! Run with `cargo bench --features io-uring --bench uring`.
+
+use mousetrap::reply::InputFocusReply;
+use mousetrap::request::GetInputFocus;
+use mousetrap::testing::MockServer;
+use std::time::{Duration, Instant};
+
+/// Requests sent before waiting on their replies.
+const BATCH: usize = 100;
+/// Batches per measurement.
+const ROUNDS: usize = 200;
+
+fn report(name: &str, elapsed: Duration) {
+    let requests = (BATCH * ROUNDS) as f64;
+    println!(
+        "{:<10} {:>10.0} requests/s ({:?} for {} round trips of {} requests)",
+        name,
+        requests / elapsed.as_secs_f64(),
+        elapsed,
+        ROUNDS,
+        BATCH
+    );
+}
+
+fn classic(server: &MockServer) -> Duration {
+    let mut conn = server.connect().unwrap();
+    let request = GetInputFocus.serialize();
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        let cookies = (0..BATCH)
+            .map(|_| conn.send_request_with_reply::<InputFocusReply>(&request))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        for cookie in cookies {
+            cookie.reply(&mut conn).unwrap();
+        }
+    }
+    start.elapsed()
+}
+
+fn uring(server: &MockServer) -> Duration {
+    let mut conn = server.connect_uring().unwrap();
+    let request = GetInputFocus.serialize();
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        let cookies = (0..BATCH)
+            .map(|_| conn.send_request_with_reply::<InputFocusReply>(&request))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        for cookie in cookies {
+            cookie.reply(&mut conn).unwrap();
+        }
+    }
+    start.elapsed()
+}
+
+fn main() {
+    let server = MockServer::builder().discard_requests().start().unwrap();
+    // Warms up both paths before measuring.
+    classic(&server);
+    uring(&server);
+    report("classic", classic(&server));
+    report("io_uring", uring(&server));
+}
@@ -0,0 +1,121 @@
+//! Following the active window, the way time and productivity trackers do.
+//!
+//! The window manager keeps the active window in the _NET_ACTIVE_WINDOW
+//! property of the root window. [ActiveWindowWatcher] selects PropertyNotify on
+//! the root and reports each change of it, with the [WindowInfo] of the new
+//! active window.
+//!
+//! ```no_run
+//! use mousetrap::active_window::ActiveWindowWatcher;
+//! use mousetrap::connection::Connection;
+//!
+//! let mut conn = Connection::init().unwrap();
+//! let mut watcher = ActiveWindowWatcher::new(&mut conn).unwrap();
+//! loop {
+//!     let change = watcher.next_change(&mut conn).unwrap();
+//!     match change.info {
+//!         Some(info) => println!("{:?} {:?}", info.title, info.class),
+//!         None => println!("no active window"),
+//!     }
+//! }
+//! ```
+
+use crate::connection::Connection;
+use crate::errors::ConnectionError;
+use crate::event::Event;
+use crate::protocol::{Atom, EventMask, Timestamp, Window};
+use crate::window_info::WindowInfo;
+
+/// The active window changed.
+#[derive(Debug, Clone)]
+pub struct ActiveWindowChange {
+    /// The new active window, or `None` if no window is active.
+    pub window: Option<Window>,
+    /// The information of the new active window, or `None` if there is none or
+    /// it was destroyed before it could be queried.
+    pub info: Option<WindowInfo>,
+    /// The server time of the change.
+    pub time: Timestamp,
+}
+
+/// Reports the changes of the active window.
+#[derive(Debug)]
+pub struct ActiveWindowWatcher {
+    root: Window,
+    active_window: Atom,
+    /// The active window of the last change.
+    current: Option<Window>,
+}
+
+impl ActiveWindowWatcher {
+    /// Starts watching the active window of the default screen. PropertyNotify is
+    /// added to the events the connection selects on the root window.
+    pub fn new(conn: &mut Connection) -> Result<ActiveWindowWatcher, ConnectionError> {
+        let root = conn.default_root();
+        let attributes = conn.get_window_attributes(root)?.reply(conn)?;
+        let mask = EventMask(attributes.your_event_mask) | EventMask::PROPERTY_CHANGE;
+        conn.select_input(root, mask)?;
+        let atoms = conn.ewmh_atoms()?;
+        Ok(ActiveWindowWatcher {
+            root,
+            active_window: atoms.active_window,
+            current: conn.ewmh_active_window()?,
+        })
+    }
+
+    /// Returns the active window as of the last change.
+    pub fn current(&self) -> Option<Window> {
+        self.current
+    }
+
+    /// Returns the next change of the active window, blocking until there is one.
+    /// Other events stay queued.
+    pub fn next_change(
+        &mut self,
+        conn: &mut Connection,
+    ) -> Result<ActiveWindowChange, ConnectionError> {
+        let (root, active_window) = (self.root, self.active_window);
+        loop {
+            let event = conn.wait_for_event_matching(|event| {
+                matches!(event, Event::PropertyNotify(e) if e.window == root && e.atom == active_window)
+            })?;
+            if let Some(change) = self.handle_event(conn, &event)? {
+                return Ok(change);
+            }
+        }
+    }
+
+    /// Handles an event read by the caller's own loop. Returns the change if the
+    /// event is a PropertyNotify of _NET_ACTIVE_WINDOW giving another window.
+    pub fn handle_event(
+        &mut self,
+        conn: &mut Connection,
+        event: &Event,
+    ) -> Result<Option<ActiveWindowChange>, ConnectionError> {
+        let Event::PropertyNotify(notify) = event else {
+            return Ok(None);
+        };
+        if notify.window != self.root || notify.atom != self.active_window {
+            return Ok(None);
+        }
+        // The window manager may set the property again to the same window.
+        let window = conn.ewmh_active_window()?;
+        if window == self.current {
+            return Ok(None);
+        }
+        self.current = window;
+        let info = match window {
+            Some(window) => match WindowInfo::query(conn, window) {
+                Ok(info) => Some(info),
+                Err(ConnectionError::ProtocolError(_)) => None,
+                Err(err) => return Err(err),
+            },
+            None => None,
+        };
+        Ok(Some(ActiveWindowChange {
+            window,
+            info,
+            time: notify.time,
+        }))
+    }
+}
@@ -0,0 +1,607 @@
+//! An async connection to the X server, for daemons running on an async runtime.
+//!
+//! The connection is independent of the runtime: it does its I/O through an
+//! [AsyncIoDriver], and the protocol itself is handled by the sans-io
+//! [ProtocolState]. Drivers are provided for tokio (the `tokio` feature) and for
+//! async-std (the `async-std` feature).
+//!
+//! It speaks the same protocol as the blocking [crate::connection::Connection] and
+//! shares its serialization: requests are serialized with the types of
+//! [crate::request] or a [crate::request::RequestBuilder], and replies are parsed
+//! by their [Reply] implementation. Only the waiting is async.
+//!
+//! ```no_run
+//! # #[cfg(feature = "tokio")]
+//! # async fn example() -> Result<(), mousetrap::errors::ConnectionError> {
+//! use mousetrap::aio::TokioConnection;
+//! use mousetrap::reply::InternAtomReply;
+//! use mousetrap::request::InternAtom;
+//!
+//! let mut conn = TokioConnection::init().await?;
+//! let request = InternAtom {
+//!     only_if_exists: false,
+//!     name: b"_NET_WM_NAME",
+//! };
+//! let cookie = conn
+//!     .send_request_with_reply::<InternAtomReply>(&request.serialize())
+//!     .await?;
+//! println!("atom: {:?}", cookie.reply(&mut conn).await?.atom);
+//!
+//! let mut events = conn.events();
+//! while let Some(event) = events.next().await {
+//!     println!("{:?}", event?);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The connection is used through `&mut`, so it belongs to a single task. Waiting
+//! for a reply or an event is cancel safe: bytes read before the future is dropped
+//! stay buffered in the connection, and a reply whose wait was dropped is kept
+//! until it is waited on again; [Cookie::reply_timeout] discards it instead.
+//! Sending is not, as a request written in part
+//! leaves the connection unusable. File descriptors can not be passed.
+
+use crate::connection::{parse_conf, ExtensionInfo};
+use crate::cookie::{parse_reply, ParseMode, Reply};
+use crate::errors::{ConnectAttempt, ConnectionError, XError};
+use crate::event::Event;
+use crate::protocol::{ConnSetup, ConnSetupRequest, Screen, Window, XId};
+use crate::protocol_state::ProtocolState;
+use crate::reply::{InputFocusReply, QueryExtensionReply};
+use crate::request::{GetInputFocus, QueryExtension};
+use std::env;
+use std::future::{poll_fn, Future};
+use std::io;
+use std::marker::PhantomData;
+use std::pin::pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+/// The socket of an async [Connection], as provided by an async runtime.
+///
+/// Reads and writes do not wait: they fail with [io::ErrorKind::WouldBlock] until
+/// the runtime reports the socket ready again.
+pub trait AsyncIoDriver: Sized + Send {
+    /// Connects to the unix socket at `path`.
+    fn connect_unix(path: &str) -> impl Future<Output = io::Result<Self>> + Send;
+
+    /// Connects over TCP.
+    fn connect_tcp(host: &str, port: u16) -> impl Future<Output = io::Result<Self>> + Send;
+
+    /// Polls whether the socket can be read from.
+    fn poll_readable(&self, cx: &mut Context<'_>) -> Poll<io::Result<()>>;
+
+    /// Polls whether the socket can be written to.
+    fn poll_writable(&self, cx: &mut Context<'_>) -> Poll<io::Result<()>>;
+
+    /// Reads what is available without waiting.
+    fn try_read(&self, buf: &mut [u8]) -> io::Result<usize>;
+
+    /// Writes what fits without waiting.
+    fn try_write(&self, buf: &[u8]) -> io::Result<usize>;
+
+    /// Completes once `duration` elapsed, on the timer of the runtime.
+    fn sleep(duration: Duration) -> impl Future<Output = ()> + Send;
+}
+
+/// Reads bytes into `buf`, waiting until some are available.
+async fn read<D: AsyncIoDriver>(driver: &D, buf: &mut [u8]) -> io::Result<usize> {
+    loop {
+        match driver.try_read(buf) {
+            Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => {
+                poll_fn(|cx| driver.poll_readable(cx)).await?
+            }
+            Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+            result => return result,
+        }
+    }
+}
+
+/// Reads exactly enough bytes to fill `buf`.
+async fn read_exact<D: AsyncIoDriver>(driver: &D, mut buf: &mut [u8]) -> io::Result<()> {
+    while !buf.is_empty() {
+        match read(driver, buf).await? {
+            0 => return Err(io::Error::from(io::ErrorKind::UnexpectedEof)),
+            n => buf = &mut buf[n..],
+        }
+    }
+    Ok(())
+}
+
+/// Writes the whole buffer.
+async fn write_all<D: AsyncIoDriver>(driver: &D, mut data: &[u8]) -> io::Result<()> {
+    while !data.is_empty() {
+        match driver.try_write(data) {
+            Ok(0) => return Err(io::Error::from(io::ErrorKind::WriteZero)),
+            Ok(n) => data = &data[n..],
+            Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => {
+                poll_fn(|cx| driver.poll_writable(cx)).await?
+            }
+            Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(())
+}
+
+/// An async connection to the X server. See the [module](self) documentation.
+pub struct Connection<D> {
+    driver: D,
+    /// Sequence numbers, buffered messages, replies and events.
+    state: ProtocolState,
+}
+
+/// A [Connection] on the tokio runtime.
+#[cfg(feature = "tokio")]
+pub type TokioConnection = Connection<TokioDriver>;
+
+/// A [Connection] on the async-std runtime.
+#[cfg(feature = "async-std")]
+pub type AsyncStdConnection = Connection<AsyncStdDriver>;
+
+/// A handle to the reply of a request sent through an async [Connection].
+#[derive(Debug)]
+#[must_use = "the reply should be retrieved, otherwise it is kept in memory"]
+pub struct Cookie<R> {
+    /// Sequence number of the request.
+    pub sequence: u16,
+    reply: PhantomData<R>,
+}
+
+impl<R: Reply> Cookie<R> {
+    pub fn new(sequence: u16) -> Self {
+        Self {
+            sequence,
+            reply: PhantomData,
+        }
+    }
+
+    /// Waits until the reply arrives and parses it.
+    pub async fn reply<D: AsyncIoDriver>(
+        self,
+        conn: &mut Connection<D>,
+    ) -> Result<R, ConnectionError> {
+        let bytes = conn.wait_for_reply(self.sequence).await?;
+        parse_reply(&bytes, conn.parse_mode())
+    }
+
+    /// Waits until the reply arrives and parses it, or until `timeout` elapsed, in
+    /// which case `None` is returned. The reply is then discarded whenever it
+    /// arrives, so the connection stays usable.
+    pub async fn reply_timeout<D: AsyncIoDriver>(
+        self,
+        conn: &mut Connection<D>,
+        timeout: Duration,
+    ) -> Result<Option<R>, ConnectionError> {
+        let response = {
+            let mut reply = pin!(conn.wait_for_reply(self.sequence));
+            let mut sleep = pin!(D::sleep(timeout));
+            poll_fn(|cx| match reply.as_mut().poll(cx) {
+                Poll::Ready(response) => Poll::Ready(Some(response)),
+                Poll::Pending => sleep.as_mut().poll(cx).map(|()| None),
+            })
+            .await
+        };
+        match response {
+            Some(bytes) => parse_reply(&bytes?, conn.parse_mode()).map(Some),
+            None => {
+                conn.state.abandon(self.sequence);
+                Ok(None)
+            }
+        }
+    }
+}
+
+impl<D: AsyncIoDriver> Connection<D> {
+    /// Connects to the display named by `DISPLAY`, through its unix socket or, if
+    /// that fails, over TCP.
+    pub async fn init() -> Result<Self, ConnectionError> {
+        let display_name = env::var("DISPLAY").map_err(|_| ConnectionError::DisplayNotSet)?;
+        let conf = parse_conf(display_name);
+        let mut attempts = Vec::new();
+        let driver = match D::connect_unix(&conf.socket_path).await {
+            Ok(driver) => driver,
+            Err(error) => {
+                attempts.push(ConnectAttempt {
+                    address: conf.socket_path.clone(),
+                    error,
+                });
+                match D::connect_tcp(&conf.host, conf.port).await {
+                    Ok(driver) => driver,
+                    Err(error) => {
+                        attempts.push(ConnectAttempt {
+                            address: format!("{}:{}", conf.host, conf.port),
+                            error,
+                        });
+                        return Err(ConnectionError::Connect {
+                            display: conf.display_name,
+                            attempts,
+                        });
+                    }
+                }
+            }
+        };
+        Self::with_driver(driver, conf.display_number, conf.screen_number).await
+    }
+
+    /// Sets up the connection over a connected driver.
+    pub(crate) async fn with_driver(
+        driver: D,
+        display_number: u8,
+        screen_number: usize,
+    ) -> Result<Self, ConnectionError> {
+        write_all(
+            &driver,
+            &ConnSetupRequest::from_xauthority(display_number).serialize()?,
+        )
+        .await?;
+        let mut buff = vec![0u8; 8];
+        read_exact(&driver, &mut buff).await?;
+        buff.resize(ConnSetup::length(&buff)?, 0);
+        read_exact(&driver, &mut buff[8..]).await?;
+        let setup = ConnSetup::parse_into(&buff)?;
+
+        Ok(Connection {
+            driver,
+            state: ProtocolState::new(setup, screen_number),
+        })
+    }
+
+    /// Returns the driver doing the I/O of the connection.
+    pub fn driver(&self) -> &D {
+        &self.driver
+    }
+
+    /// Returns the server's response to the connection setup.
+    pub fn setup(&self) -> &ConnSetup {
+        self.state.setup()
+    }
+
+    /// Returns the screen given in the display name, or the first screen if the
+    /// server has no such screen.
+    pub fn default_screen(&self) -> &Screen {
+        self.state.default_screen()
+    }
+
+    /// Returns the root window of the default screen.
+    pub fn default_root(&self) -> Window {
+        self.state.default_root()
+    }
+
+    /// Allocates a new id for a resource such as a window or a pixmap.
+    pub fn generate_id(&mut self) -> Result<XId, ConnectionError> {
+        self.state.generate_id()
+    }
+
+    /// Sets how strictly replies are parsed.
+    pub fn set_parse_mode(&mut self, mode: ParseMode) {
+        self.state.set_parse_mode(mode);
+    }
+
+    /// Returns how strictly replies are parsed.
+    pub fn parse_mode(&self) -> ParseMode {
+        self.state.parse_mode()
+    }
+
+    /// Sets the function called with the errors of unchecked requests. By default
+    /// these errors are logged as warnings through the `log` crate.
+    pub fn set_error_handler(&mut self, handler: Box<dyn Fn(XError) + Send>) {
+        self.state.set_error_handler(handler);
+    }
+
+    /// Sends a serialized request to the server and returns its sequence number.
+    ///
+    /// The request is unchecked: an error caused by it is passed to the error
+    /// handler.
+    pub async fn send_request(&mut self, request: &[u8]) -> Result<u16, ConnectionError> {
+        self.send(request, false).await
+    }
+
+    /// Sends a serialized request that has a reply and returns the cookie of the reply.
+    /// An error caused by the request is returned by [Cookie::reply].
+    pub async fn send_request_with_reply<R: Reply>(
+        &mut self,
+        request: &[u8],
+    ) -> Result<Cookie<R>, ConnectionError> {
+        Ok(Cookie::new(self.send(request, true).await?))
+    }
+
+    async fn send(&mut self, request: &[u8], checked: bool) -> Result<u16, ConnectionError> {
+        if self.state.is_disconnected() {
+            return Err(ConnectionError::Disconnected);
+        }
+        self.state.check_request_length(request)?;
+        if let Err(err) = write_all(&self.driver, request).await {
+            return Err(self.state.connection_lost(err));
+        }
+        Ok(self.state.request_sent(request, checked))
+    }
+
+    /// Waits until the server processed every request sent so far and returns the
+    /// errors caused by unchecked requests that were not read from the server yet.
+    pub async fn sync(&mut self) -> Result<Vec<XError>, ConnectionError> {
+        let cookie: Cookie<InputFocusReply> = self
+            .send_request_with_reply(&GetInputFocus.serialize())
+            .await?;
+        self.state.begin_sync();
+        let result = cookie.reply(self).await;
+        let errors = self.state.end_sync();
+        result.map(|_| errors)
+    }
+
+    /// Returns the codes of the extension `name`, or `None` if the server does not
+    /// support it. The server is only asked the first time.
+    pub async fn extension(
+        &mut self,
+        name: &'static str,
+    ) -> Result<Option<ExtensionInfo>, ConnectionError> {
+        if let Some(info) = self.state.extension(name) {
+            return Ok(info);
+        }
+        let request = QueryExtension {
+            name: name.as_bytes(),
+        };
+        let reply = self
+            .send_request_with_reply::<QueryExtensionReply>(&request.serialize())
+            .await?
+            .reply(self)
+            .await?;
+        Ok(self.state.extension_queried(name, &reply))
+    }
+
+    /// Waits for the reply to the request with the given sequence number and returns
+    /// its bytes.
+    pub async fn wait_for_reply(&mut self, sequence: u16) -> Result<Vec<u8>, ConnectionError> {
+        loop {
+            if let Some(response) = self.state.take_response(sequence) {
+                return response;
+            }
+            self.read_messages().await?;
+        }
+    }
+
+    /// Returns the next event, waiting until one arrives.
+    pub async fn wait_for_event(&mut self) -> Result<Event, ConnectionError> {
+        loop {
+            if let Some(event) = self.state.take_event() {
+                return Ok(event);
+            }
+            if let Err(err) = self.read_messages().await {
+                // The events read before the connection closed are still delivered.
+                return self.state.take_event().ok_or(err);
+            }
+        }
+    }
+
+    /// Returns the next event if one is available, without waiting.
+    pub fn poll_for_event(&mut self) -> Result<Option<Event>, ConnectionError> {
+        if let Some(event) = self.state.take_event() {
+            return Ok(Some(event));
+        }
+        if let Err(err) = self.read_available() {
+            return self.state.take_event().map(Some).ok_or(err);
+        }
+        Ok(self.state.take_event())
+    }
+
+    /// Returns the next event if one is available, or else registers `cx` to be
+    /// woken when the server sent more.
+    pub fn poll_event(&mut self, cx: &mut Context<'_>) -> Poll<Result<Event, ConnectionError>> {
+        loop {
+            match self.poll_for_event() {
+                Ok(Some(event)) => return Poll::Ready(Ok(event)),
+                Ok(None) => {}
+                Err(err) => return Poll::Ready(Err(err)),
+            }
+            match self.driver.poll_readable(cx) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(Ok(())) => {}
+                Poll::Ready(Err(err)) => return Poll::Ready(Err(self.state.connection_lost(err))),
+            }
+        }
+    }
+
+    /// Returns the stream of the events of the connection, which ends once the
+    /// connection is closed, after [Event::ConnectionClosed].
+    ///
+    /// With the `tokio` or `async-std` feature it is a `futures_core::Stream`, so
+    /// it can be used with `select!` and stream combinators.
+    pub fn events(&mut self) -> EventStream<'_, D> {
+        EventStream { conn: self }
+    }
+
+    /// Waits until at least one complete message was read.
+    async fn read_messages(&mut self) -> Result<(), ConnectionError> {
+        if self.state.is_disconnected() {
+            return Err(ConnectionError::Disconnected);
+        }
+        let mut buf = [0u8; 4096];
+        loop {
+            let err = match read(&self.driver, &mut buf).await {
+                Ok(0) => io::Error::from(io::ErrorKind::UnexpectedEof),
+                Ok(n) => {
+                    if self.state.bytes_received(&buf[..n])? {
+                        return Ok(());
+                    }
+                    continue;
+                }
+                Err(err) => err,
+            };
+            return Err(self.state.connection_lost(err));
+        }
+    }
+
+    /// Reads what is available without waiting.
+    fn read_available(&mut self) -> Result<(), ConnectionError> {
+        if self.state.is_disconnected() {
+            return Err(ConnectionError::Disconnected);
+        }
+        let mut buf = [0u8; 4096];
+        loop {
+            match self.driver.try_read(&mut buf) {
+                Ok(0) => {
+                    let err = io::Error::from(io::ErrorKind::UnexpectedEof);
+                    return Err(self.state.connection_lost(err));
+                }
+                Ok(n) => {
+                    self.state.bytes_received(&buf[..n])?;
+                }
+                Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => return Ok(()),
+                Err(e) => return Err(self.state.connection_lost(e)),
+            }
+        }
+    }
+}
+
+/// The events of an async [Connection]. See [Connection::events].
+pub struct EventStream<'a, D> {
+    conn: &'a mut Connection<D>,
+}
+
+impl<D: AsyncIoDriver> EventStream<'_, D> {
+    /// Waits for the next event. Returns `None` once the connection is closed,
+    /// after [Event::ConnectionClosed] was returned.
+    pub async fn next(&mut self) -> Option<Result<Event, ConnectionError>> {
+        poll_fn(|cx| self.poll_next_event(cx)).await
+    }
+
+    fn poll_next_event(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Event, ConnectionError>>> {
+        self.conn.poll_event(cx).map(|result| match result {
+            Err(ConnectionError::Disconnected) => None,
+            result => Some(result),
+        })
+    }
+}
+
+#[cfg(any(feature = "tokio", feature = "async-std"))]
+impl<D: AsyncIoDriver> futures_core::Stream for EventStream<'_, D> {
+    type Item = Result<Event, ConnectionError>;
+
+    fn poll_next(self: std::pin::Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().poll_next_event(cx)
+    }
+}
+
+/// The socket of a [TokioConnection].
+#[cfg(feature = "tokio")]
+pub enum TokioDriver {
+    Tcp(tokio::net::TcpStream),
+    Unix(tokio::net::UnixStream),
+}
+
+#[cfg(feature = "tokio")]
+impl AsyncIoDriver for TokioDriver {
+    async fn connect_unix(path: &str) -> io::Result<Self> {
+        tokio::net::UnixStream::connect(path)
+            .await
+            .map(TokioDriver::Unix)
+    }
+
+    async fn connect_tcp(host: &str, port: u16) -> io::Result<Self> {
+        tokio::net::TcpStream::connect((host, port))
+            .await
+            .map(TokioDriver::Tcp)
+    }
+
+    fn poll_readable(&self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self {
+            TokioDriver::Tcp(stream) => stream.poll_read_ready(cx),
+            TokioDriver::Unix(stream) => stream.poll_read_ready(cx),
+        }
+    }
+
+    fn poll_writable(&self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self {
+            TokioDriver::Tcp(stream) => stream.poll_write_ready(cx),
+            TokioDriver::Unix(stream) => stream.poll_write_ready(cx),
+        }
+    }
+
+    fn try_read(&self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            TokioDriver::Tcp(stream) => stream.try_read(buf),
+            TokioDriver::Unix(stream) => stream.try_read(buf),
+        }
+    }
+
+    fn try_write(&self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            TokioDriver::Tcp(stream) => stream.try_write(buf),
+            TokioDriver::Unix(stream) => stream.try_write(buf),
+        }
+    }
+
+    async fn sleep(duration: Duration) {
+        tokio::time::sleep(duration).await;
+    }
+}
+
+/// The socket of an [AsyncStdConnection].
+///
+/// async-std runs on the reactor of async-io, with which this socket is registered,
+/// so it also works on smol.
+#[cfg(feature = "async-std")]
+pub enum AsyncStdDriver {
+    Tcp(async_io::Async<std::net::TcpStream>),
+    Unix(async_io::Async<std::os::unix::net::UnixStream>),
+}
+
+#[cfg(feature = "async-std")]
+impl AsyncIoDriver for AsyncStdDriver {
+    async fn connect_unix(path: &str) -> io::Result<Self> {
+        async_io::Async::<std::os::unix::net::UnixStream>::connect(path)
+            .await
+            .map(AsyncStdDriver::Unix)
+    }
+
+    async fn connect_tcp(host: &str, port: u16) -> io::Result<Self> {
+        // The display's host is usually an address, so resolving it rarely blocks.
+        let mut last_error = io::Error::from(io::ErrorKind::AddrNotAvailable);
+        for address in std::net::ToSocketAddrs::to_socket_addrs(&(host, port))? {
+            match async_io::Async::<std::net::TcpStream>::connect(address).await {
+                Ok(stream) => return Ok(AsyncStdDriver::Tcp(stream)),
+                Err(err) => last_error = err,
+            }
+        }
+        Err(last_error)
+    }
+
+    fn poll_readable(&self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self {
+            AsyncStdDriver::Tcp(stream) => stream.poll_readable(cx),
+            AsyncStdDriver::Unix(stream) => stream.poll_readable(cx),
+        }
+    }
+
+    fn poll_writable(&self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self {
+            AsyncStdDriver::Tcp(stream) => stream.poll_writable(cx),
+            AsyncStdDriver::Unix(stream) => stream.poll_writable(cx),
+        }
+    }
+
+    fn try_read(&self, buf: &mut [u8]) -> io::Result<usize> {
+        use std::io::Read;
+        match self {
+            AsyncStdDriver::Tcp(stream) => stream.get_ref().read(buf),
+            AsyncStdDriver::Unix(stream) => stream.get_ref().read(buf),
+        }
+    }
+
+    fn try_write(&self, buf: &[u8]) -> io::Result<usize> {
+        use std::io::Write;
+        match self {
+            AsyncStdDriver::Tcp(stream) => stream.get_ref().write(buf),
+            AsyncStdDriver::Unix(stream) => stream.get_ref().write(buf),
+        }
+    }
+
+    async fn sleep(duration: Duration) {
+        async_io::Timer::after(duration).await;
+    }
+}
@@ -0,0 +1,238 @@
+//! A cache of atoms by name, and of names by atom.
+//!
+//! Every atom a tool uses costs a round trip to intern, and desktop tools use
+//! dozens. [AtomCache::preload] interns a whole list in one go, sending every
+//! InternAtom request before waiting on any reply, so the list costs a single round
+//! trip. [CommonAtoms::ALL] lists the atoms of EWMH, ICCCM and the clipboard.
+//!
+//! ```no_run
+//! use mousetrap::atom_cache::{AtomCache, CommonAtoms};
+//! use mousetrap::connection::Connection;
+//!
+//! let mut conn = Connection::init().unwrap();
+//! let mut atoms = AtomCache::preload(&mut conn, CommonAtoms::ALL).unwrap();
+//! println!("{:?}", atoms.get("_NET_ACTIVE_WINDOW"));
+//! // Atoms that were not preloaded are interned on first use.
+//! println!("{:?}", atoms.intern(&mut conn, "_MY_PROPERTY").unwrap());
+//! ```
+
+use crate::connection::Connection;
+use crate::errors::ConnectionError;
+use crate::protocol::Atom;
+use std::collections::HashMap;
+
+/// Lists of frequently used atoms, to pass to [AtomCache::preload].
+pub struct CommonAtoms;
+
+impl CommonAtoms {
+    /// The atoms of the EWMH root window properties and messages, application
+    /// window properties, window types, states and protocols.
+    pub const EWMH: &'static [&'static str] = &[
+        "_NET_SUPPORTED",
+        "_NET_CLIENT_LIST",
+        "_NET_CLIENT_LIST_STACKING",
+        "_NET_NUMBER_OF_DESKTOPS",
+        "_NET_DESKTOP_GEOMETRY",
+        "_NET_DESKTOP_VIEWPORT",
+        "_NET_CURRENT_DESKTOP",
+        "_NET_DESKTOP_NAMES",
+        "_NET_ACTIVE_WINDOW",
+        "_NET_WORKAREA",
+        "_NET_SUPPORTING_WM_CHECK",
+        "_NET_VIRTUAL_ROOTS",
+        "_NET_DESKTOP_LAYOUT",
+        "_NET_SHOWING_DESKTOP",
+        "_NET_CLOSE_WINDOW",
+        "_NET_MOVERESIZE_WINDOW",
+        "_NET_WM_MOVERESIZE",
+        "_NET_RESTACK_WINDOW",
+        "_NET_REQUEST_FRAME_EXTENTS",
+        "_NET_WM_NAME",
+        "_NET_WM_VISIBLE_NAME",
+        "_NET_WM_ICON_NAME",
+        "_NET_WM_VISIBLE_ICON_NAME",
+        "_NET_WM_DESKTOP",
+        "_NET_WM_WINDOW_TYPE",
+        "_NET_WM_STATE",
+        "_NET_WM_ALLOWED_ACTIONS",
+        "_NET_WM_STRUT",
+        "_NET_WM_STRUT_PARTIAL",
+        "_NET_WM_ICON_GEOMETRY",
+        "_NET_WM_ICON",
+        "_NET_WM_PID",
+        "_NET_WM_USER_TIME",
+        "_NET_WM_USER_TIME_WINDOW",
+        "_NET_FRAME_EXTENTS",
+        "_NET_WM_OPAQUE_REGION",
+        "_NET_WM_BYPASS_COMPOSITOR",
+        "_NET_WM_WINDOW_OPACITY",
+        "_NET_WM_WINDOW_TYPE_DESKTOP",
+        "_NET_WM_WINDOW_TYPE_DOCK",
+        "_NET_WM_WINDOW_TYPE_TOOLBAR",
+        "_NET_WM_WINDOW_TYPE_MENU",
+        "_NET_WM_WINDOW_TYPE_UTILITY",
+        "_NET_WM_WINDOW_TYPE_SPLASH",
+        "_NET_WM_WINDOW_TYPE_DIALOG",
+        "_NET_WM_WINDOW_TYPE_DROPDOWN_MENU",
+        "_NET_WM_WINDOW_TYPE_POPUP_MENU",
+        "_NET_WM_WINDOW_TYPE_TOOLTIP",
+        "_NET_WM_WINDOW_TYPE_NOTIFICATION",
+        "_NET_WM_WINDOW_TYPE_COMBO",
+        "_NET_WM_WINDOW_TYPE_DND",
+        "_NET_WM_WINDOW_TYPE_NORMAL",
+        "_NET_WM_STATE_MODAL",
+        "_NET_WM_STATE_STICKY",
+        "_NET_WM_STATE_MAXIMIZED_VERT",
+        "_NET_WM_STATE_MAXIMIZED_HORZ",
+        "_NET_WM_STATE_SHADED",
+        "_NET_WM_STATE_SKIP_TASKBAR",
+        "_NET_WM_STATE_SKIP_PAGER",
+        "_NET_WM_STATE_HIDDEN",
+        "_NET_WM_STATE_FULLSCREEN",
+        "_NET_WM_STATE_ABOVE",
+        "_NET_WM_STATE_BELOW",
+        "_NET_WM_STATE_DEMANDS_ATTENTION",
+        "_NET_WM_STATE_FOCUSED",
+        "_NET_WM_PING",
+        "_NET_WM_SYNC_REQUEST",
+        "_NET_WM_SYNC_REQUEST_COUNTER",
+        "_NET_WM_FULLSCREEN_MONITORS",
+    ];
+
+    /// The ICCCM atoms that are not predefined by the core protocol.
+    pub const ICCCM: &'static [&'static str] = &[
+        "WM_PROTOCOLS",
+        "WM_DELETE_WINDOW",
+        "WM_TAKE_FOCUS",
+        "WM_STATE",
+        "WM_CHANGE_STATE",
+        "WM_CLIENT_LEADER",
+        "WM_WINDOW_ROLE",
+        "WM_COLORMAP_WINDOWS",
+        "SM_CLIENT_ID",
+    ];
+
+    /// The selections, targets and properties of the clipboard.
+    pub const CLIPBOARD: &'static [&'static str] = &[
+        "CLIPBOARD",
+        "CLIPBOARD_MANAGER",
+        "TARGETS",
+        "MULTIPLE",
+        "TIMESTAMP",
+        "INCR",
+        "SAVE_TARGETS",
+        "UTF8_STRING",
+        "TEXT",
+        "COMPOUND_TEXT",
+        "text/plain",
+        "text/plain;charset=utf-8",
+        "text/uri-list",
+        "text/html",
+        "image/png",
+    ];
+
+    /// All of the above.
+    pub const ALL: &'static [&'static [&'static str]] = &[Self::EWMH, Self::ICCCM, Self::CLIPBOARD];
+}
+
+/// Lists of atom names [AtomCache::preload] accepts: a list, or a list of lists
+/// such as [CommonAtoms::ALL].
+pub trait AtomNames {
+    /// Calls `f` with every name.
+    fn for_each_name(&self, f: &mut dyn FnMut(&str));
+}
+
+impl AtomNames for [&str] {
+    fn for_each_name(&self, f: &mut dyn FnMut(&str)) {
+        self.iter().for_each(|name| f(name));
+    }
+}
+
+impl<const N: usize> AtomNames for [&str; N] {
+    fn for_each_name(&self, f: &mut dyn FnMut(&str)) {
+        self[..].for_each_name(f);
+    }
+}
+
+impl AtomNames for [&[&str]] {
+    fn for_each_name(&self, f: &mut dyn FnMut(&str)) {
+        self.iter().for_each(|names| names.for_each_name(f));
+    }
+}
+
+/// Atoms interned so far, by name, and their names.
+#[derive(Debug, Clone, Default)]
+pub struct AtomCache {
+    atoms: HashMap<String, Atom>,
+    names: HashMap<Atom, String>,
+}
+
+impl AtomCache {
+    /// Creates an empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a cache holding the atoms of `names`, interned in a single round trip.
+    pub fn preload<N: AtomNames + ?Sized>(
+        conn: &mut Connection,
+        names: &N,
+    ) -> Result<Self, ConnectionError> {
+        let mut cache = Self::new();
+        cache.intern_all(conn, names)?;
+        Ok(cache)
+    }
+
+    /// Interns the atoms of `names` that are not cached yet, sending every request
+    /// before waiting on any reply.
+    pub fn intern_all<N: AtomNames + ?Sized>(
+        &mut self,
+        conn: &mut Connection,
+        names: &N,
+    ) -> Result<(), ConnectionError> {
+        let mut missing = Vec::new();
+        names.for_each_name(&mut |name| {
+            if !self.atoms.contains_key(name) {
+                missing.push(name.to_owned());
+            }
+        });
+        let mut cookies = Vec::with_capacity(missing.len());
+        for name in &missing {
+            cookies.push(conn.intern_atom(false, name)?);
+        }
+        for (name, cookie) in missing.into_iter().zip(cookies) {
+            let atom = cookie.reply(conn)?.atom;
+            self.insert(name, atom);
+        }
+        Ok(())
+    }
+
+    /// Returns the atom of `name` if it is cached.
+    pub fn get(&self, name: &str) -> Option<Atom> {
+        self.atoms.get(name).copied()
+    }
+
+    /// Returns the atom of `name`, interning it if it is not cached.
+    pub fn intern(&mut self, conn: &mut Connection, name: &str) -> Result<Atom, ConnectionError> {
+        if let Some(atom) = self.get(name) {
+            return Ok(atom);
+        }
+        let atom = conn.intern_atom(false, name)?.reply(conn)?.atom;
+        self.insert(name.to_owned(), atom);
+        Ok(atom)
+    }
+
+    /// Returns the name of `atom`, asking the server if it is not cached.
+    pub fn name(&mut self, conn: &mut Connection, atom: Atom) -> Result<&str, ConnectionError> {
+        if !self.names.contains_key(&atom) {
+            let reply = conn.get_atom_name(atom)?.reply(conn)?;
+            self.insert(reply.name.as_str_lossy().into_owned(), atom);
+        }
+        Ok(&self.names[&atom])
+    }
+
+    fn insert(&mut self, name: String, atom: Atom) {
+        self.names.insert(atom, name.clone());
+        self.atoms.insert(name, atom);
+    }
+}
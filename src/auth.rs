@@ -1,12 +1,31 @@
 use super::protocol::Family;
 use std::env::var_os;
+use std::ffi::OsString;
 use std::fs::File;
-use std::io::{self, Read};
+use std::io::{self, Read, Write};
+use std::net::{IpAddr, ToSocketAddrs};
 use std::path::PathBuf;
 
+/// The values of [XAuthEntry::family].
+pub mod family {
+    use crate::protocol::Family;
+
+    /// The address is an IPv4 address.
+    pub const INTERNET: Family = 0;
+    pub const DECNET: Family = 1;
+    pub const CHAOS: Family = 2;
+    pub const SERVER_INTERPRETED: Family = 5;
+    /// The address is an IPv6 address.
+    pub const INTERNET6: Family = 6;
+    /// The address is the hostname of the local machine.
+    pub const LOCAL: Family = 256;
+    /// The entry applies to any address.
+    pub const WILD: Family = 65535;
+}
+
 /// XAuthEntry represents an entry in the .Xauthority file.
 /// It is a binary file consisting of a sequence of entries.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct XAuthEntry {
     /// The protocol family.
     pub family: Family,
@@ -24,7 +43,12 @@ impl XAuthEntry {
     pub fn parse() -> io::Result<Vec<XAuthEntry>> {
         // Open .Xauthority file
         let xauth_file = open()?;
-        let mut reader = io::BufReader::new(xauth_file);
+        Ok(Self::parse_from(io::BufReader::new(xauth_file)))
+    }
+
+    /// Parses the entries of a .Xauthority file from `reader`, up to the first
+    /// truncated entry.
+    pub fn parse_from<R: Read>(mut reader: R) -> Vec<XAuthEntry> {
         let mut xauth_entries = Vec::new();
 
         /// Reads 2 bytes from the provided reader and returns them as a u16 value.
@@ -82,38 +106,270 @@ impl XAuthEntry {
         while let Ok(record) = read_xauth_entry(&mut reader) {
             xauth_entries.push(record);
         }
-        Ok(xauth_entries)
+        xauth_entries
+    }
+
+    /// Appends the entry to a .Xauthority file, in the format read by [XAuthEntry::parse].
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        fn write_bytes<W: Write>(writer: &mut W, bytes: &[u8]) -> io::Result<()> {
+            let len = u16::try_from(bytes.len())
+                .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "Field too long"))?;
+            writer.write_all(&len.to_be_bytes())?;
+            writer.write_all(bytes)
+        }
+
+        writer.write_all(&self.family.to_be_bytes())?;
+        write_bytes(writer, &self.address)?;
+        write_bytes(writer, &self.display_number)?;
+        write_bytes(writer, &self.authorization_protocol_name)?;
+        write_bytes(writer, &self.authorization_protocol_data)
+    }
+
+    /// Whether the entry applies to a display whose host is named `address` in
+    /// the given family, see [host_address]: its display number is the same or
+    /// empty, and it is for that address or any address.
+    pub fn matches(&self, family: Family, address: &[u8], display_number: u8) -> bool {
+        let display_matches = self.display_number.is_empty()
+            || self.display_number == display_number.to_string().as_bytes();
+        let address_matches =
+            self.family == family::WILD || (self.family == family && self.address == address);
+        display_matches && address_matches
     }
 }
 
-/// Get XAUTHORITY file path
+/// Returns the index of the entry used to connect to the display `display_number`
+/// of `host`, the part of the display name before the colon: the first one that
+/// [XAuthEntry::matches] it, as Xlib does.
+pub fn find_entry(entries: &[XAuthEntry], host: &str, display_number: u8) -> Option<usize> {
+    let (family, address) = host_address(host);
+    entries
+        .iter()
+        .position(|entry| entry.matches(family, &address, display_number))
+}
+
+/// Returns the family and the address naming `host` in the entries.
+///
+/// A local display, with no host, `unix` or a loopback address, is named by the
+/// hostname of the local machine in the [family::LOCAL] family, as is `host` if
+/// it is that hostname. An IPv4 address is named in the [family::INTERNET]
+/// family and an IPv6 one, possibly in brackets, in [family::INTERNET6]. Any
+/// other host is resolved to its first address.
+pub fn host_address(host: &str) -> (Family, Vec<u8>) {
+    let hostname = hostname().unwrap_or_default();
+    if host.is_empty() || host == "unix" || host.as_bytes() == hostname {
+        return (family::LOCAL, hostname);
+    }
+    let host = host
+        .strip_prefix('[')
+        .and_then(|host| host.strip_suffix(']'))
+        .unwrap_or(host);
+    let ip = match host.parse::<IpAddr>() {
+        Ok(ip) => Some(ip),
+        Err(_) => (host, 0)
+            .to_socket_addrs()
+            .ok()
+            .and_then(|mut addresses| addresses.next())
+            .map(|address| address.ip()),
+    };
+    match ip.map(|ip| ip.to_canonical()) {
+        Some(ip) if ip.is_loopback() => (family::LOCAL, hostname),
+        Some(IpAddr::V4(ip)) => (family::INTERNET, ip.octets().to_vec()),
+        Some(IpAddr::V6(ip)) => (family::INTERNET6, ip.octets().to_vec()),
+        // Named by no entry but the wild ones.
+        None => (family::WILD, Vec::new()),
+    }
+}
+
+/// Returns the hostname of the local machine, which names it in entries of the
+/// [family::LOCAL] family.
+pub fn hostname() -> Option<Vec<u8>> {
+    let mut buf = [0u8; 256];
+    // SAFETY: the buffer is valid for its length.
+    if unsafe { libc::gethostname(buf.as_mut_ptr().cast(), buf.len()) } != 0 {
+        return None;
+    }
+    let len = buf.iter().position(|&b| b == 0).unwrap_or(buf.len());
+    Some(buf[..len].to_vec())
+}
+
+/// Returns the path of the .Xauthority file: XAUTHORITY, or else
+/// `.Xauthority` in HOME, as Xlib does.
 pub fn get_xauth_filename() -> Option<PathBuf> {
-    // TODO: check in home directory
-    match var_os("XAUTHORITY") {
-        Some(p) => Some(p.into()),
-        None => None,
+    xauth_filename(var_os("XAUTHORITY"), var_os("HOME"))
+}
+
+fn xauth_filename(xauthority: Option<OsString>, home: Option<OsString>) -> Option<PathBuf> {
+    match xauthority {
+        Some(path) if !path.is_empty() => Some(path.into()),
+        _ => home
+            .filter(|home| !home.is_empty())
+            .map(|home| PathBuf::from(home).join(".Xauthority")),
     }
 }
 
 /// Open Xauthority file
 pub fn open() -> std::io::Result<File> {
     if let Some(path) = get_xauth_filename() {
-        match File::open(path) {
+        match File::open(&path) {
             Ok(f) => Ok(f),
             Err(e) => {
-                println!("File open err: {}", e);
-                Err(std::io::Error::new(
-                    std::io::ErrorKind::Other,
-                    "Failed opening file",
-                ))
+                log::debug!("cannot open {}: {}", path.display(), e);
+                Err(std::io::Error::other("Failed opening file"))
             }
         }
     } else {
         // if !Path::new(&path).exists() {
-        Err(std::io::Error::new(
-            std::io::ErrorKind::Other,
-            "Failed to get XAUTHORITY environment variable: The variable may not be set",
+        Err(std::io::Error::other(
+            "Failed to find the .Xauthority file: neither XAUTHORITY nor HOME is set",
         ))
         // }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(family: Family, address: &[u8], display_number: &str) -> XAuthEntry {
+        XAuthEntry {
+            family,
+            address: address.to_vec(),
+            display_number: display_number.as_bytes().to_vec(),
+            authorization_protocol_name: b"MIT-MAGIC-COOKIE-1".to_vec(),
+            authorization_protocol_data: vec![0xc0; 16],
+        }
+    }
+
+    #[test]
+    fn parse_from_reads_entries_up_to_a_truncated_one() {
+        #[rustfmt::skip]
+        let mut bytes = vec![
+            0x01, 0x00, // family: local
+            0x00, 0x04, b'h', b'o', b's', b't',
+            0x00, 0x01, b'0',
+            0x00, 0x03, b'M', b'I', b'T',
+            0x00, 0x02, 0xab, 0xcd,
+        ];
+        entry(family::WILD, b"", "1").write_to(&mut bytes).unwrap();
+        // A third entry, cut in its address.
+        bytes.extend_from_slice(&[0x00, 0x00, 0x00, 0x04, 127]);
+
+        let entries = XAuthEntry::parse_from(bytes.as_slice());
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].family, family::LOCAL);
+        assert_eq!(entries[0].address, b"host");
+        assert_eq!(entries[0].display_number, b"0");
+        assert_eq!(entries[0].authorization_protocol_name, b"MIT");
+        assert_eq!(entries[0].authorization_protocol_data, [0xab, 0xcd]);
+        assert_eq!(entries[1].family, family::WILD);
+        assert_eq!(entries[1].display_number, b"1");
+        assert_eq!(entries[1].authorization_protocol_data, [0xc0; 16]);
+    }
+
+    #[test]
+    fn xauth_filename_falls_back_to_home() {
+        let home = Some(OsString::from("/home/user"));
+        assert_eq!(
+            xauth_filename(Some("/tmp/xauth".into()), home.clone()),
+            Some(PathBuf::from("/tmp/xauth"))
+        );
+        assert_eq!(
+            xauth_filename(None, home.clone()),
+            Some(PathBuf::from("/home/user/.Xauthority"))
+        );
+        assert_eq!(
+            xauth_filename(Some(OsString::new()), home),
+            Some(PathBuf::from("/home/user/.Xauthority"))
+        );
+        assert_eq!(xauth_filename(None, Some(OsString::new())), None);
+        assert_eq!(xauth_filename(None, None), None);
+    }
+
+    #[test]
+    fn write_to_rejects_long_fields() {
+        let mut long = entry(family::LOCAL, b"host", "0");
+        long.authorization_protocol_data = vec![0; 0x1_0000];
+        let error = long.write_to(&mut Vec::new()).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn matches_the_display_and_the_address() {
+        let local = |entry: XAuthEntry, display_number| {
+            entry.matches(family::LOCAL, b"host", display_number)
+        };
+        assert!(local(entry(family::LOCAL, b"host", "0"), 0));
+        assert!(local(entry(family::LOCAL, b"host", ""), 7));
+        assert!(local(entry(family::WILD, b"", "10"), 10));
+        assert!(!local(entry(family::LOCAL, b"host", "0"), 1));
+        assert!(!local(entry(family::LOCAL, b"host", "1"), 10));
+        assert!(!local(entry(family::LOCAL, b"other", "0"), 0));
+        assert!(!local(entry(family::INTERNET, &[127, 0, 0, 1], "0"), 0));
+
+        let remote = entry(family::INTERNET, &[192, 168, 1, 7], "10");
+        assert!(remote.matches(family::INTERNET, &[192, 168, 1, 7], 10));
+        assert!(!remote.matches(family::INTERNET, &[192, 168, 1, 8], 10));
+        assert!(!remote.matches(family::LOCAL, b"host", 10));
+    }
+
+    #[test]
+    fn host_addresses() {
+        let hostname = hostname().unwrap();
+        for host in ["", "unix", "127.0.0.1", "[::1]", "::ffff:127.0.0.1"] {
+            assert_eq!(host_address(host), (family::LOCAL, hostname.clone()));
+        }
+        assert_eq!(
+            host_address(std::str::from_utf8(&hostname).unwrap()),
+            (family::LOCAL, hostname.clone())
+        );
+        assert_eq!(
+            host_address("192.168.1.7"),
+            (family::INTERNET, vec![192, 168, 1, 7])
+        );
+        assert_eq!(
+            host_address("::ffff:192.168.1.7"),
+            (family::INTERNET, vec![192, 168, 1, 7])
+        );
+        let mut fe80 = vec![0; 16];
+        fe80[..2].copy_from_slice(&[0xfe, 0x80]);
+        fe80[15] = 1;
+        assert_eq!(host_address("[fe80::1]"), (family::INTERNET6, fe80));
+    }
+
+    #[test]
+    fn find_entry_takes_the_first_match() {
+        let hostname = hostname().unwrap();
+        let entries = [
+            entry(family::INTERNET, &[127, 0, 0, 1], "0"),
+            entry(family::LOCAL, b"elsewhere", "0"),
+            entry(family::LOCAL, &hostname, "1"),
+            entry(family::WILD, b"", "0"),
+            entry(family::LOCAL, &hostname, "0"),
+        ];
+        assert_eq!(find_entry(&entries, "", 0), Some(3));
+        assert_eq!(find_entry(&entries, "", 1), Some(2));
+        assert_eq!(find_entry(&entries, "unix", 1), Some(2));
+        assert_eq!(find_entry(&entries, "", 2), None);
+        assert_eq!(find_entry(&[], "", 0), None);
+    }
+
+    #[test]
+    fn find_entry_matches_remote_displays() {
+        let hostname = hostname().unwrap();
+        let entries = [
+            entry(family::LOCAL, &hostname, "10"),
+            entry(family::INTERNET, &[192, 168, 1, 7], "10"),
+            entry(
+                family::INTERNET6,
+                &[0xfe, 0x80, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1],
+                "10",
+            ),
+            entry(family::WILD, b"", "11"),
+        ];
+        assert_eq!(find_entry(&entries, "192.168.1.7", 10), Some(1));
+        assert_eq!(find_entry(&entries, "[fe80::1]", 10), Some(2));
+        assert_eq!(find_entry(&entries, "127.0.0.1", 10), Some(0));
+        assert_eq!(find_entry(&entries, "192.168.1.8", 10), None);
+        assert_eq!(find_entry(&entries, "192.168.1.8", 11), Some(3));
+    }
+}
@@ -0,0 +1,67 @@
+//! The BIG-REQUESTS extension: requests longer than the 16-bit length field of
+//! the core protocol allows.
+//!
+//! Once enabled, a request whose length field is 0 carries its length in the 4
+//! bytes following the header, as [RequestBuilder::finish] writes requests of
+//! more than 65535 units. [Connection] enables the extension the first time such
+//! a request is sent, so it does not need to be enabled by hand.
+
+use crate::connection::Connection;
+use crate::cookie::{Cookie, Reply};
+use crate::errors::{ConnectionError, ParseError};
+use crate::request::RequestBuilder;
+use crate::utils::{check_trailing_bytes, deserialize_into, skip};
+
+/// Name of the extension, as given to QueryExtension.
+pub const NAME: &str = "BIG-REQUESTS";
+
+/// Minor opcodes of the BIG-REQUESTS requests.
+pub mod opcode {
+    pub const ENABLE: u8 = 0;
+}
+
+/// Names of the BIG-REQUESTS requests, indexed by minor opcode.
+pub const REQUEST_NAMES: &[&str] = &["Enable"];
+
+/// The extension defines no errors.
+pub const ERROR_NAMES: &[&str] = &[];
+
+/// Reply of the BigReqEnable request.
+#[derive(Debug, Clone)]
+pub struct EnableReply {
+    /// Length of the longest request the server accepts from now on, in 4-byte units.
+    pub maximum_request_length: u32,
+}
+
+impl Reply for EnableReply {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1                        unused
+        // 2  CARD16                sequence number
+        // 4  0                     reply length
+        // 4  CARD32                maximum-request-length
+        // 20                       unused
+        let rest = skip(bytes, 8)?;
+        let (maximum_request_length, _) = deserialize_into::<u32>(rest)?;
+        Ok(EnableReply {
+            maximum_request_length,
+        })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        check_trailing_bytes("bigreq::EnableReply", bytes, 32)
+    }
+}
+
+impl Connection {
+    /// Enables the long length of requests and returns the length of the longest
+    /// request the server accepts from now on.
+    pub fn bigreq_enable(&mut self) -> Result<Cookie<EnableReply>, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (0)
+        //   2 bytes    Request length (1)
+        let info = self.require_extension(NAME, REQUEST_NAMES, ERROR_NAMES)?;
+        let request = RequestBuilder::new(info.major_opcode, opcode::ENABLE).finish();
+        self.send_request_with_reply(&request)
+    }
+}
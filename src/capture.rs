@@ -0,0 +1,460 @@
+//! Capturing the screen, the way screenshot and screen recording tools do.
+//!
+//! A [Capturer] reads the pixels of the root window: through a MIT-SHM segment
+//! when the server is local, and with GetImage otherwise. The pixels of any
+//! TrueColor visual are converted to [Frame]s of 8-bit RGBA. The server does
+//! not draw the cursor into the root window, so the capturer can composite the
+//! image of the current cursor from XFixes on top.
+//!
+//! For screen sharing, a [FrameStream] follows the changes of the screen with
+//! DAMAGE and delivers only the areas that changed, with the whole screen as a
+//! keyframe at regular intervals.
+//!
+//! With the `png` feature, `Frame::write_png` saves a frame as a PNG file.
+//!
+//! ```no_run
+//! use mousetrap::capture::Capturer;
+//! use mousetrap::connection::Connection;
+//!
+//! let mut conn = Connection::init().unwrap();
+//! let mut capturer = Capturer::new(&mut conn, true).unwrap();
+//! let frame = capturer.capture_screen(&mut conn).unwrap();
+//! println!("{}x{} through SHM: {}", frame.width(), frame.height(), capturer.uses_shm());
+//! capturer.close(&mut conn).unwrap();
+//! ```
+
+use crate::connection::Connection;
+use crate::damage::{self, Damage, DamageNotifyEvent, ReportLevel};
+use crate::errors::ConnectionError;
+use crate::event::Event;
+use crate::protocol::{Rectangle, Window, XId};
+use crate::request::{GetImage, ImageFormat};
+use crate::shm::{self, Shm, ShmSegment};
+use crate::wallpaper::PixelLayout;
+use crate::xfixes::{self, XFixes};
+use std::time::{Duration, Instant};
+
+/// Beyond this many changed rectangles, a [FrameStream] reads their bounding
+/// box instead, which costs fewer requests.
+const MAX_DELTA_RECTANGLES: usize = 32;
+
+/// An image of a part of the screen.
+#[derive(Debug, Clone)]
+pub struct Frame {
+    /// The part of the root window the frame shows.
+    pub area: Rectangle,
+    /// The rows from top to bottom, without padding, of 4 bytes per pixel: red,
+    /// green, blue and alpha, which is always opaque.
+    pub data: Vec<u8>,
+    /// When the pixels were read.
+    pub timestamp: Instant,
+}
+
+impl Frame {
+    /// Returns the width of the frame in pixels.
+    pub fn width(&self) -> u16 {
+        self.area.width
+    }
+
+    /// Returns the height of the frame in pixels.
+    pub fn height(&self) -> u16 {
+        self.area.height
+    }
+
+    /// Encodes the frame as a PNG image.
+    #[cfg(feature = "png")]
+    pub fn write_png<W: std::io::Write>(&self, writer: W) -> std::io::Result<()> {
+        let width = u32::from(self.area.width);
+        let height = u32::from(self.area.height);
+        let mut encoder = png::Encoder::new(writer, width, height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.data)?;
+        Ok(writer.finish()?)
+    }
+}
+
+/// A segment attached to the server.
+struct ShmBuffer {
+    segment: ShmSegment,
+    shmseg: XId,
+}
+
+/// Captures frames of the default screen.
+pub struct Capturer {
+    root: Window,
+    screen: Rectangle,
+    layout: PixelLayout,
+    /// The segment the server writes the images to, if it is local.
+    shm: Option<ShmBuffer>,
+    cursor: bool,
+}
+
+impl Capturer {
+    /// Prepares capturing the default screen, attaching a segment large enough
+    /// for the whole screen if the server supports MIT-SHM and shares memory
+    /// with this process. If `cursor`, the cursor is composited on the frames,
+    /// which requires XFixes.
+    ///
+    /// Fails with [ConnectionError::UnsupportedVisual] if the root visual is not
+    /// TrueColor or DirectColor.
+    pub fn new(conn: &mut Connection, cursor: bool) -> Result<Capturer, ConnectionError> {
+        let screen = conn.default_screen();
+        let root = screen.root;
+        let layout = PixelLayout::new(conn, screen)?;
+        let screen = Rectangle {
+            x: 0,
+            y: 0,
+            width: screen.width_in_px,
+            height: screen.height_in_px,
+        };
+        if cursor && conn.enable::<XFixes>()?.is_none() {
+            return Err(ConnectionError::ExtensionNotSupported(xfixes::NAME));
+        }
+        let size = layout.stride(screen.width) * usize::from(screen.height);
+        let shm = attach_segment(conn, size)?;
+        Ok(Capturer {
+            root,
+            screen,
+            layout,
+            shm,
+            cursor,
+        })
+    }
+
+    /// Whether the frames are read through shared memory.
+    pub fn uses_shm(&self) -> bool {
+        self.shm.is_some()
+    }
+
+    /// Returns the whole screen.
+    pub fn capture_screen(&mut self, conn: &mut Connection) -> Result<Frame, ConnectionError> {
+        self.capture(conn, self.screen)
+    }
+
+    /// Returns the part of an area of the root window that is on the screen, or
+    /// `None` if none is.
+    pub fn capture_area(
+        &mut self,
+        conn: &mut Connection,
+        area: Rectangle,
+    ) -> Result<Option<Frame>, ConnectionError> {
+        match area.intersection(&self.screen) {
+            Some(area) => self.capture(conn, area).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the inside of a window, as the screen shows it: the windows
+    /// above it are captured too. Returns `None` if the window is off the screen.
+    pub fn capture_window(
+        &mut self,
+        conn: &mut Connection,
+        window: Window,
+    ) -> Result<Option<Frame>, ConnectionError> {
+        let geometry = conn.get_geometry(window)?;
+        let position = conn.translate_coordinates(window, self.root, 0, 0)?;
+        let geometry = geometry.reply(conn)?;
+        let position = position.reply(conn)?;
+        let area = Rectangle {
+            x: position.dst_x,
+            y: position.dst_y,
+            width: geometry.width,
+            height: geometry.height,
+        };
+        self.capture_area(conn, area)
+    }
+
+    /// Detaches the segment from the server. Dropping the capturer without
+    /// closing it leaves the segment attached until the connection closes.
+    pub fn close(self, conn: &mut Connection) -> Result<(), ConnectionError> {
+        if let Some(shm) = self.shm {
+            conn.shm_detach(shm.shmseg)?;
+            // The segment may only be removed once the server detached it.
+            if let Some(error) = conn.sync()?.into_iter().next() {
+                return Err(ConnectionError::ProtocolError(error));
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads an area inside the screen.
+    fn capture(
+        &mut self,
+        conn: &mut Connection,
+        area: Rectangle,
+    ) -> Result<Frame, ConnectionError> {
+        let cursor = if self.cursor {
+            Some(conn.xfixes_get_cursor_image()?)
+        } else {
+            None
+        };
+        let mut frame = match &self.shm {
+            Some(shm) => {
+                let reply = conn
+                    .shm_get_image(&shm::GetImage {
+                        drawable: self.root,
+                        x: area.x,
+                        y: area.y,
+                        width: area.width,
+                        height: area.height,
+                        plane_mask: !0,
+                        format: ImageFormat::Z_PIXMAP,
+                        shmseg: shm.shmseg,
+                        offset: 0,
+                    })?
+                    .reply(conn)?;
+                let timestamp = Instant::now();
+                let size = (reply.size as usize).min(shm.segment.len());
+                let data = &shm.segment.as_slice()[..size];
+                self.convert(data, area, timestamp)
+            }
+            None => {
+                let reply = conn
+                    .get_image(&GetImage {
+                        format: ImageFormat::Z_PIXMAP,
+                        drawable: self.root,
+                        x: area.x,
+                        y: area.y,
+                        width: area.width,
+                        height: area.height,
+                        plane_mask: !0,
+                    })?
+                    .reply(conn)?;
+                self.convert(&reply.data, area, Instant::now())
+            }
+        };
+        if let Some(cursor) = cursor {
+            draw_cursor(&mut frame, &cursor.reply(conn)?);
+        }
+        Ok(frame)
+    }
+
+    /// Converts a ZPixmap image of the root window to RGBA.
+    fn convert(&self, data: &[u8], area: Rectangle, timestamp: Instant) -> Frame {
+        let stride = self.layout.stride(area.width);
+        let bytes_per_pixel = self.layout.bytes_per_pixel;
+        let mut rgba = Vec::with_capacity(usize::from(area.width) * usize::from(area.height) * 4);
+        for row in data.chunks(stride).take(usize::from(area.height)) {
+            for pixel in row
+                .chunks_exact(bytes_per_pixel)
+                .take(usize::from(area.width))
+            {
+                let [red, green, blue] = self.layout.rgb(self.layout.get(pixel));
+                rgba.extend_from_slice(&[red, green, blue, 0xff]);
+            }
+        }
+        Frame {
+            area,
+            data: rgba,
+            timestamp,
+        }
+    }
+}
+
+/// Creates a segment of `size` bytes and attaches it, or returns `None` if the
+/// server does not support MIT-SHM or cannot access the segment, as when it
+/// runs on another machine.
+fn attach_segment(
+    conn: &mut Connection,
+    size: usize,
+) -> Result<Option<ShmBuffer>, ConnectionError> {
+    if conn.enable::<Shm>()?.is_none() {
+        return Ok(None);
+    }
+    let Ok(segment) = ShmSegment::new(size) else {
+        return Ok(None);
+    };
+    let shmseg = conn.shm_attach(&segment, false)?;
+    if !conn.sync()?.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(ShmBuffer { segment, shmseg }))
+}
+
+/// Blends the image of the cursor over a frame.
+fn draw_cursor(frame: &mut Frame, cursor: &xfixes::CursorImage) {
+    // The top left corner of the image, relative to the frame.
+    let left = i32::from(cursor.x) - i32::from(cursor.xhot) - i32::from(frame.area.x);
+    let top = i32::from(cursor.y) - i32::from(cursor.yhot) - i32::from(frame.area.y);
+    let (width, height) = (i32::from(frame.area.width), i32::from(frame.area.height));
+    for (i, &pixel) in cursor.pixels.iter().enumerate() {
+        let x = left + (i % usize::from(cursor.width)) as i32;
+        let y = top + (i / usize::from(cursor.width)) as i32;
+        if x < 0 || y < 0 || x >= width || y >= height {
+            continue;
+        }
+        let offset = (y * width + x) as usize * 4;
+        // The pixels are premultiplied ARGB.
+        let alpha = pixel >> 24;
+        let source = [pixel >> 16, pixel >> 8, pixel];
+        for (channel, source) in frame.data[offset..offset + 3].iter_mut().zip(source) {
+            let blended = (source & 0xff) + u32::from(*channel) * (255 - alpha) / 255;
+            *channel = blended.min(255) as u8;
+        }
+    }
+}
+
+/// How a [FrameStream] delivers frames.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StreamOptions {
+    /// The most frames delivered per second.
+    pub max_fps: u32,
+    /// How often the whole screen is delivered, changed or not, so that a
+    /// receiver joining late or losing a frame catches up.
+    pub keyframe_interval: Duration,
+}
+
+impl Default for StreamOptions {
+    fn default() -> Self {
+        StreamOptions {
+            max_fps: 30,
+            keyframe_interval: Duration::from_secs(5),
+        }
+    }
+}
+
+/// A frame delivered by a [FrameStream].
+#[derive(Debug, Clone)]
+pub enum StreamFrame {
+    /// The whole screen.
+    Keyframe(Frame),
+    /// The areas of the screen that changed since the previous frame.
+    Delta(Vec<Frame>),
+}
+
+/// Delivers the changes of the default screen, as the screen sharing and
+/// remote desktop tools need them.
+///
+/// The frames do not show the cursor, whose moves do not damage the screen;
+/// send it separately, from [Connection::xfixes_get_cursor_image].
+pub struct FrameStream {
+    capturer: Capturer,
+    options: StreamOptions,
+    damage: XId,
+    /// The region the changed areas are fetched through.
+    region: XId,
+    first_event: u8,
+    last_frame: Option<Instant>,
+    last_keyframe: Option<Instant>,
+}
+
+impl FrameStream {
+    /// Starts following the changes of the default screen. Requires DAMAGE and
+    /// XFixes 2.0.
+    pub fn new(
+        conn: &mut Connection,
+        options: StreamOptions,
+    ) -> Result<FrameStream, ConnectionError> {
+        if conn.enable::<XFixes>()?.is_none() {
+            return Err(ConnectionError::ExtensionNotSupported(xfixes::NAME));
+        }
+        if conn.enable::<Damage>()?.is_none() {
+            return Err(ConnectionError::ExtensionNotSupported(damage::NAME));
+        }
+        let first_event = conn
+            .extension(damage::NAME)?
+            .ok_or(ConnectionError::ExtensionNotSupported(damage::NAME))?
+            .first_event;
+        let capturer = Capturer::new(conn, false)?;
+        let root = conn.default_root();
+        // A single event tells that the screen changed; the changes themselves
+        // are fetched when the next frame is due.
+        let damage = conn.damage_create(root, ReportLevel::NON_EMPTY)?;
+        let region = conn.xfixes_create_region(&[])?;
+        Ok(FrameStream {
+            capturer,
+            options,
+            damage,
+            region,
+            first_event,
+            last_frame: None,
+            last_keyframe: None,
+        })
+    }
+
+    /// Returns the capturer reading the frames.
+    pub fn capturer(&self) -> &Capturer {
+        &self.capturer
+    }
+
+    /// Returns the next frame, blocking until the screen changes or a keyframe
+    /// is due, and at least until the frame rate allows a new frame. The first
+    /// frame is a keyframe. Other events stay queued.
+    pub fn next_frame(&mut self, conn: &mut Connection) -> Result<StreamFrame, ConnectionError> {
+        let interval = Duration::from_secs(1) / self.options.max_fps.max(1);
+        loop {
+            if let Some(last_frame) = self.last_frame {
+                let wait = interval.saturating_sub(last_frame.elapsed());
+                if !wait.is_zero() {
+                    std::thread::sleep(wait);
+                }
+            }
+            let keyframe_due = self.last_keyframe.map_or(Duration::ZERO, |last| {
+                self.options
+                    .keyframe_interval
+                    .saturating_sub(last.elapsed())
+            });
+            if keyframe_due.is_zero() {
+                // The keyframe covers every change so far.
+                conn.damage_subtract(self.damage, XId(0), XId(0))?;
+                self.drain_notifications(conn)?;
+                let frame = self.capturer.capture_screen(conn)?;
+                self.last_frame = Some(frame.timestamp);
+                self.last_keyframe = Some(frame.timestamp);
+                return Ok(StreamFrame::Keyframe(frame));
+            }
+            let (damage, first_event) = (self.damage, self.first_event);
+            let notification = conn.wait_for_event_matching_timeout(
+                |event| is_notification(event, damage, first_event),
+                keyframe_due,
+            )?;
+            if notification.is_none() {
+                continue;
+            }
+            self.drain_notifications(conn)?;
+            // Take the changes out of the damage before reading them, so that
+            // the changes made meanwhile are reported again.
+            conn.damage_subtract(self.damage, XId(0), self.region)?;
+            let region = conn.xfixes_fetch_region(self.region)?.reply(conn)?;
+            let areas = if region.rectangles.len() > MAX_DELTA_RECTANGLES {
+                vec![region.extents]
+            } else {
+                region.rectangles
+            };
+            let mut frames = Vec::with_capacity(areas.len());
+            for area in areas {
+                frames.extend(self.capturer.capture_area(conn, area)?);
+            }
+            // A notification may precede a subtraction that already took its change.
+            if let Some(frame) = frames.first() {
+                self.last_frame = Some(frame.timestamp);
+                return Ok(StreamFrame::Delta(frames));
+            }
+        }
+    }
+
+    /// Stops following the changes of the screen and detaches the segment of
+    /// the capturer.
+    pub fn close(self, conn: &mut Connection) -> Result<(), ConnectionError> {
+        conn.damage_destroy(self.damage)?;
+        conn.xfixes_destroy_region(self.region)?;
+        self.capturer.close(conn)
+    }
+
+    /// Discards the queued notifications of the damage.
+    fn drain_notifications(&self, conn: &mut Connection) -> Result<(), ConnectionError> {
+        let (damage, first_event) = (self.damage, self.first_event);
+        while conn
+            .poll_for_event_matching(|event| is_notification(event, damage, first_event))?
+            .is_some()
+        {}
+        Ok(())
+    }
+}
+
+/// Whether an event is a DamageNotify of the given damage.
+fn is_notification(event: &Event, damage: XId, first_event: u8) -> bool {
+    DamageNotifyEvent::from_event(event, first_event).is_some_and(|e| e.damage == damage)
+}
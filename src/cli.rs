@@ -0,0 +1,235 @@
+//! The subcommands of the binary, one module each.
+
+mod clipboard;
+mod events;
+mod idle;
+mod info;
+mod input;
+mod monitor;
+mod mouse;
+mod ping;
+mod prop;
+mod recorder;
+#[cfg(feature = "png")]
+mod screenshot;
+mod windows;
+mod xauth;
+
+use clap::{Args, Parser, Subcommand};
+use mousetrap::connection::Connection;
+use mousetrap::errors::ConnectionError;
+use mousetrap::protocol::{atom, Window, XId};
+use mousetrap::region;
+use mousetrap::reply::GetPropertyReply;
+use std::error::Error;
+use std::fmt;
+
+/// What a subcommand returns.
+pub type Result<T = ()> = std::result::Result<T, Box<dyn Error>>;
+
+/// Ends the binary with an exit code, for subcommands which already printed
+/// what went wrong.
+#[derive(Debug)]
+pub struct Exit(pub u8);
+
+impl fmt::Display for Exit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "exit code {}", self.0)
+    }
+}
+
+impl Error for Exit {}
+
+#[derive(Debug, Parser)]
+#[command(version, about = "Inspect and drive an X server")]
+pub struct Cli {
+    /// The display to connect to, instead of the one named by DISPLAY.
+    #[arg(long, global = true)]
+    display: Option<String>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Print the server's setup: vendor, screens, visuals, pixmap formats and
+    /// extensions.
+    Info,
+    /// Print the events of a test window, or of the window given with --id.
+    Events(events::EventsArgs),
+    /// Print the properties of a window, or change them.
+    Prop(prop::PropArgs),
+    /// Follow the mouse.
+    #[command(subcommand)]
+    Mouse(mouse::MouseCommand),
+    /// Read, own or watch the clipboard and the other selections.
+    #[command(subcommand)]
+    Clipboard(clipboard::ClipboardCommand),
+    /// Click a pointer button, optionally at a position.
+    Click(input::ClickArgs),
+    /// Type text with the keyboard.
+    Type(input::TypeArgs),
+    /// Press key combinations such as ctrl+shift+t.
+    Key(input::KeyArgs),
+    /// Save the screen, a window or a selected rectangle as a PNG image.
+    #[cfg(feature = "png")]
+    Screenshot(screenshot::ScreenshotArgs),
+    /// Print the outputs of the screen with their monitors' mode, refresh rate
+    /// and density.
+    Monitor(monitor::MonitorArgs),
+    /// Print how many milliseconds the user has been idle.
+    Idle(idle::IdleArgs),
+    /// Record the keyboard and the mouse into a macro file until a stop key.
+    Record(recorder::RecordArgs),
+    /// Replay a macro file recorded with `record`.
+    Replay(recorder::ReplayArgs),
+    /// List the window tree, or the windows of the window manager, with their
+    /// geometry, class and title.
+    Windows(windows::WindowsArgs),
+    /// Inspect the .Xauthority file.
+    #[command(subcommand)]
+    Xauth(xauth::XauthCommand),
+    /// Check that the display accepts clients, printing a JSON diagnosis and
+    /// exiting with 0 if it does, 1 if it refused the credentials and 2 if it
+    /// could not be reached.
+    #[command(alias = "healthcheck")]
+    Ping(ping::PingArgs),
+}
+
+impl Cli {
+    pub fn run(self) -> Result {
+        let display = self.display.as_deref();
+        match self.command {
+            Command::Info => info::run(&mut connect(display)?),
+            Command::Events(args) => events::run(&mut connect(display)?, args),
+            Command::Prop(args) => prop::run(&mut connect(display)?, args),
+            Command::Mouse(command) => mouse::run(display, command),
+            Command::Clipboard(command) => clipboard::run(&mut connect(display)?, command),
+            Command::Click(args) => input::click(&mut connect(display)?, args),
+            Command::Type(args) => input::type_text(&mut connect(display)?, args),
+            Command::Key(args) => input::key(&mut connect(display)?, args),
+            #[cfg(feature = "png")]
+            Command::Screenshot(args) => screenshot::run(&mut connect(display)?, args),
+            Command::Monitor(args) => monitor::run(&mut connect(display)?, args),
+            Command::Idle(args) => idle::run(&mut connect(display)?, args),
+            Command::Record(args) => recorder::record(display, args),
+            Command::Replay(args) => recorder::replay(display, args),
+            Command::Windows(args) => windows::run(&mut connect(display)?, args),
+            Command::Xauth(command) => xauth::run(display, command),
+            Command::Ping(args) => ping::run(display, args),
+        }
+    }
+}
+
+/// Connects to `display`, or else to the display named by DISPLAY.
+fn connect(display: Option<&str>) -> std::result::Result<Connection, ConnectionError> {
+    match display {
+        Some(display) => Connection::connect(display),
+        None => Connection::init(),
+    }
+}
+
+/// Parses a window id, in hexadecimal with a `0x` prefix or in decimal.
+fn parse_window(s: &str) -> std::result::Result<Window, String> {
+    let id = match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        Some(hex) => u32::from_str_radix(hex, 16),
+        None => s.parse(),
+    };
+    id.map(XId)
+        .map_err(|err| format!("invalid window id {:?}: {}", s, err))
+}
+
+/// How a subcommand working on a window picks it. Without any of the options,
+/// the user clicks the window.
+#[derive(Debug, Args)]
+pub struct WindowArgs {
+    /// The id of the window, e.g. 0x1a00003.
+    #[arg(long, value_parser = parse_window, group = "window")]
+    id: Option<Window>,
+
+    /// The window with this title (_NET_WM_NAME or WM_NAME).
+    #[arg(long, group = "window")]
+    name: Option<String>,
+
+    /// The root window.
+    #[arg(long, group = "window")]
+    root: bool,
+}
+
+impl WindowArgs {
+    /// Returns the window the options name, asking the user to click it if none
+    /// is given.
+    pub fn resolve(&self, conn: &mut Connection) -> Result<Window> {
+        if let Some(window) = self.id {
+            return Ok(window);
+        }
+        if self.root {
+            return Ok(conn.default_root());
+        }
+        if let Some(name) = &self.name {
+            return find_by_name(conn, name)?
+                .ok_or_else(|| format!("no window is named {:?}", name).into());
+        }
+        eprintln!("Click the window, or press Escape to cancel.");
+        region::select_window(conn)?.ok_or_else(|| "cancelled".into())
+    }
+}
+
+/// Returns the first window with the title `name`, searching the tree of the
+/// default screen breadth-first.
+fn find_by_name(conn: &mut Connection, name: &str) -> Result<Option<Window>> {
+    let net_wm_name = conn.ewmh_atoms()?.wm_name;
+    let mut level = vec![conn.default_root()];
+    while !level.is_empty() {
+        let mut cookies = Vec::with_capacity(level.len());
+        for &window in &level {
+            let net = conn.get_property(false, window, net_wm_name, atom::ANY, 0, u32::MAX)?;
+            let icccm = conn.get_property(false, window, atom::WM_NAME, atom::ANY, 0, u32::MAX)?;
+            let tree = conn.query_tree(window)?;
+            cookies.push((window, net, icccm, tree));
+        }
+        let mut found = None;
+        let mut next = Vec::new();
+        for (window, net, icccm, tree) in cookies {
+            let net = net.reply(conn)?;
+            let icccm = icccm.reply(conn)?;
+            let title = decode_text(&net).or_else(|| decode_text(&icccm));
+            if found.is_none() && title.as_deref() == Some(name) {
+                found = Some(window);
+            }
+            next.extend(tree.reply(conn)?.children);
+        }
+        if found.is_some() {
+            return Ok(found);
+        }
+        level = next;
+    }
+    Ok(None)
+}
+
+/// Decodes a text property: a STRING is Latin-1, anything else is taken as UTF-8.
+fn decode_text(reply: &GetPropertyReply) -> Option<String> {
+    let value = reply.value8()?;
+    Some(if reply.type_ == atom::STRING {
+        value.iter().map(|&b| char::from(b)).collect()
+    } else {
+        String::from_utf8_lossy(value).into_owned()
+    })
+}
+
+/// Quotes a string for JSON.
+fn json_string(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str(r#"\""#),
+            '\\' => quoted.push_str(r"\\"),
+            c if u32::from(c) < 0x20 => quoted.push_str(&format!("\\u{:04x}", u32::from(c))),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
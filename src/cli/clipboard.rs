@@ -0,0 +1,163 @@
+//! `mousetrap clipboard`, in the manner of xclip.
+
+use super::Result;
+use clap::{Args, Subcommand, ValueEnum};
+use mousetrap::clipboard::{Clipboard, Selection, SelectionData};
+use mousetrap::connection::Connection;
+use mousetrap::xfixes::{self, selection_subtype, SelectionNotifyEvent, XFixes};
+use std::io::{self, Read, Write};
+use std::time::Instant;
+
+#[derive(Debug, Subcommand)]
+pub enum ClipboardCommand {
+    /// Print the contents of a selection.
+    Get(GetArgs),
+    /// Own a selection holding the standard input, serving it until another
+    /// client takes the selection over.
+    Set(SetArgs),
+    /// Print the changes of owner of a selection.
+    Watch(SelectionArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct SelectionArgs {
+    #[arg(long, value_enum, default_value_t = SelectionName::Clipboard)]
+    selection: SelectionName,
+}
+
+#[derive(Debug, Args)]
+pub struct GetArgs {
+    #[command(flatten)]
+    selection: SelectionArgs,
+
+    /// The type to ask the contents in, e.g. image/png, instead of text.
+    #[arg(long)]
+    target: Option<String>,
+
+    /// Print the types the owner offers instead of the contents.
+    #[arg(long, conflicts_with = "target")]
+    list_targets: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct SetArgs {
+    #[command(flatten)]
+    selection: SelectionArgs,
+
+    /// The type of the contents, e.g. image/png, instead of text.
+    #[arg(long)]
+    target: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum SelectionName {
+    Clipboard,
+    Primary,
+    Secondary,
+}
+
+impl From<SelectionName> for Selection {
+    fn from(name: SelectionName) -> Selection {
+        match name {
+            SelectionName::Clipboard => Selection::Clipboard,
+            SelectionName::Primary => Selection::Primary,
+            SelectionName::Secondary => Selection::Secondary,
+        }
+    }
+}
+
+pub fn run(conn: &mut Connection, command: ClipboardCommand) -> Result {
+    let mut clipboard = Clipboard::new(conn)?;
+    match command {
+        ClipboardCommand::Get(args) => get(conn, &clipboard, args),
+        ClipboardCommand::Set(args) => set(conn, &mut clipboard, args),
+        ClipboardCommand::Watch(args) => watch(conn, &clipboard, args.selection.into()),
+    }
+}
+
+fn get(conn: &mut Connection, clipboard: &Clipboard, args: GetArgs) -> Result {
+    let selection = args.selection.selection.into();
+    if args.list_targets {
+        for target in clipboard.targets(conn, selection)? {
+            let name = conn.get_atom_name(target)?.reply(conn)?.name;
+            println!("{}", name.as_str_lossy());
+        }
+        return Ok(());
+    }
+    let contents = match &args.target {
+        Some(target) => clipboard.get(conn, selection, target)?,
+        None => clipboard.get_text(conn, selection)?.map(String::into_bytes),
+    };
+    let contents = contents.ok_or("the selection is empty or has no such target")?;
+    io::stdout().lock().write_all(&contents)?;
+    Ok(())
+}
+
+fn set(conn: &mut Connection, clipboard: &mut Clipboard, args: SetArgs) -> Result {
+    let selection = args.selection.selection.into();
+    let mut contents = Vec::new();
+    io::stdin().lock().read_to_end(&mut contents)?;
+    let owned = match &args.target {
+        Some(target) => {
+            let target = conn.intern_atom(false, target)?.reply(conn)?.atom;
+            clipboard.set_owner(conn, selection, &[target], move |requested| {
+                (requested == target).then(|| SelectionData {
+                    type_: target,
+                    format: 8,
+                    data: contents.clone(),
+                })
+            })?
+        }
+        None => {
+            let text = String::from_utf8(contents)
+                .map_err(|_| "the input is not UTF-8 text, give its type with --target")?;
+            clipboard.set_text(conn, selection, &text)?
+        }
+    };
+    if !owned {
+        return Err("another client kept the selection".into());
+    }
+    while clipboard.is_serving() {
+        clipboard.serve_event(conn)?;
+    }
+    Ok(())
+}
+
+fn watch(conn: &mut Connection, clipboard: &Clipboard, selection: Selection) -> Result {
+    conn.enable::<XFixes>()?
+        .ok_or("the server does not support XFIXES")?;
+    let first_event = conn
+        .extension(xfixes::NAME)?
+        .ok_or("the server does not support XFIXES")?
+        .first_event;
+    let atom = clipboard.selection_atom(selection);
+    let mask = xfixes::SET_SELECTION_OWNER_NOTIFY_MASK
+        | xfixes::SELECTION_WINDOW_DESTROY_NOTIFY_MASK
+        | xfixes::SELECTION_CLIENT_CLOSE_NOTIFY_MASK;
+    conn.xfixes_select_selection_input(clipboard.window(), atom, mask)?;
+    let owner = conn.get_selection_owner(atom)?.reply(conn)?.owner;
+    println!("{:10.3}  owner {:#x}", 0.0, owner.0);
+
+    let start = Instant::now();
+    let mut out = io::stdout().lock();
+    loop {
+        let event = conn.wait_for_event()?;
+        let Some(notify) = SelectionNotifyEvent::from_event(&event, first_event) else {
+            continue;
+        };
+        let elapsed = start.elapsed().as_secs_f64();
+        let line = match notify.subtype {
+            selection_subtype::SET_SELECTION_OWNER => {
+                format!("{:10.3}  owner {:#x}", elapsed, notify.owner.0)
+            }
+            selection_subtype::SELECTION_WINDOW_DESTROY => {
+                format!("{:10.3}  owner window destroyed", elapsed)
+            }
+            _ => format!("{:10.3}  owner client closed", elapsed),
+        };
+        match writeln!(out, "{}", line) {
+            Err(err) if err.kind() == io::ErrorKind::BrokenPipe => return Ok(()),
+            result => result?,
+        }
+    }
+}
@@ -0,0 +1,110 @@
+//! `mousetrap events`, in the manner of xev.
+
+use super::Result;
+use clap::Args;
+use mousetrap::connection::Connection;
+use mousetrap::event::Event;
+use mousetrap::icccm::ProtocolMessage;
+use mousetrap::keysym;
+use mousetrap::protocol::{EventMask, Window};
+use mousetrap::request::{CreateWindow, WindowAttributes, WindowClass};
+use std::time::Instant;
+
+#[derive(Debug, Args)]
+pub struct EventsArgs {
+    /// Watch this window instead of creating one, e.g. 0x1a00003.
+    #[arg(long, value_parser = super::parse_window)]
+    id: Option<Window>,
+
+    /// Print each event over several lines, one field per line.
+    #[arg(short, long)]
+    verbose: bool,
+}
+
+/// The events of a window of this client: all of them but the redirects, which
+/// would keep the window from being resized, and the motion hint, which would
+/// leave a single MotionNotify per move.
+const OWN_WINDOW_MASK: EventMask = EventMask(
+    ((EventMask::OWNER_GRAB_BUTTON.0 << 1) - 1)
+        & !(EventMask::POINTER_MOTION_HINT.0
+            | EventMask::RESIZE_REDIRECT.0
+            | EventMask::SUBSTRUCTURE_REDIRECT.0),
+);
+
+/// The events of another client's window: a single client may select
+/// ButtonPress, and it is usually the owner.
+const FOREIGN_WINDOW_MASK: EventMask = EventMask(OWN_WINDOW_MASK.0 & !EventMask::BUTTON_PRESS.0);
+
+pub fn run(conn: &mut Connection, args: EventsArgs) -> Result {
+    let own = args.id.is_none();
+    let window = match args.id {
+        Some(window) => {
+            conn.select_input(window, FOREIGN_WINDOW_MASK)?;
+            window
+        }
+        None => create_window(conn)?,
+    };
+    // Errors such as a bad window id are reported before the first event.
+    if let Some(err) = conn.sync()?.into_iter().next() {
+        return Err(err.into());
+    }
+    println!("watching window {:#x}", window.0);
+
+    let start = Instant::now();
+    loop {
+        let event = conn.wait_for_event()?;
+        let elapsed = start.elapsed().as_secs_f64();
+        if matches!(event, Event::ConnectionClosed) {
+            return Ok(());
+        }
+        if args.verbose {
+            println!("{:10.3}  {:#?}", elapsed, event);
+        } else {
+            println!("{:10.3}  {:?}", elapsed, event);
+        }
+        if let Event::KeyPress(key) | Event::KeyRelease(key) = &event {
+            let keysym = conn.keycode_to_keysym(key.detail, key.state)?;
+            let name = keysym::name(keysym).unwrap_or("NoSymbol");
+            match keysym::keysym_to_char(keysym) {
+                Some(c) => println!("{:10}  keysym {:#x} ({}), {:?}", "", keysym, name, c),
+                None => println!("{:10}  keysym {:#x} ({})", "", keysym, name),
+            }
+        }
+        if own {
+            if let Some(ProtocolMessage::CloseRequested { .. }) = conn.protocol_message(&event)? {
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// Creates and maps a window titled after the subcommand.
+fn create_window(conn: &mut Connection) -> Result<Window> {
+    let window = conn.generate_id()?;
+    let screen = conn.default_screen();
+    let request = CreateWindow {
+        depth: 0,
+        window,
+        parent: screen.root,
+        x: 0,
+        y: 0,
+        width: 400,
+        height: 300,
+        border_width: 1,
+        class: WindowClass::INPUT_OUTPUT,
+        visual: 0,
+        attributes: WindowAttributes {
+            background_pixel: Some(screen.white_pixel),
+            border_pixel: Some(screen.black_pixel),
+            event_mask: Some(OWN_WINDOW_MASK),
+            ..Default::default()
+        },
+    };
+    conn.create_window(&request)?;
+    let mut handle = conn.window(window);
+    handle.set_name("mousetrap events")?;
+    handle.register_protocols()?;
+    conn.set_auto_pong(true)?;
+    conn.map_window(window)?;
+    Ok(window)
+}
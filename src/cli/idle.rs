@@ -0,0 +1,40 @@
+//! `mousetrap idle`, in the manner of xprintidle.
+
+use super::Result;
+use clap::Args;
+use mousetrap::connection::Connection;
+use mousetrap::screensaver::ScreenSaver;
+use std::io::{self, Write};
+use std::thread;
+use std::time::Duration;
+
+#[derive(Debug, Args)]
+pub struct IdleArgs {
+    /// Keep printing the idle time, once per interval.
+    #[arg(long)]
+    watch: bool,
+
+    /// Milliseconds between two lines with --watch.
+    #[arg(long, default_value_t = 1000)]
+    interval: u64,
+}
+
+/// Prints the milliseconds since the last input of any device.
+pub fn run(conn: &mut Connection, args: IdleArgs) -> Result {
+    conn.enable::<ScreenSaver>()?
+        .ok_or("the server does not support MIT-SCREEN-SAVER")?;
+    let root = conn.default_root();
+    let mut out = io::stdout().lock();
+    loop {
+        let info = conn.screensaver_query_info(root)?.reply(conn)?;
+        match writeln!(out, "{}", info.ms_since_user_input) {
+            Err(err) if err.kind() == io::ErrorKind::BrokenPipe => return Ok(()),
+            result => result?,
+        }
+        if !args.watch {
+            return Ok(());
+        }
+        out.flush()?;
+        thread::sleep(Duration::from_millis(args.interval));
+    }
+}
@@ -0,0 +1,162 @@
+//! `mousetrap info`, in the manner of xdpyinfo.
+
+use super::Result;
+use mousetrap::connection::Connection;
+use mousetrap::protocol::{BackingStore, BitOrder, Screen, VisualClass};
+
+pub fn run(conn: &mut Connection) -> Result {
+    let mut extensions: Vec<String> = conn
+        .list_extensions()?
+        .reply(conn)?
+        .names
+        .iter()
+        .map(|name| name.as_str_lossy().into_owned())
+        .collect();
+    extensions.sort();
+
+    let setup = conn.setup();
+    println!(
+        "version number:    {}.{}",
+        setup.protocol_major_version, setup.protocol_minor_version
+    );
+    println!("vendor string:    {}", setup.vendor);
+    println!("vendor release number:    {}", setup.release_number);
+    println!(
+        "maximum request size:  {} bytes",
+        usize::from(setup.maximum_request_length) * 4
+    );
+    println!("motion buffer size:  {}", setup.motion_buffer_size);
+    println!(
+        "bitmap unit, bit order, padding:    {}, {}, {}",
+        setup.bitmap_scanline_unit,
+        bit_order(&setup.bitmap_bit_order),
+        setup.bitmap_scanline_pad
+    );
+    println!(
+        "image byte order:    {}",
+        if setup.image_byte_order == 0 {
+            "LSBFirst"
+        } else {
+            "MSBFirst"
+        }
+    );
+    println!(
+        "number of supported pixmap formats:    {}",
+        setup.pixmap_formats.len()
+    );
+    println!("supported pixmap formats:");
+    for format in &setup.pixmap_formats {
+        println!(
+            "    depth {}, bits_per_pixel {}, scanline_pad {}",
+            format.depth, format.bits_per_pixel, format.scanline_pad
+        );
+    }
+    println!(
+        "keycode range:    minimum {}, maximum {}",
+        setup.min_keycode, setup.max_keycode
+    );
+    println!("number of extensions:    {}", extensions.len());
+    for name in &extensions {
+        println!("    {}", name);
+    }
+    println!("default screen number:    {}", conn.default_screen_number());
+    println!("number of screens:    {}", setup.roots.len());
+
+    for (number, screen) in setup.roots.iter().enumerate() {
+        println!();
+        print_screen(number, screen);
+    }
+    Ok(())
+}
+
+fn print_screen(number: usize, screen: &Screen) {
+    println!("screen #{}:", number);
+    println!(
+        "  dimensions:    {}x{} pixels ({}x{} millimeters)",
+        screen.width_in_px, screen.height_in_px, screen.width_in_mm, screen.height_in_mm
+    );
+    println!(
+        "  resolution:    {}x{} dots per inch",
+        dpi(screen.width_in_px, screen.width_in_mm),
+        dpi(screen.height_in_px, screen.height_in_mm)
+    );
+    let depths: Vec<String> = screen
+        .allowed_depths
+        .iter()
+        .map(|depth| depth.depth.to_string())
+        .collect();
+    println!("  depths ({}):    {}", depths.len(), depths.join(", "));
+    println!("  root window id:    {:#x}", screen.root.0);
+    println!("  depth of root window:    {} planes", screen.root_depth);
+    println!(
+        "  number of colormaps:    minimum {}, maximum {}",
+        screen.min_installed_maps, screen.max_installed_maps
+    );
+    println!("  default colormap:    {:#x}", screen.default_colormap);
+    println!(
+        "  preallocated pixels:    black {}, white {}",
+        screen.black_pixel, screen.white_pixel
+    );
+    println!(
+        "  options:    backing-store {}, save-unders {}",
+        match screen.backing_stores {
+            BackingStore::Never => "NO",
+            BackingStore::WhenMapped => "WHEN MAPPED",
+            BackingStore::Always => "YES",
+        },
+        if screen.save_unders { "YES" } else { "NO" }
+    );
+    let visuals = screen.allowed_depths.iter().flat_map(|depth| {
+        depth
+            .visuals
+            .iter()
+            .map(move |visual| (depth.depth, visual))
+    });
+    println!("  number of visuals:    {}", visuals.clone().count());
+    println!("  default visual id:  {:#x}", screen.root_visual);
+    for (depth, visual) in visuals {
+        println!("  visual:");
+        println!("    visual id:    {:#x}", visual.visual_id);
+        println!("    class:    {}", visual_class(visual.class));
+        println!("    depth:    {} planes", depth);
+        println!(
+            "    available colormap entries:    {}",
+            visual.colormap_entries
+        );
+        println!(
+            "    red, green, blue masks:    {:#x}, {:#x}, {:#x}",
+            visual.red_mask, visual.green_mask, visual.blue_mask
+        );
+        println!(
+            "    significant bits in color specification:    {} bits",
+            visual.bits_per_rgb_value
+        );
+    }
+}
+
+/// Dots per inch of `pixels` spread over `millimeters`, 0 if the size is unknown.
+fn dpi(pixels: u16, millimeters: u16) -> u32 {
+    if millimeters == 0 {
+        return 0;
+    }
+    (f64::from(pixels) * 25.4 / f64::from(millimeters)).round() as u32
+}
+
+fn bit_order(order: &BitOrder) -> &'static str {
+    match order {
+        BitOrder::LeastSignificant => "LSBFirst",
+        BitOrder::MostSignificant => "MSBFirst",
+    }
+}
+
+fn visual_class(class: VisualClass) -> &'static str {
+    match class {
+        VisualClass::STATIC_GRAY => "StaticGray",
+        VisualClass::GRAY_SCALE => "GrayScale",
+        VisualClass::STATIC_COLOR => "StaticColor",
+        VisualClass::PSEUDO_COLOR => "PseudoColor",
+        VisualClass::TRUE_COLOR => "TrueColor",
+        VisualClass::DIRECT_COLOR => "DirectColor",
+        _ => "Unknown",
+    }
+}
@@ -0,0 +1,175 @@
+//! `mousetrap click`, `type` and `key`, in the manner of xdotool, through the
+//! XTEST extension.
+
+use super::Result;
+use clap::Args;
+use mousetrap::connection::Connection;
+use mousetrap::keysym::{self, XK_Return};
+use mousetrap::protocol::{KeyCode, Keysym};
+use mousetrap::xtest::XTest;
+use std::thread;
+use std::time::Duration;
+
+#[derive(Debug, Args)]
+pub struct ClickArgs {
+    /// The button: 1 is the left one, 2 the middle one, 3 the right one, 4 and 5
+    /// scroll vertically.
+    #[arg(long, default_value_t = 1)]
+    button: u8,
+
+    /// Move the pointer to this position of the root window first, e.g. 100,200.
+    #[arg(long, value_name = "X,Y", value_parser = parse_position)]
+    at: Option<(i16, i16)>,
+
+    /// Click this many times, e.g. 2 for a double click.
+    #[arg(long, default_value_t = 1)]
+    repeat: u32,
+
+    /// Milliseconds between two clicks.
+    #[arg(long, default_value_t = 100)]
+    delay: u64,
+}
+
+#[derive(Debug, Args)]
+pub struct TypeArgs {
+    /// The text to type.
+    text: String,
+
+    /// Milliseconds between two characters.
+    #[arg(long, default_value_t = 12)]
+    delay: u64,
+}
+
+#[derive(Debug, Args)]
+pub struct KeyArgs {
+    /// The keys to press, one after the other. Each is a keysym name or a
+    /// character, after modifiers joined by `+`, e.g. `ctrl+shift+t` or `Return`.
+    #[arg(required = true)]
+    combos: Vec<String>,
+
+    /// Milliseconds between two keys.
+    #[arg(long, default_value_t = 12)]
+    delay: u64,
+}
+
+pub fn click(conn: &mut Connection, args: ClickArgs) -> Result {
+    conn.enable::<XTest>()?;
+    if let Some((x, y)) = args.at {
+        conn.xtest_fake_motion(x, y, false)?;
+    }
+    for i in 0..args.repeat {
+        if i > 0 {
+            thread::sleep(Duration::from_millis(args.delay));
+        }
+        conn.xtest_fake_button(args.button, true)?;
+        conn.xtest_fake_button(args.button, false)?;
+        sync(conn)?;
+    }
+    sync(conn)
+}
+
+pub fn type_text(conn: &mut Connection, args: TypeArgs) -> Result {
+    conn.enable::<XTest>()?;
+    for c in args.text.chars() {
+        let keysym = match c {
+            '\n' => XK_Return,
+            c => keysym::char_to_keysym(c),
+        };
+        let (keycode, modifiers) = resolve(conn, keysym)?
+            .ok_or_else(|| format!("no key of the keyboard types {:?}", c))?;
+        press_chord(conn, &modifiers, keycode)?;
+        thread::sleep(Duration::from_millis(args.delay));
+    }
+    Ok(())
+}
+
+pub fn key(conn: &mut Connection, args: KeyArgs) -> Result {
+    conn.enable::<XTest>()?;
+    for combo in &args.combos {
+        let mut keycodes = Vec::new();
+        for name in combo.split('+') {
+            let keysym = parse_key(name).ok_or_else(|| format!("unknown key {:?}", name))?;
+            let (keycode, modifiers) = resolve(conn, keysym)?
+                .ok_or_else(|| format!("no key of the keyboard is {:?}", name))?;
+            for keycode in modifiers.into_iter().chain([keycode]) {
+                if !keycodes.contains(&keycode) {
+                    keycodes.push(keycode);
+                }
+            }
+        }
+        let (&keycode, modifiers) = keycodes.split_last().ok_or("empty key combination")?;
+        press_chord(conn, modifiers, keycode)?;
+        thread::sleep(Duration::from_millis(args.delay));
+    }
+    Ok(())
+}
+
+/// Returns the keycode producing `keysym` and the keycodes of the modifiers it
+/// needs, or `None` if no key produces it.
+fn resolve(conn: &mut Connection, keysym: Keysym) -> Result<Option<(KeyCode, Vec<KeyCode>)>> {
+    let Some((keycode, state)) = conn.keysym_to_keycode(keysym)? else {
+        return Ok(None);
+    };
+    let mapping = conn.modifier_mapping()?;
+    let mut modifiers = Vec::new();
+    for modifier in (0..8).filter(|&modifier| state & 1 << modifier != 0) {
+        match mapping.keycodes(modifier).iter().find(|&&k| k != 0) {
+            Some(&keycode) => modifiers.push(keycode),
+            None => return Ok(None),
+        }
+    }
+    Ok(Some((keycode, modifiers)))
+}
+
+/// Presses the modifiers in order, then presses and releases the key, then
+/// releases the modifiers in reverse order.
+fn press_chord(conn: &mut Connection, modifiers: &[KeyCode], keycode: KeyCode) -> Result {
+    for &modifier in modifiers {
+        conn.xtest_fake_key(modifier, true)?;
+    }
+    conn.xtest_fake_key(keycode, true)?;
+    conn.xtest_fake_key(keycode, false)?;
+    for &modifier in modifiers.iter().rev() {
+        conn.xtest_fake_key(modifier, false)?;
+    }
+    sync(conn)
+}
+
+/// Returns the keysym of a key of a combination: a modifier alias such as
+/// `ctrl`, a keysym name, or a single character.
+fn parse_key(name: &str) -> Option<Keysym> {
+    let alias = match name.to_ascii_lowercase().as_str() {
+        "ctrl" | "control" => Some("Control_L"),
+        "shift" => Some("Shift_L"),
+        "alt" => Some("Alt_L"),
+        "super" | "win" => Some("Super_L"),
+        "meta" => Some("Meta_L"),
+        "altgr" => Some("ISO_Level3_Shift"),
+        _ => None,
+    };
+    if let Some(keysym) = keysym::from_name(alias.unwrap_or(name)) {
+        return Some(keysym);
+    }
+    let mut chars = name.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Some(keysym::char_to_keysym(c)),
+        _ => None,
+    }
+}
+
+/// Parses a position given as `X,Y`.
+fn parse_position(s: &str) -> std::result::Result<(i16, i16), String> {
+    let parse = || {
+        let (x, y) = s.split_once(',')?;
+        Some((x.trim().parse().ok()?, y.trim().parse().ok()?))
+    };
+    parse().ok_or_else(|| format!("expected X,Y, got {:?}", s))
+}
+
+/// Waits for the server to process the faked input, reporting its first error.
+fn sync(conn: &mut Connection) -> Result {
+    match conn.sync()?.into_iter().next() {
+        Some(err) => Err(err.into()),
+        None => Ok(()),
+    }
+}
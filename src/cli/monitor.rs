@@ -0,0 +1,160 @@
+//! `mousetrap monitor`, in the manner of `xrandr --query`.
+
+use super::Result;
+use clap::Args;
+use mousetrap::connection::Connection;
+use mousetrap::extension::Version;
+use mousetrap::protocol::Window;
+use mousetrap::randr::{
+    self, CrtcInfo, NotifyMask, OutputConnection, OutputInfo, Randr, RandrEvent, Rotation,
+    ScreenResources,
+};
+use std::time::Instant;
+
+#[derive(Debug, Args)]
+pub struct MonitorArgs {
+    /// Keep running and report monitors being plugged in, unplugged or
+    /// reconfigured.
+    #[arg(long)]
+    watch: bool,
+}
+
+pub fn run(conn: &mut Connection, args: MonitorArgs) -> Result {
+    let version = conn
+        .enable::<Randr>()?
+        .ok_or("the server does not support RandR 1.2")?;
+    let root = conn.default_root();
+    print_outputs(conn, root, version)?;
+    if !args.watch {
+        return Ok(());
+    }
+
+    let first_event = conn
+        .extension(randr::NAME)?
+        .ok_or("the server does not support RandR")?
+        .first_event;
+    let mask = NotifyMask(
+        NotifyMask::SCREEN_CHANGE.0 | NotifyMask::CRTC_CHANGE.0 | NotifyMask::OUTPUT_CHANGE.0,
+    );
+    conn.randr_select_input(root, mask)?;
+    let start = Instant::now();
+    loop {
+        let event = conn.wait_for_event()?;
+        let Some(event) = RandrEvent::from_event(&event, first_event) else {
+            continue;
+        };
+        let elapsed = start.elapsed().as_secs_f64();
+        match event {
+            RandrEvent::ScreenChangeNotify(change) => println!(
+                "{:10.3}  screen resized to {}x{} ({}x{} mm)",
+                elapsed, change.width, change.height, change.mm_width, change.mm_height
+            ),
+            RandrEvent::CrtcChange(change) if change.mode == 0 => {
+                println!("{:10.3}  crtc {:#x} disabled", elapsed, change.crtc)
+            }
+            RandrEvent::CrtcChange(change) => println!(
+                "{:10.3}  crtc {:#x} shows {}x{}+{}+{}",
+                elapsed, change.crtc, change.width, change.height, change.x, change.y
+            ),
+            RandrEvent::OutputChange(change) => {
+                let info = conn
+                    .randr_get_output_info(change.output, change.config_timestamp)?
+                    .reply(conn)?;
+                println!(
+                    "{:10.3}  {} {}",
+                    elapsed,
+                    info.name.as_str_lossy(),
+                    connection_name(change.connection)
+                );
+                print_outputs(conn, root, version)?;
+            }
+            RandrEvent::OutputProperty(_) => {}
+        }
+    }
+}
+
+/// Prints each output with its monitor's mode, position, refresh rate, size and
+/// density, the primary one marked.
+fn print_outputs(conn: &mut Connection, root: Window, version: Version) -> Result {
+    // Before 1.3, only the request probing the hardware exists.
+    let (resources, primary) = if version >= (1, 3) {
+        let primary = conn.randr_get_output_primary(root)?;
+        let resources = conn.randr_get_screen_resources_current(root)?;
+        (resources.reply(conn)?, primary.reply(conn)?.output)
+    } else {
+        (conn.randr_get_screen_resources(root)?.reply(conn)?, 0)
+    };
+    let timestamp = resources.config_timestamp;
+    let mut cookies = Vec::with_capacity(resources.outputs.len());
+    for &output in &resources.outputs {
+        cookies.push((output, conn.randr_get_output_info(output, timestamp)?));
+    }
+    for (output, cookie) in cookies {
+        let info = cookie.reply(conn)?;
+        let crtc = match info.crtc {
+            0 => None,
+            crtc => Some(conn.randr_get_crtc_info(crtc, timestamp)?.reply(conn)?),
+        };
+        println!(
+            "{}",
+            format_output(&resources, &info, crtc.as_ref(), output == primary)
+        );
+    }
+    Ok(())
+}
+
+fn format_output(
+    resources: &ScreenResources,
+    info: &OutputInfo,
+    crtc: Option<&CrtcInfo>,
+    primary: bool,
+) -> String {
+    let mut line = format!(
+        "{} {}",
+        info.name.as_str_lossy(),
+        connection_name(info.connection)
+    );
+    if primary {
+        line.push_str(" primary");
+    }
+    let Some(crtc) = crtc.filter(|crtc| crtc.mode != 0) else {
+        if info.connection == OutputConnection::CONNECTED {
+            line.push_str(" (off)");
+        }
+        return line;
+    };
+    line.push_str(&format!(
+        " {}x{}+{}+{}",
+        crtc.width, crtc.height, crtc.x, crtc.y
+    ));
+    if let Some(mode) = resources.mode(crtc.mode) {
+        line.push_str(&format!(" {:.2} Hz", mode.refresh_rate()));
+    }
+    if info.mm_width == 0 || info.mm_height == 0 {
+        return line;
+    }
+    line.push_str(&format!(" {}x{} mm", info.mm_width, info.mm_height));
+    // The size is the one of the panel, which the CRTC may show rotated.
+    let rotated =
+        crtc.rotation.contains(Rotation::ROTATE_90) || crtc.rotation.contains(Rotation::ROTATE_270);
+    let (mm_width, mm_height) = if rotated {
+        (info.mm_height, info.mm_width)
+    } else {
+        (info.mm_width, info.mm_height)
+    };
+    let dpi = |pixels: u16, mm: u32| f64::from(pixels) * 25.4 / f64::from(mm);
+    line.push_str(&format!(
+        " {:.0}x{:.0} dpi",
+        dpi(crtc.width, mm_width),
+        dpi(crtc.height, mm_height)
+    ));
+    line
+}
+
+fn connection_name(connection: OutputConnection) -> &'static str {
+    match connection {
+        OutputConnection::CONNECTED => "connected",
+        OutputConnection::DISCONNECTED => "disconnected",
+        _ => "unknown connection",
+    }
+}
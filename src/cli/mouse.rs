@@ -0,0 +1,177 @@
+//! `mousetrap mouse`, following what the user does with the mouse.
+
+use super::{json_string, Result};
+use clap::{Args, Subcommand};
+use mousetrap::connection::Connection;
+use mousetrap::errors::ConnectionError;
+use mousetrap::monitors::Monitors;
+use mousetrap::mouse::{MouseEvent, MouseListener};
+use mousetrap::pointer::PointerTracker;
+use std::io::{self, Write};
+use std::sync::mpsc::{self, Sender};
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Subcommand)]
+pub enum MouseCommand {
+    /// Print the pointer motions, buttons and scrolls of the whole screen, one
+    /// JSON object per line.
+    Track(TrackArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct TrackArgs {
+    /// Only report buttons and scrolls.
+    #[arg(long)]
+    no_motion: bool,
+
+    /// Interval in milliseconds between two queries of the pointer, when the
+    /// server does not have XI2 raw events.
+    #[arg(long, default_value_t = 10)]
+    interval: u64,
+}
+
+/// What the threads following the pointer report.
+enum Report {
+    Motion { x: i16, y: i16, buttons: u8 },
+    Mouse(MouseEvent),
+}
+
+pub fn run(display: Option<&str>, command: MouseCommand) -> Result {
+    match command {
+        MouseCommand::Track(args) => track(display, args),
+    }
+}
+
+/// Prints the reports of two threads, each with its own connection: one follows
+/// the position through a [PointerTracker], the other the buttons through a
+/// [MouseListener]. Both select XI2 raw events, which would replace each other on
+/// a single connection.
+fn track(display: Option<&str>, args: TrackArgs) -> Result {
+    let monitors = Monitors::query(&mut super::connect(display)?)?;
+    let start = Instant::now();
+    let (sender, receiver) = mpsc::channel();
+
+    if !args.no_motion {
+        let interval = Duration::from_millis(args.interval);
+        spawn_follower(display, sender.clone(), move |conn, sender| {
+            let mut tracker = PointerTracker::new(conn)?;
+            tracker.set_interval(interval);
+            loop {
+                let sample = tracker.next_sample(conn)?;
+                let report = Report::Motion {
+                    x: sample.x,
+                    y: sample.y,
+                    buttons: sample.buttons,
+                };
+                if sender.send(Ok((sample.time, report))).is_err() {
+                    return Ok(());
+                }
+            }
+        });
+    }
+    spawn_follower(display, sender, |conn, sender| {
+        let mut listener = MouseListener::new(conn)?;
+        loop {
+            let event = listener.next_event(conn)?;
+            if sender
+                .send(Ok((Instant::now(), Report::Mouse(event))))
+                .is_err()
+            {
+                return listener.stop(conn);
+            }
+        }
+    });
+
+    let mut out = io::stdout().lock();
+    for report in receiver {
+        let (time, report) = report?;
+        let time = time.saturating_duration_since(start).as_millis();
+        let line = format_report(&monitors, time, &report);
+        match writeln!(out, "{}", line) {
+            // The reader went away, e.g. `| head`.
+            Err(err) if err.kind() == io::ErrorKind::BrokenPipe => return Ok(()),
+            result => result?,
+        }
+    }
+    Ok(())
+}
+
+type Reports = Sender<std::result::Result<(Instant, Report), ConnectionError>>;
+
+/// Runs `follow` on a thread with a connection of its own to `display`, sending
+/// its error, if any, after its reports.
+fn spawn_follower<F>(display: Option<&str>, sender: Reports, follow: F)
+where
+    F: FnOnce(&mut Connection, &Reports) -> std::result::Result<(), ConnectionError>
+        + Send
+        + 'static,
+{
+    let display = display.map(str::to_owned);
+    thread::spawn(move || {
+        let result =
+            super::connect(display.as_deref()).and_then(|mut conn| follow(&mut conn, &sender));
+        if let Err(err) = result {
+            let _ = sender.send(Err(err));
+        }
+    });
+}
+
+/// Formats a report as a JSON object, with the monitor showing its position.
+fn format_report(monitors: &Monitors, time: u128, report: &Report) -> String {
+    let (kind, x, y, fields) = match *report {
+        Report::Motion { x, y, buttons } => ("motion", x, y, format!(r#""buttons":{}"#, buttons)),
+        Report::Mouse(ref event) => match *event {
+            MouseEvent::Press { button, x, y } => ("press", x, y, button_field(button)),
+            MouseEvent::Release { button, x, y } => ("release", x, y, button_field(button)),
+            MouseEvent::Click { button, x, y } => ("click", x, y, button_field(button)),
+            MouseEvent::Scroll {
+                x,
+                y,
+                delta,
+                horizontal,
+            } => (
+                "scroll",
+                x,
+                y,
+                format!(r#""delta":{},"horizontal":{}"#, delta, horizontal),
+            ),
+            MouseEvent::Drag {
+                button,
+                start,
+                x,
+                y,
+            } => ("drag", x, y, drag_fields(button, start)),
+            MouseEvent::DragEnd {
+                button,
+                start,
+                x,
+                y,
+            } => ("drag_end", x, y, drag_fields(button, start)),
+        },
+    };
+    let monitor = match monitors.to_local(x, y) {
+        Some((monitor, local_x, local_y)) => format!(
+            r#""monitor":{},"monitor_x":{},"monitor_y":{}"#,
+            monitor.name.as_deref().map_or("null".into(), json_string),
+            local_x,
+            local_y
+        ),
+        None => r#""monitor":null"#.into(),
+    };
+    format!(
+        r#"{{"time_ms":{},"type":"{}","x":{},"y":{},{},{}}}"#,
+        time, kind, x, y, fields, monitor
+    )
+}
+
+fn button_field(button: u8) -> String {
+    format!(r#""button":{}"#, button)
+}
+
+fn drag_fields(button: u8, start: (i16, i16)) -> String {
+    format!(
+        r#""button":{},"start_x":{},"start_y":{}"#,
+        button, start.0, start.1
+    )
+}
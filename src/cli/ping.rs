@@ -0,0 +1,101 @@
+//! `mousetrap ping`, telling whether a display accepts clients, e.g. while a CI
+//! job waits for its Xvfb to start.
+
+use super::{json_string, Exit, Result};
+use clap::Args;
+use mousetrap::errors::ConnectionError;
+use std::env;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Args)]
+pub struct PingArgs {
+    /// Milliseconds to wait for the server before reporting it unreachable.
+    #[arg(long, default_value_t = 5000)]
+    timeout: u64,
+}
+
+/// What the server told about itself once connected.
+struct Server {
+    vendor: String,
+    release: u32,
+    protocol: (u16, u16),
+    screens: usize,
+}
+
+/// Connects and makes a round trip, then prints a JSON line and exits with 0
+/// if the server answered, 1 if it refused the client's credentials and 2 if it
+/// could not be reached.
+pub fn run(display: Option<&str>, args: PingArgs) -> Result {
+    let name = display
+        .map(str::to_owned)
+        .or_else(|| env::var("DISPLAY").ok())
+        .unwrap_or_default();
+    let display = display.map(str::to_owned);
+    let start = Instant::now();
+    // Connecting blocks in the handshake of a server which accepted the socket
+    // but hangs, so it runs aside and is left behind on timeout.
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let _ = sender.send(ping(display.as_deref()));
+    });
+    let result = receiver.recv_timeout(Duration::from_millis(args.timeout));
+    let elapsed = start.elapsed().as_millis();
+
+    let (code, status, details) = match result {
+        Ok(Ok(server)) => (
+            0,
+            "ok",
+            format!(
+                r#""vendor":{},"release":{},"protocol":"{}.{}","screens":{}"#,
+                json_string(&server.vendor),
+                server.release,
+                server.protocol.0,
+                server.protocol.1,
+                server.screens
+            ),
+        ),
+        Ok(Err(
+            err @ (ConnectionError::ConnectionRefused { .. }
+            | ConnectionError::FurtherAuthenticationRequired { .. }),
+        )) => (1, "auth_failure", error_field(&err.to_string())),
+        Ok(Err(err)) => (2, "unreachable", error_field(&err.to_string())),
+        Err(RecvTimeoutError::Timeout) => (
+            2,
+            "unreachable",
+            error_field(&format!("no answer within {} ms", args.timeout)),
+        ),
+        Err(RecvTimeoutError::Disconnected) => {
+            (2, "unreachable", error_field("the connection panicked"))
+        }
+    };
+    println!(
+        r#"{{"display":{},"status":"{}","elapsed_ms":{},{}}}"#,
+        json_string(&name),
+        status,
+        elapsed,
+        details
+    );
+    match code {
+        0 => Ok(()),
+        code => Err(Exit(code).into()),
+    }
+}
+
+fn ping(display: Option<&str>) -> std::result::Result<Server, ConnectionError> {
+    let mut conn = super::connect(display)?;
+    // The handshake alone does not show that the server processes requests.
+    conn.sync()?;
+    let setup = conn.setup();
+    Ok(Server {
+        vendor: setup.vendor.to_string(),
+        release: setup.release_number,
+        protocol: (setup.protocol_major_version, setup.protocol_minor_version),
+        screens: setup.roots.len(),
+    })
+}
+
+fn error_field(message: &str) -> String {
+    format!(r#""error":{}"#, json_string(message))
+}
@@ -0,0 +1,213 @@
+//! `mousetrap prop`, in the manner of xprop.
+
+use super::{Result, WindowArgs};
+use clap::Args;
+use mousetrap::atom_cache::AtomCache;
+use mousetrap::connection::Connection;
+use mousetrap::icccm::{SizeHints, WmHints};
+use mousetrap::protocol::{atom, Window};
+use mousetrap::reply::GetPropertyReply;
+use mousetrap::request::PropMode;
+
+#[derive(Debug, Args)]
+pub struct PropArgs {
+    #[command(flatten)]
+    window: WindowArgs,
+
+    /// Set a property, e.g. `--set _MY_NOTE="hello"`. Repeatable.
+    #[arg(long, value_name = "NAME=VALUE")]
+    set: Vec<String>,
+
+    /// The type of the values given with --set: STRING, UTF8_STRING, CARDINAL,
+    /// INTEGER, ATOM or WINDOW. Text is a STRING if it is ASCII, a UTF8_STRING
+    /// otherwise. Numbers, atoms and windows are separated by commas.
+    #[arg(long = "type", value_name = "TYPE")]
+    type_: Option<String>,
+
+    /// Remove a property. Repeatable.
+    #[arg(long, value_name = "NAME")]
+    remove: Vec<String>,
+
+    /// Only print these properties.
+    properties: Vec<String>,
+}
+
+pub fn run(conn: &mut Connection, args: PropArgs) -> Result {
+    let window = args.window.resolve(conn)?;
+    let mut atoms = AtomCache::new();
+    if args.set.is_empty() && args.remove.is_empty() {
+        return print_properties(conn, &mut atoms, window, &args.properties);
+    }
+
+    for assignment in &args.set {
+        let (name, value) = assignment
+            .split_once('=')
+            .ok_or_else(|| format!("expected NAME=VALUE, got {:?}", assignment))?;
+        let property = atoms.intern(conn, name)?;
+        let type_name = match &args.type_ {
+            Some(type_name) => type_name.as_str(),
+            None if value.is_ascii() => "STRING",
+            None => "UTF8_STRING",
+        };
+        let (format, data) = encode(conn, &mut atoms, type_name, value)?;
+        let type_ = atoms.intern(conn, type_name)?;
+        conn.change_property(PropMode::REPLACE, window, property, type_, format, &data)?;
+    }
+    for name in &args.remove {
+        // A property can only be named by an existing atom, so none is created.
+        let property = conn.intern_atom(true, name)?.reply(conn)?.atom;
+        if property != atom::NONE {
+            conn.delete_property(window, property)?;
+        }
+    }
+    if let Some(err) = conn.sync()?.into_iter().next() {
+        return Err(err.into());
+    }
+    Ok(())
+}
+
+/// Prints the properties of `window`, or only those named in `only` if any, one
+/// per line as `NAME(TYPE) = value`.
+fn print_properties(
+    conn: &mut Connection,
+    atoms: &mut AtomCache,
+    window: Window,
+    only: &[String],
+) -> Result {
+    let properties = if only.is_empty() {
+        conn.list_properties(window)?.reply(conn)?.atoms
+    } else {
+        let mut properties = Vec::new();
+        for name in only {
+            properties.push(atoms.intern(conn, name)?);
+        }
+        properties
+    };
+    let mut cookies = Vec::with_capacity(properties.len());
+    for &property in &properties {
+        cookies.push(conn.get_property(false, window, property, atom::ANY, 0, u32::MAX)?);
+    }
+    for (property, cookie) in properties.into_iter().zip(cookies) {
+        let reply = cookie.reply(conn)?;
+        let name = atoms.name(conn, property)?.to_owned();
+        if !reply.exists() {
+            println!("{}:  not found.", name);
+            continue;
+        }
+        let type_name = atoms.name(conn, reply.type_)?.to_owned();
+        let value = format_value(conn, atoms, &name, &type_name, &reply)?;
+        println!("{}({}) = {}", name, type_name, value);
+    }
+    Ok(())
+}
+
+/// Formats the value of a property according to its type.
+fn format_value(
+    conn: &mut Connection,
+    atoms: &mut AtomCache,
+    name: &str,
+    type_name: &str,
+    reply: &GetPropertyReply,
+) -> Result<String> {
+    if let Some(bytes) = reply.value8() {
+        return Ok(match type_name {
+            "STRING" => quote_strings(bytes, |s| s.iter().map(|&b| char::from(b)).collect()),
+            "UTF8_STRING" | "TEXT" | "COMPOUND_TEXT" => {
+                quote_strings(bytes, |s| String::from_utf8_lossy(s).into_owned())
+            }
+            "CARDINAL" => join(bytes, |b| b.to_string()),
+            "INTEGER" => join(bytes, |&b| (b as i8).to_string()),
+            _ => join(bytes, |b| format!("{:#04x}", b)),
+        });
+    }
+    if let Some(values) = reply.value16() {
+        return Ok(match type_name {
+            "CARDINAL" => join(&values, |v| v.to_string()),
+            "INTEGER" => join(&values, |&v| (v as i16).to_string()),
+            _ => join(&values, |v| format!("{:#06x}", v)),
+        });
+    }
+    let values = reply.value32().unwrap_or_default();
+    Ok(match (name, type_name) {
+        ("_NET_WM_ICON", "CARDINAL") => format_icons(&values),
+        (_, "WM_HINTS") => format!("{:?}", WmHints::decode(&values)),
+        (_, "WM_SIZE_HINTS") => format!("{:?}", SizeHints::decode(&values)),
+        (_, "ATOM") => {
+            let mut names = Vec::with_capacity(values.len());
+            for &atom in &values {
+                names.push(atoms.name(conn, atom)?.to_owned());
+            }
+            names.join(", ")
+        }
+        (_, "WINDOW" | "PIXMAP" | "DRAWABLE" | "CURSOR" | "COLORMAP" | "FONT" | "VISUALID") => {
+            join(&values, |v| format!("{:#x}", v))
+        }
+        (_, "CARDINAL") => join(&values, |v| v.to_string()),
+        (_, "INTEGER") => join(&values, |&v| (v as i32).to_string()),
+        _ => join(&values, |v| format!("{:#010x}", v)),
+    })
+}
+
+/// Quotes the NUL-separated strings of a text property.
+fn quote_strings(bytes: &[u8], decode: impl Fn(&[u8]) -> String) -> String {
+    let bytes = bytes.strip_suffix(&[0]).unwrap_or(bytes);
+    let strings: Vec<String> = bytes
+        .split(|&b| b == 0)
+        .map(|s| format!("\"{}\"", decode(s).escape_debug()))
+        .collect();
+    strings.join(", ")
+}
+
+fn join<T>(values: &[T], format: impl Fn(&T) -> String) -> String {
+    values.iter().map(format).collect::<Vec<_>>().join(", ")
+}
+
+/// Lists the sizes of the icons in a _NET_WM_ICON, each a width and a height
+/// followed by that many pixels, rather than the pixels.
+fn format_icons(mut values: &[u32]) -> String {
+    let mut sizes = Vec::new();
+    while let [width, height, rest @ ..] = values {
+        sizes.push(format!("{}x{}", width, height));
+        let pixels = (*width as usize).saturating_mul(*height as usize);
+        values = rest.get(pixels..).unwrap_or(&[]);
+    }
+    format!("{} icons: {}", sizes.len(), sizes.join(", "))
+}
+
+/// Encodes the value given to --set as the data of a property of `type_name`,
+/// and returns its format.
+fn encode(
+    conn: &mut Connection,
+    atoms: &mut AtomCache,
+    type_name: &str,
+    value: &str,
+) -> Result<(u8, Vec<u8>)> {
+    let items = || value.split(',').map(str::trim);
+    let values: Vec<u32> = match type_name {
+        "STRING" => {
+            let latin1: Option<Vec<u8>> = value.chars().map(|c| u8::try_from(c).ok()).collect();
+            let bytes = latin1.ok_or("a STRING can only hold Latin-1 characters")?;
+            return Ok((8, bytes));
+        }
+        "UTF8_STRING" => return Ok((8, value.as_bytes().to_vec())),
+        "CARDINAL" => items()
+            .map(str::parse::<u32>)
+            .collect::<std::result::Result<_, _>>()?,
+        "INTEGER" => items()
+            .map(|item| item.parse::<i32>().map(|v| v as u32))
+            .collect::<std::result::Result<_, _>>()?,
+        "ATOM" => {
+            let mut values = Vec::new();
+            for name in items() {
+                values.push(atoms.intern(conn, name)?);
+            }
+            values
+        }
+        "WINDOW" => items()
+            .map(|item| super::parse_window(item).map(|window| window.0))
+            .collect::<std::result::Result<_, _>>()?,
+        _ => return Err(format!("cannot set a property of type {}", type_name).into()),
+    };
+    let bytes = values.iter().flat_map(|v| v.to_ne_bytes()).collect();
+    Ok((32, bytes))
+}
@@ -0,0 +1,103 @@
+//! `mousetrap record` and `replay`, input macros in the manner of xmacro.
+
+use super::Result;
+use clap::Args;
+use mousetrap::keysym;
+use mousetrap::recorder::{InputAction, InputLog, Recorder};
+use mousetrap::xtest::XTest;
+use std::fs;
+use std::path::PathBuf;
+
+#[derive(Debug, Args)]
+pub struct RecordArgs {
+    /// The file to save the macro to.
+    #[arg(short, long)]
+    output: PathBuf,
+
+    /// The key that ends the recording, as a keysym name. It is left out of
+    /// the macro.
+    #[arg(long, default_value = "Escape")]
+    stop_key: String,
+}
+
+#[derive(Debug, Args)]
+pub struct ReplayArgs {
+    /// The file of the macro.
+    input: PathBuf,
+
+    /// How much faster than recorded to replay, e.g. 2.0 for twice as fast.
+    #[arg(long, default_value_t = 1.0, value_parser = parse_speed)]
+    speed: f64,
+
+    /// Print the events instead of replaying them.
+    #[arg(long)]
+    dry_run: bool,
+}
+
+pub fn record(display: Option<&str>, args: RecordArgs) -> Result {
+    let mut conn = super::connect(display)?;
+    let keysym = keysym::from_name(&args.stop_key)
+        .ok_or_else(|| format!("unknown keysym {:?}", args.stop_key))?;
+    let (stop_keycode, _) = conn
+        .keysym_to_keycode(keysym)?
+        .ok_or_else(|| format!("no key of the keyboard is {}", args.stop_key))?;
+
+    let mut recorder = Recorder::start_with(&mut conn, super::connect(display)?)?;
+    eprintln!("Recording, press {} to stop.", args.stop_key);
+    while let Some(event) = recorder.next_event()? {
+        if event.action == InputAction::KeyPress(stop_keycode) {
+            break;
+        }
+    }
+    let mut log = recorder.stop(&mut conn)?;
+    // Leave out the stop key and what followed it while the recording ended.
+    if let Some(end) = log
+        .events
+        .iter()
+        .rposition(|event| event.action == InputAction::KeyPress(stop_keycode))
+    {
+        log.events.truncate(end);
+    }
+
+    fs::write(&args.output, log.to_bytes())?;
+    eprintln!(
+        "{} events over {:.1} s written to {}",
+        log.events.len(),
+        log.duration().as_secs_f64(),
+        args.output.display()
+    );
+    Ok(())
+}
+
+pub fn replay(display: Option<&str>, args: ReplayArgs) -> Result {
+    let log = InputLog::from_bytes(&fs::read(&args.input)?)?;
+    if args.dry_run {
+        for event in &log.events {
+            let time = f64::from(event.time) / args.speed;
+            println!("{:10.0} ms  {}", time, format_action(event.action));
+        }
+        return Ok(());
+    }
+    let mut conn = super::connect(display)?;
+    conn.enable::<XTest>()?;
+    log.replay(&mut conn, args.speed)?;
+    Ok(())
+}
+
+fn format_action(action: InputAction) -> String {
+    match action {
+        InputAction::KeyPress(keycode) => format!("key press {}", keycode),
+        InputAction::KeyRelease(keycode) => format!("key release {}", keycode),
+        InputAction::ButtonPress(button) => format!("button press {}", button),
+        InputAction::ButtonRelease(button) => format!("button release {}", button),
+        InputAction::Motion { x, y } => format!("motion {},{}", x, y),
+    }
+}
+
+/// Parses a speed, which must be positive.
+fn parse_speed(s: &str) -> std::result::Result<f64, String> {
+    match s.parse::<f64>() {
+        Ok(speed) if speed > 0.0 && speed.is_finite() => Ok(speed),
+        _ => Err(format!("expected a positive number, got {:?}", s)),
+    }
+}
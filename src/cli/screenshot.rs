@@ -0,0 +1,80 @@
+//! `mousetrap screenshot`, in the manner of scrot and maim.
+
+use super::Result;
+use clap::Args;
+use mousetrap::capture::{Capturer, Frame};
+use mousetrap::connection::Connection;
+use mousetrap::protocol::Window;
+use mousetrap::region;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::PathBuf;
+
+#[derive(Debug, Args)]
+pub struct ScreenshotArgs {
+    /// The PNG file to write, `-` for the standard output.
+    output: PathBuf,
+
+    /// Capture this window instead of the whole screen, e.g. 0x1a00003.
+    #[arg(long, value_parser = super::parse_window, group = "source")]
+    id: Option<Window>,
+
+    /// Capture the window the user clicks.
+    #[arg(long, group = "source")]
+    window: bool,
+
+    /// Capture the rectangle the user drags out.
+    #[arg(long, group = "source")]
+    select: bool,
+
+    /// Draw the cursor on the image, which requires XFixes.
+    #[arg(long)]
+    cursor: bool,
+}
+
+pub fn run(conn: &mut Connection, args: ScreenshotArgs) -> Result {
+    let mut capturer = Capturer::new(conn, args.cursor)?;
+    let frame = capture(conn, &mut capturer, &args);
+    capturer.close(conn)?;
+    let frame = frame?.ok_or("the area is off the screen")?;
+
+    if args.output.as_os_str() == "-" {
+        frame.write_png(io::stdout().lock())?;
+        return Ok(());
+    }
+    let mut file = BufWriter::new(File::create(&args.output)?);
+    frame.write_png(&mut file)?;
+    file.flush()?;
+    eprintln!(
+        "{}x{} written to {}",
+        frame.width(),
+        frame.height(),
+        args.output.display()
+    );
+    Ok(())
+}
+
+/// Returns the frame the options ask for, or `None` if it is off the screen.
+fn capture(
+    conn: &mut Connection,
+    capturer: &mut Capturer,
+    args: &ScreenshotArgs,
+) -> Result<Option<Frame>> {
+    if let Some(window) = args.id {
+        return Ok(capturer.capture_window(conn, window)?);
+    }
+    if args.window {
+        eprintln!("Click the window, or press Escape to cancel.");
+        let window = region::select_window(conn)?.ok_or("cancelled")?;
+        return Ok(capturer.capture_window(conn, window)?);
+    }
+    if args.select {
+        eprintln!("Drag out a rectangle, or press Escape to cancel.");
+        let area = region::select_region(conn)?.ok_or("cancelled")?;
+        if area.width == 0 || area.height == 0 {
+            return Err("the selection is empty".into());
+        }
+        return Ok(capturer.capture_area(conn, area)?);
+    }
+    Ok(Some(capturer.capture_screen(conn)?))
+}
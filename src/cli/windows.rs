@@ -0,0 +1,96 @@
+//! `mousetrap windows`, in the manner of `wmctrl -l` and `xwininfo -tree`.
+
+use super::Result;
+use clap::Args;
+use mousetrap::connection::Connection;
+use mousetrap::errors::ConnectionError;
+use mousetrap::protocol::Window;
+use mousetrap::reply::map_state;
+use mousetrap::window_info::WindowInfo;
+
+#[derive(Debug, Args)]
+pub struct WindowsArgs {
+    /// Only list the windows the window manager manages (_NET_CLIENT_LIST), in
+    /// the order it maps them.
+    #[arg(long)]
+    clients: bool,
+
+    /// Leave out unmapped windows and their children.
+    #[arg(long)]
+    mapped: bool,
+}
+
+pub fn run(conn: &mut Connection, args: WindowsArgs) -> Result {
+    if args.clients {
+        for window in conn.ewmh_client_list()? {
+            if let Some((info, state)) = describe(conn, window)? {
+                if !args.mapped || state != map_state::UNMAPPED {
+                    println!("{}", format_line(&info, state));
+                }
+            }
+        }
+        return Ok(());
+    }
+
+    // Depth-first from the root, each level in stacking order from the bottom.
+    let mut stack = vec![(conn.default_root(), 0)];
+    while let Some((window, depth)) = stack.pop() {
+        let Some((info, state)) = describe(conn, window)? else {
+            continue;
+        };
+        if args.mapped && state == map_state::UNMAPPED {
+            continue;
+        }
+        println!(
+            "{:width$}{}",
+            "",
+            format_line(&info, state),
+            width = depth * 2
+        );
+        let children = match conn.query_tree(window)?.reply(conn) {
+            Ok(reply) => reply.children,
+            Err(ConnectionError::ProtocolError(_)) => continue,
+            Err(err) => return Err(err.into()),
+        };
+        stack.extend(children.into_iter().rev().map(|child| (child, depth + 1)));
+    }
+    Ok(())
+}
+
+/// Returns the information and the map state of a window, or `None` if it was
+/// destroyed meanwhile.
+fn describe(
+    conn: &mut Connection,
+    window: Window,
+) -> std::result::Result<Option<(WindowInfo, u8)>, ConnectionError> {
+    let attributes = conn.get_window_attributes(window)?;
+    let info = WindowInfo::query(conn, window);
+    let attributes = attributes.reply(conn);
+    match (info, attributes) {
+        (Ok(info), Ok(attributes)) => Ok(Some((info, attributes.map_state))),
+        (Err(ConnectionError::ProtocolError(_)), _)
+        | (_, Err(ConnectionError::ProtocolError(_))) => Ok(None),
+        (Err(err), _) | (_, Err(err)) => Err(err),
+    }
+}
+
+/// Formats a window as its id, map state, geometry, class and title.
+fn format_line(info: &WindowInfo, state: u8) -> String {
+    let state = match state {
+        map_state::UNMAPPED => "Unmapped",
+        map_state::UNVIEWABLE => "Unviewable",
+        _ => "Viewable",
+    };
+    let geometry = info.geometry;
+    let class = info.class.as_ref().map_or("-".into(), |class| {
+        format!("{}.{}", class.instance, class.class)
+    });
+    let title = info
+        .title
+        .as_deref()
+        .map_or("(no title)".into(), |title| format!("{:?}", title));
+    format!(
+        "{:#010x}  {:<10}  {}x{}+{}+{}  {}  {}",
+        info.window.0, state, geometry.width, geometry.height, geometry.x, geometry.y, class, title
+    )
+}
@@ -0,0 +1,143 @@
+//! `mousetrap xauth`, in the manner of `xauth list`.
+
+use super::Result;
+use clap::{Args, Subcommand};
+use mousetrap::auth::{self, family, XAuthEntry};
+use std::env;
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+#[derive(Debug, Subcommand)]
+pub enum XauthCommand {
+    /// Print the entries of the .Xauthority file, marking with `*` the one used
+    /// to connect to the display.
+    List(ListArgs),
+    /// Connect to the display with the entry `list` marks, and report whether
+    /// the server accepts it.
+    Verify(ListArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct ListArgs {
+    /// Print the cookies instead of their length.
+    #[arg(long)]
+    show_secrets: bool,
+}
+
+pub fn run(display: Option<&str>, command: XauthCommand) -> Result {
+    let display_name = match display {
+        Some(display) => display.to_owned(),
+        None => env::var("DISPLAY").map_err(|_| "DISPLAY is not set")?,
+    };
+    let (host, display_number) = parse_display_name(&display_name)
+        .ok_or_else(|| format!("invalid display name {:?}", display_name))?;
+    let path = auth::get_xauth_filename().ok_or("neither XAUTHORITY nor HOME is set")?;
+    let entries = XAuthEntry::parse()?;
+    let chosen = auth::find_entry(&entries, host, display_number);
+
+    match command {
+        XauthCommand::List(args) => {
+            println!("{}: {} entries", path.display(), entries.len());
+            for (index, entry) in entries.iter().enumerate() {
+                let mark = if Some(index) == chosen { '*' } else { ' ' };
+                println!("{} {}", mark, format_entry(entry, args.show_secrets));
+            }
+            match chosen {
+                Some(_) => println!("* is used for {}", display_name),
+                None => println!("no entry matches {}", display_name),
+            }
+            Ok(())
+        }
+        XauthCommand::Verify(args) => {
+            match chosen {
+                Some(index) => println!(
+                    "using entry {}: {}",
+                    index,
+                    format_entry(&entries[index], args.show_secrets)
+                ),
+                None => println!("no entry matches {}, connecting without one", display_name),
+            }
+            let conn = super::connect(Some(&display_name))?;
+            let setup = conn.setup();
+            println!(
+                "{} accepted the connection: {} {}",
+                display_name, setup.vendor, setup.release_number
+            );
+            Ok(())
+        }
+    }
+}
+
+/// Returns the host, empty for a local display, and the display number of a
+/// display name such as `:0`, `:1.0` or `host:10`.
+fn parse_display_name(display_name: &str) -> Option<(&str, u8)> {
+    let (host, display) = display_name.rsplit_once(':')?;
+    let number = display.split('.').next()?;
+    Some((host, number.parse().ok()?))
+}
+
+/// Formats an entry as its family, address, display, protocol and cookie.
+fn format_entry(entry: &XAuthEntry, show_secrets: bool) -> String {
+    let data = &entry.authorization_protocol_data;
+    let cookie = if show_secrets {
+        hex(data)
+    } else {
+        format!("<{} bytes>", data.len())
+    };
+    format!(
+        "{:<9} {:<24} :{:<4} {:<20} {}",
+        family_name(entry.family),
+        format_address(entry),
+        String::from_utf8_lossy(&entry.display_number),
+        String::from_utf8_lossy(&entry.authorization_protocol_name),
+        cookie
+    )
+}
+
+fn family_name(value: u16) -> String {
+    match value {
+        family::INTERNET => "internet".into(),
+        family::DECNET => "decnet".into(),
+        family::CHAOS => "chaos".into(),
+        family::SERVER_INTERPRETED => "server".into(),
+        family::INTERNET6 => "internet6".into(),
+        family::LOCAL => "local".into(),
+        family::WILD => "wild".into(),
+        _ => format!("#{}", value),
+    }
+}
+
+fn format_address(entry: &XAuthEntry) -> String {
+    let address = &entry.address;
+    match entry.family {
+        family::INTERNET => match <[u8; 4]>::try_from(address.as_slice()) {
+            Ok(octets) => Ipv4Addr::from(octets).to_string(),
+            Err(_) => hex(address),
+        },
+        family::INTERNET6 => match <[u8; 16]>::try_from(address.as_slice()) {
+            Ok(octets) => Ipv6Addr::from(octets).to_string(),
+            Err(_) => hex(address),
+        },
+        family::WILD => "*".into(),
+        _ => String::from_utf8_lossy(address).into_owned(),
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_names() {
+        assert_eq!(parse_display_name(":0"), Some(("", 0)));
+        assert_eq!(parse_display_name(":1.0"), Some(("", 1)));
+        assert_eq!(parse_display_name("host:10"), Some(("host", 10)));
+        assert_eq!(parse_display_name("[::1]:2.1"), Some(("[::1]", 2)));
+        assert_eq!(parse_display_name("0"), None);
+        assert_eq!(parse_display_name(":x"), None);
+        assert_eq!(parse_display_name(":256"), None);
+    }
+}
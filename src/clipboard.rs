@@ -0,0 +1,634 @@
+//! Reading the selections, such as the clipboard, from their owners.
+//!
+//! The contents of a selection are not stored by the server: they are asked of
+//! the client owning it, converted to a target type such as UTF8_STRING or
+//! image/png. The owner writes them to a property of a hidden window of the
+//! [Clipboard], in chunks if they are large (the INCR protocol).
+//!
+//! A [Clipboard] can also own selections. It then answers the requests of other
+//! clients, passed to [Clipboard::handle_event], with the contents returned by
+//! a provider, until another client takes the selection over. Large contents
+//! are sent in chunks for as long as the requestor asks for them: transfers
+//! whose requestor is destroyed or stops asking for the timeout of [Clipboard::set_timeout] are
+//! dropped.
+//!
+//! ```no_run
+//! use mousetrap::clipboard::{Clipboard, Selection};
+//! use mousetrap::connection::Connection;
+//!
+//! let mut conn = Connection::init().unwrap();
+//! let clipboard = Clipboard::new(&mut conn).unwrap();
+//! println!("{:?}", clipboard.get_text(&mut conn, Selection::Clipboard).unwrap());
+//! if let Some(png) = clipboard.get(&mut conn, Selection::Clipboard, "image/png").unwrap() {
+//!     std::fs::write("/tmp/clipboard.png", png).unwrap();
+//! }
+//! ```
+//!
+//! ```no_run
+//! use mousetrap::clipboard::{Clipboard, Selection};
+//! use mousetrap::connection::Connection;
+//!
+//! let mut conn = Connection::init().unwrap();
+//! let mut clipboard = Clipboard::new(&mut conn).unwrap();
+//! clipboard.set_text(&mut conn, Selection::Clipboard, "copied").unwrap();
+//! while clipboard.is_serving() {
+//!     clipboard.serve_event(&mut conn).unwrap();
+//! }
+//! ```
+
+use crate::connection::Connection;
+use crate::errors::ConnectionError;
+use crate::event::{Event, SelectionNotifyEvent, SelectionRequestEvent};
+use crate::protocol::{atom, Atom, EventMask, Timestamp, Window, XId, CURRENT_TIME};
+use crate::request::{ConvertSelection, CreateWindow, PropMode, WindowAttributes, WindowClass};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// A selection to read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Selection {
+    /// The text selected last, pasted with the middle button.
+    Primary,
+    Secondary,
+    /// The clipboard of copy and paste.
+    Clipboard,
+}
+
+/// Atoms of the selection protocols.
+#[derive(Debug, Clone, Copy)]
+struct Atoms {
+    clipboard: Atom,
+    targets: Atom,
+    incr: Atom,
+    timestamp: Atom,
+    utf8_string: Atom,
+    /// The property of the hidden window the owners write to.
+    property: Atom,
+}
+
+/// The contents of a selection converted to a target.
+#[derive(Debug, Clone)]
+pub struct SelectionData {
+    /// The type the owner converted the selection to, usually the target.
+    pub type_: Atom,
+    /// Whether `data` holds 8, 16 or 32 bit values.
+    pub format: u8,
+    pub data: Vec<u8>,
+}
+
+/// Returns the contents of an owned selection converted to a target, or `None`
+/// if it cannot be converted to it.
+pub type Provider = Box<dyn FnMut(Atom) -> Option<SelectionData>>;
+
+/// A selection owned by the hidden window.
+struct Owned {
+    /// When the window became the owner. Older requests are refused.
+    time: Timestamp,
+    /// The targets the provider converts to, besides TARGETS and TIMESTAMP.
+    targets: Vec<Atom>,
+    provider: Provider,
+}
+
+/// Contents too large for one request, sent in chunks with the INCR protocol.
+struct Transfer {
+    data: SelectionData,
+    /// How much of the data was sent so far.
+    offset: usize,
+    /// When the transfer is dropped if the requestor has not asked for the
+    /// next chunk.
+    deadline: Instant,
+}
+
+/// Largest contents written to the property of a requestor at once. Larger ones
+/// are sent in chunks of this size, a multiple of 4 bytes so the chunks hold
+/// whole values of any format.
+const INCR_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Most bytes reserved up front for an incremental transfer. The size announced
+/// by the owner is only a hint, and another client's word.
+const INCR_PREALLOCATION_LIMIT: usize = 16 * 1024 * 1024;
+
+/// Reads the selections into a hidden window, and owns selections on behalf of
+/// the application.
+pub struct Clipboard {
+    window: Window,
+    atoms: Atoms,
+    timeout: Duration,
+    /// The selections owned, by atom.
+    owned: HashMap<Atom, Owned>,
+    /// INCR transfers in progress, by requestor and property.
+    transfers: HashMap<(Window, Atom), Transfer>,
+}
+
+impl Clipboard {
+    /// How long the owner of a selection has to answer, by default.
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(2);
+
+    /// Creates the hidden window the selections are converted into.
+    pub fn new(conn: &mut Connection) -> Result<Clipboard, ConnectionError> {
+        let window = conn.generate_id()?;
+        let request = CreateWindow {
+            depth: 0,
+            window,
+            parent: conn.default_root(),
+            x: -1,
+            y: -1,
+            width: 1,
+            height: 1,
+            border_width: 0,
+            class: WindowClass::INPUT_ONLY,
+            visual: 0,
+            attributes: WindowAttributes {
+                event_mask: Some(EventMask::PROPERTY_CHANGE),
+                ..Default::default()
+            },
+        };
+        conn.send_request(&request.serialize())?;
+        let names = [
+            "CLIPBOARD",
+            "TARGETS",
+            "INCR",
+            "TIMESTAMP",
+            "UTF8_STRING",
+            "_MOUSETRAP_SELECTION",
+        ];
+        let mut cookies = Vec::with_capacity(names.len());
+        for name in names {
+            cookies.push(conn.intern_atom(false, name)?);
+        }
+        let mut atoms = Vec::with_capacity(names.len());
+        for cookie in cookies {
+            atoms.push(cookie.reply(conn)?.atom);
+        }
+        let [clipboard, targets, incr, timestamp, utf8_string, property] = atoms[..] else {
+            unreachable!("one atom is interned per name");
+        };
+        Ok(Clipboard {
+            window,
+            atoms: Atoms {
+                clipboard,
+                targets,
+                incr,
+                timestamp,
+                utf8_string,
+                property,
+            },
+            timeout: Self::DEFAULT_TIMEOUT,
+            owned: HashMap::new(),
+            transfers: HashMap::new(),
+        })
+    }
+
+    /// Returns the hidden window.
+    pub fn window(&self) -> Window {
+        self.window
+    }
+
+    /// Sets how long the owner of a selection has to answer, and to send each
+    /// chunk of large contents. The requestors of contents served in chunks
+    /// have as long to ask for each of them.
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.timeout = timeout;
+    }
+
+    /// Returns the atom of a selection.
+    pub fn selection_atom(&self, selection: Selection) -> Atom {
+        match selection {
+            Selection::Primary => atom::PRIMARY,
+            Selection::Secondary => atom::SECONDARY,
+            Selection::Clipboard => self.atoms.clipboard,
+        }
+    }
+
+    /// Returns the contents of a selection converted to the target named `target`,
+    /// e.g. `text/plain` or `image/png`. Returns `None` if the selection has no
+    /// owner or the owner cannot convert it.
+    pub fn get(
+        &self,
+        conn: &mut Connection,
+        selection: Selection,
+        target: &str,
+    ) -> Result<Option<Vec<u8>>, ConnectionError> {
+        let target = conn.intern_atom(false, target)?.reply(conn)?.atom;
+        let data = self.convert(conn, self.selection_atom(selection), target)?;
+        Ok(data.map(|data| data.data))
+    }
+
+    /// Returns the contents of a selection as text, asking for UTF8_STRING and
+    /// falling back to Latin-1 STRING for old owners.
+    pub fn get_text(
+        &self,
+        conn: &mut Connection,
+        selection: Selection,
+    ) -> Result<Option<String>, ConnectionError> {
+        let selection = self.selection_atom(selection);
+        if let Some(data) = self.convert(conn, selection, self.atoms.utf8_string)? {
+            return Ok(Some(String::from_utf8_lossy(&data.data).into_owned()));
+        }
+        let data = self.convert(conn, selection, atom::STRING)?;
+        Ok(data.map(|data| data.data.iter().map(|&b| char::from(b)).collect()))
+    }
+
+    /// Returns the targets the owner of a selection can convert it to.
+    pub fn targets(
+        &self,
+        conn: &mut Connection,
+        selection: Selection,
+    ) -> Result<Vec<Atom>, ConnectionError> {
+        let selection = self.selection_atom(selection);
+        let Some(data) = self.convert(conn, selection, self.atoms.targets)? else {
+            return Ok(Vec::new());
+        };
+        if data.format != 32 {
+            return Ok(Vec::new());
+        }
+        let targets = data.data.chunks_exact(4);
+        Ok(targets
+            .map(|v| u32::from_ne_bytes([v[0], v[1], v[2], v[3]]))
+            .collect())
+    }
+
+    /// Asks the owner of `selection` to convert it to `target` and returns the
+    /// result, reassembled if the owner sent it in chunks. Returns `None` if the
+    /// selection has no owner or the owner refused, and [ConnectionError::Timeout]
+    /// if it did not answer in time.
+    pub fn convert(
+        &self,
+        conn: &mut Connection,
+        selection: Atom,
+        target: Atom,
+    ) -> Result<Option<SelectionData>, ConnectionError> {
+        let (window, property) = (self.window, self.atoms.property);
+        let request = ConvertSelection {
+            requestor: window,
+            selection,
+            target,
+            property,
+            time: CURRENT_TIME,
+        };
+        conn.send_request(&request.serialize())?;
+        let is_notify = |event: &Event| matches!(event, Event::SelectionNotify(e) if e.requestor == window && e.selection == selection);
+        let notify = conn.wait_for_event_matching_timeout(is_notify, self.timeout)?;
+        let Some(Event::SelectionNotify(notify)) = notify else {
+            return Err(ConnectionError::Timeout);
+        };
+        if notify.property == atom::NONE {
+            return Ok(None);
+        }
+        // The PropertyNotify events of the owner writing the property are already
+        // queued. Dropping them before the property is deleted leaves only the
+        // ones of the chunks.
+        let is_change = |event: &Event| matches!(event, Event::PropertyNotify(e) if e.window == window && e.atom == property);
+        while conn.poll_for_event_matching(is_change)?.is_some() {}
+
+        let reply = conn
+            .get_property(true, window, property, atom::ANY, 0, u32::MAX)?
+            .reply(conn)?;
+        if reply.type_ != self.atoms.incr {
+            return Ok(Some(SelectionData {
+                type_: reply.type_,
+                format: reply.format,
+                data: reply.value,
+            }));
+        }
+
+        // INCR: the value is a lower bound of the size. Deleting the property asks
+        // for the next chunk, and an empty chunk ends the transfer.
+        let size = reply
+            .value32()
+            .and_then(|v| v.first().copied())
+            .unwrap_or(0);
+        let mut data = SelectionData {
+            type_: atom::NONE,
+            format: 8,
+            data: Vec::with_capacity((size as usize).min(INCR_PREALLOCATION_LIMIT)),
+        };
+        loop {
+            let event = conn.wait_for_event_matching_timeout(is_change, self.timeout)?;
+            match event {
+                Some(Event::PropertyNotify(e)) if e.state == 0 => {}
+                Some(_) => continue,
+                None => return Err(ConnectionError::Timeout),
+            }
+            let chunk = conn
+                .get_property(true, window, property, atom::ANY, 0, u32::MAX)?
+                .reply(conn)?;
+            if chunk.value.is_empty() {
+                return Ok(Some(data));
+            }
+            data.type_ = chunk.type_;
+            data.format = chunk.format;
+            data.data.extend_from_slice(&chunk.value);
+        }
+    }
+
+    /// Makes the hidden window the owner of a selection. The requests of other
+    /// clients for one of `targets` are answered with the contents `provider`
+    /// returns. Returns whether the window became the owner.
+    pub fn set_owner<F>(
+        &mut self,
+        conn: &mut Connection,
+        selection: Selection,
+        targets: &[Atom],
+        provider: F,
+    ) -> Result<bool, ConnectionError>
+    where
+        F: FnMut(Atom) -> Option<SelectionData> + 'static,
+    {
+        let selection = self.selection_atom(selection);
+        // Requests made before the change of owner must be told apart.
+        let time = conn.current_server_time()?;
+        conn.set_selection_owner(self.window, selection, time)?;
+        let owner = conn.get_selection_owner(selection)?.reply(conn)?.owner;
+        if owner != self.window {
+            return Ok(false);
+        }
+        let owned = Owned {
+            time,
+            targets: targets.to_vec(),
+            provider: Box::new(provider),
+        };
+        self.owned.insert(selection, owned);
+        Ok(true)
+    }
+
+    /// Owns a selection holding `text`, served as UTF8_STRING and as Latin-1
+    /// STRING with the characters it cannot encode replaced by `?`.
+    pub fn set_text(
+        &mut self,
+        conn: &mut Connection,
+        selection: Selection,
+        text: &str,
+    ) -> Result<bool, ConnectionError> {
+        let utf8_string = self.atoms.utf8_string;
+        let text = text.to_owned();
+        let provider = move |target| {
+            let data = if target == utf8_string {
+                text.as_bytes().to_vec()
+            } else {
+                let latin1 = text.chars().map(|c| u8::try_from(c).unwrap_or(b'?'));
+                latin1.collect()
+            };
+            Some(SelectionData {
+                type_: target,
+                format: 8,
+                data,
+            })
+        };
+        self.set_owner(conn, selection, &[utf8_string, atom::STRING], provider)
+    }
+
+    /// Whether the hidden window owns a selection.
+    pub fn owns(&self, selection: Selection) -> bool {
+        self.owned.contains_key(&self.selection_atom(selection))
+    }
+
+    /// Whether a selection is owned or contents are still being sent, i.e.
+    /// whether events should still be passed to [Clipboard::handle_event].
+    pub fn is_serving(&self) -> bool {
+        !self.owned.is_empty() || !self.transfers.is_empty()
+    }
+
+    /// Gives up a selection owned by the hidden window.
+    pub fn release(
+        &mut self,
+        conn: &mut Connection,
+        selection: Selection,
+    ) -> Result<(), ConnectionError> {
+        let selection = self.selection_atom(selection);
+        if let Some(owned) = self.owned.remove(&selection) {
+            conn.set_selection_owner(XId(atom::NONE), selection, owned.time)?;
+        }
+        Ok(())
+    }
+
+    /// Waits for the next event and handles it, for applications doing nothing
+    /// else while they serve selections. The wait ends early when a transfer
+    /// times out. Returns the event if it was not about the owned selections.
+    pub fn serve_event(&mut self, conn: &mut Connection) -> Result<Option<Event>, ConnectionError> {
+        let deadline = self.transfers.values().map(|t| t.deadline).min();
+        let event = match deadline {
+            Some(deadline) => {
+                let left = deadline.saturating_duration_since(Instant::now());
+                conn.wait_for_event_matching_timeout(|_| true, left)?
+            }
+            None => Some(conn.wait_for_event()?),
+        };
+        match event {
+            Some(event) if !self.handle_event(conn, &event)? => Ok(Some(event)),
+            Some(_) => Ok(None),
+            None => {
+                self.drop_stalled_transfers(conn)?;
+                Ok(None)
+            }
+        }
+    }
+
+    /// Handles the events about the owned selections: answers the requests of
+    /// other clients, sends the next chunk of large contents, and forgets the
+    /// selections taken over by other clients and the transfers to destroyed
+    /// windows. Returns whether the event was one of them. Transfers whose
+    /// requestor stopped asking for chunks are dropped as well.
+    pub fn handle_event(
+        &mut self,
+        conn: &mut Connection,
+        event: &Event,
+    ) -> Result<bool, ConnectionError> {
+        self.drop_stalled_transfers(conn)?;
+        match event {
+            Event::SelectionRequest(request) if request.owner == self.window => {
+                self.answer(conn, request)?;
+                Ok(true)
+            }
+            Event::SelectionClear(clear) if clear.owner == self.window => {
+                self.owned.remove(&clear.selection);
+                Ok(true)
+            }
+            // The requestor deleting the property asks for the next chunk.
+            Event::PropertyNotify(e)
+                if e.state == 1 && self.transfers.contains_key(&(e.window, e.atom)) =>
+            {
+                self.send_chunk(conn, e.window, e.atom)?;
+                Ok(true)
+            }
+            Event::DestroyNotify(e)
+                if self.transfers.keys().any(|&(window, _)| window == e.window) =>
+            {
+                self.transfers.retain(|&(window, _), _| window != e.window);
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    /// Converts an owned selection for a requestor and notifies it.
+    fn answer(
+        &mut self,
+        conn: &mut Connection,
+        request: &SelectionRequestEvent,
+    ) -> Result<(), ConnectionError> {
+        // Obsolete clients leave the choice of the property to the owner.
+        let property = if request.property == atom::NONE {
+            request.target
+        } else {
+            request.property
+        };
+        let converted = match self.converted(request) {
+            Some(data) => {
+                self.write(conn, request.requestor, property, data)?;
+                property
+            }
+            None => atom::NONE,
+        };
+        let notify = SelectionNotifyEvent {
+            time: request.time,
+            requestor: request.requestor,
+            selection: request.selection,
+            target: request.target,
+            property: converted,
+        };
+        conn.send_event(
+            false,
+            request.requestor,
+            EventMask::NO_EVENT,
+            notify.serialize(),
+        )?;
+        Ok(())
+    }
+
+    /// Returns the contents of an owned selection converted to the target of
+    /// a request, or `None` if the request is refused.
+    fn converted(&mut self, request: &SelectionRequestEvent) -> Option<SelectionData> {
+        let owned = self.owned.get_mut(&request.selection)?;
+        if request.time != CURRENT_TIME && request.time < owned.time {
+            return None;
+        }
+        if request.target == self.atoms.targets {
+            let mut targets = vec![self.atoms.targets, self.atoms.timestamp];
+            targets.extend(&owned.targets);
+            Some(SelectionData {
+                type_: atom::ATOM,
+                format: 32,
+                data: targets.iter().flat_map(|t| t.to_ne_bytes()).collect(),
+            })
+        } else if request.target == self.atoms.timestamp {
+            Some(SelectionData {
+                type_: atom::INTEGER,
+                format: 32,
+                data: owned.time.to_ne_bytes().to_vec(),
+            })
+        } else if owned.targets.contains(&request.target) {
+            (owned.provider)(request.target)
+        } else {
+            None
+        }
+    }
+
+    /// Writes converted contents to the property of a requestor, starting an
+    /// INCR transfer if they are too large for one request.
+    fn write(
+        &mut self,
+        conn: &mut Connection,
+        requestor: Window,
+        property: Atom,
+        data: SelectionData,
+    ) -> Result<(), ConnectionError> {
+        if data.data.len() <= INCR_CHUNK_SIZE {
+            conn.change_property(
+                PropMode::REPLACE,
+                requestor,
+                property,
+                data.type_,
+                data.format,
+                &data.data,
+            )?;
+            return Ok(());
+        }
+        // The value of the INCR property is a lower bound of the size.
+        let size = u32::try_from(data.data.len()).unwrap_or(u32::MAX);
+        conn.select_input(
+            requestor,
+            EventMask::PROPERTY_CHANGE | EventMask::STRUCTURE_NOTIFY,
+        )?;
+        conn.change_property(
+            PropMode::REPLACE,
+            requestor,
+            property,
+            self.atoms.incr,
+            32,
+            &size.to_ne_bytes(),
+        )?;
+        let transfer = Transfer {
+            data,
+            offset: 0,
+            deadline: Instant::now() + self.timeout,
+        };
+        self.transfers.insert((requestor, property), transfer);
+        Ok(())
+    }
+
+    /// Writes the next chunk of an INCR transfer. The last one is empty.
+    fn send_chunk(
+        &mut self,
+        conn: &mut Connection,
+        requestor: Window,
+        property: Atom,
+    ) -> Result<(), ConnectionError> {
+        let Some(transfer) = self.transfers.get_mut(&(requestor, property)) else {
+            return Ok(());
+        };
+        let data = &transfer.data;
+        let end = data.data.len().min(transfer.offset + INCR_CHUNK_SIZE);
+        let chunk = &data.data[transfer.offset..end];
+        conn.change_property(
+            PropMode::REPLACE,
+            requestor,
+            property,
+            data.type_,
+            data.format,
+            chunk,
+        )?;
+        if chunk.is_empty() {
+            self.end_transfer(conn, requestor, property)?;
+        } else {
+            transfer.offset = end;
+            transfer.deadline = Instant::now() + self.timeout;
+        }
+        Ok(())
+    }
+
+    /// Drops the transfers whose requestor has not asked for the next chunk in
+    /// time, e.g. because it exited.
+    fn drop_stalled_transfers(&mut self, conn: &mut Connection) -> Result<(), ConnectionError> {
+        let now = Instant::now();
+        let stalled: Vec<_> = self
+            .transfers
+            .iter()
+            .filter(|(_, transfer)| transfer.deadline <= now)
+            .map(|(&key, _)| key)
+            .collect();
+        for (requestor, property) in stalled {
+            self.end_transfer(conn, requestor, property)?;
+        }
+        Ok(())
+    }
+
+    /// Forgets a transfer, and stops listening to the requestor once it has no
+    /// other transfers.
+    fn end_transfer(
+        &mut self,
+        conn: &mut Connection,
+        requestor: Window,
+        property: Atom,
+    ) -> Result<(), ConnectionError> {
+        self.transfers.remove(&(requestor, property));
+        if !self
+            .transfers
+            .keys()
+            .any(|&(window, _)| window == requestor)
+        {
+            conn.select_input(requestor, EventMask::NO_EVENT)?;
+        }
+        Ok(())
+    }
+}
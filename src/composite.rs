@@ -0,0 +1,271 @@
+//! The Composite extension: off-screen storage of window contents.
+//!
+//! Once a window is redirected, the server renders it into a pixmap instead of
+//! the screen, so its contents can be read even while it is obscured or
+//! unmapped. [Connection::composite_name_window_pixmap] gives a name to that
+//! pixmap, which can then be read with GetImage or [Connection::shm_get_image].
+//!
+//! Call [Connection::composite_query_version] before any other request.
+
+use crate::connection::Connection;
+use crate::cookie::{Cookie, Reply};
+use crate::errors::{ConnectionError, ParseError};
+use crate::extension::{Extension, Version};
+use crate::protocol::{Window, XId};
+use crate::request::RequestBuilder;
+use crate::utils::{check_trailing_bytes, deserialize_into, skip};
+
+/// Name of the extension, as given to QueryExtension.
+pub const NAME: &str = "Composite";
+
+/// The newest version of the extension this module implements.
+pub const VERSION: (u32, u32) = (0, 4);
+
+/// Minor opcodes of the Composite requests.
+pub mod opcode {
+    pub const QUERY_VERSION: u8 = 0;
+    pub const REDIRECT_WINDOW: u8 = 1;
+    pub const REDIRECT_SUBWINDOWS: u8 = 2;
+    pub const UNREDIRECT_WINDOW: u8 = 3;
+    pub const UNREDIRECT_SUBWINDOWS: u8 = 4;
+    pub const CREATE_REGION_FROM_BORDER_CLIP: u8 = 5;
+    pub const NAME_WINDOW_PIXMAP: u8 = 6;
+    pub const GET_OVERLAY_WINDOW: u8 = 7;
+    pub const RELEASE_OVERLAY_WINDOW: u8 = 8;
+}
+
+/// Names of the Composite requests, indexed by minor opcode.
+pub const REQUEST_NAMES: &[&str] = &[
+    "QueryVersion",
+    "RedirectWindow",
+    "RedirectSubwindows",
+    "UnredirectWindow",
+    "UnredirectSubwindows",
+    "CreateRegionFromBorderClip",
+    "NameWindowPixmap",
+    "GetOverlayWindow",
+    "ReleaseOverlayWindow",
+];
+
+/// The extension defines no errors.
+pub const ERROR_NAMES: &[&str] = &[];
+
+/// Who paints redirected windows on the screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Update {
+    /// The server keeps showing the window contents on the screen.
+    Automatic = 0,
+    /// The client, usually a compositing manager, does.
+    Manual = 1,
+}
+
+/// Starts a request of the extension.
+fn request(conn: &mut Connection, minor_opcode: u8) -> Result<RequestBuilder, ConnectionError> {
+    let info = conn.require_extension(NAME, REQUEST_NAMES, ERROR_NAMES)?;
+    Ok(RequestBuilder::new(info.major_opcode, minor_opcode))
+}
+
+/// The Composite extension, for [Connection::enable].
+pub struct Composite;
+
+impl Extension for Composite {
+    const NAME: &'static str = NAME;
+    const MIN_VERSION: Version = (0, 2);
+
+    fn negotiate(conn: &mut Connection) -> Result<Option<Version>, ConnectionError> {
+        let reply = conn
+            .composite_query_version(VERSION.0, VERSION.1)?
+            .reply(conn)?;
+        Ok(Some((reply.major_version, reply.minor_version)))
+    }
+}
+
+/// Reply of the CompositeQueryVersion request.
+#[derive(Debug, Clone)]
+pub struct QueryVersionReply {
+    pub major_version: u32,
+    pub minor_version: u32,
+}
+
+impl Reply for QueryVersionReply {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1                        unused
+        // 2  CARD16                sequence number
+        // 4  0                     reply length
+        // 4  CARD32                major-version
+        // 4  CARD32                minor-version
+        // 16                       unused
+        let rest = skip(bytes, 8)?;
+        let (major_version, rest) = deserialize_into::<u32>(rest)?;
+        let (minor_version, _) = deserialize_into::<u32>(rest)?;
+        Ok(QueryVersionReply {
+            major_version,
+            minor_version,
+        })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        check_trailing_bytes("composite::QueryVersionReply", bytes, 32)
+    }
+}
+
+/// Reply of the GetOverlayWindow request.
+#[derive(Debug, Clone)]
+pub struct GetOverlayWindowReply {
+    pub overlay_window: Window,
+}
+
+impl Reply for GetOverlayWindowReply {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1                        unused
+        // 2  CARD16                sequence number
+        // 4  0                     reply length
+        // 4  WINDOW                overlay-window
+        // 20                       unused
+        let rest = skip(bytes, 8)?;
+        let (overlay_window, _) = deserialize_into::<Window>(rest)?;
+        Ok(GetOverlayWindowReply { overlay_window })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        check_trailing_bytes("composite::GetOverlayWindowReply", bytes, 32)
+    }
+}
+
+impl Connection {
+    /// Tells the server the version of the extension the client supports and
+    /// returns the version both support. Call it before any other Composite request.
+    pub fn composite_query_version(
+        &mut self,
+        major_version: u32,
+        minor_version: u32,
+    ) -> Result<Cookie<QueryVersionReply>, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (0)
+        //   2 bytes    Request length (3)
+        //   4 bytes    Client major version
+        //   4 bytes    Client minor version
+        let request = request(self, opcode::QUERY_VERSION)?
+            .put(major_version)
+            .put(minor_version)
+            .finish();
+        self.send_request_with_reply(&request)
+    }
+
+    /// Redirects `window` and its children to off-screen storage.
+    ///
+    /// Only one client may redirect a window with [Update::Manual].
+    pub fn composite_redirect_window(
+        &mut self,
+        window: Window,
+        update: Update,
+    ) -> Result<u16, ConnectionError> {
+        self.composite_redirect(opcode::REDIRECT_WINDOW, window, update)
+    }
+
+    /// Redirects all current and future children of `window` to off-screen storage.
+    pub fn composite_redirect_subwindows(
+        &mut self,
+        window: Window,
+        update: Update,
+    ) -> Result<u16, ConnectionError> {
+        self.composite_redirect(opcode::REDIRECT_SUBWINDOWS, window, update)
+    }
+
+    /// Stops a redirection made by [Connection::composite_redirect_window].
+    pub fn composite_unredirect_window(
+        &mut self,
+        window: Window,
+        update: Update,
+    ) -> Result<u16, ConnectionError> {
+        self.composite_redirect(opcode::UNREDIRECT_WINDOW, window, update)
+    }
+
+    /// Stops a redirection made by [Connection::composite_redirect_subwindows].
+    pub fn composite_unredirect_subwindows(
+        &mut self,
+        window: Window,
+        update: Update,
+    ) -> Result<u16, ConnectionError> {
+        self.composite_redirect(opcode::UNREDIRECT_SUBWINDOWS, window, update)
+    }
+
+    //   1 byte     Major opcode
+    //   1 byte     Minor opcode
+    //   2 bytes    Request length (3)
+    //   4 bytes    Window
+    //   1 byte     Update
+    //   3 bytes    Unused
+    fn composite_redirect(
+        &mut self,
+        minor_opcode: u8,
+        window: Window,
+        update: Update,
+    ) -> Result<u16, ConnectionError> {
+        let request = request(self, minor_opcode)?
+            .put(window)
+            .put(update as u8)
+            .pad(3)
+            .finish();
+        self.send_request(&request)
+    }
+
+    /// Creates an XFIXES region from the border clip of a redirected window and
+    /// returns its id.
+    pub fn composite_create_region_from_border_clip(
+        &mut self,
+        window: Window,
+    ) -> Result<XId, ConnectionError> {
+        let region = self.generate_id()?;
+        let request = request(self, opcode::CREATE_REGION_FROM_BORDER_CLIP)?
+            .put(region)
+            .put(window)
+            .finish();
+        self.send_request(&request)?;
+        Ok(region)
+    }
+
+    /// Names the off-screen pixmap of a redirected window and returns its id.
+    ///
+    /// The pixmap keeps the contents at the time of the request: name it again
+    /// after the window is resized or remapped. Free it with [Connection::free_pixmap].
+    pub fn composite_name_window_pixmap(&mut self, window: Window) -> Result<XId, ConnectionError> {
+        let pixmap = self.generate_id()?;
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (6)
+        //   2 bytes    Request length (3)
+        //   4 bytes    Window
+        //   4 bytes    Pixmap
+        let request = request(self, opcode::NAME_WINDOW_PIXMAP)?
+            .put(window)
+            .put(pixmap)
+            .finish();
+        self.send_request(&request)?;
+        Ok(pixmap)
+    }
+
+    /// Returns the overlay window of the screen of `window`, which lies above all
+    /// windows but below the screen saver.
+    pub fn composite_get_overlay_window(
+        &mut self,
+        window: Window,
+    ) -> Result<Cookie<GetOverlayWindowReply>, ConnectionError> {
+        let request = request(self, opcode::GET_OVERLAY_WINDOW)?
+            .put(window)
+            .finish();
+        self.send_request_with_reply(&request)
+    }
+
+    /// Releases the overlay window got with [Connection::composite_get_overlay_window].
+    pub fn composite_release_overlay_window(
+        &mut self,
+        window: Window,
+    ) -> Result<u16, ConnectionError> {
+        let request = request(self, opcode::RELEASE_OVERLAY_WINDOW)?
+            .put(window)
+            .finish();
+        self.send_request(&request)
+    }
+}
@@ -1,59 +1,205 @@
-use super::auth::XAuthEntry;
+use super::auth::{self, XAuthEntry};
+use crate::bigreq;
 use crate::byteorder::BYTE_ORDER;
-use crate::errors::{ConnectionError, ParseError};
+use crate::cookie::{Cookie, ParseMode, Reply};
+use crate::errors::{ConnectAttempt, ConnectionError, ParseError, SerializeError, XError};
+use crate::event::{self, ClientMessageEvent, Event, EVENT_SIZE};
+use crate::ewmh;
+use crate::extension::{Extension, Version};
+use crate::icccm;
+use crate::keyboard::{KeyboardMapping, ModifierMapping, ModifierMasks, Modifiers, PointerMapping};
 use crate::protocol::{
-    ConnSetup, ConnSetupRequest, Format, PROTOCOL_MAJOR_VERSION, PROTOCOL_MINOR_VERSION,
+    atom, Atom, BitOrder, ColorMap, ConnFailed, ConnSetup, ConnSetupRequest, EventMask, Format,
+    KeyCode, Screen, Timestamp, VisualId, Window, XId, PROTOCOL_MAJOR_VERSION,
+    PROTOCOL_MINOR_VERSION,
 };
+use crate::protocol_state::ProtocolState;
+use crate::reply::{
+    map_state, AtomNameReply, GeometryReply, GetImageReply, GetPropertyReply, GrabReply,
+    InputFocusReply, InternAtomReply, ListExtensionsReply, ListPropertiesReply,
+    QueryExtensionReply, QueryPointerReply, QueryTreeReply, SelectionOwnerReply,
+    TranslateCoordinatesReply, WindowAttributesReply,
+};
+use crate::request::{
+    AllowEvents, AllowMode, ChangeProperty, ChangeWindowAttributes, ClearArea, CloseDownMode,
+    CloseFont, ColormapAlloc, CreateColormap, CreateGc, CreateGlyphCursor, CreatePixmap,
+    CreateWindow, DeleteProperty, DestroyWindow, FreeColormap, FreeCursor, FreeGc, FreePixmap,
+    GcValues, GetAtomName, GetGeometry, GetImage, GetInputFocus, GetKeyboardMapping,
+    GetModifierMapping, GetPointerMapping, GetProperty, GetSelectionOwner, GetWindowAttributes,
+    GrabButton, GrabKeyboard, GrabPointer, InternAtom, KillClient, ListExtensions, ListProperties,
+    MapWindow, OpenFont, PropMode, PutImage, QueryExtension, QueryPointer, QueryTree, SendEvent,
+    SetCloseDownMode, SetSelectionOwner, TranslateCoordinates, UngrabButton, UngrabKeyboard,
+    UngrabPointer, UnmapWindow, WindowAttributes, WindowClass,
+};
+#[cfg(feature = "trace")]
+use crate::trace::Trace;
+use crate::transport::Transport;
 use crate::utils::{
-    deserialize_into, deserialize_into_string, deserialize_into_vec, trim_by_padding,
+    deserialize_into, deserialize_into_string, deserialize_into_variable_vec, deserialize_into_vec,
+    skip, trim_by_padding,
 };
-use std::io::{self, Error, Write};
-use std::net::TcpStream;
+use crate::waker::{self, Waker};
+use std::collections::{HashMap, VecDeque};
+use std::env;
+use std::io::{self, Write};
+use std::net::{Shutdown, TcpStream};
+use std::os::fd::{AsFd, AsRawFd, BorrowedFd, FromRawFd, OwnedFd, RawFd};
 use std::os::unix::net::UnixStream;
-use std::time::Duration;
-use std::{env, process};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 /// Stream is a wrapper for the `UnixStream` and `TcpStream`.
 pub struct Stream {
     variants: StreamVariants,
     /// Indicates whether the Tcp or Socket connection is open.
     open: bool,
+    /// File descriptors received along with the bytes read so far, oldest first.
+    received_fds: VecDeque<OwnedFd>,
 }
 
 /// Variants of Stream
 pub enum StreamVariants {
     Tcp(TcpStream),
     Unix(UnixStream),
+    Transport(Box<dyn Transport>),
+}
+
+/// Longest message accepted from the server, in bytes. A reply or generic event
+/// announcing more is taken as a corrupted stream rather than buffered: its length
+/// field allows up to 16 GiB.
+pub const MAX_MESSAGE_LENGTH: usize = 1 << 30;
+
+/// Most file descriptors accepted along with a single read.
+const MAX_FDS_PER_READ: usize = 16;
+
+/// Reads from a unix socket, collecting the file descriptors passed with the
+/// bytes (SCM_RIGHTS ancillary data) into `fds`.
+fn recv_with_fds(
+    stream: &UnixStream,
+    buf: &mut [u8],
+    fds: &mut VecDeque<OwnedFd>,
+) -> io::Result<usize> {
+    let mut iov = libc::iovec {
+        iov_base: buf.as_mut_ptr().cast(),
+        iov_len: buf.len(),
+    };
+    // u64 elements keep the control buffer aligned for cmsghdr.
+    let space = unsafe { libc::CMSG_SPACE((MAX_FDS_PER_READ * size_of::<RawFd>()) as u32) };
+    let mut control = vec![0u64; (space as usize).div_ceil(8)];
+    let mut msg: libc::msghdr = unsafe { std::mem::zeroed() };
+    msg.msg_iov = &mut iov;
+    msg.msg_iovlen = 1;
+    msg.msg_control = control.as_mut_ptr().cast();
+    msg.msg_controllen = space as _;
+
+    let n = unsafe { libc::recvmsg(stream.as_raw_fd(), &mut msg, libc::MSG_CMSG_CLOEXEC) };
+    if n < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    let mut cmsg = unsafe { libc::CMSG_FIRSTHDR(&msg) };
+    while !cmsg.is_null() {
+        let header = unsafe { &*cmsg };
+        if header.cmsg_level == libc::SOL_SOCKET && header.cmsg_type == libc::SCM_RIGHTS {
+            let data = unsafe { libc::CMSG_DATA(cmsg) }.cast::<RawFd>();
+            let length = header.cmsg_len as usize - unsafe { libc::CMSG_LEN(0) } as usize;
+            for i in 0..length / size_of::<RawFd>() {
+                let fd = unsafe { data.add(i).read_unaligned() };
+                fds.push_back(unsafe { OwnedFd::from_raw_fd(fd) });
+            }
+        }
+        cmsg = unsafe { libc::CMSG_NXTHDR(&msg, cmsg) };
+    }
+    Ok(n as usize)
+}
+
+/// Writes to a unix socket, passing `fds` as SCM_RIGHTS ancillary data.
+fn send_with_fds(stream: &UnixStream, data: &[u8], fds: &[BorrowedFd<'_>]) -> io::Result<usize> {
+    let mut iov = libc::iovec {
+        iov_base: data.as_ptr() as *mut _,
+        iov_len: data.len(),
+    };
+    let fds_length = (fds.len() * size_of::<RawFd>()) as u32;
+    let space = unsafe { libc::CMSG_SPACE(fds_length) };
+    let mut control = vec![0u64; (space as usize).div_ceil(8)];
+    let mut msg: libc::msghdr = unsafe { std::mem::zeroed() };
+    msg.msg_iov = &mut iov;
+    msg.msg_iovlen = 1;
+    msg.msg_control = control.as_mut_ptr().cast();
+    msg.msg_controllen = space as _;
+
+    unsafe {
+        let cmsg = libc::CMSG_FIRSTHDR(&msg);
+        (*cmsg).cmsg_level = libc::SOL_SOCKET;
+        (*cmsg).cmsg_type = libc::SCM_RIGHTS;
+        (*cmsg).cmsg_len = libc::CMSG_LEN(fds_length) as _;
+        let target = libc::CMSG_DATA(cmsg).cast::<RawFd>();
+        for (i, fd) in fds.iter().enumerate() {
+            target.add(i).write_unaligned(fd.as_raw_fd());
+        }
+    }
+
+    let n = unsafe { libc::sendmsg(stream.as_raw_fd(), &msg, 0) };
+    if n < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(n as usize)
 }
 
 pub struct Connection {
     stream: Stream,
+    /// Sequence numbers, responses, events and the names of requests and errors.
+    state: ProtocolState,
+    /// Versions negotiated by [Connection::enable] by extension name, `None` if the
+    /// server does not support the extension or a recent enough version of it.
+    versions: HashMap<&'static str, Option<Version>>,
+    /// The EWMH atoms, once interned by [Connection::ewmh_atoms].
+    ewmh_atoms: Option<ewmh::Atoms>,
+    /// The ICCCM atoms, once interned by [Connection::icccm_atoms].
+    icccm_atoms: Option<icccm::Atoms>,
+    /// Hidden window used to obtain the current server time, and the property
+    /// changed on it.
+    time_window: Option<(Window, Atom)>,
+    /// Read and write ends of the pipe of [Connection::create_waker], once created.
+    wakeup: Option<(OwnedFd, Arc<OwnedFd>)>,
 }
 
-pub struct ConnSetupResponse {
-    buffer: Vec<u8>,
+/// The codes the server assigned to an extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExtensionInfo {
+    /// The major opcode of the requests of the extension.
+    pub major_opcode: u8,
+    /// The code of the first event of the extension.
+    pub first_event: u8,
+    /// The code of the first error of the extension.
+    pub first_error: u8,
 }
 
 // Basic config variables for the x11 connection.
 #[derive(Debug)]
-struct XConf {
-    display_number: u8,
-    host: String,
-    port: u16,
-    socket_path: String,
+pub(crate) struct XConf {
+    pub(crate) display_name: String,
+    pub(crate) display_number: u8,
+    pub(crate) screen_number: usize,
+    pub(crate) host: String,
+    pub(crate) port: u16,
+    pub(crate) socket_path: String,
 }
 
-fn parse_conf(display_name: String) -> XConf {
+pub(crate) fn parse_conf(display_name: String) -> XConf {
     let mut conf = XConf {
+        display_name: display_name.clone(),
         display_number: 0,
+        screen_number: 0,
         host: String::from("127.0.0.1"),
         port: 6000,
         socket_path: String::from("/tmp/.X11-unix/X"),
     };
 
-    // Display number
-    let display_number = display_name.trim_start_matches(":");
+    // Display number and screen number: ":<display>.<screen>"
+    let display = display_name.trim_start_matches(":");
+    let (display_number, screen_number) = display.split_once('.').unwrap_or((display, "0"));
     conf.display_number = display_number.parse::<u8>().unwrap_or(0);
+    conf.screen_number = screen_number.parse::<usize>().unwrap_or(0);
 
     // Port
     conf.port += conf.display_number as u16;
@@ -75,6 +221,17 @@ impl ConnSetupRequest {
         }
     }
 
+    /// Creates the setup request with the .Xauthority entry for a local display,
+    /// see [auth::find_entry], or without authorization if there is none.
+    pub fn from_xauthority(display_number: u8) -> Self {
+        let mut entries = XAuthEntry::parse().unwrap_or_default();
+        let entry = match auth::find_entry(&entries, "", display_number) {
+            Some(index) => entries.swap_remove(index),
+            None => XAuthEntry::default(),
+        };
+        Self::new(entry)
+    }
+
     /// Converts an instance of `ConnSetupRequest` to x11 raw bytes
     //
     //  The connection setup request payload should be as follows:
@@ -91,7 +248,21 @@ impl ConnSetupRequest {
     //   Q bytes    Unused (Q = pad(D): To align the authorization protocol data to a 4-byte boundary)
     //
     //  TODO: think about rewriting the function to reduce code duplication.
-    pub fn serialize(&self) -> Vec<u8> {
+    pub fn serialize(&self) -> Result<Vec<u8>, SerializeError> {
+        let name_length = u16::try_from(self.authorization_protocol_name.len()).map_err(|_| {
+            SerializeError::TooLong {
+                field: "authorization protocol name",
+                length: self.authorization_protocol_name.len(),
+                max: u16::MAX.into(),
+            }
+        })?;
+        let data_length = u16::try_from(self.authorization_protocol_data.len()).map_err(|_| {
+            SerializeError::TooLong {
+                field: "authorization protocol data",
+                length: self.authorization_protocol_data.len(),
+                max: u16::MAX.into(),
+            }
+        })?;
         let mut payload: Vec<u8> = Vec::new();
 
         // Byte Order: 1 byte
@@ -107,18 +278,10 @@ impl ConnSetupRequest {
         payload.extend_from_slice(&self.protocol_minor_version.to_ne_bytes());
 
         // Authorization protocol name length: 2 bytes
-        payload.extend_from_slice(
-            &u16::try_from(self.authorization_protocol_name.len())
-                .unwrap()
-                .to_ne_bytes(),
-        );
+        payload.extend_from_slice(&name_length.to_ne_bytes());
 
         // Authorization protocol data length: 2 bytes
-        payload.extend_from_slice(
-            &u16::try_from(self.authorization_protocol_data.len())
-                .unwrap()
-                .to_ne_bytes(),
-        );
+        payload.extend_from_slice(&data_length.to_ne_bytes());
 
         // Padding: 2 bytes (For alignment. Unused)
         payload.extend_from_slice(&[0; 2]);
@@ -134,27 +297,57 @@ impl ConnSetupRequest {
 
         // Padding: (To align the authorization protocol name to a 4-byte boundary. Unused)
         payload.extend_from_slice(&[0; 3][..(4 - (payload.len() % 4)) % 4]);
-        payload
+        Ok(payload)
     }
 }
 
 impl ConnSetup {
+    /// Returns the length of the server's response to the connection setup, from
+    /// its first 8 bytes.
+    //
+    //  Every response starts with 8 bytes, the last two of which hold the
+    //  length in 4-byte units of the additional data that follows.
+    pub fn length(header: &[u8]) -> Result<usize, ParseError> {
+        let rest = skip(header, 6)?;
+        let (additional_data_length, _) = deserialize_into::<u16>(rest)?;
+        Ok(8 + usize::from(additional_data_length) * 4)
+    }
+
     pub fn parse_into(bytes: &[u8]) -> Result<ConnSetup, ConnectionError> {
-        match bytes.get(0) {
+        match bytes.first() {
             Some(0) => {
                 // Connection failed
-                // TODO: parse to ConnFailed
-                Err(ConnectionError::ConnectionRefused)
+                let failed = ConnFailed::from_bytes(bytes).map_err(|err| err.in_message(bytes))?;
+                Err(ConnectionError::ConnectionRefused {
+                    reason: failed.reason,
+                })
             }
             Some(1) => {
                 // Connection established
-                // TODO: instead of unwrap, use ?
-                Ok(Self::from_bytes(bytes).map_err(|e| ConnectionError::from(e))?)
+                let setup = Self::from_bytes(bytes).map_err(|err| err.in_message(bytes))?;
+                // Every client needs a default screen.
+                if setup.roots.is_empty() {
+                    return Err(ConnectionError::InvalidResponseFromServer);
+                }
+                Ok(setup)
             }
             Some(2) => {
                 // Further authentication required
-                // TODO: parse to AuthRequired
-                Err(ConnectionError::FurtherAuthenticationRequired)
+                //   1 byte     2 (Authenticate)
+                //   5 bytes    Unused
+                //   2 bytes    Length in 4-byte units of the reason
+                //   n bytes    Reason (padded)
+                let rest = skip(bytes, 6)?;
+                let (length, rest) = deserialize_into::<u16>(rest)?;
+                let (reason, _) = deserialize_into_string(rest, length.saturating_mul(4))?;
+                // The reason is padded with zeros to a 4-byte boundary.
+                let mut reason = reason.into_bytes();
+                while reason.last() == Some(&0) {
+                    reason.pop();
+                }
+                Err(ConnectionError::FurtherAuthenticationRequired {
+                    reason: reason.into(),
+                })
             }
             _ => Err(ConnectionError::InvalidResponseFromServer),
         }
@@ -164,12 +357,12 @@ impl ConnSetup {
         let (success, rest) = deserialize_into::<u8>(bytes)?;
 
         // Trim the unused 1 byte
-        let rest = &rest[1..];
+        let rest = skip(rest, 1)?;
         let (protocol_major_version, rest) = deserialize_into::<u16>(rest)?;
         let (protocol_minor_version, rest) = deserialize_into::<u16>(rest)?;
 
         // 8+2n+(v+p+m)/4 : length in 4-byte units of "additional data"
-        let (ln, rest) = deserialize_into::<u16>(rest)?;
+        let (_additional_data_length, rest) = deserialize_into::<u16>(rest)?;
         let (release_number, rest) = deserialize_into::<u32>(rest)?;
         let (resource_id_base, rest) = deserialize_into::<u32>(rest)?;
         let (resource_id_mask, rest) = deserialize_into::<u32>(rest)?;
@@ -186,19 +379,57 @@ impl ConnSetup {
         let (max_keycode, rest) = deserialize_into::<u8>(rest)?;
 
         // Trim the unused 4 bytes
-        let rest: &[u8] = &rest[4..];
+        let rest = skip(rest, 4)?;
         let (vendor, rest) = deserialize_into_string(rest, vendor_length)?;
-        // let number_of_formats = 100000 as usize;
         // Trim the padding of vendor. p=pad(vendor)
-        let rest = trim_by_padding(rest, vendor_length, 4);
+        let rest = trim_by_padding(rest, vendor_length, 4)?;
         let (pixmap_formats, rest) =
             deserialize_into_vec::<Format>(rest, number_of_formats.into())?;
+        let (roots, _) = deserialize_into_variable_vec::<Screen>(rest, number_of_screens.into())?;
 
-        // println!("pf: {:?}", pixmap_formats);
-
-        
+        Ok(ConnSetup {
+            success,
+            protocol_major_version,
+            protocol_minor_version,
+            vendor,
+            release_number,
+            resource_id_base,
+            resource_id_mask,
+            image_byte_order,
+            bitmap_scanline_unit,
+            bitmap_scanline_pad,
+            bitmap_bit_order: BitOrder::from(bitmap_bit_order),
+            pixmap_formats,
+            roots,
+            motion_buffer_size,
+            maximum_request_length,
+            min_keycode,
+            max_keycode,
+        })
+    }
+}
 
-        todo!()
+impl ConnFailed {
+    //   1 byte     0 (Failed)
+    //   1 byte     Length of the reason in bytes (n)
+    //   2 bytes    Protocol major version
+    //   2 bytes    Protocol minor version
+    //   2 bytes    Length in 4-byte units of additional data
+    //   n bytes    Reason
+    //   p bytes    Unused (p = pad(n))
+    pub fn from_bytes(bytes: &[u8]) -> Result<ConnFailed, ParseError> {
+        let (status, rest) = deserialize_into::<u8>(bytes)?;
+        let (reason_length, rest) = deserialize_into::<u8>(rest)?;
+        let (protocol_major_version, rest) = deserialize_into::<u16>(rest)?;
+        let (protocol_minor_version, rest) = deserialize_into::<u16>(rest)?;
+        let rest = skip(rest, 2)?;
+        let (reason, _) = deserialize_into_string(rest, reason_length.into())?;
+        Ok(ConnFailed {
+            status,
+            protocol_major_version,
+            protocol_minor_version,
+            reason,
+        })
     }
 }
 
@@ -208,56 +439,78 @@ impl Stream {
     /// Typically TCP connections are used for connecting to remote X11 server.
     /// So that we will first attempt to connect through Unix sockets.
     /// If that is unsuccessful, connect via TCP.
-    fn open(display_name: String) -> Result<Self, Error> {
+    pub(crate) fn open(conf: &XConf) -> Result<Self, ConnectionError> {
         let XConf {
+            display_name,
             socket_path,
             port,
             host,
             ..
-        } = &parse_conf(display_name);
+        } = conf;
+        let mut attempts = Vec::new();
 
         // TODO: connect using abstract unix socket first
-        let stream = match Self::connect_unix_socket(socket_path) {
-            Ok(stream) if stream.open => stream,
-            _ => Self::connect_tcp(host, port)?,
-        };
-        Ok(stream)
+        match Self::connect_unix_socket(socket_path) {
+            Ok(stream) if stream.open => {
+                log::debug!("connected to display {} at {}", display_name, socket_path);
+                return Ok(stream);
+            }
+            Ok(_) => {}
+            Err(error) => {
+                log::debug!("cannot connect to {}: {}", socket_path, error);
+                attempts.push(ConnectAttempt {
+                    address: socket_path.clone(),
+                    error,
+                })
+            }
+        }
+        match Self::connect_tcp(host, port) {
+            Ok(stream) => {
+                log::debug!("connected to display {} at {}:{}", display_name, host, port);
+                Ok(stream)
+            }
+            Err(error) => {
+                log::debug!("cannot connect to {}:{}: {}", host, port, error);
+                attempts.push(ConnectAttempt {
+                    address: format!("{}:{}", host, port),
+                    error,
+                });
+                Err(ConnectionError::Connect {
+                    display: display_name.clone(),
+                    attempts,
+                })
+            }
+        }
     }
 
     /// Connects to the X11 server using socket path
-    fn connect_unix_socket(socket_path: &str) -> std::io::Result<Stream> {
-        match UnixStream::connect(socket_path) {
-            Ok(stream) => Ok(Stream {
-                variants: StreamVariants::Unix(stream),
-                open: true,
-            }),
-            Err(err) => {
-                println!("Could not establish socket connection: {}", err);
-                Err(std::io::Error::new(
-                    std::io::ErrorKind::Other,
-                    "Could not establish socket connection",
-                ))
-            }
-        }
+    pub(crate) fn connect_unix_socket(socket_path: &str) -> std::io::Result<Stream> {
+        let stream = UnixStream::connect(socket_path)?;
+        Ok(Stream {
+            variants: StreamVariants::Unix(stream),
+            open: true,
+            received_fds: VecDeque::new(),
+        })
     }
 
     /// Connect to the X11  server via Tcp
     fn connect_tcp(host: &str, port: &u16) -> std::io::Result<Stream> {
         let addr: String = format!("{}:{}", host, port);
-        println!("addr: {}", addr);
-        match TcpStream::connect(addr) {
-            Ok(stream) => Ok(Stream {
-                variants: StreamVariants::Tcp(stream),
-                open: true,
-            }),
-            _ => {
-                // eprintln!("Could not establish connection: {}", e);
-                // process::exit(0);
-                Err(std::io::Error::new(
-                    std::io::ErrorKind::Other,
-                    "Could not establish socket connection",
-                ))
-            }
+        let stream = TcpStream::connect(addr)?;
+        Ok(Stream {
+            variants: StreamVariants::Tcp(stream),
+            open: true,
+            received_fds: VecDeque::new(),
+        })
+    }
+
+    /// Wraps a transport other than a socket, e.g. to replay a recording. See
+    /// [crate::transport].
+    pub fn from_transport<T: Transport + 'static>(transport: T) -> Stream {
+        Stream {
+            variants: StreamVariants::Transport(Box::new(transport)),
+            open: true,
+            received_fds: VecDeque::new(),
         }
     }
 
@@ -272,6 +525,7 @@ impl Stream {
         match self.variants {
             StreamVariants::Tcp(ref mut stream) => stream.write(data),
             StreamVariants::Unix(ref mut stream) => stream.write(data),
+            StreamVariants::Transport(ref mut transport) => transport.write(data),
         }
     }
 
@@ -286,7 +540,78 @@ impl Stream {
         use std::io::Read;
         match self.variants {
             StreamVariants::Tcp(ref mut stream) => stream.read(buf),
-            StreamVariants::Unix(ref mut stream) => stream.read(buf),
+            StreamVariants::Transport(ref mut transport) => transport.read(buf),
+            StreamVariants::Unix(ref mut stream) => {
+                recv_with_fds(stream, buf, &mut self.received_fds)
+            }
+        }
+    }
+
+    /// Writes to the stream, passing `fds` along with the first byte written.
+    ///
+    /// File descriptors can only be passed over a unix socket.
+    pub fn write_with_fds(&mut self, data: &[u8], fds: &[BorrowedFd<'_>]) -> io::Result<usize> {
+        if fds.is_empty() {
+            return self.write(data);
+        }
+        match self.variants {
+            StreamVariants::Unix(ref mut stream) if self.open => send_with_fds(stream, data, fds),
+            StreamVariants::Unix(_) => Err(io::Error::new(
+                io::ErrorKind::NotConnected,
+                "Connection not established yet",
+            )),
+            StreamVariants::Transport(ref mut transport) if self.open => {
+                transport.write_with_fds(data, fds)
+            }
+            StreamVariants::Transport(_) => Err(io::Error::new(
+                io::ErrorKind::NotConnected,
+                "Connection not established yet",
+            )),
+            StreamVariants::Tcp(_) => Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "File descriptors can only be passed over a unix socket",
+            )),
+        }
+    }
+
+    /// Removes and returns the `n` oldest received file descriptors, fewer if
+    /// less were received.
+    pub fn take_fds(&mut self, n: usize) -> Vec<OwnedFd> {
+        if let StreamVariants::Transport(ref mut transport) = self.variants {
+            return transport.take_fds(n);
+        }
+        let n = n.min(self.received_fds.len());
+        self.received_fds.drain(..n).collect()
+    }
+
+    /// Creates a second handle to the same socket, e.g. to read from it in one
+    /// thread while writing to it in another.
+    pub fn try_clone(&self) -> io::Result<Stream> {
+        let variants = match self.variants {
+            StreamVariants::Tcp(ref stream) => StreamVariants::Tcp(stream.try_clone()?),
+            StreamVariants::Unix(ref stream) => StreamVariants::Unix(stream.try_clone()?),
+            StreamVariants::Transport(_) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    "A transport cannot be cloned",
+                ))
+            }
+        };
+        Ok(Stream {
+            variants,
+            open: self.open,
+            received_fds: VecDeque::new(),
+        })
+    }
+
+    /// Shuts down the reading, writing or both halves of the socket, which also
+    /// ends the reads of other handles to it.
+    pub fn shutdown(&self, how: Shutdown) -> io::Result<()> {
+        match self.variants {
+            StreamVariants::Tcp(ref stream) => stream.shutdown(how),
+            StreamVariants::Unix(ref stream) => stream.shutdown(how),
+            // Dropping the transport closes it.
+            StreamVariants::Transport(_) => Ok(()),
         }
     }
 
@@ -295,96 +620,1663 @@ impl Stream {
         match self.variants {
             StreamVariants::Tcp(ref mut stream) => stream.set_nonblocking(non_blocking),
             StreamVariants::Unix(ref mut stream) => stream.set_nonblocking(non_blocking),
+            StreamVariants::Transport(ref mut transport) => transport.set_nonblocking(non_blocking),
         }
     }
 
-    /// Authenticate connection
-    pub fn authenticate(&mut self) {
-        let xauth_entries = XAuthEntry::parse().unwrap();
+    /// Waits until the stream can be read from, at most `timeout`. Returns false
+    /// if it timed out.
+    pub fn wait_readable(&self, timeout: Duration) -> io::Result<bool> {
+        let mut pollfd = libc::pollfd {
+            fd: self.as_raw_fd(),
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        Ok(waker::poll_readable(std::slice::from_mut(&mut pollfd), Some(timeout))? > 0)
+    }
 
-        // Construct the ConnSetupRequest
-        let setup_request = ConnSetupRequest::new(xauth_entries[0].clone());
+    /// Writes the whole buffer to the stream
+    pub fn write_all(&mut self, data: &[u8]) -> io::Result<()> {
+        self.write_all_with_fds(data, &[])
+    }
+
+    /// Writes the whole buffer to the stream, passing `fds` along with it.
+    pub fn write_all_with_fds(
+        &mut self,
+        mut data: &[u8],
+        mut fds: &[BorrowedFd<'_>],
+    ) -> io::Result<()> {
+        while !data.is_empty() {
+            match self.write_with_fds(data, fds) {
+                Ok(0) => return Err(io::Error::from(io::ErrorKind::WriteZero)),
+                Ok(n) => {
+                    data = &data[n..];
+                    fds = &[];
+                }
+                Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
+    }
 
-        // Serialize the Setup Request
-        let sr = setup_request.serialize();
+    /// Reads exactly enough bytes to fill `buf` from the stream
+    pub fn read_exact(&mut self, mut buf: &mut [u8]) -> io::Result<()> {
+        while !buf.is_empty() {
+            match self.read(buf) {
+                Ok(0) => return Err(io::Error::from(io::ErrorKind::UnexpectedEof)),
+                Ok(n) => buf = &mut buf[n..],
+                Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
+    }
 
+    /// Authenticate connection to the display with the given number
+    pub fn authenticate(&mut self, display_number: u8) -> Result<ConnSetup, ConnectionError> {
         // Write the Connection Setup Request to the stream
-        let mut written_count = 0;
-        while written_count < sr.len() {
-            match Self::write(self, &sr) {
-                Ok(c) => written_count += c,
-                Err(e) => {
-                    eprintln!("An error occurred: {}", e);
-                    process::exit(1);
-                }
+        let request = ConnSetupRequest::from_xauthority(display_number);
+        log::debug!(
+            "handshake with authorization {:?}",
+            String::from_utf8_lossy(&request.authorization_protocol_name)
+        );
+        self.write_all(&request.serialize()?)?;
+
+        // Read server's connection setup response from the stream.
+        let mut buff = vec![0u8; 8];
+        self.read_exact(&mut buff)?;
+        buff.resize(ConnSetup::length(&buff)?, 0);
+        self.read_exact(&mut buff[8..])?;
+
+        // Deserialize the bytes to `ConnSetup`
+        let result = ConnSetup::parse_into(&buff);
+        match &result {
+            Ok(setup) => log::debug!(
+                "handshake done in {} bytes: {} release {}, protocol {}.{}, {} screens",
+                buff.len(),
+                setup.vendor,
+                setup.release_number,
+                setup.protocol_major_version,
+                setup.protocol_minor_version,
+                setup.roots.len()
+            ),
+            Err(err) => log::debug!("handshake failed: {}", err),
+        }
+        result
+    }
+}
+
+impl AsFd for Stream {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        match self.variants {
+            StreamVariants::Tcp(ref stream) => stream.as_fd(),
+            StreamVariants::Unix(ref stream) => stream.as_fd(),
+            StreamVariants::Transport(ref transport) => transport.as_fd(),
+        }
+    }
+}
+
+impl AsRawFd for Stream {
+    fn as_raw_fd(&self) -> RawFd {
+        self.as_fd().as_raw_fd()
+    }
+}
+
+/// The socket of the connection, to wait for it to become readable in an event
+/// loop. Read events with [Connection::poll_for_event] when it is, and check
+/// [Connection::has_queued_events] before waiting, as events read while waiting
+/// on a reply are queued without the socket staying readable.
+impl AsFd for Connection {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.stream.as_fd()
+    }
+}
+
+impl AsRawFd for Connection {
+    fn as_raw_fd(&self) -> RawFd {
+        self.stream.as_raw_fd()
+    }
+}
+
+impl Connection {
+    /// Connects to the display named by `DISPLAY`. See [Connection::connect].
+    pub fn init() -> Result<Self, ConnectionError> {
+        let display_name = env::var("DISPLAY").map_err(|_| ConnectionError::DisplayNotSet)?;
+        Self::connect(&display_name)
+    }
+
+    /// Connects to the display `display_name`, such as `:1` or `:0.1`, through
+    /// its unix socket or, if that fails, over TCP. Unlike setting `DISPLAY`
+    /// for [Connection::init], this is safe to call from any thread.
+    pub fn connect(display_name: &str) -> Result<Self, ConnectionError> {
+        // Opens a connection stream
+        let conf = parse_conf(display_name.to_owned());
+        let stream = Stream::open(&conf)?;
+        Self::with_stream(stream, conf.display_number, conf.screen_number)
+    }
+
+    /// Sets up a connection over `transport` instead of a socket, see
+    /// [crate::transport]. The client authenticates with the .Xauthority entry
+    /// of display `display_number`, and `screen_number` is the default screen.
+    pub fn from_transport<T: Transport + 'static>(
+        transport: T,
+        display_number: u8,
+        screen_number: usize,
+    ) -> Result<Self, ConnectionError> {
+        Self::with_stream(
+            Stream::from_transport(transport),
+            display_number,
+            screen_number,
+        )
+    }
+
+    pub(crate) fn with_stream(
+        mut stream: Stream,
+        display_number: u8,
+        screen_number: usize,
+    ) -> Result<Self, ConnectionError> {
+        // Authenticate the connection
+        let setup = stream.authenticate(display_number)?;
+
+        Ok(Connection {
+            stream,
+            state: ProtocolState::new(setup, screen_number),
+            versions: HashMap::new(),
+            ewmh_atoms: None,
+            icccm_atoms: None,
+            time_window: None,
+            wakeup: None,
+        })
+    }
+
+    /// Returns the server's response to the connection setup.
+    pub fn setup(&self) -> &ConnSetup {
+        self.state.setup()
+    }
+
+    /// Returns the screen given in the display name (`:<display>.<screen>`).
+    ///
+    /// Falls back to the first screen if the server has no such screen.
+    pub fn default_screen(&self) -> &Screen {
+        self.state.default_screen()
+    }
+
+    /// Returns the number of the default screen, its index in the roots of the
+    /// setup, as used in the names of per-screen selections like `_NET_WM_CM_S0`.
+    pub fn default_screen_number(&self) -> usize {
+        self.state.default_screen_number()
+    }
+
+    /// Returns the root window of the default screen.
+    pub fn default_root(&self) -> Window {
+        self.default_screen().root
+    }
+
+    /// Allocates a new id for a resource such as a window or a pixmap.
+    pub fn generate_id(&mut self) -> Result<XId, ConnectionError> {
+        self.state.generate_id()
+    }
+
+    /// Sends a serialized request to the server and returns its sequence number.
+    ///
+    /// The request is unchecked: an error caused by it is passed to the error
+    /// handler. See [Connection::set_error_handler].
+    pub fn send_request(&mut self, request: &[u8]) -> Result<u16, ConnectionError> {
+        self.send(request, &[], false, false)
+    }
+
+    /// Sends a serialized request along with file descriptors, which are
+    /// duplicated into the server. Only possible over a unix socket.
+    pub fn send_request_with_fds(
+        &mut self,
+        request: &[u8],
+        fds: &[BorrowedFd<'_>],
+    ) -> Result<u16, ConnectionError> {
+        self.send(request, fds, false, false)
+    }
+
+    /// Sends a serialized request whose reply carries file descriptors, and
+    /// returns the cookie of the reply. See [Cookie::reply_with_fds].
+    pub fn send_request_with_fd_reply<R: Reply>(
+        &mut self,
+        request: &[u8],
+        fds: &[BorrowedFd<'_>],
+    ) -> Result<Cookie<R>, ConnectionError> {
+        Ok(Cookie::new(self.send(request, fds, true, true)?))
+    }
+
+    /// Sends a serialized request that has a reply and returns the cookie of the reply.
+    /// An error caused by the request is returned by [Cookie::reply].
+    pub fn send_request_with_reply<R: Reply>(
+        &mut self,
+        request: &[u8],
+    ) -> Result<Cookie<R>, ConnectionError> {
+        Ok(Cookie::new(self.send(request, &[], true, false)?))
+    }
+
+    fn send(
+        &mut self,
+        request: &[u8],
+        fds: &[BorrowedFd<'_>],
+        checked: bool,
+        fd_reply: bool,
+    ) -> Result<u16, ConnectionError> {
+        if self.state.is_disconnected() {
+            return Err(ConnectionError::Disconnected);
+        }
+        if request.len() > self.state.maximum_request_bytes() && !self.state.big_requests_enabled()
+        {
+            self.enable_big_requests()?;
+        }
+        self.state.check_request_length(request)?;
+        if let Err(err) = self.stream.write_all_with_fds(request, fds) {
+            return Err(self.state.connection_lost(err));
+        }
+        Ok(if fd_reply {
+            self.state.request_with_fd_reply_sent(request)
+        } else {
+            self.state.request_sent(request, checked)
+        })
+    }
+
+    /// Sets what is logged of the requests sent and the messages read, see
+    /// [crate::trace]. Tracing can be switched on and off at any time.
+    #[cfg(feature = "trace")]
+    pub fn set_trace(&mut self, trace: Trace) {
+        self.state.set_trace(trace);
+    }
+
+    /// Sets the function called with the errors of unchecked requests, the ones
+    /// sent without a cookie. By default these errors are logged as warnings
+    /// through the `log` crate.
+    ///
+    /// The handler is called while the connection reads from the server, i.e.
+    /// from within the call that happened to read the error.
+    pub fn set_error_handler(&mut self, handler: Box<dyn Fn(XError) + Send>) {
+        self.state.set_error_handler(handler);
+    }
+
+    /// Waits until the server processed every request sent so far and returns the
+    /// errors caused by unchecked requests that were not read from the server yet.
+    ///
+    /// The returned errors are not passed to the error handler. This is done with a
+    /// GetInputFocus round trip, so a batch of requests can be checked at once:
+    ///
+    /// ```no_run
+    /// # use mousetrap::connection::Connection;
+    /// # use mousetrap::request::PropMode;
+    /// # use mousetrap::protocol::atom;
+    /// let mut conn = Connection::init().unwrap();
+    /// let root = conn.default_root();
+    /// conn.change_property(PropMode::REPLACE, root, atom::WM_NAME, atom::STRING, 8, b"a")
+    ///     .unwrap();
+    /// for error in conn.sync().unwrap() {
+    ///     eprintln!("{}", error);
+    /// }
+    /// ```
+    pub fn sync(&mut self) -> Result<Vec<XError>, ConnectionError> {
+        let cookie: Cookie<InputFocusReply> =
+            self.send_request_with_reply(&GetInputFocus.serialize())?;
+        self.state.begin_sync();
+        let result = cookie.reply(self);
+        let errors = self.state.end_sync();
+        result.map(|_| errors)
+    }
+
+    /// Sets how strictly replies are parsed. Lenient by default, which copes with
+    /// the quirks of some servers; strict parsing is meant for testing servers.
+    pub fn set_parse_mode(&mut self, mode: ParseMode) {
+        self.state.set_parse_mode(mode);
+    }
+
+    /// Returns how strictly replies are parsed.
+    pub fn parse_mode(&self) -> ParseMode {
+        self.state.parse_mode()
+    }
+
+    /// Sets how many events are queued at most, see
+    /// [ProtocolState::set_event_queue_limit].
+    pub fn set_event_queue_limit(&mut self, limit: usize) {
+        self.state.set_event_queue_limit(limit);
+    }
+
+    /// Asks the server whether it supports the extension `name`, e.g. `RANDR`.
+    pub fn query_extension(
+        &mut self,
+        name: &str,
+    ) -> Result<Cookie<QueryExtensionReply>, ConnectionError> {
+        let request = QueryExtension {
+            name: name.as_bytes(),
+        };
+        self.send_request_with_reply(&request.serialize())
+    }
+
+    /// Returns the names of the extensions the server supports.
+    pub fn list_extensions(&mut self) -> Result<Cookie<ListExtensionsReply>, ConnectionError> {
+        self.send_request_with_reply(&ListExtensions.serialize())
+    }
+
+    /// Returns the codes of the extension `name`, or `None` if the server does not
+    /// support it. The server is only asked the first time.
+    pub fn extension(
+        &mut self,
+        name: &'static str,
+    ) -> Result<Option<ExtensionInfo>, ConnectionError> {
+        if let Some(info) = self.state.extension(name) {
+            return Ok(info);
+        }
+        let reply = self.query_extension(name)?.reply(self)?;
+        Ok(self.state.extension_queried(name, &reply))
+    }
+
+    /// Negotiates the version of an extension, e.g. `conn.enable::<Randr>()`, and
+    /// returns it, or `None` if the server does not support the extension or
+    /// supports a version older than [Extension::MIN_VERSION]. The server is only
+    /// asked the first time.
+    pub fn enable<E: Extension>(&mut self) -> Result<Option<Version>, ConnectionError> {
+        if let Some(version) = self.versions.get(E::NAME) {
+            return Ok(*version);
+        }
+        let version = match self.extension(E::NAME)? {
+            Some(_) => E::negotiate(self)?.filter(|version| *version >= E::MIN_VERSION),
+            None => None,
+        };
+        self.versions.insert(E::NAME, version);
+        Ok(version)
+    }
+
+    /// Returns the version of an extension negotiated by [Connection::enable], or
+    /// `None` if the extension was not enabled or is not supported.
+    pub fn extension_version<E: Extension>(&self) -> Option<Version> {
+        self.versions.get(E::NAME).copied().flatten()
+    }
+
+    /// Returns whether an extension was enabled with [Connection::enable] and is
+    /// supported by the server.
+    pub fn is_supported<E: Extension>(&self) -> bool {
+        self.extension_version::<E>().is_some()
+    }
+
+    /// Returns whether an extension was enabled with [Connection::enable] and the
+    /// negotiated version is at least `version`, e.g. to check that the requests
+    /// added by that version can be used.
+    pub fn supports_version<E: Extension>(&self, version: Version) -> bool {
+        self.extension_version::<E>()
+            .is_some_and(|negotiated| negotiated >= version)
+    }
+
+    /// Returns the codes of an extension the caller is about to use, failing if the
+    /// server does not support it. The names of its requests and errors are
+    /// registered the first time.
+    pub(crate) fn require_extension(
+        &mut self,
+        name: &'static str,
+        request_names: &'static [&'static str],
+        error_names: &'static [&'static str],
+    ) -> Result<ExtensionInfo, ConnectionError> {
+        let info = self
+            .extension(name)?
+            .ok_or(ConnectionError::ExtensionNotSupported(name))?;
+        self.state
+            .register_extension(name, info, request_names, error_names);
+        Ok(info)
+    }
+
+    /// Enables BIG-REQUESTS, which raises the length of the longest request the
+    /// server accepts, if the server supports it.
+    fn enable_big_requests(&mut self) -> Result<(), ConnectionError> {
+        if self.extension(bigreq::NAME)?.is_some() {
+            let reply = self.bigreq_enable()?.reply(self)?;
+            self.state.enable_big_requests(reply.maximum_request_length);
+        }
+        Ok(())
+    }
+
+    /// Registers the names of the requests of an extension, so errors caused by them
+    /// name the request, e.g. `BadMatch for RANDR:SetCrtcConfig`.
+    ///
+    /// `request_names` is indexed by minor opcode.
+    pub fn register_extension_requests(
+        &mut self,
+        major_opcode: u8,
+        extension_name: &'static str,
+        request_names: &'static [&'static str],
+    ) {
+        self.state
+            .register_extension_requests(major_opcode, extension_name, request_names);
+    }
+
+    /// Registers the names of the errors of an extension, so they are decoded to
+    /// e.g. `RANDR:BadOutput` rather than a bare error code.
+    ///
+    /// `first_error` is the one reported by QueryExtension and `error_names` is
+    /// indexed by the error code minus `first_error`.
+    pub fn register_extension_errors(
+        &mut self,
+        first_error: u8,
+        extension_name: &'static str,
+        error_names: &'static [&'static str],
+    ) {
+        self.state
+            .register_extension_errors(first_error, extension_name, error_names);
+    }
+
+    /// Selects the events `window` should report to this client.
+    pub fn select_input(
+        &mut self,
+        window: Window,
+        mask: EventMask,
+    ) -> Result<u16, ConnectionError> {
+        let request = ChangeWindowAttributes {
+            window,
+            attributes: WindowAttributes {
+                event_mask: Some(mask),
+                ..Default::default()
+            },
+        };
+        self.send_request(&request.serialize())
+    }
+
+    /// Creates a window. It stays unmapped until [Connection::map_window].
+    pub fn create_window(&mut self, request: &CreateWindow) -> Result<u16, ConnectionError> {
+        self.send_request(&request.serialize())
+    }
+
+    /// Destroys a window and its children.
+    pub fn destroy_window(&mut self, window: Window) -> Result<u16, ConnectionError> {
+        self.send_request(&DestroyWindow { window }.serialize())
+    }
+
+    /// Maps a window, which makes it visible once its ancestors are mapped too.
+    pub fn map_window(&mut self, window: Window) -> Result<u16, ConnectionError> {
+        self.send_request(&MapWindow { window }.serialize())
+    }
+
+    /// Unmaps a window, hiding it and its children.
+    pub fn unmap_window(&mut self, window: Window) -> Result<u16, ConnectionError> {
+        self.send_request(&UnmapWindow { window }.serialize())
+    }
+
+    /// Creates a colormap for a visual of the screen of `window` and returns its id.
+    pub fn create_colormap(
+        &mut self,
+        alloc: ColormapAlloc,
+        window: Window,
+        visual: VisualId,
+    ) -> Result<ColorMap, ConnectionError> {
+        let colormap = self.generate_id()?.0;
+        let request = CreateColormap {
+            alloc,
+            colormap,
+            window,
+            visual,
+        };
+        self.send_request(&request.serialize())?;
+        Ok(colormap)
+    }
+
+    /// Frees a colormap. Windows using it keep it until they are destroyed.
+    pub fn free_colormap(&mut self, colormap: ColorMap) -> Result<u16, ConnectionError> {
+        self.send_request(&FreeColormap { colormap }.serialize())
+    }
+
+    /// Changes the attributes of a window that are `Some` in `attributes`.
+    pub fn change_window_attributes(
+        &mut self,
+        window: Window,
+        attributes: WindowAttributes,
+    ) -> Result<u16, ConnectionError> {
+        self.send_request(&ChangeWindowAttributes { window, attributes }.serialize())
+    }
+
+    /// Paints an area of a window with its background. A width or height of 0
+    /// extends the area to the edge of the window.
+    pub fn clear_area(
+        &mut self,
+        exposures: bool,
+        window: Window,
+        x: i16,
+        y: i16,
+        width: u16,
+        height: u16,
+    ) -> Result<u16, ConnectionError> {
+        let request = ClearArea {
+            exposures,
+            window,
+            x,
+            y,
+            width,
+            height,
+        };
+        self.send_request(&request.serialize())
+    }
+
+    /// Creates a pixmap on the screen of `drawable` and returns its id.
+    pub fn create_pixmap(
+        &mut self,
+        depth: u8,
+        drawable: XId,
+        width: u16,
+        height: u16,
+    ) -> Result<XId, ConnectionError> {
+        let pixmap = self.generate_id()?;
+        let request = CreatePixmap {
+            depth,
+            pixmap,
+            drawable,
+            width,
+            height,
+        };
+        self.send_request(&request.serialize())?;
+        Ok(pixmap)
+    }
+
+    /// Frees a pixmap, e.g. one named with [Connection::composite_name_window_pixmap].
+    pub fn free_pixmap(&mut self, pixmap: XId) -> Result<u16, ConnectionError> {
+        self.send_request(&FreePixmap { pixmap }.serialize())
+    }
+
+    /// Opens a server font and returns its id.
+    pub fn open_font(&mut self, name: &str) -> Result<XId, ConnectionError> {
+        let font = self.generate_id()?;
+        let request = OpenFont {
+            font,
+            name: name.as_bytes(),
+        };
+        self.send_request(&request.serialize())?;
+        Ok(font)
+    }
+
+    /// Closes a font once no resource refers to it anymore.
+    pub fn close_font(&mut self, font: XId) -> Result<u16, ConnectionError> {
+        self.send_request(&CloseFont { font }.serialize())
+    }
+
+    /// Creates one of the standard cursors of the `cursor` font, black with a
+    /// white outline, and returns its id. See [crate::request::cursor_font] for the glyphs.
+    pub fn create_font_cursor(&mut self, glyph: u16) -> Result<XId, ConnectionError> {
+        // The mask is the glyph following the cursor.
+        let mask_glyph = glyph.checked_add(1).ok_or(SerializeError::OutOfRange {
+            field: "glyph",
+            value: u32::from(glyph),
+        })?;
+        let font = self.open_font("cursor")?;
+        let cursor = self.generate_id()?;
+        let request = CreateGlyphCursor {
+            cursor,
+            source_font: font,
+            mask_font: font,
+            source_char: glyph,
+            mask_char: mask_glyph,
+            foreground: [0; 3],
+            background: [0xffff; 3],
+        };
+        self.send_request(&request.serialize())?;
+        self.close_font(font)?;
+        Ok(cursor)
+    }
+
+    /// Frees a cursor, e.g. one created with [Connection::render_create_cursor].
+    pub fn free_cursor(&mut self, cursor: XId) -> Result<u16, ConnectionError> {
+        self.send_request(&FreeCursor { cursor }.serialize())
+    }
+
+    /// Creates a graphics context for drawing on drawables of the screen and
+    /// depth of `drawable`, and returns its id.
+    pub fn create_gc(&mut self, drawable: XId, values: GcValues) -> Result<XId, ConnectionError> {
+        let gc = self.generate_id()?;
+        let request = CreateGc {
+            gc,
+            drawable,
+            values,
+        };
+        self.send_request(&request.serialize())?;
+        Ok(gc)
+    }
+
+    /// Frees a graphics context.
+    pub fn free_gc(&mut self, gc: XId) -> Result<u16, ConnectionError> {
+        self.send_request(&FreeGc { gc }.serialize())
+    }
+
+    /// Writes an image to a drawable. The request must fit in
+    /// [ConnSetup::maximum_request_length]; larger images are sent in strips.
+    pub fn put_image(&mut self, request: &PutImage) -> Result<u16, ConnectionError> {
+        self.send_request(&request.serialize())
+    }
+
+    /// Reads the contents of a rectangle of a drawable. A window must be viewable
+    /// and the rectangle inside the screen, or the server answers with a Match
+    /// error; the parts covered by other windows hold their contents.
+    pub fn get_image(
+        &mut self,
+        request: &GetImage,
+    ) -> Result<Cookie<GetImageReply>, ConnectionError> {
+        self.send_request_with_reply(&request.serialize())
+    }
+
+    /// Sets what happens to the resources of this client when its connection closes.
+    pub fn set_close_down_mode(&mut self, mode: CloseDownMode) -> Result<u16, ConnectionError> {
+        self.send_request(&SetCloseDownMode { mode }.serialize())
+    }
+
+    /// Closes the connection of the client that created `resource`, or
+    /// destroys its resources if they were retained after it closed.
+    pub fn kill_client(&mut self, resource: u32) -> Result<u16, ConnectionError> {
+        self.send_request(&KillClient { resource }.serialize())
+    }
+
+    /// Blocks until the reply to the request with the given sequence number arrives
+    /// and returns its bytes.
+    pub fn wait_for_reply(&mut self, sequence: u16) -> Result<Vec<u8>, ConnectionError> {
+        loop {
+            if let Some(response) = self.state.take_response(sequence) {
+                return response;
             }
+            self.read_messages(true)?;
         }
+    }
 
-        // println!("len: {}, written_count : {}", sr.len(), written_count);
+    /// Like [Connection::wait_for_reply], but gives up once `timeout` elapsed and
+    /// returns `None`. The reply is then discarded when it arrives, so the
+    /// connection stays usable.
+    pub fn wait_for_reply_timeout(
+        &mut self,
+        sequence: u16,
+        timeout: Duration,
+    ) -> Result<Option<Vec<u8>>, ConnectionError> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            if let Some(response) = self.state.take_response(sequence) {
+                return response.map(Some);
+            }
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                self.state.abandon(sequence);
+                return Ok(None);
+            }
+            if self.wait_readable(Some(remaining))? {
+                self.read_messages(false)?;
+            }
+        }
+    }
+
+    /// Like [Connection::wait_for_reply], also returning the file descriptors passed
+    /// with the reply. The request must have been sent with
+    /// [Connection::send_request_with_fd_reply].
+    pub fn wait_for_reply_with_fds(
+        &mut self,
+        sequence: u16,
+    ) -> Result<(Vec<u8>, Vec<OwnedFd>), ConnectionError> {
+        let reply = self.wait_for_reply(sequence)?;
+        let fds = self.state.take_reply_fds(sequence);
+        Ok((reply, fds))
+    }
+
+    /// Returns the atom of `name`, creating it unless `only_if_exists` is set.
+    pub fn intern_atom(
+        &mut self,
+        only_if_exists: bool,
+        name: &str,
+    ) -> Result<Cookie<InternAtomReply>, ConnectionError> {
+        let request = InternAtom {
+            only_if_exists,
+            name: name.as_bytes(),
+        };
+        self.send_request_with_reply(&request.serialize())
+    }
 
-        // Read server's connection setup response from the stream
-        // TODO: The connection setup response wont have a fixed size. 
-        // Here i am reading a fixed number of bytes (for the purpose of quick bootstrapping), 
-        // which might end up in incomplete reads or reading data belonging to another field. 
-        // This should be fixed.
-        let mut buff = vec![0u8; 1000];
+    /// Returns the name of an atom.
+    pub fn get_atom_name(&mut self, atom: Atom) -> Result<Cookie<AtomNameReply>, ConnectionError> {
+        self.send_request_with_reply(&GetAtomName { atom }.serialize())
+    }
 
-        Self::set_nonblocking(self, true); // TODO: handle error
+    /// Changes a property of a window. `data` holds values of `format` (8, 16 or 32) bits.
+    pub fn change_property(
+        &mut self,
+        mode: PropMode,
+        window: Window,
+        property: Atom,
+        type_: Atom,
+        format: u8,
+        data: &[u8],
+    ) -> Result<u16, ConnectionError> {
+        let request = ChangeProperty {
+            mode,
+            window,
+            property,
+            type_,
+            format,
+            data,
+        };
+        self.send_request(&request.serialize())
+    }
 
+    /// Removes a property from a window. Nothing happens if it does not exist.
+    pub fn delete_property(
+        &mut self,
+        window: Window,
+        property: Atom,
+    ) -> Result<u16, ConnectionError> {
+        self.send_request(&DeleteProperty { window, property }.serialize())
+    }
+
+    /// Returns part of the value of a property of a window: at most `long_length`
+    /// 4-byte units from `long_offset`. The value is empty if the property is not
+    /// of type `type_`, unless it is [atom::ANY].
+    pub fn get_property(
+        &mut self,
+        delete: bool,
+        window: Window,
+        property: Atom,
+        type_: Atom,
+        long_offset: u32,
+        long_length: u32,
+    ) -> Result<Cookie<GetPropertyReply>, ConnectionError> {
+        let request = GetProperty {
+            delete,
+            window,
+            property,
+            type_,
+            long_offset,
+            long_length,
+        };
+        self.send_request_with_reply(&request.serialize())
+    }
+
+    /// Returns a reader of the value of a property, fetched in chunks the size of
+    /// the buffers passed to [PropertyReader::read]. Meant for values that can be
+    /// megabytes, such as `_NET_WM_ICON`, which [Connection::get_property] would
+    /// return in a single allocation.
+    ///
+    /// ```no_run
+    /// # use mousetrap::connection::Connection;
+    /// # use mousetrap::protocol::atom;
+    /// let mut conn = Connection::init().unwrap();
+    /// let root = conn.default_root();
+    /// let mut reader = conn.property_reader(root, atom::RESOURCE_MANAGER, atom::STRING);
+    /// let mut buf = [0u8; 4096];
+    /// loop {
+    ///     let n = reader.read(&mut buf).unwrap();
+    ///     if n == 0 {
+    ///         break;
+    ///     }
+    ///     print!("{}", String::from_utf8_lossy(&buf[..n]));
+    /// }
+    /// ```
+    pub fn property_reader(
+        &mut self,
+        window: Window,
+        property: Atom,
+        type_: Atom,
+    ) -> PropertyReader<'_> {
+        PropertyReader {
+            conn: self,
+            window,
+            property,
+            requested_type: type_,
+            long_offset: 0,
+            format: 0,
+            actual_type: atom::NONE,
+            done: false,
+        }
+    }
+
+    /// Returns the properties set on a window.
+    pub fn list_properties(
+        &mut self,
+        window: Window,
+    ) -> Result<Cookie<ListPropertiesReply>, ConnectionError> {
+        self.send_request_with_reply(&ListProperties { window }.serialize())
+    }
+
+    /// Makes `owner` the owner of a selection, or disowns it if `owner` is
+    /// [atom::NONE]. Nothing happens if `time` is older than the last change of
+    /// owner; check with [Connection::get_selection_owner].
+    pub fn set_selection_owner(
+        &mut self,
+        owner: Window,
+        selection: Atom,
+        time: Timestamp,
+    ) -> Result<u16, ConnectionError> {
+        let request = SetSelectionOwner {
+            owner,
+            selection,
+            time,
+        };
+        self.send_request(&request.serialize())
+    }
+
+    /// Returns the owner of a selection.
+    pub fn get_selection_owner(
+        &mut self,
+        selection: Atom,
+    ) -> Result<Cookie<SelectionOwnerReply>, ConnectionError> {
+        self.send_request_with_reply(&GetSelectionOwner { selection }.serialize())
+    }
+
+    /// Returns the attributes of a window, e.g. its class and map state.
+    pub fn get_window_attributes(
+        &mut self,
+        window: Window,
+    ) -> Result<Cookie<WindowAttributesReply>, ConnectionError> {
+        self.send_request_with_reply(&GetWindowAttributes { window }.serialize())
+    }
+
+    /// Returns the position, size, border width and depth of a window or pixmap.
+    pub fn get_geometry(
+        &mut self,
+        drawable: XId,
+    ) -> Result<Cookie<GeometryReply>, ConnectionError> {
+        self.send_request_with_reply(&GetGeometry { drawable }.serialize())
+    }
+
+    /// Returns the root, the parent and the children of a window.
+    pub fn query_tree(
+        &mut self,
+        window: Window,
+    ) -> Result<Cookie<QueryTreeReply>, ConnectionError> {
+        self.send_request_with_reply(&QueryTree { window }.serialize())
+    }
+
+    /// Converts a position relative to `src_window` into one relative to
+    /// `dst_window`, and returns the mapped child of `dst_window` containing it.
+    pub fn translate_coordinates(
+        &mut self,
+        src_window: Window,
+        dst_window: Window,
+        src_x: i16,
+        src_y: i16,
+    ) -> Result<Cookie<TranslateCoordinatesReply>, ConnectionError> {
+        let request = TranslateCoordinates {
+            src_window,
+            dst_window,
+            src_x,
+            src_y,
+        };
+        self.send_request_with_reply(&request.serialize())
+    }
+
+    /// Returns the deepest window at a position of the root window, skipping the
+    /// unmapped and InputOnly windows, or `None` if only the root is there.
+    ///
+    /// This is usually a frame of the window manager or a window inside a client;
+    /// tools picking a client window look for the one with WM_STATE among its
+    /// ancestors and descendants.
+    pub fn window_at(
+        &mut self,
+        root: Window,
+        x: i16,
+        y: i16,
+    ) -> Result<Option<Window>, ConnectionError> {
+        let mut window = root;
         loop {
-            match Self::read(self, &mut buff) {
-                Ok(n) => {
-                    println!("Read {} bytes", n);
-                    break;
-                }
-                Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => {
-                    std::thread::sleep(Duration::from_millis(1000));
-                    continue;
+            let reply = self
+                .translate_coordinates(root, window, x, y)?
+                .reply(self)?;
+            if !reply.same_screen {
+                return Ok(None);
+            }
+            let mut child = reply.child;
+            if child != XId(0) {
+                let attributes = self.get_window_attributes(child)?.reply(self)?;
+                // TranslateCoordinates picks InputOnly windows too; look for a
+                // window that is drawn below it.
+                if attributes.class == WindowClass::INPUT_ONLY.0 {
+                    child = self.visible_child_at(window, reply.dst_x, reply.dst_y)?;
                 }
-                Err(e) => {
-                    eprintln!("An error occurred: {}", e);
-                    process::exit(1);
-                } // Handle other errors
+            }
+            if child == XId(0) {
+                return Ok((window != root).then_some(window));
+            }
+            window = child;
+        }
+    }
+
+    /// Returns the topmost viewable InputOutput child of `window` containing a
+    /// position relative to it, or None.
+    fn visible_child_at(
+        &mut self,
+        window: Window,
+        x: i16,
+        y: i16,
+    ) -> Result<Window, ConnectionError> {
+        let children = self.query_tree(window)?.reply(self)?.children;
+        let mut cookies = Vec::with_capacity(children.len());
+        for &child in &children {
+            cookies.push((
+                self.get_window_attributes(child)?,
+                self.get_geometry(child)?,
+            ));
+        }
+        let (x, y) = (i32::from(x), i32::from(y));
+        let mut found = XId(0);
+        // Children come from bottom to top, so the last match is the topmost.
+        for (child, (attributes, geometry)) in children.into_iter().zip(cookies) {
+            let attributes = attributes.reply(self)?;
+            let geometry = geometry.reply(self)?;
+            let border = 2 * i32::from(geometry.border_width);
+            let (left, top) = (i32::from(geometry.x), i32::from(geometry.y));
+            let inside = x >= left
+                && y >= top
+                && x < left + i32::from(geometry.width) + border
+                && y < top + i32::from(geometry.height) + border;
+            if inside
+                && attributes.map_state == map_state::VIEWABLE
+                && attributes.class == WindowClass::INPUT_OUTPUT.0
+            {
+                found = child;
             }
         }
+        Ok(found)
+    }
 
-        // println!("read: {:?}", buff);
+    /// Returns the deepest window under the pointer, see [Connection::window_at].
+    pub fn window_under_pointer(&mut self) -> Result<Option<Window>, ConnectionError> {
+        let root = self.default_root();
+        let reply = self.query_pointer(root)?.reply(self)?;
+        self.window_at(reply.root, reply.root_x, reply.root_y)
+    }
 
-        // Deserialize the bytes to `ConnSetup`
-        let r = ConnSetup::parse_into(&buff);
+    /// Passively grabs the pointer when `button` is pressed with `modifiers` over
+    /// `grab_window`. See [GrabButton].
+    pub fn grab_button(&mut self, request: &GrabButton) -> Result<u16, ConnectionError> {
+        self.send_request(&request.serialize())
+    }
 
-        match self.variants {
-            StreamVariants::Tcp(ref stream) => {
-                println!("V: tcp");
+    /// Actively grabs the pointer. The grab failed unless the status of the
+    /// reply is [crate::reply::GrabStatus::SUCCESS].
+    pub fn grab_pointer(
+        &mut self,
+        request: &GrabPointer,
+    ) -> Result<Cookie<GrabReply>, ConnectionError> {
+        self.send_request_with_reply(&request.serialize())
+    }
+
+    /// Releases an active grab of the pointer.
+    pub fn ungrab_pointer(&mut self, time: Timestamp) -> Result<u16, ConnectionError> {
+        self.send_request(&UngrabPointer { time }.serialize())
+    }
+
+    /// Actively grabs the keyboard. The grab failed unless the status of the
+    /// reply is [crate::reply::GrabStatus::SUCCESS].
+    pub fn grab_keyboard(
+        &mut self,
+        request: &GrabKeyboard,
+    ) -> Result<Cookie<GrabReply>, ConnectionError> {
+        self.send_request_with_reply(&request.serialize())
+    }
+
+    /// Releases an active grab of the keyboard.
+    pub fn ungrab_keyboard(&mut self, time: Timestamp) -> Result<u16, ConnectionError> {
+        self.send_request(&UngrabKeyboard { time }.serialize())
+    }
+
+    /// Releases a passive grab of [Connection::grab_button].
+    pub fn ungrab_button(
+        &mut self,
+        button: u8,
+        grab_window: Window,
+        modifiers: u16,
+    ) -> Result<u16, ConnectionError> {
+        let request = UngrabButton {
+            button,
+            grab_window,
+            modifiers,
+        };
+        self.send_request(&request.serialize())
+    }
+
+    /// Releases the events frozen by a synchronous grab.
+    pub fn allow_events(
+        &mut self,
+        mode: AllowMode,
+        time: Timestamp,
+    ) -> Result<u16, ConnectionError> {
+        self.send_request(&AllowEvents { mode, time }.serialize())
+    }
+
+    /// Returns the position of the pointer, relative to the root window and to
+    /// `window`, and the state of the modifiers and buttons.
+    pub fn query_pointer(
+        &mut self,
+        window: Window,
+    ) -> Result<Cookie<QueryPointerReply>, ConnectionError> {
+        self.send_request_with_reply(&QueryPointer { window }.serialize())
+    }
+
+    /// Returns the position of the pointer on the default screen, in root window
+    /// coordinates.
+    pub fn pointer_position(&mut self) -> Result<(i16, i16), ConnectionError> {
+        let root = self.default_root();
+        let reply = self.query_pointer(root)?.reply(self)?;
+        Ok((reply.root_x, reply.root_y))
+    }
+
+    /// Returns the current server time.
+    ///
+    /// Requests like SetSelectionOwner or the grabs should be given a real timestamp
+    /// rather than [crate::protocol::CURRENT_TIME]. The server only reports its time
+    /// in events, so this appends nothing to a property of a hidden window and waits
+    /// for the resulting PropertyNotify. Other events stay queued.
+    pub fn current_server_time(&mut self) -> Result<Timestamp, ConnectionError> {
+        let (window, property) = match self.time_window {
+            Some(time_window) => time_window,
+            None => {
+                let window = self.generate_id()?;
+                let request = CreateWindow {
+                    depth: 0,
+                    window,
+                    parent: self.default_root(),
+                    x: -1,
+                    y: -1,
+                    width: 1,
+                    height: 1,
+                    border_width: 0,
+                    class: WindowClass::INPUT_ONLY,
+                    visual: 0,
+                    attributes: WindowAttributes {
+                        event_mask: Some(EventMask::PROPERTY_CHANGE),
+                        ..Default::default()
+                    },
+                };
+                self.send_request(&request.serialize())?;
+                let property = self
+                    .intern_atom(false, "_MOUSETRAP_TIMESTAMP")?
+                    .reply(self)?
+                    .atom;
+                *self.time_window.insert((window, property))
             }
-            StreamVariants::Unix(ref stream) => {
-                println!("V: unix");
+        };
+        self.change_property(PropMode::APPEND, window, property, atom::STRING, 8, &[])?;
+        let event = self.wait_for_event_matching(
+            |event| matches!(event, Event::PropertyNotify(e) if e.window == window && e.atom == property),
+        )?;
+        match event {
+            Event::PropertyNotify(e) => Ok(e.time),
+            _ => Err(ConnectionError::InvalidResponseFromServer),
+        }
+    }
+
+    /// Returns the keysyms of the keycodes in `first_keycode..first_keycode + count`.
+    pub fn get_keyboard_mapping(
+        &mut self,
+        first_keycode: KeyCode,
+        count: u8,
+    ) -> Result<Cookie<KeyboardMapping>, ConnectionError> {
+        let request = GetKeyboardMapping {
+            first_keycode,
+            count,
+        };
+        self.send_request_with_reply(&request.serialize())
+    }
+
+    /// Returns the keycodes bound to the modifiers.
+    pub fn get_modifier_mapping(&mut self) -> Result<Cookie<ModifierMapping>, ConnectionError> {
+        self.send_request_with_reply(&GetModifierMapping.serialize())
+    }
+
+    /// Returns the mapping of the pointer buttons.
+    pub fn get_pointer_mapping(&mut self) -> Result<Cookie<PointerMapping>, ConnectionError> {
+        self.send_request_with_reply(&GetPointerMapping.serialize())
+    }
+
+    /// Returns the keyboard mapping of every keycode, fetching it if it is not cached.
+    pub fn keyboard_mapping(&mut self) -> Result<&KeyboardMapping, ConnectionError> {
+        let mapping = match self.state.keyboard_mut().keyboard_mapping.take() {
+            Some(mapping) => mapping,
+            None => {
+                let first_keycode = self.setup().min_keycode;
+                let count = self
+                    .setup()
+                    .max_keycode
+                    .checked_sub(first_keycode)
+                    .ok_or(ConnectionError::InvalidResponseFromServer)?
+                    .saturating_add(1);
+                let mut mapping = self
+                    .get_keyboard_mapping(first_keycode, count)?
+                    .reply(self)?;
+                mapping.first_keycode = first_keycode;
+                mapping
             }
+        };
+        Ok(self.state.keyboard_mut().keyboard_mapping.insert(mapping))
+    }
+
+    /// Returns the modifier mapping, fetching it if it is not cached.
+    pub fn modifier_mapping(&mut self) -> Result<&ModifierMapping, ConnectionError> {
+        let mapping = match self.state.keyboard_mut().modifier_mapping.take() {
+            Some(mapping) => mapping,
+            None => self.get_modifier_mapping()?.reply(self)?,
+        };
+        Ok(self.state.keyboard_mut().modifier_mapping.insert(mapping))
+    }
+
+    /// Returns the pointer mapping, fetching it if it is not cached.
+    pub fn pointer_mapping(&mut self) -> Result<&PointerMapping, ConnectionError> {
+        let mapping = match self.state.keyboard_mut().pointer_mapping.take() {
+            Some(mapping) => mapping,
+            None => self.get_pointer_mapping()?.reply(self)?,
+        };
+        Ok(self.state.keyboard_mut().pointer_mapping.insert(mapping))
+    }
+
+    /// Returns the masks of Alt, Num Lock and the other logical modifiers,
+    /// resolved from the cached keyboard and modifier mappings.
+    pub fn modifier_masks(&mut self) -> Result<ModifierMasks, ConnectionError> {
+        let modifiers = self.modifier_mapping()?.clone();
+        let mapping = self.keyboard_mapping()?;
+        Ok(ModifierMasks::new(mapping, &modifiers))
+    }
+
+    /// Returns the logical modifiers set in the state of an event.
+    pub fn modifiers(&mut self, state: u16) -> Result<Modifiers, ConnectionError> {
+        Ok(self.modifier_masks()?.decode(state))
+    }
+
+    /// Returns the EWMH atoms, interning them on first use.
+    pub fn ewmh_atoms(&mut self) -> Result<ewmh::Atoms, ConnectionError> {
+        if let Some(atoms) = self.ewmh_atoms {
+            return Ok(atoms);
         }
+        let atoms = ewmh::Atoms::intern(self)?;
+        Ok(*self.ewmh_atoms.insert(atoms))
     }
-}
 
-impl Connection {
-    pub fn init() -> Result<Self, Error> {
-        let display_name = match env::var("DISPLAY") {
-            Ok(value) => value,
-            Err(env::VarError::NotPresent) => {
-                eprintln!("Display not found");
-                process::exit(0)
+    /// Returns the ICCCM atoms, interning them on first use.
+    pub fn icccm_atoms(&mut self) -> Result<icccm::Atoms, ConnectionError> {
+        if let Some(atoms) = self.icccm_atoms {
+            return Ok(atoms);
+        }
+        let atoms = icccm::Atoms::intern(self)?;
+        Ok(*self.icccm_atoms.insert(atoms))
+    }
+
+    /// Sets whether _NET_WM_PING messages are answered as soon as they are read,
+    /// instead of being queued. Window managers offer to kill clients that do not
+    /// answer, which happens to any client not reading its events for a while.
+    pub fn set_auto_pong(&mut self, enabled: bool) -> Result<(), ConnectionError> {
+        let atoms = if enabled {
+            // The atoms cannot be interned while reading an event.
+            Some((self.icccm_atoms()?, self.ewmh_atoms()?))
+        } else {
+            None
+        };
+        self.state.set_auto_pong(atoms);
+        Ok(())
+    }
+
+    /// Sends an event to a window. The server sets the SendEvent flag on the event.
+    ///
+    /// The event is delivered to the clients selecting any event in `event_mask` on
+    /// `destination`, or to the owner of the window if `event_mask` is empty.
+    pub fn send_event(
+        &mut self,
+        propagate: bool,
+        destination: Window,
+        event_mask: EventMask,
+        event: [u8; 32],
+    ) -> Result<u16, ConnectionError> {
+        let request = SendEvent {
+            propagate,
+            destination,
+            event_mask,
+            event,
+        };
+        self.send_request(&request.serialize())
+    }
+
+    /// Sends a ClientMessage to the root window of the default screen, the way EWMH
+    /// expects messages to the window manager (like `_NET_ACTIVE_WINDOW`) to be sent.
+    pub fn send_root_message(
+        &mut self,
+        message: &ClientMessageEvent,
+    ) -> Result<u16, ConnectionError> {
+        let root = self.default_root();
+        self.send_event(
+            false,
+            root,
+            EventMask::SUBSTRUCTURE_REDIRECT | EventMask::SUBSTRUCTURE_NOTIFY,
+            message.serialize(),
+        )
+    }
+
+    /// Returns the next event, blocking until one arrives.
+    pub fn wait_for_event(&mut self) -> Result<Event, ConnectionError> {
+        self.wait_for_event_matching(|_| true)
+    }
+
+    /// Returns the next event if one is available, without blocking.
+    pub fn poll_for_event(&mut self) -> Result<Option<Event>, ConnectionError> {
+        self.poll_for_event_matching(|_| true)
+    }
+
+    /// Returns the oldest event accepted by `filter`, blocking until one arrives.
+    ///
+    /// Events rejected by the filter stay queued, in order, for other consumers.
+    /// Each wait searches them, so they should not be left to pile up.
+    pub fn wait_for_event_matching<F>(&mut self, filter: F) -> Result<Event, ConnectionError>
+    where
+        F: Fn(&Event) -> bool,
+    {
+        loop {
+            if let Some(event) = self.take_queued_event(&filter) {
+                return Ok(event);
+            }
+            if let Err(err) = self.read_messages(true) {
+                // The events read before the connection closed are still delivered.
+                return self.take_queued_event(&filter).ok_or(err);
+            }
+        }
+    }
+
+    /// Returns the oldest event accepted by `filter`, blocking until one arrives or
+    /// `timeout` elapsed, in which case `None` is returned.
+    ///
+    /// Events rejected by the filter stay queued, in order, for other consumers.
+    /// Each wait searches them, so they should not be left to pile up.
+    pub fn wait_for_event_matching_timeout<F>(
+        &mut self,
+        filter: F,
+        timeout: Duration,
+    ) -> Result<Option<Event>, ConnectionError>
+    where
+        F: Fn(&Event) -> bool,
+    {
+        let deadline = Instant::now() + timeout;
+        loop {
+            if let Some(event) = self.take_queued_event(&filter) {
+                return Ok(Some(event));
+            }
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Ok(None);
             }
-            Err(env::VarError::NotUnicode(_)) => {
-                eprintln!("The specified environment variable was found, but it did not contain valid unicode data.");
-                process::exit(0)
+            if !self.wait_readable(Some(remaining))? {
+                continue;
             }
+            if let Err(err) = self.read_messages(false) {
+                // The events read before the connection closed are still delivered.
+                return self.take_queued_event(&filter).map(Some).ok_or(err);
+            }
+        }
+    }
+
+    /// Returns the oldest event accepted by `filter` if one is available, without blocking.
+    ///
+    /// Events rejected by the filter stay queued, in order, for other consumers.
+    pub fn poll_for_event_matching<F>(
+        &mut self,
+        filter: F,
+    ) -> Result<Option<Event>, ConnectionError>
+    where
+        F: Fn(&Event) -> bool,
+    {
+        if let Some(event) = self.take_queued_event(&filter) {
+            return Ok(Some(event));
+        }
+        if let Err(err) = self.read_messages(false) {
+            // The events read before the connection closed are still delivered.
+            return self.take_queued_event(&filter).map(Some).ok_or(err);
+        }
+        Ok(self.take_queued_event(&filter))
+    }
+
+    /// Returns a handle that interrupts blocking waits of the connection from other
+    /// threads or signal handlers: [Connection::wait_for_event] and
+    /// [Connection::poll_for_event] return [Event::Wakeup] once it is woken. The
+    /// waits with a filter only return it if the filter accepts it, and otherwise
+    /// keep waiting, leaving it queued.
+    ///
+    /// All the wakers of a connection share one pipe.
+    pub fn create_waker(&mut self) -> Result<Waker, ConnectionError> {
+        if let Some((_, write)) = &self.wakeup {
+            return Ok(Waker::new(write.clone()));
+        }
+        let (read, write) = waker::pipe()?;
+        let write = Arc::new(write);
+        self.wakeup = Some((read, write.clone()));
+        Ok(Waker::new(write))
+    }
+
+    /// Waits until the socket can be read from, at most `timeout` or forever if
+    /// `None`, and returns whether it can. A wakeup from a [Waker] ends the wait
+    /// and queues [Event::Wakeup], unless one is queued already.
+    fn wait_readable(&mut self, timeout: Option<Duration>) -> io::Result<bool> {
+        let Some((wakeup, _)) = &self.wakeup else {
+            return match timeout {
+                Some(timeout) => self.stream.wait_readable(timeout),
+                None => Ok(true),
+            };
+        };
+        let pollfd = |fd| libc::pollfd {
+            fd,
+            events: libc::POLLIN,
+            revents: 0,
         };
+        let mut fds = [pollfd(self.stream.as_raw_fd()), pollfd(wakeup.as_raw_fd())];
+        waker::poll_readable(&mut fds, timeout)?;
+        if fds[1].revents != 0 {
+            waker::drain(wakeup);
+            self.state.queue_wakeup();
+        }
+        Ok(fds[0].revents != 0)
+    }
 
-        // Opens a connection stream
-        let mut stream = Stream::open(display_name)?;
+    /// Returns whether events were read from the server but not consumed yet.
+    pub fn has_queued_events(&self) -> bool {
+        self.state.has_queued_events()
+    }
 
-        // Authenticate the connection
-        stream.authenticate();
+    /// Returns a blocking iterator over the events accepted by `filter`.
+    ///
+    /// This lets several components of a program consume disjoint subsets of the
+    /// event stream: events that do not match are left in the queue instead of being
+    /// consumed, so they can still be picked up by a later call with another filter.
+    ///
+    /// ```no_run
+    /// # use mousetrap::connection::Connection;
+    /// # use mousetrap::event::Event;
+    /// let mut conn = Connection::init().unwrap();
+    /// for event in conn.events_matching(|e| matches!(e, Event::KeyPress(_))) {
+    ///     println!("{:?}", event);
+    /// }
+    /// ```
+    pub fn events_matching<F>(&mut self, filter: F) -> EventsMatching<'_, F>
+    where
+        F: Fn(&Event) -> bool,
+    {
+        EventsMatching { conn: self, filter }
+    }
+
+    /// Returns a blocking iterator over the events, which ends once the connection
+    /// is closed, after [Event::ConnectionClosed].
+    ///
+    /// ```no_run
+    /// # use mousetrap::connection::Connection;
+    /// let mut conn = Connection::init().unwrap();
+    /// for event in conn.events() {
+    ///     println!("{:?}", event.unwrap());
+    /// }
+    /// ```
+    pub fn events(&mut self) -> Events<'_> {
+        Events { conn: self }
+    }
+
+    /// Removes and returns the oldest queued event accepted by `filter`.
+    fn take_queued_event<F>(&mut self, filter: &F) -> Option<Event>
+    where
+        F: Fn(&Event) -> bool,
+    {
+        self.state.take_event_matching(filter)
+    }
+
+    /// Reads from the stream and dispatches every complete message to the event queue
+    /// or the responses.
+    ///
+    /// If `block` is true, waits until at least one complete message was read.
+    fn read_messages(&mut self, block: bool) -> Result<(), ConnectionError> {
+        if self.state.is_disconnected() {
+            return Err(ConnectionError::Disconnected);
+        }
+        let mut buf = [0u8; 4096];
+        loop {
+            // Only a wakeup ends the wait without the socket being readable.
+            if block && !self.wait_readable(None)? {
+                return Ok(());
+            }
+            match self.receive(&mut buf, block)? {
+                Some(true) => return Ok(()),
+                Some(false) => {}
+                None if block => {}
+                None => return Ok(()),
+            }
+        }
+    }
+
+    /// Reads available bytes from the stream and passes them to the protocol state,
+    /// then answers the pings they held.
+    ///
+    /// Returns whether a message was complete, or `None` if nothing could be read
+    /// without blocking.
+    fn receive(&mut self, buf: &mut [u8], block: bool) -> Result<Option<bool>, ConnectionError> {
+        if !block {
+            self.stream.set_nonblocking(true)?;
+        }
+        let result = self.stream.read(buf);
+        if !block {
+            self.stream.set_nonblocking(false)?;
+        }
+        let n = match result {
+            Ok(0) => {
+                let eof = io::Error::from(io::ErrorKind::UnexpectedEof);
+                return Err(self.state.connection_lost(eof));
+            }
+            Ok(n) => n,
+            Err(ref e)
+                if e.kind() == io::ErrorKind::WouldBlock
+                    || e.kind() == io::ErrorKind::Interrupted =>
+            {
+                return Ok(None);
+            }
+            Err(e) => return Err(self.state.connection_lost(e)),
+        };
+        // The file descriptors arrive with the bytes of the replies carrying them.
+        self.state.fds_received(self.stream.take_fds(usize::MAX));
+        let dispatched = self.state.bytes_received(&buf[..n]);
+        while let Some((window, timestamp)) = self.state.take_ping() {
+            self.pong(window, timestamp)?;
+        }
+        dispatched.map(Some)
+    }
+}
+
+/// Allocates the resource id following `last`, which is updated. Shared by the
+/// blocking and the async connections.
+//
+//  The ids of a client are `resource_id_base` combined with any value of the bits
+//  set in `resource_id_mask`, so ids are handed out by stepping through those bits.
+pub(crate) fn next_resource_id(setup: &ConnSetup, last: &mut u32) -> Result<XId, ConnectionError> {
+    let mask = setup.resource_id_mask;
+    let step = mask & mask.wrapping_neg();
+    let next = last.wrapping_add(step);
+    if step == 0 || next > mask || next <= *last {
+        return Err(ConnectionError::ResourceIdsExhausted);
+    }
+    *last = next;
+    Ok(XId(setup.resource_id_base | next))
+}
+
+/// Returns the length of the first message of `buffer`, bytes read from the
+/// server, or `None` if the message is not complete yet.
+//
+//  Every message from the server is 32 bytes long, except replies (code 1)
+//  and generic events (code 35). Those carry a 4-byte length at offset 4, in
+//  4-byte units, of additional data following the first 32 bytes.
+//
+/// Fails with [ParseError::SuspiciousLength] if the message announces more than
+/// [MAX_MESSAGE_LENGTH] bytes.
+pub(crate) fn message_length(buffer: &[u8]) -> Result<Option<usize>, ParseError> {
+    if buffer.len() < EVENT_SIZE {
+        return Ok(None);
+    }
+    let length = match buffer[0] & 0x7f {
+        1 | event::code::GENERIC_EVENT => {
+            let (additional_length, _) = deserialize_into::<u32>(&buffer[4..8])?;
+            let length = EVENT_SIZE as u64 + u64::from(additional_length) * 4;
+            if length > MAX_MESSAGE_LENGTH as u64 {
+                return Err(ParseError::SuspiciousLength {
+                    type_name: "message",
+                    length: usize::try_from(length).unwrap_or(usize::MAX),
+                    limit: MAX_MESSAGE_LENGTH,
+                });
+            }
+            length as usize
+        }
+        _ => EVENT_SIZE,
+    };
+    Ok((buffer.len() >= length).then_some(length))
+}
+
+/// Reader of the value of a property in chunks. See [Connection::property_reader].
+///
+/// Each chunk is a GetProperty request at the next offset, so a property changed
+/// while it is read may come out as a mix of its old and new values.
+pub struct PropertyReader<'a> {
+    conn: &'a mut Connection,
+    window: Window,
+    property: Atom,
+    requested_type: Atom,
+    /// Offset of the next chunk, in 4-byte units.
+    long_offset: u32,
+    format: u8,
+    actual_type: Atom,
+    done: bool,
+}
+
+impl PropertyReader<'_> {
+    /// Reads the next chunk of the value into `buf` and returns its length in bytes,
+    /// 0 once the whole value was read, or if the property does not exist or is not
+    /// of the requested type.
+    ///
+    /// Chunks are a multiple of 4 bytes but for the last one, so up to 3 bytes at
+    /// the end of `buf` are left unused.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `buf` is shorter than 4 bytes.
+    pub fn read(&mut self, buf: &mut [u8]) -> Result<usize, ConnectionError> {
+        assert!(buf.len() >= 4, "buffer shorter than 4 bytes");
+        if self.done {
+            return Ok(0);
+        }
+        let long_length = u32::try_from(buf.len() / 4).unwrap_or(u32::MAX);
+        let reply = self
+            .conn
+            .get_property(
+                false,
+                self.window,
+                self.property,
+                self.requested_type,
+                self.long_offset,
+                long_length,
+            )?
+            .reply(self.conn)?;
+        self.format = reply.format;
+        self.actual_type = reply.type_;
+        let length = reply.value.len().min(buf.len());
+        buf[..length].copy_from_slice(&reply.value[..length]);
+        self.long_offset = self.long_offset.saturating_add((length / 4) as u32);
+        self.done = reply.bytes_after == 0 || length == 0;
+        Ok(length)
+    }
 
-        // TODO: This should be returned from the authenticate function
-        Ok(Connection { stream })
+    /// Returns the format of the value, 8, 16 or 32 bits, once a chunk was read.
+    /// 0 if the property does not exist.
+    pub fn format(&self) -> u8 {
+        self.format
+    }
+
+    /// Returns the actual type of the property once a chunk was read,
+    /// [atom::NONE] if it does not exist.
+    pub fn type_(&self) -> Atom {
+        self.actual_type
+    }
+}
+
+/// Blocking iterator over the events matching a filter. See [Connection::events_matching].
+pub struct EventsMatching<'a, F> {
+    conn: &'a mut Connection,
+    filter: F,
+}
+
+impl<F> Iterator for EventsMatching<'_, F>
+where
+    F: Fn(&Event) -> bool,
+{
+    type Item = Result<Event, ConnectionError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.conn.wait_for_event_matching(&self.filter))
+    }
+}
+
+/// Blocking iterator over the events. See [Connection::events].
+pub struct Events<'a> {
+    conn: &'a mut Connection,
+}
+
+impl Iterator for Events<'_> {
+    type Item = Result<Event, ConnectionError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.conn.wait_for_event() {
+            Err(ConnectionError::Disconnected) => None,
+            result => Some(result),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A message of `code` with `additional_length` 4-byte units after the first
+    /// 32 bytes, as announced for replies and generic events.
+    fn message(code: u8, additional_length: u32) -> Vec<u8> {
+        let mut bytes = vec![code, 0, 0, 0];
+        bytes.extend_from_slice(&additional_length.to_ne_bytes());
+        bytes.resize(EVENT_SIZE, 0);
+        bytes
+    }
+
+    #[test]
+    fn message_length_waits_for_complete_messages() {
+        let mut buffer = message(1, 2);
+        assert_eq!(message_length(&buffer), Ok(None));
+        buffer.extend_from_slice(&[0xaa; 8]);
+        buffer.extend_from_slice(&message(event::code::MAP_NOTIFY, 0)[..31]);
+        assert_eq!(message_length(&buffer), Ok(Some(40)));
+        assert_eq!(message_length(&buffer[40..]), Ok(None));
+        buffer.push(0);
+        assert_eq!(message_length(&buffer[40..]), Ok(Some(32)));
+    }
+
+    #[test]
+    fn message_lengths() {
+        // Events and errors are 32 bytes, whatever their bytes 4 to 8 hold.
+        for code in [0, event::code::MAP_NOTIFY, 0x80 | event::code::MAP_NOTIFY] {
+            assert_eq!(message_length(&message(code, 7)), Ok(Some(32)));
+        }
+        // Generic events carry a length, sent with SendEvent or not.
+        for code in [
+            event::code::GENERIC_EVENT,
+            0x80 | event::code::GENERIC_EVENT,
+        ] {
+            let mut buffer = message(code, 1);
+            buffer.extend_from_slice(&[0; 4]);
+            assert_eq!(message_length(&buffer), Ok(Some(36)));
+        }
+    }
+
+    #[test]
+    fn message_length_rejects_suspicious_lengths() {
+        // At the limit, the message is awaited.
+        let limit = ((MAX_MESSAGE_LENGTH - EVENT_SIZE) / 4) as u32;
+        assert_eq!(message_length(&message(1, limit)), Ok(None));
+        assert_eq!(
+            message_length(&message(1, limit + 1)),
+            Err(ParseError::SuspiciousLength {
+                type_name: "message",
+                length: MAX_MESSAGE_LENGTH + 4,
+                limit: MAX_MESSAGE_LENGTH,
+            })
+        );
+        let error = message_length(&message(event::code::GENERIC_EVENT, u32::MAX));
+        assert!(matches!(
+            error,
+            Err(ParseError::SuspiciousLength { length, .. }) if length == 32 + 4 * (u32::MAX as usize)
+        ));
     }
 }
@@ -0,0 +1,102 @@
+use crate::connection::Connection;
+use crate::errors::{ConnectionError, ParseError};
+use std::marker::PhantomData;
+use std::os::fd::OwnedFd;
+use std::time::Duration;
+
+/// A trait for parsing the reply of a request.
+pub trait Reply {
+    /// Creates the reply from the bytes of the reply message, header included.
+    ///
+    /// This is lenient: it only fails if the reply is too short for its contents.
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError>
+    where
+        Self: Sized;
+
+    /// Checks a parsed reply for what lenient parsing tolerates, such as trailing
+    /// bytes or unknown enum values. Only called in [ParseMode::Strict].
+    fn validate(&self, _bytes: &[u8]) -> Result<(), ParseError> {
+        Ok(())
+    }
+}
+
+/// How strictly replies are parsed. See [Connection::set_parse_mode].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ParseMode {
+    /// Quirks of the server are tolerated as long as the reply can be read.
+    #[default]
+    Lenient,
+    /// Replies that do not follow the protocol to the letter are errors.
+    /// Useful for testing servers.
+    Strict,
+}
+
+/// A handle to the reply of a request that was sent to the server.
+///
+/// Requests are answered asynchronously, so several requests can be sent before
+/// waiting on the reply of any of them, saving round trips.
+#[derive(Debug)]
+#[must_use = "the reply should be retrieved, otherwise it is kept in memory"]
+pub struct Cookie<R> {
+    /// Sequence number of the request.
+    pub sequence: u16,
+    reply: PhantomData<R>,
+}
+
+impl<R: Reply> Cookie<R> {
+    pub fn new(sequence: u16) -> Self {
+        Self {
+            sequence,
+            reply: PhantomData,
+        }
+    }
+
+    /// Blocks until the reply arrives and parses it.
+    pub fn reply(self, conn: &mut Connection) -> Result<R, ConnectionError> {
+        let bytes = conn.wait_for_reply(self.sequence)?;
+        Self::parse(conn, &bytes)
+    }
+
+    /// Blocks until the reply arrives and parses it, or until `timeout` elapsed, in
+    /// which case `None` is returned.
+    ///
+    /// Giving up does not desynchronize the connection: the reply is read and
+    /// discarded whenever it arrives. Useful against a server or compositor that
+    /// stopped answering.
+    pub fn reply_timeout(
+        self,
+        conn: &mut Connection,
+        timeout: Duration,
+    ) -> Result<Option<R>, ConnectionError> {
+        match conn.wait_for_reply_timeout(self.sequence, timeout)? {
+            Some(bytes) => Self::parse(conn, &bytes).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Blocks until the reply arrives and parses it, also returning the file
+    /// descriptors passed with it. Only for requests sent with
+    /// [Connection::send_request_with_fd_reply].
+    pub fn reply_with_fds(
+        self,
+        conn: &mut Connection,
+    ) -> Result<(R, Vec<OwnedFd>), ConnectionError> {
+        let (bytes, fds) = conn.wait_for_reply_with_fds(self.sequence)?;
+        Ok((Self::parse(conn, &bytes)?, fds))
+    }
+
+    fn parse(conn: &Connection, bytes: &[u8]) -> Result<R, ConnectionError> {
+        parse_reply(bytes, conn.parse_mode())
+    }
+}
+
+/// Parses the bytes of a reply, validating it in [ParseMode::Strict].
+pub(crate) fn parse_reply<R: Reply>(bytes: &[u8], mode: ParseMode) -> Result<R, ConnectionError> {
+    let reply = R::from_bytes(bytes)
+        .and_then(|reply| match mode {
+            ParseMode::Strict => reply.validate(bytes).map(|_| reply),
+            ParseMode::Lenient => Ok(reply),
+        })
+        .map_err(|err| err.in_message(bytes))?;
+    Ok(reply)
+}
@@ -0,0 +1,249 @@
+//! The DAMAGE extension: notifications of the changes of the contents of drawables.
+//!
+//! A damage object accumulates the areas of a drawable that were drawn to, and
+//! reports them with DamageNotify events. Screen sharing tools use it to read
+//! only the parts of the screen that changed. The accumulated areas are cleared
+//! with [Connection::damage_subtract], which can also store them in an XFIXES
+//! region to be fetched.
+//!
+//! The server treats a client as a DAMAGE 0.0 client until it called
+//! [Connection::damage_query_version], so call it before any other request.
+//! The regions of [Connection::damage_subtract] also need XFIXES 2.0.
+
+use crate::connection::Connection;
+use crate::cookie::{Cookie, Reply};
+use crate::errors::{ConnectionError, ParseError};
+use crate::event::Event;
+use crate::extension::{Extension, Version};
+use crate::protocol::{Rectangle, Timestamp, XId};
+use crate::request::RequestBuilder;
+use crate::utils::{check_trailing_bytes, deserialize_into, deserialize_into_vec, skip};
+
+/// Name of the extension, as given to QueryExtension.
+pub const NAME: &str = "DAMAGE";
+
+/// The newest version of the extension this module implements.
+pub const VERSION: (u32, u32) = (1, 1);
+
+/// Minor opcodes of the DAMAGE requests.
+pub mod opcode {
+    pub const QUERY_VERSION: u8 = 0;
+    pub const CREATE: u8 = 1;
+    pub const DESTROY: u8 = 2;
+    pub const SUBTRACT: u8 = 3;
+    pub const ADD: u8 = 4;
+}
+
+/// Names of the DAMAGE requests, indexed by minor opcode.
+pub const REQUEST_NAMES: &[&str] = &["QueryVersion", "Create", "Destroy", "Subtract", "Add"];
+
+/// Names of the DAMAGE errors, indexed from the first error.
+pub const ERROR_NAMES: &[&str] = &["BadDamage"];
+
+/// Event codes of the DAMAGE events, relative to the first event of the extension.
+pub mod event_code {
+    pub const NOTIFY: u8 = 0;
+}
+
+/// How often a damage object reports the damage of its drawable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReportLevel(pub u8);
+
+impl ReportLevel {
+    /// A DamageNotify for every drawing, with its rectangle.
+    pub const RAW_RECTANGLES: Self = Self(0);
+    /// A DamageNotify for every drawing that adds to the accumulated damage.
+    pub const DELTA_RECTANGLES: Self = Self(1);
+    /// A DamageNotify whenever the bounding box of the damage grows.
+    pub const BOUNDING_BOX: Self = Self(2);
+    /// A single DamageNotify when the damage stops being empty.
+    pub const NON_EMPTY: Self = Self(3);
+}
+
+/// Starts a request of the extension.
+fn request(conn: &mut Connection, minor_opcode: u8) -> Result<RequestBuilder, ConnectionError> {
+    let info = conn.require_extension(NAME, REQUEST_NAMES, ERROR_NAMES)?;
+    Ok(RequestBuilder::new(info.major_opcode, minor_opcode))
+}
+
+/// The DAMAGE extension, for [Connection::enable].
+pub struct Damage;
+
+impl Extension for Damage {
+    const NAME: &'static str = NAME;
+    const MIN_VERSION: Version = (1, 0);
+
+    fn negotiate(conn: &mut Connection) -> Result<Option<Version>, ConnectionError> {
+        let reply = conn
+            .damage_query_version(VERSION.0, VERSION.1)?
+            .reply(conn)?;
+        Ok(Some((reply.major_version, reply.minor_version)))
+    }
+}
+
+/// Reply of the DamageQueryVersion request.
+#[derive(Debug, Clone)]
+pub struct QueryVersionReply {
+    pub major_version: u32,
+    pub minor_version: u32,
+}
+
+impl Reply for QueryVersionReply {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1                        unused
+        // 2  CARD16                sequence number
+        // 4  0                     reply length
+        // 4  CARD32                major-version
+        // 4  CARD32                minor-version
+        // 16                       unused
+        let rest = skip(bytes, 8)?;
+        let (major_version, rest) = deserialize_into::<u32>(rest)?;
+        let (minor_version, _) = deserialize_into::<u32>(rest)?;
+        Ok(QueryVersionReply {
+            major_version,
+            minor_version,
+        })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        check_trailing_bytes("damage::QueryVersionReply", bytes, 32)
+    }
+}
+
+/// A drawable was damaged.
+#[derive(Debug, Clone)]
+pub struct DamageNotifyEvent {
+    pub level: ReportLevel,
+    /// Whether more DamageNotify events for the same damage object follow.
+    pub more: bool,
+    pub drawable: XId,
+    pub damage: XId,
+    pub timestamp: Timestamp,
+    /// The damaged area, relative to the drawable.
+    pub area: Rectangle,
+    /// The geometry of the drawable.
+    pub geometry: Rectangle,
+}
+
+impl DamageNotifyEvent {
+    /// Parses the event if `event` is a DamageNotify. `first_event` is the one of
+    /// the extension, see [Connection::extension].
+    //
+    //   1 byte     Code (first event + 0)
+    //   1 byte     Level, with bit 7 set if more events follow
+    //   2 bytes    Sequence number
+    //   4 bytes    Drawable
+    //   4 bytes    Damage
+    //   4 bytes    Timestamp
+    //   8 bytes    Area
+    //   8 bytes    Geometry
+    pub fn from_event(event: &Event, first_event: u8) -> Option<DamageNotifyEvent> {
+        let Event::Unknown(bytes) = event else {
+            return None;
+        };
+        if bytes.first()? & 0x7f != first_event.wrapping_add(event_code::NOTIFY) {
+            return None;
+        }
+        let rest = skip(bytes, 1).ok()?;
+        let (level, rest) = deserialize_into::<u8>(rest).ok()?;
+        let rest = skip(rest, 2).ok()?;
+        let (drawable, rest) = deserialize_into::<XId>(rest).ok()?;
+        let (damage, rest) = deserialize_into::<XId>(rest).ok()?;
+        let (timestamp, rest) = deserialize_into::<u32>(rest).ok()?;
+        let (rectangles, _) = deserialize_into_vec::<Rectangle>(rest, 2).ok()?;
+        Some(DamageNotifyEvent {
+            level: ReportLevel(level & 0x7f),
+            more: level & 0x80 != 0,
+            drawable,
+            damage,
+            timestamp,
+            area: rectangles[0],
+            geometry: rectangles[1],
+        })
+    }
+}
+
+impl Connection {
+    /// Tells the server the version of the extension the client supports and
+    /// returns the version both support. Call it before any other DAMAGE request.
+    pub fn damage_query_version(
+        &mut self,
+        major_version: u32,
+        minor_version: u32,
+    ) -> Result<Cookie<QueryVersionReply>, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (0)
+        //   2 bytes    Request length (3)
+        //   4 bytes    Client major version
+        //   4 bytes    Client minor version
+        let request = request(self, opcode::QUERY_VERSION)?
+            .put(major_version)
+            .put(minor_version)
+            .finish();
+        self.send_request_with_reply(&request)
+    }
+
+    /// Creates a damage object following the changes of a drawable, and returns
+    /// its id.
+    pub fn damage_create(
+        &mut self,
+        drawable: XId,
+        level: ReportLevel,
+    ) -> Result<XId, ConnectionError> {
+        let damage = self.generate_id()?;
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (1)
+        //   2 bytes    Request length (4)
+        //   4 bytes    Damage
+        //   4 bytes    Drawable
+        //   1 byte     Level
+        //   3 bytes    Unused
+        let request = request(self, opcode::CREATE)?
+            .put(damage)
+            .put(drawable)
+            .put(level.0)
+            .pad(3)
+            .finish();
+        self.send_request(&request)?;
+        Ok(damage)
+    }
+
+    pub fn damage_destroy(&mut self, damage: XId) -> Result<u16, ConnectionError> {
+        let request = request(self, opcode::DESTROY)?.put(damage).finish();
+        self.send_request(&request)
+    }
+
+    /// Removes the XFIXES region `repair` from the accumulated damage, or all of
+    /// it if `repair` is None (0), and stores the removed part in the region
+    /// `parts` unless it is None.
+    pub fn damage_subtract(
+        &mut self,
+        damage: XId,
+        repair: XId,
+        parts: XId,
+    ) -> Result<u16, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (3)
+        //   2 bytes    Request length (4)
+        //   4 bytes    Damage
+        //   4 bytes    Repair region
+        //   4 bytes    Parts region
+        let request = request(self, opcode::SUBTRACT)?
+            .put(damage)
+            .put(repair)
+            .put(parts)
+            .finish();
+        self.send_request(&request)
+    }
+
+    /// Reports the XFIXES region `region` of a drawable as damaged, for clients
+    /// drawing to it by means the server does not see. Requires DAMAGE 1.1.
+    pub fn damage_add(&mut self, drawable: XId, region: XId) -> Result<u16, ConnectionError> {
+        let request = request(self, opcode::ADD)?
+            .put(drawable)
+            .put(region)
+            .finish();
+        self.send_request(&request)
+    }
+}
@@ -0,0 +1,137 @@
+use crate::connection::Connection;
+use crate::errors::ConnectionError;
+use crate::event::Event;
+use crate::protocol::Window;
+use std::ops::ControlFlow;
+
+/// A function invoked for the events it was registered for.
+///
+/// Returning `ControlFlow::Break(())` stops [EventDispatcher::run].
+pub type Handler<'a> = Box<dyn FnMut(&mut Connection, &Event) -> ControlFlow<()> + 'a>;
+
+/// A registered handler and the events it is invoked for.
+struct Registration<'a> {
+    /// Event code the handler is registered for. `None` matches every event.
+    code: Option<u8>,
+    /// Window the handler is registered for. `None` matches every window.
+    window: Option<Window>,
+    handler: Handler<'a>,
+}
+
+impl Registration<'_> {
+    fn matches(&self, event: &Event) -> bool {
+        self.code.is_none_or(|code| code == event.code())
+            && self
+                .window
+                .is_none_or(|window| event.window() == Some(window))
+    }
+}
+
+/// Invokes registered handlers for the events read from a connection.
+///
+/// This is an ergonomic layer for small tools that don't want to write their own
+/// event loop and match statement. Event codes are the ones in [crate::event::code].
+///
+/// ```no_run
+/// # use mousetrap::connection::Connection;
+/// # use mousetrap::dispatcher::EventDispatcher;
+/// # use mousetrap::event::{code, Event};
+/// # use std::ops::ControlFlow;
+/// let mut conn = Connection::init().unwrap();
+/// let mut dispatcher = EventDispatcher::new();
+/// dispatcher.on(code::KEY_PRESS, |_, event| {
+///     println!("{:?}", event);
+///     ControlFlow::Continue(())
+/// });
+/// dispatcher.on(code::BUTTON_PRESS, |_, _| ControlFlow::Break(()));
+/// dispatcher.run(&mut conn).unwrap();
+/// ```
+#[derive(Default)]
+pub struct EventDispatcher<'a> {
+    registrations: Vec<Registration<'a>>,
+}
+
+impl<'a> EventDispatcher<'a> {
+    /// Creates a dispatcher without any handlers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a handler for every event with the given code.
+    pub fn on<F>(&mut self, code: u8, handler: F) -> &mut Self
+    where
+        F: FnMut(&mut Connection, &Event) -> ControlFlow<()> + 'a,
+    {
+        self.register(Some(code), None, Box::new(handler))
+    }
+
+    /// Registers a handler for the events with the given code reported to `window`.
+    pub fn on_window<F>(&mut self, code: u8, window: Window, handler: F) -> &mut Self
+    where
+        F: FnMut(&mut Connection, &Event) -> ControlFlow<()> + 'a,
+    {
+        self.register(Some(code), Some(window), Box::new(handler))
+    }
+
+    /// Registers a handler for every event.
+    pub fn on_any<F>(&mut self, handler: F) -> &mut Self
+    where
+        F: FnMut(&mut Connection, &Event) -> ControlFlow<()> + 'a,
+    {
+        self.register(None, None, Box::new(handler))
+    }
+
+    fn register(
+        &mut self,
+        code: Option<u8>,
+        window: Option<Window>,
+        handler: Handler<'a>,
+    ) -> &mut Self {
+        self.registrations.push(Registration {
+            code,
+            window,
+            handler,
+        });
+        self
+    }
+
+    /// Invokes every handler registered for `event`, in registration order.
+    ///
+    /// Returns `ControlFlow::Break(())` if any of the handlers asked to stop.
+    pub fn dispatch(&mut self, conn: &mut Connection, event: &Event) -> ControlFlow<()> {
+        let mut flow = ControlFlow::Continue(());
+        for registration in self.registrations.iter_mut() {
+            if registration.matches(event) && (registration.handler)(conn, event).is_break() {
+                flow = ControlFlow::Break(());
+            }
+        }
+        flow
+    }
+
+    /// Reads events from the connection and dispatches them until a handler asks
+    /// to stop or the connection fails.
+    pub fn run(&mut self, conn: &mut Connection) -> Result<(), ConnectionError> {
+        loop {
+            let event = conn.wait_for_event()?;
+            if self.dispatch(conn, &event).is_break() {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Dispatches the events that are available without blocking.
+    ///
+    /// Returns `ControlFlow::Break(())` if a handler asked to stop. Useful for
+    /// programs that have their own main loop.
+    pub fn dispatch_pending(
+        &mut self,
+        conn: &mut Connection,
+    ) -> Result<ControlFlow<()>, ConnectionError> {
+        while let Some(event) = conn.poll_for_event()? {
+            if self.dispatch(conn, &event).is_break() {
+                return Ok(ControlFlow::Break(()));
+            }
+        }
+        Ok(ControlFlow::Continue(()))
+    }
+}
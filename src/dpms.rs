@@ -0,0 +1,317 @@
+//! The DPMS extension: power management of the monitors.
+//!
+//! The server turns the monitors to standby, suspend and then off after the
+//! user has been idle for the timeouts of each level, or right away when asked
+//! with [Connection::dpms_force_level].
+//!
+//! ```no_run
+//! use mousetrap::connection::Connection;
+//! use mousetrap::dpms::PowerLevel;
+//!
+//! let mut conn = Connection::init().unwrap();
+//! conn.dpms_enable().unwrap();
+//! conn.dpms_force_level(PowerLevel::OFF).unwrap();
+//! conn.sync().unwrap();
+//! ```
+
+use crate::connection::Connection;
+use crate::cookie::{Cookie, Reply};
+use crate::errors::{ConnectionError, ParseError};
+use crate::extension::{Extension, Version};
+use crate::request::RequestBuilder;
+use crate::utils::{check_trailing_bytes, deserialize_into, skip};
+
+/// Name of the extension, as given to QueryExtension.
+pub const NAME: &str = "DPMS";
+
+/// Version of the extension implemented by this module.
+pub const VERSION: (u16, u16) = (1, 1);
+
+/// Minor opcodes of the DPMS requests.
+pub mod opcode {
+    pub const GET_VERSION: u8 = 0;
+    pub const CAPABLE: u8 = 1;
+    pub const GET_TIMEOUTS: u8 = 2;
+    pub const SET_TIMEOUTS: u8 = 3;
+    pub const ENABLE: u8 = 4;
+    pub const DISABLE: u8 = 5;
+    pub const FORCE_LEVEL: u8 = 6;
+    pub const INFO: u8 = 7;
+}
+
+/// Names of the DPMS requests, indexed by minor opcode.
+pub const REQUEST_NAMES: &[&str] = &[
+    "GetVersion",
+    "Capable",
+    "GetTimeouts",
+    "SetTimeouts",
+    "Enable",
+    "Disable",
+    "ForceLevel",
+    "Info",
+];
+
+/// The extension defines no errors.
+pub const ERROR_NAMES: &[&str] = &[];
+
+/// Starts a request of the extension.
+fn request(conn: &mut Connection, minor_opcode: u8) -> Result<RequestBuilder, ConnectionError> {
+    let info = conn.require_extension(NAME, REQUEST_NAMES, ERROR_NAMES)?;
+    Ok(RequestBuilder::new(info.major_opcode, minor_opcode))
+}
+
+/// The DPMS extension, for [Connection::enable].
+pub struct Dpms;
+
+impl Extension for Dpms {
+    const NAME: &'static str = NAME;
+    const MIN_VERSION: Version = (1, 0);
+
+    fn negotiate(conn: &mut Connection) -> Result<Option<Version>, ConnectionError> {
+        let reply = conn.dpms_get_version(VERSION.0, VERSION.1)?.reply(conn)?;
+        Ok(Some((
+            reply.major_version.into(),
+            reply.minor_version.into(),
+        )))
+    }
+}
+
+/// A power level of the monitors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PowerLevel(pub u16);
+
+impl PowerLevel {
+    pub const ON: Self = Self(0);
+    pub const STANDBY: Self = Self(1);
+    pub const SUSPEND: Self = Self(2);
+    pub const OFF: Self = Self(3);
+}
+
+/// Reply of the DPMSGetVersion request.
+#[derive(Debug, Clone)]
+pub struct GetVersionReply {
+    pub major_version: u16,
+    pub minor_version: u16,
+}
+
+impl Reply for GetVersionReply {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1                        unused
+        // 2  CARD16                sequence number
+        // 4  0                     reply length
+        // 2  CARD16                major-version
+        // 2  CARD16                minor-version
+        // 20                       unused
+        let rest = skip(bytes, 8)?;
+        let (major_version, rest) = deserialize_into::<u16>(rest)?;
+        let (minor_version, _) = deserialize_into::<u16>(rest)?;
+        Ok(GetVersionReply {
+            major_version,
+            minor_version,
+        })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        check_trailing_bytes("dpms::GetVersionReply", bytes, 32)
+    }
+}
+
+/// Reply of the DPMSCapable request.
+#[derive(Debug, Clone)]
+pub struct CapableReply {
+    /// Whether the monitors support power management.
+    pub capable: bool,
+}
+
+impl Reply for CapableReply {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1                        unused
+        // 2  CARD16                sequence number
+        // 4  0                     reply length
+        // 1  BOOL                  capable
+        // 23                       unused
+        let rest = skip(bytes, 8)?;
+        let (capable, _) = deserialize_into::<u8>(rest)?;
+        Ok(CapableReply {
+            capable: capable != 0,
+        })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        check_trailing_bytes("dpms::CapableReply", bytes, 32)
+    }
+}
+
+/// Reply of the DPMSGetTimeouts request. The timeouts are in seconds, 0 when
+/// the level is disabled.
+#[derive(Debug, Clone)]
+pub struct GetTimeoutsReply {
+    pub standby: u16,
+    pub suspend: u16,
+    pub off: u16,
+}
+
+impl Reply for GetTimeoutsReply {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1                        unused
+        // 2  CARD16                sequence number
+        // 4  0                     reply length
+        // 2  CARD16                standby-timeout
+        // 2  CARD16                suspend-timeout
+        // 2  CARD16                off-timeout
+        // 18                       unused
+        let rest = skip(bytes, 8)?;
+        let (standby, rest) = deserialize_into::<u16>(rest)?;
+        let (suspend, rest) = deserialize_into::<u16>(rest)?;
+        let (off, _) = deserialize_into::<u16>(rest)?;
+        Ok(GetTimeoutsReply {
+            standby,
+            suspend,
+            off,
+        })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        check_trailing_bytes("dpms::GetTimeoutsReply", bytes, 32)
+    }
+}
+
+/// Reply of the DPMSInfo request.
+#[derive(Debug, Clone)]
+pub struct InfoReply {
+    pub power_level: PowerLevel,
+    /// Whether power management is enabled.
+    pub enabled: bool,
+}
+
+impl Reply for InfoReply {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1                        unused
+        // 2  CARD16                sequence number
+        // 4  0                     reply length
+        // 2  CARD16                power-level
+        // 1  BOOL                  state
+        // 21                       unused
+        let rest = skip(bytes, 8)?;
+        let (power_level, rest) = deserialize_into::<u16>(rest)?;
+        let (enabled, _) = deserialize_into::<u8>(rest)?;
+        Ok(InfoReply {
+            power_level: PowerLevel(power_level),
+            enabled: enabled != 0,
+        })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        check_trailing_bytes("dpms::InfoReply", bytes, 32)
+    }
+}
+
+impl Connection {
+    /// Tells the server the version of the extension the client supports and
+    /// returns the version the server supports.
+    pub fn dpms_get_version(
+        &mut self,
+        major_version: u16,
+        minor_version: u16,
+    ) -> Result<Cookie<GetVersionReply>, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (0)
+        //   2 bytes    Request length (2)
+        //   2 bytes    Client major version
+        //   2 bytes    Client minor version
+        let request = request(self, opcode::GET_VERSION)?
+            .put(major_version)
+            .put(minor_version)
+            .finish();
+        self.send_request_with_reply(&request)
+    }
+
+    /// Returns whether the monitors support power management.
+    pub fn dpms_capable(&mut self) -> Result<Cookie<CapableReply>, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (1)
+        //   2 bytes    Request length (1)
+        let request = request(self, opcode::CAPABLE)?.finish();
+        self.send_request_with_reply(&request)
+    }
+
+    /// Returns the idle timeouts of the power levels.
+    pub fn dpms_get_timeouts(&mut self) -> Result<Cookie<GetTimeoutsReply>, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (2)
+        //   2 bytes    Request length (1)
+        let request = request(self, opcode::GET_TIMEOUTS)?.finish();
+        self.send_request_with_reply(&request)
+    }
+
+    /// Sets the idle timeouts of the power levels, in seconds. A timeout of 0
+    /// disables its level; the others must not decrease from standby to off.
+    pub fn dpms_set_timeouts(
+        &mut self,
+        standby: u16,
+        suspend: u16,
+        off: u16,
+    ) -> Result<u16, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (3)
+        //   2 bytes    Request length (3)
+        //   2 bytes    Standby timeout
+        //   2 bytes    Suspend timeout
+        //   2 bytes    Off timeout
+        //   2 bytes    Unused
+        let request = request(self, opcode::SET_TIMEOUTS)?
+            .put(standby)
+            .put(suspend)
+            .put(off)
+            .pad(2)
+            .finish();
+        self.send_request(&request)
+    }
+
+    /// Enables power management.
+    pub fn dpms_enable(&mut self) -> Result<u16, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (4)
+        //   2 bytes    Request length (1)
+        let request = request(self, opcode::ENABLE)?.finish();
+        self.send_request(&request)
+    }
+
+    /// Disables power management, which also turns the monitors on.
+    pub fn dpms_disable(&mut self) -> Result<u16, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (5)
+        //   2 bytes    Request length (1)
+        let request = request(self, opcode::DISABLE)?.finish();
+        self.send_request(&request)
+    }
+
+    /// Puts the monitors in a power level right away. Power management must be
+    /// enabled, see [Connection::dpms_enable].
+    pub fn dpms_force_level(&mut self, level: PowerLevel) -> Result<u16, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (6)
+        //   2 bytes    Request length (2)
+        //   2 bytes    Power level
+        //   2 bytes    Unused
+        let request = request(self, opcode::FORCE_LEVEL)?
+            .put(level.0)
+            .pad(2)
+            .finish();
+        self.send_request(&request)
+    }
+
+    /// Returns the power level of the monitors and whether power management is
+    /// enabled.
+    pub fn dpms_info(&mut self) -> Result<Cookie<InfoReply>, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (7)
+        //   2 bytes    Request length (1)
+        let request = request(self, opcode::INFO)?.finish();
+        self.send_request_with_reply(&request)
+    }
+}
@@ -0,0 +1,468 @@
+//! The DRI3 extension: pixmaps backed by GPU buffers.
+//!
+//! Buffers are shared with the server as dma-buf file descriptors passed over
+//! the unix socket, so that the contents of a pixmap can be read or written by
+//! the GPU without copies. [Connection::dri3_open] returns a file descriptor of
+//! the DRM device the server renders with, to allocate the buffers on.
+//!
+//! ```no_run
+//! use mousetrap::connection::Connection;
+//! use mousetrap::protocol::XId;
+//!
+//! let mut conn = Connection::init().unwrap();
+//! let window = XId(0x1200001);
+//! let pixmap = XId(0x1200002);
+//! let (buffer, fd) = conn.dri3_buffer_from_pixmap(pixmap).unwrap();
+//! println!("{}x{}, stride {}", buffer.width, buffer.height, buffer.stride);
+//! let copy = conn.dri3_pixmap_from_buffer(
+//!     window, fd, buffer.size, buffer.width, buffer.height, buffer.stride, buffer.depth,
+//!     buffer.bpp,
+//! ).unwrap();
+//! ```
+//!
+//! Requires a unix socket connection.
+
+use crate::connection::Connection;
+use crate::cookie::{Cookie, Reply};
+use crate::errors::{ConnectionError, ParseError, SerializeError};
+use crate::extension::{Extension, Version};
+use crate::protocol::{Window, XId};
+use crate::request::RequestBuilder;
+use crate::utils::{check_trailing_bytes, deserialize_into, deserialize_into_vec, skip};
+use std::os::fd::{AsFd, BorrowedFd, OwnedFd};
+
+/// Name of the extension, as given to QueryExtension.
+pub const NAME: &str = "DRI3";
+
+/// Version of the extension implemented by this module.
+pub const VERSION: (u32, u32) = (1, 2);
+
+/// Minor opcodes of the DRI3 requests.
+pub mod opcode {
+    pub const QUERY_VERSION: u8 = 0;
+    pub const OPEN: u8 = 1;
+    pub const PIXMAP_FROM_BUFFER: u8 = 2;
+    pub const BUFFER_FROM_PIXMAP: u8 = 3;
+    pub const PIXMAP_FROM_BUFFERS: u8 = 7;
+    pub const BUFFERS_FROM_PIXMAP: u8 = 8;
+}
+
+/// Names of the DRI3 requests, indexed by minor opcode.
+pub const REQUEST_NAMES: &[&str] = &[
+    "QueryVersion",
+    "Open",
+    "PixmapFromBuffer",
+    "BufferFromPixmap",
+    "FenceFromFD",
+    "FDFromFence",
+    "GetSupportedModifiers",
+    "PixmapFromBuffers",
+    "BuffersFromPixmap",
+];
+
+/// The extension defines no errors.
+pub const ERROR_NAMES: &[&str] = &[];
+
+/// Starts a request of the extension.
+fn request(conn: &mut Connection, minor_opcode: u8) -> Result<RequestBuilder, ConnectionError> {
+    let info = conn.require_extension(NAME, REQUEST_NAMES, ERROR_NAMES)?;
+    Ok(RequestBuilder::new(info.major_opcode, minor_opcode))
+}
+
+/// The DRI3 extension, for [Connection::enable].
+pub struct Dri3;
+
+impl Extension for Dri3 {
+    const NAME: &'static str = NAME;
+    const MIN_VERSION: Version = (1, 0);
+
+    fn negotiate(conn: &mut Connection) -> Result<Option<Version>, ConnectionError> {
+        let reply = conn.dri3_query_version(VERSION.0, VERSION.1)?.reply(conn)?;
+        Ok(Some((reply.major_version, reply.minor_version)))
+    }
+}
+
+/// Most planes a buffer given to [Connection::dri3_pixmap_from_buffers] can have.
+pub const MAX_PLANES: usize = 4;
+
+/// Checks the number of file descriptors announced by a reply.
+fn check_nfd(type_name: &'static str, nfd: u8, expected: u8) -> Result<(), ParseError> {
+    if nfd != expected {
+        return Err(ParseError::InvalidValue {
+            type_name,
+            field: "nfd",
+            value: nfd.into(),
+        });
+    }
+    Ok(())
+}
+
+/// Reply of the DRI3QueryVersion request.
+#[derive(Debug, Clone)]
+pub struct QueryVersionReply {
+    pub major_version: u32,
+    pub minor_version: u32,
+}
+
+impl Reply for QueryVersionReply {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1                        unused
+        // 2  CARD16                sequence number
+        // 4  0                     reply length
+        // 4  CARD32                major-version
+        // 4  CARD32                minor-version
+        // 16                       unused
+        let rest = skip(bytes, 8)?;
+        let (major_version, rest) = deserialize_into::<u32>(rest)?;
+        let (minor_version, _) = deserialize_into::<u32>(rest)?;
+        Ok(QueryVersionReply {
+            major_version,
+            minor_version,
+        })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        check_trailing_bytes("dri3::QueryVersionReply", bytes, 32)
+    }
+}
+
+/// Reply of the DRI3Open request. The file descriptor of the device is passed
+/// along with it.
+#[derive(Debug, Clone)]
+pub struct OpenReply {
+    /// Number of file descriptors passed with the reply, always 1.
+    pub nfd: u8,
+}
+
+impl Reply for OpenReply {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1  1                     nfd
+        // 2  CARD16                sequence number
+        // 4  0                     reply length
+        // 24                       unused
+        let rest = skip(bytes, 1)?;
+        let (nfd, _) = deserialize_into::<u8>(rest)?;
+        Ok(OpenReply { nfd })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        check_nfd("dri3::OpenReply", self.nfd, 1)?;
+        check_trailing_bytes("dri3::OpenReply", bytes, 32)
+    }
+}
+
+/// Reply of the DRI3BufferFromPixmap request: the layout of the single plane
+/// buffer whose file descriptor is passed along with it.
+#[derive(Debug, Clone)]
+pub struct BufferFromPixmapReply {
+    /// Number of file descriptors passed with the reply, always 1.
+    pub nfd: u8,
+    /// Size of the buffer in bytes.
+    pub size: u32,
+    pub width: u16,
+    pub height: u16,
+    /// Bytes per row.
+    pub stride: u16,
+    pub depth: u8,
+    /// Bits per pixel.
+    pub bpp: u8,
+}
+
+impl Reply for BufferFromPixmapReply {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1  1                     nfd
+        // 2  CARD16                sequence number
+        // 4  0                     reply length
+        // 4  CARD32                size
+        // 2  CARD16                width
+        // 2  CARD16                height
+        // 2  CARD16                stride
+        // 1  CARD8                 depth
+        // 1  CARD8                 bpp
+        // 12                       unused
+        let rest = skip(bytes, 1)?;
+        let (nfd, rest) = deserialize_into::<u8>(rest)?;
+        let rest = skip(rest, 6)?;
+        let (size, rest) = deserialize_into::<u32>(rest)?;
+        let (width, rest) = deserialize_into::<u16>(rest)?;
+        let (height, rest) = deserialize_into::<u16>(rest)?;
+        let (stride, rest) = deserialize_into::<u16>(rest)?;
+        let (depth, rest) = deserialize_into::<u8>(rest)?;
+        let (bpp, _) = deserialize_into::<u8>(rest)?;
+        Ok(BufferFromPixmapReply {
+            nfd,
+            size,
+            width,
+            height,
+            stride,
+            depth,
+            bpp,
+        })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        check_nfd("dri3::BufferFromPixmapReply", self.nfd, 1)?;
+        check_trailing_bytes("dri3::BufferFromPixmapReply", bytes, 32)
+    }
+}
+
+/// Reply of the DRI3BuffersFromPixmap request: the layout of the buffer whose
+/// planes are passed along with it, one file descriptor each.
+#[derive(Debug, Clone)]
+pub struct BuffersFromPixmapReply {
+    /// Number of planes, and of file descriptors passed with the reply.
+    pub nfd: u8,
+    pub width: u16,
+    pub height: u16,
+    /// The DRM format modifier of the buffer.
+    pub modifier: u64,
+    pub depth: u8,
+    /// Bits per pixel.
+    pub bpp: u8,
+    /// Bytes per row of each plane.
+    pub strides: Vec<u32>,
+    /// Offset of each plane in its buffer.
+    pub offsets: Vec<u32>,
+}
+
+impl Reply for BuffersFromPixmapReply {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1  CARD8                 nfd
+        // 2  CARD16                sequence number
+        // 4  2nfd                  reply length
+        // 2  CARD16                width
+        // 2  CARD16                height
+        // 4                        unused
+        // 8  CARD64                modifier
+        // 1  CARD8                 depth
+        // 1  CARD8                 bpp
+        // 6                        unused
+        // 4nfd LISTofCARD32        strides
+        // 4nfd LISTofCARD32        offsets
+        let rest = skip(bytes, 1)?;
+        let (nfd, rest) = deserialize_into::<u8>(rest)?;
+        let rest = skip(rest, 6)?;
+        let (width, rest) = deserialize_into::<u16>(rest)?;
+        let (height, rest) = deserialize_into::<u16>(rest)?;
+        let rest = skip(rest, 4)?;
+        let (modifier, rest) = deserialize_into::<u64>(rest)?;
+        let (depth, rest) = deserialize_into::<u8>(rest)?;
+        let (bpp, rest) = deserialize_into::<u8>(rest)?;
+        let rest = skip(rest, 6)?;
+        let (strides, rest) = deserialize_into_vec::<u32>(rest, nfd.into())?;
+        let (offsets, _) = deserialize_into_vec::<u32>(rest, nfd.into())?;
+        Ok(BuffersFromPixmapReply {
+            nfd,
+            width,
+            height,
+            modifier,
+            depth,
+            bpp,
+            strides,
+            offsets,
+        })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        let length = 32 + 8 * usize::from(self.nfd);
+        check_trailing_bytes("dri3::BuffersFromPixmapReply", bytes, length)
+    }
+}
+
+/// A plane of a buffer given to [Connection::dri3_pixmap_from_buffers].
+#[derive(Debug, Clone, Copy)]
+pub struct Plane<'a> {
+    pub fd: BorrowedFd<'a>,
+    /// Bytes per row.
+    pub stride: u32,
+    /// Offset of the plane in the buffer.
+    pub offset: u32,
+}
+
+impl Connection {
+    /// Tells the server the version of the extension the client supports and
+    /// returns the version the server supports.
+    pub fn dri3_query_version(
+        &mut self,
+        major_version: u32,
+        minor_version: u32,
+    ) -> Result<Cookie<QueryVersionReply>, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (0)
+        //   2 bytes    Request length (3)
+        //   4 bytes    Client major version
+        //   4 bytes    Client minor version
+        let request = request(self, opcode::QUERY_VERSION)?
+            .put(major_version)
+            .put(minor_version)
+            .finish();
+        self.send_request_with_reply(&request)
+    }
+
+    /// Returns a file descriptor of the DRM device the server renders `drawable`
+    /// with. `provider` is a RandR provider, or 0 for the default one.
+    pub fn dri3_open(&mut self, drawable: XId, provider: u32) -> Result<OwnedFd, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (1)
+        //   2 bytes    Request length (3)
+        //   4 bytes    Drawable
+        //   4 bytes    Provider
+        let request = request(self, opcode::OPEN)?
+            .put(drawable)
+            .put(provider)
+            .finish();
+        let cookie: Cookie<OpenReply> = self.send_request_with_fd_reply(&request, &[])?;
+        let (_, fds) = cookie.reply_with_fds(self)?;
+        fds.into_iter()
+            .next()
+            .ok_or(ConnectionError::InvalidResponseFromServer)
+    }
+
+    /// Creates a pixmap backed by a single plane buffer and returns its id. The
+    /// pixmap lives on the screen of `drawable`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn dri3_pixmap_from_buffer(
+        &mut self,
+        drawable: XId,
+        buffer: impl AsFd,
+        size: u32,
+        width: u16,
+        height: u16,
+        stride: u16,
+        depth: u8,
+        bpp: u8,
+    ) -> Result<XId, ConnectionError> {
+        let pixmap = self.generate_id()?;
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (2)
+        //   2 bytes    Request length (6)
+        //   4 bytes    Pixmap
+        //   4 bytes    Drawable
+        //   4 bytes    Size
+        //   2 bytes    Width
+        //   2 bytes    Height
+        //   2 bytes    Stride
+        //   1 byte     Depth
+        //   1 byte     Bits per pixel
+        //   (1 file descriptor passed as ancillary data)
+        let request = request(self, opcode::PIXMAP_FROM_BUFFER)?
+            .put(pixmap)
+            .put(drawable)
+            .put(size)
+            .put(width)
+            .put(height)
+            .put(stride)
+            .put(depth)
+            .put(bpp)
+            .finish();
+        self.send_request_with_fds(&request, &[buffer.as_fd()])?;
+        Ok(pixmap)
+    }
+
+    /// Returns the single plane buffer backing a pixmap, along with its layout.
+    pub fn dri3_buffer_from_pixmap(
+        &mut self,
+        pixmap: XId,
+    ) -> Result<(BufferFromPixmapReply, OwnedFd), ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (3)
+        //   2 bytes    Request length (2)
+        //   4 bytes    Pixmap
+        let request = request(self, opcode::BUFFER_FROM_PIXMAP)?
+            .put(pixmap)
+            .finish();
+        let cookie: Cookie<BufferFromPixmapReply> =
+            self.send_request_with_fd_reply(&request, &[])?;
+        let (reply, fds) = cookie.reply_with_fds(self)?;
+        let fd = fds
+            .into_iter()
+            .next()
+            .ok_or(ConnectionError::InvalidResponseFromServer)?;
+        Ok((reply, fd))
+    }
+
+    /// Creates a pixmap backed by a buffer of up to [MAX_PLANES] planes with a
+    /// DRM format modifier, and returns its id. Requires DRI3 1.2.
+    #[allow(clippy::too_many_arguments)]
+    pub fn dri3_pixmap_from_buffers(
+        &mut self,
+        window: Window,
+        planes: &[Plane<'_>],
+        width: u16,
+        height: u16,
+        depth: u8,
+        bpp: u8,
+        modifier: u64,
+    ) -> Result<XId, ConnectionError> {
+        if planes.len() > MAX_PLANES {
+            return Err(SerializeError::TooLong {
+                field: "planes",
+                length: planes.len(),
+                max: MAX_PLANES,
+            }
+            .into());
+        }
+        let pixmap = self.generate_id()?;
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (7)
+        //   2 bytes    Request length (16)
+        //   4 bytes    Pixmap
+        //   4 bytes    Window
+        //   1 byte     Number of buffers
+        //   3 bytes    Unused
+        //   2 bytes    Width
+        //   2 bytes    Height
+        //   4 bytes    Stride 0
+        //   4 bytes    Offset 0
+        //   ...        Strides and offsets 1 to 3, 0 for missing planes
+        //   1 byte     Depth
+        //   1 byte     Bits per pixel
+        //   2 bytes    Unused
+        //   8 bytes    Modifier
+        //   (1 file descriptor per plane passed as ancillary data)
+        let mut request = request(self, opcode::PIXMAP_FROM_BUFFERS)?;
+        request
+            .put(pixmap)
+            .put(window)
+            .put(planes.len() as u8)
+            .pad(3)
+            .put(width)
+            .put(height);
+        for i in 0..MAX_PLANES {
+            let (stride, offset) = planes
+                .get(i)
+                .map_or((0, 0), |plane| (plane.stride, plane.offset));
+            request.put(stride).put(offset);
+        }
+        request.put(depth).put(bpp).pad(2).put(modifier);
+        let fds: Vec<BorrowedFd<'_>> = planes.iter().map(|plane| plane.fd).collect();
+        self.send_request_with_fds(&request.finish(), &fds)?;
+        Ok(pixmap)
+    }
+
+    /// Returns the planes of the buffer backing a pixmap, one file descriptor
+    /// each, along with its layout. Requires DRI3 1.2.
+    pub fn dri3_buffers_from_pixmap(
+        &mut self,
+        pixmap: XId,
+    ) -> Result<(BuffersFromPixmapReply, Vec<OwnedFd>), ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (8)
+        //   2 bytes    Request length (2)
+        //   4 bytes    Pixmap
+        let request = request(self, opcode::BUFFERS_FROM_PIXMAP)?
+            .put(pixmap)
+            .finish();
+        let cookie: Cookie<BuffersFromPixmapReply> =
+            self.send_request_with_fd_reply(&request, &[])?;
+        let (reply, fds) = cookie.reply_with_fds(self)?;
+        if fds.len() != usize::from(reply.nfd) {
+            return Err(ConnectionError::InvalidResponseFromServer);
+        }
+        Ok((reply, fds))
+    }
+}
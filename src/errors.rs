@@ -1,43 +1,140 @@
-use std::{error::Error, fmt};
+use crate::protocol::{VisualId, XString};
+use crate::reply::GrabStatus;
+use crate::request::request_name;
+use crate::utils::{deserialize_into, skip};
+use std::{error::Error, fmt, io};
 
-/// Represents errors that may occur while attempting to establish a connection.
-///
-/// TODO: Should provide more specific information about what went wrong during the
-/// connection process.
+/// Represents errors that may occur while attempting to establish a connection
+/// or while talking to the server over it.
 #[derive(Debug)]
 pub enum ConnectionError {
-    InvalidSocketPath,
-    ConnectionRefused,
-    ConnectionNotEstablished,
-    FurtherAuthenticationRequired,
+    /// The DISPLAY environment variable is not set or is not valid unicode.
+    DisplayNotSet,
+    /// Neither the unix socket nor the TCP address of the display could be connected to.
+    Connect {
+        /// The display name, e.g. `:0`.
+        display: String,
+        /// The socket paths and addresses that were tried, in order.
+        attempts: Vec<ConnectAttempt>,
+    },
+    /// The server refused the connection setup.
+    ConnectionRefused {
+        /// The reason given by the server, e.g. `Authorization required, but no authorization protocol specified`.
+        reason: XString,
+    },
+    /// The server requires further authentication, which is not supported.
+    FurtherAuthenticationRequired {
+        reason: XString,
+    },
     InvalidResponseFromServer,
+    /// Reading from or writing to the stream failed.
+    Io(io::Error),
+    /// A message received from the server could not be parsed.
+    Parse(ParseError),
+    /// A request could not be serialized.
+    Serialize(SerializeError),
+    /// The server answered a request with an error.
+    ProtocolError(XError),
+    /// All resource ids assigned to the client are in use.
+    ResourceIdsExhausted,
+    /// The server does not support the extension with the given name.
+    ExtensionNotSupported(&'static str),
+    /// The server closed the connection, or it broke. Every further request
+    /// and pending reply fails with this error.
+    Disconnected,
+    /// Another client did not answer in time, e.g. the owner of a selection.
+    Timeout,
+    /// Pixels cannot be converted to the visual with the given id, which is not
+    /// TrueColor or DirectColor.
+    UnsupportedVisual(VisualId),
+    /// The pointer or the keyboard could not be grabbed, usually because another
+    /// client grabs it.
+    GrabFailed(GrabStatus),
 }
 
-impl Error for ConnectionError {}
+/// A failed attempt to connect to a socket path or address.
+#[derive(Debug)]
+pub struct ConnectAttempt {
+    /// The unix socket path or the `host:port` address.
+    pub address: String,
+    pub error: io::Error,
+}
+
+impl ConnectionError {
+    /// Returns the error of the first connection attempt, which is the local unix
+    /// socket unless only TCP was tried. Its kind tells e.g. a missing socket
+    /// (`NotFound`) from a socket the user may not access (`PermissionDenied`).
+    pub fn connect_error(&self) -> Option<&io::Error> {
+        match self {
+            Self::Connect { attempts, .. } => attempts.first().map(|attempt| &attempt.error),
+            _ => None,
+        }
+    }
+}
+
+impl Error for ConnectionError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Connect { attempts, .. } => attempts
+                .first()
+                .map(|attempt| &attempt.error as &(dyn Error + 'static)),
+            Self::Io(err) => Some(err),
+            Self::Parse(err) => Some(err),
+            Self::Serialize(err) => Some(err),
+            Self::ProtocolError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ConnectionError {
+    fn from(err: io::Error) -> Self {
+        ConnectionError::Io(err)
+    }
+}
 
 impl From<ParseError> for ConnectionError {
-    fn from(_: ParseError) -> Self {
-        ConnectionError::ConnectionNotEstablished
+    fn from(err: ParseError) -> Self {
+        ConnectionError::Parse(err)
+    }
+}
+
+impl From<SerializeError> for ConnectionError {
+    fn from(err: SerializeError) -> Self {
+        ConnectionError::Serialize(err)
     }
 }
 
 impl fmt::Display for ConnectionError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::InvalidSocketPath => {
-                write!(f, "Failed to connect: The socket path is invalid")
-            }
-            Self::ConnectionNotEstablished => {
+            Self::DisplayNotSet => {
                 write!(
                     f,
-                    "Failed to connect: Connection to the server is not established"
+                    "Failed to connect: The DISPLAY environment variable is not set"
                 )
             }
-            Self::ConnectionRefused => {
-                write!(f, "Failed to connect: Connection is refused by the server")
+            Self::Connect { display, attempts } => {
+                write!(f, "Failed to connect to display {}", display)?;
+                for (i, attempt) in attempts.iter().enumerate() {
+                    let separator = if i == 0 { ":" } else { ";" };
+                    write!(f, "{} {}: {}", separator, attempt.address, attempt.error)?;
+                }
+                Ok(())
             }
-            Self::FurtherAuthenticationRequired => {
-                write!(f, "Failed to connect: Further authentication is required")
+            Self::ConnectionRefused { reason } => {
+                write!(
+                    f,
+                    "Failed to connect: Connection is refused by the server: {}",
+                    reason
+                )
+            }
+            Self::FurtherAuthenticationRequired { reason } => {
+                write!(
+                    f,
+                    "Failed to connect: Further authentication is required: {}",
+                    reason
+                )
             }
             Self::InvalidResponseFromServer => {
                 write!(
@@ -45,18 +142,136 @@ impl fmt::Display for ConnectionError {
                     "Failed to connect: Invalid response from server on connection setup"
                 )
             }
+            Self::Io(err) => {
+                write!(f, "Connection error: {}", err)
+            }
+            Self::Parse(err) => {
+                write!(f, "Connection error: {}", err)
+            }
+            Self::Serialize(err) => {
+                write!(f, "Request failed: {}", err)
+            }
+            Self::ProtocolError(err) => {
+                write!(f, "Request failed: {}", err)
+            }
+            Self::ResourceIdsExhausted => {
+                write!(f, "Request failed: No resource ids left to allocate")
+            }
+            Self::ExtensionNotSupported(name) => {
+                write!(
+                    f,
+                    "Request failed: The server does not support the {} extension",
+                    name
+                )
+            }
+            Self::Disconnected => {
+                write!(
+                    f,
+                    "Connection error: The connection to the server was closed"
+                )
+            }
+            Self::Timeout => {
+                write!(f, "Request failed: Timed out waiting for another client")
+            }
+            Self::UnsupportedVisual(visual) => {
+                write!(
+                    f,
+                    "Request failed: Visual {:#x} is not TrueColor or DirectColor",
+                    visual
+                )
+            }
+            Self::GrabFailed(status) => {
+                let reason = match *status {
+                    GrabStatus::ALREADY_GRABBED => "another client grabs it",
+                    GrabStatus::INVALID_TIME => "the time is invalid",
+                    GrabStatus::NOT_VIEWABLE => "the window is not viewable",
+                    GrabStatus::FROZEN => "another client froze it",
+                    _ => "unknown status",
+                };
+                write!(f, "Request failed: The grab failed: {}", reason)
+            }
         }
     }
 }
 
 /// Represents errors that may occur while serializing or deserializing sequence of x11 bytes.
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ParseError {
-    OutOfBound,
-    NotEnoughData,
-    OverFlow,
-    Failed,
-    InvalidLength,
+    /// The data ended before a value could be parsed.
+    NotEnoughData {
+        /// The type that was being parsed, e.g. `u16`.
+        type_name: &'static str,
+        /// Offset of the value from the start of the message, once known.
+        /// See [ParseError::in_message].
+        offset: Option<usize>,
+        /// Number of bytes the value needs.
+        needed: usize,
+        /// Number of bytes that were left.
+        available: usize,
+    },
+    /// A value was parsed from a slice that is not exactly its size.
+    InvalidLength {
+        type_name: &'static str,
+        expected: usize,
+        actual: usize,
+    },
+    /// The length of a list does not fit in memory.
+    OverFlow { type_name: &'static str },
+    /// The message is longer than its contents. Only reported by strict parsing.
+    TrailingBytes {
+        type_name: &'static str,
+        count: usize,
+    },
+    /// A field holds a value the protocol does not allow. Only reported by strict parsing.
+    InvalidValue {
+        type_name: &'static str,
+        field: &'static str,
+        value: u32,
+    },
+    /// A length field announces more data than can be sane, e.g. a corrupted or
+    /// hostile reply claiming gigabytes. Reported before anything is allocated.
+    SuspiciousLength {
+        type_name: &'static str,
+        length: usize,
+        limit: usize,
+    },
+}
+
+impl ParseError {
+    pub(crate) fn not_enough_data(
+        type_name: &'static str,
+        needed: usize,
+        available: usize,
+    ) -> Self {
+        ParseError::NotEnoughData {
+            type_name,
+            offset: None,
+            needed,
+            available,
+        }
+    }
+
+    /// Resolves the offset of the failed value within `message`, the bytes that
+    /// were passed to the outermost parsing function.
+    ///
+    /// Parsers consume their input from the front, so a value that ran out of data
+    /// started `available` bytes before the end of the message.
+    pub fn in_message(self, message: &[u8]) -> Self {
+        match self {
+            ParseError::NotEnoughData {
+                type_name,
+                offset: None,
+                needed,
+                available,
+            } => ParseError::NotEnoughData {
+                type_name,
+                offset: message.len().checked_sub(available),
+                needed,
+                available,
+            },
+            other => other,
+        }
+    }
 }
 
 impl Error for ParseError {}
@@ -64,24 +279,421 @@ impl Error for ParseError {}
 impl fmt::Display for ParseError {
     fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            Self::OutOfBound => {
+            Self::NotEnoughData {
+                type_name,
+                offset,
+                needed,
+                available,
+            } => {
+                write!(formatter, "Failed to parse {}", type_name)?;
+                if let Some(offset) = offset {
+                    write!(formatter, " at byte {}", offset)?;
+                }
+                write!(
+                    formatter,
+                    ": Not enough data: {} bytes needed, {} available",
+                    needed, available
+                )
+            }
+            Self::InvalidLength {
+                type_name,
+                expected,
+                actual,
+            } => {
+                write!(
+                    formatter,
+                    "Failed to parse {}: Invalid data length: expected {} bytes, got {}",
+                    type_name, expected, actual
+                )
+            }
+            Self::OverFlow { type_name } => {
+                write!(
+                    formatter,
+                    "Failed to parse list of {}: data length overflow",
+                    type_name
+                )
+            }
+            Self::TrailingBytes { type_name, count } => {
+                write!(
+                    formatter,
+                    "Failed to parse {}: {} unexpected trailing bytes",
+                    type_name, count
+                )
+            }
+            Self::InvalidValue {
+                type_name,
+                field,
+                value,
+            } => {
                 write!(
                     formatter,
-                    "Failed to parse: The provided range is out of bound"
+                    "Failed to parse {}: Invalid value {} of {}",
+                    type_name, value, field
                 )
             }
-            Self::NotEnoughData => {
-                formatter.write_str("Failed to parse: Not enough data to parse: Out of bound")
+            Self::SuspiciousLength {
+                type_name,
+                length,
+                limit,
+            } => {
+                write!(
+                    formatter,
+                    "Failed to parse {}: Suspicious length of {} bytes, more than {}",
+                    type_name, length, limit
+                )
             }
-            Self::OverFlow => {
-                write!(formatter, "Failed to parse: data length overflow")
+        }
+    }
+}
+
+/// Represents errors that may occur while converting a value to x11 bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SerializeError {
+    /// A field is longer than its length field can describe.
+    TooLong {
+        field: &'static str,
+        length: usize,
+        max: usize,
+    },
+    /// Lists that must be of the same length are not.
+    LengthMismatch {
+        field: &'static str,
+        length: usize,
+        expected: usize,
+    },
+    /// A value is out of the range the request accepts.
+    OutOfRange { field: &'static str, value: u32 },
+}
+
+impl Error for SerializeError {}
+
+impl fmt::Display for SerializeError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::TooLong { field, length, max } => {
+                write!(
+                    formatter,
+                    "Failed to serialize: The {} is {} bytes long, at most {} are allowed",
+                    field, length, max
+                )
             }
-            Self::Failed => {
-                write!(formatter, "Failed to parse")
+            Self::LengthMismatch {
+                field,
+                length,
+                expected,
+            } => {
+                write!(
+                    formatter,
+                    "Failed to serialize: The {} has {} items, {} were expected",
+                    field, length, expected
+                )
             }
-            Self::InvalidLength => {
-                write!(formatter, "Failed to parse: Invalid data length")
+            Self::OutOfRange { field, value } => {
+                write!(
+                    formatter,
+                    "Failed to serialize: The {} cannot be {}",
+                    field, value
+                )
             }
         }
     }
 }
+
+/// The error codes of the core protocol.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorCode {
+    /// The major or minor opcode does not specify a valid request.
+    Request,
+    /// A numeric value falls outside the range of values accepted by the request.
+    Value,
+    /// A value for a WINDOW argument does not name a defined window.
+    Window,
+    /// A value for a PIXMAP argument does not name a defined pixmap.
+    Pixmap,
+    /// A value for an ATOM argument does not name a defined atom.
+    Atom,
+    /// A value for a CURSOR argument does not name a defined cursor.
+    Cursor,
+    /// A value for a FONT argument does not name a defined font.
+    Font,
+    /// An argument or pair of arguments has the correct type and range but fails
+    /// to match in some other way required by the request.
+    Match,
+    /// A value for a DRAWABLE argument does not name a defined window or pixmap.
+    Drawable,
+    /// An attempt to access a resource in a way that is not permitted.
+    Access,
+    /// The server failed to allocate the requested resource.
+    Alloc,
+    /// A value for a COLORMAP argument does not name a defined colormap.
+    Colormap,
+    /// A value for a GCONTEXT argument does not name a defined graphics context.
+    GContext,
+    /// The value chosen for a resource identifier is not in the range assigned to
+    /// the client, or is already in use.
+    IdChoice,
+    /// A font or color of the specified name does not exist.
+    Name,
+    /// The length of a request is shorter or longer than required.
+    Length,
+    /// The server does not implement some aspect of the request.
+    Implementation,
+    /// An error code outside of the core protocol, e.g. of an extension.
+    Other(u8),
+}
+
+impl From<u8> for ErrorCode {
+    fn from(code: u8) -> Self {
+        match code {
+            1 => ErrorCode::Request,
+            2 => ErrorCode::Value,
+            3 => ErrorCode::Window,
+            4 => ErrorCode::Pixmap,
+            5 => ErrorCode::Atom,
+            6 => ErrorCode::Cursor,
+            7 => ErrorCode::Font,
+            8 => ErrorCode::Match,
+            9 => ErrorCode::Drawable,
+            10 => ErrorCode::Access,
+            11 => ErrorCode::Alloc,
+            12 => ErrorCode::Colormap,
+            13 => ErrorCode::GContext,
+            14 => ErrorCode::IdChoice,
+            15 => ErrorCode::Name,
+            16 => ErrorCode::Length,
+            17 => ErrorCode::Implementation,
+            other => ErrorCode::Other(other),
+        }
+    }
+}
+
+impl From<ErrorCode> for u8 {
+    fn from(code: ErrorCode) -> u8 {
+        match code {
+            ErrorCode::Request => 1,
+            ErrorCode::Value => 2,
+            ErrorCode::Window => 3,
+            ErrorCode::Pixmap => 4,
+            ErrorCode::Atom => 5,
+            ErrorCode::Cursor => 6,
+            ErrorCode::Font => 7,
+            ErrorCode::Match => 8,
+            ErrorCode::Drawable => 9,
+            ErrorCode::Access => 10,
+            ErrorCode::Alloc => 11,
+            ErrorCode::Colormap => 12,
+            ErrorCode::GContext => 13,
+            ErrorCode::IdChoice => 14,
+            ErrorCode::Name => 15,
+            ErrorCode::Length => 16,
+            ErrorCode::Implementation => 17,
+            ErrorCode::Other(code) => code,
+        }
+    }
+}
+
+impl ErrorCode {
+    /// Returns the name of the error, e.g. `BadWindow`.
+    pub fn name(&self) -> Option<&'static str> {
+        let name = match self {
+            ErrorCode::Request => "BadRequest",
+            ErrorCode::Value => "BadValue",
+            ErrorCode::Window => "BadWindow",
+            ErrorCode::Pixmap => "BadPixmap",
+            ErrorCode::Atom => "BadAtom",
+            ErrorCode::Cursor => "BadCursor",
+            ErrorCode::Font => "BadFont",
+            ErrorCode::Match => "BadMatch",
+            ErrorCode::Drawable => "BadDrawable",
+            ErrorCode::Access => "BadAccess",
+            ErrorCode::Alloc => "BadAlloc",
+            ErrorCode::Colormap => "BadColormap",
+            ErrorCode::GContext => "BadGContext",
+            ErrorCode::IdChoice => "BadIDChoice",
+            ErrorCode::Name => "BadName",
+            ErrorCode::Length => "BadLength",
+            ErrorCode::Implementation => "BadImplementation",
+            ErrorCode::Other(_) => return None,
+        };
+        Some(name)
+    }
+
+    /// Returns true if the bad value of the error is a resource id.
+    pub fn is_resource_error(&self) -> bool {
+        matches!(
+            self,
+            ErrorCode::Window
+                | ErrorCode::Pixmap
+                | ErrorCode::Cursor
+                | ErrorCode::Font
+                | ErrorCode::Drawable
+                | ErrorCode::Colormap
+                | ErrorCode::GContext
+                | ErrorCode::IdChoice
+        )
+    }
+}
+
+/// An error sent by the server in response to a request.
+#[derive(Debug, Clone)]
+pub struct XError {
+    pub code: ErrorCode,
+    /// Sequence number of the failed request.
+    pub sequence: u16,
+    /// The offending value, resource id or atom, depending on the error code.
+    pub bad_value: u32,
+    pub minor_opcode: u16,
+    pub major_opcode: u8,
+    /// Name of the failed request, if the connection knows it.
+    pub request_name: Option<String>,
+    /// Name of an extension error, e.g. `RANDR:BadOutput`, if the connection knows it.
+    pub error_name: Option<String>,
+}
+
+impl XError {
+    /// Parses an error message.
+    //
+    //   1 byte     0 (Error)
+    //   1 byte     Code
+    //   2 bytes    Sequence number
+    //   4 bytes    Bad value (resource id, atom or value)
+    //   2 bytes    Minor opcode
+    //   1 byte     Major opcode
+    //   21 bytes   Unused
+    pub fn from_bytes(bytes: &[u8]) -> Result<XError, ParseError> {
+        let rest = skip(bytes, 1)?;
+        let (code, rest) = deserialize_into::<u8>(rest)?;
+        let (sequence, rest) = deserialize_into::<u16>(rest)?;
+        let (bad_value, rest) = deserialize_into::<u32>(rest)?;
+        let (minor_opcode, rest) = deserialize_into::<u16>(rest)?;
+        let (major_opcode, _) = deserialize_into::<u8>(rest)?;
+        Ok(XError {
+            code: ErrorCode::from(code),
+            sequence,
+            bad_value,
+            minor_opcode,
+            major_opcode,
+            request_name: None,
+            error_name: None,
+        })
+    }
+}
+
+impl Error for XError {}
+
+impl fmt::Display for XError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.error_name.as_deref().or_else(|| self.code.name()) {
+            Some(name) => write!(f, "{}", name)?,
+            None => write!(f, "Error {}", u8::from(self.code))?,
+        }
+        match self
+            .request_name
+            .as_deref()
+            .or_else(|| request_name(self.major_opcode))
+        {
+            Some(request) => write!(f, " for {}", request)?,
+            None => write!(
+                f,
+                " for request {}.{}",
+                self.major_opcode, self.minor_opcode
+            )?,
+        }
+        write!(f, " (seq {}", self.sequence)?;
+        match self.code {
+            code if code.is_resource_error() => write!(f, ", resource {:#x})", self.bad_value),
+            ErrorCode::Value => write!(f, ", value {})", self.bad_value),
+            ErrorCode::Atom => write!(f, ", atom {})", self.bad_value),
+            _ => write!(f, ")"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// An error as the server sends it, in the byte order of the machine.
+    fn error_bytes(code: u8, sequence: u16, bad_value: u32, minor: u16, major: u8) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        bytes[1] = code;
+        bytes[2..4].copy_from_slice(&sequence.to_ne_bytes());
+        bytes[4..8].copy_from_slice(&bad_value.to_ne_bytes());
+        bytes[8..10].copy_from_slice(&minor.to_ne_bytes());
+        bytes[10] = major;
+        bytes
+    }
+
+    #[test]
+    fn from_bytes_reads_the_fields() {
+        // BadWindow for MapWindow of 0x400001.
+        let error = XError::from_bytes(&error_bytes(3, 0x1234, 0x0040_0001, 0, 8)).unwrap();
+        assert_eq!(error.code, ErrorCode::Window);
+        assert_eq!(error.sequence, 0x1234);
+        assert_eq!(error.bad_value, 0x0040_0001);
+        assert_eq!(error.minor_opcode, 0);
+        assert_eq!(error.major_opcode, 8);
+        assert!(error.request_name.is_none());
+        assert!(error.error_name.is_none());
+
+        // An error of RANDR, for its GetOutputInfo.
+        let error = XError::from_bytes(&error_bytes(147, 7, 0x63, 9, 140)).unwrap();
+        assert_eq!(error.code, ErrorCode::Other(147));
+        assert_eq!((error.major_opcode, error.minor_opcode), (140, 9));
+    }
+
+    #[test]
+    fn from_bytes_rejects_short_errors() {
+        let bytes = error_bytes(3, 1, 2, 0, 8);
+        assert!(matches!(
+            XError::from_bytes(&bytes[..10]),
+            Err(ParseError::NotEnoughData { .. })
+        ));
+    }
+
+    #[test]
+    fn error_codes_convert_both_ways() {
+        for code in 0..=u8::MAX {
+            assert_eq!(u8::from(ErrorCode::from(code)), code);
+        }
+        assert_eq!(ErrorCode::from(17), ErrorCode::Implementation);
+        assert_eq!(ErrorCode::from(17).name(), Some("BadImplementation"));
+        assert_eq!(ErrorCode::from(18), ErrorCode::Other(18));
+        assert_eq!(ErrorCode::from(18).name(), None);
+        assert!(ErrorCode::IdChoice.is_resource_error());
+        assert!(!ErrorCode::Atom.is_resource_error());
+    }
+
+    #[test]
+    fn display_names_the_error_and_the_request() {
+        let display = |bytes: [u8; 32]| XError::from_bytes(&bytes).unwrap().to_string();
+        assert_eq!(
+            display(error_bytes(3, 0x1234, 0x0040_0001, 0, 8)),
+            "BadWindow for MapWindow (seq 4660, resource 0x400001)"
+        );
+        assert_eq!(
+            display(error_bytes(2, 12, 0xffff_ffff, 0, 18)),
+            "BadValue for ChangeProperty (seq 12, value 4294967295)"
+        );
+        assert_eq!(
+            display(error_bytes(5, 3, 9999, 0, 17)),
+            "BadAtom for GetAtomName (seq 3, atom 9999)"
+        );
+        assert_eq!(
+            display(error_bytes(8, 40, 0, 0, 127)),
+            "BadMatch for NoOperation (seq 40)"
+        );
+        assert_eq!(
+            display(error_bytes(147, 7, 0x63, 9, 140)),
+            "Error 147 for request 140.9 (seq 7)"
+        );
+
+        let mut error = XError::from_bytes(&error_bytes(147, 7, 0x63, 9, 140)).unwrap();
+        error.request_name = Some("RANDR:GetOutputInfo".into());
+        error.error_name = Some("RANDR:BadOutput".into());
+        assert_eq!(
+            error.to_string(),
+            "RANDR:BadOutput for RANDR:GetOutputInfo (seq 7)"
+        );
+    }
+}
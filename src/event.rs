@@ -0,0 +1,970 @@
+use crate::errors::ParseError;
+use crate::protocol::{Atom, ColorMap, KeyCode, Timestamp, Window, XId};
+use crate::utils::{deserialize_into, skip, Serialize};
+
+/// Every event sent by the server is exactly 32 bytes long, except the
+/// GenericEvent (code 35) which may carry additional data.
+pub const EVENT_SIZE: usize = 32;
+
+/// Event codes of the core protocol.
+pub mod code {
+    pub const KEY_PRESS: u8 = 2;
+    pub const KEY_RELEASE: u8 = 3;
+    pub const BUTTON_PRESS: u8 = 4;
+    pub const BUTTON_RELEASE: u8 = 5;
+    pub const MOTION_NOTIFY: u8 = 6;
+    pub const ENTER_NOTIFY: u8 = 7;
+    pub const LEAVE_NOTIFY: u8 = 8;
+    pub const FOCUS_IN: u8 = 9;
+    pub const FOCUS_OUT: u8 = 10;
+    pub const KEYMAP_NOTIFY: u8 = 11;
+    pub const EXPOSE: u8 = 12;
+    pub const GRAPHICS_EXPOSURE: u8 = 13;
+    pub const NO_EXPOSURE: u8 = 14;
+    pub const VISIBILITY_NOTIFY: u8 = 15;
+    pub const CREATE_NOTIFY: u8 = 16;
+    pub const DESTROY_NOTIFY: u8 = 17;
+    pub const UNMAP_NOTIFY: u8 = 18;
+    pub const MAP_NOTIFY: u8 = 19;
+    pub const MAP_REQUEST: u8 = 20;
+    pub const REPARENT_NOTIFY: u8 = 21;
+    pub const CONFIGURE_NOTIFY: u8 = 22;
+    pub const CONFIGURE_REQUEST: u8 = 23;
+    pub const GRAVITY_NOTIFY: u8 = 24;
+    pub const RESIZE_REQUEST: u8 = 25;
+    pub const CIRCULATE_NOTIFY: u8 = 26;
+    pub const CIRCULATE_REQUEST: u8 = 27;
+    pub const PROPERTY_NOTIFY: u8 = 28;
+    pub const SELECTION_CLEAR: u8 = 29;
+    pub const SELECTION_REQUEST: u8 = 30;
+    pub const SELECTION_NOTIFY: u8 = 31;
+    pub const COLORMAP_NOTIFY: u8 = 32;
+    pub const CLIENT_MESSAGE: u8 = 33;
+    pub const MAPPING_NOTIFY: u8 = 34;
+    pub const GENERIC_EVENT: u8 = 35;
+    /// Code of [super::Event::ConnectionClosed]. It is never sent by the server:
+    /// code 0 on the wire is an error.
+    pub const CONNECTION_CLOSED: u8 = 0;
+    /// Code of [super::Event::Wakeup]. It is never sent by the server: code 1 on
+    /// the wire is a reply.
+    pub const WAKEUP: u8 = 1;
+    /// Code of [super::Event::EventsDropped]. It is never sent by the server:
+    /// event codes have 7 bits without the SendEvent flag.
+    pub const EVENTS_DROPPED: u8 = 128;
+}
+
+/// An event generated by the x11 server.
+#[derive(Debug, Clone)]
+pub enum Event {
+    KeyPress(InputEvent),
+    KeyRelease(InputEvent),
+    ButtonPress(InputEvent),
+    ButtonRelease(InputEvent),
+    MotionNotify(InputEvent),
+    EnterNotify(CrossingEvent),
+    LeaveNotify(CrossingEvent),
+    FocusIn(FocusEvent),
+    FocusOut(FocusEvent),
+    KeymapNotify(KeymapNotifyEvent),
+    Expose(ExposeEvent),
+    GraphicsExposure(GraphicsExposureEvent),
+    NoExposure(NoExposureEvent),
+    VisibilityNotify(VisibilityNotifyEvent),
+    CreateNotify(CreateNotifyEvent),
+    DestroyNotify(DestroyNotifyEvent),
+    UnmapNotify(UnmapNotifyEvent),
+    MapNotify(MapNotifyEvent),
+    MapRequest(MapRequestEvent),
+    ReparentNotify(ReparentNotifyEvent),
+    ConfigureNotify(ConfigureNotifyEvent),
+    ConfigureRequest(ConfigureRequestEvent),
+    GravityNotify(GravityNotifyEvent),
+    ResizeRequest(ResizeRequestEvent),
+    CirculateNotify(CirculateEvent),
+    CirculateRequest(CirculateEvent),
+    PropertyNotify(PropertyNotifyEvent),
+    SelectionClear(SelectionClearEvent),
+    SelectionRequest(SelectionRequestEvent),
+    SelectionNotify(SelectionNotifyEvent),
+    ColormapNotify(ColormapNotifyEvent),
+    ClientMessage(ClientMessageEvent),
+    MappingNotify(MappingNotifyEvent),
+    /// An event of an extension using the GenericEvent (XGE) format.
+    Generic(GenericEvent),
+    /// An event the parser does not know about (most likely from an extension).
+    /// Holds the raw bytes of the event.
+    Unknown(Vec<u8>),
+    /// Queued by the connection, not sent by the server: the server closed the
+    /// connection or it broke. It is the last event of a connection.
+    ConnectionClosed,
+    /// Queued by the connection, not sent by the server: a
+    /// [crate::waker::Waker] woke the connection.
+    Wakeup,
+    /// Queued by the connection, not sent by the server: this many events were
+    /// dropped because the event queue was full, see
+    /// [crate::protocol_state::ProtocolState::set_event_queue_limit]. The events
+    /// following it arrived after the dropped ones.
+    EventsDropped(u64),
+}
+
+/// KeyPress, KeyRelease, ButtonPress, ButtonRelease and MotionNotify events
+/// share the same layout.
+#[derive(Debug, Clone)]
+pub struct InputEvent {
+    /// The keycode, the button or the motion hint depending on the event.
+    pub detail: u8,
+    pub time: Timestamp,
+    pub root: Window,
+    pub event: Window,
+    pub child: Window,
+    pub root_x: i16,
+    pub root_y: i16,
+    pub event_x: i16,
+    pub event_y: i16,
+    /// State of the modifier keys and pointer buttons just before the event.
+    pub state: u16,
+    pub same_screen: bool,
+}
+
+/// EnterNotify and LeaveNotify events.
+#[derive(Debug, Clone)]
+pub struct CrossingEvent {
+    pub detail: u8,
+    pub time: Timestamp,
+    pub root: Window,
+    pub event: Window,
+    pub child: Window,
+    pub root_x: i16,
+    pub root_y: i16,
+    pub event_x: i16,
+    pub event_y: i16,
+    pub state: u16,
+    pub mode: u8,
+    pub same_screen: bool,
+    pub focus: bool,
+}
+
+/// FocusIn and FocusOut events.
+#[derive(Debug, Clone)]
+pub struct FocusEvent {
+    pub detail: u8,
+    pub event: Window,
+    pub mode: u8,
+}
+
+#[derive(Debug, Clone)]
+pub struct KeymapNotifyEvent {
+    /// Bit vector of the keyboard state, starting at keycode 8.
+    pub keys: [u8; 31],
+}
+
+#[derive(Debug, Clone)]
+pub struct ExposeEvent {
+    pub window: Window,
+    pub x: u16,
+    pub y: u16,
+    pub width: u16,
+    pub height: u16,
+    /// Number of Expose events that are still to follow for this window.
+    pub count: u16,
+}
+
+#[derive(Debug, Clone)]
+pub struct GraphicsExposureEvent {
+    pub drawable: XId,
+    pub x: u16,
+    pub y: u16,
+    pub width: u16,
+    pub height: u16,
+    pub minor_opcode: u16,
+    pub count: u16,
+    pub major_opcode: u8,
+}
+
+#[derive(Debug, Clone)]
+pub struct NoExposureEvent {
+    pub drawable: XId,
+    pub minor_opcode: u16,
+    pub major_opcode: u8,
+}
+
+#[derive(Debug, Clone)]
+pub struct VisibilityNotifyEvent {
+    pub window: Window,
+    pub state: u8,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateNotifyEvent {
+    pub parent: Window,
+    pub window: Window,
+    pub x: i16,
+    pub y: i16,
+    pub width: u16,
+    pub height: u16,
+    pub border_width: u16,
+    pub override_redirect: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct DestroyNotifyEvent {
+    pub event: Window,
+    pub window: Window,
+}
+
+#[derive(Debug, Clone)]
+pub struct UnmapNotifyEvent {
+    pub event: Window,
+    pub window: Window,
+    pub from_configure: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct MapNotifyEvent {
+    pub event: Window,
+    pub window: Window,
+    pub override_redirect: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct MapRequestEvent {
+    pub parent: Window,
+    pub window: Window,
+}
+
+#[derive(Debug, Clone)]
+pub struct ReparentNotifyEvent {
+    pub event: Window,
+    pub window: Window,
+    pub parent: Window,
+    pub x: i16,
+    pub y: i16,
+    pub override_redirect: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConfigureNotifyEvent {
+    pub event: Window,
+    pub window: Window,
+    pub above_sibling: Window,
+    pub x: i16,
+    pub y: i16,
+    pub width: u16,
+    pub height: u16,
+    pub border_width: u16,
+    pub override_redirect: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConfigureRequestEvent {
+    pub stack_mode: u8,
+    pub parent: Window,
+    pub window: Window,
+    pub sibling: Window,
+    pub x: i16,
+    pub y: i16,
+    pub width: u16,
+    pub height: u16,
+    pub border_width: u16,
+    /// Which of the fields above were actually requested.
+    pub value_mask: u16,
+}
+
+#[derive(Debug, Clone)]
+pub struct GravityNotifyEvent {
+    pub event: Window,
+    pub window: Window,
+    pub x: i16,
+    pub y: i16,
+}
+
+#[derive(Debug, Clone)]
+pub struct ResizeRequestEvent {
+    pub window: Window,
+    pub width: u16,
+    pub height: u16,
+}
+
+/// CirculateNotify and CirculateRequest events.
+#[derive(Debug, Clone)]
+pub struct CirculateEvent {
+    pub event: Window,
+    pub window: Window,
+    /// 0 for Top, 1 for Bottom.
+    pub place: u8,
+}
+
+#[derive(Debug, Clone)]
+pub struct PropertyNotifyEvent {
+    pub window: Window,
+    pub atom: Atom,
+    pub time: Timestamp,
+    /// 0 for NewValue, 1 for Deleted.
+    pub state: u8,
+}
+
+#[derive(Debug, Clone)]
+pub struct SelectionClearEvent {
+    pub time: Timestamp,
+    pub owner: Window,
+    pub selection: Atom,
+}
+
+#[derive(Debug, Clone)]
+pub struct SelectionRequestEvent {
+    pub time: Timestamp,
+    pub owner: Window,
+    pub requestor: Window,
+    pub selection: Atom,
+    pub target: Atom,
+    pub property: Atom,
+}
+
+#[derive(Debug, Clone)]
+pub struct SelectionNotifyEvent {
+    pub time: Timestamp,
+    pub requestor: Window,
+    pub selection: Atom,
+    pub target: Atom,
+    pub property: Atom,
+}
+
+#[derive(Debug, Clone)]
+pub struct ColormapNotifyEvent {
+    pub window: Window,
+    pub colormap: ColorMap,
+    pub new: bool,
+    /// 0 for Uninstalled, 1 for Installed.
+    pub state: u8,
+}
+
+#[derive(Debug, Clone)]
+pub struct ClientMessageEvent {
+    /// Whether `data` should be interpreted as 8, 16 or 32 bit values.
+    pub format: u8,
+    pub window: Window,
+    pub message_type: Atom,
+    pub data: [u8; 20],
+}
+
+#[derive(Debug, Clone)]
+pub struct MappingNotifyEvent {
+    /// 0 for Modifier, 1 for Keyboard, 2 for Pointer.
+    pub request: u8,
+    pub first_keycode: KeyCode,
+    pub count: u8,
+}
+
+#[derive(Debug, Clone)]
+pub struct GenericEvent {
+    /// Major opcode of the extension that generated the event.
+    pub extension: u8,
+    pub event_type: u16,
+    /// The event bytes following the event type, including the additional data.
+    pub data: Vec<u8>,
+}
+
+impl Event {
+    /// Parses a single event from `bytes`.
+    ///
+    /// The most significant bit of the event code is set if the event was
+    /// generated by a SendEvent request. It is ignored here.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Event, ParseError> {
+        if bytes.len() < EVENT_SIZE {
+            return Err(ParseError::not_enough_data(
+                "Event",
+                EVENT_SIZE,
+                bytes.len(),
+            ));
+        }
+        let (response_type, rest) = deserialize_into::<u8>(bytes)?;
+        let (detail, rest) = deserialize_into::<u8>(rest)?;
+        let (_sequence, rest) = deserialize_into::<u16>(rest)?;
+
+        let event = match response_type & 0x7f {
+            code::KEY_PRESS => Event::KeyPress(InputEvent::from_bytes(detail, rest)?),
+            code::KEY_RELEASE => Event::KeyRelease(InputEvent::from_bytes(detail, rest)?),
+            code::BUTTON_PRESS => Event::ButtonPress(InputEvent::from_bytes(detail, rest)?),
+            code::BUTTON_RELEASE => Event::ButtonRelease(InputEvent::from_bytes(detail, rest)?),
+            code::MOTION_NOTIFY => Event::MotionNotify(InputEvent::from_bytes(detail, rest)?),
+            code::ENTER_NOTIFY => Event::EnterNotify(CrossingEvent::from_bytes(detail, rest)?),
+            code::LEAVE_NOTIFY => Event::LeaveNotify(CrossingEvent::from_bytes(detail, rest)?),
+            code::FOCUS_IN => Event::FocusIn(FocusEvent::from_bytes(detail, rest)?),
+            code::FOCUS_OUT => Event::FocusOut(FocusEvent::from_bytes(detail, rest)?),
+            code::KEYMAP_NOTIFY => {
+                // KeymapNotify has no sequence number. The key vector starts at byte 1.
+                let mut keys = [0u8; 31];
+                keys.copy_from_slice(&bytes[1..EVENT_SIZE]);
+                Event::KeymapNotify(KeymapNotifyEvent { keys })
+            }
+            code::EXPOSE => {
+                let (window, rest) = deserialize_into::<Window>(rest)?;
+                let (x, rest) = deserialize_into::<u16>(rest)?;
+                let (y, rest) = deserialize_into::<u16>(rest)?;
+                let (width, rest) = deserialize_into::<u16>(rest)?;
+                let (height, rest) = deserialize_into::<u16>(rest)?;
+                let (count, _) = deserialize_into::<u16>(rest)?;
+                Event::Expose(ExposeEvent {
+                    window,
+                    x,
+                    y,
+                    width,
+                    height,
+                    count,
+                })
+            }
+            code::GRAPHICS_EXPOSURE => {
+                let (drawable, rest) = deserialize_into::<XId>(rest)?;
+                let (x, rest) = deserialize_into::<u16>(rest)?;
+                let (y, rest) = deserialize_into::<u16>(rest)?;
+                let (width, rest) = deserialize_into::<u16>(rest)?;
+                let (height, rest) = deserialize_into::<u16>(rest)?;
+                let (minor_opcode, rest) = deserialize_into::<u16>(rest)?;
+                let (count, rest) = deserialize_into::<u16>(rest)?;
+                let (major_opcode, _) = deserialize_into::<u8>(rest)?;
+                Event::GraphicsExposure(GraphicsExposureEvent {
+                    drawable,
+                    x,
+                    y,
+                    width,
+                    height,
+                    minor_opcode,
+                    count,
+                    major_opcode,
+                })
+            }
+            code::NO_EXPOSURE => {
+                let (drawable, rest) = deserialize_into::<XId>(rest)?;
+                let (minor_opcode, rest) = deserialize_into::<u16>(rest)?;
+                let (major_opcode, _) = deserialize_into::<u8>(rest)?;
+                Event::NoExposure(NoExposureEvent {
+                    drawable,
+                    minor_opcode,
+                    major_opcode,
+                })
+            }
+            code::VISIBILITY_NOTIFY => {
+                let (window, rest) = deserialize_into::<Window>(rest)?;
+                let (state, _) = deserialize_into::<u8>(rest)?;
+                Event::VisibilityNotify(VisibilityNotifyEvent { window, state })
+            }
+            code::CREATE_NOTIFY => {
+                let (parent, rest) = deserialize_into::<Window>(rest)?;
+                let (window, rest) = deserialize_into::<Window>(rest)?;
+                let (x, rest) = deserialize_into::<i16>(rest)?;
+                let (y, rest) = deserialize_into::<i16>(rest)?;
+                let (width, rest) = deserialize_into::<u16>(rest)?;
+                let (height, rest) = deserialize_into::<u16>(rest)?;
+                let (border_width, rest) = deserialize_into::<u16>(rest)?;
+                let (override_redirect, _) = deserialize_into::<bool>(rest)?;
+                Event::CreateNotify(CreateNotifyEvent {
+                    parent,
+                    window,
+                    x,
+                    y,
+                    width,
+                    height,
+                    border_width,
+                    override_redirect,
+                })
+            }
+            code::DESTROY_NOTIFY => {
+                let (event, rest) = deserialize_into::<Window>(rest)?;
+                let (window, _) = deserialize_into::<Window>(rest)?;
+                Event::DestroyNotify(DestroyNotifyEvent { event, window })
+            }
+            code::UNMAP_NOTIFY => {
+                let (event, rest) = deserialize_into::<Window>(rest)?;
+                let (window, rest) = deserialize_into::<Window>(rest)?;
+                let (from_configure, _) = deserialize_into::<bool>(rest)?;
+                Event::UnmapNotify(UnmapNotifyEvent {
+                    event,
+                    window,
+                    from_configure,
+                })
+            }
+            code::MAP_NOTIFY => {
+                let (event, rest) = deserialize_into::<Window>(rest)?;
+                let (window, rest) = deserialize_into::<Window>(rest)?;
+                let (override_redirect, _) = deserialize_into::<bool>(rest)?;
+                Event::MapNotify(MapNotifyEvent {
+                    event,
+                    window,
+                    override_redirect,
+                })
+            }
+            code::MAP_REQUEST => {
+                let (parent, rest) = deserialize_into::<Window>(rest)?;
+                let (window, _) = deserialize_into::<Window>(rest)?;
+                Event::MapRequest(MapRequestEvent { parent, window })
+            }
+            code::REPARENT_NOTIFY => {
+                let (event, rest) = deserialize_into::<Window>(rest)?;
+                let (window, rest) = deserialize_into::<Window>(rest)?;
+                let (parent, rest) = deserialize_into::<Window>(rest)?;
+                let (x, rest) = deserialize_into::<i16>(rest)?;
+                let (y, rest) = deserialize_into::<i16>(rest)?;
+                let (override_redirect, _) = deserialize_into::<bool>(rest)?;
+                Event::ReparentNotify(ReparentNotifyEvent {
+                    event,
+                    window,
+                    parent,
+                    x,
+                    y,
+                    override_redirect,
+                })
+            }
+            code::CONFIGURE_NOTIFY => {
+                let (event, rest) = deserialize_into::<Window>(rest)?;
+                let (window, rest) = deserialize_into::<Window>(rest)?;
+                let (above_sibling, rest) = deserialize_into::<Window>(rest)?;
+                let (x, rest) = deserialize_into::<i16>(rest)?;
+                let (y, rest) = deserialize_into::<i16>(rest)?;
+                let (width, rest) = deserialize_into::<u16>(rest)?;
+                let (height, rest) = deserialize_into::<u16>(rest)?;
+                let (border_width, rest) = deserialize_into::<u16>(rest)?;
+                let (override_redirect, _) = deserialize_into::<bool>(rest)?;
+                Event::ConfigureNotify(ConfigureNotifyEvent {
+                    event,
+                    window,
+                    above_sibling,
+                    x,
+                    y,
+                    width,
+                    height,
+                    border_width,
+                    override_redirect,
+                })
+            }
+            code::CONFIGURE_REQUEST => {
+                let (parent, rest) = deserialize_into::<Window>(rest)?;
+                let (window, rest) = deserialize_into::<Window>(rest)?;
+                let (sibling, rest) = deserialize_into::<Window>(rest)?;
+                let (x, rest) = deserialize_into::<i16>(rest)?;
+                let (y, rest) = deserialize_into::<i16>(rest)?;
+                let (width, rest) = deserialize_into::<u16>(rest)?;
+                let (height, rest) = deserialize_into::<u16>(rest)?;
+                let (border_width, rest) = deserialize_into::<u16>(rest)?;
+                let (value_mask, _) = deserialize_into::<u16>(rest)?;
+                Event::ConfigureRequest(ConfigureRequestEvent {
+                    stack_mode: detail,
+                    parent,
+                    window,
+                    sibling,
+                    x,
+                    y,
+                    width,
+                    height,
+                    border_width,
+                    value_mask,
+                })
+            }
+            code::GRAVITY_NOTIFY => {
+                let (event, rest) = deserialize_into::<Window>(rest)?;
+                let (window, rest) = deserialize_into::<Window>(rest)?;
+                let (x, rest) = deserialize_into::<i16>(rest)?;
+                let (y, _) = deserialize_into::<i16>(rest)?;
+                Event::GravityNotify(GravityNotifyEvent {
+                    event,
+                    window,
+                    x,
+                    y,
+                })
+            }
+            code::RESIZE_REQUEST => {
+                let (window, rest) = deserialize_into::<Window>(rest)?;
+                let (width, rest) = deserialize_into::<u16>(rest)?;
+                let (height, _) = deserialize_into::<u16>(rest)?;
+                Event::ResizeRequest(ResizeRequestEvent {
+                    window,
+                    width,
+                    height,
+                })
+            }
+            code::CIRCULATE_NOTIFY => Event::CirculateNotify(CirculateEvent::from_bytes(rest)?),
+            code::CIRCULATE_REQUEST => Event::CirculateRequest(CirculateEvent::from_bytes(rest)?),
+            code::PROPERTY_NOTIFY => {
+                let (window, rest) = deserialize_into::<Window>(rest)?;
+                let (atom, rest) = deserialize_into::<Atom>(rest)?;
+                let (time, rest) = deserialize_into::<Timestamp>(rest)?;
+                let (state, _) = deserialize_into::<u8>(rest)?;
+                Event::PropertyNotify(PropertyNotifyEvent {
+                    window,
+                    atom,
+                    time,
+                    state,
+                })
+            }
+            code::SELECTION_CLEAR => {
+                let (time, rest) = deserialize_into::<Timestamp>(rest)?;
+                let (owner, rest) = deserialize_into::<Window>(rest)?;
+                let (selection, _) = deserialize_into::<Atom>(rest)?;
+                Event::SelectionClear(SelectionClearEvent {
+                    time,
+                    owner,
+                    selection,
+                })
+            }
+            code::SELECTION_REQUEST => {
+                let (time, rest) = deserialize_into::<Timestamp>(rest)?;
+                let (owner, rest) = deserialize_into::<Window>(rest)?;
+                let (requestor, rest) = deserialize_into::<Window>(rest)?;
+                let (selection, rest) = deserialize_into::<Atom>(rest)?;
+                let (target, rest) = deserialize_into::<Atom>(rest)?;
+                let (property, _) = deserialize_into::<Atom>(rest)?;
+                Event::SelectionRequest(SelectionRequestEvent {
+                    time,
+                    owner,
+                    requestor,
+                    selection,
+                    target,
+                    property,
+                })
+            }
+            code::SELECTION_NOTIFY => {
+                let (time, rest) = deserialize_into::<Timestamp>(rest)?;
+                let (requestor, rest) = deserialize_into::<Window>(rest)?;
+                let (selection, rest) = deserialize_into::<Atom>(rest)?;
+                let (target, rest) = deserialize_into::<Atom>(rest)?;
+                let (property, _) = deserialize_into::<Atom>(rest)?;
+                Event::SelectionNotify(SelectionNotifyEvent {
+                    time,
+                    requestor,
+                    selection,
+                    target,
+                    property,
+                })
+            }
+            code::COLORMAP_NOTIFY => {
+                let (window, rest) = deserialize_into::<Window>(rest)?;
+                let (colormap, rest) = deserialize_into::<ColorMap>(rest)?;
+                let (new, rest) = deserialize_into::<bool>(rest)?;
+                let (state, _) = deserialize_into::<u8>(rest)?;
+                Event::ColormapNotify(ColormapNotifyEvent {
+                    window,
+                    colormap,
+                    new,
+                    state,
+                })
+            }
+            code::CLIENT_MESSAGE => {
+                let (window, rest) = deserialize_into::<Window>(rest)?;
+                let (message_type, rest) = deserialize_into::<Atom>(rest)?;
+                let mut data = [0u8; 20];
+                data.copy_from_slice(&rest[..20]);
+                Event::ClientMessage(ClientMessageEvent {
+                    format: detail,
+                    window,
+                    message_type,
+                    data,
+                })
+            }
+            code::MAPPING_NOTIFY => {
+                let (request, rest) = deserialize_into::<u8>(rest)?;
+                let (first_keycode, rest) = deserialize_into::<KeyCode>(rest)?;
+                let (count, _) = deserialize_into::<u8>(rest)?;
+                Event::MappingNotify(MappingNotifyEvent {
+                    request,
+                    first_keycode,
+                    count,
+                })
+            }
+            code::GENERIC_EVENT => {
+                // 4 bytes length, 2 bytes event type, then the event data.
+                let rest = skip(rest, 4)?;
+                let (event_type, rest) = deserialize_into::<u16>(rest)?;
+                Event::Generic(GenericEvent {
+                    extension: detail,
+                    event_type,
+                    data: rest.to_vec(),
+                })
+            }
+            _ => Event::Unknown(bytes.to_vec()),
+        };
+        Ok(event)
+    }
+
+    /// The event code of the event, without the SendEvent flag.
+    pub fn code(&self) -> u8 {
+        match self {
+            Event::KeyPress(_) => code::KEY_PRESS,
+            Event::KeyRelease(_) => code::KEY_RELEASE,
+            Event::ButtonPress(_) => code::BUTTON_PRESS,
+            Event::ButtonRelease(_) => code::BUTTON_RELEASE,
+            Event::MotionNotify(_) => code::MOTION_NOTIFY,
+            Event::EnterNotify(_) => code::ENTER_NOTIFY,
+            Event::LeaveNotify(_) => code::LEAVE_NOTIFY,
+            Event::FocusIn(_) => code::FOCUS_IN,
+            Event::FocusOut(_) => code::FOCUS_OUT,
+            Event::KeymapNotify(_) => code::KEYMAP_NOTIFY,
+            Event::Expose(_) => code::EXPOSE,
+            Event::GraphicsExposure(_) => code::GRAPHICS_EXPOSURE,
+            Event::NoExposure(_) => code::NO_EXPOSURE,
+            Event::VisibilityNotify(_) => code::VISIBILITY_NOTIFY,
+            Event::CreateNotify(_) => code::CREATE_NOTIFY,
+            Event::DestroyNotify(_) => code::DESTROY_NOTIFY,
+            Event::UnmapNotify(_) => code::UNMAP_NOTIFY,
+            Event::MapNotify(_) => code::MAP_NOTIFY,
+            Event::MapRequest(_) => code::MAP_REQUEST,
+            Event::ReparentNotify(_) => code::REPARENT_NOTIFY,
+            Event::ConfigureNotify(_) => code::CONFIGURE_NOTIFY,
+            Event::ConfigureRequest(_) => code::CONFIGURE_REQUEST,
+            Event::GravityNotify(_) => code::GRAVITY_NOTIFY,
+            Event::ResizeRequest(_) => code::RESIZE_REQUEST,
+            Event::CirculateNotify(_) => code::CIRCULATE_NOTIFY,
+            Event::CirculateRequest(_) => code::CIRCULATE_REQUEST,
+            Event::PropertyNotify(_) => code::PROPERTY_NOTIFY,
+            Event::SelectionClear(_) => code::SELECTION_CLEAR,
+            Event::SelectionRequest(_) => code::SELECTION_REQUEST,
+            Event::SelectionNotify(_) => code::SELECTION_NOTIFY,
+            Event::ColormapNotify(_) => code::COLORMAP_NOTIFY,
+            Event::ClientMessage(_) => code::CLIENT_MESSAGE,
+            Event::MappingNotify(_) => code::MAPPING_NOTIFY,
+            Event::Generic(_) => code::GENERIC_EVENT,
+            Event::Unknown(bytes) => bytes[0] & 0x7f,
+            Event::ConnectionClosed => code::CONNECTION_CLOSED,
+            Event::Wakeup => code::WAKEUP,
+            Event::EventsDropped(_) => code::EVENTS_DROPPED,
+        }
+    }
+
+    /// The window the event was reported to, if the event is reported to a window.
+    pub fn window(&self) -> Option<Window> {
+        let window = match self {
+            Event::KeyPress(e)
+            | Event::KeyRelease(e)
+            | Event::ButtonPress(e)
+            | Event::ButtonRelease(e)
+            | Event::MotionNotify(e) => e.event,
+            Event::EnterNotify(e) | Event::LeaveNotify(e) => e.event,
+            Event::FocusIn(e) | Event::FocusOut(e) => e.event,
+            Event::Expose(e) => e.window,
+            Event::GraphicsExposure(e) => e.drawable,
+            Event::NoExposure(e) => e.drawable,
+            Event::VisibilityNotify(e) => e.window,
+            Event::CreateNotify(e) => e.parent,
+            Event::DestroyNotify(e) => e.event,
+            Event::UnmapNotify(e) => e.event,
+            Event::MapNotify(e) => e.event,
+            Event::MapRequest(e) => e.parent,
+            Event::ReparentNotify(e) => e.event,
+            Event::ConfigureNotify(e) => e.event,
+            Event::ConfigureRequest(e) => e.parent,
+            Event::GravityNotify(e) => e.event,
+            Event::ResizeRequest(e) => e.window,
+            Event::CirculateNotify(e) | Event::CirculateRequest(e) => e.event,
+            Event::PropertyNotify(e) => e.window,
+            Event::SelectionClear(e) => e.owner,
+            Event::SelectionRequest(e) => e.owner,
+            Event::SelectionNotify(e) => e.requestor,
+            Event::ColormapNotify(e) => e.window,
+            Event::ClientMessage(e) => e.window,
+            Event::KeymapNotify(_)
+            | Event::MappingNotify(_)
+            | Event::Generic(_)
+            | Event::Unknown(_)
+            | Event::ConnectionClosed
+            | Event::Wakeup
+            | Event::EventsDropped(_) => return None,
+        };
+        Some(window)
+    }
+}
+
+impl ClientMessageEvent {
+    /// Creates a ClientMessage carrying 20 8-bit values.
+    pub fn new_format8(window: Window, message_type: Atom, data: [u8; 20]) -> Self {
+        Self {
+            format: 8,
+            window,
+            message_type,
+            data,
+        }
+    }
+
+    /// Creates a ClientMessage carrying 10 16-bit values.
+    pub fn new_format16(window: Window, message_type: Atom, data: [u16; 10]) -> Self {
+        let mut bytes = [0u8; 20];
+        for (chunk, value) in bytes.chunks_exact_mut(2).zip(data) {
+            chunk.copy_from_slice(&value.to_ne_bytes());
+        }
+        Self {
+            format: 16,
+            window,
+            message_type,
+            data: bytes,
+        }
+    }
+
+    /// Creates a ClientMessage carrying 5 32-bit values, the format used by EWMH and ICCCM.
+    pub fn new_format32(window: Window, message_type: Atom, data: [u32; 5]) -> Self {
+        let mut bytes = [0u8; 20];
+        for (chunk, value) in bytes.chunks_exact_mut(4).zip(data) {
+            chunk.copy_from_slice(&value.to_ne_bytes());
+        }
+        Self {
+            format: 32,
+            window,
+            message_type,
+            data: bytes,
+        }
+    }
+
+    /// Returns the data as 8-bit values.
+    pub fn data8(&self) -> [u8; 20] {
+        self.data
+    }
+
+    /// Returns the data as 16-bit values.
+    pub fn data16(&self) -> [u16; 10] {
+        let mut values = [0u16; 10];
+        for (value, chunk) in values.iter_mut().zip(self.data.chunks_exact(2)) {
+            *value = u16::from_ne_bytes([chunk[0], chunk[1]]);
+        }
+        values
+    }
+
+    /// Returns the data as 32-bit values.
+    pub fn data32(&self) -> [u32; 5] {
+        let mut values = [0u32; 5];
+        for (value, chunk) in values.iter_mut().zip(self.data.chunks_exact(4)) {
+            *value = u32::from_ne_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        }
+        values
+    }
+
+    /// Converts the event to x11 raw bytes, e.g. to send it with SendEvent.
+    //
+    //   1 byte     Code (33)
+    //   1 byte     Format
+    //   2 bytes    Sequence number (set by the server)
+    //   4 bytes    Window
+    //   4 bytes    Type
+    //   20 bytes   Data
+    pub fn serialize(&self) -> [u8; 32] {
+        let mut bytes = Vec::with_capacity(EVENT_SIZE);
+        code::CLIENT_MESSAGE.serialize_into(&mut bytes);
+        self.format.serialize_into(&mut bytes);
+        0u16.serialize_into(&mut bytes);
+        self.window.serialize_into(&mut bytes);
+        self.message_type.serialize_into(&mut bytes);
+        bytes.extend_from_slice(&self.data);
+        let mut event = [0u8; 32];
+        event.copy_from_slice(&bytes);
+        event
+    }
+}
+
+impl SelectionNotifyEvent {
+    /// Converts the event to x11 raw bytes, to send it to the requestor with SendEvent.
+    //
+    //   1 byte     Code (31)
+    //   1 byte     Unused
+    //   2 bytes    Sequence number (set by the server)
+    //   4 bytes    Time
+    //   4 bytes    Requestor
+    //   4 bytes    Selection
+    //   4 bytes    Target
+    //   4 bytes    Property
+    //   8 bytes    Unused
+    pub fn serialize(&self) -> [u8; 32] {
+        let mut bytes = Vec::with_capacity(EVENT_SIZE);
+        code::SELECTION_NOTIFY.serialize_into(&mut bytes);
+        0u8.serialize_into(&mut bytes);
+        0u16.serialize_into(&mut bytes);
+        self.time.serialize_into(&mut bytes);
+        self.requestor.serialize_into(&mut bytes);
+        self.selection.serialize_into(&mut bytes);
+        self.target.serialize_into(&mut bytes);
+        self.property.serialize_into(&mut bytes);
+        let mut event = [0u8; 32];
+        event[..bytes.len()].copy_from_slice(&bytes);
+        event
+    }
+}
+
+impl InputEvent {
+    fn from_bytes(detail: u8, bytes: &[u8]) -> Result<Self, ParseError> {
+        let (time, rest) = deserialize_into::<Timestamp>(bytes)?;
+        let (root, rest) = deserialize_into::<Window>(rest)?;
+        let (event, rest) = deserialize_into::<Window>(rest)?;
+        let (child, rest) = deserialize_into::<Window>(rest)?;
+        let (root_x, rest) = deserialize_into::<i16>(rest)?;
+        let (root_y, rest) = deserialize_into::<i16>(rest)?;
+        let (event_x, rest) = deserialize_into::<i16>(rest)?;
+        let (event_y, rest) = deserialize_into::<i16>(rest)?;
+        let (state, rest) = deserialize_into::<u16>(rest)?;
+        let (same_screen, _) = deserialize_into::<bool>(rest)?;
+        Ok(InputEvent {
+            detail,
+            time,
+            root,
+            event,
+            child,
+            root_x,
+            root_y,
+            event_x,
+            event_y,
+            state,
+            same_screen,
+        })
+    }
+}
+
+impl CrossingEvent {
+    fn from_bytes(detail: u8, bytes: &[u8]) -> Result<Self, ParseError> {
+        let (time, rest) = deserialize_into::<Timestamp>(bytes)?;
+        let (root, rest) = deserialize_into::<Window>(rest)?;
+        let (event, rest) = deserialize_into::<Window>(rest)?;
+        let (child, rest) = deserialize_into::<Window>(rest)?;
+        let (root_x, rest) = deserialize_into::<i16>(rest)?;
+        let (root_y, rest) = deserialize_into::<i16>(rest)?;
+        let (event_x, rest) = deserialize_into::<i16>(rest)?;
+        let (event_y, rest) = deserialize_into::<i16>(rest)?;
+        let (state, rest) = deserialize_into::<u16>(rest)?;
+        let (mode, rest) = deserialize_into::<u8>(rest)?;
+        // same-screen is bit 1 and focus is bit 0
+        let (flags, _) = deserialize_into::<u8>(rest)?;
+        Ok(CrossingEvent {
+            detail,
+            time,
+            root,
+            event,
+            child,
+            root_x,
+            root_y,
+            event_x,
+            event_y,
+            state,
+            mode,
+            same_screen: flags & 0x02 != 0,
+            focus: flags & 0x01 != 0,
+        })
+    }
+}
+
+impl FocusEvent {
+    fn from_bytes(detail: u8, bytes: &[u8]) -> Result<Self, ParseError> {
+        let (event, rest) = deserialize_into::<Window>(bytes)?;
+        let (mode, _) = deserialize_into::<u8>(rest)?;
+        Ok(FocusEvent {
+            detail,
+            event,
+            mode,
+        })
+    }
+}
+
+impl CirculateEvent {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        let (event, rest) = deserialize_into::<Window>(bytes)?;
+        let (window, rest) = deserialize_into::<Window>(rest)?;
+        let rest = skip(rest, 4)?;
+        let (place, _) = deserialize_into::<u8>(rest)?;
+        Ok(CirculateEvent {
+            event,
+            window,
+            place,
+        })
+    }
+}
@@ -0,0 +1,195 @@
+//! Plugs a [Connection] into an existing event loop.
+//!
+//! The socket of the connection is available through [std::os::fd::AsFd]; once it
+//! is readable, [Connection::dispatch_events] reads what the server sent and hands
+//! every event to a callback. On top of that:
+//!
+//! - with the `calloop` feature, `ConnectionSource` is a calloop event source
+//!   calling the callback of the loop with every event,
+//! - with the `mio` feature, the connection is a `mio::event::Source` that can be
+//!   registered with a mio `Poll`.
+//!
+//! ```no_run
+//! # #[cfg(feature = "calloop")]
+//! # fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! use mousetrap::connection::Connection;
+//! use mousetrap::event_source::ConnectionSource;
+//!
+//! let mut event_loop = calloop::EventLoop::<()>::try_new()?;
+//! let source = ConnectionSource::new(Connection::init()?)?;
+//! event_loop
+//!     .handle()
+//!     .insert_source(source, |event, _conn, _| println!("{:?}", event))?;
+//! loop {
+//!     event_loop.dispatch(None, &mut ())?;
+//! }
+//! # }
+//! ```
+
+use crate::connection::Connection;
+use crate::errors::ConnectionError;
+use crate::event::Event;
+#[cfg(feature = "mio")]
+use std::os::fd::AsRawFd;
+
+impl Connection {
+    /// Reads what the server sent, without blocking, and calls `callback` with
+    /// every event until none is left.
+    ///
+    /// Meant to be called when the socket of the connection became readable in an
+    /// event loop. It reads until the socket would block, so it also suits
+    /// edge-triggered loops. Once the connection is closed, the callback gets
+    /// [Event::ConnectionClosed] and then [ConnectionError::Disconnected] is returned.
+    pub fn dispatch_events<F>(&mut self, mut callback: F) -> Result<(), ConnectionError>
+    where
+        F: FnMut(&mut Connection, Event),
+    {
+        while let Some(event) = self.poll_for_event()? {
+            callback(self, event);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "calloop")]
+pub use self::calloop_source::ConnectionSource;
+
+#[cfg(feature = "calloop")]
+mod calloop_source {
+    use crate::connection::Connection;
+    use crate::errors::ConnectionError;
+    use crate::event::Event;
+    use calloop::generic::Generic;
+    use calloop::{EventSource, Interest, Mode, Poll, PostAction, Readiness, Token, TokenFactory};
+    use std::io;
+    use std::os::fd::{AsFd, OwnedFd};
+
+    /// A calloop event source calling the callback of the loop with every event of
+    /// a connection, along with the connection.
+    ///
+    /// The source is removed from the loop once the connection is closed, after
+    /// [Event::ConnectionClosed] was dispatched.
+    pub struct ConnectionSource {
+        conn: Connection,
+        /// A duplicate of the socket of the connection, watched for readability.
+        socket: Generic<OwnedFd>,
+        /// Token under which the events queued while waiting on replies are
+        /// dispatched, as the socket does not become readable for those.
+        queued_token: Option<Token>,
+    }
+
+    impl ConnectionSource {
+        pub fn new(conn: Connection) -> io::Result<Self> {
+            let fd = conn.as_fd().try_clone_to_owned()?;
+            Ok(ConnectionSource {
+                conn,
+                socket: Generic::new(fd, Interest::READ, Mode::Level),
+                queued_token: None,
+            })
+        }
+
+        /// Returns the connection, e.g. to send requests.
+        pub fn connection(&mut self) -> &mut Connection {
+            &mut self.conn
+        }
+
+        pub fn into_connection(self) -> Connection {
+            self.conn
+        }
+    }
+
+    impl EventSource for ConnectionSource {
+        type Event = Event;
+        type Metadata = Connection;
+        type Ret = ();
+        type Error = ConnectionError;
+
+        const NEEDS_EXTRA_LIFECYCLE_EVENTS: bool = true;
+
+        fn process_events<F>(
+            &mut self,
+            _readiness: Readiness,
+            _token: Token,
+            mut callback: F,
+        ) -> Result<PostAction, ConnectionError>
+        where
+            F: FnMut(Event, &mut Connection),
+        {
+            // The socket is watched level-triggered, so there is nothing to re-arm.
+            match self
+                .conn
+                .dispatch_events(|conn, event| callback(event, conn))
+            {
+                Ok(()) => Ok(PostAction::Continue),
+                Err(ConnectionError::Disconnected) => Ok(PostAction::Remove),
+                Err(err) => Err(err),
+            }
+        }
+
+        fn register(
+            &mut self,
+            poll: &mut Poll,
+            token_factory: &mut TokenFactory,
+        ) -> calloop::Result<()> {
+            self.socket.register(poll, token_factory)?;
+            self.queued_token = Some(token_factory.token());
+            Ok(())
+        }
+
+        fn reregister(
+            &mut self,
+            poll: &mut Poll,
+            token_factory: &mut TokenFactory,
+        ) -> calloop::Result<()> {
+            self.socket.reregister(poll, token_factory)?;
+            self.queued_token = Some(token_factory.token());
+            Ok(())
+        }
+
+        fn unregister(&mut self, poll: &mut Poll) -> calloop::Result<()> {
+            self.queued_token = None;
+            self.socket.unregister(poll)
+        }
+
+        fn before_sleep(&mut self) -> calloop::Result<Option<(Readiness, Token)>> {
+            let readiness = Readiness {
+                readable: true,
+                writable: false,
+                error: false,
+            };
+            Ok(self
+                .queued_token
+                .filter(|_| self.conn.has_queued_events())
+                .map(|token| (readiness, token)))
+        }
+    }
+}
+
+/// Registers the socket of the connection with a mio `Poll`. Once it is
+/// readable, call [Connection::dispatch_events], which drains the socket as mio's
+/// edge-triggered readiness requires. Call it before polling too, as events read
+/// while waiting on replies are queued without the socket becoming readable.
+#[cfg(feature = "mio")]
+impl mio::event::Source for Connection {
+    fn register(
+        &mut self,
+        registry: &mio::Registry,
+        token: mio::Token,
+        interests: mio::Interest,
+    ) -> std::io::Result<()> {
+        mio::unix::SourceFd(&self.as_raw_fd()).register(registry, token, interests)
+    }
+
+    fn reregister(
+        &mut self,
+        registry: &mio::Registry,
+        token: mio::Token,
+        interests: mio::Interest,
+    ) -> std::io::Result<()> {
+        mio::unix::SourceFd(&self.as_raw_fd()).reregister(registry, token, interests)
+    }
+
+    fn deregister(&mut self, registry: &mio::Registry) -> std::io::Result<()> {
+        mio::unix::SourceFd(&self.as_raw_fd()).deregister(registry)
+    }
+}
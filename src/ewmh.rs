@@ -0,0 +1,356 @@
+//! The Extended Window Manager Hints: the _NET_* properties through which window
+//! managers, pagers and applications share the state of the desktop.
+//!
+//! The atoms are interned once per connection, on first use, see
+//! [Connection::ewmh_atoms]. The getters return the property as the window
+//! manager left it, or nothing if it is unset. Changes to the state of the
+//! desktop or of a managed window are asked of the window manager with a
+//! ClientMessage sent to the root window, so they take effect asynchronously.
+//!
+//! ```no_run
+//! use mousetrap::connection::Connection;
+//!
+//! let mut conn = Connection::init().unwrap();
+//! if let Some(window) = conn.ewmh_active_window().unwrap() {
+//!     println!("pid {:?}", conn.ewmh_wm_pid(window).unwrap());
+//!     println!("frame {:?}", conn.ewmh_frame_extents(window).unwrap());
+//!     conn.ewmh_set_fullscreen(window, true).unwrap();
+//! }
+//! conn.sync().unwrap();
+//! ```
+
+use crate::connection::Connection;
+use crate::errors::ConnectionError;
+use crate::event::ClientMessageEvent;
+use crate::protocol::{atom, Atom, Timestamp, Window, XId};
+use crate::request::PropMode;
+
+/// Names of the atoms in [Atoms], in the order of its fields.
+const ATOM_NAMES: [&str; 21] = [
+    "_NET_SUPPORTED",
+    "_NET_CLIENT_LIST",
+    "_NET_CLIENT_LIST_STACKING",
+    "_NET_NUMBER_OF_DESKTOPS",
+    "_NET_CURRENT_DESKTOP",
+    "_NET_ACTIVE_WINDOW",
+    "_NET_CLOSE_WINDOW",
+    "_NET_WM_NAME",
+    "_NET_WM_DESKTOP",
+    "_NET_WM_STATE",
+    "_NET_WM_STATE_MAXIMIZED_VERT",
+    "_NET_WM_STATE_MAXIMIZED_HORZ",
+    "_NET_WM_STATE_FULLSCREEN",
+    "_NET_WM_STATE_HIDDEN",
+    "_NET_WM_STATE_ABOVE",
+    "_NET_WM_STATE_BELOW",
+    "_NET_WM_STATE_STICKY",
+    "_NET_WM_PID",
+    "_NET_FRAME_EXTENTS",
+    "_NET_WM_PING",
+    "UTF8_STRING",
+];
+
+/// The atoms of the EWMH properties and messages, named after them without the
+/// `_NET_` prefix.
+#[derive(Debug, Clone, Copy)]
+pub struct Atoms {
+    pub supported: Atom,
+    pub client_list: Atom,
+    pub client_list_stacking: Atom,
+    pub number_of_desktops: Atom,
+    pub current_desktop: Atom,
+    pub active_window: Atom,
+    pub close_window: Atom,
+    pub wm_name: Atom,
+    pub wm_desktop: Atom,
+    pub wm_state: Atom,
+    pub wm_state_maximized_vert: Atom,
+    pub wm_state_maximized_horz: Atom,
+    pub wm_state_fullscreen: Atom,
+    pub wm_state_hidden: Atom,
+    pub wm_state_above: Atom,
+    pub wm_state_below: Atom,
+    pub wm_state_sticky: Atom,
+    pub wm_pid: Atom,
+    pub frame_extents: Atom,
+    pub wm_ping: Atom,
+    pub utf8_string: Atom,
+}
+
+impl Atoms {
+    /// Interns all the atoms, sending the requests before waiting on any reply.
+    pub(crate) fn intern(conn: &mut Connection) -> Result<Atoms, ConnectionError> {
+        let mut cookies = Vec::with_capacity(ATOM_NAMES.len());
+        for name in ATOM_NAMES {
+            cookies.push(conn.intern_atom(false, name)?);
+        }
+        let mut atoms = Vec::with_capacity(ATOM_NAMES.len());
+        for cookie in cookies {
+            atoms.push(cookie.reply(conn)?.atom);
+        }
+        // The fields are evaluated in order, so each takes the next atom.
+        let mut atoms = atoms.into_iter();
+        let mut next = || atoms.next().unwrap_or(atom::NONE);
+        Ok(Atoms {
+            supported: next(),
+            client_list: next(),
+            client_list_stacking: next(),
+            number_of_desktops: next(),
+            current_desktop: next(),
+            active_window: next(),
+            close_window: next(),
+            wm_name: next(),
+            wm_desktop: next(),
+            wm_state: next(),
+            wm_state_maximized_vert: next(),
+            wm_state_maximized_horz: next(),
+            wm_state_fullscreen: next(),
+            wm_state_hidden: next(),
+            wm_state_above: next(),
+            wm_state_below: next(),
+            wm_state_sticky: next(),
+            wm_pid: next(),
+            frame_extents: next(),
+            wm_ping: next(),
+            utf8_string: next(),
+        })
+    }
+}
+
+/// What a _NET_WM_STATE message does with the states it names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StateAction(pub u32);
+
+impl StateAction {
+    pub const REMOVE: Self = Self(0);
+    pub const ADD: Self = Self(1);
+    pub const TOGGLE: Self = Self(2);
+}
+
+/// The width of the borders a window manager adds around a window, from
+/// _NET_FRAME_EXTENTS.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameExtents {
+    pub left: u32,
+    pub right: u32,
+    pub top: u32,
+    pub bottom: u32,
+}
+
+/// The source indication of the messages sent by this module: a pager or
+/// another tool acting for the user, which window managers always obey.
+const SOURCE_PAGER: u32 = 2;
+
+impl Connection {
+    /// Returns the 32-bit values of a property, empty if it is unset or not of
+    /// type `type_`.
+    fn ewmh_get_u32s(
+        &mut self,
+        window: Window,
+        property: Atom,
+        type_: Atom,
+    ) -> Result<Vec<u32>, ConnectionError> {
+        let reply = self
+            .get_property(false, window, property, type_, 0, u32::MAX)?
+            .reply(self)?;
+        Ok(reply.value32().unwrap_or_default())
+    }
+
+    /// Returns the first 32-bit value of a property, if it is set.
+    fn ewmh_get_u32(
+        &mut self,
+        window: Window,
+        property: Atom,
+        type_: Atom,
+    ) -> Result<Option<u32>, ConnectionError> {
+        Ok(self
+            .ewmh_get_u32s(window, property, type_)?
+            .first()
+            .copied())
+    }
+
+    /// Sends a message about `window` to the window manager.
+    fn ewmh_send_message(
+        &mut self,
+        window: Window,
+        message_type: Atom,
+        data: [u32; 5],
+    ) -> Result<u16, ConnectionError> {
+        let message = ClientMessageEvent::new_format32(window, message_type, data);
+        self.send_root_message(&message)
+    }
+
+    /// Returns the hints the window manager supports, from _NET_SUPPORTED.
+    pub fn ewmh_supported(&mut self) -> Result<Vec<Atom>, ConnectionError> {
+        let atoms = self.ewmh_atoms()?;
+        let root = self.default_root();
+        self.ewmh_get_u32s(root, atoms.supported, atom::ATOM)
+    }
+
+    /// Returns the window that has the focus, from _NET_ACTIVE_WINDOW.
+    pub fn ewmh_active_window(&mut self) -> Result<Option<Window>, ConnectionError> {
+        let atoms = self.ewmh_atoms()?;
+        let root = self.default_root();
+        let window = self.ewmh_get_u32(root, atoms.active_window, atom::WINDOW)?;
+        Ok(window.filter(|&window| window != 0).map(XId))
+    }
+
+    /// Asks the window manager to activate a window: switch to its desktop, raise
+    /// it and focus it. `timestamp` is that of the user action causing the change.
+    pub fn ewmh_set_active_window(
+        &mut self,
+        window: Window,
+        timestamp: Timestamp,
+    ) -> Result<u16, ConnectionError> {
+        let atoms = self.ewmh_atoms()?;
+        let data = [SOURCE_PAGER, timestamp, 0, 0, 0];
+        self.ewmh_send_message(window, atoms.active_window, data)
+    }
+
+    /// Returns the windows managed by the window manager, in the order they were
+    /// mapped, from _NET_CLIENT_LIST.
+    pub fn ewmh_client_list(&mut self) -> Result<Vec<Window>, ConnectionError> {
+        let atoms = self.ewmh_atoms()?;
+        let root = self.default_root();
+        let windows = self.ewmh_get_u32s(root, atoms.client_list, atom::WINDOW)?;
+        Ok(windows.into_iter().map(XId).collect())
+    }
+
+    /// Returns the windows managed by the window manager, from bottom to top, from
+    /// _NET_CLIENT_LIST_STACKING.
+    pub fn ewmh_client_list_stacking(&mut self) -> Result<Vec<Window>, ConnectionError> {
+        let atoms = self.ewmh_atoms()?;
+        let root = self.default_root();
+        let windows = self.ewmh_get_u32s(root, atoms.client_list_stacking, atom::WINDOW)?;
+        Ok(windows.into_iter().map(XId).collect())
+    }
+
+    /// Returns the number of virtual desktops, from _NET_NUMBER_OF_DESKTOPS.
+    pub fn ewmh_number_of_desktops(&mut self) -> Result<Option<u32>, ConnectionError> {
+        let atoms = self.ewmh_atoms()?;
+        let root = self.default_root();
+        self.ewmh_get_u32(root, atoms.number_of_desktops, atom::CARDINAL)
+    }
+
+    /// Asks the window manager to change the number of virtual desktops.
+    pub fn ewmh_set_number_of_desktops(&mut self, count: u32) -> Result<u16, ConnectionError> {
+        let atoms = self.ewmh_atoms()?;
+        let root = self.default_root();
+        self.ewmh_send_message(root, atoms.number_of_desktops, [count, 0, 0, 0, 0])
+    }
+
+    /// Returns the index of the current virtual desktop, from _NET_CURRENT_DESKTOP.
+    pub fn ewmh_current_desktop(&mut self) -> Result<Option<u32>, ConnectionError> {
+        let atoms = self.ewmh_atoms()?;
+        let root = self.default_root();
+        self.ewmh_get_u32(root, atoms.current_desktop, atom::CARDINAL)
+    }
+
+    /// Asks the window manager to switch to another virtual desktop. `timestamp`
+    /// is that of the user action causing the change.
+    pub fn ewmh_set_current_desktop(
+        &mut self,
+        desktop: u32,
+        timestamp: Timestamp,
+    ) -> Result<u16, ConnectionError> {
+        let atoms = self.ewmh_atoms()?;
+        let root = self.default_root();
+        self.ewmh_send_message(root, atoms.current_desktop, [desktop, timestamp, 0, 0, 0])
+    }
+
+    /// Returns the states of a window, such as
+    /// [Atoms::wm_state_fullscreen], from _NET_WM_STATE.
+    pub fn ewmh_wm_state(&mut self, window: Window) -> Result<Vec<Atom>, ConnectionError> {
+        let atoms = self.ewmh_atoms()?;
+        self.ewmh_get_u32s(window, atoms.wm_state, atom::ATOM)
+    }
+
+    /// Asks the window manager to add, remove or toggle one or two states of a
+    /// mapped window. `second` is [atom::NONE] to change a single state.
+    pub fn ewmh_change_wm_state(
+        &mut self,
+        window: Window,
+        action: StateAction,
+        first: Atom,
+        second: Atom,
+    ) -> Result<u16, ConnectionError> {
+        let atoms = self.ewmh_atoms()?;
+        let data = [action.0, first, second, SOURCE_PAGER, 0];
+        self.ewmh_send_message(window, atoms.wm_state, data)
+    }
+
+    /// Asks the window manager to maximize a window in both directions, or to
+    /// restore it.
+    pub fn ewmh_set_maximized(
+        &mut self,
+        window: Window,
+        maximized: bool,
+    ) -> Result<u16, ConnectionError> {
+        let atoms = self.ewmh_atoms()?;
+        let action = if maximized {
+            StateAction::ADD
+        } else {
+            StateAction::REMOVE
+        };
+        self.ewmh_change_wm_state(
+            window,
+            action,
+            atoms.wm_state_maximized_vert,
+            atoms.wm_state_maximized_horz,
+        )
+    }
+
+    /// Asks the window manager to make a window fullscreen, or to restore it.
+    pub fn ewmh_set_fullscreen(
+        &mut self,
+        window: Window,
+        fullscreen: bool,
+    ) -> Result<u16, ConnectionError> {
+        let atoms = self.ewmh_atoms()?;
+        let action = if fullscreen {
+            StateAction::ADD
+        } else {
+            StateAction::REMOVE
+        };
+        self.ewmh_change_wm_state(window, action, atoms.wm_state_fullscreen, atom::NONE)
+    }
+
+    /// Returns the id of the process owning a window, from _NET_WM_PID. It is set
+    /// by the client, so it only means something for local clients.
+    pub fn ewmh_wm_pid(&mut self, window: Window) -> Result<Option<u32>, ConnectionError> {
+        let atoms = self.ewmh_atoms()?;
+        self.ewmh_get_u32(window, atoms.wm_pid, atom::CARDINAL)
+    }
+
+    /// Sets the _NET_WM_PID of a window of this client.
+    pub fn ewmh_set_wm_pid(&mut self, window: Window, pid: u32) -> Result<u16, ConnectionError> {
+        let atoms = self.ewmh_atoms()?;
+        self.change_property(
+            PropMode::REPLACE,
+            window,
+            atoms.wm_pid,
+            atom::CARDINAL,
+            32,
+            &pid.to_ne_bytes(),
+        )
+    }
+
+    /// Returns the borders the window manager added around a window, from
+    /// _NET_FRAME_EXTENTS.
+    pub fn ewmh_frame_extents(
+        &mut self,
+        window: Window,
+    ) -> Result<Option<FrameExtents>, ConnectionError> {
+        let atoms = self.ewmh_atoms()?;
+        let values = self.ewmh_get_u32s(window, atoms.frame_extents, atom::CARDINAL)?;
+        let [left, right, top, bottom] = values[..] else {
+            return Ok(None);
+        };
+        Ok(Some(FrameExtents {
+            left,
+            right,
+            top,
+            bottom,
+        }))
+    }
+}
@@ -0,0 +1,172 @@
+use crate::event::{Event, ExposeEvent};
+use crate::protocol::{Rectangle, Window};
+use std::collections::HashMap;
+
+/// The exposed areas of a window, collected from a series of Expose events.
+#[derive(Debug, Clone)]
+pub struct DamageRegion {
+    pub window: Window,
+    /// The exposed rectangles, in the order they were reported.
+    pub rectangles: Vec<Rectangle>,
+}
+
+impl DamageRegion {
+    /// Returns the smallest rectangle containing every exposed rectangle.
+    pub fn bounds(&self) -> Rectangle {
+        let mut rectangles = self.rectangles.iter();
+        let first = rectangles.next().copied().unwrap_or_default();
+        rectangles.fold(first, |bounds, rectangle| bounds.union(rectangle))
+    }
+}
+
+/// Merges the Expose events of a window into a single damage region.
+///
+/// The server reports an exposed region as a series of Expose events, where `count`
+/// is the number of events still to follow. Clients usually want to redraw once per
+/// series, so the accumulator collects the rectangles and hands out the region when
+/// the final event (`count == 0`) arrives.
+///
+/// ```no_run
+/// # use mousetrap::connection::Connection;
+/// # use mousetrap::expose::ExposeAccumulator;
+/// let mut conn = Connection::init().unwrap();
+/// let mut exposures = ExposeAccumulator::new();
+/// loop {
+///     let event = conn.wait_for_event().unwrap();
+///     if let Some(region) = exposures.push_event(&event) {
+///         println!("redraw {:?} in {:?}", region.window, region.bounds());
+///     }
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct ExposeAccumulator {
+    pending: HashMap<Window, Vec<Rectangle>>,
+}
+
+impl ExposeAccumulator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an Expose event. Returns the merged region of the window if this was
+    /// the last event of the series.
+    pub fn push(&mut self, event: &ExposeEvent) -> Option<DamageRegion> {
+        // The CARD16 coordinates of Expose past 32767 are clamped rather than
+        // turned negative.
+        let rectangle = Rectangle {
+            x: i16::try_from(event.x).unwrap_or(i16::MAX),
+            y: i16::try_from(event.y).unwrap_or(i16::MAX),
+            width: event.width,
+            height: event.height,
+        };
+        let rectangles = self.pending.entry(event.window).or_default();
+        rectangles.push(rectangle);
+        if event.count > 0 {
+            return None;
+        }
+        let rectangles = self.pending.remove(&event.window).unwrap_or_default();
+        Some(DamageRegion {
+            window: event.window,
+            rectangles,
+        })
+    }
+
+    /// Same as [ExposeAccumulator::push], ignoring events that are not Expose events.
+    pub fn push_event(&mut self, event: &Event) -> Option<DamageRegion> {
+        match event {
+            Event::Expose(expose) => self.push(expose),
+            _ => None,
+        }
+    }
+
+    /// Forgets the pending rectangles of a window, e.g. after it was destroyed.
+    pub fn discard(&mut self, window: Window) {
+        self.pending.remove(&window);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::XId;
+
+    /// Parses an Expose event from its bytes: code, unused, sequence, window,
+    /// x, y, width, height, count.
+    fn expose(window: u32, rectangle: [u16; 4], count: u16) -> Event {
+        let mut bytes = vec![12, 0, 0, 0];
+        bytes.extend_from_slice(&window.to_ne_bytes());
+        for value in rectangle.into_iter().chain([count]) {
+            bytes.extend_from_slice(&value.to_ne_bytes());
+        }
+        bytes.resize(32, 0);
+        Event::from_bytes(&bytes).unwrap()
+    }
+
+    fn rectangle(x: i16, y: i16, width: u16, height: u16) -> Rectangle {
+        Rectangle {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    #[test]
+    fn series_are_merged_per_window() {
+        let mut exposures = ExposeAccumulator::new();
+        assert!(exposures
+            .push_event(&expose(1, [0, 0, 10, 10], 2))
+            .is_none());
+        assert!(exposures.push_event(&expose(2, [5, 5, 1, 1], 1)).is_none());
+        assert!(exposures
+            .push_event(&expose(1, [20, 0, 10, 5], 1))
+            .is_none());
+        let region = exposures.push_event(&expose(1, [0, 30, 5, 5], 0)).unwrap();
+        assert_eq!(region.window, XId(1));
+        assert_eq!(
+            region.rectangles,
+            [
+                rectangle(0, 0, 10, 10),
+                rectangle(20, 0, 10, 5),
+                rectangle(0, 30, 5, 5)
+            ]
+        );
+        assert_eq!(region.bounds(), rectangle(0, 0, 30, 35));
+        // The series of the other window goes on.
+        let region = exposures.push_event(&expose(2, [0, 0, 2, 2], 0)).unwrap();
+        assert_eq!(region.window, XId(2));
+        assert_eq!(region.bounds(), rectangle(0, 0, 6, 6));
+    }
+
+    #[test]
+    fn single_events_and_discarded_series() {
+        let mut exposures = ExposeAccumulator::new();
+        let region = exposures.push_event(&expose(1, [1, 2, 3, 4], 0)).unwrap();
+        assert_eq!(region.rectangles, [rectangle(1, 2, 3, 4)]);
+        assert!(exposures.push_event(&expose(1, [0, 0, 9, 9], 1)).is_none());
+        exposures.discard(XId(1));
+        let region = exposures.push_event(&expose(1, [1, 1, 1, 1], 0)).unwrap();
+        assert_eq!(region.rectangles, [rectangle(1, 1, 1, 1)]);
+        assert!(exposures.push_event(&Event::ConnectionClosed).is_none());
+        let empty = DamageRegion {
+            window: XId(1),
+            rectangles: Vec::new(),
+        };
+        assert_eq!(empty.bounds(), Rectangle::default());
+    }
+
+    #[test]
+    fn coordinates_do_not_wrap() {
+        let mut exposures = ExposeAccumulator::new();
+        assert!(exposures
+            .push_event(&expose(1, [0, 0, 10, 10], 1))
+            .is_none());
+        let region = exposures
+            .push_event(&expose(1, [40000, 32768, 10, 10], 0))
+            .unwrap();
+        assert_eq!(region.rectangles[1], rectangle(i16::MAX, i16::MAX, 10, 10));
+        let bounds = region.bounds();
+        assert_eq!((bounds.x, bounds.y), (0, 0));
+        assert!(bounds.width >= i16::MAX as u16);
+    }
+}
@@ -0,0 +1,45 @@
+//! Version negotiation, common to the extension modules.
+//!
+//! Each extension module has a type implementing [Extension], such as
+//! [crate::randr::Randr]. [Connection::enable] asks the server whether it
+//! supports the extension and negotiates its version, once per connection.
+//! Afterwards [Connection::is_supported] and [Connection::supports_version] tell
+//! which requests can be used, without a round trip.
+//!
+//! ```no_run
+//! use mousetrap::connection::Connection;
+//! use mousetrap::randr::Randr;
+//! use mousetrap::xinput::XInput;
+//!
+//! let mut conn = Connection::init().unwrap();
+//! conn.enable::<Randr>().unwrap();
+//! conn.enable::<XInput>().unwrap();
+//! if !conn.is_supported::<Randr>() {
+//!     println!("no RandR 1.2, falling back to Xinerama");
+//! }
+//! if conn.supports_version::<XInput>((2, 2)) {
+//!     println!("touch events are available");
+//! }
+//! ```
+
+use crate::connection::Connection;
+use crate::errors::ConnectionError;
+
+/// A version of an extension, as (major, minor). Versions compare by major
+/// version first.
+pub type Version = (u32, u32);
+
+/// An extension whose version can be negotiated with [Connection::enable].
+pub trait Extension {
+    /// Name of the extension, as given to QueryExtension.
+    const NAME: &'static str;
+
+    /// The oldest version of the extension the module works with. Servers with
+    /// an older version are treated as not supporting the extension.
+    const MIN_VERSION: Version;
+
+    /// Tells the server the version of the extension the module implements and
+    /// returns the version both support, or `None` if the server refuses it.
+    /// Called once the server is known to support the extension.
+    fn negotiate(conn: &mut Connection) -> Result<Option<Version>, ConnectionError>;
+}
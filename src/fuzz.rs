@@ -0,0 +1,27 @@
+//! Entry points for fuzzing the parsers of what is read from outside. Each
+//! takes arbitrary bytes and must not panic, whatever they are.
+//!
+//! The harnesses of the `fuzz` directory call them, e.g. with
+//! `cargo +nightly fuzz run event`.
+
+use crate::auth::XAuthEntry;
+use crate::event::Event;
+use crate::protocol::ConnSetup;
+
+/// Parses the server's answer to the connection setup.
+pub fn fuzz_conn_setup(data: &[u8]) {
+    if let Ok(length) = ConnSetup::length(data) {
+        let _ = ConnSetup::parse_into(&data[..length.min(data.len())]);
+    }
+    let _ = ConnSetup::parse_into(data);
+}
+
+/// Parses an event, generic events included.
+pub fn fuzz_event(data: &[u8]) {
+    let _ = Event::from_bytes(data);
+}
+
+/// Parses the entries of a .Xauthority file.
+pub fn fuzz_xauth(data: &[u8]) {
+    let _ = XAuthEntry::parse_from(data);
+}
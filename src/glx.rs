@@ -0,0 +1,490 @@
+//! The GLX extension: OpenGL contexts on X drawables.
+//!
+//! This module covers the requests a GL loader needs to set up a context over
+//! this connection: the version handshake, the visual and framebuffer
+//! configurations of a screen, and the creation and binding of contexts. GL
+//! commands themselves are not encoded.
+//!
+//! ```no_run
+//! use mousetrap::connection::Connection;
+//! use mousetrap::glx::attribute;
+//!
+//! let mut conn = Connection::init().unwrap();
+//! let version = conn.glx_query_version(1, 4).unwrap().reply(&mut conn).unwrap();
+//! println!("GLX {}.{}", version.major_version, version.minor_version);
+//! let configs = conn.glx_get_fb_configs(0).unwrap().reply(&mut conn).unwrap();
+//! for config in &configs.fb_configs {
+//!     println!(
+//!         "fbconfig {:?}: visual {:?}, double buffered {:?}",
+//!         config.get(attribute::FBCONFIG_ID),
+//!         config.get(attribute::VISUAL_ID),
+//!         config.get(attribute::DOUBLEBUFFER),
+//!     );
+//! }
+//! ```
+
+use crate::connection::Connection;
+use crate::cookie::{Cookie, Reply};
+use crate::errors::{ConnectionError, ParseError};
+use crate::extension::{Extension, Version};
+use crate::protocol::{VisualId, XId};
+use crate::request::RequestBuilder;
+use crate::utils::{check_trailing_bytes, deserialize_into, deserialize_into_vec, skip};
+
+/// Name of the extension, as given to QueryExtension.
+pub const NAME: &str = "GLX";
+
+/// Version of the extension implemented by this module.
+pub const VERSION: (u32, u32) = (1, 4);
+
+/// Minor opcodes of the GLX requests.
+pub mod opcode {
+    pub const CREATE_CONTEXT: u8 = 3;
+    pub const DESTROY_CONTEXT: u8 = 4;
+    pub const MAKE_CURRENT: u8 = 5;
+    pub const QUERY_VERSION: u8 = 7;
+    pub const GET_VISUAL_CONFIGS: u8 = 14;
+    pub const GET_FB_CONFIGS: u8 = 21;
+}
+
+/// Names of the GLX requests, indexed by minor opcode.
+pub const REQUEST_NAMES: &[&str] = &[
+    "",
+    "Render",
+    "RenderLarge",
+    "CreateContext",
+    "DestroyContext",
+    "MakeCurrent",
+    "IsDirect",
+    "QueryVersion",
+    "WaitGL",
+    "WaitX",
+    "CopyContext",
+    "SwapBuffers",
+    "UseXFont",
+    "CreateGLXPixmap",
+    "GetVisualConfigs",
+    "DestroyGLXPixmap",
+    "VendorPrivate",
+    "VendorPrivateWithReply",
+    "QueryExtensionsString",
+    "QueryServerString",
+    "ClientInfo",
+    "GetFBConfigs",
+    "CreatePixmap",
+    "DestroyPixmap",
+    "CreateNewContext",
+    "QueryContext",
+    "MakeContextCurrent",
+    "CreatePbuffer",
+    "DestroyPbuffer",
+    "GetDrawableAttributes",
+    "ChangeDrawableAttributes",
+    "CreateWindow",
+    "DeleteWindow",
+    "SetClientInfoARB",
+    "CreateContextAttribsARB",
+    "SetClientInfo2ARB",
+];
+
+/// Names of the GLX errors, indexed by error code.
+pub const ERROR_NAMES: &[&str] = &[
+    "BadContext",
+    "BadContextState",
+    "BadDrawable",
+    "BadPixmap",
+    "BadContextTag",
+    "BadCurrentWindow",
+    "BadRenderRequest",
+    "BadLargeRequest",
+    "UnsupportedPrivateRequest",
+    "BadFBConfig",
+    "BadPbuffer",
+    "BadCurrentDrawable",
+    "BadWindow",
+    "GLXBadProfileARB",
+];
+
+/// Starts a request of the extension.
+fn request(conn: &mut Connection, minor_opcode: u8) -> Result<RequestBuilder, ConnectionError> {
+    let info = conn.require_extension(NAME, REQUEST_NAMES, ERROR_NAMES)?;
+    Ok(RequestBuilder::new(info.major_opcode, minor_opcode))
+}
+
+/// The GLX extension, for [Connection::enable].
+pub struct Glx;
+
+impl Extension for Glx {
+    const NAME: &'static str = NAME;
+    const MIN_VERSION: Version = (1, 2);
+
+    fn negotiate(conn: &mut Connection) -> Result<Option<Version>, ConnectionError> {
+        let reply = conn.glx_query_version(VERSION.0, VERSION.1)?.reply(conn)?;
+        Ok(Some((reply.major_version, reply.minor_version)))
+    }
+}
+
+/// Attributes of framebuffer configurations, the `GLX_*` constants of `glx.h`.
+pub mod attribute {
+    pub const BUFFER_SIZE: u32 = 2;
+    pub const LEVEL: u32 = 3;
+    pub const RGBA: u32 = 4;
+    pub const DOUBLEBUFFER: u32 = 5;
+    pub const STEREO: u32 = 6;
+    pub const AUX_BUFFERS: u32 = 7;
+    pub const RED_SIZE: u32 = 8;
+    pub const GREEN_SIZE: u32 = 9;
+    pub const BLUE_SIZE: u32 = 10;
+    pub const ALPHA_SIZE: u32 = 11;
+    pub const DEPTH_SIZE: u32 = 12;
+    pub const STENCIL_SIZE: u32 = 13;
+    pub const X_VISUAL_TYPE: u32 = 0x22;
+    pub const CONFIG_CAVEAT: u32 = 0x20;
+    pub const VISUAL_ID: u32 = 0x800B;
+    pub const DRAWABLE_TYPE: u32 = 0x8010;
+    pub const RENDER_TYPE: u32 = 0x8011;
+    pub const X_RENDERABLE: u32 = 0x8012;
+    pub const FBCONFIG_ID: u32 = 0x8013;
+    pub const SAMPLE_BUFFERS: u32 = 100000;
+    pub const SAMPLES: u32 = 100001;
+}
+
+/// Reply of the GLXQueryVersion request.
+#[derive(Debug, Clone)]
+pub struct QueryVersionReply {
+    pub major_version: u32,
+    pub minor_version: u32,
+}
+
+impl Reply for QueryVersionReply {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1                        unused
+        // 2  CARD16                sequence number
+        // 4  0                     reply length
+        // 4  CARD32                major-version
+        // 4  CARD32                minor-version
+        // 16                       unused
+        let rest = skip(bytes, 8)?;
+        let (major_version, rest) = deserialize_into::<u32>(rest)?;
+        let (minor_version, _) = deserialize_into::<u32>(rest)?;
+        Ok(QueryVersionReply {
+            major_version,
+            minor_version,
+        })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        check_trailing_bytes("glx::QueryVersionReply", bytes, 32)
+    }
+}
+
+/// Number of properties every visual configuration starts with.
+const VISUAL_CONFIG_PROPERTIES: u32 = 18;
+
+/// A GL capable visual, as described by GLXGetVisualConfigs.
+#[derive(Debug, Clone)]
+pub struct VisualConfig {
+    pub visual: VisualId,
+    pub class: u32,
+    /// RGBA rather than color index rendering.
+    pub rgba: bool,
+    pub red_size: u32,
+    pub green_size: u32,
+    pub blue_size: u32,
+    pub alpha_size: u32,
+    pub accum_red_size: u32,
+    pub accum_green_size: u32,
+    pub accum_blue_size: u32,
+    pub accum_alpha_size: u32,
+    pub double_buffer: bool,
+    pub stereo: bool,
+    pub buffer_size: u32,
+    pub depth_size: u32,
+    pub stencil_size: u32,
+    pub aux_buffers: u32,
+    pub level: i32,
+    /// Further properties, as (attribute, value) pairs, see [attribute].
+    pub extra: Vec<(u32, u32)>,
+}
+
+impl VisualConfig {
+    fn from_properties(properties: &[u32]) -> VisualConfig {
+        let extra = properties[VISUAL_CONFIG_PROPERTIES as usize..]
+            .chunks_exact(2)
+            .map(|pair| (pair[0], pair[1]))
+            .collect();
+        VisualConfig {
+            visual: properties[0],
+            class: properties[1],
+            rgba: properties[2] != 0,
+            red_size: properties[3],
+            green_size: properties[4],
+            blue_size: properties[5],
+            alpha_size: properties[6],
+            accum_red_size: properties[7],
+            accum_green_size: properties[8],
+            accum_blue_size: properties[9],
+            accum_alpha_size: properties[10],
+            double_buffer: properties[11] != 0,
+            stereo: properties[12] != 0,
+            buffer_size: properties[13],
+            depth_size: properties[14],
+            stencil_size: properties[15],
+            aux_buffers: properties[16],
+            level: properties[17] as i32,
+            extra,
+        }
+    }
+}
+
+/// Reply of the GLXGetVisualConfigs request.
+#[derive(Debug, Clone)]
+pub struct GetVisualConfigsReply {
+    /// Number of properties of each visual.
+    pub num_properties: u32,
+    pub visuals: Vec<VisualConfig>,
+}
+
+impl Reply for GetVisualConfigsReply {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1                        unused
+        // 2  CARD16                sequence number
+        // 4  n*p                   reply length
+        // 4  n                     number of visuals
+        // 4  p                     number of properties per visual
+        // 16                       unused
+        // 4np LISTofCARD32         properties: 18 fixed ones, then
+        //                          (attribute, value) pairs
+        let rest = skip(bytes, 8)?;
+        let (num_visuals, rest) = deserialize_into::<u32>(rest)?;
+        let (num_properties, rest) = deserialize_into::<u32>(rest)?;
+        if num_properties < VISUAL_CONFIG_PROPERTIES {
+            return Err(ParseError::InvalidValue {
+                type_name: "glx::GetVisualConfigsReply",
+                field: "num_properties",
+                value: num_properties,
+            });
+        }
+        let mut rest = skip(rest, 16)?;
+        let mut visuals = Vec::new();
+        for _ in 0..num_visuals {
+            let (properties, next) = deserialize_into_vec::<u32>(rest, num_properties as usize)?;
+            visuals.push(VisualConfig::from_properties(&properties));
+            rest = next;
+        }
+        Ok(GetVisualConfigsReply {
+            num_properties,
+            visuals,
+        })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        let length = 32 + 4 * self.visuals.len() * self.num_properties as usize;
+        check_trailing_bytes("glx::GetVisualConfigsReply", bytes, length)
+    }
+}
+
+/// A framebuffer configuration, as (attribute, value) pairs.
+#[derive(Debug, Clone)]
+pub struct FbConfig {
+    pub attributes: Vec<(u32, u32)>,
+}
+
+impl FbConfig {
+    /// Returns the value of an attribute, see [attribute].
+    pub fn get(&self, attribute: u32) -> Option<u32> {
+        self.attributes
+            .iter()
+            .find(|(name, _)| *name == attribute)
+            .map(|(_, value)| *value)
+    }
+}
+
+/// Reply of the GLXGetFBConfigs request.
+#[derive(Debug, Clone)]
+pub struct GetFbConfigsReply {
+    /// Number of attributes of each configuration.
+    pub num_properties: u32,
+    pub fb_configs: Vec<FbConfig>,
+}
+
+impl Reply for GetFbConfigsReply {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1                        unused
+        // 2  CARD16                sequence number
+        // 4  2*n*p                 reply length
+        // 4  n                     number of configurations
+        // 4  p                     number of attributes per configuration
+        // 16                       unused
+        // 8np LISTofCARD32         (attribute, value) pairs
+        let rest = skip(bytes, 8)?;
+        let (num_configs, rest) = deserialize_into::<u32>(rest)?;
+        let (num_properties, rest) = deserialize_into::<u32>(rest)?;
+        let mut rest = skip(rest, 16)?;
+        let mut fb_configs = Vec::new();
+        for _ in 0..num_configs {
+            let (pairs, next) = deserialize_into_vec::<u32>(rest, 2 * num_properties as usize)?;
+            let attributes = pairs
+                .chunks_exact(2)
+                .map(|pair| (pair[0], pair[1]))
+                .collect();
+            fb_configs.push(FbConfig { attributes });
+            rest = next;
+        }
+        Ok(GetFbConfigsReply {
+            num_properties,
+            fb_configs,
+        })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        let length = 32 + 8 * self.fb_configs.len() * self.num_properties as usize;
+        check_trailing_bytes("glx::GetFbConfigsReply", bytes, length)
+    }
+}
+
+/// Reply of the GLXMakeCurrent request.
+#[derive(Debug, Clone)]
+pub struct MakeCurrentReply {
+    /// Identifies the current context in the requests using it, such as
+    /// GLXRender.
+    pub context_tag: u32,
+}
+
+impl Reply for MakeCurrentReply {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1                        unused
+        // 2  CARD16                sequence number
+        // 4  0                     reply length
+        // 4  CARD32                context-tag
+        // 20                       unused
+        let rest = skip(bytes, 8)?;
+        let (context_tag, _) = deserialize_into::<u32>(rest)?;
+        Ok(MakeCurrentReply { context_tag })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        check_trailing_bytes("glx::MakeCurrentReply", bytes, 32)
+    }
+}
+
+impl Connection {
+    /// Tells the server the version of the extension the client supports and
+    /// returns the version the server supports.
+    pub fn glx_query_version(
+        &mut self,
+        major_version: u32,
+        minor_version: u32,
+    ) -> Result<Cookie<QueryVersionReply>, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (7)
+        //   2 bytes    Request length (3)
+        //   4 bytes    Client major version
+        //   4 bytes    Client minor version
+        let request = request(self, opcode::QUERY_VERSION)?
+            .put(major_version)
+            .put(minor_version)
+            .finish();
+        self.send_request_with_reply(&request)
+    }
+
+    /// Returns the GL capable visuals of a screen.
+    pub fn glx_get_visual_configs(
+        &mut self,
+        screen: u32,
+    ) -> Result<Cookie<GetVisualConfigsReply>, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (14)
+        //   2 bytes    Request length (2)
+        //   4 bytes    Screen
+        let request = request(self, opcode::GET_VISUAL_CONFIGS)?
+            .put(screen)
+            .finish();
+        self.send_request_with_reply(&request)
+    }
+
+    /// Returns the framebuffer configurations of a screen. Requires GLX 1.3.
+    pub fn glx_get_fb_configs(
+        &mut self,
+        screen: u32,
+    ) -> Result<Cookie<GetFbConfigsReply>, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (21)
+        //   2 bytes    Request length (2)
+        //   4 bytes    Screen
+        let request = request(self, opcode::GET_FB_CONFIGS)?.put(screen).finish();
+        self.send_request_with_reply(&request)
+    }
+
+    /// Creates a context rendering to drawables of a visual and returns its id.
+    /// The context shares display lists with `share_list`, unless it is 0.
+    /// A direct context is rendered to by the client rather than the server.
+    pub fn glx_create_context(
+        &mut self,
+        visual: VisualId,
+        screen: u32,
+        share_list: XId,
+        direct: bool,
+    ) -> Result<XId, ConnectionError> {
+        let context = self.generate_id()?;
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (3)
+        //   2 bytes    Request length (6)
+        //   4 bytes    Context
+        //   4 bytes    Visual
+        //   4 bytes    Screen
+        //   4 bytes    Share list
+        //   1 byte     Is direct
+        //   3 bytes    Unused
+        let request = request(self, opcode::CREATE_CONTEXT)?
+            .put(context)
+            .put(visual)
+            .put(screen)
+            .put(share_list)
+            .put(direct)
+            .pad(3)
+            .finish();
+        self.send_request(&request)?;
+        Ok(context)
+    }
+
+    /// Destroys a context once it is no longer current.
+    pub fn glx_destroy_context(&mut self, context: XId) -> Result<u16, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (4)
+        //   2 bytes    Request length (2)
+        //   4 bytes    Context
+        let request = request(self, opcode::DESTROY_CONTEXT)?
+            .put(context)
+            .finish();
+        self.send_request(&request)
+    }
+
+    /// Makes `context` the current context of the client, rendering to
+    /// `drawable`, and returns its tag. `old_context_tag` is the tag of the
+    /// previous current context, or 0. A `context` of 0 releases the current
+    /// context.
+    pub fn glx_make_current(
+        &mut self,
+        drawable: XId,
+        context: XId,
+        old_context_tag: u32,
+    ) -> Result<Cookie<MakeCurrentReply>, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (5)
+        //   2 bytes    Request length (4)
+        //   4 bytes    Drawable
+        //   4 bytes    Context
+        //   4 bytes    Old context tag
+        let request = request(self, opcode::MAKE_CURRENT)?
+            .put(drawable)
+            .put(context)
+            .put(old_context_tag)
+            .finish();
+        self.send_request_with_reply(&request)
+    }
+}
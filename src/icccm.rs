@@ -0,0 +1,709 @@
+//! The Inter-Client Communication Conventions: the WM_* properties through which
+//! clients describe their windows to the window manager.
+//!
+//! The properties are read and written through a [WindowHandle], obtained with
+//! [Connection::window]. [WmHints] and [SizeHints] mirror the structures of the
+//! conventions: a flags field tells which of the other fields are set.
+//!
+//! Windows taking part in WM_DELETE_WINDOW and _NET_WM_PING receive
+//! ClientMessages from the window manager, parsed by
+//! [Connection::protocol_message]. Pings can be answered automatically, see
+//! [Connection::set_auto_pong].
+//!
+//! ```no_run
+//! use mousetrap::connection::Connection;
+//! use mousetrap::icccm::{SizeHints, SizeHintsFlags, WmClass};
+//!
+//! let mut conn = Connection::init().unwrap();
+//! let window = conn.default_root();
+//! let mut handle = conn.window(window);
+//! handle.set_name("Editor").unwrap();
+//! handle.set_class(&WmClass::new("editor", "Editor")).unwrap();
+//! handle
+//!     .set_normal_hints(&SizeHints {
+//!         flags: SizeHintsFlags::P_MIN_SIZE,
+//!         min_width: 200,
+//!         min_height: 100,
+//!         ..Default::default()
+//!     })
+//!     .unwrap();
+//! println!("{:?}", handle.protocols().unwrap());
+//! ```
+
+use crate::connection::Connection;
+use crate::errors::ConnectionError;
+use crate::event::{ClientMessageEvent, Event};
+use crate::ewmh;
+use crate::protocol::{atom, Atom, Timestamp, Window, XId};
+use crate::reply::GetPropertyReply;
+use crate::request::PropMode;
+
+/// Names of the atoms in [Atoms], in the order of its fields.
+const ATOM_NAMES: [&str; 6] = [
+    "WM_PROTOCOLS",
+    "WM_DELETE_WINDOW",
+    "WM_TAKE_FOCUS",
+    "WM_STATE",
+    "WM_CHANGE_STATE",
+    "UTF8_STRING",
+];
+
+/// The atoms of the conventions that are not predefined by the core protocol.
+#[derive(Debug, Clone, Copy)]
+pub struct Atoms {
+    pub wm_protocols: Atom,
+    pub wm_delete_window: Atom,
+    pub wm_take_focus: Atom,
+    pub wm_state: Atom,
+    pub wm_change_state: Atom,
+    pub utf8_string: Atom,
+}
+
+impl Atoms {
+    /// Interns all the atoms, sending the requests before waiting on any reply.
+    pub(crate) fn intern(conn: &mut Connection) -> Result<Atoms, ConnectionError> {
+        let mut cookies = Vec::with_capacity(ATOM_NAMES.len());
+        for name in ATOM_NAMES {
+            cookies.push(conn.intern_atom(false, name)?);
+        }
+        let mut atoms = Vec::with_capacity(ATOM_NAMES.len());
+        for cookie in cookies {
+            atoms.push(cookie.reply(conn)?.atom);
+        }
+        // The fields are evaluated in order, so each takes the next atom.
+        let mut atoms = atoms.into_iter();
+        let mut next = || atoms.next().unwrap_or(atom::NONE);
+        Ok(Atoms {
+            wm_protocols: next(),
+            wm_delete_window: next(),
+            wm_take_focus: next(),
+            wm_state: next(),
+            wm_change_state: next(),
+            utf8_string: next(),
+        })
+    }
+}
+
+/// The WM_CLASS of a window: the names the resources of the application are
+/// looked up with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WmClass {
+    /// Name of this instance of the application, usually from its command line.
+    pub instance: String,
+    /// Name of the application.
+    pub class: String,
+}
+
+impl WmClass {
+    pub fn new(instance: &str, class: &str) -> Self {
+        WmClass {
+            instance: instance.to_owned(),
+            class: class.to_owned(),
+        }
+    }
+
+    /// Parses the value of the property: two strings, each followed by a NUL.
+    pub fn decode(value: &[u8]) -> Option<WmClass> {
+        let mut strings = value.split(|&b| b == 0);
+        let instance = strings.next()?;
+        let class = strings.next()?;
+        Some(WmClass {
+            instance: String::from_utf8_lossy(instance).into_owned(),
+            class: String::from_utf8_lossy(class).into_owned(),
+        })
+    }
+
+    /// Converts the class to the value of the property.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut value = Vec::with_capacity(self.instance.len() + self.class.len() + 2);
+        value.extend_from_slice(self.instance.as_bytes());
+        value.push(0);
+        value.extend_from_slice(self.class.as_bytes());
+        value.push(0);
+        value
+    }
+}
+
+/// Which fields of [WmHints] are set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct WmHintsFlags(pub u32);
+
+impl WmHintsFlags {
+    pub const INPUT: Self = Self(1 << 0);
+    pub const STATE: Self = Self(1 << 1);
+    pub const ICON_PIXMAP: Self = Self(1 << 2);
+    pub const ICON_WINDOW: Self = Self(1 << 3);
+    pub const ICON_POSITION: Self = Self(1 << 4);
+    pub const ICON_MASK: Self = Self(1 << 5);
+    pub const WINDOW_GROUP: Self = Self(1 << 6);
+    /// The window needs the attention of the user. Not a field: the flag is the hint.
+    pub const URGENCY: Self = Self(1 << 8);
+
+    /// Whether all the bits of `other` are set.
+    pub fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl std::ops::BitOr for WmHintsFlags {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+/// The state a window is in, or asks to be mapped in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct WmState(pub u32);
+
+impl WmState {
+    pub const WITHDRAWN: Self = Self(0);
+    pub const NORMAL: Self = Self(1);
+    pub const ICONIC: Self = Self(3);
+}
+
+/// The WM_HINTS of a window.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WmHints {
+    pub flags: WmHintsFlags,
+    /// Whether the client relies on the window manager to get the keyboard focus.
+    pub input: bool,
+    pub initial_state: WmState,
+    pub icon_pixmap: XId,
+    pub icon_window: Window,
+    pub icon_x: i32,
+    pub icon_y: i32,
+    pub icon_mask: XId,
+    /// The leader of the group of windows the window belongs to.
+    pub window_group: Window,
+}
+
+impl WmHints {
+    /// Parses the 32-bit values of the property. Values missing from the end,
+    /// as written by old clients, are 0.
+    pub fn decode(values: &[u32]) -> Option<WmHints> {
+        if values.is_empty() {
+            return None;
+        }
+        // The fields are evaluated in order, so each takes the next value.
+        let mut values = values.iter().copied();
+        let mut next = || values.next().unwrap_or(0);
+        Some(WmHints {
+            flags: WmHintsFlags(next()),
+            input: next() != 0,
+            initial_state: WmState(next()),
+            icon_pixmap: XId(next()),
+            icon_window: XId(next()),
+            icon_x: next() as i32,
+            icon_y: next() as i32,
+            icon_mask: XId(next()),
+            window_group: XId(next()),
+        })
+    }
+
+    /// Converts the hints to the 32-bit values of the property.
+    pub fn encode(&self) -> [u32; 9] {
+        [
+            self.flags.0,
+            u32::from(self.input),
+            self.initial_state.0,
+            self.icon_pixmap.0,
+            self.icon_window.0,
+            self.icon_x as u32,
+            self.icon_y as u32,
+            self.icon_mask.0,
+            self.window_group.0,
+        ]
+    }
+}
+
+/// Which fields of [SizeHints] are set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SizeHintsFlags(pub u32);
+
+impl SizeHintsFlags {
+    /// The user chose the position.
+    pub const US_POSITION: Self = Self(1 << 0);
+    /// The user chose the size.
+    pub const US_SIZE: Self = Self(1 << 1);
+    /// The program chose the position.
+    pub const P_POSITION: Self = Self(1 << 2);
+    /// The program chose the size.
+    pub const P_SIZE: Self = Self(1 << 3);
+    pub const P_MIN_SIZE: Self = Self(1 << 4);
+    pub const P_MAX_SIZE: Self = Self(1 << 5);
+    pub const P_RESIZE_INC: Self = Self(1 << 6);
+    pub const P_ASPECT: Self = Self(1 << 7);
+    pub const P_BASE_SIZE: Self = Self(1 << 8);
+    pub const P_WIN_GRAVITY: Self = Self(1 << 9);
+
+    /// Whether all the bits of `other` are set.
+    pub fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl std::ops::BitOr for SizeHintsFlags {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+/// The WM_NORMAL_HINTS of a window: how the window manager may place and size it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SizeHints {
+    pub flags: SizeHintsFlags,
+    /// Obsolete: window managers use the geometry of the window. Kept so the
+    /// property can be written back unchanged.
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+    pub min_width: i32,
+    pub min_height: i32,
+    pub max_width: i32,
+    pub max_height: i32,
+    pub width_inc: i32,
+    pub height_inc: i32,
+    /// The smallest aspect ratio, as numerator and denominator.
+    pub min_aspect: (i32, i32),
+    /// The largest aspect ratio, as numerator and denominator.
+    pub max_aspect: (i32, i32),
+    pub base_width: i32,
+    pub base_height: i32,
+    pub win_gravity: u32,
+}
+
+impl SizeHints {
+    /// Parses the 32-bit values of the property. Clients written before the
+    /// base size and gravity existed write only the first 15 values.
+    pub fn decode(values: &[u32]) -> Option<SizeHints> {
+        if values.len() < 15 {
+            return None;
+        }
+        // The fields are evaluated in order, so each takes the next value.
+        let mut values = values.iter().map(|&value| value as i32);
+        let mut next = || values.next().unwrap_or(0);
+        Some(SizeHints {
+            flags: SizeHintsFlags(next() as u32),
+            x: next(),
+            y: next(),
+            width: next(),
+            height: next(),
+            min_width: next(),
+            min_height: next(),
+            max_width: next(),
+            max_height: next(),
+            width_inc: next(),
+            height_inc: next(),
+            min_aspect: (next(), next()),
+            max_aspect: (next(), next()),
+            base_width: next(),
+            base_height: next(),
+            win_gravity: next() as u32,
+        })
+    }
+
+    /// Converts the hints to the 32-bit values of the property.
+    pub fn encode(&self) -> [u32; 18] {
+        [
+            self.flags.0 as i32,
+            self.x,
+            self.y,
+            self.width,
+            self.height,
+            self.min_width,
+            self.min_height,
+            self.max_width,
+            self.max_height,
+            self.width_inc,
+            self.height_inc,
+            self.min_aspect.0,
+            self.min_aspect.1,
+            self.max_aspect.0,
+            self.max_aspect.1,
+            self.base_width,
+            self.base_height,
+            self.win_gravity as i32,
+        ]
+        .map(|value| value as u32)
+    }
+}
+
+/// A message of the protocols listed in WM_PROTOCOLS, sent by the window
+/// manager as a ClientMessage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProtocolMessage {
+    /// WM_DELETE_WINDOW: the user asked to close the window, e.g. with its close
+    /// button. The client decides whether and how to close it.
+    CloseRequested {
+        window: Window,
+        timestamp: Timestamp,
+    },
+    /// WM_TAKE_FOCUS: the client should focus the window, or one of its others.
+    TakeFocus {
+        window: Window,
+        timestamp: Timestamp,
+    },
+    /// _NET_WM_PING: the window manager checks that the client still responds,
+    /// see [Connection::pong]. Clients that do not answer may be offered to be killed.
+    Ping {
+        window: Window,
+        timestamp: Timestamp,
+    },
+}
+
+impl ProtocolMessage {
+    /// Parses the message if `event` is a ClientMessage of one of the protocols.
+    pub fn from_event(
+        event: &Event,
+        atoms: &Atoms,
+        ewmh_atoms: &ewmh::Atoms,
+    ) -> Option<ProtocolMessage> {
+        let Event::ClientMessage(message) = event else {
+            return None;
+        };
+        if message.format != 32 || message.message_type != atoms.wm_protocols {
+            return None;
+        }
+        let [protocol, timestamp, ..] = message.data32();
+        let window = message.window;
+        if protocol == atoms.wm_delete_window {
+            Some(ProtocolMessage::CloseRequested { window, timestamp })
+        } else if protocol == atoms.wm_take_focus {
+            Some(ProtocolMessage::TakeFocus { window, timestamp })
+        } else if protocol == ewmh_atoms.wm_ping {
+            Some(ProtocolMessage::Ping { window, timestamp })
+        } else {
+            None
+        }
+    }
+}
+
+impl Connection {
+    /// Parses the message if `event` is a ClientMessage of WM_DELETE_WINDOW,
+    /// WM_TAKE_FOCUS or _NET_WM_PING.
+    pub fn protocol_message(
+        &mut self,
+        event: &Event,
+    ) -> Result<Option<ProtocolMessage>, ConnectionError> {
+        if !matches!(event, Event::ClientMessage(_)) {
+            return Ok(None);
+        }
+        let atoms = self.icccm_atoms()?;
+        let ewmh_atoms = self.ewmh_atoms()?;
+        Ok(ProtocolMessage::from_event(event, &atoms, &ewmh_atoms))
+    }
+
+    /// Returns the client window of `window`: `window` itself if it has WM_STATE,
+    /// otherwise its nearest descendant with WM_STATE, or `window` if none has.
+    ///
+    /// Window managers reparent clients into frames, so the window under the
+    /// pointer is usually a frame; this finds the client inside, as xprop does.
+    /// Each level of the tree takes two round trips.
+    pub fn find_client_window(&mut self, window: Window) -> Result<Window, ConnectionError> {
+        let wm_state = self.icccm_atoms()?.wm_state;
+        let mut level = vec![window];
+        while !level.is_empty() {
+            let mut cookies = Vec::with_capacity(level.len());
+            for &candidate in &level {
+                cookies.push(self.get_property(false, candidate, wm_state, atom::ANY, 0, 0)?);
+            }
+            let mut found = None;
+            for (&candidate, cookie) in level.iter().zip(cookies) {
+                // Every reply is read, so none is left behind. Windows destroyed
+                // meanwhile fail with an error and are skipped.
+                let exists = match cookie.reply(self) {
+                    Ok(reply) => reply.exists(),
+                    Err(ConnectionError::ProtocolError(_)) => false,
+                    Err(err) => return Err(err),
+                };
+                if exists && found.is_none() {
+                    found = Some(candidate);
+                }
+            }
+            if let Some(client) = found {
+                return Ok(client);
+            }
+            let mut cookies = Vec::with_capacity(level.len());
+            for &parent in &level {
+                cookies.push(self.query_tree(parent)?);
+            }
+            let mut next = Vec::new();
+            for cookie in cookies {
+                match cookie.reply(self) {
+                    Ok(reply) => next.extend(reply.children),
+                    Err(ConnectionError::ProtocolError(_)) => {}
+                    Err(err) => return Err(err),
+                }
+            }
+            level = next;
+        }
+        Ok(window)
+    }
+
+    /// Answers a [ProtocolMessage::Ping] about `window`.
+    pub fn pong(&mut self, window: Window, timestamp: Timestamp) -> Result<u16, ConnectionError> {
+        let atoms = self.icccm_atoms()?;
+        let ewmh_atoms = self.ewmh_atoms()?;
+        // The answer is the ping itself, sent to the root window.
+        let root = self.default_root();
+        let data = [ewmh_atoms.wm_ping, timestamp, window.0, 0, 0];
+        let message = ClientMessageEvent::new_format32(root, atoms.wm_protocols, data);
+        self.send_root_message(&message)
+    }
+}
+
+/// Converts 32-bit values to the bytes of a property of format 32.
+fn to_bytes(values: &[u32]) -> Vec<u8> {
+    values
+        .iter()
+        .flat_map(|value| value.to_ne_bytes())
+        .collect()
+}
+
+/// Decodes a text property such as WM_NAME: a STRING is Latin-1, and anything
+/// else, usually UTF8_STRING, is taken as UTF-8.
+pub(crate) fn decode_text(reply: &GetPropertyReply) -> Option<String> {
+    let value = reply.value8()?;
+    let text = if reply.type_ == atom::STRING {
+        value.iter().map(|&b| char::from(b)).collect()
+    } else {
+        String::from_utf8_lossy(value).into_owned()
+    };
+    Some(text)
+}
+
+/// A window of a connection, to read and write its ICCCM properties.
+pub struct WindowHandle<'a> {
+    conn: &'a mut Connection,
+    window: Window,
+}
+
+impl Connection {
+    /// Returns a handle to read and write the ICCCM properties of a window.
+    pub fn window(&mut self, window: Window) -> WindowHandle<'_> {
+        WindowHandle { conn: self, window }
+    }
+}
+
+impl WindowHandle<'_> {
+    /// Returns the id of the window.
+    pub fn id(&self) -> Window {
+        self.window
+    }
+
+    /// Returns the whole value of a property.
+    fn get(&mut self, property: Atom, type_: Atom) -> Result<GetPropertyReply, ConnectionError> {
+        self.conn
+            .get_property(false, self.window, property, type_, 0, u32::MAX)?
+            .reply(self.conn)
+    }
+
+    /// Replaces the value of a property.
+    fn set(
+        &mut self,
+        property: Atom,
+        type_: Atom,
+        format: u8,
+        data: &[u8],
+    ) -> Result<u16, ConnectionError> {
+        self.conn.change_property(
+            PropMode::REPLACE,
+            self.window,
+            property,
+            type_,
+            format,
+            data,
+        )
+    }
+
+    /// Returns the title of the window, from WM_NAME. STRING values are decoded
+    /// as Latin-1 and other types as UTF-8.
+    pub fn name(&mut self) -> Result<Option<String>, ConnectionError> {
+        let reply = self.get(atom::WM_NAME, atom::ANY)?;
+        Ok(decode_text(&reply))
+    }
+
+    /// Sets WM_NAME, as a STRING if `name` is ASCII and as a UTF8_STRING otherwise.
+    pub fn set_name(&mut self, name: &str) -> Result<u16, ConnectionError> {
+        let type_ = if name.is_ascii() {
+            atom::STRING
+        } else {
+            self.conn.icccm_atoms()?.utf8_string
+        };
+        self.set(atom::WM_NAME, type_, 8, name.as_bytes())
+    }
+
+    /// Returns the WM_CLASS of the window.
+    pub fn class(&mut self) -> Result<Option<WmClass>, ConnectionError> {
+        let reply = self.get(atom::WM_CLASS, atom::STRING)?;
+        Ok(reply.value8().and_then(WmClass::decode))
+    }
+
+    /// Sets the WM_CLASS of the window.
+    pub fn set_class(&mut self, class: &WmClass) -> Result<u16, ConnectionError> {
+        self.set(atom::WM_CLASS, atom::STRING, 8, &class.encode())
+    }
+
+    /// Returns the WM_HINTS of the window.
+    pub fn hints(&mut self) -> Result<Option<WmHints>, ConnectionError> {
+        let reply = self.get(atom::WM_HINTS, atom::WM_HINTS)?;
+        Ok(reply.value32().and_then(|values| WmHints::decode(&values)))
+    }
+
+    /// Sets the WM_HINTS of the window.
+    pub fn set_hints(&mut self, hints: &WmHints) -> Result<u16, ConnectionError> {
+        self.set(
+            atom::WM_HINTS,
+            atom::WM_HINTS,
+            32,
+            &to_bytes(&hints.encode()),
+        )
+    }
+
+    /// Returns the WM_NORMAL_HINTS of the window.
+    pub fn normal_hints(&mut self) -> Result<Option<SizeHints>, ConnectionError> {
+        let reply = self.get(atom::WM_NORMAL_HINTS, atom::WM_SIZE_HINTS)?;
+        Ok(reply
+            .value32()
+            .and_then(|values| SizeHints::decode(&values)))
+    }
+
+    /// Sets the WM_NORMAL_HINTS of the window.
+    pub fn set_normal_hints(&mut self, hints: &SizeHints) -> Result<u16, ConnectionError> {
+        let data = to_bytes(&hints.encode());
+        self.set(atom::WM_NORMAL_HINTS, atom::WM_SIZE_HINTS, 32, &data)
+    }
+
+    /// Returns the protocols the client takes part in, such as
+    /// [Atoms::wm_delete_window], from WM_PROTOCOLS.
+    pub fn protocols(&mut self) -> Result<Vec<Atom>, ConnectionError> {
+        let atoms = self.conn.icccm_atoms()?;
+        let reply = self.get(atoms.wm_protocols, atom::ATOM)?;
+        Ok(reply.value32().unwrap_or_default())
+    }
+
+    /// Sets the WM_PROTOCOLS of the window.
+    pub fn set_protocols(&mut self, protocols: &[Atom]) -> Result<u16, ConnectionError> {
+        let atoms = self.conn.icccm_atoms()?;
+        self.set(atoms.wm_protocols, atom::ATOM, 32, &to_bytes(protocols))
+    }
+
+    /// Adds WM_DELETE_WINDOW and _NET_WM_PING to the WM_PROTOCOLS of the window,
+    /// so the window manager asks before closing it and checks it responds. See
+    /// [Connection::protocol_message] and [Connection::set_auto_pong].
+    pub fn register_protocols(&mut self) -> Result<u16, ConnectionError> {
+        let atoms = self.conn.icccm_atoms()?;
+        let ewmh_atoms = self.conn.ewmh_atoms()?;
+        let mut protocols = self.protocols()?;
+        for protocol in [atoms.wm_delete_window, ewmh_atoms.wm_ping] {
+            if !protocols.contains(&protocol) {
+                protocols.push(protocol);
+            }
+        }
+        self.set_protocols(&protocols)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wm_class() {
+        let class = WmClass::new("xterm", "XTerm");
+        assert_eq!(class.encode(), b"xterm\0XTerm\0");
+        assert_eq!(WmClass::decode(b"xterm\0XTerm\0"), Some(class));
+        // Some clients leave out the last NUL.
+        assert_eq!(
+            WmClass::decode(b"xterm\0XTerm"),
+            Some(WmClass::new("xterm", "XTerm"))
+        );
+        assert_eq!(WmClass::decode(b"xterm"), None);
+    }
+
+    #[test]
+    fn wm_hints() {
+        let hints = WmHints {
+            flags: WmHintsFlags::INPUT | WmHintsFlags::ICON_POSITION | WmHintsFlags::URGENCY,
+            input: true,
+            initial_state: WmState::ICONIC,
+            icon_x: -10,
+            icon_y: 20,
+            window_group: XId(0x0040_0001),
+            ..WmHints::default()
+        };
+        let values = hints.encode();
+        assert_eq!(values[0], 0x111);
+        assert_eq!(values[5], -10i32 as u32);
+        assert_eq!(WmHints::decode(&values), Some(hints));
+        // Old clients write the flags and input only.
+        let short = WmHints::decode(&[WmHintsFlags::INPUT.0, 1]).unwrap();
+        assert!(short.flags.contains(WmHintsFlags::INPUT));
+        assert!(!short
+            .flags
+            .contains(WmHintsFlags::INPUT | WmHintsFlags::STATE));
+        assert!(short.input);
+        assert_eq!(short.window_group, XId(0));
+        assert_eq!(WmHints::decode(&[]), None);
+    }
+
+    #[test]
+    fn size_hints() {
+        let hints = SizeHints {
+            flags: SizeHintsFlags::P_MIN_SIZE | SizeHintsFlags::P_ASPECT,
+            min_width: 100,
+            min_height: 50,
+            min_aspect: (4, 3),
+            max_aspect: (16, 9),
+            x: -5,
+            win_gravity: 10,
+            ..SizeHints::default()
+        };
+        let values = hints.encode();
+        assert_eq!(values[0], 0x90);
+        assert_eq!(values[1], -5i32 as u32);
+        assert_eq!(values[11..15], [4, 3, 16, 9]);
+        assert_eq!(SizeHints::decode(&values), Some(hints.clone()));
+        // Without the base size and gravity.
+        let old = SizeHints::decode(&values[..15]).unwrap();
+        assert_eq!((old.min_aspect, old.max_aspect), ((4, 3), (16, 9)));
+        assert_eq!((old.base_width, old.win_gravity), (0, 0));
+        assert_eq!(SizeHints::decode(&values[..14]), None);
+    }
+
+    #[test]
+    fn text_properties() {
+        let reply = |type_, value: &[u8]| GetPropertyReply {
+            format: 8,
+            type_,
+            bytes_after: 0,
+            value: value.to_vec(),
+        };
+        // STRING is Latin-1.
+        assert_eq!(
+            decode_text(&reply(atom::STRING, b"caf\xe9")).as_deref(),
+            Some("café")
+        );
+        assert_eq!(
+            decode_text(&reply(0x150, "café".as_bytes())).as_deref(),
+            Some("café")
+        );
+        let missing = GetPropertyReply {
+            format: 0,
+            ..reply(atom::NONE, b"")
+        };
+        assert_eq!(decode_text(&missing), None);
+    }
+
+    #[test]
+    fn protocol_lists_are_native_words() {
+        let bytes = to_bytes(&[0x150, 0x151]);
+        assert_eq!(bytes[..4], 0x150u32.to_ne_bytes());
+        assert_eq!(bytes[4..], 0x151u32.to_ne_bytes());
+    }
+}
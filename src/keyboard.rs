@@ -0,0 +1,307 @@
+use std::ops::{BitOr, BitOrAssign};
+
+use crate::cookie::Reply;
+use crate::errors::ParseError;
+use crate::event::MappingNotifyEvent;
+use crate::keysym;
+use crate::protocol::{KeyCode, Keysym};
+use crate::utils::{check_trailing_bytes, deserialize_into, deserialize_into_vec, padding, skip};
+
+/// Mapping from keycodes to keysyms, as returned by GetKeyboardMapping.
+#[derive(Debug, Clone)]
+pub struct KeyboardMapping {
+    /// The keycode of the first entry in `keysyms`.
+    pub first_keycode: KeyCode,
+    /// Number of keysyms per keycode.
+    pub keysyms_per_keycode: u8,
+    /// `keysyms_per_keycode` keysyms for every keycode, starting at `first_keycode`.
+    pub keysyms: Vec<Keysym>,
+}
+
+impl KeyboardMapping {
+    /// Returns the keysyms of a keycode, or an empty slice if the keycode is not mapped.
+    pub fn keysyms(&self, keycode: KeyCode) -> &[Keysym] {
+        let per_keycode = usize::from(self.keysyms_per_keycode);
+        let Some(index) = keycode.checked_sub(self.first_keycode) else {
+            return &[];
+        };
+        let start = usize::from(index) * per_keycode;
+        self.keysyms.get(start..start + per_keycode).unwrap_or(&[])
+    }
+}
+
+impl Reply for KeyboardMapping {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1  n                     keysyms-per-keycode
+        // 2  CARD16                sequence number
+        // 4  nm                    reply length (m = count field from the request)
+        // 24                       unused
+        // 4nm LISTofKEYSYM         keysyms
+        let rest = skip(bytes, 1)?;
+        let (keysyms_per_keycode, rest) = deserialize_into::<u8>(rest)?;
+        let rest = skip(rest, 2)?;
+        let (length, rest) = deserialize_into::<u32>(rest)?;
+        let rest = skip(rest, 24)?;
+        let (keysyms, _) = deserialize_into_vec::<Keysym>(rest, length as usize)?;
+        Ok(KeyboardMapping {
+            // Filled in by the caller, the reply does not repeat it.
+            first_keycode: 0,
+            keysyms_per_keycode,
+            keysyms,
+        })
+    }
+
+    fn validate(&self, _bytes: &[u8]) -> Result<(), ParseError> {
+        let per_keycode = usize::from(self.keysyms_per_keycode);
+        if per_keycode == 0 && !self.keysyms.is_empty() {
+            return Err(ParseError::InvalidValue {
+                type_name: "KeyboardMapping",
+                field: "keysyms-per-keycode",
+                value: 0,
+            });
+        }
+        match self.keysyms.len().checked_rem(per_keycode) {
+            Some(count) if count > 0 => Err(ParseError::TrailingBytes {
+                type_name: "KeyboardMapping",
+                count: count * 4,
+            }),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// The keycodes bound to each of the 8 modifiers, as returned by GetModifierMapping.
+#[derive(Debug, Clone)]
+pub struct ModifierMapping {
+    pub keycodes_per_modifier: u8,
+    /// `keycodes_per_modifier` keycodes for each of Shift, Lock, Control and Mod1 to Mod5.
+    /// Unused entries are zero.
+    pub keycodes: Vec<KeyCode>,
+}
+
+impl ModifierMapping {
+    /// Returns the keycodes bound to the modifier with the given index (0 for Shift, ..., 7 for Mod5).
+    pub fn keycodes(&self, modifier: usize) -> &[KeyCode] {
+        let per_modifier = usize::from(self.keycodes_per_modifier);
+        let start = modifier * per_modifier;
+        self.keycodes
+            .get(start..start + per_modifier)
+            .unwrap_or(&[])
+    }
+
+    /// Returns the index of the modifier the keycode is bound to.
+    pub fn modifier_of(&self, keycode: KeyCode) -> Option<usize> {
+        if keycode == 0 {
+            return None;
+        }
+        (0..8).find(|&modifier| self.keycodes(modifier).contains(&keycode))
+    }
+
+    /// Returns the mask in event states of the modifiers bound to a key with
+    /// `keysym`, or 0 if none is.
+    pub fn mask_of(&self, mapping: &KeyboardMapping, keysym: Keysym) -> u16 {
+        (0..8)
+            .filter(|&modifier| {
+                self.keycodes(modifier)
+                    .iter()
+                    .any(|&keycode| keycode != 0 && mapping.keysyms(keycode).contains(&keysym))
+            })
+            .fold(0, |mask, modifier| mask | 1 << modifier)
+    }
+}
+
+impl Reply for ModifierMapping {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1  n                     keycodes-per-modifier
+        // 2  CARD16                sequence number
+        // 4  2n                    reply length
+        // 24                       unused
+        // 8n LISTofKEYCODE         keycodes
+        let rest = skip(bytes, 1)?;
+        let (keycodes_per_modifier, rest) = deserialize_into::<u8>(rest)?;
+        let rest = skip(rest, 30)?;
+        let (keycodes, _) =
+            deserialize_into_vec::<KeyCode>(rest, usize::from(keycodes_per_modifier) * 8)?;
+        Ok(ModifierMapping {
+            keycodes_per_modifier,
+            keycodes,
+        })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        check_trailing_bytes("ModifierMapping", bytes, 32 + self.keycodes.len())
+    }
+}
+
+/// The logical modifiers held during an event.
+///
+/// The state of key and pointer events only tells which of Shift, Lock, Control
+/// and Mod1 to Mod5 are set; which of Mod1 to Mod5 is Alt or Num Lock depends
+/// on the modifier mapping. [ModifierMasks] decodes states into this type.
+/// Modifiers can be combined with `|`, e.g. `Modifiers::CONTROL | Modifiers::ALT`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Modifiers(pub u16);
+
+impl Modifiers {
+    pub const NONE: Self = Self(0);
+    pub const SHIFT: Self = Self(1 << 0);
+    /// Caps Lock, or Shift Lock on some layouts.
+    pub const LOCK: Self = Self(1 << 1);
+    pub const CONTROL: Self = Self(1 << 2);
+    pub const ALT: Self = Self(1 << 3);
+    pub const META: Self = Self(1 << 4);
+    pub const SUPER: Self = Self(1 << 5);
+    pub const HYPER: Self = Self(1 << 6);
+    pub const NUM_LOCK: Self = Self(1 << 7);
+    pub const MODE_SWITCH: Self = Self(1 << 8);
+    /// The modifiers that stay set while locked.
+    pub const LOCKS: Self = Self(Self::LOCK.0 | Self::NUM_LOCK.0);
+
+    /// Returns true if every modifier in `other` is also in `self`.
+    pub fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Returns true if `self` and `other` share at least one modifier.
+    pub fn intersects(self, other: Self) -> bool {
+        self.0 & other.0 != 0
+    }
+
+    /// Returns the modifiers without Lock and Num Lock, to match hotkeys whether
+    /// or not they are locked.
+    pub fn without_locks(self) -> Self {
+        Self(self.0 & !Self::LOCKS.0)
+    }
+}
+
+impl BitOr for Modifiers {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for Modifiers {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+/// The masks in event states of the logical modifiers, resolved from the
+/// keysyms of the keys bound to Mod1 to Mod5.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ModifierMasks {
+    pub alt: u16,
+    pub meta: u16,
+    pub super_: u16,
+    pub hyper: u16,
+    pub num_lock: u16,
+    pub mode_switch: u16,
+}
+
+impl ModifierMasks {
+    /// Resolves the masks from the keyboard and modifier mappings.
+    pub fn new(mapping: &KeyboardMapping, modifiers: &ModifierMapping) -> ModifierMasks {
+        let mask = |keysyms: &[Keysym]| {
+            keysyms
+                .iter()
+                .fold(0, |mask, &keysym| mask | modifiers.mask_of(mapping, keysym))
+        };
+        ModifierMasks {
+            alt: mask(&[keysym::XK_Alt_L, keysym::XK_Alt_R]),
+            meta: mask(&[keysym::XK_Meta_L, keysym::XK_Meta_R]),
+            super_: mask(&[keysym::XK_Super_L, keysym::XK_Super_R]),
+            hyper: mask(&[keysym::XK_Hyper_L, keysym::XK_Hyper_R]),
+            num_lock: mask(&[keysym::XK_Num_Lock]),
+            mode_switch: mask(&[keysym::XK_Mode_switch, keysym::XK_ISO_Level3_Shift]),
+        }
+    }
+
+    /// Returns the logical modifiers set in the state of an event.
+    pub fn decode(&self, state: u16) -> Modifiers {
+        let mut modifiers = Modifiers(state & 0b111);
+        for (mask, modifier) in self.pairs() {
+            if state & mask != 0 {
+                modifiers |= modifier;
+            }
+        }
+        modifiers
+    }
+
+    /// Returns the state setting the logical modifiers, e.g. for GrabKey.
+    /// Modifiers bound to no key are left out.
+    pub fn encode(&self, modifiers: Modifiers) -> u16 {
+        let mut state = modifiers.0 & 0b111;
+        for (mask, modifier) in self.pairs() {
+            if modifiers.contains(modifier) {
+                state |= mask;
+            }
+        }
+        state
+    }
+
+    fn pairs(&self) -> [(u16, Modifiers); 6] {
+        [
+            (self.alt, Modifiers::ALT),
+            (self.meta, Modifiers::META),
+            (self.super_, Modifiers::SUPER),
+            (self.hyper, Modifiers::HYPER),
+            (self.num_lock, Modifiers::NUM_LOCK),
+            (self.mode_switch, Modifiers::MODE_SWITCH),
+        ]
+    }
+}
+
+/// Mapping from physical pointer buttons to logical buttons, as returned by GetPointerMapping.
+#[derive(Debug, Clone)]
+pub struct PointerMapping {
+    /// Element i holds the logical button of physical button i+1. Zero disables the button.
+    pub map: Vec<u8>,
+}
+
+impl Reply for PointerMapping {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1  n                     length of map
+        // 2  CARD16                sequence number
+        // 4  (n+p)/4               reply length
+        // 24                       unused
+        // n  LISTofCARD8           map
+        let rest = skip(bytes, 1)?;
+        let (length, rest) = deserialize_into::<u8>(rest)?;
+        let rest = skip(rest, 30)?;
+        let (map, _) = deserialize_into_vec::<u8>(rest, length.into())?;
+        Ok(PointerMapping { map })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        let length = self.map.len() + padding(self.map.len(), 4);
+        check_trailing_bytes("PointerMapping", bytes, 32 + length)
+    }
+}
+
+/// The keyboard, modifier and pointer mappings held by a connection.
+///
+/// Each mapping is fetched from the server the first time it is used, and dropped
+/// again when a MappingNotify event says it changed, so it is fetched anew on the
+/// next use. This way long-running clients follow layout changes.
+#[derive(Debug, Default)]
+pub struct KeyboardState {
+    pub(crate) keyboard_mapping: Option<KeyboardMapping>,
+    pub(crate) modifier_mapping: Option<ModifierMapping>,
+    pub(crate) pointer_mapping: Option<PointerMapping>,
+}
+
+impl KeyboardState {
+    /// Drops the mapping the MappingNotify event is about.
+    pub fn invalidate(&mut self, event: &MappingNotifyEvent) {
+        match event.request {
+            0 => self.modifier_mapping = None,
+            1 => self.keyboard_mapping = None,
+            _ => self.pointer_mapping = None,
+        }
+    }
+}
@@ -0,0 +1,5961 @@
+//! Keysyms: the symbols engraved on keys, and their lookup from keycodes.
+//!
+//! Each keycode is mapped to a list of keysyms, two for each group, see
+//! [crate::keyboard::KeyboardMapping]. [lookup] picks the keysym the core
+//! protocol rules give for a modifier state: Mode_switch selects the second
+//! group, Shift and Lock the second keysym of the group, and Num_Lock swaps the
+//! keysyms of the keypad. [keysym_to_char] then gives the character a keysym
+//! types.
+//!
+//! ```no_run
+//! use mousetrap::connection::Connection;
+//! use mousetrap::keysym;
+//!
+//! let mut conn = Connection::init().unwrap();
+//! let (keycode, state) = conn.keysym_to_keycode(keysym::XK_A).unwrap().unwrap();
+//! assert_eq!(conn.keycode_to_keysym(keycode, state).unwrap(), keysym::XK_A);
+//! println!("{:?}", keysym::name(keysym::XK_Return));
+//! assert_eq!(keysym::keysym_to_char(keysym::XK_EuroSign), Some('€'));
+//! ```
+
+#![allow(non_upper_case_globals)]
+
+use crate::connection::Connection;
+use crate::errors::ConnectionError;
+use crate::keyboard::{KeyboardMapping, ModifierMapping};
+use crate::protocol::{KeyCode, Keysym};
+
+/// No keysym, e.g. for the unused entries of a keycode.
+pub const NO_SYMBOL: Keysym = 0;
+
+/// Masks of the modifiers in the state of key and button events.
+pub mod mask {
+    pub const SHIFT: u16 = 1 << 0;
+    pub const LOCK: u16 = 1 << 1;
+    pub const CONTROL: u16 = 1 << 2;
+    pub const MOD1: u16 = 1 << 3;
+    pub const MOD2: u16 = 1 << 4;
+    pub const MOD3: u16 = 1 << 5;
+    pub const MOD4: u16 = 1 << 6;
+    pub const MOD5: u16 = 1 << 7;
+}
+
+/// Offset of the keysyms of Unicode characters: the keysym of a character is
+/// its code point plus this offset.
+pub const UNICODE_OFFSET: Keysym = 0x0100_0000;
+
+/// Returns the name of a keysym, e.g. `Return` for [XK_Return].
+pub fn name(keysym: Keysym) -> Option<&'static str> {
+    NAMES
+        .iter()
+        .find(|&&(_, value)| value == keysym)
+        .map(|&(name, _)| name)
+}
+
+/// Returns the keysym of a name, as given by [name]. Also accepts Unicode
+/// characters as `U20AC` and raw values as `0xff0d`.
+pub fn from_name(name: &str) -> Option<Keysym> {
+    if let Some(&(_, keysym)) = NAMES.iter().find(|&&(n, _)| n == name) {
+        return Some(keysym);
+    }
+    if let Some(hex) = name.strip_prefix('U') {
+        let code_point = u32::from_str_radix(hex, 16).ok()?;
+        char::from_u32(code_point)?;
+        return Some(UNICODE_OFFSET + code_point);
+    }
+    let hex = name.strip_prefix("0x")?;
+    Keysym::from_str_radix(hex, 16).ok()
+}
+
+/// Returns the character a keysym types, or `None` for the keysyms of
+/// functions and modifiers. BackSpace, Tab, Return, Escape and Delete give their
+/// ASCII control characters, and the keypad its digits and operators.
+pub fn keysym_to_char(keysym: Keysym) -> Option<char> {
+    match keysym {
+        // Latin-1, the same as Unicode
+        0x20..=0x7e | 0xa0..=0xff => char::from_u32(keysym),
+        XK_BackSpace..=XK_Clear | XK_Return | XK_Escape | XK_Delete => {
+            char::from_u32(keysym & 0x7f)
+        }
+        XK_KP_Space => Some(' '),
+        XK_KP_Tab | XK_KP_Enter | XK_KP_Multiply..=XK_KP_9 | XK_KP_Equal => {
+            char::from_u32(keysym & 0x7f)
+        }
+        UNICODE_OFFSET.. => char::from_u32(keysym - UNICODE_OFFSET),
+        _ => CHARS
+            .binary_search_by_key(&keysym, |&(keysym, _)| keysym)
+            .ok()
+            .map(|i| CHARS[i].1),
+    }
+}
+
+/// Returns the keysym of a character: the legacy one if there is one, and the
+/// Unicode one otherwise.
+pub fn char_to_keysym(c: char) -> Keysym {
+    let code_point = u32::from(c);
+    match code_point {
+        0x20..=0x7e | 0xa0..=0xff => code_point,
+        0x08 | 0x09 | 0x0d | 0x1b | 0x7f => 0xff00 | code_point,
+        _ => CHARS
+            .iter()
+            .find(|&&(_, legacy)| legacy == c)
+            .map_or(UNICODE_OFFSET + code_point, |&(keysym, _)| keysym),
+    }
+}
+
+/// Whether a keysym is one of the keypad, which Num_Lock affects.
+pub fn is_keypad(keysym: Keysym) -> bool {
+    (XK_KP_Space..=XK_KP_Equal).contains(&keysym) || (0x1100_0000..=0x1100_ffff).contains(&keysym)
+}
+
+/// Returns the lowercase and uppercase forms of a keysym. Both are the keysym
+/// itself if it has no case.
+pub fn convert_case(keysym: Keysym) -> (Keysym, Keysym) {
+    // Unicode keysyms
+    if let Some(c) = keysym.checked_sub(UNICODE_OFFSET).and_then(char::from_u32) {
+        // A case mapping to several characters has no keysym.
+        let single = |chars: &mut dyn Iterator<Item = char>| match (chars.next(), chars.next()) {
+            (Some(c), None) => UNICODE_OFFSET + u32::from(c),
+            _ => keysym,
+        };
+        let lower = single(&mut c.to_lowercase());
+        let upper = single(&mut c.to_uppercase());
+        return (lower, upper);
+    }
+    match keysym {
+        // Latin-1
+        XK_A..=XK_Z => (keysym + 0x20, keysym),
+        XK_a..=XK_z => (keysym, keysym - 0x20),
+        XK_Agrave..=XK_Thorn if keysym != XK_multiply => (keysym + 0x20, keysym),
+        XK_agrave..=XK_thorn if keysym != XK_division => (keysym, keysym - 0x20),
+        // Cyrillic, lowercase first
+        XK_Cyrillic_yu..=XK_Cyrillic_hardsign => (keysym, keysym + 0x20),
+        XK_Cyrillic_YU..=XK_Cyrillic_HARDSIGN => (keysym - 0x20, keysym),
+        // Greek, uppercase first
+        XK_Greek_ALPHA..=XK_Greek_OMEGA => (keysym + 0x20, keysym),
+        XK_Greek_alpha..=XK_Greek_omega if keysym != XK_Greek_finalsmallsigma => {
+            (keysym, keysym - 0x20)
+        }
+        _ => (keysym, keysym),
+    }
+}
+
+/// Returns the two keysyms of a group of a keycode, following the rules for
+/// the lists shorter than 4 keysyms.
+fn group_keysyms(keysyms: &[Keysym], group: usize) -> (Keysym, Keysym) {
+    let length = keysyms
+        .iter()
+        .rposition(|&k| k != NO_SYMBOL)
+        .map_or(0, |i| i + 1);
+    // A list of 1 or 2 keysyms is the same in both groups.
+    let group = if length <= 2 { 0 } else { group };
+    let get = |i: usize| keysyms.get(i).copied().unwrap_or(NO_SYMBOL);
+    let (first, second) = (get(2 * group), get(2 * group + 1));
+    if second != NO_SYMBOL {
+        return (first, second);
+    }
+    // A single keysym with a case stands for both of its forms.
+    let (lower, upper) = convert_case(first);
+    if lower != upper {
+        (lower, upper)
+    } else {
+        (first, first)
+    }
+}
+
+/// Returns the keysym of a keycode in a modifier state, following the rules of
+/// the core protocol. `state` is the one of a key event, a combination of the
+/// [mask] values.
+pub fn lookup(
+    mapping: &KeyboardMapping,
+    modifiers: &ModifierMapping,
+    keycode: KeyCode,
+    state: u16,
+) -> Keysym {
+    let keysyms = mapping.keysyms(keycode);
+    let mode_switch = modifiers.mask_of(mapping, XK_Mode_switch);
+    let num_lock = modifiers.mask_of(mapping, XK_Num_Lock);
+    let group = usize::from(state & mode_switch != 0);
+    let (first, second) = group_keysyms(keysyms, group);
+
+    // Lock is Caps Lock or Shift Lock depending on the keysyms bound to it, and
+    // ignored otherwise.
+    let lock_keysyms = modifiers
+        .keycodes(1)
+        .iter()
+        .flat_map(|&keycode| mapping.keysyms(keycode));
+    let (mut caps_lock, mut shift_lock) = (false, false);
+    if state & mask::LOCK != 0 {
+        for &keysym in lock_keysyms {
+            caps_lock |= keysym == XK_Caps_Lock;
+            shift_lock |= keysym == XK_Shift_Lock;
+        }
+    }
+    let shift = state & mask::SHIFT != 0;
+
+    if state & num_lock != 0 && is_keypad(second) {
+        if shift || shift_lock && !caps_lock {
+            first
+        } else {
+            second
+        }
+    } else if caps_lock {
+        let keysym = if shift { second } else { first };
+        convert_case(keysym).1
+    } else if shift || shift_lock {
+        second
+    } else {
+        first
+    }
+}
+
+impl Connection {
+    /// Returns the keysym of a keycode in a modifier state, using the cached
+    /// keyboard and modifier mappings. See [lookup].
+    pub fn keycode_to_keysym(
+        &mut self,
+        keycode: KeyCode,
+        state: u16,
+    ) -> Result<Keysym, ConnectionError> {
+        let modifiers = self.modifier_mapping()?.clone();
+        let mapping = self.keyboard_mapping()?;
+        Ok(lookup(mapping, &modifiers, keycode, state))
+    }
+
+    /// Returns the character a keycode types in a modifier state, see
+    /// [keysym_to_char]. Control does not turn letters into control characters.
+    pub fn keycode_to_char(
+        &mut self,
+        keycode: KeyCode,
+        state: u16,
+    ) -> Result<Option<char>, ConnectionError> {
+        Ok(keysym_to_char(self.keycode_to_keysym(keycode, state)?))
+    }
+
+    /// Returns a keycode and a modifier state producing a keysym, with the fewest
+    /// modifiers, or `None` if no key produces it.
+    pub fn keysym_to_keycode(
+        &mut self,
+        keysym: Keysym,
+    ) -> Result<Option<(KeyCode, u16)>, ConnectionError> {
+        if keysym == NO_SYMBOL {
+            return Ok(None);
+        }
+        let modifiers = self.modifier_mapping()?.clone();
+        let mapping = self.keyboard_mapping()?;
+        let mode_switch = modifiers.mask_of(mapping, XK_Mode_switch);
+        let per_keycode = usize::from(mapping.keysyms_per_keycode.max(1));
+        let count = mapping.keysyms.len() / per_keycode;
+        let last_keycode = usize::from(mapping.first_keycode) + count;
+        let keycodes = (mapping.first_keycode..=KeyCode::MAX)
+            .take_while(|&keycode| usize::from(keycode) < last_keycode);
+        let mut states = vec![0, mask::SHIFT];
+        if mode_switch != 0 {
+            states.extend([mode_switch, mode_switch | mask::SHIFT]);
+        }
+        for state in states {
+            for keycode in keycodes.clone() {
+                if lookup(mapping, &modifiers, keycode, state) == keysym {
+                    return Ok(Some((keycode, state)));
+                }
+            }
+        }
+        Ok(None)
+    }
+}
+
+// Generated from keysymdef.h and XF86keysym.h of xorgproto.
+
+pub const XK_VoidSymbol: Keysym = 0xffffff;
+pub const XK_BackSpace: Keysym = 0xff08;
+pub const XK_Tab: Keysym = 0xff09;
+pub const XK_Linefeed: Keysym = 0xff0a;
+pub const XK_Clear: Keysym = 0xff0b;
+pub const XK_Return: Keysym = 0xff0d;
+pub const XK_Pause: Keysym = 0xff13;
+pub const XK_Scroll_Lock: Keysym = 0xff14;
+pub const XK_Sys_Req: Keysym = 0xff15;
+pub const XK_Escape: Keysym = 0xff1b;
+pub const XK_Delete: Keysym = 0xffff;
+pub const XK_Multi_key: Keysym = 0xff20;
+pub const XK_Codeinput: Keysym = 0xff37;
+pub const XK_SingleCandidate: Keysym = 0xff3c;
+pub const XK_MultipleCandidate: Keysym = 0xff3d;
+pub const XK_PreviousCandidate: Keysym = 0xff3e;
+pub const XK_Kanji: Keysym = 0xff21;
+pub const XK_Muhenkan: Keysym = 0xff22;
+pub const XK_Henkan_Mode: Keysym = 0xff23;
+pub const XK_Henkan: Keysym = 0xff23;
+pub const XK_Romaji: Keysym = 0xff24;
+pub const XK_Hiragana: Keysym = 0xff25;
+pub const XK_Katakana: Keysym = 0xff26;
+pub const XK_Hiragana_Katakana: Keysym = 0xff27;
+pub const XK_Zenkaku: Keysym = 0xff28;
+pub const XK_Hankaku: Keysym = 0xff29;
+pub const XK_Zenkaku_Hankaku: Keysym = 0xff2a;
+pub const XK_Touroku: Keysym = 0xff2b;
+pub const XK_Massyo: Keysym = 0xff2c;
+pub const XK_Kana_Lock: Keysym = 0xff2d;
+pub const XK_Kana_Shift: Keysym = 0xff2e;
+pub const XK_Eisu_Shift: Keysym = 0xff2f;
+pub const XK_Eisu_toggle: Keysym = 0xff30;
+pub const XK_Kanji_Bangou: Keysym = 0xff37;
+pub const XK_Zen_Koho: Keysym = 0xff3d;
+pub const XK_Mae_Koho: Keysym = 0xff3e;
+pub const XK_Home: Keysym = 0xff50;
+pub const XK_Left: Keysym = 0xff51;
+pub const XK_Up: Keysym = 0xff52;
+pub const XK_Right: Keysym = 0xff53;
+pub const XK_Down: Keysym = 0xff54;
+pub const XK_Prior: Keysym = 0xff55;
+pub const XK_Page_Up: Keysym = 0xff55;
+pub const XK_Next: Keysym = 0xff56;
+pub const XK_Page_Down: Keysym = 0xff56;
+pub const XK_End: Keysym = 0xff57;
+pub const XK_Begin: Keysym = 0xff58;
+pub const XK_Select: Keysym = 0xff60;
+pub const XK_Print: Keysym = 0xff61;
+pub const XK_Execute: Keysym = 0xff62;
+pub const XK_Insert: Keysym = 0xff63;
+pub const XK_Undo: Keysym = 0xff65;
+pub const XK_Redo: Keysym = 0xff66;
+pub const XK_Menu: Keysym = 0xff67;
+pub const XK_Find: Keysym = 0xff68;
+pub const XK_Cancel: Keysym = 0xff69;
+pub const XK_Help: Keysym = 0xff6a;
+pub const XK_Break: Keysym = 0xff6b;
+pub const XK_Mode_switch: Keysym = 0xff7e;
+pub const XK_script_switch: Keysym = 0xff7e;
+pub const XK_Num_Lock: Keysym = 0xff7f;
+pub const XK_KP_Space: Keysym = 0xff80;
+pub const XK_KP_Tab: Keysym = 0xff89;
+pub const XK_KP_Enter: Keysym = 0xff8d;
+pub const XK_KP_F1: Keysym = 0xff91;
+pub const XK_KP_F2: Keysym = 0xff92;
+pub const XK_KP_F3: Keysym = 0xff93;
+pub const XK_KP_F4: Keysym = 0xff94;
+pub const XK_KP_Home: Keysym = 0xff95;
+pub const XK_KP_Left: Keysym = 0xff96;
+pub const XK_KP_Up: Keysym = 0xff97;
+pub const XK_KP_Right: Keysym = 0xff98;
+pub const XK_KP_Down: Keysym = 0xff99;
+pub const XK_KP_Prior: Keysym = 0xff9a;
+pub const XK_KP_Page_Up: Keysym = 0xff9a;
+pub const XK_KP_Next: Keysym = 0xff9b;
+pub const XK_KP_Page_Down: Keysym = 0xff9b;
+pub const XK_KP_End: Keysym = 0xff9c;
+pub const XK_KP_Begin: Keysym = 0xff9d;
+pub const XK_KP_Insert: Keysym = 0xff9e;
+pub const XK_KP_Delete: Keysym = 0xff9f;
+pub const XK_KP_Equal: Keysym = 0xffbd;
+pub const XK_KP_Multiply: Keysym = 0xffaa;
+pub const XK_KP_Add: Keysym = 0xffab;
+pub const XK_KP_Separator: Keysym = 0xffac;
+pub const XK_KP_Subtract: Keysym = 0xffad;
+pub const XK_KP_Decimal: Keysym = 0xffae;
+pub const XK_KP_Divide: Keysym = 0xffaf;
+pub const XK_KP_0: Keysym = 0xffb0;
+pub const XK_KP_1: Keysym = 0xffb1;
+pub const XK_KP_2: Keysym = 0xffb2;
+pub const XK_KP_3: Keysym = 0xffb3;
+pub const XK_KP_4: Keysym = 0xffb4;
+pub const XK_KP_5: Keysym = 0xffb5;
+pub const XK_KP_6: Keysym = 0xffb6;
+pub const XK_KP_7: Keysym = 0xffb7;
+pub const XK_KP_8: Keysym = 0xffb8;
+pub const XK_KP_9: Keysym = 0xffb9;
+pub const XK_F1: Keysym = 0xffbe;
+pub const XK_F2: Keysym = 0xffbf;
+pub const XK_F3: Keysym = 0xffc0;
+pub const XK_F4: Keysym = 0xffc1;
+pub const XK_F5: Keysym = 0xffc2;
+pub const XK_F6: Keysym = 0xffc3;
+pub const XK_F7: Keysym = 0xffc4;
+pub const XK_F8: Keysym = 0xffc5;
+pub const XK_F9: Keysym = 0xffc6;
+pub const XK_F10: Keysym = 0xffc7;
+pub const XK_F11: Keysym = 0xffc8;
+pub const XK_L1: Keysym = 0xffc8;
+pub const XK_F12: Keysym = 0xffc9;
+pub const XK_L2: Keysym = 0xffc9;
+pub const XK_F13: Keysym = 0xffca;
+pub const XK_L3: Keysym = 0xffca;
+pub const XK_F14: Keysym = 0xffcb;
+pub const XK_L4: Keysym = 0xffcb;
+pub const XK_F15: Keysym = 0xffcc;
+pub const XK_L5: Keysym = 0xffcc;
+pub const XK_F16: Keysym = 0xffcd;
+pub const XK_L6: Keysym = 0xffcd;
+pub const XK_F17: Keysym = 0xffce;
+pub const XK_L7: Keysym = 0xffce;
+pub const XK_F18: Keysym = 0xffcf;
+pub const XK_L8: Keysym = 0xffcf;
+pub const XK_F19: Keysym = 0xffd0;
+pub const XK_L9: Keysym = 0xffd0;
+pub const XK_F20: Keysym = 0xffd1;
+pub const XK_L10: Keysym = 0xffd1;
+pub const XK_F21: Keysym = 0xffd2;
+pub const XK_R1: Keysym = 0xffd2;
+pub const XK_F22: Keysym = 0xffd3;
+pub const XK_R2: Keysym = 0xffd3;
+pub const XK_F23: Keysym = 0xffd4;
+pub const XK_R3: Keysym = 0xffd4;
+pub const XK_F24: Keysym = 0xffd5;
+pub const XK_R4: Keysym = 0xffd5;
+pub const XK_F25: Keysym = 0xffd6;
+pub const XK_R5: Keysym = 0xffd6;
+pub const XK_F26: Keysym = 0xffd7;
+pub const XK_R6: Keysym = 0xffd7;
+pub const XK_F27: Keysym = 0xffd8;
+pub const XK_R7: Keysym = 0xffd8;
+pub const XK_F28: Keysym = 0xffd9;
+pub const XK_R8: Keysym = 0xffd9;
+pub const XK_F29: Keysym = 0xffda;
+pub const XK_R9: Keysym = 0xffda;
+pub const XK_F30: Keysym = 0xffdb;
+pub const XK_R10: Keysym = 0xffdb;
+pub const XK_F31: Keysym = 0xffdc;
+pub const XK_R11: Keysym = 0xffdc;
+pub const XK_F32: Keysym = 0xffdd;
+pub const XK_R12: Keysym = 0xffdd;
+pub const XK_F33: Keysym = 0xffde;
+pub const XK_R13: Keysym = 0xffde;
+pub const XK_F34: Keysym = 0xffdf;
+pub const XK_R14: Keysym = 0xffdf;
+pub const XK_F35: Keysym = 0xffe0;
+pub const XK_R15: Keysym = 0xffe0;
+pub const XK_Shift_L: Keysym = 0xffe1;
+pub const XK_Shift_R: Keysym = 0xffe2;
+pub const XK_Control_L: Keysym = 0xffe3;
+pub const XK_Control_R: Keysym = 0xffe4;
+pub const XK_Caps_Lock: Keysym = 0xffe5;
+pub const XK_Shift_Lock: Keysym = 0xffe6;
+pub const XK_Meta_L: Keysym = 0xffe7;
+pub const XK_Meta_R: Keysym = 0xffe8;
+pub const XK_Alt_L: Keysym = 0xffe9;
+pub const XK_Alt_R: Keysym = 0xffea;
+pub const XK_Super_L: Keysym = 0xffeb;
+pub const XK_Super_R: Keysym = 0xffec;
+pub const XK_Hyper_L: Keysym = 0xffed;
+pub const XK_Hyper_R: Keysym = 0xffee;
+pub const XK_ISO_Lock: Keysym = 0xfe01;
+pub const XK_ISO_Level2_Latch: Keysym = 0xfe02;
+pub const XK_ISO_Level3_Shift: Keysym = 0xfe03;
+pub const XK_ISO_Level3_Latch: Keysym = 0xfe04;
+pub const XK_ISO_Level3_Lock: Keysym = 0xfe05;
+pub const XK_ISO_Level5_Shift: Keysym = 0xfe11;
+pub const XK_ISO_Level5_Latch: Keysym = 0xfe12;
+pub const XK_ISO_Level5_Lock: Keysym = 0xfe13;
+pub const XK_ISO_Group_Shift: Keysym = 0xff7e;
+pub const XK_ISO_Group_Latch: Keysym = 0xfe06;
+pub const XK_ISO_Group_Lock: Keysym = 0xfe07;
+pub const XK_ISO_Next_Group: Keysym = 0xfe08;
+pub const XK_ISO_Next_Group_Lock: Keysym = 0xfe09;
+pub const XK_ISO_Prev_Group: Keysym = 0xfe0a;
+pub const XK_ISO_Prev_Group_Lock: Keysym = 0xfe0b;
+pub const XK_ISO_First_Group: Keysym = 0xfe0c;
+pub const XK_ISO_First_Group_Lock: Keysym = 0xfe0d;
+pub const XK_ISO_Last_Group: Keysym = 0xfe0e;
+pub const XK_ISO_Last_Group_Lock: Keysym = 0xfe0f;
+pub const XK_ISO_Left_Tab: Keysym = 0xfe20;
+pub const XK_ISO_Move_Line_Up: Keysym = 0xfe21;
+pub const XK_ISO_Move_Line_Down: Keysym = 0xfe22;
+pub const XK_ISO_Partial_Line_Up: Keysym = 0xfe23;
+pub const XK_ISO_Partial_Line_Down: Keysym = 0xfe24;
+pub const XK_ISO_Partial_Space_Left: Keysym = 0xfe25;
+pub const XK_ISO_Partial_Space_Right: Keysym = 0xfe26;
+pub const XK_ISO_Set_Margin_Left: Keysym = 0xfe27;
+pub const XK_ISO_Set_Margin_Right: Keysym = 0xfe28;
+pub const XK_ISO_Release_Margin_Left: Keysym = 0xfe29;
+pub const XK_ISO_Release_Margin_Right: Keysym = 0xfe2a;
+pub const XK_ISO_Release_Both_Margins: Keysym = 0xfe2b;
+pub const XK_ISO_Fast_Cursor_Left: Keysym = 0xfe2c;
+pub const XK_ISO_Fast_Cursor_Right: Keysym = 0xfe2d;
+pub const XK_ISO_Fast_Cursor_Up: Keysym = 0xfe2e;
+pub const XK_ISO_Fast_Cursor_Down: Keysym = 0xfe2f;
+pub const XK_ISO_Continuous_Underline: Keysym = 0xfe30;
+pub const XK_ISO_Discontinuous_Underline: Keysym = 0xfe31;
+pub const XK_ISO_Emphasize: Keysym = 0xfe32;
+pub const XK_ISO_Center_Object: Keysym = 0xfe33;
+pub const XK_ISO_Enter: Keysym = 0xfe34;
+pub const XK_dead_grave: Keysym = 0xfe50;
+pub const XK_dead_acute: Keysym = 0xfe51;
+pub const XK_dead_circumflex: Keysym = 0xfe52;
+pub const XK_dead_tilde: Keysym = 0xfe53;
+pub const XK_dead_perispomeni: Keysym = 0xfe53;
+pub const XK_dead_macron: Keysym = 0xfe54;
+pub const XK_dead_breve: Keysym = 0xfe55;
+pub const XK_dead_abovedot: Keysym = 0xfe56;
+pub const XK_dead_diaeresis: Keysym = 0xfe57;
+pub const XK_dead_abovering: Keysym = 0xfe58;
+pub const XK_dead_doubleacute: Keysym = 0xfe59;
+pub const XK_dead_caron: Keysym = 0xfe5a;
+pub const XK_dead_cedilla: Keysym = 0xfe5b;
+pub const XK_dead_ogonek: Keysym = 0xfe5c;
+pub const XK_dead_iota: Keysym = 0xfe5d;
+pub const XK_dead_voiced_sound: Keysym = 0xfe5e;
+pub const XK_dead_semivoiced_sound: Keysym = 0xfe5f;
+pub const XK_dead_belowdot: Keysym = 0xfe60;
+pub const XK_dead_hook: Keysym = 0xfe61;
+pub const XK_dead_horn: Keysym = 0xfe62;
+pub const XK_dead_stroke: Keysym = 0xfe63;
+pub const XK_dead_abovecomma: Keysym = 0xfe64;
+pub const XK_dead_psili: Keysym = 0xfe64;
+pub const XK_dead_abovereversedcomma: Keysym = 0xfe65;
+pub const XK_dead_dasia: Keysym = 0xfe65;
+pub const XK_dead_doublegrave: Keysym = 0xfe66;
+pub const XK_dead_belowring: Keysym = 0xfe67;
+pub const XK_dead_belowmacron: Keysym = 0xfe68;
+pub const XK_dead_belowcircumflex: Keysym = 0xfe69;
+pub const XK_dead_belowtilde: Keysym = 0xfe6a;
+pub const XK_dead_belowbreve: Keysym = 0xfe6b;
+pub const XK_dead_belowdiaeresis: Keysym = 0xfe6c;
+pub const XK_dead_invertedbreve: Keysym = 0xfe6d;
+pub const XK_dead_belowcomma: Keysym = 0xfe6e;
+pub const XK_dead_currency: Keysym = 0xfe6f;
+pub const XK_dead_lowline: Keysym = 0xfe90;
+pub const XK_dead_aboveverticalline: Keysym = 0xfe91;
+pub const XK_dead_belowverticalline: Keysym = 0xfe92;
+pub const XK_dead_longsolidusoverlay: Keysym = 0xfe93;
+pub const XK_dead_a: Keysym = 0xfe80;
+pub const XK_dead_A: Keysym = 0xfe81;
+pub const XK_dead_e: Keysym = 0xfe82;
+pub const XK_dead_E: Keysym = 0xfe83;
+pub const XK_dead_i: Keysym = 0xfe84;
+pub const XK_dead_I: Keysym = 0xfe85;
+pub const XK_dead_o: Keysym = 0xfe86;
+pub const XK_dead_O: Keysym = 0xfe87;
+pub const XK_dead_u: Keysym = 0xfe88;
+pub const XK_dead_U: Keysym = 0xfe89;
+pub const XK_dead_small_schwa: Keysym = 0xfe8a;
+pub const XK_dead_capital_schwa: Keysym = 0xfe8b;
+pub const XK_dead_greek: Keysym = 0xfe8c;
+pub const XK_First_Virtual_Screen: Keysym = 0xfed0;
+pub const XK_Prev_Virtual_Screen: Keysym = 0xfed1;
+pub const XK_Next_Virtual_Screen: Keysym = 0xfed2;
+pub const XK_Last_Virtual_Screen: Keysym = 0xfed4;
+pub const XK_Terminate_Server: Keysym = 0xfed5;
+pub const XK_AccessX_Enable: Keysym = 0xfe70;
+pub const XK_AccessX_Feedback_Enable: Keysym = 0xfe71;
+pub const XK_RepeatKeys_Enable: Keysym = 0xfe72;
+pub const XK_SlowKeys_Enable: Keysym = 0xfe73;
+pub const XK_BounceKeys_Enable: Keysym = 0xfe74;
+pub const XK_StickyKeys_Enable: Keysym = 0xfe75;
+pub const XK_MouseKeys_Enable: Keysym = 0xfe76;
+pub const XK_MouseKeys_Accel_Enable: Keysym = 0xfe77;
+pub const XK_Overlay1_Enable: Keysym = 0xfe78;
+pub const XK_Overlay2_Enable: Keysym = 0xfe79;
+pub const XK_AudibleBell_Enable: Keysym = 0xfe7a;
+pub const XK_Pointer_Left: Keysym = 0xfee0;
+pub const XK_Pointer_Right: Keysym = 0xfee1;
+pub const XK_Pointer_Up: Keysym = 0xfee2;
+pub const XK_Pointer_Down: Keysym = 0xfee3;
+pub const XK_Pointer_UpLeft: Keysym = 0xfee4;
+pub const XK_Pointer_UpRight: Keysym = 0xfee5;
+pub const XK_Pointer_DownLeft: Keysym = 0xfee6;
+pub const XK_Pointer_DownRight: Keysym = 0xfee7;
+pub const XK_Pointer_Button_Dflt: Keysym = 0xfee8;
+pub const XK_Pointer_Button1: Keysym = 0xfee9;
+pub const XK_Pointer_Button2: Keysym = 0xfeea;
+pub const XK_Pointer_Button3: Keysym = 0xfeeb;
+pub const XK_Pointer_Button4: Keysym = 0xfeec;
+pub const XK_Pointer_Button5: Keysym = 0xfeed;
+pub const XK_Pointer_DblClick_Dflt: Keysym = 0xfeee;
+pub const XK_Pointer_DblClick1: Keysym = 0xfeef;
+pub const XK_Pointer_DblClick2: Keysym = 0xfef0;
+pub const XK_Pointer_DblClick3: Keysym = 0xfef1;
+pub const XK_Pointer_DblClick4: Keysym = 0xfef2;
+pub const XK_Pointer_DblClick5: Keysym = 0xfef3;
+pub const XK_Pointer_Drag_Dflt: Keysym = 0xfef4;
+pub const XK_Pointer_Drag1: Keysym = 0xfef5;
+pub const XK_Pointer_Drag2: Keysym = 0xfef6;
+pub const XK_Pointer_Drag3: Keysym = 0xfef7;
+pub const XK_Pointer_Drag4: Keysym = 0xfef8;
+pub const XK_Pointer_Drag5: Keysym = 0xfefd;
+pub const XK_Pointer_EnableKeys: Keysym = 0xfef9;
+pub const XK_Pointer_Accelerate: Keysym = 0xfefa;
+pub const XK_Pointer_DfltBtnNext: Keysym = 0xfefb;
+pub const XK_Pointer_DfltBtnPrev: Keysym = 0xfefc;
+pub const XK_ch: Keysym = 0xfea0;
+pub const XK_Ch: Keysym = 0xfea1;
+pub const XK_CH: Keysym = 0xfea2;
+pub const XK_c_h: Keysym = 0xfea3;
+pub const XK_C_h: Keysym = 0xfea4;
+pub const XK_C_H: Keysym = 0xfea5;
+pub const XK_3270_Duplicate: Keysym = 0xfd01;
+pub const XK_3270_FieldMark: Keysym = 0xfd02;
+pub const XK_3270_Right2: Keysym = 0xfd03;
+pub const XK_3270_Left2: Keysym = 0xfd04;
+pub const XK_3270_BackTab: Keysym = 0xfd05;
+pub const XK_3270_EraseEOF: Keysym = 0xfd06;
+pub const XK_3270_EraseInput: Keysym = 0xfd07;
+pub const XK_3270_Reset: Keysym = 0xfd08;
+pub const XK_3270_Quit: Keysym = 0xfd09;
+pub const XK_3270_PA1: Keysym = 0xfd0a;
+pub const XK_3270_PA2: Keysym = 0xfd0b;
+pub const XK_3270_PA3: Keysym = 0xfd0c;
+pub const XK_3270_Test: Keysym = 0xfd0d;
+pub const XK_3270_Attn: Keysym = 0xfd0e;
+pub const XK_3270_CursorBlink: Keysym = 0xfd0f;
+pub const XK_3270_AltCursor: Keysym = 0xfd10;
+pub const XK_3270_KeyClick: Keysym = 0xfd11;
+pub const XK_3270_Jump: Keysym = 0xfd12;
+pub const XK_3270_Ident: Keysym = 0xfd13;
+pub const XK_3270_Rule: Keysym = 0xfd14;
+pub const XK_3270_Copy: Keysym = 0xfd15;
+pub const XK_3270_Play: Keysym = 0xfd16;
+pub const XK_3270_Setup: Keysym = 0xfd17;
+pub const XK_3270_Record: Keysym = 0xfd18;
+pub const XK_3270_ChangeScreen: Keysym = 0xfd19;
+pub const XK_3270_DeleteWord: Keysym = 0xfd1a;
+pub const XK_3270_ExSelect: Keysym = 0xfd1b;
+pub const XK_3270_CursorSelect: Keysym = 0xfd1c;
+pub const XK_3270_PrintScreen: Keysym = 0xfd1d;
+pub const XK_3270_Enter: Keysym = 0xfd1e;
+pub const XK_space: Keysym = 0x0020;
+pub const XK_exclam: Keysym = 0x0021;
+pub const XK_quotedbl: Keysym = 0x0022;
+pub const XK_numbersign: Keysym = 0x0023;
+pub const XK_dollar: Keysym = 0x0024;
+pub const XK_percent: Keysym = 0x0025;
+pub const XK_ampersand: Keysym = 0x0026;
+pub const XK_apostrophe: Keysym = 0x0027;
+pub const XK_quoteright: Keysym = 0x0027;
+pub const XK_parenleft: Keysym = 0x0028;
+pub const XK_parenright: Keysym = 0x0029;
+pub const XK_asterisk: Keysym = 0x002a;
+pub const XK_plus: Keysym = 0x002b;
+pub const XK_comma: Keysym = 0x002c;
+pub const XK_minus: Keysym = 0x002d;
+pub const XK_period: Keysym = 0x002e;
+pub const XK_slash: Keysym = 0x002f;
+pub const XK_0: Keysym = 0x0030;
+pub const XK_1: Keysym = 0x0031;
+pub const XK_2: Keysym = 0x0032;
+pub const XK_3: Keysym = 0x0033;
+pub const XK_4: Keysym = 0x0034;
+pub const XK_5: Keysym = 0x0035;
+pub const XK_6: Keysym = 0x0036;
+pub const XK_7: Keysym = 0x0037;
+pub const XK_8: Keysym = 0x0038;
+pub const XK_9: Keysym = 0x0039;
+pub const XK_colon: Keysym = 0x003a;
+pub const XK_semicolon: Keysym = 0x003b;
+pub const XK_less: Keysym = 0x003c;
+pub const XK_equal: Keysym = 0x003d;
+pub const XK_greater: Keysym = 0x003e;
+pub const XK_question: Keysym = 0x003f;
+pub const XK_at: Keysym = 0x0040;
+pub const XK_A: Keysym = 0x0041;
+pub const XK_B: Keysym = 0x0042;
+pub const XK_C: Keysym = 0x0043;
+pub const XK_D: Keysym = 0x0044;
+pub const XK_E: Keysym = 0x0045;
+pub const XK_F: Keysym = 0x0046;
+pub const XK_G: Keysym = 0x0047;
+pub const XK_H: Keysym = 0x0048;
+pub const XK_I: Keysym = 0x0049;
+pub const XK_J: Keysym = 0x004a;
+pub const XK_K: Keysym = 0x004b;
+pub const XK_L: Keysym = 0x004c;
+pub const XK_M: Keysym = 0x004d;
+pub const XK_N: Keysym = 0x004e;
+pub const XK_O: Keysym = 0x004f;
+pub const XK_P: Keysym = 0x0050;
+pub const XK_Q: Keysym = 0x0051;
+pub const XK_R: Keysym = 0x0052;
+pub const XK_S: Keysym = 0x0053;
+pub const XK_T: Keysym = 0x0054;
+pub const XK_U: Keysym = 0x0055;
+pub const XK_V: Keysym = 0x0056;
+pub const XK_W: Keysym = 0x0057;
+pub const XK_X: Keysym = 0x0058;
+pub const XK_Y: Keysym = 0x0059;
+pub const XK_Z: Keysym = 0x005a;
+pub const XK_bracketleft: Keysym = 0x005b;
+pub const XK_backslash: Keysym = 0x005c;
+pub const XK_bracketright: Keysym = 0x005d;
+pub const XK_asciicircum: Keysym = 0x005e;
+pub const XK_underscore: Keysym = 0x005f;
+pub const XK_grave: Keysym = 0x0060;
+pub const XK_quoteleft: Keysym = 0x0060;
+pub const XK_a: Keysym = 0x0061;
+pub const XK_b: Keysym = 0x0062;
+pub const XK_c: Keysym = 0x0063;
+pub const XK_d: Keysym = 0x0064;
+pub const XK_e: Keysym = 0x0065;
+pub const XK_f: Keysym = 0x0066;
+pub const XK_g: Keysym = 0x0067;
+pub const XK_h: Keysym = 0x0068;
+pub const XK_i: Keysym = 0x0069;
+pub const XK_j: Keysym = 0x006a;
+pub const XK_k: Keysym = 0x006b;
+pub const XK_l: Keysym = 0x006c;
+pub const XK_m: Keysym = 0x006d;
+pub const XK_n: Keysym = 0x006e;
+pub const XK_o: Keysym = 0x006f;
+pub const XK_p: Keysym = 0x0070;
+pub const XK_q: Keysym = 0x0071;
+pub const XK_r: Keysym = 0x0072;
+pub const XK_s: Keysym = 0x0073;
+pub const XK_t: Keysym = 0x0074;
+pub const XK_u: Keysym = 0x0075;
+pub const XK_v: Keysym = 0x0076;
+pub const XK_w: Keysym = 0x0077;
+pub const XK_x: Keysym = 0x0078;
+pub const XK_y: Keysym = 0x0079;
+pub const XK_z: Keysym = 0x007a;
+pub const XK_braceleft: Keysym = 0x007b;
+pub const XK_bar: Keysym = 0x007c;
+pub const XK_braceright: Keysym = 0x007d;
+pub const XK_asciitilde: Keysym = 0x007e;
+pub const XK_nobreakspace: Keysym = 0x00a0;
+pub const XK_exclamdown: Keysym = 0x00a1;
+pub const XK_cent: Keysym = 0x00a2;
+pub const XK_sterling: Keysym = 0x00a3;
+pub const XK_currency: Keysym = 0x00a4;
+pub const XK_yen: Keysym = 0x00a5;
+pub const XK_brokenbar: Keysym = 0x00a6;
+pub const XK_section: Keysym = 0x00a7;
+pub const XK_diaeresis: Keysym = 0x00a8;
+pub const XK_copyright: Keysym = 0x00a9;
+pub const XK_ordfeminine: Keysym = 0x00aa;
+pub const XK_guillemotleft: Keysym = 0x00ab;
+pub const XK_notsign: Keysym = 0x00ac;
+pub const XK_hyphen: Keysym = 0x00ad;
+pub const XK_registered: Keysym = 0x00ae;
+pub const XK_macron: Keysym = 0x00af;
+pub const XK_degree: Keysym = 0x00b0;
+pub const XK_plusminus: Keysym = 0x00b1;
+pub const XK_twosuperior: Keysym = 0x00b2;
+pub const XK_threesuperior: Keysym = 0x00b3;
+pub const XK_acute: Keysym = 0x00b4;
+pub const XK_mu: Keysym = 0x00b5;
+pub const XK_paragraph: Keysym = 0x00b6;
+pub const XK_periodcentered: Keysym = 0x00b7;
+pub const XK_cedilla: Keysym = 0x00b8;
+pub const XK_onesuperior: Keysym = 0x00b9;
+pub const XK_masculine: Keysym = 0x00ba;
+pub const XK_guillemotright: Keysym = 0x00bb;
+pub const XK_onequarter: Keysym = 0x00bc;
+pub const XK_onehalf: Keysym = 0x00bd;
+pub const XK_threequarters: Keysym = 0x00be;
+pub const XK_questiondown: Keysym = 0x00bf;
+pub const XK_Agrave: Keysym = 0x00c0;
+pub const XK_Aacute: Keysym = 0x00c1;
+pub const XK_Acircumflex: Keysym = 0x00c2;
+pub const XK_Atilde: Keysym = 0x00c3;
+pub const XK_Adiaeresis: Keysym = 0x00c4;
+pub const XK_Aring: Keysym = 0x00c5;
+pub const XK_AE: Keysym = 0x00c6;
+pub const XK_Ccedilla: Keysym = 0x00c7;
+pub const XK_Egrave: Keysym = 0x00c8;
+pub const XK_Eacute: Keysym = 0x00c9;
+pub const XK_Ecircumflex: Keysym = 0x00ca;
+pub const XK_Ediaeresis: Keysym = 0x00cb;
+pub const XK_Igrave: Keysym = 0x00cc;
+pub const XK_Iacute: Keysym = 0x00cd;
+pub const XK_Icircumflex: Keysym = 0x00ce;
+pub const XK_Idiaeresis: Keysym = 0x00cf;
+pub const XK_ETH: Keysym = 0x00d0;
+pub const XK_Eth: Keysym = 0x00d0;
+pub const XK_Ntilde: Keysym = 0x00d1;
+pub const XK_Ograve: Keysym = 0x00d2;
+pub const XK_Oacute: Keysym = 0x00d3;
+pub const XK_Ocircumflex: Keysym = 0x00d4;
+pub const XK_Otilde: Keysym = 0x00d5;
+pub const XK_Odiaeresis: Keysym = 0x00d6;
+pub const XK_multiply: Keysym = 0x00d7;
+pub const XK_Oslash: Keysym = 0x00d8;
+pub const XK_Ooblique: Keysym = 0x00d8;
+pub const XK_Ugrave: Keysym = 0x00d9;
+pub const XK_Uacute: Keysym = 0x00da;
+pub const XK_Ucircumflex: Keysym = 0x00db;
+pub const XK_Udiaeresis: Keysym = 0x00dc;
+pub const XK_Yacute: Keysym = 0x00dd;
+pub const XK_THORN: Keysym = 0x00de;
+pub const XK_Thorn: Keysym = 0x00de;
+pub const XK_ssharp: Keysym = 0x00df;
+pub const XK_agrave: Keysym = 0x00e0;
+pub const XK_aacute: Keysym = 0x00e1;
+pub const XK_acircumflex: Keysym = 0x00e2;
+pub const XK_atilde: Keysym = 0x00e3;
+pub const XK_adiaeresis: Keysym = 0x00e4;
+pub const XK_aring: Keysym = 0x00e5;
+pub const XK_ae: Keysym = 0x00e6;
+pub const XK_ccedilla: Keysym = 0x00e7;
+pub const XK_egrave: Keysym = 0x00e8;
+pub const XK_eacute: Keysym = 0x00e9;
+pub const XK_ecircumflex: Keysym = 0x00ea;
+pub const XK_ediaeresis: Keysym = 0x00eb;
+pub const XK_igrave: Keysym = 0x00ec;
+pub const XK_iacute: Keysym = 0x00ed;
+pub const XK_icircumflex: Keysym = 0x00ee;
+pub const XK_idiaeresis: Keysym = 0x00ef;
+pub const XK_eth: Keysym = 0x00f0;
+pub const XK_ntilde: Keysym = 0x00f1;
+pub const XK_ograve: Keysym = 0x00f2;
+pub const XK_oacute: Keysym = 0x00f3;
+pub const XK_ocircumflex: Keysym = 0x00f4;
+pub const XK_otilde: Keysym = 0x00f5;
+pub const XK_odiaeresis: Keysym = 0x00f6;
+pub const XK_division: Keysym = 0x00f7;
+pub const XK_oslash: Keysym = 0x00f8;
+pub const XK_ooblique: Keysym = 0x00f8;
+pub const XK_ugrave: Keysym = 0x00f9;
+pub const XK_uacute: Keysym = 0x00fa;
+pub const XK_ucircumflex: Keysym = 0x00fb;
+pub const XK_udiaeresis: Keysym = 0x00fc;
+pub const XK_yacute: Keysym = 0x00fd;
+pub const XK_thorn: Keysym = 0x00fe;
+pub const XK_ydiaeresis: Keysym = 0x00ff;
+pub const XK_Aogonek: Keysym = 0x01a1;
+pub const XK_breve: Keysym = 0x01a2;
+pub const XK_Lstroke: Keysym = 0x01a3;
+pub const XK_Lcaron: Keysym = 0x01a5;
+pub const XK_Sacute: Keysym = 0x01a6;
+pub const XK_Scaron: Keysym = 0x01a9;
+pub const XK_Scedilla: Keysym = 0x01aa;
+pub const XK_Tcaron: Keysym = 0x01ab;
+pub const XK_Zacute: Keysym = 0x01ac;
+pub const XK_Zcaron: Keysym = 0x01ae;
+pub const XK_Zabovedot: Keysym = 0x01af;
+pub const XK_aogonek: Keysym = 0x01b1;
+pub const XK_ogonek: Keysym = 0x01b2;
+pub const XK_lstroke: Keysym = 0x01b3;
+pub const XK_lcaron: Keysym = 0x01b5;
+pub const XK_sacute: Keysym = 0x01b6;
+pub const XK_caron: Keysym = 0x01b7;
+pub const XK_scaron: Keysym = 0x01b9;
+pub const XK_scedilla: Keysym = 0x01ba;
+pub const XK_tcaron: Keysym = 0x01bb;
+pub const XK_zacute: Keysym = 0x01bc;
+pub const XK_doubleacute: Keysym = 0x01bd;
+pub const XK_zcaron: Keysym = 0x01be;
+pub const XK_zabovedot: Keysym = 0x01bf;
+pub const XK_Racute: Keysym = 0x01c0;
+pub const XK_Abreve: Keysym = 0x01c3;
+pub const XK_Lacute: Keysym = 0x01c5;
+pub const XK_Cacute: Keysym = 0x01c6;
+pub const XK_Ccaron: Keysym = 0x01c8;
+pub const XK_Eogonek: Keysym = 0x01ca;
+pub const XK_Ecaron: Keysym = 0x01cc;
+pub const XK_Dcaron: Keysym = 0x01cf;
+pub const XK_Dstroke: Keysym = 0x01d0;
+pub const XK_Nacute: Keysym = 0x01d1;
+pub const XK_Ncaron: Keysym = 0x01d2;
+pub const XK_Odoubleacute: Keysym = 0x01d5;
+pub const XK_Rcaron: Keysym = 0x01d8;
+pub const XK_Uring: Keysym = 0x01d9;
+pub const XK_Udoubleacute: Keysym = 0x01db;
+pub const XK_Tcedilla: Keysym = 0x01de;
+pub const XK_racute: Keysym = 0x01e0;
+pub const XK_abreve: Keysym = 0x01e3;
+pub const XK_lacute: Keysym = 0x01e5;
+pub const XK_cacute: Keysym = 0x01e6;
+pub const XK_ccaron: Keysym = 0x01e8;
+pub const XK_eogonek: Keysym = 0x01ea;
+pub const XK_ecaron: Keysym = 0x01ec;
+pub const XK_dcaron: Keysym = 0x01ef;
+pub const XK_dstroke: Keysym = 0x01f0;
+pub const XK_nacute: Keysym = 0x01f1;
+pub const XK_ncaron: Keysym = 0x01f2;
+pub const XK_odoubleacute: Keysym = 0x01f5;
+pub const XK_rcaron: Keysym = 0x01f8;
+pub const XK_uring: Keysym = 0x01f9;
+pub const XK_udoubleacute: Keysym = 0x01fb;
+pub const XK_tcedilla: Keysym = 0x01fe;
+pub const XK_abovedot: Keysym = 0x01ff;
+pub const XK_Hstroke: Keysym = 0x02a1;
+pub const XK_Hcircumflex: Keysym = 0x02a6;
+pub const XK_Iabovedot: Keysym = 0x02a9;
+pub const XK_Gbreve: Keysym = 0x02ab;
+pub const XK_Jcircumflex: Keysym = 0x02ac;
+pub const XK_hstroke: Keysym = 0x02b1;
+pub const XK_hcircumflex: Keysym = 0x02b6;
+pub const XK_idotless: Keysym = 0x02b9;
+pub const XK_gbreve: Keysym = 0x02bb;
+pub const XK_jcircumflex: Keysym = 0x02bc;
+pub const XK_Cabovedot: Keysym = 0x02c5;
+pub const XK_Ccircumflex: Keysym = 0x02c6;
+pub const XK_Gabovedot: Keysym = 0x02d5;
+pub const XK_Gcircumflex: Keysym = 0x02d8;
+pub const XK_Ubreve: Keysym = 0x02dd;
+pub const XK_Scircumflex: Keysym = 0x02de;
+pub const XK_cabovedot: Keysym = 0x02e5;
+pub const XK_ccircumflex: Keysym = 0x02e6;
+pub const XK_gabovedot: Keysym = 0x02f5;
+pub const XK_gcircumflex: Keysym = 0x02f8;
+pub const XK_ubreve: Keysym = 0x02fd;
+pub const XK_scircumflex: Keysym = 0x02fe;
+pub const XK_kra: Keysym = 0x03a2;
+pub const XK_kappa: Keysym = 0x03a2;
+pub const XK_Rcedilla: Keysym = 0x03a3;
+pub const XK_Itilde: Keysym = 0x03a5;
+pub const XK_Lcedilla: Keysym = 0x03a6;
+pub const XK_Emacron: Keysym = 0x03aa;
+pub const XK_Gcedilla: Keysym = 0x03ab;
+pub const XK_Tslash: Keysym = 0x03ac;
+pub const XK_rcedilla: Keysym = 0x03b3;
+pub const XK_itilde: Keysym = 0x03b5;
+pub const XK_lcedilla: Keysym = 0x03b6;
+pub const XK_emacron: Keysym = 0x03ba;
+pub const XK_gcedilla: Keysym = 0x03bb;
+pub const XK_tslash: Keysym = 0x03bc;
+pub const XK_ENG: Keysym = 0x03bd;
+pub const XK_eng: Keysym = 0x03bf;
+pub const XK_Amacron: Keysym = 0x03c0;
+pub const XK_Iogonek: Keysym = 0x03c7;
+pub const XK_Eabovedot: Keysym = 0x03cc;
+pub const XK_Imacron: Keysym = 0x03cf;
+pub const XK_Ncedilla: Keysym = 0x03d1;
+pub const XK_Omacron: Keysym = 0x03d2;
+pub const XK_Kcedilla: Keysym = 0x03d3;
+pub const XK_Uogonek: Keysym = 0x03d9;
+pub const XK_Utilde: Keysym = 0x03dd;
+pub const XK_Umacron: Keysym = 0x03de;
+pub const XK_amacron: Keysym = 0x03e0;
+pub const XK_iogonek: Keysym = 0x03e7;
+pub const XK_eabovedot: Keysym = 0x03ec;
+pub const XK_imacron: Keysym = 0x03ef;
+pub const XK_ncedilla: Keysym = 0x03f1;
+pub const XK_omacron: Keysym = 0x03f2;
+pub const XK_kcedilla: Keysym = 0x03f3;
+pub const XK_uogonek: Keysym = 0x03f9;
+pub const XK_utilde: Keysym = 0x03fd;
+pub const XK_umacron: Keysym = 0x03fe;
+pub const XK_Wcircumflex: Keysym = 0x1000174;
+pub const XK_wcircumflex: Keysym = 0x1000175;
+pub const XK_Ycircumflex: Keysym = 0x1000176;
+pub const XK_ycircumflex: Keysym = 0x1000177;
+pub const XK_Babovedot: Keysym = 0x1001e02;
+pub const XK_babovedot: Keysym = 0x1001e03;
+pub const XK_Dabovedot: Keysym = 0x1001e0a;
+pub const XK_dabovedot: Keysym = 0x1001e0b;
+pub const XK_Fabovedot: Keysym = 0x1001e1e;
+pub const XK_fabovedot: Keysym = 0x1001e1f;
+pub const XK_Mabovedot: Keysym = 0x1001e40;
+pub const XK_mabovedot: Keysym = 0x1001e41;
+pub const XK_Pabovedot: Keysym = 0x1001e56;
+pub const XK_pabovedot: Keysym = 0x1001e57;
+pub const XK_Sabovedot: Keysym = 0x1001e60;
+pub const XK_sabovedot: Keysym = 0x1001e61;
+pub const XK_Tabovedot: Keysym = 0x1001e6a;
+pub const XK_tabovedot: Keysym = 0x1001e6b;
+pub const XK_Wgrave: Keysym = 0x1001e80;
+pub const XK_wgrave: Keysym = 0x1001e81;
+pub const XK_Wacute: Keysym = 0x1001e82;
+pub const XK_wacute: Keysym = 0x1001e83;
+pub const XK_Wdiaeresis: Keysym = 0x1001e84;
+pub const XK_wdiaeresis: Keysym = 0x1001e85;
+pub const XK_Ygrave: Keysym = 0x1001ef2;
+pub const XK_ygrave: Keysym = 0x1001ef3;
+pub const XK_OE: Keysym = 0x13bc;
+pub const XK_oe: Keysym = 0x13bd;
+pub const XK_Ydiaeresis: Keysym = 0x13be;
+pub const XK_overline: Keysym = 0x047e;
+pub const XK_kana_fullstop: Keysym = 0x04a1;
+pub const XK_kana_openingbracket: Keysym = 0x04a2;
+pub const XK_kana_closingbracket: Keysym = 0x04a3;
+pub const XK_kana_comma: Keysym = 0x04a4;
+pub const XK_kana_conjunctive: Keysym = 0x04a5;
+pub const XK_kana_middledot: Keysym = 0x04a5;
+pub const XK_kana_WO: Keysym = 0x04a6;
+pub const XK_kana_a: Keysym = 0x04a7;
+pub const XK_kana_i: Keysym = 0x04a8;
+pub const XK_kana_u: Keysym = 0x04a9;
+pub const XK_kana_e: Keysym = 0x04aa;
+pub const XK_kana_o: Keysym = 0x04ab;
+pub const XK_kana_ya: Keysym = 0x04ac;
+pub const XK_kana_yu: Keysym = 0x04ad;
+pub const XK_kana_yo: Keysym = 0x04ae;
+pub const XK_kana_tsu: Keysym = 0x04af;
+pub const XK_kana_tu: Keysym = 0x04af;
+pub const XK_prolongedsound: Keysym = 0x04b0;
+pub const XK_kana_A: Keysym = 0x04b1;
+pub const XK_kana_I: Keysym = 0x04b2;
+pub const XK_kana_U: Keysym = 0x04b3;
+pub const XK_kana_E: Keysym = 0x04b4;
+pub const XK_kana_O: Keysym = 0x04b5;
+pub const XK_kana_KA: Keysym = 0x04b6;
+pub const XK_kana_KI: Keysym = 0x04b7;
+pub const XK_kana_KU: Keysym = 0x04b8;
+pub const XK_kana_KE: Keysym = 0x04b9;
+pub const XK_kana_KO: Keysym = 0x04ba;
+pub const XK_kana_SA: Keysym = 0x04bb;
+pub const XK_kana_SHI: Keysym = 0x04bc;
+pub const XK_kana_SU: Keysym = 0x04bd;
+pub const XK_kana_SE: Keysym = 0x04be;
+pub const XK_kana_SO: Keysym = 0x04bf;
+pub const XK_kana_TA: Keysym = 0x04c0;
+pub const XK_kana_CHI: Keysym = 0x04c1;
+pub const XK_kana_TI: Keysym = 0x04c1;
+pub const XK_kana_TSU: Keysym = 0x04c2;
+pub const XK_kana_TU: Keysym = 0x04c2;
+pub const XK_kana_TE: Keysym = 0x04c3;
+pub const XK_kana_TO: Keysym = 0x04c4;
+pub const XK_kana_NA: Keysym = 0x04c5;
+pub const XK_kana_NI: Keysym = 0x04c6;
+pub const XK_kana_NU: Keysym = 0x04c7;
+pub const XK_kana_NE: Keysym = 0x04c8;
+pub const XK_kana_NO: Keysym = 0x04c9;
+pub const XK_kana_HA: Keysym = 0x04ca;
+pub const XK_kana_HI: Keysym = 0x04cb;
+pub const XK_kana_FU: Keysym = 0x04cc;
+pub const XK_kana_HU: Keysym = 0x04cc;
+pub const XK_kana_HE: Keysym = 0x04cd;
+pub const XK_kana_HO: Keysym = 0x04ce;
+pub const XK_kana_MA: Keysym = 0x04cf;
+pub const XK_kana_MI: Keysym = 0x04d0;
+pub const XK_kana_MU: Keysym = 0x04d1;
+pub const XK_kana_ME: Keysym = 0x04d2;
+pub const XK_kana_MO: Keysym = 0x04d3;
+pub const XK_kana_YA: Keysym = 0x04d4;
+pub const XK_kana_YU: Keysym = 0x04d5;
+pub const XK_kana_YO: Keysym = 0x04d6;
+pub const XK_kana_RA: Keysym = 0x04d7;
+pub const XK_kana_RI: Keysym = 0x04d8;
+pub const XK_kana_RU: Keysym = 0x04d9;
+pub const XK_kana_RE: Keysym = 0x04da;
+pub const XK_kana_RO: Keysym = 0x04db;
+pub const XK_kana_WA: Keysym = 0x04dc;
+pub const XK_kana_N: Keysym = 0x04dd;
+pub const XK_voicedsound: Keysym = 0x04de;
+pub const XK_semivoicedsound: Keysym = 0x04df;
+pub const XK_kana_switch: Keysym = 0xff7e;
+pub const XK_Farsi_0: Keysym = 0x10006f0;
+pub const XK_Farsi_1: Keysym = 0x10006f1;
+pub const XK_Farsi_2: Keysym = 0x10006f2;
+pub const XK_Farsi_3: Keysym = 0x10006f3;
+pub const XK_Farsi_4: Keysym = 0x10006f4;
+pub const XK_Farsi_5: Keysym = 0x10006f5;
+pub const XK_Farsi_6: Keysym = 0x10006f6;
+pub const XK_Farsi_7: Keysym = 0x10006f7;
+pub const XK_Farsi_8: Keysym = 0x10006f8;
+pub const XK_Farsi_9: Keysym = 0x10006f9;
+pub const XK_Arabic_percent: Keysym = 0x100066a;
+pub const XK_Arabic_superscript_alef: Keysym = 0x1000670;
+pub const XK_Arabic_tteh: Keysym = 0x1000679;
+pub const XK_Arabic_peh: Keysym = 0x100067e;
+pub const XK_Arabic_tcheh: Keysym = 0x1000686;
+pub const XK_Arabic_ddal: Keysym = 0x1000688;
+pub const XK_Arabic_rreh: Keysym = 0x1000691;
+pub const XK_Arabic_comma: Keysym = 0x05ac;
+pub const XK_Arabic_fullstop: Keysym = 0x10006d4;
+pub const XK_Arabic_0: Keysym = 0x1000660;
+pub const XK_Arabic_1: Keysym = 0x1000661;
+pub const XK_Arabic_2: Keysym = 0x1000662;
+pub const XK_Arabic_3: Keysym = 0x1000663;
+pub const XK_Arabic_4: Keysym = 0x1000664;
+pub const XK_Arabic_5: Keysym = 0x1000665;
+pub const XK_Arabic_6: Keysym = 0x1000666;
+pub const XK_Arabic_7: Keysym = 0x1000667;
+pub const XK_Arabic_8: Keysym = 0x1000668;
+pub const XK_Arabic_9: Keysym = 0x1000669;
+pub const XK_Arabic_semicolon: Keysym = 0x05bb;
+pub const XK_Arabic_question_mark: Keysym = 0x05bf;
+pub const XK_Arabic_hamza: Keysym = 0x05c1;
+pub const XK_Arabic_maddaonalef: Keysym = 0x05c2;
+pub const XK_Arabic_hamzaonalef: Keysym = 0x05c3;
+pub const XK_Arabic_hamzaonwaw: Keysym = 0x05c4;
+pub const XK_Arabic_hamzaunderalef: Keysym = 0x05c5;
+pub const XK_Arabic_hamzaonyeh: Keysym = 0x05c6;
+pub const XK_Arabic_alef: Keysym = 0x05c7;
+pub const XK_Arabic_beh: Keysym = 0x05c8;
+pub const XK_Arabic_tehmarbuta: Keysym = 0x05c9;
+pub const XK_Arabic_teh: Keysym = 0x05ca;
+pub const XK_Arabic_theh: Keysym = 0x05cb;
+pub const XK_Arabic_jeem: Keysym = 0x05cc;
+pub const XK_Arabic_hah: Keysym = 0x05cd;
+pub const XK_Arabic_khah: Keysym = 0x05ce;
+pub const XK_Arabic_dal: Keysym = 0x05cf;
+pub const XK_Arabic_thal: Keysym = 0x05d0;
+pub const XK_Arabic_ra: Keysym = 0x05d1;
+pub const XK_Arabic_zain: Keysym = 0x05d2;
+pub const XK_Arabic_seen: Keysym = 0x05d3;
+pub const XK_Arabic_sheen: Keysym = 0x05d4;
+pub const XK_Arabic_sad: Keysym = 0x05d5;
+pub const XK_Arabic_dad: Keysym = 0x05d6;
+pub const XK_Arabic_tah: Keysym = 0x05d7;
+pub const XK_Arabic_zah: Keysym = 0x05d8;
+pub const XK_Arabic_ain: Keysym = 0x05d9;
+pub const XK_Arabic_ghain: Keysym = 0x05da;
+pub const XK_Arabic_tatweel: Keysym = 0x05e0;
+pub const XK_Arabic_feh: Keysym = 0x05e1;
+pub const XK_Arabic_qaf: Keysym = 0x05e2;
+pub const XK_Arabic_kaf: Keysym = 0x05e3;
+pub const XK_Arabic_lam: Keysym = 0x05e4;
+pub const XK_Arabic_meem: Keysym = 0x05e5;
+pub const XK_Arabic_noon: Keysym = 0x05e6;
+pub const XK_Arabic_ha: Keysym = 0x05e7;
+pub const XK_Arabic_heh: Keysym = 0x05e7;
+pub const XK_Arabic_waw: Keysym = 0x05e8;
+pub const XK_Arabic_alefmaksura: Keysym = 0x05e9;
+pub const XK_Arabic_yeh: Keysym = 0x05ea;
+pub const XK_Arabic_fathatan: Keysym = 0x05eb;
+pub const XK_Arabic_dammatan: Keysym = 0x05ec;
+pub const XK_Arabic_kasratan: Keysym = 0x05ed;
+pub const XK_Arabic_fatha: Keysym = 0x05ee;
+pub const XK_Arabic_damma: Keysym = 0x05ef;
+pub const XK_Arabic_kasra: Keysym = 0x05f0;
+pub const XK_Arabic_shadda: Keysym = 0x05f1;
+pub const XK_Arabic_sukun: Keysym = 0x05f2;
+pub const XK_Arabic_madda_above: Keysym = 0x1000653;
+pub const XK_Arabic_hamza_above: Keysym = 0x1000654;
+pub const XK_Arabic_hamza_below: Keysym = 0x1000655;
+pub const XK_Arabic_jeh: Keysym = 0x1000698;
+pub const XK_Arabic_veh: Keysym = 0x10006a4;
+pub const XK_Arabic_keheh: Keysym = 0x10006a9;
+pub const XK_Arabic_gaf: Keysym = 0x10006af;
+pub const XK_Arabic_noon_ghunna: Keysym = 0x10006ba;
+pub const XK_Arabic_heh_doachashmee: Keysym = 0x10006be;
+pub const XK_Farsi_yeh: Keysym = 0x10006cc;
+pub const XK_Arabic_farsi_yeh: Keysym = 0x10006cc;
+pub const XK_Arabic_yeh_baree: Keysym = 0x10006d2;
+pub const XK_Arabic_heh_goal: Keysym = 0x10006c1;
+pub const XK_Arabic_switch: Keysym = 0xff7e;
+pub const XK_Cyrillic_GHE_bar: Keysym = 0x1000492;
+pub const XK_Cyrillic_ghe_bar: Keysym = 0x1000493;
+pub const XK_Cyrillic_ZHE_descender: Keysym = 0x1000496;
+pub const XK_Cyrillic_zhe_descender: Keysym = 0x1000497;
+pub const XK_Cyrillic_KA_descender: Keysym = 0x100049a;
+pub const XK_Cyrillic_ka_descender: Keysym = 0x100049b;
+pub const XK_Cyrillic_KA_vertstroke: Keysym = 0x100049c;
+pub const XK_Cyrillic_ka_vertstroke: Keysym = 0x100049d;
+pub const XK_Cyrillic_EN_descender: Keysym = 0x10004a2;
+pub const XK_Cyrillic_en_descender: Keysym = 0x10004a3;
+pub const XK_Cyrillic_U_straight: Keysym = 0x10004ae;
+pub const XK_Cyrillic_u_straight: Keysym = 0x10004af;
+pub const XK_Cyrillic_U_straight_bar: Keysym = 0x10004b0;
+pub const XK_Cyrillic_u_straight_bar: Keysym = 0x10004b1;
+pub const XK_Cyrillic_HA_descender: Keysym = 0x10004b2;
+pub const XK_Cyrillic_ha_descender: Keysym = 0x10004b3;
+pub const XK_Cyrillic_CHE_descender: Keysym = 0x10004b6;
+pub const XK_Cyrillic_che_descender: Keysym = 0x10004b7;
+pub const XK_Cyrillic_CHE_vertstroke: Keysym = 0x10004b8;
+pub const XK_Cyrillic_che_vertstroke: Keysym = 0x10004b9;
+pub const XK_Cyrillic_SHHA: Keysym = 0x10004ba;
+pub const XK_Cyrillic_shha: Keysym = 0x10004bb;
+pub const XK_Cyrillic_SCHWA: Keysym = 0x10004d8;
+pub const XK_Cyrillic_schwa: Keysym = 0x10004d9;
+pub const XK_Cyrillic_I_macron: Keysym = 0x10004e2;
+pub const XK_Cyrillic_i_macron: Keysym = 0x10004e3;
+pub const XK_Cyrillic_O_bar: Keysym = 0x10004e8;
+pub const XK_Cyrillic_o_bar: Keysym = 0x10004e9;
+pub const XK_Cyrillic_U_macron: Keysym = 0x10004ee;
+pub const XK_Cyrillic_u_macron: Keysym = 0x10004ef;
+pub const XK_Serbian_dje: Keysym = 0x06a1;
+pub const XK_Macedonia_gje: Keysym = 0x06a2;
+pub const XK_Cyrillic_io: Keysym = 0x06a3;
+pub const XK_Ukrainian_ie: Keysym = 0x06a4;
+pub const XK_Ukranian_je: Keysym = 0x06a4;
+pub const XK_Macedonia_dse: Keysym = 0x06a5;
+pub const XK_Ukrainian_i: Keysym = 0x06a6;
+pub const XK_Ukranian_i: Keysym = 0x06a6;
+pub const XK_Ukrainian_yi: Keysym = 0x06a7;
+pub const XK_Ukranian_yi: Keysym = 0x06a7;
+pub const XK_Cyrillic_je: Keysym = 0x06a8;
+pub const XK_Serbian_je: Keysym = 0x06a8;
+pub const XK_Cyrillic_lje: Keysym = 0x06a9;
+pub const XK_Serbian_lje: Keysym = 0x06a9;
+pub const XK_Cyrillic_nje: Keysym = 0x06aa;
+pub const XK_Serbian_nje: Keysym = 0x06aa;
+pub const XK_Serbian_tshe: Keysym = 0x06ab;
+pub const XK_Macedonia_kje: Keysym = 0x06ac;
+pub const XK_Ukrainian_ghe_with_upturn: Keysym = 0x06ad;
+pub const XK_Byelorussian_shortu: Keysym = 0x06ae;
+pub const XK_Cyrillic_dzhe: Keysym = 0x06af;
+pub const XK_Serbian_dze: Keysym = 0x06af;
+pub const XK_numerosign: Keysym = 0x06b0;
+pub const XK_Serbian_DJE: Keysym = 0x06b1;
+pub const XK_Macedonia_GJE: Keysym = 0x06b2;
+pub const XK_Cyrillic_IO: Keysym = 0x06b3;
+pub const XK_Ukrainian_IE: Keysym = 0x06b4;
+pub const XK_Ukranian_JE: Keysym = 0x06b4;
+pub const XK_Macedonia_DSE: Keysym = 0x06b5;
+pub const XK_Ukrainian_I: Keysym = 0x06b6;
+pub const XK_Ukranian_I: Keysym = 0x06b6;
+pub const XK_Ukrainian_YI: Keysym = 0x06b7;
+pub const XK_Ukranian_YI: Keysym = 0x06b7;
+pub const XK_Cyrillic_JE: Keysym = 0x06b8;
+pub const XK_Serbian_JE: Keysym = 0x06b8;
+pub const XK_Cyrillic_LJE: Keysym = 0x06b9;
+pub const XK_Serbian_LJE: Keysym = 0x06b9;
+pub const XK_Cyrillic_NJE: Keysym = 0x06ba;
+pub const XK_Serbian_NJE: Keysym = 0x06ba;
+pub const XK_Serbian_TSHE: Keysym = 0x06bb;
+pub const XK_Macedonia_KJE: Keysym = 0x06bc;
+pub const XK_Ukrainian_GHE_WITH_UPTURN: Keysym = 0x06bd;
+pub const XK_Byelorussian_SHORTU: Keysym = 0x06be;
+pub const XK_Cyrillic_DZHE: Keysym = 0x06bf;
+pub const XK_Serbian_DZE: Keysym = 0x06bf;
+pub const XK_Cyrillic_yu: Keysym = 0x06c0;
+pub const XK_Cyrillic_a: Keysym = 0x06c1;
+pub const XK_Cyrillic_be: Keysym = 0x06c2;
+pub const XK_Cyrillic_tse: Keysym = 0x06c3;
+pub const XK_Cyrillic_de: Keysym = 0x06c4;
+pub const XK_Cyrillic_ie: Keysym = 0x06c5;
+pub const XK_Cyrillic_ef: Keysym = 0x06c6;
+pub const XK_Cyrillic_ghe: Keysym = 0x06c7;
+pub const XK_Cyrillic_ha: Keysym = 0x06c8;
+pub const XK_Cyrillic_i: Keysym = 0x06c9;
+pub const XK_Cyrillic_shorti: Keysym = 0x06ca;
+pub const XK_Cyrillic_ka: Keysym = 0x06cb;
+pub const XK_Cyrillic_el: Keysym = 0x06cc;
+pub const XK_Cyrillic_em: Keysym = 0x06cd;
+pub const XK_Cyrillic_en: Keysym = 0x06ce;
+pub const XK_Cyrillic_o: Keysym = 0x06cf;
+pub const XK_Cyrillic_pe: Keysym = 0x06d0;
+pub const XK_Cyrillic_ya: Keysym = 0x06d1;
+pub const XK_Cyrillic_er: Keysym = 0x06d2;
+pub const XK_Cyrillic_es: Keysym = 0x06d3;
+pub const XK_Cyrillic_te: Keysym = 0x06d4;
+pub const XK_Cyrillic_u: Keysym = 0x06d5;
+pub const XK_Cyrillic_zhe: Keysym = 0x06d6;
+pub const XK_Cyrillic_ve: Keysym = 0x06d7;
+pub const XK_Cyrillic_softsign: Keysym = 0x06d8;
+pub const XK_Cyrillic_yeru: Keysym = 0x06d9;
+pub const XK_Cyrillic_ze: Keysym = 0x06da;
+pub const XK_Cyrillic_sha: Keysym = 0x06db;
+pub const XK_Cyrillic_e: Keysym = 0x06dc;
+pub const XK_Cyrillic_shcha: Keysym = 0x06dd;
+pub const XK_Cyrillic_che: Keysym = 0x06de;
+pub const XK_Cyrillic_hardsign: Keysym = 0x06df;
+pub const XK_Cyrillic_YU: Keysym = 0x06e0;
+pub const XK_Cyrillic_A: Keysym = 0x06e1;
+pub const XK_Cyrillic_BE: Keysym = 0x06e2;
+pub const XK_Cyrillic_TSE: Keysym = 0x06e3;
+pub const XK_Cyrillic_DE: Keysym = 0x06e4;
+pub const XK_Cyrillic_IE: Keysym = 0x06e5;
+pub const XK_Cyrillic_EF: Keysym = 0x06e6;
+pub const XK_Cyrillic_GHE: Keysym = 0x06e7;
+pub const XK_Cyrillic_HA: Keysym = 0x06e8;
+pub const XK_Cyrillic_I: Keysym = 0x06e9;
+pub const XK_Cyrillic_SHORTI: Keysym = 0x06ea;
+pub const XK_Cyrillic_KA: Keysym = 0x06eb;
+pub const XK_Cyrillic_EL: Keysym = 0x06ec;
+pub const XK_Cyrillic_EM: Keysym = 0x06ed;
+pub const XK_Cyrillic_EN: Keysym = 0x06ee;
+pub const XK_Cyrillic_O: Keysym = 0x06ef;
+pub const XK_Cyrillic_PE: Keysym = 0x06f0;
+pub const XK_Cyrillic_YA: Keysym = 0x06f1;
+pub const XK_Cyrillic_ER: Keysym = 0x06f2;
+pub const XK_Cyrillic_ES: Keysym = 0x06f3;
+pub const XK_Cyrillic_TE: Keysym = 0x06f4;
+pub const XK_Cyrillic_U: Keysym = 0x06f5;
+pub const XK_Cyrillic_ZHE: Keysym = 0x06f6;
+pub const XK_Cyrillic_VE: Keysym = 0x06f7;
+pub const XK_Cyrillic_SOFTSIGN: Keysym = 0x06f8;
+pub const XK_Cyrillic_YERU: Keysym = 0x06f9;
+pub const XK_Cyrillic_ZE: Keysym = 0x06fa;
+pub const XK_Cyrillic_SHA: Keysym = 0x06fb;
+pub const XK_Cyrillic_E: Keysym = 0x06fc;
+pub const XK_Cyrillic_SHCHA: Keysym = 0x06fd;
+pub const XK_Cyrillic_CHE: Keysym = 0x06fe;
+pub const XK_Cyrillic_HARDSIGN: Keysym = 0x06ff;
+pub const XK_Greek_ALPHAaccent: Keysym = 0x07a1;
+pub const XK_Greek_EPSILONaccent: Keysym = 0x07a2;
+pub const XK_Greek_ETAaccent: Keysym = 0x07a3;
+pub const XK_Greek_IOTAaccent: Keysym = 0x07a4;
+pub const XK_Greek_IOTAdieresis: Keysym = 0x07a5;
+pub const XK_Greek_IOTAdiaeresis: Keysym = 0x07a5;
+pub const XK_Greek_OMICRONaccent: Keysym = 0x07a7;
+pub const XK_Greek_UPSILONaccent: Keysym = 0x07a8;
+pub const XK_Greek_UPSILONdieresis: Keysym = 0x07a9;
+pub const XK_Greek_OMEGAaccent: Keysym = 0x07ab;
+pub const XK_Greek_accentdieresis: Keysym = 0x07ae;
+pub const XK_Greek_horizbar: Keysym = 0x07af;
+pub const XK_Greek_alphaaccent: Keysym = 0x07b1;
+pub const XK_Greek_epsilonaccent: Keysym = 0x07b2;
+pub const XK_Greek_etaaccent: Keysym = 0x07b3;
+pub const XK_Greek_iotaaccent: Keysym = 0x07b4;
+pub const XK_Greek_iotadieresis: Keysym = 0x07b5;
+pub const XK_Greek_iotaaccentdieresis: Keysym = 0x07b6;
+pub const XK_Greek_omicronaccent: Keysym = 0x07b7;
+pub const XK_Greek_upsilonaccent: Keysym = 0x07b8;
+pub const XK_Greek_upsilondieresis: Keysym = 0x07b9;
+pub const XK_Greek_upsilonaccentdieresis: Keysym = 0x07ba;
+pub const XK_Greek_omegaaccent: Keysym = 0x07bb;
+pub const XK_Greek_ALPHA: Keysym = 0x07c1;
+pub const XK_Greek_BETA: Keysym = 0x07c2;
+pub const XK_Greek_GAMMA: Keysym = 0x07c3;
+pub const XK_Greek_DELTA: Keysym = 0x07c4;
+pub const XK_Greek_EPSILON: Keysym = 0x07c5;
+pub const XK_Greek_ZETA: Keysym = 0x07c6;
+pub const XK_Greek_ETA: Keysym = 0x07c7;
+pub const XK_Greek_THETA: Keysym = 0x07c8;
+pub const XK_Greek_IOTA: Keysym = 0x07c9;
+pub const XK_Greek_KAPPA: Keysym = 0x07ca;
+pub const XK_Greek_LAMDA: Keysym = 0x07cb;
+pub const XK_Greek_LAMBDA: Keysym = 0x07cb;
+pub const XK_Greek_MU: Keysym = 0x07cc;
+pub const XK_Greek_NU: Keysym = 0x07cd;
+pub const XK_Greek_XI: Keysym = 0x07ce;
+pub const XK_Greek_OMICRON: Keysym = 0x07cf;
+pub const XK_Greek_PI: Keysym = 0x07d0;
+pub const XK_Greek_RHO: Keysym = 0x07d1;
+pub const XK_Greek_SIGMA: Keysym = 0x07d2;
+pub const XK_Greek_TAU: Keysym = 0x07d4;
+pub const XK_Greek_UPSILON: Keysym = 0x07d5;
+pub const XK_Greek_PHI: Keysym = 0x07d6;
+pub const XK_Greek_CHI: Keysym = 0x07d7;
+pub const XK_Greek_PSI: Keysym = 0x07d8;
+pub const XK_Greek_OMEGA: Keysym = 0x07d9;
+pub const XK_Greek_alpha: Keysym = 0x07e1;
+pub const XK_Greek_beta: Keysym = 0x07e2;
+pub const XK_Greek_gamma: Keysym = 0x07e3;
+pub const XK_Greek_delta: Keysym = 0x07e4;
+pub const XK_Greek_epsilon: Keysym = 0x07e5;
+pub const XK_Greek_zeta: Keysym = 0x07e6;
+pub const XK_Greek_eta: Keysym = 0x07e7;
+pub const XK_Greek_theta: Keysym = 0x07e8;
+pub const XK_Greek_iota: Keysym = 0x07e9;
+pub const XK_Greek_kappa: Keysym = 0x07ea;
+pub const XK_Greek_lamda: Keysym = 0x07eb;
+pub const XK_Greek_lambda: Keysym = 0x07eb;
+pub const XK_Greek_mu: Keysym = 0x07ec;
+pub const XK_Greek_nu: Keysym = 0x07ed;
+pub const XK_Greek_xi: Keysym = 0x07ee;
+pub const XK_Greek_omicron: Keysym = 0x07ef;
+pub const XK_Greek_pi: Keysym = 0x07f0;
+pub const XK_Greek_rho: Keysym = 0x07f1;
+pub const XK_Greek_sigma: Keysym = 0x07f2;
+pub const XK_Greek_finalsmallsigma: Keysym = 0x07f3;
+pub const XK_Greek_tau: Keysym = 0x07f4;
+pub const XK_Greek_upsilon: Keysym = 0x07f5;
+pub const XK_Greek_phi: Keysym = 0x07f6;
+pub const XK_Greek_chi: Keysym = 0x07f7;
+pub const XK_Greek_psi: Keysym = 0x07f8;
+pub const XK_Greek_omega: Keysym = 0x07f9;
+pub const XK_Greek_switch: Keysym = 0xff7e;
+pub const XK_leftradical: Keysym = 0x08a1;
+pub const XK_topleftradical: Keysym = 0x08a2;
+pub const XK_horizconnector: Keysym = 0x08a3;
+pub const XK_topintegral: Keysym = 0x08a4;
+pub const XK_botintegral: Keysym = 0x08a5;
+pub const XK_vertconnector: Keysym = 0x08a6;
+pub const XK_topleftsqbracket: Keysym = 0x08a7;
+pub const XK_botleftsqbracket: Keysym = 0x08a8;
+pub const XK_toprightsqbracket: Keysym = 0x08a9;
+pub const XK_botrightsqbracket: Keysym = 0x08aa;
+pub const XK_topleftparens: Keysym = 0x08ab;
+pub const XK_botleftparens: Keysym = 0x08ac;
+pub const XK_toprightparens: Keysym = 0x08ad;
+pub const XK_botrightparens: Keysym = 0x08ae;
+pub const XK_leftmiddlecurlybrace: Keysym = 0x08af;
+pub const XK_rightmiddlecurlybrace: Keysym = 0x08b0;
+pub const XK_topleftsummation: Keysym = 0x08b1;
+pub const XK_botleftsummation: Keysym = 0x08b2;
+pub const XK_topvertsummationconnector: Keysym = 0x08b3;
+pub const XK_botvertsummationconnector: Keysym = 0x08b4;
+pub const XK_toprightsummation: Keysym = 0x08b5;
+pub const XK_botrightsummation: Keysym = 0x08b6;
+pub const XK_rightmiddlesummation: Keysym = 0x08b7;
+pub const XK_lessthanequal: Keysym = 0x08bc;
+pub const XK_notequal: Keysym = 0x08bd;
+pub const XK_greaterthanequal: Keysym = 0x08be;
+pub const XK_integral: Keysym = 0x08bf;
+pub const XK_therefore: Keysym = 0x08c0;
+pub const XK_variation: Keysym = 0x08c1;
+pub const XK_infinity: Keysym = 0x08c2;
+pub const XK_nabla: Keysym = 0x08c5;
+pub const XK_approximate: Keysym = 0x08c8;
+pub const XK_similarequal: Keysym = 0x08c9;
+pub const XK_ifonlyif: Keysym = 0x08cd;
+pub const XK_implies: Keysym = 0x08ce;
+pub const XK_identical: Keysym = 0x08cf;
+pub const XK_radical: Keysym = 0x08d6;
+pub const XK_includedin: Keysym = 0x08da;
+pub const XK_includes: Keysym = 0x08db;
+pub const XK_intersection: Keysym = 0x08dc;
+pub const XK_union: Keysym = 0x08dd;
+pub const XK_logicaland: Keysym = 0x08de;
+pub const XK_logicalor: Keysym = 0x08df;
+pub const XK_partialderivative: Keysym = 0x08ef;
+pub const XK_function: Keysym = 0x08f6;
+pub const XK_leftarrow: Keysym = 0x08fb;
+pub const XK_uparrow: Keysym = 0x08fc;
+pub const XK_rightarrow: Keysym = 0x08fd;
+pub const XK_downarrow: Keysym = 0x08fe;
+pub const XK_blank: Keysym = 0x09df;
+pub const XK_soliddiamond: Keysym = 0x09e0;
+pub const XK_checkerboard: Keysym = 0x09e1;
+pub const XK_ht: Keysym = 0x09e2;
+pub const XK_ff: Keysym = 0x09e3;
+pub const XK_cr: Keysym = 0x09e4;
+pub const XK_lf: Keysym = 0x09e5;
+pub const XK_nl: Keysym = 0x09e8;
+pub const XK_vt: Keysym = 0x09e9;
+pub const XK_lowrightcorner: Keysym = 0x09ea;
+pub const XK_uprightcorner: Keysym = 0x09eb;
+pub const XK_upleftcorner: Keysym = 0x09ec;
+pub const XK_lowleftcorner: Keysym = 0x09ed;
+pub const XK_crossinglines: Keysym = 0x09ee;
+pub const XK_horizlinescan1: Keysym = 0x09ef;
+pub const XK_horizlinescan3: Keysym = 0x09f0;
+pub const XK_horizlinescan5: Keysym = 0x09f1;
+pub const XK_horizlinescan7: Keysym = 0x09f2;
+pub const XK_horizlinescan9: Keysym = 0x09f3;
+pub const XK_leftt: Keysym = 0x09f4;
+pub const XK_rightt: Keysym = 0x09f5;
+pub const XK_bott: Keysym = 0x09f6;
+pub const XK_topt: Keysym = 0x09f7;
+pub const XK_vertbar: Keysym = 0x09f8;
+pub const XK_emspace: Keysym = 0x0aa1;
+pub const XK_enspace: Keysym = 0x0aa2;
+pub const XK_em3space: Keysym = 0x0aa3;
+pub const XK_em4space: Keysym = 0x0aa4;
+pub const XK_digitspace: Keysym = 0x0aa5;
+pub const XK_punctspace: Keysym = 0x0aa6;
+pub const XK_thinspace: Keysym = 0x0aa7;
+pub const XK_hairspace: Keysym = 0x0aa8;
+pub const XK_emdash: Keysym = 0x0aa9;
+pub const XK_endash: Keysym = 0x0aaa;
+pub const XK_signifblank: Keysym = 0x0aac;
+pub const XK_ellipsis: Keysym = 0x0aae;
+pub const XK_doubbaselinedot: Keysym = 0x0aaf;
+pub const XK_onethird: Keysym = 0x0ab0;
+pub const XK_twothirds: Keysym = 0x0ab1;
+pub const XK_onefifth: Keysym = 0x0ab2;
+pub const XK_twofifths: Keysym = 0x0ab3;
+pub const XK_threefifths: Keysym = 0x0ab4;
+pub const XK_fourfifths: Keysym = 0x0ab5;
+pub const XK_onesixth: Keysym = 0x0ab6;
+pub const XK_fivesixths: Keysym = 0x0ab7;
+pub const XK_careof: Keysym = 0x0ab8;
+pub const XK_figdash: Keysym = 0x0abb;
+pub const XK_leftanglebracket: Keysym = 0x0abc;
+pub const XK_decimalpoint: Keysym = 0x0abd;
+pub const XK_rightanglebracket: Keysym = 0x0abe;
+pub const XK_marker: Keysym = 0x0abf;
+pub const XK_oneeighth: Keysym = 0x0ac3;
+pub const XK_threeeighths: Keysym = 0x0ac4;
+pub const XK_fiveeighths: Keysym = 0x0ac5;
+pub const XK_seveneighths: Keysym = 0x0ac6;
+pub const XK_trademark: Keysym = 0x0ac9;
+pub const XK_signaturemark: Keysym = 0x0aca;
+pub const XK_trademarkincircle: Keysym = 0x0acb;
+pub const XK_leftopentriangle: Keysym = 0x0acc;
+pub const XK_rightopentriangle: Keysym = 0x0acd;
+pub const XK_emopencircle: Keysym = 0x0ace;
+pub const XK_emopenrectangle: Keysym = 0x0acf;
+pub const XK_leftsinglequotemark: Keysym = 0x0ad0;
+pub const XK_rightsinglequotemark: Keysym = 0x0ad1;
+pub const XK_leftdoublequotemark: Keysym = 0x0ad2;
+pub const XK_rightdoublequotemark: Keysym = 0x0ad3;
+pub const XK_prescription: Keysym = 0x0ad4;
+pub const XK_permille: Keysym = 0x0ad5;
+pub const XK_minutes: Keysym = 0x0ad6;
+pub const XK_seconds: Keysym = 0x0ad7;
+pub const XK_latincross: Keysym = 0x0ad9;
+pub const XK_hexagram: Keysym = 0x0ada;
+pub const XK_filledrectbullet: Keysym = 0x0adb;
+pub const XK_filledlefttribullet: Keysym = 0x0adc;
+pub const XK_filledrighttribullet: Keysym = 0x0add;
+pub const XK_emfilledcircle: Keysym = 0x0ade;
+pub const XK_emfilledrect: Keysym = 0x0adf;
+pub const XK_enopencircbullet: Keysym = 0x0ae0;
+pub const XK_enopensquarebullet: Keysym = 0x0ae1;
+pub const XK_openrectbullet: Keysym = 0x0ae2;
+pub const XK_opentribulletup: Keysym = 0x0ae3;
+pub const XK_opentribulletdown: Keysym = 0x0ae4;
+pub const XK_openstar: Keysym = 0x0ae5;
+pub const XK_enfilledcircbullet: Keysym = 0x0ae6;
+pub const XK_enfilledsqbullet: Keysym = 0x0ae7;
+pub const XK_filledtribulletup: Keysym = 0x0ae8;
+pub const XK_filledtribulletdown: Keysym = 0x0ae9;
+pub const XK_leftpointer: Keysym = 0x0aea;
+pub const XK_rightpointer: Keysym = 0x0aeb;
+pub const XK_club: Keysym = 0x0aec;
+pub const XK_diamond: Keysym = 0x0aed;
+pub const XK_heart: Keysym = 0x0aee;
+pub const XK_maltesecross: Keysym = 0x0af0;
+pub const XK_dagger: Keysym = 0x0af1;
+pub const XK_doubledagger: Keysym = 0x0af2;
+pub const XK_checkmark: Keysym = 0x0af3;
+pub const XK_ballotcross: Keysym = 0x0af4;
+pub const XK_musicalsharp: Keysym = 0x0af5;
+pub const XK_musicalflat: Keysym = 0x0af6;
+pub const XK_malesymbol: Keysym = 0x0af7;
+pub const XK_femalesymbol: Keysym = 0x0af8;
+pub const XK_telephone: Keysym = 0x0af9;
+pub const XK_telephonerecorder: Keysym = 0x0afa;
+pub const XK_phonographcopyright: Keysym = 0x0afb;
+pub const XK_caret: Keysym = 0x0afc;
+pub const XK_singlelowquotemark: Keysym = 0x0afd;
+pub const XK_doublelowquotemark: Keysym = 0x0afe;
+pub const XK_cursor: Keysym = 0x0aff;
+pub const XK_leftcaret: Keysym = 0x0ba3;
+pub const XK_rightcaret: Keysym = 0x0ba6;
+pub const XK_downcaret: Keysym = 0x0ba8;
+pub const XK_upcaret: Keysym = 0x0ba9;
+pub const XK_overbar: Keysym = 0x0bc0;
+pub const XK_downtack: Keysym = 0x0bc2;
+pub const XK_upshoe: Keysym = 0x0bc3;
+pub const XK_downstile: Keysym = 0x0bc4;
+pub const XK_underbar: Keysym = 0x0bc6;
+pub const XK_jot: Keysym = 0x0bca;
+pub const XK_quad: Keysym = 0x0bcc;
+pub const XK_uptack: Keysym = 0x0bce;
+pub const XK_circle: Keysym = 0x0bcf;
+pub const XK_upstile: Keysym = 0x0bd3;
+pub const XK_downshoe: Keysym = 0x0bd6;
+pub const XK_rightshoe: Keysym = 0x0bd8;
+pub const XK_leftshoe: Keysym = 0x0bda;
+pub const XK_lefttack: Keysym = 0x0bdc;
+pub const XK_righttack: Keysym = 0x0bfc;
+pub const XK_hebrew_doublelowline: Keysym = 0x0cdf;
+pub const XK_hebrew_aleph: Keysym = 0x0ce0;
+pub const XK_hebrew_bet: Keysym = 0x0ce1;
+pub const XK_hebrew_beth: Keysym = 0x0ce1;
+pub const XK_hebrew_gimel: Keysym = 0x0ce2;
+pub const XK_hebrew_gimmel: Keysym = 0x0ce2;
+pub const XK_hebrew_dalet: Keysym = 0x0ce3;
+pub const XK_hebrew_daleth: Keysym = 0x0ce3;
+pub const XK_hebrew_he: Keysym = 0x0ce4;
+pub const XK_hebrew_waw: Keysym = 0x0ce5;
+pub const XK_hebrew_zain: Keysym = 0x0ce6;
+pub const XK_hebrew_zayin: Keysym = 0x0ce6;
+pub const XK_hebrew_chet: Keysym = 0x0ce7;
+pub const XK_hebrew_het: Keysym = 0x0ce7;
+pub const XK_hebrew_tet: Keysym = 0x0ce8;
+pub const XK_hebrew_teth: Keysym = 0x0ce8;
+pub const XK_hebrew_yod: Keysym = 0x0ce9;
+pub const XK_hebrew_finalkaph: Keysym = 0x0cea;
+pub const XK_hebrew_kaph: Keysym = 0x0ceb;
+pub const XK_hebrew_lamed: Keysym = 0x0cec;
+pub const XK_hebrew_finalmem: Keysym = 0x0ced;
+pub const XK_hebrew_mem: Keysym = 0x0cee;
+pub const XK_hebrew_finalnun: Keysym = 0x0cef;
+pub const XK_hebrew_nun: Keysym = 0x0cf0;
+pub const XK_hebrew_samech: Keysym = 0x0cf1;
+pub const XK_hebrew_samekh: Keysym = 0x0cf1;
+pub const XK_hebrew_ayin: Keysym = 0x0cf2;
+pub const XK_hebrew_finalpe: Keysym = 0x0cf3;
+pub const XK_hebrew_pe: Keysym = 0x0cf4;
+pub const XK_hebrew_finalzade: Keysym = 0x0cf5;
+pub const XK_hebrew_finalzadi: Keysym = 0x0cf5;
+pub const XK_hebrew_zade: Keysym = 0x0cf6;
+pub const XK_hebrew_zadi: Keysym = 0x0cf6;
+pub const XK_hebrew_qoph: Keysym = 0x0cf7;
+pub const XK_hebrew_kuf: Keysym = 0x0cf7;
+pub const XK_hebrew_resh: Keysym = 0x0cf8;
+pub const XK_hebrew_shin: Keysym = 0x0cf9;
+pub const XK_hebrew_taw: Keysym = 0x0cfa;
+pub const XK_hebrew_taf: Keysym = 0x0cfa;
+pub const XK_Hebrew_switch: Keysym = 0xff7e;
+pub const XK_Thai_kokai: Keysym = 0x0da1;
+pub const XK_Thai_khokhai: Keysym = 0x0da2;
+pub const XK_Thai_khokhuat: Keysym = 0x0da3;
+pub const XK_Thai_khokhwai: Keysym = 0x0da4;
+pub const XK_Thai_khokhon: Keysym = 0x0da5;
+pub const XK_Thai_khorakhang: Keysym = 0x0da6;
+pub const XK_Thai_ngongu: Keysym = 0x0da7;
+pub const XK_Thai_chochan: Keysym = 0x0da8;
+pub const XK_Thai_choching: Keysym = 0x0da9;
+pub const XK_Thai_chochang: Keysym = 0x0daa;
+pub const XK_Thai_soso: Keysym = 0x0dab;
+pub const XK_Thai_chochoe: Keysym = 0x0dac;
+pub const XK_Thai_yoying: Keysym = 0x0dad;
+pub const XK_Thai_dochada: Keysym = 0x0dae;
+pub const XK_Thai_topatak: Keysym = 0x0daf;
+pub const XK_Thai_thothan: Keysym = 0x0db0;
+pub const XK_Thai_thonangmontho: Keysym = 0x0db1;
+pub const XK_Thai_thophuthao: Keysym = 0x0db2;
+pub const XK_Thai_nonen: Keysym = 0x0db3;
+pub const XK_Thai_dodek: Keysym = 0x0db4;
+pub const XK_Thai_totao: Keysym = 0x0db5;
+pub const XK_Thai_thothung: Keysym = 0x0db6;
+pub const XK_Thai_thothahan: Keysym = 0x0db7;
+pub const XK_Thai_thothong: Keysym = 0x0db8;
+pub const XK_Thai_nonu: Keysym = 0x0db9;
+pub const XK_Thai_bobaimai: Keysym = 0x0dba;
+pub const XK_Thai_popla: Keysym = 0x0dbb;
+pub const XK_Thai_phophung: Keysym = 0x0dbc;
+pub const XK_Thai_fofa: Keysym = 0x0dbd;
+pub const XK_Thai_phophan: Keysym = 0x0dbe;
+pub const XK_Thai_fofan: Keysym = 0x0dbf;
+pub const XK_Thai_phosamphao: Keysym = 0x0dc0;
+pub const XK_Thai_moma: Keysym = 0x0dc1;
+pub const XK_Thai_yoyak: Keysym = 0x0dc2;
+pub const XK_Thai_rorua: Keysym = 0x0dc3;
+pub const XK_Thai_ru: Keysym = 0x0dc4;
+pub const XK_Thai_loling: Keysym = 0x0dc5;
+pub const XK_Thai_lu: Keysym = 0x0dc6;
+pub const XK_Thai_wowaen: Keysym = 0x0dc7;
+pub const XK_Thai_sosala: Keysym = 0x0dc8;
+pub const XK_Thai_sorusi: Keysym = 0x0dc9;
+pub const XK_Thai_sosua: Keysym = 0x0dca;
+pub const XK_Thai_hohip: Keysym = 0x0dcb;
+pub const XK_Thai_lochula: Keysym = 0x0dcc;
+pub const XK_Thai_oang: Keysym = 0x0dcd;
+pub const XK_Thai_honokhuk: Keysym = 0x0dce;
+pub const XK_Thai_paiyannoi: Keysym = 0x0dcf;
+pub const XK_Thai_saraa: Keysym = 0x0dd0;
+pub const XK_Thai_maihanakat: Keysym = 0x0dd1;
+pub const XK_Thai_saraaa: Keysym = 0x0dd2;
+pub const XK_Thai_saraam: Keysym = 0x0dd3;
+pub const XK_Thai_sarai: Keysym = 0x0dd4;
+pub const XK_Thai_saraii: Keysym = 0x0dd5;
+pub const XK_Thai_saraue: Keysym = 0x0dd6;
+pub const XK_Thai_sarauee: Keysym = 0x0dd7;
+pub const XK_Thai_sarau: Keysym = 0x0dd8;
+pub const XK_Thai_sarauu: Keysym = 0x0dd9;
+pub const XK_Thai_phinthu: Keysym = 0x0dda;
+pub const XK_Thai_maihanakat_maitho: Keysym = 0x0dde;
+pub const XK_Thai_baht: Keysym = 0x0ddf;
+pub const XK_Thai_sarae: Keysym = 0x0de0;
+pub const XK_Thai_saraae: Keysym = 0x0de1;
+pub const XK_Thai_sarao: Keysym = 0x0de2;
+pub const XK_Thai_saraaimaimuan: Keysym = 0x0de3;
+pub const XK_Thai_saraaimaimalai: Keysym = 0x0de4;
+pub const XK_Thai_lakkhangyao: Keysym = 0x0de5;
+pub const XK_Thai_maiyamok: Keysym = 0x0de6;
+pub const XK_Thai_maitaikhu: Keysym = 0x0de7;
+pub const XK_Thai_maiek: Keysym = 0x0de8;
+pub const XK_Thai_maitho: Keysym = 0x0de9;
+pub const XK_Thai_maitri: Keysym = 0x0dea;
+pub const XK_Thai_maichattawa: Keysym = 0x0deb;
+pub const XK_Thai_thanthakhat: Keysym = 0x0dec;
+pub const XK_Thai_nikhahit: Keysym = 0x0ded;
+pub const XK_Thai_leksun: Keysym = 0x0df0;
+pub const XK_Thai_leknung: Keysym = 0x0df1;
+pub const XK_Thai_leksong: Keysym = 0x0df2;
+pub const XK_Thai_leksam: Keysym = 0x0df3;
+pub const XK_Thai_leksi: Keysym = 0x0df4;
+pub const XK_Thai_lekha: Keysym = 0x0df5;
+pub const XK_Thai_lekhok: Keysym = 0x0df6;
+pub const XK_Thai_lekchet: Keysym = 0x0df7;
+pub const XK_Thai_lekpaet: Keysym = 0x0df8;
+pub const XK_Thai_lekkao: Keysym = 0x0df9;
+pub const XK_Hangul: Keysym = 0xff31;
+pub const XK_Hangul_Start: Keysym = 0xff32;
+pub const XK_Hangul_End: Keysym = 0xff33;
+pub const XK_Hangul_Hanja: Keysym = 0xff34;
+pub const XK_Hangul_Jamo: Keysym = 0xff35;
+pub const XK_Hangul_Romaja: Keysym = 0xff36;
+pub const XK_Hangul_Codeinput: Keysym = 0xff37;
+pub const XK_Hangul_Jeonja: Keysym = 0xff38;
+pub const XK_Hangul_Banja: Keysym = 0xff39;
+pub const XK_Hangul_PreHanja: Keysym = 0xff3a;
+pub const XK_Hangul_PostHanja: Keysym = 0xff3b;
+pub const XK_Hangul_SingleCandidate: Keysym = 0xff3c;
+pub const XK_Hangul_MultipleCandidate: Keysym = 0xff3d;
+pub const XK_Hangul_PreviousCandidate: Keysym = 0xff3e;
+pub const XK_Hangul_Special: Keysym = 0xff3f;
+pub const XK_Hangul_switch: Keysym = 0xff7e;
+pub const XK_Hangul_Kiyeog: Keysym = 0x0ea1;
+pub const XK_Hangul_SsangKiyeog: Keysym = 0x0ea2;
+pub const XK_Hangul_KiyeogSios: Keysym = 0x0ea3;
+pub const XK_Hangul_Nieun: Keysym = 0x0ea4;
+pub const XK_Hangul_NieunJieuj: Keysym = 0x0ea5;
+pub const XK_Hangul_NieunHieuh: Keysym = 0x0ea6;
+pub const XK_Hangul_Dikeud: Keysym = 0x0ea7;
+pub const XK_Hangul_SsangDikeud: Keysym = 0x0ea8;
+pub const XK_Hangul_Rieul: Keysym = 0x0ea9;
+pub const XK_Hangul_RieulKiyeog: Keysym = 0x0eaa;
+pub const XK_Hangul_RieulMieum: Keysym = 0x0eab;
+pub const XK_Hangul_RieulPieub: Keysym = 0x0eac;
+pub const XK_Hangul_RieulSios: Keysym = 0x0ead;
+pub const XK_Hangul_RieulTieut: Keysym = 0x0eae;
+pub const XK_Hangul_RieulPhieuf: Keysym = 0x0eaf;
+pub const XK_Hangul_RieulHieuh: Keysym = 0x0eb0;
+pub const XK_Hangul_Mieum: Keysym = 0x0eb1;
+pub const XK_Hangul_Pieub: Keysym = 0x0eb2;
+pub const XK_Hangul_SsangPieub: Keysym = 0x0eb3;
+pub const XK_Hangul_PieubSios: Keysym = 0x0eb4;
+pub const XK_Hangul_Sios: Keysym = 0x0eb5;
+pub const XK_Hangul_SsangSios: Keysym = 0x0eb6;
+pub const XK_Hangul_Ieung: Keysym = 0x0eb7;
+pub const XK_Hangul_Jieuj: Keysym = 0x0eb8;
+pub const XK_Hangul_SsangJieuj: Keysym = 0x0eb9;
+pub const XK_Hangul_Cieuc: Keysym = 0x0eba;
+pub const XK_Hangul_Khieuq: Keysym = 0x0ebb;
+pub const XK_Hangul_Tieut: Keysym = 0x0ebc;
+pub const XK_Hangul_Phieuf: Keysym = 0x0ebd;
+pub const XK_Hangul_Hieuh: Keysym = 0x0ebe;
+pub const XK_Hangul_A: Keysym = 0x0ebf;
+pub const XK_Hangul_AE: Keysym = 0x0ec0;
+pub const XK_Hangul_YA: Keysym = 0x0ec1;
+pub const XK_Hangul_YAE: Keysym = 0x0ec2;
+pub const XK_Hangul_EO: Keysym = 0x0ec3;
+pub const XK_Hangul_E: Keysym = 0x0ec4;
+pub const XK_Hangul_YEO: Keysym = 0x0ec5;
+pub const XK_Hangul_YE: Keysym = 0x0ec6;
+pub const XK_Hangul_O: Keysym = 0x0ec7;
+pub const XK_Hangul_WA: Keysym = 0x0ec8;
+pub const XK_Hangul_WAE: Keysym = 0x0ec9;
+pub const XK_Hangul_OE: Keysym = 0x0eca;
+pub const XK_Hangul_YO: Keysym = 0x0ecb;
+pub const XK_Hangul_U: Keysym = 0x0ecc;
+pub const XK_Hangul_WEO: Keysym = 0x0ecd;
+pub const XK_Hangul_WE: Keysym = 0x0ece;
+pub const XK_Hangul_WI: Keysym = 0x0ecf;
+pub const XK_Hangul_YU: Keysym = 0x0ed0;
+pub const XK_Hangul_EU: Keysym = 0x0ed1;
+pub const XK_Hangul_YI: Keysym = 0x0ed2;
+pub const XK_Hangul_I: Keysym = 0x0ed3;
+pub const XK_Hangul_J_Kiyeog: Keysym = 0x0ed4;
+pub const XK_Hangul_J_SsangKiyeog: Keysym = 0x0ed5;
+pub const XK_Hangul_J_KiyeogSios: Keysym = 0x0ed6;
+pub const XK_Hangul_J_Nieun: Keysym = 0x0ed7;
+pub const XK_Hangul_J_NieunJieuj: Keysym = 0x0ed8;
+pub const XK_Hangul_J_NieunHieuh: Keysym = 0x0ed9;
+pub const XK_Hangul_J_Dikeud: Keysym = 0x0eda;
+pub const XK_Hangul_J_Rieul: Keysym = 0x0edb;
+pub const XK_Hangul_J_RieulKiyeog: Keysym = 0x0edc;
+pub const XK_Hangul_J_RieulMieum: Keysym = 0x0edd;
+pub const XK_Hangul_J_RieulPieub: Keysym = 0x0ede;
+pub const XK_Hangul_J_RieulSios: Keysym = 0x0edf;
+pub const XK_Hangul_J_RieulTieut: Keysym = 0x0ee0;
+pub const XK_Hangul_J_RieulPhieuf: Keysym = 0x0ee1;
+pub const XK_Hangul_J_RieulHieuh: Keysym = 0x0ee2;
+pub const XK_Hangul_J_Mieum: Keysym = 0x0ee3;
+pub const XK_Hangul_J_Pieub: Keysym = 0x0ee4;
+pub const XK_Hangul_J_PieubSios: Keysym = 0x0ee5;
+pub const XK_Hangul_J_Sios: Keysym = 0x0ee6;
+pub const XK_Hangul_J_SsangSios: Keysym = 0x0ee7;
+pub const XK_Hangul_J_Ieung: Keysym = 0x0ee8;
+pub const XK_Hangul_J_Jieuj: Keysym = 0x0ee9;
+pub const XK_Hangul_J_Cieuc: Keysym = 0x0eea;
+pub const XK_Hangul_J_Khieuq: Keysym = 0x0eeb;
+pub const XK_Hangul_J_Tieut: Keysym = 0x0eec;
+pub const XK_Hangul_J_Phieuf: Keysym = 0x0eed;
+pub const XK_Hangul_J_Hieuh: Keysym = 0x0eee;
+pub const XK_Hangul_RieulYeorinHieuh: Keysym = 0x0eef;
+pub const XK_Hangul_SunkyeongeumMieum: Keysym = 0x0ef0;
+pub const XK_Hangul_SunkyeongeumPieub: Keysym = 0x0ef1;
+pub const XK_Hangul_PanSios: Keysym = 0x0ef2;
+pub const XK_Hangul_KkogjiDalrinIeung: Keysym = 0x0ef3;
+pub const XK_Hangul_SunkyeongeumPhieuf: Keysym = 0x0ef4;
+pub const XK_Hangul_YeorinHieuh: Keysym = 0x0ef5;
+pub const XK_Hangul_AraeA: Keysym = 0x0ef6;
+pub const XK_Hangul_AraeAE: Keysym = 0x0ef7;
+pub const XK_Hangul_J_PanSios: Keysym = 0x0ef8;
+pub const XK_Hangul_J_KkogjiDalrinIeung: Keysym = 0x0ef9;
+pub const XK_Hangul_J_YeorinHieuh: Keysym = 0x0efa;
+pub const XK_Korean_Won: Keysym = 0x0eff;
+pub const XK_Armenian_ligature_ew: Keysym = 0x1000587;
+pub const XK_Armenian_full_stop: Keysym = 0x1000589;
+pub const XK_Armenian_verjaket: Keysym = 0x1000589;
+pub const XK_Armenian_separation_mark: Keysym = 0x100055d;
+pub const XK_Armenian_but: Keysym = 0x100055d;
+pub const XK_Armenian_hyphen: Keysym = 0x100058a;
+pub const XK_Armenian_yentamna: Keysym = 0x100058a;
+pub const XK_Armenian_exclam: Keysym = 0x100055c;
+pub const XK_Armenian_amanak: Keysym = 0x100055c;
+pub const XK_Armenian_accent: Keysym = 0x100055b;
+pub const XK_Armenian_shesht: Keysym = 0x100055b;
+pub const XK_Armenian_question: Keysym = 0x100055e;
+pub const XK_Armenian_paruyk: Keysym = 0x100055e;
+pub const XK_Armenian_AYB: Keysym = 0x1000531;
+pub const XK_Armenian_ayb: Keysym = 0x1000561;
+pub const XK_Armenian_BEN: Keysym = 0x1000532;
+pub const XK_Armenian_ben: Keysym = 0x1000562;
+pub const XK_Armenian_GIM: Keysym = 0x1000533;
+pub const XK_Armenian_gim: Keysym = 0x1000563;
+pub const XK_Armenian_DA: Keysym = 0x1000534;
+pub const XK_Armenian_da: Keysym = 0x1000564;
+pub const XK_Armenian_YECH: Keysym = 0x1000535;
+pub const XK_Armenian_yech: Keysym = 0x1000565;
+pub const XK_Armenian_ZA: Keysym = 0x1000536;
+pub const XK_Armenian_za: Keysym = 0x1000566;
+pub const XK_Armenian_E: Keysym = 0x1000537;
+pub const XK_Armenian_e: Keysym = 0x1000567;
+pub const XK_Armenian_AT: Keysym = 0x1000538;
+pub const XK_Armenian_at: Keysym = 0x1000568;
+pub const XK_Armenian_TO: Keysym = 0x1000539;
+pub const XK_Armenian_to: Keysym = 0x1000569;
+pub const XK_Armenian_ZHE: Keysym = 0x100053a;
+pub const XK_Armenian_zhe: Keysym = 0x100056a;
+pub const XK_Armenian_INI: Keysym = 0x100053b;
+pub const XK_Armenian_ini: Keysym = 0x100056b;
+pub const XK_Armenian_LYUN: Keysym = 0x100053c;
+pub const XK_Armenian_lyun: Keysym = 0x100056c;
+pub const XK_Armenian_KHE: Keysym = 0x100053d;
+pub const XK_Armenian_khe: Keysym = 0x100056d;
+pub const XK_Armenian_TSA: Keysym = 0x100053e;
+pub const XK_Armenian_tsa: Keysym = 0x100056e;
+pub const XK_Armenian_KEN: Keysym = 0x100053f;
+pub const XK_Armenian_ken: Keysym = 0x100056f;
+pub const XK_Armenian_HO: Keysym = 0x1000540;
+pub const XK_Armenian_ho: Keysym = 0x1000570;
+pub const XK_Armenian_DZA: Keysym = 0x1000541;
+pub const XK_Armenian_dza: Keysym = 0x1000571;
+pub const XK_Armenian_GHAT: Keysym = 0x1000542;
+pub const XK_Armenian_ghat: Keysym = 0x1000572;
+pub const XK_Armenian_TCHE: Keysym = 0x1000543;
+pub const XK_Armenian_tche: Keysym = 0x1000573;
+pub const XK_Armenian_MEN: Keysym = 0x1000544;
+pub const XK_Armenian_men: Keysym = 0x1000574;
+pub const XK_Armenian_HI: Keysym = 0x1000545;
+pub const XK_Armenian_hi: Keysym = 0x1000575;
+pub const XK_Armenian_NU: Keysym = 0x1000546;
+pub const XK_Armenian_nu: Keysym = 0x1000576;
+pub const XK_Armenian_SHA: Keysym = 0x1000547;
+pub const XK_Armenian_sha: Keysym = 0x1000577;
+pub const XK_Armenian_VO: Keysym = 0x1000548;
+pub const XK_Armenian_vo: Keysym = 0x1000578;
+pub const XK_Armenian_CHA: Keysym = 0x1000549;
+pub const XK_Armenian_cha: Keysym = 0x1000579;
+pub const XK_Armenian_PE: Keysym = 0x100054a;
+pub const XK_Armenian_pe: Keysym = 0x100057a;
+pub const XK_Armenian_JE: Keysym = 0x100054b;
+pub const XK_Armenian_je: Keysym = 0x100057b;
+pub const XK_Armenian_RA: Keysym = 0x100054c;
+pub const XK_Armenian_ra: Keysym = 0x100057c;
+pub const XK_Armenian_SE: Keysym = 0x100054d;
+pub const XK_Armenian_se: Keysym = 0x100057d;
+pub const XK_Armenian_VEV: Keysym = 0x100054e;
+pub const XK_Armenian_vev: Keysym = 0x100057e;
+pub const XK_Armenian_TYUN: Keysym = 0x100054f;
+pub const XK_Armenian_tyun: Keysym = 0x100057f;
+pub const XK_Armenian_RE: Keysym = 0x1000550;
+pub const XK_Armenian_re: Keysym = 0x1000580;
+pub const XK_Armenian_TSO: Keysym = 0x1000551;
+pub const XK_Armenian_tso: Keysym = 0x1000581;
+pub const XK_Armenian_VYUN: Keysym = 0x1000552;
+pub const XK_Armenian_vyun: Keysym = 0x1000582;
+pub const XK_Armenian_PYUR: Keysym = 0x1000553;
+pub const XK_Armenian_pyur: Keysym = 0x1000583;
+pub const XK_Armenian_KE: Keysym = 0x1000554;
+pub const XK_Armenian_ke: Keysym = 0x1000584;
+pub const XK_Armenian_O: Keysym = 0x1000555;
+pub const XK_Armenian_o: Keysym = 0x1000585;
+pub const XK_Armenian_FE: Keysym = 0x1000556;
+pub const XK_Armenian_fe: Keysym = 0x1000586;
+pub const XK_Armenian_apostrophe: Keysym = 0x100055a;
+pub const XK_Georgian_an: Keysym = 0x10010d0;
+pub const XK_Georgian_ban: Keysym = 0x10010d1;
+pub const XK_Georgian_gan: Keysym = 0x10010d2;
+pub const XK_Georgian_don: Keysym = 0x10010d3;
+pub const XK_Georgian_en: Keysym = 0x10010d4;
+pub const XK_Georgian_vin: Keysym = 0x10010d5;
+pub const XK_Georgian_zen: Keysym = 0x10010d6;
+pub const XK_Georgian_tan: Keysym = 0x10010d7;
+pub const XK_Georgian_in: Keysym = 0x10010d8;
+pub const XK_Georgian_kan: Keysym = 0x10010d9;
+pub const XK_Georgian_las: Keysym = 0x10010da;
+pub const XK_Georgian_man: Keysym = 0x10010db;
+pub const XK_Georgian_nar: Keysym = 0x10010dc;
+pub const XK_Georgian_on: Keysym = 0x10010dd;
+pub const XK_Georgian_par: Keysym = 0x10010de;
+pub const XK_Georgian_zhar: Keysym = 0x10010df;
+pub const XK_Georgian_rae: Keysym = 0x10010e0;
+pub const XK_Georgian_san: Keysym = 0x10010e1;
+pub const XK_Georgian_tar: Keysym = 0x10010e2;
+pub const XK_Georgian_un: Keysym = 0x10010e3;
+pub const XK_Georgian_phar: Keysym = 0x10010e4;
+pub const XK_Georgian_khar: Keysym = 0x10010e5;
+pub const XK_Georgian_ghan: Keysym = 0x10010e6;
+pub const XK_Georgian_qar: Keysym = 0x10010e7;
+pub const XK_Georgian_shin: Keysym = 0x10010e8;
+pub const XK_Georgian_chin: Keysym = 0x10010e9;
+pub const XK_Georgian_can: Keysym = 0x10010ea;
+pub const XK_Georgian_jil: Keysym = 0x10010eb;
+pub const XK_Georgian_cil: Keysym = 0x10010ec;
+pub const XK_Georgian_char: Keysym = 0x10010ed;
+pub const XK_Georgian_xan: Keysym = 0x10010ee;
+pub const XK_Georgian_jhan: Keysym = 0x10010ef;
+pub const XK_Georgian_hae: Keysym = 0x10010f0;
+pub const XK_Georgian_he: Keysym = 0x10010f1;
+pub const XK_Georgian_hie: Keysym = 0x10010f2;
+pub const XK_Georgian_we: Keysym = 0x10010f3;
+pub const XK_Georgian_har: Keysym = 0x10010f4;
+pub const XK_Georgian_hoe: Keysym = 0x10010f5;
+pub const XK_Georgian_fi: Keysym = 0x10010f6;
+pub const XK_Xabovedot: Keysym = 0x1001e8a;
+pub const XK_Ibreve: Keysym = 0x100012c;
+pub const XK_Zstroke: Keysym = 0x10001b5;
+pub const XK_Gcaron: Keysym = 0x10001e6;
+pub const XK_Ocaron: Keysym = 0x10001d1;
+pub const XK_Obarred: Keysym = 0x100019f;
+pub const XK_xabovedot: Keysym = 0x1001e8b;
+pub const XK_ibreve: Keysym = 0x100012d;
+pub const XK_zstroke: Keysym = 0x10001b6;
+pub const XK_gcaron: Keysym = 0x10001e7;
+pub const XK_ocaron: Keysym = 0x10001d2;
+pub const XK_obarred: Keysym = 0x1000275;
+pub const XK_SCHWA: Keysym = 0x100018f;
+pub const XK_schwa: Keysym = 0x1000259;
+pub const XK_EZH: Keysym = 0x10001b7;
+pub const XK_ezh: Keysym = 0x1000292;
+pub const XK_Lbelowdot: Keysym = 0x1001e36;
+pub const XK_lbelowdot: Keysym = 0x1001e37;
+pub const XK_Abelowdot: Keysym = 0x1001ea0;
+pub const XK_abelowdot: Keysym = 0x1001ea1;
+pub const XK_Ahook: Keysym = 0x1001ea2;
+pub const XK_ahook: Keysym = 0x1001ea3;
+pub const XK_Acircumflexacute: Keysym = 0x1001ea4;
+pub const XK_acircumflexacute: Keysym = 0x1001ea5;
+pub const XK_Acircumflexgrave: Keysym = 0x1001ea6;
+pub const XK_acircumflexgrave: Keysym = 0x1001ea7;
+pub const XK_Acircumflexhook: Keysym = 0x1001ea8;
+pub const XK_acircumflexhook: Keysym = 0x1001ea9;
+pub const XK_Acircumflextilde: Keysym = 0x1001eaa;
+pub const XK_acircumflextilde: Keysym = 0x1001eab;
+pub const XK_Acircumflexbelowdot: Keysym = 0x1001eac;
+pub const XK_acircumflexbelowdot: Keysym = 0x1001ead;
+pub const XK_Abreveacute: Keysym = 0x1001eae;
+pub const XK_abreveacute: Keysym = 0x1001eaf;
+pub const XK_Abrevegrave: Keysym = 0x1001eb0;
+pub const XK_abrevegrave: Keysym = 0x1001eb1;
+pub const XK_Abrevehook: Keysym = 0x1001eb2;
+pub const XK_abrevehook: Keysym = 0x1001eb3;
+pub const XK_Abrevetilde: Keysym = 0x1001eb4;
+pub const XK_abrevetilde: Keysym = 0x1001eb5;
+pub const XK_Abrevebelowdot: Keysym = 0x1001eb6;
+pub const XK_abrevebelowdot: Keysym = 0x1001eb7;
+pub const XK_Ebelowdot: Keysym = 0x1001eb8;
+pub const XK_ebelowdot: Keysym = 0x1001eb9;
+pub const XK_Ehook: Keysym = 0x1001eba;
+pub const XK_ehook: Keysym = 0x1001ebb;
+pub const XK_Etilde: Keysym = 0x1001ebc;
+pub const XK_etilde: Keysym = 0x1001ebd;
+pub const XK_Ecircumflexacute: Keysym = 0x1001ebe;
+pub const XK_ecircumflexacute: Keysym = 0x1001ebf;
+pub const XK_Ecircumflexgrave: Keysym = 0x1001ec0;
+pub const XK_ecircumflexgrave: Keysym = 0x1001ec1;
+pub const XK_Ecircumflexhook: Keysym = 0x1001ec2;
+pub const XK_ecircumflexhook: Keysym = 0x1001ec3;
+pub const XK_Ecircumflextilde: Keysym = 0x1001ec4;
+pub const XK_ecircumflextilde: Keysym = 0x1001ec5;
+pub const XK_Ecircumflexbelowdot: Keysym = 0x1001ec6;
+pub const XK_ecircumflexbelowdot: Keysym = 0x1001ec7;
+pub const XK_Ihook: Keysym = 0x1001ec8;
+pub const XK_ihook: Keysym = 0x1001ec9;
+pub const XK_Ibelowdot: Keysym = 0x1001eca;
+pub const XK_ibelowdot: Keysym = 0x1001ecb;
+pub const XK_Obelowdot: Keysym = 0x1001ecc;
+pub const XK_obelowdot: Keysym = 0x1001ecd;
+pub const XK_Ohook: Keysym = 0x1001ece;
+pub const XK_ohook: Keysym = 0x1001ecf;
+pub const XK_Ocircumflexacute: Keysym = 0x1001ed0;
+pub const XK_ocircumflexacute: Keysym = 0x1001ed1;
+pub const XK_Ocircumflexgrave: Keysym = 0x1001ed2;
+pub const XK_ocircumflexgrave: Keysym = 0x1001ed3;
+pub const XK_Ocircumflexhook: Keysym = 0x1001ed4;
+pub const XK_ocircumflexhook: Keysym = 0x1001ed5;
+pub const XK_Ocircumflextilde: Keysym = 0x1001ed6;
+pub const XK_ocircumflextilde: Keysym = 0x1001ed7;
+pub const XK_Ocircumflexbelowdot: Keysym = 0x1001ed8;
+pub const XK_ocircumflexbelowdot: Keysym = 0x1001ed9;
+pub const XK_Ohornacute: Keysym = 0x1001eda;
+pub const XK_ohornacute: Keysym = 0x1001edb;
+pub const XK_Ohorngrave: Keysym = 0x1001edc;
+pub const XK_ohorngrave: Keysym = 0x1001edd;
+pub const XK_Ohornhook: Keysym = 0x1001ede;
+pub const XK_ohornhook: Keysym = 0x1001edf;
+pub const XK_Ohorntilde: Keysym = 0x1001ee0;
+pub const XK_ohorntilde: Keysym = 0x1001ee1;
+pub const XK_Ohornbelowdot: Keysym = 0x1001ee2;
+pub const XK_ohornbelowdot: Keysym = 0x1001ee3;
+pub const XK_Ubelowdot: Keysym = 0x1001ee4;
+pub const XK_ubelowdot: Keysym = 0x1001ee5;
+pub const XK_Uhook: Keysym = 0x1001ee6;
+pub const XK_uhook: Keysym = 0x1001ee7;
+pub const XK_Uhornacute: Keysym = 0x1001ee8;
+pub const XK_uhornacute: Keysym = 0x1001ee9;
+pub const XK_Uhorngrave: Keysym = 0x1001eea;
+pub const XK_uhorngrave: Keysym = 0x1001eeb;
+pub const XK_Uhornhook: Keysym = 0x1001eec;
+pub const XK_uhornhook: Keysym = 0x1001eed;
+pub const XK_Uhorntilde: Keysym = 0x1001eee;
+pub const XK_uhorntilde: Keysym = 0x1001eef;
+pub const XK_Uhornbelowdot: Keysym = 0x1001ef0;
+pub const XK_uhornbelowdot: Keysym = 0x1001ef1;
+pub const XK_Ybelowdot: Keysym = 0x1001ef4;
+pub const XK_ybelowdot: Keysym = 0x1001ef5;
+pub const XK_Yhook: Keysym = 0x1001ef6;
+pub const XK_yhook: Keysym = 0x1001ef7;
+pub const XK_Ytilde: Keysym = 0x1001ef8;
+pub const XK_ytilde: Keysym = 0x1001ef9;
+pub const XK_Ohorn: Keysym = 0x10001a0;
+pub const XK_ohorn: Keysym = 0x10001a1;
+pub const XK_Uhorn: Keysym = 0x10001af;
+pub const XK_uhorn: Keysym = 0x10001b0;
+pub const XK_combining_tilde: Keysym = 0x1000303;
+pub const XK_combining_grave: Keysym = 0x1000300;
+pub const XK_combining_acute: Keysym = 0x1000301;
+pub const XK_combining_hook: Keysym = 0x1000309;
+pub const XK_combining_belowdot: Keysym = 0x1000323;
+pub const XK_EcuSign: Keysym = 0x10020a0;
+pub const XK_ColonSign: Keysym = 0x10020a1;
+pub const XK_CruzeiroSign: Keysym = 0x10020a2;
+pub const XK_FFrancSign: Keysym = 0x10020a3;
+pub const XK_LiraSign: Keysym = 0x10020a4;
+pub const XK_MillSign: Keysym = 0x10020a5;
+pub const XK_NairaSign: Keysym = 0x10020a6;
+pub const XK_PesetaSign: Keysym = 0x10020a7;
+pub const XK_RupeeSign: Keysym = 0x10020a8;
+pub const XK_WonSign: Keysym = 0x10020a9;
+pub const XK_NewSheqelSign: Keysym = 0x10020aa;
+pub const XK_DongSign: Keysym = 0x10020ab;
+pub const XK_EuroSign: Keysym = 0x20ac;
+pub const XK_zerosuperior: Keysym = 0x1002070;
+pub const XK_foursuperior: Keysym = 0x1002074;
+pub const XK_fivesuperior: Keysym = 0x1002075;
+pub const XK_sixsuperior: Keysym = 0x1002076;
+pub const XK_sevensuperior: Keysym = 0x1002077;
+pub const XK_eightsuperior: Keysym = 0x1002078;
+pub const XK_ninesuperior: Keysym = 0x1002079;
+pub const XK_zerosubscript: Keysym = 0x1002080;
+pub const XK_onesubscript: Keysym = 0x1002081;
+pub const XK_twosubscript: Keysym = 0x1002082;
+pub const XK_threesubscript: Keysym = 0x1002083;
+pub const XK_foursubscript: Keysym = 0x1002084;
+pub const XK_fivesubscript: Keysym = 0x1002085;
+pub const XK_sixsubscript: Keysym = 0x1002086;
+pub const XK_sevensubscript: Keysym = 0x1002087;
+pub const XK_eightsubscript: Keysym = 0x1002088;
+pub const XK_ninesubscript: Keysym = 0x1002089;
+pub const XK_partdifferential: Keysym = 0x1002202;
+pub const XK_emptyset: Keysym = 0x1002205;
+pub const XK_elementof: Keysym = 0x1002208;
+pub const XK_notelementof: Keysym = 0x1002209;
+pub const XK_containsas: Keysym = 0x100220b;
+pub const XK_squareroot: Keysym = 0x100221a;
+pub const XK_cuberoot: Keysym = 0x100221b;
+pub const XK_fourthroot: Keysym = 0x100221c;
+pub const XK_dintegral: Keysym = 0x100222c;
+pub const XK_tintegral: Keysym = 0x100222d;
+pub const XK_because: Keysym = 0x1002235;
+pub const XK_approxeq: Keysym = 0x1002248;
+pub const XK_notapproxeq: Keysym = 0x1002247;
+pub const XK_notidentical: Keysym = 0x1002262;
+pub const XK_stricteq: Keysym = 0x1002263;
+pub const XK_braille_dot_1: Keysym = 0xfff1;
+pub const XK_braille_dot_2: Keysym = 0xfff2;
+pub const XK_braille_dot_3: Keysym = 0xfff3;
+pub const XK_braille_dot_4: Keysym = 0xfff4;
+pub const XK_braille_dot_5: Keysym = 0xfff5;
+pub const XK_braille_dot_6: Keysym = 0xfff6;
+pub const XK_braille_dot_7: Keysym = 0xfff7;
+pub const XK_braille_dot_8: Keysym = 0xfff8;
+pub const XK_braille_dot_9: Keysym = 0xfff9;
+pub const XK_braille_dot_10: Keysym = 0xfffa;
+pub const XK_braille_blank: Keysym = 0x1002800;
+pub const XK_braille_dots_1: Keysym = 0x1002801;
+pub const XK_braille_dots_2: Keysym = 0x1002802;
+pub const XK_braille_dots_12: Keysym = 0x1002803;
+pub const XK_braille_dots_3: Keysym = 0x1002804;
+pub const XK_braille_dots_13: Keysym = 0x1002805;
+pub const XK_braille_dots_23: Keysym = 0x1002806;
+pub const XK_braille_dots_123: Keysym = 0x1002807;
+pub const XK_braille_dots_4: Keysym = 0x1002808;
+pub const XK_braille_dots_14: Keysym = 0x1002809;
+pub const XK_braille_dots_24: Keysym = 0x100280a;
+pub const XK_braille_dots_124: Keysym = 0x100280b;
+pub const XK_braille_dots_34: Keysym = 0x100280c;
+pub const XK_braille_dots_134: Keysym = 0x100280d;
+pub const XK_braille_dots_234: Keysym = 0x100280e;
+pub const XK_braille_dots_1234: Keysym = 0x100280f;
+pub const XK_braille_dots_5: Keysym = 0x1002810;
+pub const XK_braille_dots_15: Keysym = 0x1002811;
+pub const XK_braille_dots_25: Keysym = 0x1002812;
+pub const XK_braille_dots_125: Keysym = 0x1002813;
+pub const XK_braille_dots_35: Keysym = 0x1002814;
+pub const XK_braille_dots_135: Keysym = 0x1002815;
+pub const XK_braille_dots_235: Keysym = 0x1002816;
+pub const XK_braille_dots_1235: Keysym = 0x1002817;
+pub const XK_braille_dots_45: Keysym = 0x1002818;
+pub const XK_braille_dots_145: Keysym = 0x1002819;
+pub const XK_braille_dots_245: Keysym = 0x100281a;
+pub const XK_braille_dots_1245: Keysym = 0x100281b;
+pub const XK_braille_dots_345: Keysym = 0x100281c;
+pub const XK_braille_dots_1345: Keysym = 0x100281d;
+pub const XK_braille_dots_2345: Keysym = 0x100281e;
+pub const XK_braille_dots_12345: Keysym = 0x100281f;
+pub const XK_braille_dots_6: Keysym = 0x1002820;
+pub const XK_braille_dots_16: Keysym = 0x1002821;
+pub const XK_braille_dots_26: Keysym = 0x1002822;
+pub const XK_braille_dots_126: Keysym = 0x1002823;
+pub const XK_braille_dots_36: Keysym = 0x1002824;
+pub const XK_braille_dots_136: Keysym = 0x1002825;
+pub const XK_braille_dots_236: Keysym = 0x1002826;
+pub const XK_braille_dots_1236: Keysym = 0x1002827;
+pub const XK_braille_dots_46: Keysym = 0x1002828;
+pub const XK_braille_dots_146: Keysym = 0x1002829;
+pub const XK_braille_dots_246: Keysym = 0x100282a;
+pub const XK_braille_dots_1246: Keysym = 0x100282b;
+pub const XK_braille_dots_346: Keysym = 0x100282c;
+pub const XK_braille_dots_1346: Keysym = 0x100282d;
+pub const XK_braille_dots_2346: Keysym = 0x100282e;
+pub const XK_braille_dots_12346: Keysym = 0x100282f;
+pub const XK_braille_dots_56: Keysym = 0x1002830;
+pub const XK_braille_dots_156: Keysym = 0x1002831;
+pub const XK_braille_dots_256: Keysym = 0x1002832;
+pub const XK_braille_dots_1256: Keysym = 0x1002833;
+pub const XK_braille_dots_356: Keysym = 0x1002834;
+pub const XK_braille_dots_1356: Keysym = 0x1002835;
+pub const XK_braille_dots_2356: Keysym = 0x1002836;
+pub const XK_braille_dots_12356: Keysym = 0x1002837;
+pub const XK_braille_dots_456: Keysym = 0x1002838;
+pub const XK_braille_dots_1456: Keysym = 0x1002839;
+pub const XK_braille_dots_2456: Keysym = 0x100283a;
+pub const XK_braille_dots_12456: Keysym = 0x100283b;
+pub const XK_braille_dots_3456: Keysym = 0x100283c;
+pub const XK_braille_dots_13456: Keysym = 0x100283d;
+pub const XK_braille_dots_23456: Keysym = 0x100283e;
+pub const XK_braille_dots_123456: Keysym = 0x100283f;
+pub const XK_braille_dots_7: Keysym = 0x1002840;
+pub const XK_braille_dots_17: Keysym = 0x1002841;
+pub const XK_braille_dots_27: Keysym = 0x1002842;
+pub const XK_braille_dots_127: Keysym = 0x1002843;
+pub const XK_braille_dots_37: Keysym = 0x1002844;
+pub const XK_braille_dots_137: Keysym = 0x1002845;
+pub const XK_braille_dots_237: Keysym = 0x1002846;
+pub const XK_braille_dots_1237: Keysym = 0x1002847;
+pub const XK_braille_dots_47: Keysym = 0x1002848;
+pub const XK_braille_dots_147: Keysym = 0x1002849;
+pub const XK_braille_dots_247: Keysym = 0x100284a;
+pub const XK_braille_dots_1247: Keysym = 0x100284b;
+pub const XK_braille_dots_347: Keysym = 0x100284c;
+pub const XK_braille_dots_1347: Keysym = 0x100284d;
+pub const XK_braille_dots_2347: Keysym = 0x100284e;
+pub const XK_braille_dots_12347: Keysym = 0x100284f;
+pub const XK_braille_dots_57: Keysym = 0x1002850;
+pub const XK_braille_dots_157: Keysym = 0x1002851;
+pub const XK_braille_dots_257: Keysym = 0x1002852;
+pub const XK_braille_dots_1257: Keysym = 0x1002853;
+pub const XK_braille_dots_357: Keysym = 0x1002854;
+pub const XK_braille_dots_1357: Keysym = 0x1002855;
+pub const XK_braille_dots_2357: Keysym = 0x1002856;
+pub const XK_braille_dots_12357: Keysym = 0x1002857;
+pub const XK_braille_dots_457: Keysym = 0x1002858;
+pub const XK_braille_dots_1457: Keysym = 0x1002859;
+pub const XK_braille_dots_2457: Keysym = 0x100285a;
+pub const XK_braille_dots_12457: Keysym = 0x100285b;
+pub const XK_braille_dots_3457: Keysym = 0x100285c;
+pub const XK_braille_dots_13457: Keysym = 0x100285d;
+pub const XK_braille_dots_23457: Keysym = 0x100285e;
+pub const XK_braille_dots_123457: Keysym = 0x100285f;
+pub const XK_braille_dots_67: Keysym = 0x1002860;
+pub const XK_braille_dots_167: Keysym = 0x1002861;
+pub const XK_braille_dots_267: Keysym = 0x1002862;
+pub const XK_braille_dots_1267: Keysym = 0x1002863;
+pub const XK_braille_dots_367: Keysym = 0x1002864;
+pub const XK_braille_dots_1367: Keysym = 0x1002865;
+pub const XK_braille_dots_2367: Keysym = 0x1002866;
+pub const XK_braille_dots_12367: Keysym = 0x1002867;
+pub const XK_braille_dots_467: Keysym = 0x1002868;
+pub const XK_braille_dots_1467: Keysym = 0x1002869;
+pub const XK_braille_dots_2467: Keysym = 0x100286a;
+pub const XK_braille_dots_12467: Keysym = 0x100286b;
+pub const XK_braille_dots_3467: Keysym = 0x100286c;
+pub const XK_braille_dots_13467: Keysym = 0x100286d;
+pub const XK_braille_dots_23467: Keysym = 0x100286e;
+pub const XK_braille_dots_123467: Keysym = 0x100286f;
+pub const XK_braille_dots_567: Keysym = 0x1002870;
+pub const XK_braille_dots_1567: Keysym = 0x1002871;
+pub const XK_braille_dots_2567: Keysym = 0x1002872;
+pub const XK_braille_dots_12567: Keysym = 0x1002873;
+pub const XK_braille_dots_3567: Keysym = 0x1002874;
+pub const XK_braille_dots_13567: Keysym = 0x1002875;
+pub const XK_braille_dots_23567: Keysym = 0x1002876;
+pub const XK_braille_dots_123567: Keysym = 0x1002877;
+pub const XK_braille_dots_4567: Keysym = 0x1002878;
+pub const XK_braille_dots_14567: Keysym = 0x1002879;
+pub const XK_braille_dots_24567: Keysym = 0x100287a;
+pub const XK_braille_dots_124567: Keysym = 0x100287b;
+pub const XK_braille_dots_34567: Keysym = 0x100287c;
+pub const XK_braille_dots_134567: Keysym = 0x100287d;
+pub const XK_braille_dots_234567: Keysym = 0x100287e;
+pub const XK_braille_dots_1234567: Keysym = 0x100287f;
+pub const XK_braille_dots_8: Keysym = 0x1002880;
+pub const XK_braille_dots_18: Keysym = 0x1002881;
+pub const XK_braille_dots_28: Keysym = 0x1002882;
+pub const XK_braille_dots_128: Keysym = 0x1002883;
+pub const XK_braille_dots_38: Keysym = 0x1002884;
+pub const XK_braille_dots_138: Keysym = 0x1002885;
+pub const XK_braille_dots_238: Keysym = 0x1002886;
+pub const XK_braille_dots_1238: Keysym = 0x1002887;
+pub const XK_braille_dots_48: Keysym = 0x1002888;
+pub const XK_braille_dots_148: Keysym = 0x1002889;
+pub const XK_braille_dots_248: Keysym = 0x100288a;
+pub const XK_braille_dots_1248: Keysym = 0x100288b;
+pub const XK_braille_dots_348: Keysym = 0x100288c;
+pub const XK_braille_dots_1348: Keysym = 0x100288d;
+pub const XK_braille_dots_2348: Keysym = 0x100288e;
+pub const XK_braille_dots_12348: Keysym = 0x100288f;
+pub const XK_braille_dots_58: Keysym = 0x1002890;
+pub const XK_braille_dots_158: Keysym = 0x1002891;
+pub const XK_braille_dots_258: Keysym = 0x1002892;
+pub const XK_braille_dots_1258: Keysym = 0x1002893;
+pub const XK_braille_dots_358: Keysym = 0x1002894;
+pub const XK_braille_dots_1358: Keysym = 0x1002895;
+pub const XK_braille_dots_2358: Keysym = 0x1002896;
+pub const XK_braille_dots_12358: Keysym = 0x1002897;
+pub const XK_braille_dots_458: Keysym = 0x1002898;
+pub const XK_braille_dots_1458: Keysym = 0x1002899;
+pub const XK_braille_dots_2458: Keysym = 0x100289a;
+pub const XK_braille_dots_12458: Keysym = 0x100289b;
+pub const XK_braille_dots_3458: Keysym = 0x100289c;
+pub const XK_braille_dots_13458: Keysym = 0x100289d;
+pub const XK_braille_dots_23458: Keysym = 0x100289e;
+pub const XK_braille_dots_123458: Keysym = 0x100289f;
+pub const XK_braille_dots_68: Keysym = 0x10028a0;
+pub const XK_braille_dots_168: Keysym = 0x10028a1;
+pub const XK_braille_dots_268: Keysym = 0x10028a2;
+pub const XK_braille_dots_1268: Keysym = 0x10028a3;
+pub const XK_braille_dots_368: Keysym = 0x10028a4;
+pub const XK_braille_dots_1368: Keysym = 0x10028a5;
+pub const XK_braille_dots_2368: Keysym = 0x10028a6;
+pub const XK_braille_dots_12368: Keysym = 0x10028a7;
+pub const XK_braille_dots_468: Keysym = 0x10028a8;
+pub const XK_braille_dots_1468: Keysym = 0x10028a9;
+pub const XK_braille_dots_2468: Keysym = 0x10028aa;
+pub const XK_braille_dots_12468: Keysym = 0x10028ab;
+pub const XK_braille_dots_3468: Keysym = 0x10028ac;
+pub const XK_braille_dots_13468: Keysym = 0x10028ad;
+pub const XK_braille_dots_23468: Keysym = 0x10028ae;
+pub const XK_braille_dots_123468: Keysym = 0x10028af;
+pub const XK_braille_dots_568: Keysym = 0x10028b0;
+pub const XK_braille_dots_1568: Keysym = 0x10028b1;
+pub const XK_braille_dots_2568: Keysym = 0x10028b2;
+pub const XK_braille_dots_12568: Keysym = 0x10028b3;
+pub const XK_braille_dots_3568: Keysym = 0x10028b4;
+pub const XK_braille_dots_13568: Keysym = 0x10028b5;
+pub const XK_braille_dots_23568: Keysym = 0x10028b6;
+pub const XK_braille_dots_123568: Keysym = 0x10028b7;
+pub const XK_braille_dots_4568: Keysym = 0x10028b8;
+pub const XK_braille_dots_14568: Keysym = 0x10028b9;
+pub const XK_braille_dots_24568: Keysym = 0x10028ba;
+pub const XK_braille_dots_124568: Keysym = 0x10028bb;
+pub const XK_braille_dots_34568: Keysym = 0x10028bc;
+pub const XK_braille_dots_134568: Keysym = 0x10028bd;
+pub const XK_braille_dots_234568: Keysym = 0x10028be;
+pub const XK_braille_dots_1234568: Keysym = 0x10028bf;
+pub const XK_braille_dots_78: Keysym = 0x10028c0;
+pub const XK_braille_dots_178: Keysym = 0x10028c1;
+pub const XK_braille_dots_278: Keysym = 0x10028c2;
+pub const XK_braille_dots_1278: Keysym = 0x10028c3;
+pub const XK_braille_dots_378: Keysym = 0x10028c4;
+pub const XK_braille_dots_1378: Keysym = 0x10028c5;
+pub const XK_braille_dots_2378: Keysym = 0x10028c6;
+pub const XK_braille_dots_12378: Keysym = 0x10028c7;
+pub const XK_braille_dots_478: Keysym = 0x10028c8;
+pub const XK_braille_dots_1478: Keysym = 0x10028c9;
+pub const XK_braille_dots_2478: Keysym = 0x10028ca;
+pub const XK_braille_dots_12478: Keysym = 0x10028cb;
+pub const XK_braille_dots_3478: Keysym = 0x10028cc;
+pub const XK_braille_dots_13478: Keysym = 0x10028cd;
+pub const XK_braille_dots_23478: Keysym = 0x10028ce;
+pub const XK_braille_dots_123478: Keysym = 0x10028cf;
+pub const XK_braille_dots_578: Keysym = 0x10028d0;
+pub const XK_braille_dots_1578: Keysym = 0x10028d1;
+pub const XK_braille_dots_2578: Keysym = 0x10028d2;
+pub const XK_braille_dots_12578: Keysym = 0x10028d3;
+pub const XK_braille_dots_3578: Keysym = 0x10028d4;
+pub const XK_braille_dots_13578: Keysym = 0x10028d5;
+pub const XK_braille_dots_23578: Keysym = 0x10028d6;
+pub const XK_braille_dots_123578: Keysym = 0x10028d7;
+pub const XK_braille_dots_4578: Keysym = 0x10028d8;
+pub const XK_braille_dots_14578: Keysym = 0x10028d9;
+pub const XK_braille_dots_24578: Keysym = 0x10028da;
+pub const XK_braille_dots_124578: Keysym = 0x10028db;
+pub const XK_braille_dots_34578: Keysym = 0x10028dc;
+pub const XK_braille_dots_134578: Keysym = 0x10028dd;
+pub const XK_braille_dots_234578: Keysym = 0x10028de;
+pub const XK_braille_dots_1234578: Keysym = 0x10028df;
+pub const XK_braille_dots_678: Keysym = 0x10028e0;
+pub const XK_braille_dots_1678: Keysym = 0x10028e1;
+pub const XK_braille_dots_2678: Keysym = 0x10028e2;
+pub const XK_braille_dots_12678: Keysym = 0x10028e3;
+pub const XK_braille_dots_3678: Keysym = 0x10028e4;
+pub const XK_braille_dots_13678: Keysym = 0x10028e5;
+pub const XK_braille_dots_23678: Keysym = 0x10028e6;
+pub const XK_braille_dots_123678: Keysym = 0x10028e7;
+pub const XK_braille_dots_4678: Keysym = 0x10028e8;
+pub const XK_braille_dots_14678: Keysym = 0x10028e9;
+pub const XK_braille_dots_24678: Keysym = 0x10028ea;
+pub const XK_braille_dots_124678: Keysym = 0x10028eb;
+pub const XK_braille_dots_34678: Keysym = 0x10028ec;
+pub const XK_braille_dots_134678: Keysym = 0x10028ed;
+pub const XK_braille_dots_234678: Keysym = 0x10028ee;
+pub const XK_braille_dots_1234678: Keysym = 0x10028ef;
+pub const XK_braille_dots_5678: Keysym = 0x10028f0;
+pub const XK_braille_dots_15678: Keysym = 0x10028f1;
+pub const XK_braille_dots_25678: Keysym = 0x10028f2;
+pub const XK_braille_dots_125678: Keysym = 0x10028f3;
+pub const XK_braille_dots_35678: Keysym = 0x10028f4;
+pub const XK_braille_dots_135678: Keysym = 0x10028f5;
+pub const XK_braille_dots_235678: Keysym = 0x10028f6;
+pub const XK_braille_dots_1235678: Keysym = 0x10028f7;
+pub const XK_braille_dots_45678: Keysym = 0x10028f8;
+pub const XK_braille_dots_145678: Keysym = 0x10028f9;
+pub const XK_braille_dots_245678: Keysym = 0x10028fa;
+pub const XK_braille_dots_1245678: Keysym = 0x10028fb;
+pub const XK_braille_dots_345678: Keysym = 0x10028fc;
+pub const XK_braille_dots_1345678: Keysym = 0x10028fd;
+pub const XK_braille_dots_2345678: Keysym = 0x10028fe;
+pub const XK_braille_dots_12345678: Keysym = 0x10028ff;
+pub const XK_Sinh_ng: Keysym = 0x1000d82;
+pub const XK_Sinh_h2: Keysym = 0x1000d83;
+pub const XK_Sinh_a: Keysym = 0x1000d85;
+pub const XK_Sinh_aa: Keysym = 0x1000d86;
+pub const XK_Sinh_ae: Keysym = 0x1000d87;
+pub const XK_Sinh_aee: Keysym = 0x1000d88;
+pub const XK_Sinh_i: Keysym = 0x1000d89;
+pub const XK_Sinh_ii: Keysym = 0x1000d8a;
+pub const XK_Sinh_u: Keysym = 0x1000d8b;
+pub const XK_Sinh_uu: Keysym = 0x1000d8c;
+pub const XK_Sinh_ri: Keysym = 0x1000d8d;
+pub const XK_Sinh_rii: Keysym = 0x1000d8e;
+pub const XK_Sinh_lu: Keysym = 0x1000d8f;
+pub const XK_Sinh_luu: Keysym = 0x1000d90;
+pub const XK_Sinh_e: Keysym = 0x1000d91;
+pub const XK_Sinh_ee: Keysym = 0x1000d92;
+pub const XK_Sinh_ai: Keysym = 0x1000d93;
+pub const XK_Sinh_o: Keysym = 0x1000d94;
+pub const XK_Sinh_oo: Keysym = 0x1000d95;
+pub const XK_Sinh_au: Keysym = 0x1000d96;
+pub const XK_Sinh_ka: Keysym = 0x1000d9a;
+pub const XK_Sinh_kha: Keysym = 0x1000d9b;
+pub const XK_Sinh_ga: Keysym = 0x1000d9c;
+pub const XK_Sinh_gha: Keysym = 0x1000d9d;
+pub const XK_Sinh_ng2: Keysym = 0x1000d9e;
+pub const XK_Sinh_nga: Keysym = 0x1000d9f;
+pub const XK_Sinh_ca: Keysym = 0x1000da0;
+pub const XK_Sinh_cha: Keysym = 0x1000da1;
+pub const XK_Sinh_ja: Keysym = 0x1000da2;
+pub const XK_Sinh_jha: Keysym = 0x1000da3;
+pub const XK_Sinh_nya: Keysym = 0x1000da4;
+pub const XK_Sinh_jnya: Keysym = 0x1000da5;
+pub const XK_Sinh_nja: Keysym = 0x1000da6;
+pub const XK_Sinh_tta: Keysym = 0x1000da7;
+pub const XK_Sinh_ttha: Keysym = 0x1000da8;
+pub const XK_Sinh_dda: Keysym = 0x1000da9;
+pub const XK_Sinh_ddha: Keysym = 0x1000daa;
+pub const XK_Sinh_nna: Keysym = 0x1000dab;
+pub const XK_Sinh_ndda: Keysym = 0x1000dac;
+pub const XK_Sinh_tha: Keysym = 0x1000dad;
+pub const XK_Sinh_thha: Keysym = 0x1000dae;
+pub const XK_Sinh_dha: Keysym = 0x1000daf;
+pub const XK_Sinh_dhha: Keysym = 0x1000db0;
+pub const XK_Sinh_na: Keysym = 0x1000db1;
+pub const XK_Sinh_ndha: Keysym = 0x1000db3;
+pub const XK_Sinh_pa: Keysym = 0x1000db4;
+pub const XK_Sinh_pha: Keysym = 0x1000db5;
+pub const XK_Sinh_ba: Keysym = 0x1000db6;
+pub const XK_Sinh_bha: Keysym = 0x1000db7;
+pub const XK_Sinh_ma: Keysym = 0x1000db8;
+pub const XK_Sinh_mba: Keysym = 0x1000db9;
+pub const XK_Sinh_ya: Keysym = 0x1000dba;
+pub const XK_Sinh_ra: Keysym = 0x1000dbb;
+pub const XK_Sinh_la: Keysym = 0x1000dbd;
+pub const XK_Sinh_va: Keysym = 0x1000dc0;
+pub const XK_Sinh_sha: Keysym = 0x1000dc1;
+pub const XK_Sinh_ssha: Keysym = 0x1000dc2;
+pub const XK_Sinh_sa: Keysym = 0x1000dc3;
+pub const XK_Sinh_ha: Keysym = 0x1000dc4;
+pub const XK_Sinh_lla: Keysym = 0x1000dc5;
+pub const XK_Sinh_fa: Keysym = 0x1000dc6;
+pub const XK_Sinh_al: Keysym = 0x1000dca;
+pub const XK_Sinh_aa2: Keysym = 0x1000dcf;
+pub const XK_Sinh_ae2: Keysym = 0x1000dd0;
+pub const XK_Sinh_aee2: Keysym = 0x1000dd1;
+pub const XK_Sinh_i2: Keysym = 0x1000dd2;
+pub const XK_Sinh_ii2: Keysym = 0x1000dd3;
+pub const XK_Sinh_u2: Keysym = 0x1000dd4;
+pub const XK_Sinh_uu2: Keysym = 0x1000dd6;
+pub const XK_Sinh_ru2: Keysym = 0x1000dd8;
+pub const XK_Sinh_e2: Keysym = 0x1000dd9;
+pub const XK_Sinh_ee2: Keysym = 0x1000dda;
+pub const XK_Sinh_ai2: Keysym = 0x1000ddb;
+pub const XK_Sinh_o2: Keysym = 0x1000ddc;
+pub const XK_Sinh_oo2: Keysym = 0x1000ddd;
+pub const XK_Sinh_au2: Keysym = 0x1000dde;
+pub const XK_Sinh_lu2: Keysym = 0x1000ddf;
+pub const XK_Sinh_ruu2: Keysym = 0x1000df2;
+pub const XK_Sinh_luu2: Keysym = 0x1000df3;
+pub const XK_Sinh_kunddaliya: Keysym = 0x1000df4;
+pub const XF86XK_ModeLock: Keysym = 0x1008ff01;
+pub const XF86XK_MonBrightnessUp: Keysym = 0x1008ff02;
+pub const XF86XK_MonBrightnessDown: Keysym = 0x1008ff03;
+pub const XF86XK_KbdLightOnOff: Keysym = 0x1008ff04;
+pub const XF86XK_KbdBrightnessUp: Keysym = 0x1008ff05;
+pub const XF86XK_KbdBrightnessDown: Keysym = 0x1008ff06;
+pub const XF86XK_MonBrightnessCycle: Keysym = 0x1008ff07;
+pub const XF86XK_Standby: Keysym = 0x1008ff10;
+pub const XF86XK_AudioLowerVolume: Keysym = 0x1008ff11;
+pub const XF86XK_AudioMute: Keysym = 0x1008ff12;
+pub const XF86XK_AudioRaiseVolume: Keysym = 0x1008ff13;
+pub const XF86XK_AudioPlay: Keysym = 0x1008ff14;
+pub const XF86XK_AudioStop: Keysym = 0x1008ff15;
+pub const XF86XK_AudioPrev: Keysym = 0x1008ff16;
+pub const XF86XK_AudioNext: Keysym = 0x1008ff17;
+pub const XF86XK_HomePage: Keysym = 0x1008ff18;
+pub const XF86XK_Mail: Keysym = 0x1008ff19;
+pub const XF86XK_Start: Keysym = 0x1008ff1a;
+pub const XF86XK_Search: Keysym = 0x1008ff1b;
+pub const XF86XK_AudioRecord: Keysym = 0x1008ff1c;
+pub const XF86XK_Calculator: Keysym = 0x1008ff1d;
+pub const XF86XK_Memo: Keysym = 0x1008ff1e;
+pub const XF86XK_ToDoList: Keysym = 0x1008ff1f;
+pub const XF86XK_Calendar: Keysym = 0x1008ff20;
+pub const XF86XK_PowerDown: Keysym = 0x1008ff21;
+pub const XF86XK_ContrastAdjust: Keysym = 0x1008ff22;
+pub const XF86XK_RockerUp: Keysym = 0x1008ff23;
+pub const XF86XK_RockerDown: Keysym = 0x1008ff24;
+pub const XF86XK_RockerEnter: Keysym = 0x1008ff25;
+pub const XF86XK_Back: Keysym = 0x1008ff26;
+pub const XF86XK_Forward: Keysym = 0x1008ff27;
+pub const XF86XK_Stop: Keysym = 0x1008ff28;
+pub const XF86XK_Refresh: Keysym = 0x1008ff29;
+pub const XF86XK_PowerOff: Keysym = 0x1008ff2a;
+pub const XF86XK_WakeUp: Keysym = 0x1008ff2b;
+pub const XF86XK_Eject: Keysym = 0x1008ff2c;
+pub const XF86XK_ScreenSaver: Keysym = 0x1008ff2d;
+pub const XF86XK_WWW: Keysym = 0x1008ff2e;
+pub const XF86XK_Sleep: Keysym = 0x1008ff2f;
+pub const XF86XK_Favorites: Keysym = 0x1008ff30;
+pub const XF86XK_AudioPause: Keysym = 0x1008ff31;
+pub const XF86XK_AudioMedia: Keysym = 0x1008ff32;
+pub const XF86XK_MyComputer: Keysym = 0x1008ff33;
+pub const XF86XK_VendorHome: Keysym = 0x1008ff34;
+pub const XF86XK_LightBulb: Keysym = 0x1008ff35;
+pub const XF86XK_Shop: Keysym = 0x1008ff36;
+pub const XF86XK_History: Keysym = 0x1008ff37;
+pub const XF86XK_OpenURL: Keysym = 0x1008ff38;
+pub const XF86XK_AddFavorite: Keysym = 0x1008ff39;
+pub const XF86XK_HotLinks: Keysym = 0x1008ff3a;
+pub const XF86XK_BrightnessAdjust: Keysym = 0x1008ff3b;
+pub const XF86XK_Finance: Keysym = 0x1008ff3c;
+pub const XF86XK_Community: Keysym = 0x1008ff3d;
+pub const XF86XK_AudioRewind: Keysym = 0x1008ff3e;
+pub const XF86XK_BackForward: Keysym = 0x1008ff3f;
+pub const XF86XK_Launch0: Keysym = 0x1008ff40;
+pub const XF86XK_Launch1: Keysym = 0x1008ff41;
+pub const XF86XK_Launch2: Keysym = 0x1008ff42;
+pub const XF86XK_Launch3: Keysym = 0x1008ff43;
+pub const XF86XK_Launch4: Keysym = 0x1008ff44;
+pub const XF86XK_Launch5: Keysym = 0x1008ff45;
+pub const XF86XK_Launch6: Keysym = 0x1008ff46;
+pub const XF86XK_Launch7: Keysym = 0x1008ff47;
+pub const XF86XK_Launch8: Keysym = 0x1008ff48;
+pub const XF86XK_Launch9: Keysym = 0x1008ff49;
+pub const XF86XK_LaunchA: Keysym = 0x1008ff4a;
+pub const XF86XK_LaunchB: Keysym = 0x1008ff4b;
+pub const XF86XK_LaunchC: Keysym = 0x1008ff4c;
+pub const XF86XK_LaunchD: Keysym = 0x1008ff4d;
+pub const XF86XK_LaunchE: Keysym = 0x1008ff4e;
+pub const XF86XK_LaunchF: Keysym = 0x1008ff4f;
+pub const XF86XK_ApplicationLeft: Keysym = 0x1008ff50;
+pub const XF86XK_ApplicationRight: Keysym = 0x1008ff51;
+pub const XF86XK_Book: Keysym = 0x1008ff52;
+pub const XF86XK_CD: Keysym = 0x1008ff53;
+pub const XF86XK_Calculater: Keysym = 0x1008ff54;
+pub const XF86XK_Clear: Keysym = 0x1008ff55;
+pub const XF86XK_Close: Keysym = 0x1008ff56;
+pub const XF86XK_Copy: Keysym = 0x1008ff57;
+pub const XF86XK_Cut: Keysym = 0x1008ff58;
+pub const XF86XK_Display: Keysym = 0x1008ff59;
+pub const XF86XK_DOS: Keysym = 0x1008ff5a;
+pub const XF86XK_Documents: Keysym = 0x1008ff5b;
+pub const XF86XK_Excel: Keysym = 0x1008ff5c;
+pub const XF86XK_Explorer: Keysym = 0x1008ff5d;
+pub const XF86XK_Game: Keysym = 0x1008ff5e;
+pub const XF86XK_Go: Keysym = 0x1008ff5f;
+pub const XF86XK_iTouch: Keysym = 0x1008ff60;
+pub const XF86XK_LogOff: Keysym = 0x1008ff61;
+pub const XF86XK_Market: Keysym = 0x1008ff62;
+pub const XF86XK_Meeting: Keysym = 0x1008ff63;
+pub const XF86XK_MenuKB: Keysym = 0x1008ff65;
+pub const XF86XK_MenuPB: Keysym = 0x1008ff66;
+pub const XF86XK_MySites: Keysym = 0x1008ff67;
+pub const XF86XK_New: Keysym = 0x1008ff68;
+pub const XF86XK_News: Keysym = 0x1008ff69;
+pub const XF86XK_OfficeHome: Keysym = 0x1008ff6a;
+pub const XF86XK_Open: Keysym = 0x1008ff6b;
+pub const XF86XK_Option: Keysym = 0x1008ff6c;
+pub const XF86XK_Paste: Keysym = 0x1008ff6d;
+pub const XF86XK_Phone: Keysym = 0x1008ff6e;
+pub const XF86XK_Q: Keysym = 0x1008ff70;
+pub const XF86XK_Reply: Keysym = 0x1008ff72;
+pub const XF86XK_Reload: Keysym = 0x1008ff73;
+pub const XF86XK_RotateWindows: Keysym = 0x1008ff74;
+pub const XF86XK_RotationPB: Keysym = 0x1008ff75;
+pub const XF86XK_RotationKB: Keysym = 0x1008ff76;
+pub const XF86XK_Save: Keysym = 0x1008ff77;
+pub const XF86XK_ScrollUp: Keysym = 0x1008ff78;
+pub const XF86XK_ScrollDown: Keysym = 0x1008ff79;
+pub const XF86XK_ScrollClick: Keysym = 0x1008ff7a;
+pub const XF86XK_Send: Keysym = 0x1008ff7b;
+pub const XF86XK_Spell: Keysym = 0x1008ff7c;
+pub const XF86XK_SplitScreen: Keysym = 0x1008ff7d;
+pub const XF86XK_Support: Keysym = 0x1008ff7e;
+pub const XF86XK_TaskPane: Keysym = 0x1008ff7f;
+pub const XF86XK_Terminal: Keysym = 0x1008ff80;
+pub const XF86XK_Tools: Keysym = 0x1008ff81;
+pub const XF86XK_Travel: Keysym = 0x1008ff82;
+pub const XF86XK_UserPB: Keysym = 0x1008ff84;
+pub const XF86XK_User1KB: Keysym = 0x1008ff85;
+pub const XF86XK_User2KB: Keysym = 0x1008ff86;
+pub const XF86XK_Video: Keysym = 0x1008ff87;
+pub const XF86XK_WheelButton: Keysym = 0x1008ff88;
+pub const XF86XK_Word: Keysym = 0x1008ff89;
+pub const XF86XK_Xfer: Keysym = 0x1008ff8a;
+pub const XF86XK_ZoomIn: Keysym = 0x1008ff8b;
+pub const XF86XK_ZoomOut: Keysym = 0x1008ff8c;
+pub const XF86XK_Away: Keysym = 0x1008ff8d;
+pub const XF86XK_Messenger: Keysym = 0x1008ff8e;
+pub const XF86XK_WebCam: Keysym = 0x1008ff8f;
+pub const XF86XK_MailForward: Keysym = 0x1008ff90;
+pub const XF86XK_Pictures: Keysym = 0x1008ff91;
+pub const XF86XK_Music: Keysym = 0x1008ff92;
+pub const XF86XK_Battery: Keysym = 0x1008ff93;
+pub const XF86XK_Bluetooth: Keysym = 0x1008ff94;
+pub const XF86XK_WLAN: Keysym = 0x1008ff95;
+pub const XF86XK_UWB: Keysym = 0x1008ff96;
+pub const XF86XK_AudioForward: Keysym = 0x1008ff97;
+pub const XF86XK_AudioRepeat: Keysym = 0x1008ff98;
+pub const XF86XK_AudioRandomPlay: Keysym = 0x1008ff99;
+pub const XF86XK_Subtitle: Keysym = 0x1008ff9a;
+pub const XF86XK_AudioCycleTrack: Keysym = 0x1008ff9b;
+pub const XF86XK_CycleAngle: Keysym = 0x1008ff9c;
+pub const XF86XK_FrameBack: Keysym = 0x1008ff9d;
+pub const XF86XK_FrameForward: Keysym = 0x1008ff9e;
+pub const XF86XK_Time: Keysym = 0x1008ff9f;
+pub const XF86XK_Select: Keysym = 0x1008ffa0;
+pub const XF86XK_View: Keysym = 0x1008ffa1;
+pub const XF86XK_TopMenu: Keysym = 0x1008ffa2;
+pub const XF86XK_Red: Keysym = 0x1008ffa3;
+pub const XF86XK_Green: Keysym = 0x1008ffa4;
+pub const XF86XK_Yellow: Keysym = 0x1008ffa5;
+pub const XF86XK_Blue: Keysym = 0x1008ffa6;
+pub const XF86XK_Suspend: Keysym = 0x1008ffa7;
+pub const XF86XK_Hibernate: Keysym = 0x1008ffa8;
+pub const XF86XK_TouchpadToggle: Keysym = 0x1008ffa9;
+pub const XF86XK_TouchpadOn: Keysym = 0x1008ffb0;
+pub const XF86XK_TouchpadOff: Keysym = 0x1008ffb1;
+pub const XF86XK_AudioMicMute: Keysym = 0x1008ffb2;
+pub const XF86XK_Keyboard: Keysym = 0x1008ffb3;
+pub const XF86XK_WWAN: Keysym = 0x1008ffb4;
+pub const XF86XK_RFKill: Keysym = 0x1008ffb5;
+pub const XF86XK_AudioPreset: Keysym = 0x1008ffb6;
+pub const XF86XK_RotationLockToggle: Keysym = 0x1008ffb7;
+pub const XF86XK_FullScreen: Keysym = 0x1008ffb8;
+pub const XF86XK_Switch_VT_1: Keysym = 0x1008fe01;
+pub const XF86XK_Switch_VT_2: Keysym = 0x1008fe02;
+pub const XF86XK_Switch_VT_3: Keysym = 0x1008fe03;
+pub const XF86XK_Switch_VT_4: Keysym = 0x1008fe04;
+pub const XF86XK_Switch_VT_5: Keysym = 0x1008fe05;
+pub const XF86XK_Switch_VT_6: Keysym = 0x1008fe06;
+pub const XF86XK_Switch_VT_7: Keysym = 0x1008fe07;
+pub const XF86XK_Switch_VT_8: Keysym = 0x1008fe08;
+pub const XF86XK_Switch_VT_9: Keysym = 0x1008fe09;
+pub const XF86XK_Switch_VT_10: Keysym = 0x1008fe0a;
+pub const XF86XK_Switch_VT_11: Keysym = 0x1008fe0b;
+pub const XF86XK_Switch_VT_12: Keysym = 0x1008fe0c;
+pub const XF86XK_Ungrab: Keysym = 0x1008fe20;
+pub const XF86XK_ClearGrab: Keysym = 0x1008fe21;
+pub const XF86XK_Next_VMode: Keysym = 0x1008fe22;
+pub const XF86XK_Prev_VMode: Keysym = 0x1008fe23;
+pub const XF86XK_LogWindowTree: Keysym = 0x1008fe24;
+pub const XF86XK_LogGrabInfo: Keysym = 0x1008fe25;
+pub const XF86XK_BrightnessAuto: Keysym = 0x100810f4;
+pub const XF86XK_DisplayOff: Keysym = 0x100810f5;
+pub const XF86XK_Info: Keysym = 0x10081166;
+pub const XF86XK_AspectRatio: Keysym = 0x10081177;
+pub const XF86XK_DVD: Keysym = 0x10081185;
+pub const XF86XK_Audio: Keysym = 0x10081188;
+pub const XF86XK_ChannelUp: Keysym = 0x10081192;
+pub const XF86XK_ChannelDown: Keysym = 0x10081193;
+pub const XF86XK_Break: Keysym = 0x1008119b;
+pub const XF86XK_VideoPhone: Keysym = 0x100811a0;
+pub const XF86XK_ZoomReset: Keysym = 0x100811a4;
+pub const XF86XK_Editor: Keysym = 0x100811a6;
+pub const XF86XK_GraphicsEditor: Keysym = 0x100811a8;
+pub const XF86XK_Presentation: Keysym = 0x100811a9;
+pub const XF86XK_Database: Keysym = 0x100811aa;
+pub const XF86XK_Voicemail: Keysym = 0x100811ac;
+pub const XF86XK_Addressbook: Keysym = 0x100811ad;
+pub const XF86XK_DisplayToggle: Keysym = 0x100811af;
+pub const XF86XK_SpellCheck: Keysym = 0x100811b0;
+pub const XF86XK_ContextMenu: Keysym = 0x100811b6;
+pub const XF86XK_MediaRepeat: Keysym = 0x100811b7;
+pub const XF86XK_10ChannelsUp: Keysym = 0x100811b8;
+pub const XF86XK_10ChannelsDown: Keysym = 0x100811b9;
+pub const XF86XK_Images: Keysym = 0x100811ba;
+pub const XF86XK_NotificationCenter: Keysym = 0x100811bc;
+pub const XF86XK_PickupPhone: Keysym = 0x100811bd;
+pub const XF86XK_HangupPhone: Keysym = 0x100811be;
+pub const XF86XK_Fn: Keysym = 0x100811d0;
+pub const XF86XK_Fn_Esc: Keysym = 0x100811d1;
+pub const XF86XK_FnRightShift: Keysym = 0x100811e5;
+pub const XF86XK_Numeric0: Keysym = 0x10081200;
+pub const XF86XK_Numeric1: Keysym = 0x10081201;
+pub const XF86XK_Numeric2: Keysym = 0x10081202;
+pub const XF86XK_Numeric3: Keysym = 0x10081203;
+pub const XF86XK_Numeric4: Keysym = 0x10081204;
+pub const XF86XK_Numeric5: Keysym = 0x10081205;
+pub const XF86XK_Numeric6: Keysym = 0x10081206;
+pub const XF86XK_Numeric7: Keysym = 0x10081207;
+pub const XF86XK_Numeric8: Keysym = 0x10081208;
+pub const XF86XK_Numeric9: Keysym = 0x10081209;
+pub const XF86XK_NumericStar: Keysym = 0x1008120a;
+pub const XF86XK_NumericPound: Keysym = 0x1008120b;
+pub const XF86XK_NumericA: Keysym = 0x1008120c;
+pub const XF86XK_NumericB: Keysym = 0x1008120d;
+pub const XF86XK_NumericC: Keysym = 0x1008120e;
+pub const XF86XK_NumericD: Keysym = 0x1008120f;
+pub const XF86XK_CameraFocus: Keysym = 0x10081210;
+pub const XF86XK_WPSButton: Keysym = 0x10081211;
+pub const XF86XK_CameraZoomIn: Keysym = 0x10081215;
+pub const XF86XK_CameraZoomOut: Keysym = 0x10081216;
+pub const XF86XK_CameraUp: Keysym = 0x10081217;
+pub const XF86XK_CameraDown: Keysym = 0x10081218;
+pub const XF86XK_CameraLeft: Keysym = 0x10081219;
+pub const XF86XK_CameraRight: Keysym = 0x1008121a;
+pub const XF86XK_AttendantOn: Keysym = 0x1008121b;
+pub const XF86XK_AttendantOff: Keysym = 0x1008121c;
+pub const XF86XK_AttendantToggle: Keysym = 0x1008121d;
+pub const XF86XK_LightsToggle: Keysym = 0x1008121e;
+pub const XF86XK_ALSToggle: Keysym = 0x10081230;
+pub const XF86XK_Buttonconfig: Keysym = 0x10081240;
+pub const XF86XK_Taskmanager: Keysym = 0x10081241;
+pub const XF86XK_Journal: Keysym = 0x10081242;
+pub const XF86XK_ControlPanel: Keysym = 0x10081243;
+pub const XF86XK_AppSelect: Keysym = 0x10081244;
+pub const XF86XK_Screensaver: Keysym = 0x10081245;
+pub const XF86XK_VoiceCommand: Keysym = 0x10081246;
+pub const XF86XK_Assistant: Keysym = 0x10081247;
+pub const XF86XK_EmojiPicker: Keysym = 0x10081249;
+pub const XF86XK_Dictate: Keysym = 0x1008124a;
+pub const XF86XK_BrightnessMin: Keysym = 0x10081250;
+pub const XF86XK_BrightnessMax: Keysym = 0x10081251;
+pub const XF86XK_KbdInputAssistPrev: Keysym = 0x10081260;
+pub const XF86XK_KbdInputAssistNext: Keysym = 0x10081261;
+pub const XF86XK_KbdInputAssistPrevgroup: Keysym = 0x10081262;
+pub const XF86XK_KbdInputAssistNextgroup: Keysym = 0x10081263;
+pub const XF86XK_KbdInputAssistAccept: Keysym = 0x10081264;
+pub const XF86XK_KbdInputAssistCancel: Keysym = 0x10081265;
+pub const XF86XK_RightUp: Keysym = 0x10081266;
+pub const XF86XK_RightDown: Keysym = 0x10081267;
+pub const XF86XK_LeftUp: Keysym = 0x10081268;
+pub const XF86XK_LeftDown: Keysym = 0x10081269;
+pub const XF86XK_RootMenu: Keysym = 0x1008126a;
+pub const XF86XK_MediaTopMenu: Keysym = 0x1008126b;
+pub const XF86XK_Numeric11: Keysym = 0x1008126c;
+pub const XF86XK_Numeric12: Keysym = 0x1008126d;
+pub const XF86XK_AudioDesc: Keysym = 0x1008126e;
+pub const XF86XK_3DMode: Keysym = 0x1008126f;
+pub const XF86XK_NextFavorite: Keysym = 0x10081270;
+pub const XF86XK_StopRecord: Keysym = 0x10081271;
+pub const XF86XK_PauseRecord: Keysym = 0x10081272;
+pub const XF86XK_VOD: Keysym = 0x10081273;
+pub const XF86XK_Unmute: Keysym = 0x10081274;
+pub const XF86XK_FastReverse: Keysym = 0x10081275;
+pub const XF86XK_SlowReverse: Keysym = 0x10081276;
+pub const XF86XK_Data: Keysym = 0x10081277;
+pub const XF86XK_OnScreenKeyboard: Keysym = 0x10081278;
+pub const XF86XK_PrivacyScreenToggle: Keysym = 0x10081279;
+pub const XF86XK_SelectiveScreenshot: Keysym = 0x1008127a;
+pub const XF86XK_Macro1: Keysym = 0x10081290;
+pub const XF86XK_Macro2: Keysym = 0x10081291;
+pub const XF86XK_Macro3: Keysym = 0x10081292;
+pub const XF86XK_Macro4: Keysym = 0x10081293;
+pub const XF86XK_Macro5: Keysym = 0x10081294;
+pub const XF86XK_Macro6: Keysym = 0x10081295;
+pub const XF86XK_Macro7: Keysym = 0x10081296;
+pub const XF86XK_Macro8: Keysym = 0x10081297;
+pub const XF86XK_Macro9: Keysym = 0x10081298;
+pub const XF86XK_Macro10: Keysym = 0x10081299;
+pub const XF86XK_Macro11: Keysym = 0x1008129a;
+pub const XF86XK_Macro12: Keysym = 0x1008129b;
+pub const XF86XK_Macro13: Keysym = 0x1008129c;
+pub const XF86XK_Macro14: Keysym = 0x1008129d;
+pub const XF86XK_Macro15: Keysym = 0x1008129e;
+pub const XF86XK_Macro16: Keysym = 0x1008129f;
+pub const XF86XK_Macro17: Keysym = 0x100812a0;
+pub const XF86XK_Macro18: Keysym = 0x100812a1;
+pub const XF86XK_Macro19: Keysym = 0x100812a2;
+pub const XF86XK_Macro20: Keysym = 0x100812a3;
+pub const XF86XK_Macro21: Keysym = 0x100812a4;
+pub const XF86XK_Macro22: Keysym = 0x100812a5;
+pub const XF86XK_Macro23: Keysym = 0x100812a6;
+pub const XF86XK_Macro24: Keysym = 0x100812a7;
+pub const XF86XK_Macro25: Keysym = 0x100812a8;
+pub const XF86XK_Macro26: Keysym = 0x100812a9;
+pub const XF86XK_Macro27: Keysym = 0x100812aa;
+pub const XF86XK_Macro28: Keysym = 0x100812ab;
+pub const XF86XK_Macro29: Keysym = 0x100812ac;
+pub const XF86XK_Macro30: Keysym = 0x100812ad;
+pub const XF86XK_MacroRecordStart: Keysym = 0x100812b0;
+pub const XF86XK_MacroRecordStop: Keysym = 0x100812b1;
+pub const XF86XK_MacroPresetCycle: Keysym = 0x100812b2;
+pub const XF86XK_MacroPreset1: Keysym = 0x100812b3;
+pub const XF86XK_MacroPreset2: Keysym = 0x100812b4;
+pub const XF86XK_MacroPreset3: Keysym = 0x100812b5;
+pub const XF86XK_KbdLcdMenu1: Keysym = 0x100812b8;
+pub const XF86XK_KbdLcdMenu2: Keysym = 0x100812b9;
+pub const XF86XK_KbdLcdMenu3: Keysym = 0x100812ba;
+pub const XF86XK_KbdLcdMenu4: Keysym = 0x100812bb;
+pub const XF86XK_KbdLcdMenu5: Keysym = 0x100812bc;
+
+/// The names of the keysyms, as [name] and [from_name] use them.
+const NAMES: &[(&str, Keysym)] = &[
+    ("VoidSymbol", XK_VoidSymbol),
+    ("BackSpace", XK_BackSpace),
+    ("Tab", XK_Tab),
+    ("Linefeed", XK_Linefeed),
+    ("Clear", XK_Clear),
+    ("Return", XK_Return),
+    ("Pause", XK_Pause),
+    ("Scroll_Lock", XK_Scroll_Lock),
+    ("Sys_Req", XK_Sys_Req),
+    ("Escape", XK_Escape),
+    ("Delete", XK_Delete),
+    ("Multi_key", XK_Multi_key),
+    ("Codeinput", XK_Codeinput),
+    ("SingleCandidate", XK_SingleCandidate),
+    ("MultipleCandidate", XK_MultipleCandidate),
+    ("PreviousCandidate", XK_PreviousCandidate),
+    ("Kanji", XK_Kanji),
+    ("Muhenkan", XK_Muhenkan),
+    ("Henkan_Mode", XK_Henkan_Mode),
+    ("Henkan", XK_Henkan),
+    ("Romaji", XK_Romaji),
+    ("Hiragana", XK_Hiragana),
+    ("Katakana", XK_Katakana),
+    ("Hiragana_Katakana", XK_Hiragana_Katakana),
+    ("Zenkaku", XK_Zenkaku),
+    ("Hankaku", XK_Hankaku),
+    ("Zenkaku_Hankaku", XK_Zenkaku_Hankaku),
+    ("Touroku", XK_Touroku),
+    ("Massyo", XK_Massyo),
+    ("Kana_Lock", XK_Kana_Lock),
+    ("Kana_Shift", XK_Kana_Shift),
+    ("Eisu_Shift", XK_Eisu_Shift),
+    ("Eisu_toggle", XK_Eisu_toggle),
+    ("Kanji_Bangou", XK_Kanji_Bangou),
+    ("Zen_Koho", XK_Zen_Koho),
+    ("Mae_Koho", XK_Mae_Koho),
+    ("Home", XK_Home),
+    ("Left", XK_Left),
+    ("Up", XK_Up),
+    ("Right", XK_Right),
+    ("Down", XK_Down),
+    ("Prior", XK_Prior),
+    ("Page_Up", XK_Page_Up),
+    ("Next", XK_Next),
+    ("Page_Down", XK_Page_Down),
+    ("End", XK_End),
+    ("Begin", XK_Begin),
+    ("Select", XK_Select),
+    ("Print", XK_Print),
+    ("Execute", XK_Execute),
+    ("Insert", XK_Insert),
+    ("Undo", XK_Undo),
+    ("Redo", XK_Redo),
+    ("Menu", XK_Menu),
+    ("Find", XK_Find),
+    ("Cancel", XK_Cancel),
+    ("Help", XK_Help),
+    ("Break", XK_Break),
+    ("Mode_switch", XK_Mode_switch),
+    ("script_switch", XK_script_switch),
+    ("Num_Lock", XK_Num_Lock),
+    ("KP_Space", XK_KP_Space),
+    ("KP_Tab", XK_KP_Tab),
+    ("KP_Enter", XK_KP_Enter),
+    ("KP_F1", XK_KP_F1),
+    ("KP_F2", XK_KP_F2),
+    ("KP_F3", XK_KP_F3),
+    ("KP_F4", XK_KP_F4),
+    ("KP_Home", XK_KP_Home),
+    ("KP_Left", XK_KP_Left),
+    ("KP_Up", XK_KP_Up),
+    ("KP_Right", XK_KP_Right),
+    ("KP_Down", XK_KP_Down),
+    ("KP_Prior", XK_KP_Prior),
+    ("KP_Page_Up", XK_KP_Page_Up),
+    ("KP_Next", XK_KP_Next),
+    ("KP_Page_Down", XK_KP_Page_Down),
+    ("KP_End", XK_KP_End),
+    ("KP_Begin", XK_KP_Begin),
+    ("KP_Insert", XK_KP_Insert),
+    ("KP_Delete", XK_KP_Delete),
+    ("KP_Equal", XK_KP_Equal),
+    ("KP_Multiply", XK_KP_Multiply),
+    ("KP_Add", XK_KP_Add),
+    ("KP_Separator", XK_KP_Separator),
+    ("KP_Subtract", XK_KP_Subtract),
+    ("KP_Decimal", XK_KP_Decimal),
+    ("KP_Divide", XK_KP_Divide),
+    ("KP_0", XK_KP_0),
+    ("KP_1", XK_KP_1),
+    ("KP_2", XK_KP_2),
+    ("KP_3", XK_KP_3),
+    ("KP_4", XK_KP_4),
+    ("KP_5", XK_KP_5),
+    ("KP_6", XK_KP_6),
+    ("KP_7", XK_KP_7),
+    ("KP_8", XK_KP_8),
+    ("KP_9", XK_KP_9),
+    ("F1", XK_F1),
+    ("F2", XK_F2),
+    ("F3", XK_F3),
+    ("F4", XK_F4),
+    ("F5", XK_F5),
+    ("F6", XK_F6),
+    ("F7", XK_F7),
+    ("F8", XK_F8),
+    ("F9", XK_F9),
+    ("F10", XK_F10),
+    ("F11", XK_F11),
+    ("L1", XK_L1),
+    ("F12", XK_F12),
+    ("L2", XK_L2),
+    ("F13", XK_F13),
+    ("L3", XK_L3),
+    ("F14", XK_F14),
+    ("L4", XK_L4),
+    ("F15", XK_F15),
+    ("L5", XK_L5),
+    ("F16", XK_F16),
+    ("L6", XK_L6),
+    ("F17", XK_F17),
+    ("L7", XK_L7),
+    ("F18", XK_F18),
+    ("L8", XK_L8),
+    ("F19", XK_F19),
+    ("L9", XK_L9),
+    ("F20", XK_F20),
+    ("L10", XK_L10),
+    ("F21", XK_F21),
+    ("R1", XK_R1),
+    ("F22", XK_F22),
+    ("R2", XK_R2),
+    ("F23", XK_F23),
+    ("R3", XK_R3),
+    ("F24", XK_F24),
+    ("R4", XK_R4),
+    ("F25", XK_F25),
+    ("R5", XK_R5),
+    ("F26", XK_F26),
+    ("R6", XK_R6),
+    ("F27", XK_F27),
+    ("R7", XK_R7),
+    ("F28", XK_F28),
+    ("R8", XK_R8),
+    ("F29", XK_F29),
+    ("R9", XK_R9),
+    ("F30", XK_F30),
+    ("R10", XK_R10),
+    ("F31", XK_F31),
+    ("R11", XK_R11),
+    ("F32", XK_F32),
+    ("R12", XK_R12),
+    ("F33", XK_F33),
+    ("R13", XK_R13),
+    ("F34", XK_F34),
+    ("R14", XK_R14),
+    ("F35", XK_F35),
+    ("R15", XK_R15),
+    ("Shift_L", XK_Shift_L),
+    ("Shift_R", XK_Shift_R),
+    ("Control_L", XK_Control_L),
+    ("Control_R", XK_Control_R),
+    ("Caps_Lock", XK_Caps_Lock),
+    ("Shift_Lock", XK_Shift_Lock),
+    ("Meta_L", XK_Meta_L),
+    ("Meta_R", XK_Meta_R),
+    ("Alt_L", XK_Alt_L),
+    ("Alt_R", XK_Alt_R),
+    ("Super_L", XK_Super_L),
+    ("Super_R", XK_Super_R),
+    ("Hyper_L", XK_Hyper_L),
+    ("Hyper_R", XK_Hyper_R),
+    ("ISO_Lock", XK_ISO_Lock),
+    ("ISO_Level2_Latch", XK_ISO_Level2_Latch),
+    ("ISO_Level3_Shift", XK_ISO_Level3_Shift),
+    ("ISO_Level3_Latch", XK_ISO_Level3_Latch),
+    ("ISO_Level3_Lock", XK_ISO_Level3_Lock),
+    ("ISO_Level5_Shift", XK_ISO_Level5_Shift),
+    ("ISO_Level5_Latch", XK_ISO_Level5_Latch),
+    ("ISO_Level5_Lock", XK_ISO_Level5_Lock),
+    ("ISO_Group_Shift", XK_ISO_Group_Shift),
+    ("ISO_Group_Latch", XK_ISO_Group_Latch),
+    ("ISO_Group_Lock", XK_ISO_Group_Lock),
+    ("ISO_Next_Group", XK_ISO_Next_Group),
+    ("ISO_Next_Group_Lock", XK_ISO_Next_Group_Lock),
+    ("ISO_Prev_Group", XK_ISO_Prev_Group),
+    ("ISO_Prev_Group_Lock", XK_ISO_Prev_Group_Lock),
+    ("ISO_First_Group", XK_ISO_First_Group),
+    ("ISO_First_Group_Lock", XK_ISO_First_Group_Lock),
+    ("ISO_Last_Group", XK_ISO_Last_Group),
+    ("ISO_Last_Group_Lock", XK_ISO_Last_Group_Lock),
+    ("ISO_Left_Tab", XK_ISO_Left_Tab),
+    ("ISO_Move_Line_Up", XK_ISO_Move_Line_Up),
+    ("ISO_Move_Line_Down", XK_ISO_Move_Line_Down),
+    ("ISO_Partial_Line_Up", XK_ISO_Partial_Line_Up),
+    ("ISO_Partial_Line_Down", XK_ISO_Partial_Line_Down),
+    ("ISO_Partial_Space_Left", XK_ISO_Partial_Space_Left),
+    ("ISO_Partial_Space_Right", XK_ISO_Partial_Space_Right),
+    ("ISO_Set_Margin_Left", XK_ISO_Set_Margin_Left),
+    ("ISO_Set_Margin_Right", XK_ISO_Set_Margin_Right),
+    ("ISO_Release_Margin_Left", XK_ISO_Release_Margin_Left),
+    ("ISO_Release_Margin_Right", XK_ISO_Release_Margin_Right),
+    ("ISO_Release_Both_Margins", XK_ISO_Release_Both_Margins),
+    ("ISO_Fast_Cursor_Left", XK_ISO_Fast_Cursor_Left),
+    ("ISO_Fast_Cursor_Right", XK_ISO_Fast_Cursor_Right),
+    ("ISO_Fast_Cursor_Up", XK_ISO_Fast_Cursor_Up),
+    ("ISO_Fast_Cursor_Down", XK_ISO_Fast_Cursor_Down),
+    ("ISO_Continuous_Underline", XK_ISO_Continuous_Underline),
+    (
+        "ISO_Discontinuous_Underline",
+        XK_ISO_Discontinuous_Underline,
+    ),
+    ("ISO_Emphasize", XK_ISO_Emphasize),
+    ("ISO_Center_Object", XK_ISO_Center_Object),
+    ("ISO_Enter", XK_ISO_Enter),
+    ("dead_grave", XK_dead_grave),
+    ("dead_acute", XK_dead_acute),
+    ("dead_circumflex", XK_dead_circumflex),
+    ("dead_tilde", XK_dead_tilde),
+    ("dead_perispomeni", XK_dead_perispomeni),
+    ("dead_macron", XK_dead_macron),
+    ("dead_breve", XK_dead_breve),
+    ("dead_abovedot", XK_dead_abovedot),
+    ("dead_diaeresis", XK_dead_diaeresis),
+    ("dead_abovering", XK_dead_abovering),
+    ("dead_doubleacute", XK_dead_doubleacute),
+    ("dead_caron", XK_dead_caron),
+    ("dead_cedilla", XK_dead_cedilla),
+    ("dead_ogonek", XK_dead_ogonek),
+    ("dead_iota", XK_dead_iota),
+    ("dead_voiced_sound", XK_dead_voiced_sound),
+    ("dead_semivoiced_sound", XK_dead_semivoiced_sound),
+    ("dead_belowdot", XK_dead_belowdot),
+    ("dead_hook", XK_dead_hook),
+    ("dead_horn", XK_dead_horn),
+    ("dead_stroke", XK_dead_stroke),
+    ("dead_abovecomma", XK_dead_abovecomma),
+    ("dead_psili", XK_dead_psili),
+    ("dead_abovereversedcomma", XK_dead_abovereversedcomma),
+    ("dead_dasia", XK_dead_dasia),
+    ("dead_doublegrave", XK_dead_doublegrave),
+    ("dead_belowring", XK_dead_belowring),
+    ("dead_belowmacron", XK_dead_belowmacron),
+    ("dead_belowcircumflex", XK_dead_belowcircumflex),
+    ("dead_belowtilde", XK_dead_belowtilde),
+    ("dead_belowbreve", XK_dead_belowbreve),
+    ("dead_belowdiaeresis", XK_dead_belowdiaeresis),
+    ("dead_invertedbreve", XK_dead_invertedbreve),
+    ("dead_belowcomma", XK_dead_belowcomma),
+    ("dead_currency", XK_dead_currency),
+    ("dead_lowline", XK_dead_lowline),
+    ("dead_aboveverticalline", XK_dead_aboveverticalline),
+    ("dead_belowverticalline", XK_dead_belowverticalline),
+    ("dead_longsolidusoverlay", XK_dead_longsolidusoverlay),
+    ("dead_a", XK_dead_a),
+    ("dead_A", XK_dead_A),
+    ("dead_e", XK_dead_e),
+    ("dead_E", XK_dead_E),
+    ("dead_i", XK_dead_i),
+    ("dead_I", XK_dead_I),
+    ("dead_o", XK_dead_o),
+    ("dead_O", XK_dead_O),
+    ("dead_u", XK_dead_u),
+    ("dead_U", XK_dead_U),
+    ("dead_small_schwa", XK_dead_small_schwa),
+    ("dead_capital_schwa", XK_dead_capital_schwa),
+    ("dead_greek", XK_dead_greek),
+    ("First_Virtual_Screen", XK_First_Virtual_Screen),
+    ("Prev_Virtual_Screen", XK_Prev_Virtual_Screen),
+    ("Next_Virtual_Screen", XK_Next_Virtual_Screen),
+    ("Last_Virtual_Screen", XK_Last_Virtual_Screen),
+    ("Terminate_Server", XK_Terminate_Server),
+    ("AccessX_Enable", XK_AccessX_Enable),
+    ("AccessX_Feedback_Enable", XK_AccessX_Feedback_Enable),
+    ("RepeatKeys_Enable", XK_RepeatKeys_Enable),
+    ("SlowKeys_Enable", XK_SlowKeys_Enable),
+    ("BounceKeys_Enable", XK_BounceKeys_Enable),
+    ("StickyKeys_Enable", XK_StickyKeys_Enable),
+    ("MouseKeys_Enable", XK_MouseKeys_Enable),
+    ("MouseKeys_Accel_Enable", XK_MouseKeys_Accel_Enable),
+    ("Overlay1_Enable", XK_Overlay1_Enable),
+    ("Overlay2_Enable", XK_Overlay2_Enable),
+    ("AudibleBell_Enable", XK_AudibleBell_Enable),
+    ("Pointer_Left", XK_Pointer_Left),
+    ("Pointer_Right", XK_Pointer_Right),
+    ("Pointer_Up", XK_Pointer_Up),
+    ("Pointer_Down", XK_Pointer_Down),
+    ("Pointer_UpLeft", XK_Pointer_UpLeft),
+    ("Pointer_UpRight", XK_Pointer_UpRight),
+    ("Pointer_DownLeft", XK_Pointer_DownLeft),
+    ("Pointer_DownRight", XK_Pointer_DownRight),
+    ("Pointer_Button_Dflt", XK_Pointer_Button_Dflt),
+    ("Pointer_Button1", XK_Pointer_Button1),
+    ("Pointer_Button2", XK_Pointer_Button2),
+    ("Pointer_Button3", XK_Pointer_Button3),
+    ("Pointer_Button4", XK_Pointer_Button4),
+    ("Pointer_Button5", XK_Pointer_Button5),
+    ("Pointer_DblClick_Dflt", XK_Pointer_DblClick_Dflt),
+    ("Pointer_DblClick1", XK_Pointer_DblClick1),
+    ("Pointer_DblClick2", XK_Pointer_DblClick2),
+    ("Pointer_DblClick3", XK_Pointer_DblClick3),
+    ("Pointer_DblClick4", XK_Pointer_DblClick4),
+    ("Pointer_DblClick5", XK_Pointer_DblClick5),
+    ("Pointer_Drag_Dflt", XK_Pointer_Drag_Dflt),
+    ("Pointer_Drag1", XK_Pointer_Drag1),
+    ("Pointer_Drag2", XK_Pointer_Drag2),
+    ("Pointer_Drag3", XK_Pointer_Drag3),
+    ("Pointer_Drag4", XK_Pointer_Drag4),
+    ("Pointer_Drag5", XK_Pointer_Drag5),
+    ("Pointer_EnableKeys", XK_Pointer_EnableKeys),
+    ("Pointer_Accelerate", XK_Pointer_Accelerate),
+    ("Pointer_DfltBtnNext", XK_Pointer_DfltBtnNext),
+    ("Pointer_DfltBtnPrev", XK_Pointer_DfltBtnPrev),
+    ("ch", XK_ch),
+    ("Ch", XK_Ch),
+    ("CH", XK_CH),
+    ("c_h", XK_c_h),
+    ("C_h", XK_C_h),
+    ("C_H", XK_C_H),
+    ("3270_Duplicate", XK_3270_Duplicate),
+    ("3270_FieldMark", XK_3270_FieldMark),
+    ("3270_Right2", XK_3270_Right2),
+    ("3270_Left2", XK_3270_Left2),
+    ("3270_BackTab", XK_3270_BackTab),
+    ("3270_EraseEOF", XK_3270_EraseEOF),
+    ("3270_EraseInput", XK_3270_EraseInput),
+    ("3270_Reset", XK_3270_Reset),
+    ("3270_Quit", XK_3270_Quit),
+    ("3270_PA1", XK_3270_PA1),
+    ("3270_PA2", XK_3270_PA2),
+    ("3270_PA3", XK_3270_PA3),
+    ("3270_Test", XK_3270_Test),
+    ("3270_Attn", XK_3270_Attn),
+    ("3270_CursorBlink", XK_3270_CursorBlink),
+    ("3270_AltCursor", XK_3270_AltCursor),
+    ("3270_KeyClick", XK_3270_KeyClick),
+    ("3270_Jump", XK_3270_Jump),
+    ("3270_Ident", XK_3270_Ident),
+    ("3270_Rule", XK_3270_Rule),
+    ("3270_Copy", XK_3270_Copy),
+    ("3270_Play", XK_3270_Play),
+    ("3270_Setup", XK_3270_Setup),
+    ("3270_Record", XK_3270_Record),
+    ("3270_ChangeScreen", XK_3270_ChangeScreen),
+    ("3270_DeleteWord", XK_3270_DeleteWord),
+    ("3270_ExSelect", XK_3270_ExSelect),
+    ("3270_CursorSelect", XK_3270_CursorSelect),
+    ("3270_PrintScreen", XK_3270_PrintScreen),
+    ("3270_Enter", XK_3270_Enter),
+    ("space", XK_space),
+    ("exclam", XK_exclam),
+    ("quotedbl", XK_quotedbl),
+    ("numbersign", XK_numbersign),
+    ("dollar", XK_dollar),
+    ("percent", XK_percent),
+    ("ampersand", XK_ampersand),
+    ("apostrophe", XK_apostrophe),
+    ("quoteright", XK_quoteright),
+    ("parenleft", XK_parenleft),
+    ("parenright", XK_parenright),
+    ("asterisk", XK_asterisk),
+    ("plus", XK_plus),
+    ("comma", XK_comma),
+    ("minus", XK_minus),
+    ("period", XK_period),
+    ("slash", XK_slash),
+    ("0", XK_0),
+    ("1", XK_1),
+    ("2", XK_2),
+    ("3", XK_3),
+    ("4", XK_4),
+    ("5", XK_5),
+    ("6", XK_6),
+    ("7", XK_7),
+    ("8", XK_8),
+    ("9", XK_9),
+    ("colon", XK_colon),
+    ("semicolon", XK_semicolon),
+    ("less", XK_less),
+    ("equal", XK_equal),
+    ("greater", XK_greater),
+    ("question", XK_question),
+    ("at", XK_at),
+    ("A", XK_A),
+    ("B", XK_B),
+    ("C", XK_C),
+    ("D", XK_D),
+    ("E", XK_E),
+    ("F", XK_F),
+    ("G", XK_G),
+    ("H", XK_H),
+    ("I", XK_I),
+    ("J", XK_J),
+    ("K", XK_K),
+    ("L", XK_L),
+    ("M", XK_M),
+    ("N", XK_N),
+    ("O", XK_O),
+    ("P", XK_P),
+    ("Q", XK_Q),
+    ("R", XK_R),
+    ("S", XK_S),
+    ("T", XK_T),
+    ("U", XK_U),
+    ("V", XK_V),
+    ("W", XK_W),
+    ("X", XK_X),
+    ("Y", XK_Y),
+    ("Z", XK_Z),
+    ("bracketleft", XK_bracketleft),
+    ("backslash", XK_backslash),
+    ("bracketright", XK_bracketright),
+    ("asciicircum", XK_asciicircum),
+    ("underscore", XK_underscore),
+    ("grave", XK_grave),
+    ("quoteleft", XK_quoteleft),
+    ("a", XK_a),
+    ("b", XK_b),
+    ("c", XK_c),
+    ("d", XK_d),
+    ("e", XK_e),
+    ("f", XK_f),
+    ("g", XK_g),
+    ("h", XK_h),
+    ("i", XK_i),
+    ("j", XK_j),
+    ("k", XK_k),
+    ("l", XK_l),
+    ("m", XK_m),
+    ("n", XK_n),
+    ("o", XK_o),
+    ("p", XK_p),
+    ("q", XK_q),
+    ("r", XK_r),
+    ("s", XK_s),
+    ("t", XK_t),
+    ("u", XK_u),
+    ("v", XK_v),
+    ("w", XK_w),
+    ("x", XK_x),
+    ("y", XK_y),
+    ("z", XK_z),
+    ("braceleft", XK_braceleft),
+    ("bar", XK_bar),
+    ("braceright", XK_braceright),
+    ("asciitilde", XK_asciitilde),
+    ("nobreakspace", XK_nobreakspace),
+    ("exclamdown", XK_exclamdown),
+    ("cent", XK_cent),
+    ("sterling", XK_sterling),
+    ("currency", XK_currency),
+    ("yen", XK_yen),
+    ("brokenbar", XK_brokenbar),
+    ("section", XK_section),
+    ("diaeresis", XK_diaeresis),
+    ("copyright", XK_copyright),
+    ("ordfeminine", XK_ordfeminine),
+    ("guillemotleft", XK_guillemotleft),
+    ("notsign", XK_notsign),
+    ("hyphen", XK_hyphen),
+    ("registered", XK_registered),
+    ("macron", XK_macron),
+    ("degree", XK_degree),
+    ("plusminus", XK_plusminus),
+    ("twosuperior", XK_twosuperior),
+    ("threesuperior", XK_threesuperior),
+    ("acute", XK_acute),
+    ("mu", XK_mu),
+    ("paragraph", XK_paragraph),
+    ("periodcentered", XK_periodcentered),
+    ("cedilla", XK_cedilla),
+    ("onesuperior", XK_onesuperior),
+    ("masculine", XK_masculine),
+    ("guillemotright", XK_guillemotright),
+    ("onequarter", XK_onequarter),
+    ("onehalf", XK_onehalf),
+    ("threequarters", XK_threequarters),
+    ("questiondown", XK_questiondown),
+    ("Agrave", XK_Agrave),
+    ("Aacute", XK_Aacute),
+    ("Acircumflex", XK_Acircumflex),
+    ("Atilde", XK_Atilde),
+    ("Adiaeresis", XK_Adiaeresis),
+    ("Aring", XK_Aring),
+    ("AE", XK_AE),
+    ("Ccedilla", XK_Ccedilla),
+    ("Egrave", XK_Egrave),
+    ("Eacute", XK_Eacute),
+    ("Ecircumflex", XK_Ecircumflex),
+    ("Ediaeresis", XK_Ediaeresis),
+    ("Igrave", XK_Igrave),
+    ("Iacute", XK_Iacute),
+    ("Icircumflex", XK_Icircumflex),
+    ("Idiaeresis", XK_Idiaeresis),
+    ("ETH", XK_ETH),
+    ("Eth", XK_Eth),
+    ("Ntilde", XK_Ntilde),
+    ("Ograve", XK_Ograve),
+    ("Oacute", XK_Oacute),
+    ("Ocircumflex", XK_Ocircumflex),
+    ("Otilde", XK_Otilde),
+    ("Odiaeresis", XK_Odiaeresis),
+    ("multiply", XK_multiply),
+    ("Oslash", XK_Oslash),
+    ("Ooblique", XK_Ooblique),
+    ("Ugrave", XK_Ugrave),
+    ("Uacute", XK_Uacute),
+    ("Ucircumflex", XK_Ucircumflex),
+    ("Udiaeresis", XK_Udiaeresis),
+    ("Yacute", XK_Yacute),
+    ("THORN", XK_THORN),
+    ("Thorn", XK_Thorn),
+    ("ssharp", XK_ssharp),
+    ("agrave", XK_agrave),
+    ("aacute", XK_aacute),
+    ("acircumflex", XK_acircumflex),
+    ("atilde", XK_atilde),
+    ("adiaeresis", XK_adiaeresis),
+    ("aring", XK_aring),
+    ("ae", XK_ae),
+    ("ccedilla", XK_ccedilla),
+    ("egrave", XK_egrave),
+    ("eacute", XK_eacute),
+    ("ecircumflex", XK_ecircumflex),
+    ("ediaeresis", XK_ediaeresis),
+    ("igrave", XK_igrave),
+    ("iacute", XK_iacute),
+    ("icircumflex", XK_icircumflex),
+    ("idiaeresis", XK_idiaeresis),
+    ("eth", XK_eth),
+    ("ntilde", XK_ntilde),
+    ("ograve", XK_ograve),
+    ("oacute", XK_oacute),
+    ("ocircumflex", XK_ocircumflex),
+    ("otilde", XK_otilde),
+    ("odiaeresis", XK_odiaeresis),
+    ("division", XK_division),
+    ("oslash", XK_oslash),
+    ("ooblique", XK_ooblique),
+    ("ugrave", XK_ugrave),
+    ("uacute", XK_uacute),
+    ("ucircumflex", XK_ucircumflex),
+    ("udiaeresis", XK_udiaeresis),
+    ("yacute", XK_yacute),
+    ("thorn", XK_thorn),
+    ("ydiaeresis", XK_ydiaeresis),
+    ("Aogonek", XK_Aogonek),
+    ("breve", XK_breve),
+    ("Lstroke", XK_Lstroke),
+    ("Lcaron", XK_Lcaron),
+    ("Sacute", XK_Sacute),
+    ("Scaron", XK_Scaron),
+    ("Scedilla", XK_Scedilla),
+    ("Tcaron", XK_Tcaron),
+    ("Zacute", XK_Zacute),
+    ("Zcaron", XK_Zcaron),
+    ("Zabovedot", XK_Zabovedot),
+    ("aogonek", XK_aogonek),
+    ("ogonek", XK_ogonek),
+    ("lstroke", XK_lstroke),
+    ("lcaron", XK_lcaron),
+    ("sacute", XK_sacute),
+    ("caron", XK_caron),
+    ("scaron", XK_scaron),
+    ("scedilla", XK_scedilla),
+    ("tcaron", XK_tcaron),
+    ("zacute", XK_zacute),
+    ("doubleacute", XK_doubleacute),
+    ("zcaron", XK_zcaron),
+    ("zabovedot", XK_zabovedot),
+    ("Racute", XK_Racute),
+    ("Abreve", XK_Abreve),
+    ("Lacute", XK_Lacute),
+    ("Cacute", XK_Cacute),
+    ("Ccaron", XK_Ccaron),
+    ("Eogonek", XK_Eogonek),
+    ("Ecaron", XK_Ecaron),
+    ("Dcaron", XK_Dcaron),
+    ("Dstroke", XK_Dstroke),
+    ("Nacute", XK_Nacute),
+    ("Ncaron", XK_Ncaron),
+    ("Odoubleacute", XK_Odoubleacute),
+    ("Rcaron", XK_Rcaron),
+    ("Uring", XK_Uring),
+    ("Udoubleacute", XK_Udoubleacute),
+    ("Tcedilla", XK_Tcedilla),
+    ("racute", XK_racute),
+    ("abreve", XK_abreve),
+    ("lacute", XK_lacute),
+    ("cacute", XK_cacute),
+    ("ccaron", XK_ccaron),
+    ("eogonek", XK_eogonek),
+    ("ecaron", XK_ecaron),
+    ("dcaron", XK_dcaron),
+    ("dstroke", XK_dstroke),
+    ("nacute", XK_nacute),
+    ("ncaron", XK_ncaron),
+    ("odoubleacute", XK_odoubleacute),
+    ("rcaron", XK_rcaron),
+    ("uring", XK_uring),
+    ("udoubleacute", XK_udoubleacute),
+    ("tcedilla", XK_tcedilla),
+    ("abovedot", XK_abovedot),
+    ("Hstroke", XK_Hstroke),
+    ("Hcircumflex", XK_Hcircumflex),
+    ("Iabovedot", XK_Iabovedot),
+    ("Gbreve", XK_Gbreve),
+    ("Jcircumflex", XK_Jcircumflex),
+    ("hstroke", XK_hstroke),
+    ("hcircumflex", XK_hcircumflex),
+    ("idotless", XK_idotless),
+    ("gbreve", XK_gbreve),
+    ("jcircumflex", XK_jcircumflex),
+    ("Cabovedot", XK_Cabovedot),
+    ("Ccircumflex", XK_Ccircumflex),
+    ("Gabovedot", XK_Gabovedot),
+    ("Gcircumflex", XK_Gcircumflex),
+    ("Ubreve", XK_Ubreve),
+    ("Scircumflex", XK_Scircumflex),
+    ("cabovedot", XK_cabovedot),
+    ("ccircumflex", XK_ccircumflex),
+    ("gabovedot", XK_gabovedot),
+    ("gcircumflex", XK_gcircumflex),
+    ("ubreve", XK_ubreve),
+    ("scircumflex", XK_scircumflex),
+    ("kra", XK_kra),
+    ("kappa", XK_kappa),
+    ("Rcedilla", XK_Rcedilla),
+    ("Itilde", XK_Itilde),
+    ("Lcedilla", XK_Lcedilla),
+    ("Emacron", XK_Emacron),
+    ("Gcedilla", XK_Gcedilla),
+    ("Tslash", XK_Tslash),
+    ("rcedilla", XK_rcedilla),
+    ("itilde", XK_itilde),
+    ("lcedilla", XK_lcedilla),
+    ("emacron", XK_emacron),
+    ("gcedilla", XK_gcedilla),
+    ("tslash", XK_tslash),
+    ("ENG", XK_ENG),
+    ("eng", XK_eng),
+    ("Amacron", XK_Amacron),
+    ("Iogonek", XK_Iogonek),
+    ("Eabovedot", XK_Eabovedot),
+    ("Imacron", XK_Imacron),
+    ("Ncedilla", XK_Ncedilla),
+    ("Omacron", XK_Omacron),
+    ("Kcedilla", XK_Kcedilla),
+    ("Uogonek", XK_Uogonek),
+    ("Utilde", XK_Utilde),
+    ("Umacron", XK_Umacron),
+    ("amacron", XK_amacron),
+    ("iogonek", XK_iogonek),
+    ("eabovedot", XK_eabovedot),
+    ("imacron", XK_imacron),
+    ("ncedilla", XK_ncedilla),
+    ("omacron", XK_omacron),
+    ("kcedilla", XK_kcedilla),
+    ("uogonek", XK_uogonek),
+    ("utilde", XK_utilde),
+    ("umacron", XK_umacron),
+    ("Wcircumflex", XK_Wcircumflex),
+    ("wcircumflex", XK_wcircumflex),
+    ("Ycircumflex", XK_Ycircumflex),
+    ("ycircumflex", XK_ycircumflex),
+    ("Babovedot", XK_Babovedot),
+    ("babovedot", XK_babovedot),
+    ("Dabovedot", XK_Dabovedot),
+    ("dabovedot", XK_dabovedot),
+    ("Fabovedot", XK_Fabovedot),
+    ("fabovedot", XK_fabovedot),
+    ("Mabovedot", XK_Mabovedot),
+    ("mabovedot", XK_mabovedot),
+    ("Pabovedot", XK_Pabovedot),
+    ("pabovedot", XK_pabovedot),
+    ("Sabovedot", XK_Sabovedot),
+    ("sabovedot", XK_sabovedot),
+    ("Tabovedot", XK_Tabovedot),
+    ("tabovedot", XK_tabovedot),
+    ("Wgrave", XK_Wgrave),
+    ("wgrave", XK_wgrave),
+    ("Wacute", XK_Wacute),
+    ("wacute", XK_wacute),
+    ("Wdiaeresis", XK_Wdiaeresis),
+    ("wdiaeresis", XK_wdiaeresis),
+    ("Ygrave", XK_Ygrave),
+    ("ygrave", XK_ygrave),
+    ("OE", XK_OE),
+    ("oe", XK_oe),
+    ("Ydiaeresis", XK_Ydiaeresis),
+    ("overline", XK_overline),
+    ("kana_fullstop", XK_kana_fullstop),
+    ("kana_openingbracket", XK_kana_openingbracket),
+    ("kana_closingbracket", XK_kana_closingbracket),
+    ("kana_comma", XK_kana_comma),
+    ("kana_conjunctive", XK_kana_conjunctive),
+    ("kana_middledot", XK_kana_middledot),
+    ("kana_WO", XK_kana_WO),
+    ("kana_a", XK_kana_a),
+    ("kana_i", XK_kana_i),
+    ("kana_u", XK_kana_u),
+    ("kana_e", XK_kana_e),
+    ("kana_o", XK_kana_o),
+    ("kana_ya", XK_kana_ya),
+    ("kana_yu", XK_kana_yu),
+    ("kana_yo", XK_kana_yo),
+    ("kana_tsu", XK_kana_tsu),
+    ("kana_tu", XK_kana_tu),
+    ("prolongedsound", XK_prolongedsound),
+    ("kana_A", XK_kana_A),
+    ("kana_I", XK_kana_I),
+    ("kana_U", XK_kana_U),
+    ("kana_E", XK_kana_E),
+    ("kana_O", XK_kana_O),
+    ("kana_KA", XK_kana_KA),
+    ("kana_KI", XK_kana_KI),
+    ("kana_KU", XK_kana_KU),
+    ("kana_KE", XK_kana_KE),
+    ("kana_KO", XK_kana_KO),
+    ("kana_SA", XK_kana_SA),
+    ("kana_SHI", XK_kana_SHI),
+    ("kana_SU", XK_kana_SU),
+    ("kana_SE", XK_kana_SE),
+    ("kana_SO", XK_kana_SO),
+    ("kana_TA", XK_kana_TA),
+    ("kana_CHI", XK_kana_CHI),
+    ("kana_TI", XK_kana_TI),
+    ("kana_TSU", XK_kana_TSU),
+    ("kana_TU", XK_kana_TU),
+    ("kana_TE", XK_kana_TE),
+    ("kana_TO", XK_kana_TO),
+    ("kana_NA", XK_kana_NA),
+    ("kana_NI", XK_kana_NI),
+    ("kana_NU", XK_kana_NU),
+    ("kana_NE", XK_kana_NE),
+    ("kana_NO", XK_kana_NO),
+    ("kana_HA", XK_kana_HA),
+    ("kana_HI", XK_kana_HI),
+    ("kana_FU", XK_kana_FU),
+    ("kana_HU", XK_kana_HU),
+    ("kana_HE", XK_kana_HE),
+    ("kana_HO", XK_kana_HO),
+    ("kana_MA", XK_kana_MA),
+    ("kana_MI", XK_kana_MI),
+    ("kana_MU", XK_kana_MU),
+    ("kana_ME", XK_kana_ME),
+    ("kana_MO", XK_kana_MO),
+    ("kana_YA", XK_kana_YA),
+    ("kana_YU", XK_kana_YU),
+    ("kana_YO", XK_kana_YO),
+    ("kana_RA", XK_kana_RA),
+    ("kana_RI", XK_kana_RI),
+    ("kana_RU", XK_kana_RU),
+    ("kana_RE", XK_kana_RE),
+    ("kana_RO", XK_kana_RO),
+    ("kana_WA", XK_kana_WA),
+    ("kana_N", XK_kana_N),
+    ("voicedsound", XK_voicedsound),
+    ("semivoicedsound", XK_semivoicedsound),
+    ("kana_switch", XK_kana_switch),
+    ("Farsi_0", XK_Farsi_0),
+    ("Farsi_1", XK_Farsi_1),
+    ("Farsi_2", XK_Farsi_2),
+    ("Farsi_3", XK_Farsi_3),
+    ("Farsi_4", XK_Farsi_4),
+    ("Farsi_5", XK_Farsi_5),
+    ("Farsi_6", XK_Farsi_6),
+    ("Farsi_7", XK_Farsi_7),
+    ("Farsi_8", XK_Farsi_8),
+    ("Farsi_9", XK_Farsi_9),
+    ("Arabic_percent", XK_Arabic_percent),
+    ("Arabic_superscript_alef", XK_Arabic_superscript_alef),
+    ("Arabic_tteh", XK_Arabic_tteh),
+    ("Arabic_peh", XK_Arabic_peh),
+    ("Arabic_tcheh", XK_Arabic_tcheh),
+    ("Arabic_ddal", XK_Arabic_ddal),
+    ("Arabic_rreh", XK_Arabic_rreh),
+    ("Arabic_comma", XK_Arabic_comma),
+    ("Arabic_fullstop", XK_Arabic_fullstop),
+    ("Arabic_0", XK_Arabic_0),
+    ("Arabic_1", XK_Arabic_1),
+    ("Arabic_2", XK_Arabic_2),
+    ("Arabic_3", XK_Arabic_3),
+    ("Arabic_4", XK_Arabic_4),
+    ("Arabic_5", XK_Arabic_5),
+    ("Arabic_6", XK_Arabic_6),
+    ("Arabic_7", XK_Arabic_7),
+    ("Arabic_8", XK_Arabic_8),
+    ("Arabic_9", XK_Arabic_9),
+    ("Arabic_semicolon", XK_Arabic_semicolon),
+    ("Arabic_question_mark", XK_Arabic_question_mark),
+    ("Arabic_hamza", XK_Arabic_hamza),
+    ("Arabic_maddaonalef", XK_Arabic_maddaonalef),
+    ("Arabic_hamzaonalef", XK_Arabic_hamzaonalef),
+    ("Arabic_hamzaonwaw", XK_Arabic_hamzaonwaw),
+    ("Arabic_hamzaunderalef", XK_Arabic_hamzaunderalef),
+    ("Arabic_hamzaonyeh", XK_Arabic_hamzaonyeh),
+    ("Arabic_alef", XK_Arabic_alef),
+    ("Arabic_beh", XK_Arabic_beh),
+    ("Arabic_tehmarbuta", XK_Arabic_tehmarbuta),
+    ("Arabic_teh", XK_Arabic_teh),
+    ("Arabic_theh", XK_Arabic_theh),
+    ("Arabic_jeem", XK_Arabic_jeem),
+    ("Arabic_hah", XK_Arabic_hah),
+    ("Arabic_khah", XK_Arabic_khah),
+    ("Arabic_dal", XK_Arabic_dal),
+    ("Arabic_thal", XK_Arabic_thal),
+    ("Arabic_ra", XK_Arabic_ra),
+    ("Arabic_zain", XK_Arabic_zain),
+    ("Arabic_seen", XK_Arabic_seen),
+    ("Arabic_sheen", XK_Arabic_sheen),
+    ("Arabic_sad", XK_Arabic_sad),
+    ("Arabic_dad", XK_Arabic_dad),
+    ("Arabic_tah", XK_Arabic_tah),
+    ("Arabic_zah", XK_Arabic_zah),
+    ("Arabic_ain", XK_Arabic_ain),
+    ("Arabic_ghain", XK_Arabic_ghain),
+    ("Arabic_tatweel", XK_Arabic_tatweel),
+    ("Arabic_feh", XK_Arabic_feh),
+    ("Arabic_qaf", XK_Arabic_qaf),
+    ("Arabic_kaf", XK_Arabic_kaf),
+    ("Arabic_lam", XK_Arabic_lam),
+    ("Arabic_meem", XK_Arabic_meem),
+    ("Arabic_noon", XK_Arabic_noon),
+    ("Arabic_ha", XK_Arabic_ha),
+    ("Arabic_heh", XK_Arabic_heh),
+    ("Arabic_waw", XK_Arabic_waw),
+    ("Arabic_alefmaksura", XK_Arabic_alefmaksura),
+    ("Arabic_yeh", XK_Arabic_yeh),
+    ("Arabic_fathatan", XK_Arabic_fathatan),
+    ("Arabic_dammatan", XK_Arabic_dammatan),
+    ("Arabic_kasratan", XK_Arabic_kasratan),
+    ("Arabic_fatha", XK_Arabic_fatha),
+    ("Arabic_damma", XK_Arabic_damma),
+    ("Arabic_kasra", XK_Arabic_kasra),
+    ("Arabic_shadda", XK_Arabic_shadda),
+    ("Arabic_sukun", XK_Arabic_sukun),
+    ("Arabic_madda_above", XK_Arabic_madda_above),
+    ("Arabic_hamza_above", XK_Arabic_hamza_above),
+    ("Arabic_hamza_below", XK_Arabic_hamza_below),
+    ("Arabic_jeh", XK_Arabic_jeh),
+    ("Arabic_veh", XK_Arabic_veh),
+    ("Arabic_keheh", XK_Arabic_keheh),
+    ("Arabic_gaf", XK_Arabic_gaf),
+    ("Arabic_noon_ghunna", XK_Arabic_noon_ghunna),
+    ("Arabic_heh_doachashmee", XK_Arabic_heh_doachashmee),
+    ("Farsi_yeh", XK_Farsi_yeh),
+    ("Arabic_farsi_yeh", XK_Arabic_farsi_yeh),
+    ("Arabic_yeh_baree", XK_Arabic_yeh_baree),
+    ("Arabic_heh_goal", XK_Arabic_heh_goal),
+    ("Arabic_switch", XK_Arabic_switch),
+    ("Cyrillic_GHE_bar", XK_Cyrillic_GHE_bar),
+    ("Cyrillic_ghe_bar", XK_Cyrillic_ghe_bar),
+    ("Cyrillic_ZHE_descender", XK_Cyrillic_ZHE_descender),
+    ("Cyrillic_zhe_descender", XK_Cyrillic_zhe_descender),
+    ("Cyrillic_KA_descender", XK_Cyrillic_KA_descender),
+    ("Cyrillic_ka_descender", XK_Cyrillic_ka_descender),
+    ("Cyrillic_KA_vertstroke", XK_Cyrillic_KA_vertstroke),
+    ("Cyrillic_ka_vertstroke", XK_Cyrillic_ka_vertstroke),
+    ("Cyrillic_EN_descender", XK_Cyrillic_EN_descender),
+    ("Cyrillic_en_descender", XK_Cyrillic_en_descender),
+    ("Cyrillic_U_straight", XK_Cyrillic_U_straight),
+    ("Cyrillic_u_straight", XK_Cyrillic_u_straight),
+    ("Cyrillic_U_straight_bar", XK_Cyrillic_U_straight_bar),
+    ("Cyrillic_u_straight_bar", XK_Cyrillic_u_straight_bar),
+    ("Cyrillic_HA_descender", XK_Cyrillic_HA_descender),
+    ("Cyrillic_ha_descender", XK_Cyrillic_ha_descender),
+    ("Cyrillic_CHE_descender", XK_Cyrillic_CHE_descender),
+    ("Cyrillic_che_descender", XK_Cyrillic_che_descender),
+    ("Cyrillic_CHE_vertstroke", XK_Cyrillic_CHE_vertstroke),
+    ("Cyrillic_che_vertstroke", XK_Cyrillic_che_vertstroke),
+    ("Cyrillic_SHHA", XK_Cyrillic_SHHA),
+    ("Cyrillic_shha", XK_Cyrillic_shha),
+    ("Cyrillic_SCHWA", XK_Cyrillic_SCHWA),
+    ("Cyrillic_schwa", XK_Cyrillic_schwa),
+    ("Cyrillic_I_macron", XK_Cyrillic_I_macron),
+    ("Cyrillic_i_macron", XK_Cyrillic_i_macron),
+    ("Cyrillic_O_bar", XK_Cyrillic_O_bar),
+    ("Cyrillic_o_bar", XK_Cyrillic_o_bar),
+    ("Cyrillic_U_macron", XK_Cyrillic_U_macron),
+    ("Cyrillic_u_macron", XK_Cyrillic_u_macron),
+    ("Serbian_dje", XK_Serbian_dje),
+    ("Macedonia_gje", XK_Macedonia_gje),
+    ("Cyrillic_io", XK_Cyrillic_io),
+    ("Ukrainian_ie", XK_Ukrainian_ie),
+    ("Ukranian_je", XK_Ukranian_je),
+    ("Macedonia_dse", XK_Macedonia_dse),
+    ("Ukrainian_i", XK_Ukrainian_i),
+    ("Ukranian_i", XK_Ukranian_i),
+    ("Ukrainian_yi", XK_Ukrainian_yi),
+    ("Ukranian_yi", XK_Ukranian_yi),
+    ("Cyrillic_je", XK_Cyrillic_je),
+    ("Serbian_je", XK_Serbian_je),
+    ("Cyrillic_lje", XK_Cyrillic_lje),
+    ("Serbian_lje", XK_Serbian_lje),
+    ("Cyrillic_nje", XK_Cyrillic_nje),
+    ("Serbian_nje", XK_Serbian_nje),
+    ("Serbian_tshe", XK_Serbian_tshe),
+    ("Macedonia_kje", XK_Macedonia_kje),
+    ("Ukrainian_ghe_with_upturn", XK_Ukrainian_ghe_with_upturn),
+    ("Byelorussian_shortu", XK_Byelorussian_shortu),
+    ("Cyrillic_dzhe", XK_Cyrillic_dzhe),
+    ("Serbian_dze", XK_Serbian_dze),
+    ("numerosign", XK_numerosign),
+    ("Serbian_DJE", XK_Serbian_DJE),
+    ("Macedonia_GJE", XK_Macedonia_GJE),
+    ("Cyrillic_IO", XK_Cyrillic_IO),
+    ("Ukrainian_IE", XK_Ukrainian_IE),
+    ("Ukranian_JE", XK_Ukranian_JE),
+    ("Macedonia_DSE", XK_Macedonia_DSE),
+    ("Ukrainian_I", XK_Ukrainian_I),
+    ("Ukranian_I", XK_Ukranian_I),
+    ("Ukrainian_YI", XK_Ukrainian_YI),
+    ("Ukranian_YI", XK_Ukranian_YI),
+    ("Cyrillic_JE", XK_Cyrillic_JE),
+    ("Serbian_JE", XK_Serbian_JE),
+    ("Cyrillic_LJE", XK_Cyrillic_LJE),
+    ("Serbian_LJE", XK_Serbian_LJE),
+    ("Cyrillic_NJE", XK_Cyrillic_NJE),
+    ("Serbian_NJE", XK_Serbian_NJE),
+    ("Serbian_TSHE", XK_Serbian_TSHE),
+    ("Macedonia_KJE", XK_Macedonia_KJE),
+    ("Ukrainian_GHE_WITH_UPTURN", XK_Ukrainian_GHE_WITH_UPTURN),
+    ("Byelorussian_SHORTU", XK_Byelorussian_SHORTU),
+    ("Cyrillic_DZHE", XK_Cyrillic_DZHE),
+    ("Serbian_DZE", XK_Serbian_DZE),
+    ("Cyrillic_yu", XK_Cyrillic_yu),
+    ("Cyrillic_a", XK_Cyrillic_a),
+    ("Cyrillic_be", XK_Cyrillic_be),
+    ("Cyrillic_tse", XK_Cyrillic_tse),
+    ("Cyrillic_de", XK_Cyrillic_de),
+    ("Cyrillic_ie", XK_Cyrillic_ie),
+    ("Cyrillic_ef", XK_Cyrillic_ef),
+    ("Cyrillic_ghe", XK_Cyrillic_ghe),
+    ("Cyrillic_ha", XK_Cyrillic_ha),
+    ("Cyrillic_i", XK_Cyrillic_i),
+    ("Cyrillic_shorti", XK_Cyrillic_shorti),
+    ("Cyrillic_ka", XK_Cyrillic_ka),
+    ("Cyrillic_el", XK_Cyrillic_el),
+    ("Cyrillic_em", XK_Cyrillic_em),
+    ("Cyrillic_en", XK_Cyrillic_en),
+    ("Cyrillic_o", XK_Cyrillic_o),
+    ("Cyrillic_pe", XK_Cyrillic_pe),
+    ("Cyrillic_ya", XK_Cyrillic_ya),
+    ("Cyrillic_er", XK_Cyrillic_er),
+    ("Cyrillic_es", XK_Cyrillic_es),
+    ("Cyrillic_te", XK_Cyrillic_te),
+    ("Cyrillic_u", XK_Cyrillic_u),
+    ("Cyrillic_zhe", XK_Cyrillic_zhe),
+    ("Cyrillic_ve", XK_Cyrillic_ve),
+    ("Cyrillic_softsign", XK_Cyrillic_softsign),
+    ("Cyrillic_yeru", XK_Cyrillic_yeru),
+    ("Cyrillic_ze", XK_Cyrillic_ze),
+    ("Cyrillic_sha", XK_Cyrillic_sha),
+    ("Cyrillic_e", XK_Cyrillic_e),
+    ("Cyrillic_shcha", XK_Cyrillic_shcha),
+    ("Cyrillic_che", XK_Cyrillic_che),
+    ("Cyrillic_hardsign", XK_Cyrillic_hardsign),
+    ("Cyrillic_YU", XK_Cyrillic_YU),
+    ("Cyrillic_A", XK_Cyrillic_A),
+    ("Cyrillic_BE", XK_Cyrillic_BE),
+    ("Cyrillic_TSE", XK_Cyrillic_TSE),
+    ("Cyrillic_DE", XK_Cyrillic_DE),
+    ("Cyrillic_IE", XK_Cyrillic_IE),
+    ("Cyrillic_EF", XK_Cyrillic_EF),
+    ("Cyrillic_GHE", XK_Cyrillic_GHE),
+    ("Cyrillic_HA", XK_Cyrillic_HA),
+    ("Cyrillic_I", XK_Cyrillic_I),
+    ("Cyrillic_SHORTI", XK_Cyrillic_SHORTI),
+    ("Cyrillic_KA", XK_Cyrillic_KA),
+    ("Cyrillic_EL", XK_Cyrillic_EL),
+    ("Cyrillic_EM", XK_Cyrillic_EM),
+    ("Cyrillic_EN", XK_Cyrillic_EN),
+    ("Cyrillic_O", XK_Cyrillic_O),
+    ("Cyrillic_PE", XK_Cyrillic_PE),
+    ("Cyrillic_YA", XK_Cyrillic_YA),
+    ("Cyrillic_ER", XK_Cyrillic_ER),
+    ("Cyrillic_ES", XK_Cyrillic_ES),
+    ("Cyrillic_TE", XK_Cyrillic_TE),
+    ("Cyrillic_U", XK_Cyrillic_U),
+    ("Cyrillic_ZHE", XK_Cyrillic_ZHE),
+    ("Cyrillic_VE", XK_Cyrillic_VE),
+    ("Cyrillic_SOFTSIGN", XK_Cyrillic_SOFTSIGN),
+    ("Cyrillic_YERU", XK_Cyrillic_YERU),
+    ("Cyrillic_ZE", XK_Cyrillic_ZE),
+    ("Cyrillic_SHA", XK_Cyrillic_SHA),
+    ("Cyrillic_E", XK_Cyrillic_E),
+    ("Cyrillic_SHCHA", XK_Cyrillic_SHCHA),
+    ("Cyrillic_CHE", XK_Cyrillic_CHE),
+    ("Cyrillic_HARDSIGN", XK_Cyrillic_HARDSIGN),
+    ("Greek_ALPHAaccent", XK_Greek_ALPHAaccent),
+    ("Greek_EPSILONaccent", XK_Greek_EPSILONaccent),
+    ("Greek_ETAaccent", XK_Greek_ETAaccent),
+    ("Greek_IOTAaccent", XK_Greek_IOTAaccent),
+    ("Greek_IOTAdieresis", XK_Greek_IOTAdieresis),
+    ("Greek_IOTAdiaeresis", XK_Greek_IOTAdiaeresis),
+    ("Greek_OMICRONaccent", XK_Greek_OMICRONaccent),
+    ("Greek_UPSILONaccent", XK_Greek_UPSILONaccent),
+    ("Greek_UPSILONdieresis", XK_Greek_UPSILONdieresis),
+    ("Greek_OMEGAaccent", XK_Greek_OMEGAaccent),
+    ("Greek_accentdieresis", XK_Greek_accentdieresis),
+    ("Greek_horizbar", XK_Greek_horizbar),
+    ("Greek_alphaaccent", XK_Greek_alphaaccent),
+    ("Greek_epsilonaccent", XK_Greek_epsilonaccent),
+    ("Greek_etaaccent", XK_Greek_etaaccent),
+    ("Greek_iotaaccent", XK_Greek_iotaaccent),
+    ("Greek_iotadieresis", XK_Greek_iotadieresis),
+    ("Greek_iotaaccentdieresis", XK_Greek_iotaaccentdieresis),
+    ("Greek_omicronaccent", XK_Greek_omicronaccent),
+    ("Greek_upsilonaccent", XK_Greek_upsilonaccent),
+    ("Greek_upsilondieresis", XK_Greek_upsilondieresis),
+    (
+        "Greek_upsilonaccentdieresis",
+        XK_Greek_upsilonaccentdieresis,
+    ),
+    ("Greek_omegaaccent", XK_Greek_omegaaccent),
+    ("Greek_ALPHA", XK_Greek_ALPHA),
+    ("Greek_BETA", XK_Greek_BETA),
+    ("Greek_GAMMA", XK_Greek_GAMMA),
+    ("Greek_DELTA", XK_Greek_DELTA),
+    ("Greek_EPSILON", XK_Greek_EPSILON),
+    ("Greek_ZETA", XK_Greek_ZETA),
+    ("Greek_ETA", XK_Greek_ETA),
+    ("Greek_THETA", XK_Greek_THETA),
+    ("Greek_IOTA", XK_Greek_IOTA),
+    ("Greek_KAPPA", XK_Greek_KAPPA),
+    ("Greek_LAMDA", XK_Greek_LAMDA),
+    ("Greek_LAMBDA", XK_Greek_LAMBDA),
+    ("Greek_MU", XK_Greek_MU),
+    ("Greek_NU", XK_Greek_NU),
+    ("Greek_XI", XK_Greek_XI),
+    ("Greek_OMICRON", XK_Greek_OMICRON),
+    ("Greek_PI", XK_Greek_PI),
+    ("Greek_RHO", XK_Greek_RHO),
+    ("Greek_SIGMA", XK_Greek_SIGMA),
+    ("Greek_TAU", XK_Greek_TAU),
+    ("Greek_UPSILON", XK_Greek_UPSILON),
+    ("Greek_PHI", XK_Greek_PHI),
+    ("Greek_CHI", XK_Greek_CHI),
+    ("Greek_PSI", XK_Greek_PSI),
+    ("Greek_OMEGA", XK_Greek_OMEGA),
+    ("Greek_alpha", XK_Greek_alpha),
+    ("Greek_beta", XK_Greek_beta),
+    ("Greek_gamma", XK_Greek_gamma),
+    ("Greek_delta", XK_Greek_delta),
+    ("Greek_epsilon", XK_Greek_epsilon),
+    ("Greek_zeta", XK_Greek_zeta),
+    ("Greek_eta", XK_Greek_eta),
+    ("Greek_theta", XK_Greek_theta),
+    ("Greek_iota", XK_Greek_iota),
+    ("Greek_kappa", XK_Greek_kappa),
+    ("Greek_lamda", XK_Greek_lamda),
+    ("Greek_lambda", XK_Greek_lambda),
+    ("Greek_mu", XK_Greek_mu),
+    ("Greek_nu", XK_Greek_nu),
+    ("Greek_xi", XK_Greek_xi),
+    ("Greek_omicron", XK_Greek_omicron),
+    ("Greek_pi", XK_Greek_pi),
+    ("Greek_rho", XK_Greek_rho),
+    ("Greek_sigma", XK_Greek_sigma),
+    ("Greek_finalsmallsigma", XK_Greek_finalsmallsigma),
+    ("Greek_tau", XK_Greek_tau),
+    ("Greek_upsilon", XK_Greek_upsilon),
+    ("Greek_phi", XK_Greek_phi),
+    ("Greek_chi", XK_Greek_chi),
+    ("Greek_psi", XK_Greek_psi),
+    ("Greek_omega", XK_Greek_omega),
+    ("Greek_switch", XK_Greek_switch),
+    ("leftradical", XK_leftradical),
+    ("topleftradical", XK_topleftradical),
+    ("horizconnector", XK_horizconnector),
+    ("topintegral", XK_topintegral),
+    ("botintegral", XK_botintegral),
+    ("vertconnector", XK_vertconnector),
+    ("topleftsqbracket", XK_topleftsqbracket),
+    ("botleftsqbracket", XK_botleftsqbracket),
+    ("toprightsqbracket", XK_toprightsqbracket),
+    ("botrightsqbracket", XK_botrightsqbracket),
+    ("topleftparens", XK_topleftparens),
+    ("botleftparens", XK_botleftparens),
+    ("toprightparens", XK_toprightparens),
+    ("botrightparens", XK_botrightparens),
+    ("leftmiddlecurlybrace", XK_leftmiddlecurlybrace),
+    ("rightmiddlecurlybrace", XK_rightmiddlecurlybrace),
+    ("topleftsummation", XK_topleftsummation),
+    ("botleftsummation", XK_botleftsummation),
+    ("topvertsummationconnector", XK_topvertsummationconnector),
+    ("botvertsummationconnector", XK_botvertsummationconnector),
+    ("toprightsummation", XK_toprightsummation),
+    ("botrightsummation", XK_botrightsummation),
+    ("rightmiddlesummation", XK_rightmiddlesummation),
+    ("lessthanequal", XK_lessthanequal),
+    ("notequal", XK_notequal),
+    ("greaterthanequal", XK_greaterthanequal),
+    ("integral", XK_integral),
+    ("therefore", XK_therefore),
+    ("variation", XK_variation),
+    ("infinity", XK_infinity),
+    ("nabla", XK_nabla),
+    ("approximate", XK_approximate),
+    ("similarequal", XK_similarequal),
+    ("ifonlyif", XK_ifonlyif),
+    ("implies", XK_implies),
+    ("identical", XK_identical),
+    ("radical", XK_radical),
+    ("includedin", XK_includedin),
+    ("includes", XK_includes),
+    ("intersection", XK_intersection),
+    ("union", XK_union),
+    ("logicaland", XK_logicaland),
+    ("logicalor", XK_logicalor),
+    ("partialderivative", XK_partialderivative),
+    ("function", XK_function),
+    ("leftarrow", XK_leftarrow),
+    ("uparrow", XK_uparrow),
+    ("rightarrow", XK_rightarrow),
+    ("downarrow", XK_downarrow),
+    ("blank", XK_blank),
+    ("soliddiamond", XK_soliddiamond),
+    ("checkerboard", XK_checkerboard),
+    ("ht", XK_ht),
+    ("ff", XK_ff),
+    ("cr", XK_cr),
+    ("lf", XK_lf),
+    ("nl", XK_nl),
+    ("vt", XK_vt),
+    ("lowrightcorner", XK_lowrightcorner),
+    ("uprightcorner", XK_uprightcorner),
+    ("upleftcorner", XK_upleftcorner),
+    ("lowleftcorner", XK_lowleftcorner),
+    ("crossinglines", XK_crossinglines),
+    ("horizlinescan1", XK_horizlinescan1),
+    ("horizlinescan3", XK_horizlinescan3),
+    ("horizlinescan5", XK_horizlinescan5),
+    ("horizlinescan7", XK_horizlinescan7),
+    ("horizlinescan9", XK_horizlinescan9),
+    ("leftt", XK_leftt),
+    ("rightt", XK_rightt),
+    ("bott", XK_bott),
+    ("topt", XK_topt),
+    ("vertbar", XK_vertbar),
+    ("emspace", XK_emspace),
+    ("enspace", XK_enspace),
+    ("em3space", XK_em3space),
+    ("em4space", XK_em4space),
+    ("digitspace", XK_digitspace),
+    ("punctspace", XK_punctspace),
+    ("thinspace", XK_thinspace),
+    ("hairspace", XK_hairspace),
+    ("emdash", XK_emdash),
+    ("endash", XK_endash),
+    ("signifblank", XK_signifblank),
+    ("ellipsis", XK_ellipsis),
+    ("doubbaselinedot", XK_doubbaselinedot),
+    ("onethird", XK_onethird),
+    ("twothirds", XK_twothirds),
+    ("onefifth", XK_onefifth),
+    ("twofifths", XK_twofifths),
+    ("threefifths", XK_threefifths),
+    ("fourfifths", XK_fourfifths),
+    ("onesixth", XK_onesixth),
+    ("fivesixths", XK_fivesixths),
+    ("careof", XK_careof),
+    ("figdash", XK_figdash),
+    ("leftanglebracket", XK_leftanglebracket),
+    ("decimalpoint", XK_decimalpoint),
+    ("rightanglebracket", XK_rightanglebracket),
+    ("marker", XK_marker),
+    ("oneeighth", XK_oneeighth),
+    ("threeeighths", XK_threeeighths),
+    ("fiveeighths", XK_fiveeighths),
+    ("seveneighths", XK_seveneighths),
+    ("trademark", XK_trademark),
+    ("signaturemark", XK_signaturemark),
+    ("trademarkincircle", XK_trademarkincircle),
+    ("leftopentriangle", XK_leftopentriangle),
+    ("rightopentriangle", XK_rightopentriangle),
+    ("emopencircle", XK_emopencircle),
+    ("emopenrectangle", XK_emopenrectangle),
+    ("leftsinglequotemark", XK_leftsinglequotemark),
+    ("rightsinglequotemark", XK_rightsinglequotemark),
+    ("leftdoublequotemark", XK_leftdoublequotemark),
+    ("rightdoublequotemark", XK_rightdoublequotemark),
+    ("prescription", XK_prescription),
+    ("permille", XK_permille),
+    ("minutes", XK_minutes),
+    ("seconds", XK_seconds),
+    ("latincross", XK_latincross),
+    ("hexagram", XK_hexagram),
+    ("filledrectbullet", XK_filledrectbullet),
+    ("filledlefttribullet", XK_filledlefttribullet),
+    ("filledrighttribullet", XK_filledrighttribullet),
+    ("emfilledcircle", XK_emfilledcircle),
+    ("emfilledrect", XK_emfilledrect),
+    ("enopencircbullet", XK_enopencircbullet),
+    ("enopensquarebullet", XK_enopensquarebullet),
+    ("openrectbullet", XK_openrectbullet),
+    ("opentribulletup", XK_opentribulletup),
+    ("opentribulletdown", XK_opentribulletdown),
+    ("openstar", XK_openstar),
+    ("enfilledcircbullet", XK_enfilledcircbullet),
+    ("enfilledsqbullet", XK_enfilledsqbullet),
+    ("filledtribulletup", XK_filledtribulletup),
+    ("filledtribulletdown", XK_filledtribulletdown),
+    ("leftpointer", XK_leftpointer),
+    ("rightpointer", XK_rightpointer),
+    ("club", XK_club),
+    ("diamond", XK_diamond),
+    ("heart", XK_heart),
+    ("maltesecross", XK_maltesecross),
+    ("dagger", XK_dagger),
+    ("doubledagger", XK_doubledagger),
+    ("checkmark", XK_checkmark),
+    ("ballotcross", XK_ballotcross),
+    ("musicalsharp", XK_musicalsharp),
+    ("musicalflat", XK_musicalflat),
+    ("malesymbol", XK_malesymbol),
+    ("femalesymbol", XK_femalesymbol),
+    ("telephone", XK_telephone),
+    ("telephonerecorder", XK_telephonerecorder),
+    ("phonographcopyright", XK_phonographcopyright),
+    ("caret", XK_caret),
+    ("singlelowquotemark", XK_singlelowquotemark),
+    ("doublelowquotemark", XK_doublelowquotemark),
+    ("cursor", XK_cursor),
+    ("leftcaret", XK_leftcaret),
+    ("rightcaret", XK_rightcaret),
+    ("downcaret", XK_downcaret),
+    ("upcaret", XK_upcaret),
+    ("overbar", XK_overbar),
+    ("downtack", XK_downtack),
+    ("upshoe", XK_upshoe),
+    ("downstile", XK_downstile),
+    ("underbar", XK_underbar),
+    ("jot", XK_jot),
+    ("quad", XK_quad),
+    ("uptack", XK_uptack),
+    ("circle", XK_circle),
+    ("upstile", XK_upstile),
+    ("downshoe", XK_downshoe),
+    ("rightshoe", XK_rightshoe),
+    ("leftshoe", XK_leftshoe),
+    ("lefttack", XK_lefttack),
+    ("righttack", XK_righttack),
+    ("hebrew_doublelowline", XK_hebrew_doublelowline),
+    ("hebrew_aleph", XK_hebrew_aleph),
+    ("hebrew_bet", XK_hebrew_bet),
+    ("hebrew_beth", XK_hebrew_beth),
+    ("hebrew_gimel", XK_hebrew_gimel),
+    ("hebrew_gimmel", XK_hebrew_gimmel),
+    ("hebrew_dalet", XK_hebrew_dalet),
+    ("hebrew_daleth", XK_hebrew_daleth),
+    ("hebrew_he", XK_hebrew_he),
+    ("hebrew_waw", XK_hebrew_waw),
+    ("hebrew_zain", XK_hebrew_zain),
+    ("hebrew_zayin", XK_hebrew_zayin),
+    ("hebrew_chet", XK_hebrew_chet),
+    ("hebrew_het", XK_hebrew_het),
+    ("hebrew_tet", XK_hebrew_tet),
+    ("hebrew_teth", XK_hebrew_teth),
+    ("hebrew_yod", XK_hebrew_yod),
+    ("hebrew_finalkaph", XK_hebrew_finalkaph),
+    ("hebrew_kaph", XK_hebrew_kaph),
+    ("hebrew_lamed", XK_hebrew_lamed),
+    ("hebrew_finalmem", XK_hebrew_finalmem),
+    ("hebrew_mem", XK_hebrew_mem),
+    ("hebrew_finalnun", XK_hebrew_finalnun),
+    ("hebrew_nun", XK_hebrew_nun),
+    ("hebrew_samech", XK_hebrew_samech),
+    ("hebrew_samekh", XK_hebrew_samekh),
+    ("hebrew_ayin", XK_hebrew_ayin),
+    ("hebrew_finalpe", XK_hebrew_finalpe),
+    ("hebrew_pe", XK_hebrew_pe),
+    ("hebrew_finalzade", XK_hebrew_finalzade),
+    ("hebrew_finalzadi", XK_hebrew_finalzadi),
+    ("hebrew_zade", XK_hebrew_zade),
+    ("hebrew_zadi", XK_hebrew_zadi),
+    ("hebrew_qoph", XK_hebrew_qoph),
+    ("hebrew_kuf", XK_hebrew_kuf),
+    ("hebrew_resh", XK_hebrew_resh),
+    ("hebrew_shin", XK_hebrew_shin),
+    ("hebrew_taw", XK_hebrew_taw),
+    ("hebrew_taf", XK_hebrew_taf),
+    ("Hebrew_switch", XK_Hebrew_switch),
+    ("Thai_kokai", XK_Thai_kokai),
+    ("Thai_khokhai", XK_Thai_khokhai),
+    ("Thai_khokhuat", XK_Thai_khokhuat),
+    ("Thai_khokhwai", XK_Thai_khokhwai),
+    ("Thai_khokhon", XK_Thai_khokhon),
+    ("Thai_khorakhang", XK_Thai_khorakhang),
+    ("Thai_ngongu", XK_Thai_ngongu),
+    ("Thai_chochan", XK_Thai_chochan),
+    ("Thai_choching", XK_Thai_choching),
+    ("Thai_chochang", XK_Thai_chochang),
+    ("Thai_soso", XK_Thai_soso),
+    ("Thai_chochoe", XK_Thai_chochoe),
+    ("Thai_yoying", XK_Thai_yoying),
+    ("Thai_dochada", XK_Thai_dochada),
+    ("Thai_topatak", XK_Thai_topatak),
+    ("Thai_thothan", XK_Thai_thothan),
+    ("Thai_thonangmontho", XK_Thai_thonangmontho),
+    ("Thai_thophuthao", XK_Thai_thophuthao),
+    ("Thai_nonen", XK_Thai_nonen),
+    ("Thai_dodek", XK_Thai_dodek),
+    ("Thai_totao", XK_Thai_totao),
+    ("Thai_thothung", XK_Thai_thothung),
+    ("Thai_thothahan", XK_Thai_thothahan),
+    ("Thai_thothong", XK_Thai_thothong),
+    ("Thai_nonu", XK_Thai_nonu),
+    ("Thai_bobaimai", XK_Thai_bobaimai),
+    ("Thai_popla", XK_Thai_popla),
+    ("Thai_phophung", XK_Thai_phophung),
+    ("Thai_fofa", XK_Thai_fofa),
+    ("Thai_phophan", XK_Thai_phophan),
+    ("Thai_fofan", XK_Thai_fofan),
+    ("Thai_phosamphao", XK_Thai_phosamphao),
+    ("Thai_moma", XK_Thai_moma),
+    ("Thai_yoyak", XK_Thai_yoyak),
+    ("Thai_rorua", XK_Thai_rorua),
+    ("Thai_ru", XK_Thai_ru),
+    ("Thai_loling", XK_Thai_loling),
+    ("Thai_lu", XK_Thai_lu),
+    ("Thai_wowaen", XK_Thai_wowaen),
+    ("Thai_sosala", XK_Thai_sosala),
+    ("Thai_sorusi", XK_Thai_sorusi),
+    ("Thai_sosua", XK_Thai_sosua),
+    ("Thai_hohip", XK_Thai_hohip),
+    ("Thai_lochula", XK_Thai_lochula),
+    ("Thai_oang", XK_Thai_oang),
+    ("Thai_honokhuk", XK_Thai_honokhuk),
+    ("Thai_paiyannoi", XK_Thai_paiyannoi),
+    ("Thai_saraa", XK_Thai_saraa),
+    ("Thai_maihanakat", XK_Thai_maihanakat),
+    ("Thai_saraaa", XK_Thai_saraaa),
+    ("Thai_saraam", XK_Thai_saraam),
+    ("Thai_sarai", XK_Thai_sarai),
+    ("Thai_saraii", XK_Thai_saraii),
+    ("Thai_saraue", XK_Thai_saraue),
+    ("Thai_sarauee", XK_Thai_sarauee),
+    ("Thai_sarau", XK_Thai_sarau),
+    ("Thai_sarauu", XK_Thai_sarauu),
+    ("Thai_phinthu", XK_Thai_phinthu),
+    ("Thai_maihanakat_maitho", XK_Thai_maihanakat_maitho),
+    ("Thai_baht", XK_Thai_baht),
+    ("Thai_sarae", XK_Thai_sarae),
+    ("Thai_saraae", XK_Thai_saraae),
+    ("Thai_sarao", XK_Thai_sarao),
+    ("Thai_saraaimaimuan", XK_Thai_saraaimaimuan),
+    ("Thai_saraaimaimalai", XK_Thai_saraaimaimalai),
+    ("Thai_lakkhangyao", XK_Thai_lakkhangyao),
+    ("Thai_maiyamok", XK_Thai_maiyamok),
+    ("Thai_maitaikhu", XK_Thai_maitaikhu),
+    ("Thai_maiek", XK_Thai_maiek),
+    ("Thai_maitho", XK_Thai_maitho),
+    ("Thai_maitri", XK_Thai_maitri),
+    ("Thai_maichattawa", XK_Thai_maichattawa),
+    ("Thai_thanthakhat", XK_Thai_thanthakhat),
+    ("Thai_nikhahit", XK_Thai_nikhahit),
+    ("Thai_leksun", XK_Thai_leksun),
+    ("Thai_leknung", XK_Thai_leknung),
+    ("Thai_leksong", XK_Thai_leksong),
+    ("Thai_leksam", XK_Thai_leksam),
+    ("Thai_leksi", XK_Thai_leksi),
+    ("Thai_lekha", XK_Thai_lekha),
+    ("Thai_lekhok", XK_Thai_lekhok),
+    ("Thai_lekchet", XK_Thai_lekchet),
+    ("Thai_lekpaet", XK_Thai_lekpaet),
+    ("Thai_lekkao", XK_Thai_lekkao),
+    ("Hangul", XK_Hangul),
+    ("Hangul_Start", XK_Hangul_Start),
+    ("Hangul_End", XK_Hangul_End),
+    ("Hangul_Hanja", XK_Hangul_Hanja),
+    ("Hangul_Jamo", XK_Hangul_Jamo),
+    ("Hangul_Romaja", XK_Hangul_Romaja),
+    ("Hangul_Codeinput", XK_Hangul_Codeinput),
+    ("Hangul_Jeonja", XK_Hangul_Jeonja),
+    ("Hangul_Banja", XK_Hangul_Banja),
+    ("Hangul_PreHanja", XK_Hangul_PreHanja),
+    ("Hangul_PostHanja", XK_Hangul_PostHanja),
+    ("Hangul_SingleCandidate", XK_Hangul_SingleCandidate),
+    ("Hangul_MultipleCandidate", XK_Hangul_MultipleCandidate),
+    ("Hangul_PreviousCandidate", XK_Hangul_PreviousCandidate),
+    ("Hangul_Special", XK_Hangul_Special),
+    ("Hangul_switch", XK_Hangul_switch),
+    ("Hangul_Kiyeog", XK_Hangul_Kiyeog),
+    ("Hangul_SsangKiyeog", XK_Hangul_SsangKiyeog),
+    ("Hangul_KiyeogSios", XK_Hangul_KiyeogSios),
+    ("Hangul_Nieun", XK_Hangul_Nieun),
+    ("Hangul_NieunJieuj", XK_Hangul_NieunJieuj),
+    ("Hangul_NieunHieuh", XK_Hangul_NieunHieuh),
+    ("Hangul_Dikeud", XK_Hangul_Dikeud),
+    ("Hangul_SsangDikeud", XK_Hangul_SsangDikeud),
+    ("Hangul_Rieul", XK_Hangul_Rieul),
+    ("Hangul_RieulKiyeog", XK_Hangul_RieulKiyeog),
+    ("Hangul_RieulMieum", XK_Hangul_RieulMieum),
+    ("Hangul_RieulPieub", XK_Hangul_RieulPieub),
+    ("Hangul_RieulSios", XK_Hangul_RieulSios),
+    ("Hangul_RieulTieut", XK_Hangul_RieulTieut),
+    ("Hangul_RieulPhieuf", XK_Hangul_RieulPhieuf),
+    ("Hangul_RieulHieuh", XK_Hangul_RieulHieuh),
+    ("Hangul_Mieum", XK_Hangul_Mieum),
+    ("Hangul_Pieub", XK_Hangul_Pieub),
+    ("Hangul_SsangPieub", XK_Hangul_SsangPieub),
+    ("Hangul_PieubSios", XK_Hangul_PieubSios),
+    ("Hangul_Sios", XK_Hangul_Sios),
+    ("Hangul_SsangSios", XK_Hangul_SsangSios),
+    ("Hangul_Ieung", XK_Hangul_Ieung),
+    ("Hangul_Jieuj", XK_Hangul_Jieuj),
+    ("Hangul_SsangJieuj", XK_Hangul_SsangJieuj),
+    ("Hangul_Cieuc", XK_Hangul_Cieuc),
+    ("Hangul_Khieuq", XK_Hangul_Khieuq),
+    ("Hangul_Tieut", XK_Hangul_Tieut),
+    ("Hangul_Phieuf", XK_Hangul_Phieuf),
+    ("Hangul_Hieuh", XK_Hangul_Hieuh),
+    ("Hangul_A", XK_Hangul_A),
+    ("Hangul_AE", XK_Hangul_AE),
+    ("Hangul_YA", XK_Hangul_YA),
+    ("Hangul_YAE", XK_Hangul_YAE),
+    ("Hangul_EO", XK_Hangul_EO),
+    ("Hangul_E", XK_Hangul_E),
+    ("Hangul_YEO", XK_Hangul_YEO),
+    ("Hangul_YE", XK_Hangul_YE),
+    ("Hangul_O", XK_Hangul_O),
+    ("Hangul_WA", XK_Hangul_WA),
+    ("Hangul_WAE", XK_Hangul_WAE),
+    ("Hangul_OE", XK_Hangul_OE),
+    ("Hangul_YO", XK_Hangul_YO),
+    ("Hangul_U", XK_Hangul_U),
+    ("Hangul_WEO", XK_Hangul_WEO),
+    ("Hangul_WE", XK_Hangul_WE),
+    ("Hangul_WI", XK_Hangul_WI),
+    ("Hangul_YU", XK_Hangul_YU),
+    ("Hangul_EU", XK_Hangul_EU),
+    ("Hangul_YI", XK_Hangul_YI),
+    ("Hangul_I", XK_Hangul_I),
+    ("Hangul_J_Kiyeog", XK_Hangul_J_Kiyeog),
+    ("Hangul_J_SsangKiyeog", XK_Hangul_J_SsangKiyeog),
+    ("Hangul_J_KiyeogSios", XK_Hangul_J_KiyeogSios),
+    ("Hangul_J_Nieun", XK_Hangul_J_Nieun),
+    ("Hangul_J_NieunJieuj", XK_Hangul_J_NieunJieuj),
+    ("Hangul_J_NieunHieuh", XK_Hangul_J_NieunHieuh),
+    ("Hangul_J_Dikeud", XK_Hangul_J_Dikeud),
+    ("Hangul_J_Rieul", XK_Hangul_J_Rieul),
+    ("Hangul_J_RieulKiyeog", XK_Hangul_J_RieulKiyeog),
+    ("Hangul_J_RieulMieum", XK_Hangul_J_RieulMieum),
+    ("Hangul_J_RieulPieub", XK_Hangul_J_RieulPieub),
+    ("Hangul_J_RieulSios", XK_Hangul_J_RieulSios),
+    ("Hangul_J_RieulTieut", XK_Hangul_J_RieulTieut),
+    ("Hangul_J_RieulPhieuf", XK_Hangul_J_RieulPhieuf),
+    ("Hangul_J_RieulHieuh", XK_Hangul_J_RieulHieuh),
+    ("Hangul_J_Mieum", XK_Hangul_J_Mieum),
+    ("Hangul_J_Pieub", XK_Hangul_J_Pieub),
+    ("Hangul_J_PieubSios", XK_Hangul_J_PieubSios),
+    ("Hangul_J_Sios", XK_Hangul_J_Sios),
+    ("Hangul_J_SsangSios", XK_Hangul_J_SsangSios),
+    ("Hangul_J_Ieung", XK_Hangul_J_Ieung),
+    ("Hangul_J_Jieuj", XK_Hangul_J_Jieuj),
+    ("Hangul_J_Cieuc", XK_Hangul_J_Cieuc),
+    ("Hangul_J_Khieuq", XK_Hangul_J_Khieuq),
+    ("Hangul_J_Tieut", XK_Hangul_J_Tieut),
+    ("Hangul_J_Phieuf", XK_Hangul_J_Phieuf),
+    ("Hangul_J_Hieuh", XK_Hangul_J_Hieuh),
+    ("Hangul_RieulYeorinHieuh", XK_Hangul_RieulYeorinHieuh),
+    ("Hangul_SunkyeongeumMieum", XK_Hangul_SunkyeongeumMieum),
+    ("Hangul_SunkyeongeumPieub", XK_Hangul_SunkyeongeumPieub),
+    ("Hangul_PanSios", XK_Hangul_PanSios),
+    ("Hangul_KkogjiDalrinIeung", XK_Hangul_KkogjiDalrinIeung),
+    ("Hangul_SunkyeongeumPhieuf", XK_Hangul_SunkyeongeumPhieuf),
+    ("Hangul_YeorinHieuh", XK_Hangul_YeorinHieuh),
+    ("Hangul_AraeA", XK_Hangul_AraeA),
+    ("Hangul_AraeAE", XK_Hangul_AraeAE),
+    ("Hangul_J_PanSios", XK_Hangul_J_PanSios),
+    ("Hangul_J_KkogjiDalrinIeung", XK_Hangul_J_KkogjiDalrinIeung),
+    ("Hangul_J_YeorinHieuh", XK_Hangul_J_YeorinHieuh),
+    ("Korean_Won", XK_Korean_Won),
+    ("Armenian_ligature_ew", XK_Armenian_ligature_ew),
+    ("Armenian_full_stop", XK_Armenian_full_stop),
+    ("Armenian_verjaket", XK_Armenian_verjaket),
+    ("Armenian_separation_mark", XK_Armenian_separation_mark),
+    ("Armenian_but", XK_Armenian_but),
+    ("Armenian_hyphen", XK_Armenian_hyphen),
+    ("Armenian_yentamna", XK_Armenian_yentamna),
+    ("Armenian_exclam", XK_Armenian_exclam),
+    ("Armenian_amanak", XK_Armenian_amanak),
+    ("Armenian_accent", XK_Armenian_accent),
+    ("Armenian_shesht", XK_Armenian_shesht),
+    ("Armenian_question", XK_Armenian_question),
+    ("Armenian_paruyk", XK_Armenian_paruyk),
+    ("Armenian_AYB", XK_Armenian_AYB),
+    ("Armenian_ayb", XK_Armenian_ayb),
+    ("Armenian_BEN", XK_Armenian_BEN),
+    ("Armenian_ben", XK_Armenian_ben),
+    ("Armenian_GIM", XK_Armenian_GIM),
+    ("Armenian_gim", XK_Armenian_gim),
+    ("Armenian_DA", XK_Armenian_DA),
+    ("Armenian_da", XK_Armenian_da),
+    ("Armenian_YECH", XK_Armenian_YECH),
+    ("Armenian_yech", XK_Armenian_yech),
+    ("Armenian_ZA", XK_Armenian_ZA),
+    ("Armenian_za", XK_Armenian_za),
+    ("Armenian_E", XK_Armenian_E),
+    ("Armenian_e", XK_Armenian_e),
+    ("Armenian_AT", XK_Armenian_AT),
+    ("Armenian_at", XK_Armenian_at),
+    ("Armenian_TO", XK_Armenian_TO),
+    ("Armenian_to", XK_Armenian_to),
+    ("Armenian_ZHE", XK_Armenian_ZHE),
+    ("Armenian_zhe", XK_Armenian_zhe),
+    ("Armenian_INI", XK_Armenian_INI),
+    ("Armenian_ini", XK_Armenian_ini),
+    ("Armenian_LYUN", XK_Armenian_LYUN),
+    ("Armenian_lyun", XK_Armenian_lyun),
+    ("Armenian_KHE", XK_Armenian_KHE),
+    ("Armenian_khe", XK_Armenian_khe),
+    ("Armenian_TSA", XK_Armenian_TSA),
+    ("Armenian_tsa", XK_Armenian_tsa),
+    ("Armenian_KEN", XK_Armenian_KEN),
+    ("Armenian_ken", XK_Armenian_ken),
+    ("Armenian_HO", XK_Armenian_HO),
+    ("Armenian_ho", XK_Armenian_ho),
+    ("Armenian_DZA", XK_Armenian_DZA),
+    ("Armenian_dza", XK_Armenian_dza),
+    ("Armenian_GHAT", XK_Armenian_GHAT),
+    ("Armenian_ghat", XK_Armenian_ghat),
+    ("Armenian_TCHE", XK_Armenian_TCHE),
+    ("Armenian_tche", XK_Armenian_tche),
+    ("Armenian_MEN", XK_Armenian_MEN),
+    ("Armenian_men", XK_Armenian_men),
+    ("Armenian_HI", XK_Armenian_HI),
+    ("Armenian_hi", XK_Armenian_hi),
+    ("Armenian_NU", XK_Armenian_NU),
+    ("Armenian_nu", XK_Armenian_nu),
+    ("Armenian_SHA", XK_Armenian_SHA),
+    ("Armenian_sha", XK_Armenian_sha),
+    ("Armenian_VO", XK_Armenian_VO),
+    ("Armenian_vo", XK_Armenian_vo),
+    ("Armenian_CHA", XK_Armenian_CHA),
+    ("Armenian_cha", XK_Armenian_cha),
+    ("Armenian_PE", XK_Armenian_PE),
+    ("Armenian_pe", XK_Armenian_pe),
+    ("Armenian_JE", XK_Armenian_JE),
+    ("Armenian_je", XK_Armenian_je),
+    ("Armenian_RA", XK_Armenian_RA),
+    ("Armenian_ra", XK_Armenian_ra),
+    ("Armenian_SE", XK_Armenian_SE),
+    ("Armenian_se", XK_Armenian_se),
+    ("Armenian_VEV", XK_Armenian_VEV),
+    ("Armenian_vev", XK_Armenian_vev),
+    ("Armenian_TYUN", XK_Armenian_TYUN),
+    ("Armenian_tyun", XK_Armenian_tyun),
+    ("Armenian_RE", XK_Armenian_RE),
+    ("Armenian_re", XK_Armenian_re),
+    ("Armenian_TSO", XK_Armenian_TSO),
+    ("Armenian_tso", XK_Armenian_tso),
+    ("Armenian_VYUN", XK_Armenian_VYUN),
+    ("Armenian_vyun", XK_Armenian_vyun),
+    ("Armenian_PYUR", XK_Armenian_PYUR),
+    ("Armenian_pyur", XK_Armenian_pyur),
+    ("Armenian_KE", XK_Armenian_KE),
+    ("Armenian_ke", XK_Armenian_ke),
+    ("Armenian_O", XK_Armenian_O),
+    ("Armenian_o", XK_Armenian_o),
+    ("Armenian_FE", XK_Armenian_FE),
+    ("Armenian_fe", XK_Armenian_fe),
+    ("Armenian_apostrophe", XK_Armenian_apostrophe),
+    ("Georgian_an", XK_Georgian_an),
+    ("Georgian_ban", XK_Georgian_ban),
+    ("Georgian_gan", XK_Georgian_gan),
+    ("Georgian_don", XK_Georgian_don),
+    ("Georgian_en", XK_Georgian_en),
+    ("Georgian_vin", XK_Georgian_vin),
+    ("Georgian_zen", XK_Georgian_zen),
+    ("Georgian_tan", XK_Georgian_tan),
+    ("Georgian_in", XK_Georgian_in),
+    ("Georgian_kan", XK_Georgian_kan),
+    ("Georgian_las", XK_Georgian_las),
+    ("Georgian_man", XK_Georgian_man),
+    ("Georgian_nar", XK_Georgian_nar),
+    ("Georgian_on", XK_Georgian_on),
+    ("Georgian_par", XK_Georgian_par),
+    ("Georgian_zhar", XK_Georgian_zhar),
+    ("Georgian_rae", XK_Georgian_rae),
+    ("Georgian_san", XK_Georgian_san),
+    ("Georgian_tar", XK_Georgian_tar),
+    ("Georgian_un", XK_Georgian_un),
+    ("Georgian_phar", XK_Georgian_phar),
+    ("Georgian_khar", XK_Georgian_khar),
+    ("Georgian_ghan", XK_Georgian_ghan),
+    ("Georgian_qar", XK_Georgian_qar),
+    ("Georgian_shin", XK_Georgian_shin),
+    ("Georgian_chin", XK_Georgian_chin),
+    ("Georgian_can", XK_Georgian_can),
+    ("Georgian_jil", XK_Georgian_jil),
+    ("Georgian_cil", XK_Georgian_cil),
+    ("Georgian_char", XK_Georgian_char),
+    ("Georgian_xan", XK_Georgian_xan),
+    ("Georgian_jhan", XK_Georgian_jhan),
+    ("Georgian_hae", XK_Georgian_hae),
+    ("Georgian_he", XK_Georgian_he),
+    ("Georgian_hie", XK_Georgian_hie),
+    ("Georgian_we", XK_Georgian_we),
+    ("Georgian_har", XK_Georgian_har),
+    ("Georgian_hoe", XK_Georgian_hoe),
+    ("Georgian_fi", XK_Georgian_fi),
+    ("Xabovedot", XK_Xabovedot),
+    ("Ibreve", XK_Ibreve),
+    ("Zstroke", XK_Zstroke),
+    ("Gcaron", XK_Gcaron),
+    ("Ocaron", XK_Ocaron),
+    ("Obarred", XK_Obarred),
+    ("xabovedot", XK_xabovedot),
+    ("ibreve", XK_ibreve),
+    ("zstroke", XK_zstroke),
+    ("gcaron", XK_gcaron),
+    ("ocaron", XK_ocaron),
+    ("obarred", XK_obarred),
+    ("SCHWA", XK_SCHWA),
+    ("schwa", XK_schwa),
+    ("EZH", XK_EZH),
+    ("ezh", XK_ezh),
+    ("Lbelowdot", XK_Lbelowdot),
+    ("lbelowdot", XK_lbelowdot),
+    ("Abelowdot", XK_Abelowdot),
+    ("abelowdot", XK_abelowdot),
+    ("Ahook", XK_Ahook),
+    ("ahook", XK_ahook),
+    ("Acircumflexacute", XK_Acircumflexacute),
+    ("acircumflexacute", XK_acircumflexacute),
+    ("Acircumflexgrave", XK_Acircumflexgrave),
+    ("acircumflexgrave", XK_acircumflexgrave),
+    ("Acircumflexhook", XK_Acircumflexhook),
+    ("acircumflexhook", XK_acircumflexhook),
+    ("Acircumflextilde", XK_Acircumflextilde),
+    ("acircumflextilde", XK_acircumflextilde),
+    ("Acircumflexbelowdot", XK_Acircumflexbelowdot),
+    ("acircumflexbelowdot", XK_acircumflexbelowdot),
+    ("Abreveacute", XK_Abreveacute),
+    ("abreveacute", XK_abreveacute),
+    ("Abrevegrave", XK_Abrevegrave),
+    ("abrevegrave", XK_abrevegrave),
+    ("Abrevehook", XK_Abrevehook),
+    ("abrevehook", XK_abrevehook),
+    ("Abrevetilde", XK_Abrevetilde),
+    ("abrevetilde", XK_abrevetilde),
+    ("Abrevebelowdot", XK_Abrevebelowdot),
+    ("abrevebelowdot", XK_abrevebelowdot),
+    ("Ebelowdot", XK_Ebelowdot),
+    ("ebelowdot", XK_ebelowdot),
+    ("Ehook", XK_Ehook),
+    ("ehook", XK_ehook),
+    ("Etilde", XK_Etilde),
+    ("etilde", XK_etilde),
+    ("Ecircumflexacute", XK_Ecircumflexacute),
+    ("ecircumflexacute", XK_ecircumflexacute),
+    ("Ecircumflexgrave", XK_Ecircumflexgrave),
+    ("ecircumflexgrave", XK_ecircumflexgrave),
+    ("Ecircumflexhook", XK_Ecircumflexhook),
+    ("ecircumflexhook", XK_ecircumflexhook),
+    ("Ecircumflextilde", XK_Ecircumflextilde),
+    ("ecircumflextilde", XK_ecircumflextilde),
+    ("Ecircumflexbelowdot", XK_Ecircumflexbelowdot),
+    ("ecircumflexbelowdot", XK_ecircumflexbelowdot),
+    ("Ihook", XK_Ihook),
+    ("ihook", XK_ihook),
+    ("Ibelowdot", XK_Ibelowdot),
+    ("ibelowdot", XK_ibelowdot),
+    ("Obelowdot", XK_Obelowdot),
+    ("obelowdot", XK_obelowdot),
+    ("Ohook", XK_Ohook),
+    ("ohook", XK_ohook),
+    ("Ocircumflexacute", XK_Ocircumflexacute),
+    ("ocircumflexacute", XK_ocircumflexacute),
+    ("Ocircumflexgrave", XK_Ocircumflexgrave),
+    ("ocircumflexgrave", XK_ocircumflexgrave),
+    ("Ocircumflexhook", XK_Ocircumflexhook),
+    ("ocircumflexhook", XK_ocircumflexhook),
+    ("Ocircumflextilde", XK_Ocircumflextilde),
+    ("ocircumflextilde", XK_ocircumflextilde),
+    ("Ocircumflexbelowdot", XK_Ocircumflexbelowdot),
+    ("ocircumflexbelowdot", XK_ocircumflexbelowdot),
+    ("Ohornacute", XK_Ohornacute),
+    ("ohornacute", XK_ohornacute),
+    ("Ohorngrave", XK_Ohorngrave),
+    ("ohorngrave", XK_ohorngrave),
+    ("Ohornhook", XK_Ohornhook),
+    ("ohornhook", XK_ohornhook),
+    ("Ohorntilde", XK_Ohorntilde),
+    ("ohorntilde", XK_ohorntilde),
+    ("Ohornbelowdot", XK_Ohornbelowdot),
+    ("ohornbelowdot", XK_ohornbelowdot),
+    ("Ubelowdot", XK_Ubelowdot),
+    ("ubelowdot", XK_ubelowdot),
+    ("Uhook", XK_Uhook),
+    ("uhook", XK_uhook),
+    ("Uhornacute", XK_Uhornacute),
+    ("uhornacute", XK_uhornacute),
+    ("Uhorngrave", XK_Uhorngrave),
+    ("uhorngrave", XK_uhorngrave),
+    ("Uhornhook", XK_Uhornhook),
+    ("uhornhook", XK_uhornhook),
+    ("Uhorntilde", XK_Uhorntilde),
+    ("uhorntilde", XK_uhorntilde),
+    ("Uhornbelowdot", XK_Uhornbelowdot),
+    ("uhornbelowdot", XK_uhornbelowdot),
+    ("Ybelowdot", XK_Ybelowdot),
+    ("ybelowdot", XK_ybelowdot),
+    ("Yhook", XK_Yhook),
+    ("yhook", XK_yhook),
+    ("Ytilde", XK_Ytilde),
+    ("ytilde", XK_ytilde),
+    ("Ohorn", XK_Ohorn),
+    ("ohorn", XK_ohorn),
+    ("Uhorn", XK_Uhorn),
+    ("uhorn", XK_uhorn),
+    ("combining_tilde", XK_combining_tilde),
+    ("combining_grave", XK_combining_grave),
+    ("combining_acute", XK_combining_acute),
+    ("combining_hook", XK_combining_hook),
+    ("combining_belowdot", XK_combining_belowdot),
+    ("EcuSign", XK_EcuSign),
+    ("ColonSign", XK_ColonSign),
+    ("CruzeiroSign", XK_CruzeiroSign),
+    ("FFrancSign", XK_FFrancSign),
+    ("LiraSign", XK_LiraSign),
+    ("MillSign", XK_MillSign),
+    ("NairaSign", XK_NairaSign),
+    ("PesetaSign", XK_PesetaSign),
+    ("RupeeSign", XK_RupeeSign),
+    ("WonSign", XK_WonSign),
+    ("NewSheqelSign", XK_NewSheqelSign),
+    ("DongSign", XK_DongSign),
+    ("EuroSign", XK_EuroSign),
+    ("zerosuperior", XK_zerosuperior),
+    ("foursuperior", XK_foursuperior),
+    ("fivesuperior", XK_fivesuperior),
+    ("sixsuperior", XK_sixsuperior),
+    ("sevensuperior", XK_sevensuperior),
+    ("eightsuperior", XK_eightsuperior),
+    ("ninesuperior", XK_ninesuperior),
+    ("zerosubscript", XK_zerosubscript),
+    ("onesubscript", XK_onesubscript),
+    ("twosubscript", XK_twosubscript),
+    ("threesubscript", XK_threesubscript),
+    ("foursubscript", XK_foursubscript),
+    ("fivesubscript", XK_fivesubscript),
+    ("sixsubscript", XK_sixsubscript),
+    ("sevensubscript", XK_sevensubscript),
+    ("eightsubscript", XK_eightsubscript),
+    ("ninesubscript", XK_ninesubscript),
+    ("partdifferential", XK_partdifferential),
+    ("emptyset", XK_emptyset),
+    ("elementof", XK_elementof),
+    ("notelementof", XK_notelementof),
+    ("containsas", XK_containsas),
+    ("squareroot", XK_squareroot),
+    ("cuberoot", XK_cuberoot),
+    ("fourthroot", XK_fourthroot),
+    ("dintegral", XK_dintegral),
+    ("tintegral", XK_tintegral),
+    ("because", XK_because),
+    ("approxeq", XK_approxeq),
+    ("notapproxeq", XK_notapproxeq),
+    ("notidentical", XK_notidentical),
+    ("stricteq", XK_stricteq),
+    ("braille_dot_1", XK_braille_dot_1),
+    ("braille_dot_2", XK_braille_dot_2),
+    ("braille_dot_3", XK_braille_dot_3),
+    ("braille_dot_4", XK_braille_dot_4),
+    ("braille_dot_5", XK_braille_dot_5),
+    ("braille_dot_6", XK_braille_dot_6),
+    ("braille_dot_7", XK_braille_dot_7),
+    ("braille_dot_8", XK_braille_dot_8),
+    ("braille_dot_9", XK_braille_dot_9),
+    ("braille_dot_10", XK_braille_dot_10),
+    ("braille_blank", XK_braille_blank),
+    ("braille_dots_1", XK_braille_dots_1),
+    ("braille_dots_2", XK_braille_dots_2),
+    ("braille_dots_12", XK_braille_dots_12),
+    ("braille_dots_3", XK_braille_dots_3),
+    ("braille_dots_13", XK_braille_dots_13),
+    ("braille_dots_23", XK_braille_dots_23),
+    ("braille_dots_123", XK_braille_dots_123),
+    ("braille_dots_4", XK_braille_dots_4),
+    ("braille_dots_14", XK_braille_dots_14),
+    ("braille_dots_24", XK_braille_dots_24),
+    ("braille_dots_124", XK_braille_dots_124),
+    ("braille_dots_34", XK_braille_dots_34),
+    ("braille_dots_134", XK_braille_dots_134),
+    ("braille_dots_234", XK_braille_dots_234),
+    ("braille_dots_1234", XK_braille_dots_1234),
+    ("braille_dots_5", XK_braille_dots_5),
+    ("braille_dots_15", XK_braille_dots_15),
+    ("braille_dots_25", XK_braille_dots_25),
+    ("braille_dots_125", XK_braille_dots_125),
+    ("braille_dots_35", XK_braille_dots_35),
+    ("braille_dots_135", XK_braille_dots_135),
+    ("braille_dots_235", XK_braille_dots_235),
+    ("braille_dots_1235", XK_braille_dots_1235),
+    ("braille_dots_45", XK_braille_dots_45),
+    ("braille_dots_145", XK_braille_dots_145),
+    ("braille_dots_245", XK_braille_dots_245),
+    ("braille_dots_1245", XK_braille_dots_1245),
+    ("braille_dots_345", XK_braille_dots_345),
+    ("braille_dots_1345", XK_braille_dots_1345),
+    ("braille_dots_2345", XK_braille_dots_2345),
+    ("braille_dots_12345", XK_braille_dots_12345),
+    ("braille_dots_6", XK_braille_dots_6),
+    ("braille_dots_16", XK_braille_dots_16),
+    ("braille_dots_26", XK_braille_dots_26),
+    ("braille_dots_126", XK_braille_dots_126),
+    ("braille_dots_36", XK_braille_dots_36),
+    ("braille_dots_136", XK_braille_dots_136),
+    ("braille_dots_236", XK_braille_dots_236),
+    ("braille_dots_1236", XK_braille_dots_1236),
+    ("braille_dots_46", XK_braille_dots_46),
+    ("braille_dots_146", XK_braille_dots_146),
+    ("braille_dots_246", XK_braille_dots_246),
+    ("braille_dots_1246", XK_braille_dots_1246),
+    ("braille_dots_346", XK_braille_dots_346),
+    ("braille_dots_1346", XK_braille_dots_1346),
+    ("braille_dots_2346", XK_braille_dots_2346),
+    ("braille_dots_12346", XK_braille_dots_12346),
+    ("braille_dots_56", XK_braille_dots_56),
+    ("braille_dots_156", XK_braille_dots_156),
+    ("braille_dots_256", XK_braille_dots_256),
+    ("braille_dots_1256", XK_braille_dots_1256),
+    ("braille_dots_356", XK_braille_dots_356),
+    ("braille_dots_1356", XK_braille_dots_1356),
+    ("braille_dots_2356", XK_braille_dots_2356),
+    ("braille_dots_12356", XK_braille_dots_12356),
+    ("braille_dots_456", XK_braille_dots_456),
+    ("braille_dots_1456", XK_braille_dots_1456),
+    ("braille_dots_2456", XK_braille_dots_2456),
+    ("braille_dots_12456", XK_braille_dots_12456),
+    ("braille_dots_3456", XK_braille_dots_3456),
+    ("braille_dots_13456", XK_braille_dots_13456),
+    ("braille_dots_23456", XK_braille_dots_23456),
+    ("braille_dots_123456", XK_braille_dots_123456),
+    ("braille_dots_7", XK_braille_dots_7),
+    ("braille_dots_17", XK_braille_dots_17),
+    ("braille_dots_27", XK_braille_dots_27),
+    ("braille_dots_127", XK_braille_dots_127),
+    ("braille_dots_37", XK_braille_dots_37),
+    ("braille_dots_137", XK_braille_dots_137),
+    ("braille_dots_237", XK_braille_dots_237),
+    ("braille_dots_1237", XK_braille_dots_1237),
+    ("braille_dots_47", XK_braille_dots_47),
+    ("braille_dots_147", XK_braille_dots_147),
+    ("braille_dots_247", XK_braille_dots_247),
+    ("braille_dots_1247", XK_braille_dots_1247),
+    ("braille_dots_347", XK_braille_dots_347),
+    ("braille_dots_1347", XK_braille_dots_1347),
+    ("braille_dots_2347", XK_braille_dots_2347),
+    ("braille_dots_12347", XK_braille_dots_12347),
+    ("braille_dots_57", XK_braille_dots_57),
+    ("braille_dots_157", XK_braille_dots_157),
+    ("braille_dots_257", XK_braille_dots_257),
+    ("braille_dots_1257", XK_braille_dots_1257),
+    ("braille_dots_357", XK_braille_dots_357),
+    ("braille_dots_1357", XK_braille_dots_1357),
+    ("braille_dots_2357", XK_braille_dots_2357),
+    ("braille_dots_12357", XK_braille_dots_12357),
+    ("braille_dots_457", XK_braille_dots_457),
+    ("braille_dots_1457", XK_braille_dots_1457),
+    ("braille_dots_2457", XK_braille_dots_2457),
+    ("braille_dots_12457", XK_braille_dots_12457),
+    ("braille_dots_3457", XK_braille_dots_3457),
+    ("braille_dots_13457", XK_braille_dots_13457),
+    ("braille_dots_23457", XK_braille_dots_23457),
+    ("braille_dots_123457", XK_braille_dots_123457),
+    ("braille_dots_67", XK_braille_dots_67),
+    ("braille_dots_167", XK_braille_dots_167),
+    ("braille_dots_267", XK_braille_dots_267),
+    ("braille_dots_1267", XK_braille_dots_1267),
+    ("braille_dots_367", XK_braille_dots_367),
+    ("braille_dots_1367", XK_braille_dots_1367),
+    ("braille_dots_2367", XK_braille_dots_2367),
+    ("braille_dots_12367", XK_braille_dots_12367),
+    ("braille_dots_467", XK_braille_dots_467),
+    ("braille_dots_1467", XK_braille_dots_1467),
+    ("braille_dots_2467", XK_braille_dots_2467),
+    ("braille_dots_12467", XK_braille_dots_12467),
+    ("braille_dots_3467", XK_braille_dots_3467),
+    ("braille_dots_13467", XK_braille_dots_13467),
+    ("braille_dots_23467", XK_braille_dots_23467),
+    ("braille_dots_123467", XK_braille_dots_123467),
+    ("braille_dots_567", XK_braille_dots_567),
+    ("braille_dots_1567", XK_braille_dots_1567),
+    ("braille_dots_2567", XK_braille_dots_2567),
+    ("braille_dots_12567", XK_braille_dots_12567),
+    ("braille_dots_3567", XK_braille_dots_3567),
+    ("braille_dots_13567", XK_braille_dots_13567),
+    ("braille_dots_23567", XK_braille_dots_23567),
+    ("braille_dots_123567", XK_braille_dots_123567),
+    ("braille_dots_4567", XK_braille_dots_4567),
+    ("braille_dots_14567", XK_braille_dots_14567),
+    ("braille_dots_24567", XK_braille_dots_24567),
+    ("braille_dots_124567", XK_braille_dots_124567),
+    ("braille_dots_34567", XK_braille_dots_34567),
+    ("braille_dots_134567", XK_braille_dots_134567),
+    ("braille_dots_234567", XK_braille_dots_234567),
+    ("braille_dots_1234567", XK_braille_dots_1234567),
+    ("braille_dots_8", XK_braille_dots_8),
+    ("braille_dots_18", XK_braille_dots_18),
+    ("braille_dots_28", XK_braille_dots_28),
+    ("braille_dots_128", XK_braille_dots_128),
+    ("braille_dots_38", XK_braille_dots_38),
+    ("braille_dots_138", XK_braille_dots_138),
+    ("braille_dots_238", XK_braille_dots_238),
+    ("braille_dots_1238", XK_braille_dots_1238),
+    ("braille_dots_48", XK_braille_dots_48),
+    ("braille_dots_148", XK_braille_dots_148),
+    ("braille_dots_248", XK_braille_dots_248),
+    ("braille_dots_1248", XK_braille_dots_1248),
+    ("braille_dots_348", XK_braille_dots_348),
+    ("braille_dots_1348", XK_braille_dots_1348),
+    ("braille_dots_2348", XK_braille_dots_2348),
+    ("braille_dots_12348", XK_braille_dots_12348),
+    ("braille_dots_58", XK_braille_dots_58),
+    ("braille_dots_158", XK_braille_dots_158),
+    ("braille_dots_258", XK_braille_dots_258),
+    ("braille_dots_1258", XK_braille_dots_1258),
+    ("braille_dots_358", XK_braille_dots_358),
+    ("braille_dots_1358", XK_braille_dots_1358),
+    ("braille_dots_2358", XK_braille_dots_2358),
+    ("braille_dots_12358", XK_braille_dots_12358),
+    ("braille_dots_458", XK_braille_dots_458),
+    ("braille_dots_1458", XK_braille_dots_1458),
+    ("braille_dots_2458", XK_braille_dots_2458),
+    ("braille_dots_12458", XK_braille_dots_12458),
+    ("braille_dots_3458", XK_braille_dots_3458),
+    ("braille_dots_13458", XK_braille_dots_13458),
+    ("braille_dots_23458", XK_braille_dots_23458),
+    ("braille_dots_123458", XK_braille_dots_123458),
+    ("braille_dots_68", XK_braille_dots_68),
+    ("braille_dots_168", XK_braille_dots_168),
+    ("braille_dots_268", XK_braille_dots_268),
+    ("braille_dots_1268", XK_braille_dots_1268),
+    ("braille_dots_368", XK_braille_dots_368),
+    ("braille_dots_1368", XK_braille_dots_1368),
+    ("braille_dots_2368", XK_braille_dots_2368),
+    ("braille_dots_12368", XK_braille_dots_12368),
+    ("braille_dots_468", XK_braille_dots_468),
+    ("braille_dots_1468", XK_braille_dots_1468),
+    ("braille_dots_2468", XK_braille_dots_2468),
+    ("braille_dots_12468", XK_braille_dots_12468),
+    ("braille_dots_3468", XK_braille_dots_3468),
+    ("braille_dots_13468", XK_braille_dots_13468),
+    ("braille_dots_23468", XK_braille_dots_23468),
+    ("braille_dots_123468", XK_braille_dots_123468),
+    ("braille_dots_568", XK_braille_dots_568),
+    ("braille_dots_1568", XK_braille_dots_1568),
+    ("braille_dots_2568", XK_braille_dots_2568),
+    ("braille_dots_12568", XK_braille_dots_12568),
+    ("braille_dots_3568", XK_braille_dots_3568),
+    ("braille_dots_13568", XK_braille_dots_13568),
+    ("braille_dots_23568", XK_braille_dots_23568),
+    ("braille_dots_123568", XK_braille_dots_123568),
+    ("braille_dots_4568", XK_braille_dots_4568),
+    ("braille_dots_14568", XK_braille_dots_14568),
+    ("braille_dots_24568", XK_braille_dots_24568),
+    ("braille_dots_124568", XK_braille_dots_124568),
+    ("braille_dots_34568", XK_braille_dots_34568),
+    ("braille_dots_134568", XK_braille_dots_134568),
+    ("braille_dots_234568", XK_braille_dots_234568),
+    ("braille_dots_1234568", XK_braille_dots_1234568),
+    ("braille_dots_78", XK_braille_dots_78),
+    ("braille_dots_178", XK_braille_dots_178),
+    ("braille_dots_278", XK_braille_dots_278),
+    ("braille_dots_1278", XK_braille_dots_1278),
+    ("braille_dots_378", XK_braille_dots_378),
+    ("braille_dots_1378", XK_braille_dots_1378),
+    ("braille_dots_2378", XK_braille_dots_2378),
+    ("braille_dots_12378", XK_braille_dots_12378),
+    ("braille_dots_478", XK_braille_dots_478),
+    ("braille_dots_1478", XK_braille_dots_1478),
+    ("braille_dots_2478", XK_braille_dots_2478),
+    ("braille_dots_12478", XK_braille_dots_12478),
+    ("braille_dots_3478", XK_braille_dots_3478),
+    ("braille_dots_13478", XK_braille_dots_13478),
+    ("braille_dots_23478", XK_braille_dots_23478),
+    ("braille_dots_123478", XK_braille_dots_123478),
+    ("braille_dots_578", XK_braille_dots_578),
+    ("braille_dots_1578", XK_braille_dots_1578),
+    ("braille_dots_2578", XK_braille_dots_2578),
+    ("braille_dots_12578", XK_braille_dots_12578),
+    ("braille_dots_3578", XK_braille_dots_3578),
+    ("braille_dots_13578", XK_braille_dots_13578),
+    ("braille_dots_23578", XK_braille_dots_23578),
+    ("braille_dots_123578", XK_braille_dots_123578),
+    ("braille_dots_4578", XK_braille_dots_4578),
+    ("braille_dots_14578", XK_braille_dots_14578),
+    ("braille_dots_24578", XK_braille_dots_24578),
+    ("braille_dots_124578", XK_braille_dots_124578),
+    ("braille_dots_34578", XK_braille_dots_34578),
+    ("braille_dots_134578", XK_braille_dots_134578),
+    ("braille_dots_234578", XK_braille_dots_234578),
+    ("braille_dots_1234578", XK_braille_dots_1234578),
+    ("braille_dots_678", XK_braille_dots_678),
+    ("braille_dots_1678", XK_braille_dots_1678),
+    ("braille_dots_2678", XK_braille_dots_2678),
+    ("braille_dots_12678", XK_braille_dots_12678),
+    ("braille_dots_3678", XK_braille_dots_3678),
+    ("braille_dots_13678", XK_braille_dots_13678),
+    ("braille_dots_23678", XK_braille_dots_23678),
+    ("braille_dots_123678", XK_braille_dots_123678),
+    ("braille_dots_4678", XK_braille_dots_4678),
+    ("braille_dots_14678", XK_braille_dots_14678),
+    ("braille_dots_24678", XK_braille_dots_24678),
+    ("braille_dots_124678", XK_braille_dots_124678),
+    ("braille_dots_34678", XK_braille_dots_34678),
+    ("braille_dots_134678", XK_braille_dots_134678),
+    ("braille_dots_234678", XK_braille_dots_234678),
+    ("braille_dots_1234678", XK_braille_dots_1234678),
+    ("braille_dots_5678", XK_braille_dots_5678),
+    ("braille_dots_15678", XK_braille_dots_15678),
+    ("braille_dots_25678", XK_braille_dots_25678),
+    ("braille_dots_125678", XK_braille_dots_125678),
+    ("braille_dots_35678", XK_braille_dots_35678),
+    ("braille_dots_135678", XK_braille_dots_135678),
+    ("braille_dots_235678", XK_braille_dots_235678),
+    ("braille_dots_1235678", XK_braille_dots_1235678),
+    ("braille_dots_45678", XK_braille_dots_45678),
+    ("braille_dots_145678", XK_braille_dots_145678),
+    ("braille_dots_245678", XK_braille_dots_245678),
+    ("braille_dots_1245678", XK_braille_dots_1245678),
+    ("braille_dots_345678", XK_braille_dots_345678),
+    ("braille_dots_1345678", XK_braille_dots_1345678),
+    ("braille_dots_2345678", XK_braille_dots_2345678),
+    ("braille_dots_12345678", XK_braille_dots_12345678),
+    ("Sinh_ng", XK_Sinh_ng),
+    ("Sinh_h2", XK_Sinh_h2),
+    ("Sinh_a", XK_Sinh_a),
+    ("Sinh_aa", XK_Sinh_aa),
+    ("Sinh_ae", XK_Sinh_ae),
+    ("Sinh_aee", XK_Sinh_aee),
+    ("Sinh_i", XK_Sinh_i),
+    ("Sinh_ii", XK_Sinh_ii),
+    ("Sinh_u", XK_Sinh_u),
+    ("Sinh_uu", XK_Sinh_uu),
+    ("Sinh_ri", XK_Sinh_ri),
+    ("Sinh_rii", XK_Sinh_rii),
+    ("Sinh_lu", XK_Sinh_lu),
+    ("Sinh_luu", XK_Sinh_luu),
+    ("Sinh_e", XK_Sinh_e),
+    ("Sinh_ee", XK_Sinh_ee),
+    ("Sinh_ai", XK_Sinh_ai),
+    ("Sinh_o", XK_Sinh_o),
+    ("Sinh_oo", XK_Sinh_oo),
+    ("Sinh_au", XK_Sinh_au),
+    ("Sinh_ka", XK_Sinh_ka),
+    ("Sinh_kha", XK_Sinh_kha),
+    ("Sinh_ga", XK_Sinh_ga),
+    ("Sinh_gha", XK_Sinh_gha),
+    ("Sinh_ng2", XK_Sinh_ng2),
+    ("Sinh_nga", XK_Sinh_nga),
+    ("Sinh_ca", XK_Sinh_ca),
+    ("Sinh_cha", XK_Sinh_cha),
+    ("Sinh_ja", XK_Sinh_ja),
+    ("Sinh_jha", XK_Sinh_jha),
+    ("Sinh_nya", XK_Sinh_nya),
+    ("Sinh_jnya", XK_Sinh_jnya),
+    ("Sinh_nja", XK_Sinh_nja),
+    ("Sinh_tta", XK_Sinh_tta),
+    ("Sinh_ttha", XK_Sinh_ttha),
+    ("Sinh_dda", XK_Sinh_dda),
+    ("Sinh_ddha", XK_Sinh_ddha),
+    ("Sinh_nna", XK_Sinh_nna),
+    ("Sinh_ndda", XK_Sinh_ndda),
+    ("Sinh_tha", XK_Sinh_tha),
+    ("Sinh_thha", XK_Sinh_thha),
+    ("Sinh_dha", XK_Sinh_dha),
+    ("Sinh_dhha", XK_Sinh_dhha),
+    ("Sinh_na", XK_Sinh_na),
+    ("Sinh_ndha", XK_Sinh_ndha),
+    ("Sinh_pa", XK_Sinh_pa),
+    ("Sinh_pha", XK_Sinh_pha),
+    ("Sinh_ba", XK_Sinh_ba),
+    ("Sinh_bha", XK_Sinh_bha),
+    ("Sinh_ma", XK_Sinh_ma),
+    ("Sinh_mba", XK_Sinh_mba),
+    ("Sinh_ya", XK_Sinh_ya),
+    ("Sinh_ra", XK_Sinh_ra),
+    ("Sinh_la", XK_Sinh_la),
+    ("Sinh_va", XK_Sinh_va),
+    ("Sinh_sha", XK_Sinh_sha),
+    ("Sinh_ssha", XK_Sinh_ssha),
+    ("Sinh_sa", XK_Sinh_sa),
+    ("Sinh_ha", XK_Sinh_ha),
+    ("Sinh_lla", XK_Sinh_lla),
+    ("Sinh_fa", XK_Sinh_fa),
+    ("Sinh_al", XK_Sinh_al),
+    ("Sinh_aa2", XK_Sinh_aa2),
+    ("Sinh_ae2", XK_Sinh_ae2),
+    ("Sinh_aee2", XK_Sinh_aee2),
+    ("Sinh_i2", XK_Sinh_i2),
+    ("Sinh_ii2", XK_Sinh_ii2),
+    ("Sinh_u2", XK_Sinh_u2),
+    ("Sinh_uu2", XK_Sinh_uu2),
+    ("Sinh_ru2", XK_Sinh_ru2),
+    ("Sinh_e2", XK_Sinh_e2),
+    ("Sinh_ee2", XK_Sinh_ee2),
+    ("Sinh_ai2", XK_Sinh_ai2),
+    ("Sinh_o2", XK_Sinh_o2),
+    ("Sinh_oo2", XK_Sinh_oo2),
+    ("Sinh_au2", XK_Sinh_au2),
+    ("Sinh_lu2", XK_Sinh_lu2),
+    ("Sinh_ruu2", XK_Sinh_ruu2),
+    ("Sinh_luu2", XK_Sinh_luu2),
+    ("Sinh_kunddaliya", XK_Sinh_kunddaliya),
+    ("XF86ModeLock", XF86XK_ModeLock),
+    ("XF86MonBrightnessUp", XF86XK_MonBrightnessUp),
+    ("XF86MonBrightnessDown", XF86XK_MonBrightnessDown),
+    ("XF86KbdLightOnOff", XF86XK_KbdLightOnOff),
+    ("XF86KbdBrightnessUp", XF86XK_KbdBrightnessUp),
+    ("XF86KbdBrightnessDown", XF86XK_KbdBrightnessDown),
+    ("XF86MonBrightnessCycle", XF86XK_MonBrightnessCycle),
+    ("XF86Standby", XF86XK_Standby),
+    ("XF86AudioLowerVolume", XF86XK_AudioLowerVolume),
+    ("XF86AudioMute", XF86XK_AudioMute),
+    ("XF86AudioRaiseVolume", XF86XK_AudioRaiseVolume),
+    ("XF86AudioPlay", XF86XK_AudioPlay),
+    ("XF86AudioStop", XF86XK_AudioStop),
+    ("XF86AudioPrev", XF86XK_AudioPrev),
+    ("XF86AudioNext", XF86XK_AudioNext),
+    ("XF86HomePage", XF86XK_HomePage),
+    ("XF86Mail", XF86XK_Mail),
+    ("XF86Start", XF86XK_Start),
+    ("XF86Search", XF86XK_Search),
+    ("XF86AudioRecord", XF86XK_AudioRecord),
+    ("XF86Calculator", XF86XK_Calculator),
+    ("XF86Memo", XF86XK_Memo),
+    ("XF86ToDoList", XF86XK_ToDoList),
+    ("XF86Calendar", XF86XK_Calendar),
+    ("XF86PowerDown", XF86XK_PowerDown),
+    ("XF86ContrastAdjust", XF86XK_ContrastAdjust),
+    ("XF86RockerUp", XF86XK_RockerUp),
+    ("XF86RockerDown", XF86XK_RockerDown),
+    ("XF86RockerEnter", XF86XK_RockerEnter),
+    ("XF86Back", XF86XK_Back),
+    ("XF86Forward", XF86XK_Forward),
+    ("XF86Stop", XF86XK_Stop),
+    ("XF86Refresh", XF86XK_Refresh),
+    ("XF86PowerOff", XF86XK_PowerOff),
+    ("XF86WakeUp", XF86XK_WakeUp),
+    ("XF86Eject", XF86XK_Eject),
+    ("XF86ScreenSaver", XF86XK_ScreenSaver),
+    ("XF86WWW", XF86XK_WWW),
+    ("XF86Sleep", XF86XK_Sleep),
+    ("XF86Favorites", XF86XK_Favorites),
+    ("XF86AudioPause", XF86XK_AudioPause),
+    ("XF86AudioMedia", XF86XK_AudioMedia),
+    ("XF86MyComputer", XF86XK_MyComputer),
+    ("XF86VendorHome", XF86XK_VendorHome),
+    ("XF86LightBulb", XF86XK_LightBulb),
+    ("XF86Shop", XF86XK_Shop),
+    ("XF86History", XF86XK_History),
+    ("XF86OpenURL", XF86XK_OpenURL),
+    ("XF86AddFavorite", XF86XK_AddFavorite),
+    ("XF86HotLinks", XF86XK_HotLinks),
+    ("XF86BrightnessAdjust", XF86XK_BrightnessAdjust),
+    ("XF86Finance", XF86XK_Finance),
+    ("XF86Community", XF86XK_Community),
+    ("XF86AudioRewind", XF86XK_AudioRewind),
+    ("XF86BackForward", XF86XK_BackForward),
+    ("XF86Launch0", XF86XK_Launch0),
+    ("XF86Launch1", XF86XK_Launch1),
+    ("XF86Launch2", XF86XK_Launch2),
+    ("XF86Launch3", XF86XK_Launch3),
+    ("XF86Launch4", XF86XK_Launch4),
+    ("XF86Launch5", XF86XK_Launch5),
+    ("XF86Launch6", XF86XK_Launch6),
+    ("XF86Launch7", XF86XK_Launch7),
+    ("XF86Launch8", XF86XK_Launch8),
+    ("XF86Launch9", XF86XK_Launch9),
+    ("XF86LaunchA", XF86XK_LaunchA),
+    ("XF86LaunchB", XF86XK_LaunchB),
+    ("XF86LaunchC", XF86XK_LaunchC),
+    ("XF86LaunchD", XF86XK_LaunchD),
+    ("XF86LaunchE", XF86XK_LaunchE),
+    ("XF86LaunchF", XF86XK_LaunchF),
+    ("XF86ApplicationLeft", XF86XK_ApplicationLeft),
+    ("XF86ApplicationRight", XF86XK_ApplicationRight),
+    ("XF86Book", XF86XK_Book),
+    ("XF86CD", XF86XK_CD),
+    ("XF86Calculater", XF86XK_Calculater),
+    ("XF86Clear", XF86XK_Clear),
+    ("XF86Close", XF86XK_Close),
+    ("XF86Copy", XF86XK_Copy),
+    ("XF86Cut", XF86XK_Cut),
+    ("XF86Display", XF86XK_Display),
+    ("XF86DOS", XF86XK_DOS),
+    ("XF86Documents", XF86XK_Documents),
+    ("XF86Excel", XF86XK_Excel),
+    ("XF86Explorer", XF86XK_Explorer),
+    ("XF86Game", XF86XK_Game),
+    ("XF86Go", XF86XK_Go),
+    ("XF86iTouch", XF86XK_iTouch),
+    ("XF86LogOff", XF86XK_LogOff),
+    ("XF86Market", XF86XK_Market),
+    ("XF86Meeting", XF86XK_Meeting),
+    ("XF86MenuKB", XF86XK_MenuKB),
+    ("XF86MenuPB", XF86XK_MenuPB),
+    ("XF86MySites", XF86XK_MySites),
+    ("XF86New", XF86XK_New),
+    ("XF86News", XF86XK_News),
+    ("XF86OfficeHome", XF86XK_OfficeHome),
+    ("XF86Open", XF86XK_Open),
+    ("XF86Option", XF86XK_Option),
+    ("XF86Paste", XF86XK_Paste),
+    ("XF86Phone", XF86XK_Phone),
+    ("XF86Q", XF86XK_Q),
+    ("XF86Reply", XF86XK_Reply),
+    ("XF86Reload", XF86XK_Reload),
+    ("XF86RotateWindows", XF86XK_RotateWindows),
+    ("XF86RotationPB", XF86XK_RotationPB),
+    ("XF86RotationKB", XF86XK_RotationKB),
+    ("XF86Save", XF86XK_Save),
+    ("XF86ScrollUp", XF86XK_ScrollUp),
+    ("XF86ScrollDown", XF86XK_ScrollDown),
+    ("XF86ScrollClick", XF86XK_ScrollClick),
+    ("XF86Send", XF86XK_Send),
+    ("XF86Spell", XF86XK_Spell),
+    ("XF86SplitScreen", XF86XK_SplitScreen),
+    ("XF86Support", XF86XK_Support),
+    ("XF86TaskPane", XF86XK_TaskPane),
+    ("XF86Terminal", XF86XK_Terminal),
+    ("XF86Tools", XF86XK_Tools),
+    ("XF86Travel", XF86XK_Travel),
+    ("XF86UserPB", XF86XK_UserPB),
+    ("XF86User1KB", XF86XK_User1KB),
+    ("XF86User2KB", XF86XK_User2KB),
+    ("XF86Video", XF86XK_Video),
+    ("XF86WheelButton", XF86XK_WheelButton),
+    ("XF86Word", XF86XK_Word),
+    ("XF86Xfer", XF86XK_Xfer),
+    ("XF86ZoomIn", XF86XK_ZoomIn),
+    ("XF86ZoomOut", XF86XK_ZoomOut),
+    ("XF86Away", XF86XK_Away),
+    ("XF86Messenger", XF86XK_Messenger),
+    ("XF86WebCam", XF86XK_WebCam),
+    ("XF86MailForward", XF86XK_MailForward),
+    ("XF86Pictures", XF86XK_Pictures),
+    ("XF86Music", XF86XK_Music),
+    ("XF86Battery", XF86XK_Battery),
+    ("XF86Bluetooth", XF86XK_Bluetooth),
+    ("XF86WLAN", XF86XK_WLAN),
+    ("XF86UWB", XF86XK_UWB),
+    ("XF86AudioForward", XF86XK_AudioForward),
+    ("XF86AudioRepeat", XF86XK_AudioRepeat),
+    ("XF86AudioRandomPlay", XF86XK_AudioRandomPlay),
+    ("XF86Subtitle", XF86XK_Subtitle),
+    ("XF86AudioCycleTrack", XF86XK_AudioCycleTrack),
+    ("XF86CycleAngle", XF86XK_CycleAngle),
+    ("XF86FrameBack", XF86XK_FrameBack),
+    ("XF86FrameForward", XF86XK_FrameForward),
+    ("XF86Time", XF86XK_Time),
+    ("XF86Select", XF86XK_Select),
+    ("XF86View", XF86XK_View),
+    ("XF86TopMenu", XF86XK_TopMenu),
+    ("XF86Red", XF86XK_Red),
+    ("XF86Green", XF86XK_Green),
+    ("XF86Yellow", XF86XK_Yellow),
+    ("XF86Blue", XF86XK_Blue),
+    ("XF86Suspend", XF86XK_Suspend),
+    ("XF86Hibernate", XF86XK_Hibernate),
+    ("XF86TouchpadToggle", XF86XK_TouchpadToggle),
+    ("XF86TouchpadOn", XF86XK_TouchpadOn),
+    ("XF86TouchpadOff", XF86XK_TouchpadOff),
+    ("XF86AudioMicMute", XF86XK_AudioMicMute),
+    ("XF86Keyboard", XF86XK_Keyboard),
+    ("XF86WWAN", XF86XK_WWAN),
+    ("XF86RFKill", XF86XK_RFKill),
+    ("XF86AudioPreset", XF86XK_AudioPreset),
+    ("XF86RotationLockToggle", XF86XK_RotationLockToggle),
+    ("XF86FullScreen", XF86XK_FullScreen),
+    ("XF86Switch_VT_1", XF86XK_Switch_VT_1),
+    ("XF86Switch_VT_2", XF86XK_Switch_VT_2),
+    ("XF86Switch_VT_3", XF86XK_Switch_VT_3),
+    ("XF86Switch_VT_4", XF86XK_Switch_VT_4),
+    ("XF86Switch_VT_5", XF86XK_Switch_VT_5),
+    ("XF86Switch_VT_6", XF86XK_Switch_VT_6),
+    ("XF86Switch_VT_7", XF86XK_Switch_VT_7),
+    ("XF86Switch_VT_8", XF86XK_Switch_VT_8),
+    ("XF86Switch_VT_9", XF86XK_Switch_VT_9),
+    ("XF86Switch_VT_10", XF86XK_Switch_VT_10),
+    ("XF86Switch_VT_11", XF86XK_Switch_VT_11),
+    ("XF86Switch_VT_12", XF86XK_Switch_VT_12),
+    ("XF86Ungrab", XF86XK_Ungrab),
+    ("XF86ClearGrab", XF86XK_ClearGrab),
+    ("XF86Next_VMode", XF86XK_Next_VMode),
+    ("XF86Prev_VMode", XF86XK_Prev_VMode),
+    ("XF86LogWindowTree", XF86XK_LogWindowTree),
+    ("XF86LogGrabInfo", XF86XK_LogGrabInfo),
+    ("XF86BrightnessAuto", XF86XK_BrightnessAuto),
+    ("XF86DisplayOff", XF86XK_DisplayOff),
+    ("XF86Info", XF86XK_Info),
+    ("XF86AspectRatio", XF86XK_AspectRatio),
+    ("XF86DVD", XF86XK_DVD),
+    ("XF86Audio", XF86XK_Audio),
+    ("XF86ChannelUp", XF86XK_ChannelUp),
+    ("XF86ChannelDown", XF86XK_ChannelDown),
+    ("XF86Break", XF86XK_Break),
+    ("XF86VideoPhone", XF86XK_VideoPhone),
+    ("XF86ZoomReset", XF86XK_ZoomReset),
+    ("XF86Editor", XF86XK_Editor),
+    ("XF86GraphicsEditor", XF86XK_GraphicsEditor),
+    ("XF86Presentation", XF86XK_Presentation),
+    ("XF86Database", XF86XK_Database),
+    ("XF86Voicemail", XF86XK_Voicemail),
+    ("XF86Addressbook", XF86XK_Addressbook),
+    ("XF86DisplayToggle", XF86XK_DisplayToggle),
+    ("XF86SpellCheck", XF86XK_SpellCheck),
+    ("XF86ContextMenu", XF86XK_ContextMenu),
+    ("XF86MediaRepeat", XF86XK_MediaRepeat),
+    ("XF8610ChannelsUp", XF86XK_10ChannelsUp),
+    ("XF8610ChannelsDown", XF86XK_10ChannelsDown),
+    ("XF86Images", XF86XK_Images),
+    ("XF86NotificationCenter", XF86XK_NotificationCenter),
+    ("XF86PickupPhone", XF86XK_PickupPhone),
+    ("XF86HangupPhone", XF86XK_HangupPhone),
+    ("XF86Fn", XF86XK_Fn),
+    ("XF86Fn_Esc", XF86XK_Fn_Esc),
+    ("XF86FnRightShift", XF86XK_FnRightShift),
+    ("XF86Numeric0", XF86XK_Numeric0),
+    ("XF86Numeric1", XF86XK_Numeric1),
+    ("XF86Numeric2", XF86XK_Numeric2),
+    ("XF86Numeric3", XF86XK_Numeric3),
+    ("XF86Numeric4", XF86XK_Numeric4),
+    ("XF86Numeric5", XF86XK_Numeric5),
+    ("XF86Numeric6", XF86XK_Numeric6),
+    ("XF86Numeric7", XF86XK_Numeric7),
+    ("XF86Numeric8", XF86XK_Numeric8),
+    ("XF86Numeric9", XF86XK_Numeric9),
+    ("XF86NumericStar", XF86XK_NumericStar),
+    ("XF86NumericPound", XF86XK_NumericPound),
+    ("XF86NumericA", XF86XK_NumericA),
+    ("XF86NumericB", XF86XK_NumericB),
+    ("XF86NumericC", XF86XK_NumericC),
+    ("XF86NumericD", XF86XK_NumericD),
+    ("XF86CameraFocus", XF86XK_CameraFocus),
+    ("XF86WPSButton", XF86XK_WPSButton),
+    ("XF86CameraZoomIn", XF86XK_CameraZoomIn),
+    ("XF86CameraZoomOut", XF86XK_CameraZoomOut),
+    ("XF86CameraUp", XF86XK_CameraUp),
+    ("XF86CameraDown", XF86XK_CameraDown),
+    ("XF86CameraLeft", XF86XK_CameraLeft),
+    ("XF86CameraRight", XF86XK_CameraRight),
+    ("XF86AttendantOn", XF86XK_AttendantOn),
+    ("XF86AttendantOff", XF86XK_AttendantOff),
+    ("XF86AttendantToggle", XF86XK_AttendantToggle),
+    ("XF86LightsToggle", XF86XK_LightsToggle),
+    ("XF86ALSToggle", XF86XK_ALSToggle),
+    ("XF86Buttonconfig", XF86XK_Buttonconfig),
+    ("XF86Taskmanager", XF86XK_Taskmanager),
+    ("XF86Journal", XF86XK_Journal),
+    ("XF86ControlPanel", XF86XK_ControlPanel),
+    ("XF86AppSelect", XF86XK_AppSelect),
+    ("XF86Screensaver", XF86XK_Screensaver),
+    ("XF86VoiceCommand", XF86XK_VoiceCommand),
+    ("XF86Assistant", XF86XK_Assistant),
+    ("XF86EmojiPicker", XF86XK_EmojiPicker),
+    ("XF86Dictate", XF86XK_Dictate),
+    ("XF86BrightnessMin", XF86XK_BrightnessMin),
+    ("XF86BrightnessMax", XF86XK_BrightnessMax),
+    ("XF86KbdInputAssistPrev", XF86XK_KbdInputAssistPrev),
+    ("XF86KbdInputAssistNext", XF86XK_KbdInputAssistNext),
+    (
+        "XF86KbdInputAssistPrevgroup",
+        XF86XK_KbdInputAssistPrevgroup,
+    ),
+    (
+        "XF86KbdInputAssistNextgroup",
+        XF86XK_KbdInputAssistNextgroup,
+    ),
+    ("XF86KbdInputAssistAccept", XF86XK_KbdInputAssistAccept),
+    ("XF86KbdInputAssistCancel", XF86XK_KbdInputAssistCancel),
+    ("XF86RightUp", XF86XK_RightUp),
+    ("XF86RightDown", XF86XK_RightDown),
+    ("XF86LeftUp", XF86XK_LeftUp),
+    ("XF86LeftDown", XF86XK_LeftDown),
+    ("XF86RootMenu", XF86XK_RootMenu),
+    ("XF86MediaTopMenu", XF86XK_MediaTopMenu),
+    ("XF86Numeric11", XF86XK_Numeric11),
+    ("XF86Numeric12", XF86XK_Numeric12),
+    ("XF86AudioDesc", XF86XK_AudioDesc),
+    ("XF863DMode", XF86XK_3DMode),
+    ("XF86NextFavorite", XF86XK_NextFavorite),
+    ("XF86StopRecord", XF86XK_StopRecord),
+    ("XF86PauseRecord", XF86XK_PauseRecord),
+    ("XF86VOD", XF86XK_VOD),
+    ("XF86Unmute", XF86XK_Unmute),
+    ("XF86FastReverse", XF86XK_FastReverse),
+    ("XF86SlowReverse", XF86XK_SlowReverse),
+    ("XF86Data", XF86XK_Data),
+    ("XF86OnScreenKeyboard", XF86XK_OnScreenKeyboard),
+    ("XF86PrivacyScreenToggle", XF86XK_PrivacyScreenToggle),
+    ("XF86SelectiveScreenshot", XF86XK_SelectiveScreenshot),
+    ("XF86Macro1", XF86XK_Macro1),
+    ("XF86Macro2", XF86XK_Macro2),
+    ("XF86Macro3", XF86XK_Macro3),
+    ("XF86Macro4", XF86XK_Macro4),
+    ("XF86Macro5", XF86XK_Macro5),
+    ("XF86Macro6", XF86XK_Macro6),
+    ("XF86Macro7", XF86XK_Macro7),
+    ("XF86Macro8", XF86XK_Macro8),
+    ("XF86Macro9", XF86XK_Macro9),
+    ("XF86Macro10", XF86XK_Macro10),
+    ("XF86Macro11", XF86XK_Macro11),
+    ("XF86Macro12", XF86XK_Macro12),
+    ("XF86Macro13", XF86XK_Macro13),
+    ("XF86Macro14", XF86XK_Macro14),
+    ("XF86Macro15", XF86XK_Macro15),
+    ("XF86Macro16", XF86XK_Macro16),
+    ("XF86Macro17", XF86XK_Macro17),
+    ("XF86Macro18", XF86XK_Macro18),
+    ("XF86Macro19", XF86XK_Macro19),
+    ("XF86Macro20", XF86XK_Macro20),
+    ("XF86Macro21", XF86XK_Macro21),
+    ("XF86Macro22", XF86XK_Macro22),
+    ("XF86Macro23", XF86XK_Macro23),
+    ("XF86Macro24", XF86XK_Macro24),
+    ("XF86Macro25", XF86XK_Macro25),
+    ("XF86Macro26", XF86XK_Macro26),
+    ("XF86Macro27", XF86XK_Macro27),
+    ("XF86Macro28", XF86XK_Macro28),
+    ("XF86Macro29", XF86XK_Macro29),
+    ("XF86Macro30", XF86XK_Macro30),
+    ("XF86MacroRecordStart", XF86XK_MacroRecordStart),
+    ("XF86MacroRecordStop", XF86XK_MacroRecordStop),
+    ("XF86MacroPresetCycle", XF86XK_MacroPresetCycle),
+    ("XF86MacroPreset1", XF86XK_MacroPreset1),
+    ("XF86MacroPreset2", XF86XK_MacroPreset2),
+    ("XF86MacroPreset3", XF86XK_MacroPreset3),
+    ("XF86KbdLcdMenu1", XF86XK_KbdLcdMenu1),
+    ("XF86KbdLcdMenu2", XF86XK_KbdLcdMenu2),
+    ("XF86KbdLcdMenu3", XF86XK_KbdLcdMenu3),
+    ("XF86KbdLcdMenu4", XF86XK_KbdLcdMenu4),
+    ("XF86KbdLcdMenu5", XF86XK_KbdLcdMenu5),
+];
+
+/// The characters of the legacy keysyms outside of Latin-1, sorted by keysym.
+const CHARS: &[(Keysym, char)] = &[
+    (0x01a1, '\u{0104}'),
+    (0x01a2, '\u{02d8}'),
+    (0x01a3, '\u{0141}'),
+    (0x01a5, '\u{013d}'),
+    (0x01a6, '\u{015a}'),
+    (0x01a9, '\u{0160}'),
+    (0x01aa, '\u{015e}'),
+    (0x01ab, '\u{0164}'),
+    (0x01ac, '\u{0179}'),
+    (0x01ae, '\u{017d}'),
+    (0x01af, '\u{017b}'),
+    (0x01b1, '\u{0105}'),
+    (0x01b2, '\u{02db}'),
+    (0x01b3, '\u{0142}'),
+    (0x01b5, '\u{013e}'),
+    (0x01b6, '\u{015b}'),
+    (0x01b7, '\u{02c7}'),
+    (0x01b9, '\u{0161}'),
+    (0x01ba, '\u{015f}'),
+    (0x01bb, '\u{0165}'),
+    (0x01bc, '\u{017a}'),
+    (0x01bd, '\u{02dd}'),
+    (0x01be, '\u{017e}'),
+    (0x01bf, '\u{017c}'),
+    (0x01c0, '\u{0154}'),
+    (0x01c3, '\u{0102}'),
+    (0x01c5, '\u{0139}'),
+    (0x01c6, '\u{0106}'),
+    (0x01c8, '\u{010c}'),
+    (0x01ca, '\u{0118}'),
+    (0x01cc, '\u{011a}'),
+    (0x01cf, '\u{010e}'),
+    (0x01d0, '\u{0110}'),
+    (0x01d1, '\u{0143}'),
+    (0x01d2, '\u{0147}'),
+    (0x01d5, '\u{0150}'),
+    (0x01d8, '\u{0158}'),
+    (0x01d9, '\u{016e}'),
+    (0x01db, '\u{0170}'),
+    (0x01de, '\u{0162}'),
+    (0x01e0, '\u{0155}'),
+    (0x01e3, '\u{0103}'),
+    (0x01e5, '\u{013a}'),
+    (0x01e6, '\u{0107}'),
+    (0x01e8, '\u{010d}'),
+    (0x01ea, '\u{0119}'),
+    (0x01ec, '\u{011b}'),
+    (0x01ef, '\u{010f}'),
+    (0x01f0, '\u{0111}'),
+    (0x01f1, '\u{0144}'),
+    (0x01f2, '\u{0148}'),
+    (0x01f5, '\u{0151}'),
+    (0x01f8, '\u{0159}'),
+    (0x01f9, '\u{016f}'),
+    (0x01fb, '\u{0171}'),
+    (0x01fe, '\u{0163}'),
+    (0x01ff, '\u{02d9}'),
+    (0x02a1, '\u{0126}'),
+    (0x02a6, '\u{0124}'),
+    (0x02a9, '\u{0130}'),
+    (0x02ab, '\u{011e}'),
+    (0x02ac, '\u{0134}'),
+    (0x02b1, '\u{0127}'),
+    (0x02b6, '\u{0125}'),
+    (0x02b9, '\u{0131}'),
+    (0x02bb, '\u{011f}'),
+    (0x02bc, '\u{0135}'),
+    (0x02c5, '\u{010a}'),
+    (0x02c6, '\u{0108}'),
+    (0x02d5, '\u{0120}'),
+    (0x02d8, '\u{011c}'),
+    (0x02dd, '\u{016c}'),
+    (0x02de, '\u{015c}'),
+    (0x02e5, '\u{010b}'),
+    (0x02e6, '\u{0109}'),
+    (0x02f5, '\u{0121}'),
+    (0x02f8, '\u{011d}'),
+    (0x02fd, '\u{016d}'),
+    (0x02fe, '\u{015d}'),
+    (0x03a2, '\u{0138}'),
+    (0x03a3, '\u{0156}'),
+    (0x03a5, '\u{0128}'),
+    (0x03a6, '\u{013b}'),
+    (0x03aa, '\u{0112}'),
+    (0x03ab, '\u{0122}'),
+    (0x03ac, '\u{0166}'),
+    (0x03b3, '\u{0157}'),
+    (0x03b5, '\u{0129}'),
+    (0x03b6, '\u{013c}'),
+    (0x03ba, '\u{0113}'),
+    (0x03bb, '\u{0123}'),
+    (0x03bc, '\u{0167}'),
+    (0x03bd, '\u{014a}'),
+    (0x03bf, '\u{014b}'),
+    (0x03c0, '\u{0100}'),
+    (0x03c7, '\u{012e}'),
+    (0x03cc, '\u{0116}'),
+    (0x03cf, '\u{012a}'),
+    (0x03d1, '\u{0145}'),
+    (0x03d2, '\u{014c}'),
+    (0x03d3, '\u{0136}'),
+    (0x03d9, '\u{0172}'),
+    (0x03dd, '\u{0168}'),
+    (0x03de, '\u{016a}'),
+    (0x03e0, '\u{0101}'),
+    (0x03e7, '\u{012f}'),
+    (0x03ec, '\u{0117}'),
+    (0x03ef, '\u{012b}'),
+    (0x03f1, '\u{0146}'),
+    (0x03f2, '\u{014d}'),
+    (0x03f3, '\u{0137}'),
+    (0x03f9, '\u{0173}'),
+    (0x03fd, '\u{0169}'),
+    (0x03fe, '\u{016b}'),
+    (0x047e, '\u{203e}'),
+    (0x04a1, '\u{3002}'),
+    (0x04a2, '\u{300c}'),
+    (0x04a3, '\u{300d}'),
+    (0x04a4, '\u{3001}'),
+    (0x04a5, '\u{30fb}'),
+    (0x04a6, '\u{30f2}'),
+    (0x04a7, '\u{30a1}'),
+    (0x04a8, '\u{30a3}'),
+    (0x04a9, '\u{30a5}'),
+    (0x04aa, '\u{30a7}'),
+    (0x04ab, '\u{30a9}'),
+    (0x04ac, '\u{30e3}'),
+    (0x04ad, '\u{30e5}'),
+    (0x04ae, '\u{30e7}'),
+    (0x04af, '\u{30c3}'),
+    (0x04b0, '\u{30fc}'),
+    (0x04b1, '\u{30a2}'),
+    (0x04b2, '\u{30a4}'),
+    (0x04b3, '\u{30a6}'),
+    (0x04b4, '\u{30a8}'),
+    (0x04b5, '\u{30aa}'),
+    (0x04b6, '\u{30ab}'),
+    (0x04b7, '\u{30ad}'),
+    (0x04b8, '\u{30af}'),
+    (0x04b9, '\u{30b1}'),
+    (0x04ba, '\u{30b3}'),
+    (0x04bb, '\u{30b5}'),
+    (0x04bc, '\u{30b7}'),
+    (0x04bd, '\u{30b9}'),
+    (0x04be, '\u{30bb}'),
+    (0x04bf, '\u{30bd}'),
+    (0x04c0, '\u{30bf}'),
+    (0x04c1, '\u{30c1}'),
+    (0x04c2, '\u{30c4}'),
+    (0x04c3, '\u{30c6}'),
+    (0x04c4, '\u{30c8}'),
+    (0x04c5, '\u{30ca}'),
+    (0x04c6, '\u{30cb}'),
+    (0x04c7, '\u{30cc}'),
+    (0x04c8, '\u{30cd}'),
+    (0x04c9, '\u{30ce}'),
+    (0x04ca, '\u{30cf}'),
+    (0x04cb, '\u{30d2}'),
+    (0x04cc, '\u{30d5}'),
+    (0x04cd, '\u{30d8}'),
+    (0x04ce, '\u{30db}'),
+    (0x04cf, '\u{30de}'),
+    (0x04d0, '\u{30df}'),
+    (0x04d1, '\u{30e0}'),
+    (0x04d2, '\u{30e1}'),
+    (0x04d3, '\u{30e2}'),
+    (0x04d4, '\u{30e4}'),
+    (0x04d5, '\u{30e6}'),
+    (0x04d6, '\u{30e8}'),
+    (0x04d7, '\u{30e9}'),
+    (0x04d8, '\u{30ea}'),
+    (0x04d9, '\u{30eb}'),
+    (0x04da, '\u{30ec}'),
+    (0x04db, '\u{30ed}'),
+    (0x04dc, '\u{30ef}'),
+    (0x04dd, '\u{30f3}'),
+    (0x04de, '\u{309b}'),
+    (0x04df, '\u{309c}'),
+    (0x05ac, '\u{060c}'),
+    (0x05bb, '\u{061b}'),
+    (0x05bf, '\u{061f}'),
+    (0x05c1, '\u{0621}'),
+    (0x05c2, '\u{0622}'),
+    (0x05c3, '\u{0623}'),
+    (0x05c4, '\u{0624}'),
+    (0x05c5, '\u{0625}'),
+    (0x05c6, '\u{0626}'),
+    (0x05c7, '\u{0627}'),
+    (0x05c8, '\u{0628}'),
+    (0x05c9, '\u{0629}'),
+    (0x05ca, '\u{062a}'),
+    (0x05cb, '\u{062b}'),
+    (0x05cc, '\u{062c}'),
+    (0x05cd, '\u{062d}'),
+    (0x05ce, '\u{062e}'),
+    (0x05cf, '\u{062f}'),
+    (0x05d0, '\u{0630}'),
+    (0x05d1, '\u{0631}'),
+    (0x05d2, '\u{0632}'),
+    (0x05d3, '\u{0633}'),
+    (0x05d4, '\u{0634}'),
+    (0x05d5, '\u{0635}'),
+    (0x05d6, '\u{0636}'),
+    (0x05d7, '\u{0637}'),
+    (0x05d8, '\u{0638}'),
+    (0x05d9, '\u{0639}'),
+    (0x05da, '\u{063a}'),
+    (0x05e0, '\u{0640}'),
+    (0x05e1, '\u{0641}'),
+    (0x05e2, '\u{0642}'),
+    (0x05e3, '\u{0643}'),
+    (0x05e4, '\u{0644}'),
+    (0x05e5, '\u{0645}'),
+    (0x05e6, '\u{0646}'),
+    (0x05e7, '\u{0647}'),
+    (0x05e8, '\u{0648}'),
+    (0x05e9, '\u{0649}'),
+    (0x05ea, '\u{064a}'),
+    (0x05eb, '\u{064b}'),
+    (0x05ec, '\u{064c}'),
+    (0x05ed, '\u{064d}'),
+    (0x05ee, '\u{064e}'),
+    (0x05ef, '\u{064f}'),
+    (0x05f0, '\u{0650}'),
+    (0x05f1, '\u{0651}'),
+    (0x05f2, '\u{0652}'),
+    (0x06a1, '\u{0452}'),
+    (0x06a2, '\u{0453}'),
+    (0x06a3, '\u{0451}'),
+    (0x06a4, '\u{0454}'),
+    (0x06a5, '\u{0455}'),
+    (0x06a6, '\u{0456}'),
+    (0x06a7, '\u{0457}'),
+    (0x06a8, '\u{0458}'),
+    (0x06a9, '\u{0459}'),
+    (0x06aa, '\u{045a}'),
+    (0x06ab, '\u{045b}'),
+    (0x06ac, '\u{045c}'),
+    (0x06ad, '\u{0491}'),
+    (0x06ae, '\u{045e}'),
+    (0x06af, '\u{045f}'),
+    (0x06b0, '\u{2116}'),
+    (0x06b1, '\u{0402}'),
+    (0x06b2, '\u{0403}'),
+    (0x06b3, '\u{0401}'),
+    (0x06b4, '\u{0404}'),
+    (0x06b5, '\u{0405}'),
+    (0x06b6, '\u{0406}'),
+    (0x06b7, '\u{0407}'),
+    (0x06b8, '\u{0408}'),
+    (0x06b9, '\u{0409}'),
+    (0x06ba, '\u{040a}'),
+    (0x06bb, '\u{040b}'),
+    (0x06bc, '\u{040c}'),
+    (0x06bd, '\u{0490}'),
+    (0x06be, '\u{040e}'),
+    (0x06bf, '\u{040f}'),
+    (0x06c0, '\u{044e}'),
+    (0x06c1, '\u{0430}'),
+    (0x06c2, '\u{0431}'),
+    (0x06c3, '\u{0446}'),
+    (0x06c4, '\u{0434}'),
+    (0x06c5, '\u{0435}'),
+    (0x06c6, '\u{0444}'),
+    (0x06c7, '\u{0433}'),
+    (0x06c8, '\u{0445}'),
+    (0x06c9, '\u{0438}'),
+    (0x06ca, '\u{0439}'),
+    (0x06cb, '\u{043a}'),
+    (0x06cc, '\u{043b}'),
+    (0x06cd, '\u{043c}'),
+    (0x06ce, '\u{043d}'),
+    (0x06cf, '\u{043e}'),
+    (0x06d0, '\u{043f}'),
+    (0x06d1, '\u{044f}'),
+    (0x06d2, '\u{0440}'),
+    (0x06d3, '\u{0441}'),
+    (0x06d4, '\u{0442}'),
+    (0x06d5, '\u{0443}'),
+    (0x06d6, '\u{0436}'),
+    (0x06d7, '\u{0432}'),
+    (0x06d8, '\u{044c}'),
+    (0x06d9, '\u{044b}'),
+    (0x06da, '\u{0437}'),
+    (0x06db, '\u{0448}'),
+    (0x06dc, '\u{044d}'),
+    (0x06dd, '\u{0449}'),
+    (0x06de, '\u{0447}'),
+    (0x06df, '\u{044a}'),
+    (0x06e0, '\u{042e}'),
+    (0x06e1, '\u{0410}'),
+    (0x06e2, '\u{0411}'),
+    (0x06e3, '\u{0426}'),
+    (0x06e4, '\u{0414}'),
+    (0x06e5, '\u{0415}'),
+    (0x06e6, '\u{0424}'),
+    (0x06e7, '\u{0413}'),
+    (0x06e8, '\u{0425}'),
+    (0x06e9, '\u{0418}'),
+    (0x06ea, '\u{0419}'),
+    (0x06eb, '\u{041a}'),
+    (0x06ec, '\u{041b}'),
+    (0x06ed, '\u{041c}'),
+    (0x06ee, '\u{041d}'),
+    (0x06ef, '\u{041e}'),
+    (0x06f0, '\u{041f}'),
+    (0x06f1, '\u{042f}'),
+    (0x06f2, '\u{0420}'),
+    (0x06f3, '\u{0421}'),
+    (0x06f4, '\u{0422}'),
+    (0x06f5, '\u{0423}'),
+    (0x06f6, '\u{0416}'),
+    (0x06f7, '\u{0412}'),
+    (0x06f8, '\u{042c}'),
+    (0x06f9, '\u{042b}'),
+    (0x06fa, '\u{0417}'),
+    (0x06fb, '\u{0428}'),
+    (0x06fc, '\u{042d}'),
+    (0x06fd, '\u{0429}'),
+    (0x06fe, '\u{0427}'),
+    (0x06ff, '\u{042a}'),
+    (0x07a1, '\u{0386}'),
+    (0x07a2, '\u{0388}'),
+    (0x07a3, '\u{0389}'),
+    (0x07a4, '\u{038a}'),
+    (0x07a5, '\u{03aa}'),
+    (0x07a7, '\u{038c}'),
+    (0x07a8, '\u{038e}'),
+    (0x07a9, '\u{03ab}'),
+    (0x07ab, '\u{038f}'),
+    (0x07ae, '\u{0385}'),
+    (0x07af, '\u{2015}'),
+    (0x07b1, '\u{03ac}'),
+    (0x07b2, '\u{03ad}'),
+    (0x07b3, '\u{03ae}'),
+    (0x07b4, '\u{03af}'),
+    (0x07b5, '\u{03ca}'),
+    (0x07b6, '\u{0390}'),
+    (0x07b7, '\u{03cc}'),
+    (0x07b8, '\u{03cd}'),
+    (0x07b9, '\u{03cb}'),
+    (0x07ba, '\u{03b0}'),
+    (0x07bb, '\u{03ce}'),
+    (0x07c1, '\u{0391}'),
+    (0x07c2, '\u{0392}'),
+    (0x07c3, '\u{0393}'),
+    (0x07c4, '\u{0394}'),
+    (0x07c5, '\u{0395}'),
+    (0x07c6, '\u{0396}'),
+    (0x07c7, '\u{0397}'),
+    (0x07c8, '\u{0398}'),
+    (0x07c9, '\u{0399}'),
+    (0x07ca, '\u{039a}'),
+    (0x07cb, '\u{039b}'),
+    (0x07cc, '\u{039c}'),
+    (0x07cd, '\u{039d}'),
+    (0x07ce, '\u{039e}'),
+    (0x07cf, '\u{039f}'),
+    (0x07d0, '\u{03a0}'),
+    (0x07d1, '\u{03a1}'),
+    (0x07d2, '\u{03a3}'),
+    (0x07d4, '\u{03a4}'),
+    (0x07d5, '\u{03a5}'),
+    (0x07d6, '\u{03a6}'),
+    (0x07d7, '\u{03a7}'),
+    (0x07d8, '\u{03a8}'),
+    (0x07d9, '\u{03a9}'),
+    (0x07e1, '\u{03b1}'),
+    (0x07e2, '\u{03b2}'),
+    (0x07e3, '\u{03b3}'),
+    (0x07e4, '\u{03b4}'),
+    (0x07e5, '\u{03b5}'),
+    (0x07e6, '\u{03b6}'),
+    (0x07e7, '\u{03b7}'),
+    (0x07e8, '\u{03b8}'),
+    (0x07e9, '\u{03b9}'),
+    (0x07ea, '\u{03ba}'),
+    (0x07eb, '\u{03bb}'),
+    (0x07ec, '\u{03bc}'),
+    (0x07ed, '\u{03bd}'),
+    (0x07ee, '\u{03be}'),
+    (0x07ef, '\u{03bf}'),
+    (0x07f0, '\u{03c0}'),
+    (0x07f1, '\u{03c1}'),
+    (0x07f2, '\u{03c3}'),
+    (0x07f3, '\u{03c2}'),
+    (0x07f4, '\u{03c4}'),
+    (0x07f5, '\u{03c5}'),
+    (0x07f6, '\u{03c6}'),
+    (0x07f7, '\u{03c7}'),
+    (0x07f8, '\u{03c8}'),
+    (0x07f9, '\u{03c9}'),
+    (0x08a1, '\u{23b7}'),
+    (0x08a2, '\u{250c}'),
+    (0x08a3, '\u{2500}'),
+    (0x08a4, '\u{2320}'),
+    (0x08a5, '\u{2321}'),
+    (0x08a6, '\u{2502}'),
+    (0x08a7, '\u{23a1}'),
+    (0x08a8, '\u{23a3}'),
+    (0x08a9, '\u{23a4}'),
+    (0x08aa, '\u{23a6}'),
+    (0x08ab, '\u{239b}'),
+    (0x08ac, '\u{239d}'),
+    (0x08ad, '\u{239e}'),
+    (0x08ae, '\u{23a0}'),
+    (0x08af, '\u{23a8}'),
+    (0x08b0, '\u{23ac}'),
+    (0x08bc, '\u{2264}'),
+    (0x08bd, '\u{2260}'),
+    (0x08be, '\u{2265}'),
+    (0x08bf, '\u{222b}'),
+    (0x08c0, '\u{2234}'),
+    (0x08c1, '\u{221d}'),
+    (0x08c2, '\u{221e}'),
+    (0x08c5, '\u{2207}'),
+    (0x08c8, '\u{223c}'),
+    (0x08c9, '\u{2243}'),
+    (0x08cd, '\u{21d4}'),
+    (0x08ce, '\u{21d2}'),
+    (0x08cf, '\u{2261}'),
+    (0x08d6, '\u{221a}'),
+    (0x08da, '\u{2282}'),
+    (0x08db, '\u{2283}'),
+    (0x08dc, '\u{2229}'),
+    (0x08dd, '\u{222a}'),
+    (0x08de, '\u{2227}'),
+    (0x08df, '\u{2228}'),
+    (0x08ef, '\u{2202}'),
+    (0x08f6, '\u{0192}'),
+    (0x08fb, '\u{2190}'),
+    (0x08fc, '\u{2191}'),
+    (0x08fd, '\u{2192}'),
+    (0x08fe, '\u{2193}'),
+    (0x09e0, '\u{25c6}'),
+    (0x09e1, '\u{2592}'),
+    (0x09e2, '\u{2409}'),
+    (0x09e3, '\u{240c}'),
+    (0x09e4, '\u{240d}'),
+    (0x09e5, '\u{240a}'),
+    (0x09e8, '\u{2424}'),
+    (0x09e9, '\u{240b}'),
+    (0x09ea, '\u{2518}'),
+    (0x09eb, '\u{2510}'),
+    (0x09ec, '\u{250c}'),
+    (0x09ed, '\u{2514}'),
+    (0x09ee, '\u{253c}'),
+    (0x09ef, '\u{23ba}'),
+    (0x09f0, '\u{23bb}'),
+    (0x09f1, '\u{2500}'),
+    (0x09f2, '\u{23bc}'),
+    (0x09f3, '\u{23bd}'),
+    (0x09f4, '\u{251c}'),
+    (0x09f5, '\u{2524}'),
+    (0x09f6, '\u{2534}'),
+    (0x09f7, '\u{252c}'),
+    (0x09f8, '\u{2502}'),
+    (0x0aa1, '\u{2003}'),
+    (0x0aa2, '\u{2002}'),
+    (0x0aa3, '\u{2004}'),
+    (0x0aa4, '\u{2005}'),
+    (0x0aa5, '\u{2007}'),
+    (0x0aa6, '\u{2008}'),
+    (0x0aa7, '\u{2009}'),
+    (0x0aa8, '\u{200a}'),
+    (0x0aa9, '\u{2014}'),
+    (0x0aaa, '\u{2013}'),
+    (0x0aac, '\u{2423}'),
+    (0x0aae, '\u{2026}'),
+    (0x0aaf, '\u{2025}'),
+    (0x0ab0, '\u{2153}'),
+    (0x0ab1, '\u{2154}'),
+    (0x0ab2, '\u{2155}'),
+    (0x0ab3, '\u{2156}'),
+    (0x0ab4, '\u{2157}'),
+    (0x0ab5, '\u{2158}'),
+    (0x0ab6, '\u{2159}'),
+    (0x0ab7, '\u{215a}'),
+    (0x0ab8, '\u{2105}'),
+    (0x0abb, '\u{2012}'),
+    (0x0abc, '\u{2329}'),
+    (0x0abd, '\u{002e}'),
+    (0x0abe, '\u{232a}'),
+    (0x0ac3, '\u{215b}'),
+    (0x0ac4, '\u{215c}'),
+    (0x0ac5, '\u{215d}'),
+    (0x0ac6, '\u{215e}'),
+    (0x0ac9, '\u{2122}'),
+    (0x0aca, '\u{2613}'),
+    (0x0acc, '\u{25c1}'),
+    (0x0acd, '\u{25b7}'),
+    (0x0ace, '\u{25cb}'),
+    (0x0acf, '\u{25af}'),
+    (0x0ad0, '\u{2018}'),
+    (0x0ad1, '\u{2019}'),
+    (0x0ad2, '\u{201c}'),
+    (0x0ad3, '\u{201d}'),
+    (0x0ad4, '\u{211e}'),
+    (0x0ad5, '\u{2030}'),
+    (0x0ad6, '\u{2032}'),
+    (0x0ad7, '\u{2033}'),
+    (0x0ad9, '\u{271d}'),
+    (0x0adb, '\u{25ac}'),
+    (0x0adc, '\u{25c0}'),
+    (0x0add, '\u{25b6}'),
+    (0x0ade, '\u{25cf}'),
+    (0x0adf, '\u{25ae}'),
+    (0x0ae0, '\u{25e6}'),
+    (0x0ae1, '\u{25ab}'),
+    (0x0ae2, '\u{25ad}'),
+    (0x0ae3, '\u{25b3}'),
+    (0x0ae4, '\u{25bd}'),
+    (0x0ae5, '\u{2606}'),
+    (0x0ae6, '\u{2022}'),
+    (0x0ae7, '\u{25aa}'),
+    (0x0ae8, '\u{25b2}'),
+    (0x0ae9, '\u{25bc}'),
+    (0x0aea, '\u{261c}'),
+    (0x0aeb, '\u{261e}'),
+    (0x0aec, '\u{2663}'),
+    (0x0aed, '\u{2666}'),
+    (0x0aee, '\u{2665}'),
+    (0x0af0, '\u{2720}'),
+    (0x0af1, '\u{2020}'),
+    (0x0af2, '\u{2021}'),
+    (0x0af3, '\u{2713}'),
+    (0x0af4, '\u{2717}'),
+    (0x0af5, '\u{266f}'),
+    (0x0af6, '\u{266d}'),
+    (0x0af7, '\u{2642}'),
+    (0x0af8, '\u{2640}'),
+    (0x0af9, '\u{260e}'),
+    (0x0afa, '\u{2315}'),
+    (0x0afb, '\u{2117}'),
+    (0x0afc, '\u{2038}'),
+    (0x0afd, '\u{201a}'),
+    (0x0afe, '\u{201e}'),
+    (0x0ba3, '\u{003c}'),
+    (0x0ba6, '\u{003e}'),
+    (0x0ba8, '\u{2228}'),
+    (0x0ba9, '\u{2227}'),
+    (0x0bc0, '\u{00af}'),
+    (0x0bc2, '\u{22a4}'),
+    (0x0bc3, '\u{2229}'),
+    (0x0bc4, '\u{230a}'),
+    (0x0bc6, '\u{005f}'),
+    (0x0bca, '\u{2218}'),
+    (0x0bcc, '\u{2395}'),
+    (0x0bce, '\u{22a5}'),
+    (0x0bcf, '\u{25cb}'),
+    (0x0bd3, '\u{2308}'),
+    (0x0bd6, '\u{222a}'),
+    (0x0bd8, '\u{2283}'),
+    (0x0bda, '\u{2282}'),
+    (0x0bdc, '\u{22a3}'),
+    (0x0bfc, '\u{22a2}'),
+    (0x0cdf, '\u{2017}'),
+    (0x0ce0, '\u{05d0}'),
+    (0x0ce1, '\u{05d1}'),
+    (0x0ce2, '\u{05d2}'),
+    (0x0ce3, '\u{05d3}'),
+    (0x0ce4, '\u{05d4}'),
+    (0x0ce5, '\u{05d5}'),
+    (0x0ce6, '\u{05d6}'),
+    (0x0ce7, '\u{05d7}'),
+    (0x0ce8, '\u{05d8}'),
+    (0x0ce9, '\u{05d9}'),
+    (0x0cea, '\u{05da}'),
+    (0x0ceb, '\u{05db}'),
+    (0x0cec, '\u{05dc}'),
+    (0x0ced, '\u{05dd}'),
+    (0x0cee, '\u{05de}'),
+    (0x0cef, '\u{05df}'),
+    (0x0cf0, '\u{05e0}'),
+    (0x0cf1, '\u{05e1}'),
+    (0x0cf2, '\u{05e2}'),
+    (0x0cf3, '\u{05e3}'),
+    (0x0cf4, '\u{05e4}'),
+    (0x0cf5, '\u{05e5}'),
+    (0x0cf6, '\u{05e6}'),
+    (0x0cf7, '\u{05e7}'),
+    (0x0cf8, '\u{05e8}'),
+    (0x0cf9, '\u{05e9}'),
+    (0x0cfa, '\u{05ea}'),
+    (0x0da1, '\u{0e01}'),
+    (0x0da2, '\u{0e02}'),
+    (0x0da3, '\u{0e03}'),
+    (0x0da4, '\u{0e04}'),
+    (0x0da5, '\u{0e05}'),
+    (0x0da6, '\u{0e06}'),
+    (0x0da7, '\u{0e07}'),
+    (0x0da8, '\u{0e08}'),
+    (0x0da9, '\u{0e09}'),
+    (0x0daa, '\u{0e0a}'),
+    (0x0dab, '\u{0e0b}'),
+    (0x0dac, '\u{0e0c}'),
+    (0x0dad, '\u{0e0d}'),
+    (0x0dae, '\u{0e0e}'),
+    (0x0daf, '\u{0e0f}'),
+    (0x0db0, '\u{0e10}'),
+    (0x0db1, '\u{0e11}'),
+    (0x0db2, '\u{0e12}'),
+    (0x0db3, '\u{0e13}'),
+    (0x0db4, '\u{0e14}'),
+    (0x0db5, '\u{0e15}'),
+    (0x0db6, '\u{0e16}'),
+    (0x0db7, '\u{0e17}'),
+    (0x0db8, '\u{0e18}'),
+    (0x0db9, '\u{0e19}'),
+    (0x0dba, '\u{0e1a}'),
+    (0x0dbb, '\u{0e1b}'),
+    (0x0dbc, '\u{0e1c}'),
+    (0x0dbd, '\u{0e1d}'),
+    (0x0dbe, '\u{0e1e}'),
+    (0x0dbf, '\u{0e1f}'),
+    (0x0dc0, '\u{0e20}'),
+    (0x0dc1, '\u{0e21}'),
+    (0x0dc2, '\u{0e22}'),
+    (0x0dc3, '\u{0e23}'),
+    (0x0dc4, '\u{0e24}'),
+    (0x0dc5, '\u{0e25}'),
+    (0x0dc6, '\u{0e26}'),
+    (0x0dc7, '\u{0e27}'),
+    (0x0dc8, '\u{0e28}'),
+    (0x0dc9, '\u{0e29}'),
+    (0x0dca, '\u{0e2a}'),
+    (0x0dcb, '\u{0e2b}'),
+    (0x0dcc, '\u{0e2c}'),
+    (0x0dcd, '\u{0e2d}'),
+    (0x0dce, '\u{0e2e}'),
+    (0x0dcf, '\u{0e2f}'),
+    (0x0dd0, '\u{0e30}'),
+    (0x0dd1, '\u{0e31}'),
+    (0x0dd2, '\u{0e32}'),
+    (0x0dd3, '\u{0e33}'),
+    (0x0dd4, '\u{0e34}'),
+    (0x0dd5, '\u{0e35}'),
+    (0x0dd6, '\u{0e36}'),
+    (0x0dd7, '\u{0e37}'),
+    (0x0dd8, '\u{0e38}'),
+    (0x0dd9, '\u{0e39}'),
+    (0x0dda, '\u{0e3a}'),
+    (0x0ddf, '\u{0e3f}'),
+    (0x0de0, '\u{0e40}'),
+    (0x0de1, '\u{0e41}'),
+    (0x0de2, '\u{0e42}'),
+    (0x0de3, '\u{0e43}'),
+    (0x0de4, '\u{0e44}'),
+    (0x0de5, '\u{0e45}'),
+    (0x0de6, '\u{0e46}'),
+    (0x0de7, '\u{0e47}'),
+    (0x0de8, '\u{0e48}'),
+    (0x0de9, '\u{0e49}'),
+    (0x0dea, '\u{0e4a}'),
+    (0x0deb, '\u{0e4b}'),
+    (0x0dec, '\u{0e4c}'),
+    (0x0ded, '\u{0e4d}'),
+    (0x0df0, '\u{0e50}'),
+    (0x0df1, '\u{0e51}'),
+    (0x0df2, '\u{0e52}'),
+    (0x0df3, '\u{0e53}'),
+    (0x0df4, '\u{0e54}'),
+    (0x0df5, '\u{0e55}'),
+    (0x0df6, '\u{0e56}'),
+    (0x0df7, '\u{0e57}'),
+    (0x0df8, '\u{0e58}'),
+    (0x0df9, '\u{0e59}'),
+    (0x0ea1, '\u{3131}'),
+    (0x0ea2, '\u{3132}'),
+    (0x0ea3, '\u{3133}'),
+    (0x0ea4, '\u{3134}'),
+    (0x0ea5, '\u{3135}'),
+    (0x0ea6, '\u{3136}'),
+    (0x0ea7, '\u{3137}'),
+    (0x0ea8, '\u{3138}'),
+    (0x0ea9, '\u{3139}'),
+    (0x0eaa, '\u{313a}'),
+    (0x0eab, '\u{313b}'),
+    (0x0eac, '\u{313c}'),
+    (0x0ead, '\u{313d}'),
+    (0x0eae, '\u{313e}'),
+    (0x0eaf, '\u{313f}'),
+    (0x0eb0, '\u{3140}'),
+    (0x0eb1, '\u{3141}'),
+    (0x0eb2, '\u{3142}'),
+    (0x0eb3, '\u{3143}'),
+    (0x0eb4, '\u{3144}'),
+    (0x0eb5, '\u{3145}'),
+    (0x0eb6, '\u{3146}'),
+    (0x0eb7, '\u{3147}'),
+    (0x0eb8, '\u{3148}'),
+    (0x0eb9, '\u{3149}'),
+    (0x0eba, '\u{314a}'),
+    (0x0ebb, '\u{314b}'),
+    (0x0ebc, '\u{314c}'),
+    (0x0ebd, '\u{314d}'),
+    (0x0ebe, '\u{314e}'),
+    (0x0ebf, '\u{314f}'),
+    (0x0ec0, '\u{3150}'),
+    (0x0ec1, '\u{3151}'),
+    (0x0ec2, '\u{3152}'),
+    (0x0ec3, '\u{3153}'),
+    (0x0ec4, '\u{3154}'),
+    (0x0ec5, '\u{3155}'),
+    (0x0ec6, '\u{3156}'),
+    (0x0ec7, '\u{3157}'),
+    (0x0ec8, '\u{3158}'),
+    (0x0ec9, '\u{3159}'),
+    (0x0eca, '\u{315a}'),
+    (0x0ecb, '\u{315b}'),
+    (0x0ecc, '\u{315c}'),
+    (0x0ecd, '\u{315d}'),
+    (0x0ece, '\u{315e}'),
+    (0x0ecf, '\u{315f}'),
+    (0x0ed0, '\u{3160}'),
+    (0x0ed1, '\u{3161}'),
+    (0x0ed2, '\u{3162}'),
+    (0x0ed3, '\u{3163}'),
+    (0x0ed4, '\u{11a8}'),
+    (0x0ed5, '\u{11a9}'),
+    (0x0ed6, '\u{11aa}'),
+    (0x0ed7, '\u{11ab}'),
+    (0x0ed8, '\u{11ac}'),
+    (0x0ed9, '\u{11ad}'),
+    (0x0eda, '\u{11ae}'),
+    (0x0edb, '\u{11af}'),
+    (0x0edc, '\u{11b0}'),
+    (0x0edd, '\u{11b1}'),
+    (0x0ede, '\u{11b2}'),
+    (0x0edf, '\u{11b3}'),
+    (0x0ee0, '\u{11b4}'),
+    (0x0ee1, '\u{11b5}'),
+    (0x0ee2, '\u{11b6}'),
+    (0x0ee3, '\u{11b7}'),
+    (0x0ee4, '\u{11b8}'),
+    (0x0ee5, '\u{11b9}'),
+    (0x0ee6, '\u{11ba}'),
+    (0x0ee7, '\u{11bb}'),
+    (0x0ee8, '\u{11bc}'),
+    (0x0ee9, '\u{11bd}'),
+    (0x0eea, '\u{11be}'),
+    (0x0eeb, '\u{11bf}'),
+    (0x0eec, '\u{11c0}'),
+    (0x0eed, '\u{11c1}'),
+    (0x0eee, '\u{11c2}'),
+    (0x0eef, '\u{316d}'),
+    (0x0ef0, '\u{3171}'),
+    (0x0ef1, '\u{3178}'),
+    (0x0ef2, '\u{317f}'),
+    (0x0ef3, '\u{3181}'),
+    (0x0ef4, '\u{3184}'),
+    (0x0ef5, '\u{3186}'),
+    (0x0ef6, '\u{318d}'),
+    (0x0ef7, '\u{318e}'),
+    (0x0ef8, '\u{11eb}'),
+    (0x0ef9, '\u{11f0}'),
+    (0x0efa, '\u{11f9}'),
+    (0x0eff, '\u{20a9}'),
+    (0x13bc, '\u{0152}'),
+    (0x13bd, '\u{0153}'),
+    (0x13be, '\u{0178}'),
+    (0x20ac, '\u{20ac}'),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chars_are_sorted() {
+        assert!(CHARS.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    }
+
+    #[test]
+    fn keysym_to_char_ranges() {
+        // Latin-1.
+        assert_eq!(keysym_to_char(XK_A), Some('A'));
+        assert_eq!(keysym_to_char(0xe9), Some('é'));
+        // Functions typing control characters, and the keypad.
+        assert_eq!(keysym_to_char(XK_BackSpace), Some('\u{8}'));
+        assert_eq!(keysym_to_char(XK_Return), Some('\r'));
+        assert_eq!(keysym_to_char(XK_Delete), Some('\u{7f}'));
+        assert_eq!(keysym_to_char(XK_KP_Space), Some(' '));
+        assert_eq!(keysym_to_char(XK_KP_Enter), Some('\r'));
+        assert_eq!(keysym_to_char(XK_KP_Multiply), Some('*'));
+        assert_eq!(keysym_to_char(XK_KP_Decimal), Some('.'));
+        assert_eq!(keysym_to_char(XK_KP_5), Some('5'));
+        // The legacy table.
+        assert_eq!(keysym_to_char(XK_Greek_alpha), Some('α'));
+        assert_eq!(keysym_to_char(XK_Cyrillic_a), Some('а'));
+        assert_eq!(keysym_to_char(XK_oe), Some('œ'));
+        assert_eq!(keysym_to_char(XK_EuroSign), Some('€'));
+        // The Unicode range, up to its invalid code points.
+        assert_eq!(keysym_to_char(UNICODE_OFFSET + 0x1f600), Some('😀'));
+        assert_eq!(keysym_to_char(UNICODE_OFFSET + 0xd800), None);
+        assert_eq!(keysym_to_char(UNICODE_OFFSET + 0x11_0000), None);
+        // Functions and modifiers.
+        for keysym in [NO_SYMBOL, XK_F1, XK_Shift_L, XK_KP_Home, XK_VoidSymbol] {
+            assert_eq!(keysym_to_char(keysym), None, "keysym {:#x}", keysym);
+        }
+    }
+
+    #[test]
+    fn char_to_keysym_prefers_legacy_keysyms() {
+        assert_eq!(char_to_keysym('a'), XK_a);
+        assert_eq!(char_to_keysym('\r'), XK_Return);
+        assert_eq!(char_to_keysym('\u{1b}'), XK_Escape);
+        assert_eq!(char_to_keysym('€'), XK_EuroSign);
+        assert_eq!(char_to_keysym('α'), XK_Greek_alpha);
+        assert_eq!(char_to_keysym('😀'), UNICODE_OFFSET + 0x1f600);
+        for &(keysym, c) in CHARS {
+            assert_eq!(
+                keysym_to_char(char_to_keysym(c)),
+                Some(c),
+                "keysym {:#x}",
+                keysym
+            );
+        }
+    }
+}
@@ -0,0 +1,69 @@
+pub mod active_window;
+pub mod aio;
+pub mod atom_cache;
+pub mod auth;
+pub mod bigreq;
+pub mod byteorder;
+pub mod capture;
+pub mod clipboard;
+pub mod composite;
+pub mod connection;
+pub mod cookie;
+pub mod damage;
+pub mod dispatcher;
+pub mod dpms;
+pub mod dri3;
+pub mod errors;
+pub mod event;
+pub mod event_source;
+pub mod ewmh;
+pub mod expose;
+pub mod extension;
+#[doc(hidden)]
+pub mod fuzz;
+pub mod glx;
+pub mod icccm;
+pub mod keyboard;
+pub mod keysym;
+pub mod monitors;
+pub mod mouse;
+pub mod overlay;
+pub mod pointer;
+pub mod present;
+pub mod protocol;
+pub mod protocol_state;
+pub mod randr;
+pub mod record;
+pub mod recorder;
+pub mod region;
+pub mod render;
+pub mod reply;
+pub mod request;
+pub mod screensaver;
+pub mod security;
+pub mod shape;
+pub mod shared;
+pub mod shm;
+pub mod testing;
+#[cfg(feature = "reader-thread")]
+pub mod threaded;
+#[cfg(feature = "trace")]
+pub mod trace;
+pub mod transport;
+pub mod tray;
+#[cfg(all(target_os = "linux", feature = "io-uring"))]
+pub mod uring;
+pub mod utils;
+pub mod waker;
+pub mod wallpaper;
+pub mod window_info;
+pub mod xcursor;
+pub mod xdnd;
+pub mod xfixes;
+pub mod xinerama;
+pub mod xinput;
+pub mod xkb;
+pub mod xres;
+pub mod xresources;
+pub mod xtest;
+pub mod xv;
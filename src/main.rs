@@ -1,17 +1,44 @@
-mod connection;
-mod auth;
-mod byteorder;
-mod utils;
-mod errors;
-mod protocol;
+//! `mousetrap`, command line tools to inspect and drive an X server.
 
-use connection::Connection;
+mod cli;
 
-fn main() {
-    let _conn = match Connection::init() {
-        Ok(conn) => conn,
-        Err(err) => {
-            panic!("Connection failed: {}", err)
+use clap::Parser;
+use std::process::ExitCode;
+
+/// Prints the warnings of the library, e.g. the errors of unchecked requests.
+struct StderrLogger;
+
+impl log::Log for StderrLogger {
+    fn enabled(&self, metadata: &log::Metadata<'_>) -> bool {
+        metadata.level() <= log::Level::Warn
+    }
+
+    fn log(&self, record: &log::Record<'_>) {
+        if self.enabled(record.metadata()) {
+            let level = match record.level() {
+                log::Level::Error => "error",
+                _ => "warning",
+            };
+            eprintln!("mousetrap: {}: {}", level, record.args());
         }
-    };
+    }
+
+    fn flush(&self) {}
+}
+
+fn main() -> ExitCode {
+    if log::set_logger(&StderrLogger).is_ok() {
+        log::set_max_level(log::LevelFilter::Warn);
+    }
+    let cli = cli::Cli::parse();
+    match cli.run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => match err.downcast_ref::<cli::Exit>() {
+            Some(exit) => ExitCode::from(exit.0),
+            None => {
+                eprintln!("mousetrap: {}", err);
+                ExitCode::FAILURE
+            }
+        },
+    }
 }
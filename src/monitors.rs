@@ -0,0 +1,262 @@
+//! The monitors of the screen, for tools that place things per monitor.
+//!
+//! The root window spans every monitor. [Monitors] tells which part of it each
+//! monitor shows, from RandR when the server has it and from Xinerama
+//! otherwise, so that global root window coordinates can be turned into
+//! coordinates of a monitor and back.
+//!
+//! ```no_run
+//! use mousetrap::connection::Connection;
+//! use mousetrap::monitors::Monitors;
+//!
+//! let mut conn = Connection::init().unwrap();
+//! let monitors = Monitors::query(&mut conn).unwrap();
+//! let (x, y) = conn.pointer_position().unwrap();
+//! if let Some(monitor) = monitors.monitor_at(x, y) {
+//!     println!("{:?} at {:?}", monitor.name, monitor.to_local(x, y));
+//! }
+//! ```
+
+use crate::connection::Connection;
+use crate::errors::ConnectionError;
+use crate::protocol::{Rectangle, Window};
+use crate::randr::Randr;
+use crate::xinerama::Xinerama;
+
+/// Millimeters in an inch.
+const MM_PER_INCH: f64 = 25.4;
+
+/// A monitor and the part of the root window it shows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Monitor {
+    /// The name RandR gives the monitor, e.g. "HDMI-1".
+    pub name: Option<String>,
+    /// Whether the monitor is the primary one.
+    pub primary: bool,
+    /// The area of the root window the monitor shows.
+    pub geometry: Rectangle,
+    /// The physical size in millimeters, 0 if unknown.
+    pub width_mm: u32,
+    pub height_mm: u32,
+}
+
+impl Monitor {
+    /// Whether the monitor shows the given point of the root window.
+    pub fn contains(&self, x: i16, y: i16) -> bool {
+        let (x, y) = (i32::from(x), i32::from(y));
+        let (left, top) = (i32::from(self.geometry.x), i32::from(self.geometry.y));
+        x >= left
+            && y >= top
+            && x < left + i32::from(self.geometry.width)
+            && y < top + i32::from(self.geometry.height)
+    }
+
+    /// Converts root window coordinates to coordinates relative to the top left
+    /// corner of the monitor.
+    pub fn to_local(&self, x: i16, y: i16) -> (i16, i16) {
+        (
+            x.wrapping_sub(self.geometry.x),
+            y.wrapping_sub(self.geometry.y),
+        )
+    }
+
+    /// Converts coordinates relative to the monitor to root window coordinates.
+    pub fn to_global(&self, x: i16, y: i16) -> (i16, i16) {
+        (
+            x.wrapping_add(self.geometry.x),
+            y.wrapping_add(self.geometry.y),
+        )
+    }
+
+    /// Returns the horizontal and vertical dots per inch, or `None` if the
+    /// physical size is unknown.
+    pub fn dpi(&self) -> Option<(f64, f64)> {
+        dpi(
+            (self.geometry.width, self.geometry.height),
+            (self.width_mm, self.height_mm),
+        )
+    }
+}
+
+/// The monitors of the default screen.
+#[derive(Debug, Clone)]
+pub struct Monitors {
+    /// The monitors, in the order the server lists them.
+    pub monitors: Vec<Monitor>,
+    /// The size of the screen in pixels and in millimeters.
+    screen_size: (u16, u16),
+    screen_mm: (u16, u16),
+}
+
+impl Monitors {
+    /// Queries the monitors of the default screen through RandR 1.5, the CRTCs
+    /// of RandR 1.3 or Xinerama, whichever the server supports first. Without
+    /// any, the whole screen is a single monitor.
+    pub fn query(conn: &mut Connection) -> Result<Monitors, ConnectionError> {
+        let screen = conn.default_screen();
+        let root = screen.root;
+        let screen_size = (screen.width_in_px, screen.height_in_px);
+        let screen_mm = (screen.width_in_mm, screen.height_in_mm);
+        let mut monitors = match conn.enable::<Randr>()? {
+            Some(version) if version >= (1, 5) => randr_monitors(conn, root)?,
+            Some(version) if version >= (1, 3) => randr_crtcs(conn, root)?,
+            _ => Vec::new(),
+        };
+        if monitors.is_empty() && conn.enable::<Xinerama>()?.is_some() {
+            monitors = xinerama_screens(conn)?;
+        }
+        if monitors.is_empty() {
+            monitors.push(Monitor {
+                name: None,
+                primary: true,
+                geometry: Rectangle {
+                    x: 0,
+                    y: 0,
+                    width: screen_size.0,
+                    height: screen_size.1,
+                },
+                width_mm: screen_mm.0.into(),
+                height_mm: screen_mm.1.into(),
+            });
+        }
+        Ok(Monitors {
+            monitors,
+            screen_size,
+            screen_mm,
+        })
+    }
+
+    /// Returns the monitor showing the given point of the root window.
+    pub fn monitor_at(&self, x: i16, y: i16) -> Option<&Monitor> {
+        self.monitors.iter().find(|monitor| monitor.contains(x, y))
+    }
+
+    /// Returns the primary monitor, or the first one if none is primary.
+    pub fn primary(&self) -> Option<&Monitor> {
+        self.monitors
+            .iter()
+            .find(|monitor| monitor.primary)
+            .or(self.monitors.first())
+    }
+
+    /// Converts root window coordinates to coordinates relative to the monitor
+    /// showing the point, and returns the monitor with them.
+    pub fn to_local(&self, x: i16, y: i16) -> Option<(&Monitor, i16, i16)> {
+        let monitor = self.monitor_at(x, y)?;
+        let (x, y) = monitor.to_local(x, y);
+        Some((monitor, x, y))
+    }
+
+    /// Returns the horizontal and vertical dots per inch of the whole screen, as
+    /// the server reports its size in millimeters, or `None` if it does not.
+    pub fn screen_dpi(&self) -> Option<(f64, f64)> {
+        dpi(
+            self.screen_size,
+            (self.screen_mm.0.into(), self.screen_mm.1.into()),
+        )
+    }
+}
+
+/// Returns the dots per inch of an area of the given size in pixels and millimeters.
+fn dpi(size: (u16, u16), mm: (u32, u32)) -> Option<(f64, f64)> {
+    if mm.0 == 0 || mm.1 == 0 {
+        return None;
+    }
+    Some((
+        f64::from(size.0) * MM_PER_INCH / f64::from(mm.0),
+        f64::from(size.1) * MM_PER_INCH / f64::from(mm.1),
+    ))
+}
+
+/// Returns the active monitors of RandR 1.5.
+fn randr_monitors(conn: &mut Connection, root: Window) -> Result<Vec<Monitor>, ConnectionError> {
+    let reply = conn.randr_get_monitors(root, true)?.reply(conn)?;
+    let cookies = reply
+        .monitors
+        .iter()
+        .map(|monitor| conn.get_atom_name(monitor.name))
+        .collect::<Result<Vec<_>, ConnectionError>>()?;
+    let mut monitors = Vec::with_capacity(cookies.len());
+    for (info, cookie) in reply.monitors.into_iter().zip(cookies) {
+        monitors.push(Monitor {
+            name: Some(cookie.reply(conn)?.name.to_string()),
+            primary: info.primary,
+            geometry: Rectangle {
+                x: info.x,
+                y: info.y,
+                width: info.width,
+                height: info.height,
+            },
+            width_mm: info.width_mm,
+            height_mm: info.height_mm,
+        });
+    }
+    Ok(monitors)
+}
+
+/// Returns a monitor for each enabled CRTC, named after its first output.
+fn randr_crtcs(conn: &mut Connection, root: Window) -> Result<Vec<Monitor>, ConnectionError> {
+    let primary = conn.randr_get_output_primary(root)?;
+    let resources = conn.randr_get_screen_resources_current(root)?.reply(conn)?;
+    let timestamp = resources.config_timestamp;
+    let crtcs = resources
+        .crtcs
+        .iter()
+        .map(|&crtc| conn.randr_get_crtc_info(crtc, timestamp))
+        .collect::<Result<Vec<_>, ConnectionError>>()?;
+    let outputs = resources
+        .outputs
+        .iter()
+        .map(|&output| conn.randr_get_output_info(output, timestamp))
+        .collect::<Result<Vec<_>, ConnectionError>>()?;
+    let primary = primary.reply(conn)?.output;
+    let crtcs = crtcs
+        .into_iter()
+        .map(|cookie| cookie.reply(conn))
+        .collect::<Result<Vec<_>, ConnectionError>>()?;
+    let outputs = outputs
+        .into_iter()
+        .map(|cookie| cookie.reply(conn))
+        .collect::<Result<Vec<_>, ConnectionError>>()?;
+    let monitors = crtcs
+        .into_iter()
+        .filter(|crtc| crtc.mode != 0)
+        .map(|crtc| {
+            let output = crtc.outputs.first().and_then(|first| {
+                let index = resources
+                    .outputs
+                    .iter()
+                    .position(|output| output == first)?;
+                outputs.get(index)
+            });
+            Monitor {
+                name: output.map(|output| output.name.to_string()),
+                primary: primary != 0 && crtc.outputs.contains(&primary),
+                geometry: Rectangle {
+                    x: crtc.x,
+                    y: crtc.y,
+                    width: crtc.width,
+                    height: crtc.height,
+                },
+                width_mm: output.map_or(0, |output| output.mm_width),
+                height_mm: output.map_or(0, |output| output.mm_height),
+            }
+        })
+        .collect();
+    Ok(monitors)
+}
+
+/// Returns the monitors of Xinerama, empty if it is not active.
+fn xinerama_screens(conn: &mut Connection) -> Result<Vec<Monitor>, ConnectionError> {
+    let screens = conn.xinerama_query_screens()?.reply(conn)?.screens;
+    Ok(screens
+        .into_iter()
+        .map(|geometry| Monitor {
+            name: None,
+            primary: false,
+            geometry,
+            width_mm: 0,
+            height_mm: 0,
+        })
+        .collect())
+}
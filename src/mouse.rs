@@ -0,0 +1,364 @@
+//! System-wide mouse clicks, scrolls and drags.
+//!
+//! [MouseListener] reports what the user does with the mouse anywhere on the
+//! screen, whichever window is under the pointer, without taking the input away
+//! from it. With XI2 it follows the raw button and motion events of every
+//! device. Without it, it grabs the buttons on the root window synchronously and
+//! replays each press to the window below, then polls the pointer while a
+//! button is held to see drags and releases.
+//!
+//! ```no_run
+//! use mousetrap::connection::Connection;
+//! use mousetrap::mouse::{MouseEvent, MouseListener};
+//!
+//! let mut conn = Connection::init().unwrap();
+//! let mut listener = MouseListener::new(&mut conn).unwrap();
+//! loop {
+//!     match listener.next_event(&mut conn).unwrap() {
+//!         MouseEvent::Click { button, x, y } => println!("click {button} at {x},{y}"),
+//!         MouseEvent::Scroll { delta, .. } => println!("scroll {delta}"),
+//!         event => println!("{event:?}"),
+//!     }
+//! }
+//! ```
+
+use crate::connection::Connection;
+use crate::errors::ConnectionError;
+use crate::event::Event;
+use crate::pointer::{self, DEFAULT_POLL_INTERVAL};
+use crate::protocol::{EventMask, Window, XId};
+use crate::request::{AllowMode, GrabButton, GrabMode, ANY_BUTTON, ANY_MODIFIER};
+use crate::xinput::{self, EventSelection, XInput, XiEvent, XiEventMask};
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// Default distance in pixels the pointer moves with a button held before the
+/// press becomes a drag.
+pub const DEFAULT_DRAG_THRESHOLD: u16 = 4;
+
+/// Something the user did with the mouse. Positions are in root window
+/// coordinates of the default screen, and buttons are logical ones.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MouseEvent {
+    /// A button was pressed.
+    Press { button: u8, x: i16, y: i16 },
+    /// A button was released, after a [MouseEvent::Press].
+    Release { button: u8, x: i16, y: i16 },
+    /// A button was pressed and released without moving, reported after its
+    /// [MouseEvent::Release].
+    Click { button: u8, x: i16, y: i16 },
+    /// The wheel turned by `delta` notches: positive down, or right if
+    /// `horizontal`.
+    Scroll {
+        x: i16,
+        y: i16,
+        delta: i32,
+        horizontal: bool,
+    },
+    /// The pointer moved with a button held, from where the button was pressed.
+    Drag {
+        button: u8,
+        start: (i16, i16),
+        x: i16,
+        y: i16,
+    },
+    /// The button of a drag was released, reported after its
+    /// [MouseEvent::Release].
+    DragEnd {
+        button: u8,
+        start: (i16, i16),
+        x: i16,
+        y: i16,
+    },
+}
+
+/// A button held down.
+#[derive(Debug, Clone, Copy)]
+struct Held {
+    button: u8,
+    start: (i16, i16),
+    dragging: bool,
+}
+
+/// Where the listener gets the button events from.
+#[derive(Debug, Clone, Copy)]
+enum Source {
+    /// The XI2 raw events of the extension with this major opcode.
+    RawEvents(u8),
+    /// Synchronous button grabs on the root window.
+    Grab(Window),
+}
+
+/// Reports the clicks, scrolls and drags of the whole screen.
+#[derive(Debug)]
+pub struct MouseListener {
+    source: Source,
+    drag_threshold: u16,
+    poll_interval: Duration,
+    held: Vec<Held>,
+    /// The position the pointer was last seen at.
+    position: (i16, i16),
+    pending: VecDeque<MouseEvent>,
+}
+
+impl MouseListener {
+    /// Starts listening, through XI2 raw events if the server supports them and
+    /// through root window grabs otherwise.
+    ///
+    /// The grabs fail if another client, usually the window manager, already grabs
+    /// every button on the root window. The raw events are selected on the default
+    /// root window, replacing any XI2 selection of the connection on it.
+    pub fn new(conn: &mut Connection) -> Result<MouseListener, ConnectionError> {
+        let root = conn.default_root();
+        let opcode = match conn.enable::<XInput>()? {
+            Some(_) => conn.extension(xinput::NAME)?.map(|info| info.major_opcode),
+            None => None,
+        };
+        let source = match opcode {
+            Some(opcode) => {
+                let mask = XiEventMask::RAW_MOTION
+                    | XiEventMask::RAW_BUTTON_PRESS
+                    | XiEventMask::RAW_BUTTON_RELEASE;
+                let selection = EventSelection::new(xinput::ALL_MASTER_DEVICES, mask);
+                conn.xinput_select_events(root, &[selection])?;
+                Source::RawEvents(opcode)
+            }
+            None => {
+                conn.grab_button(&GrabButton {
+                    owner_events: true,
+                    grab_window: root,
+                    event_mask: EventMask::BUTTON_PRESS,
+                    pointer_mode: GrabMode::SYNC,
+                    keyboard_mode: GrabMode::ASYNC,
+                    confine_to: XId(0),
+                    cursor: XId(0),
+                    button: ANY_BUTTON,
+                    modifiers: ANY_MODIFIER,
+                })?;
+                if let Some(error) = conn.sync()?.into_iter().next() {
+                    return Err(ConnectionError::ProtocolError(error));
+                }
+                Source::Grab(root)
+            }
+        };
+        Ok(MouseListener {
+            source,
+            drag_threshold: DEFAULT_DRAG_THRESHOLD,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            held: Vec::new(),
+            position: (0, 0),
+            pending: VecDeque::new(),
+        })
+    }
+
+    /// Sets the distance in pixels the pointer moves with a button held before
+    /// the press becomes a drag.
+    pub fn set_drag_threshold(&mut self, threshold: u16) {
+        self.drag_threshold = threshold;
+    }
+
+    /// Sets the interval between two queries of the pointer while a button is
+    /// held, when listening through grabs.
+    pub fn set_poll_interval(&mut self, interval: Duration) {
+        self.poll_interval = interval;
+    }
+
+    /// Returns whether the listener follows XI2 raw events rather than grabs.
+    pub fn uses_raw_events(&self) -> bool {
+        matches!(self.source, Source::RawEvents(_))
+    }
+
+    /// Returns the next mouse event, blocking until there is one.
+    pub fn next_event(&mut self, conn: &mut Connection) -> Result<MouseEvent, ConnectionError> {
+        loop {
+            if let Some(event) = self.pending.pop_front() {
+                return Ok(event);
+            }
+            match self.source {
+                Source::RawEvents(opcode) => self.read_raw_event(conn, opcode)?,
+                Source::Grab(root) => self.read_grab_event(conn, root)?,
+            }
+        }
+    }
+
+    /// Stops listening, releasing the grabs or the XI2 selection.
+    pub fn stop(self, conn: &mut Connection) -> Result<(), ConnectionError> {
+        let root = conn.default_root();
+        match self.source {
+            Source::RawEvents(_) => {
+                let selection = EventSelection::new(xinput::ALL_MASTER_DEVICES, XiEventMask(0));
+                conn.xinput_select_events(root, &[selection])?;
+            }
+            Source::Grab(root) => {
+                conn.ungrab_button(ANY_BUTTON, root, ANY_MODIFIER)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Waits for a raw event and turns it into mouse events.
+    fn read_raw_event(&mut self, conn: &mut Connection, opcode: u8) -> Result<(), ConnectionError> {
+        let event =
+            conn.wait_for_event_matching(|event| pointer::is_raw_pointer_event(event, opcode))?;
+        match XiEvent::from_event(&event, opcode) {
+            Some(XiEvent::RawButtonPress(raw)) => {
+                if let Some(button) = logical_button(conn, raw.detail)? {
+                    self.query(conn)?;
+                    self.press(button);
+                }
+            }
+            Some(XiEvent::RawButtonRelease(raw)) => {
+                if let Some(button) = logical_button(conn, raw.detail)? {
+                    self.query(conn)?;
+                    self.release(button);
+                }
+            }
+            Some(XiEvent::RawMotion(_)) if !self.held.is_empty() => {
+                // One query covers the motion events already received.
+                while conn
+                    .poll_for_event_matching(|event| is_raw_motion(event, opcode))?
+                    .is_some()
+                {}
+                self.query(conn)?;
+                self.motion();
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Waits for a press caught by the grab, or polls the pointer while buttons
+    /// are held, and turns them into mouse events.
+    fn read_grab_event(
+        &mut self,
+        conn: &mut Connection,
+        root: Window,
+    ) -> Result<(), ConnectionError> {
+        let filter = |event: &Event| matches!(event, Event::ButtonPress(e) if e.event == root);
+        let event = if self.held.is_empty() {
+            Some(conn.wait_for_event_matching(filter)?)
+        } else {
+            conn.wait_for_event_matching_timeout(filter, self.poll_interval)?
+        };
+        if let Some(Event::ButtonPress(press)) = event {
+            // Let the press go on to the window it was meant for.
+            conn.allow_events(AllowMode::REPLAY_POINTER, press.time)?;
+            self.position = (press.root_x, press.root_y);
+            self.press(press.detail);
+            return Ok(());
+        }
+        let root = conn.default_root();
+        let reply = conn.query_pointer(root)?.reply(conn)?;
+        let moved = self.position != (reply.root_x, reply.root_y);
+        self.position = (reply.root_x, reply.root_y);
+        let held: Vec<u8> = self.held.iter().map(|held| held.button).collect();
+        for button in held {
+            if (1..=5).contains(&button) && reply.mask & (0x80 << button) == 0 {
+                self.release(button);
+            }
+        }
+        if moved {
+            self.motion();
+        }
+        Ok(())
+    }
+
+    /// Updates the position of the pointer.
+    fn query(&mut self, conn: &mut Connection) -> Result<(), ConnectionError> {
+        let root = conn.default_root();
+        let reply = conn.query_pointer(root)?.reply(conn)?;
+        self.position = (reply.root_x, reply.root_y);
+        Ok(())
+    }
+
+    fn press(&mut self, button: u8) {
+        let (x, y) = self.position;
+        if let Some((delta, horizontal)) = scroll(button) {
+            self.pending.push_back(MouseEvent::Scroll {
+                x,
+                y,
+                delta,
+                horizontal,
+            });
+            return;
+        }
+        self.held.retain(|held| held.button != button);
+        self.held.push(Held {
+            button,
+            start: (x, y),
+            dragging: false,
+        });
+        self.pending.push_back(MouseEvent::Press { button, x, y });
+    }
+
+    fn release(&mut self, button: u8) {
+        let Some(index) = self.held.iter().position(|held| held.button == button) else {
+            return;
+        };
+        let held = self.held.remove(index);
+        let (x, y) = self.position;
+        self.pending.push_back(MouseEvent::Release { button, x, y });
+        self.pending.push_back(if held.dragging {
+            MouseEvent::DragEnd {
+                button,
+                start: held.start,
+                x,
+                y,
+            }
+        } else {
+            MouseEvent::Click { button, x, y }
+        });
+    }
+
+    /// Reports a drag for the first button held, once the pointer moved far
+    /// enough from where it was pressed.
+    fn motion(&mut self) {
+        let (x, y) = self.position;
+        let threshold = self.drag_threshold;
+        let Some(held) = self.held.first_mut() else {
+            return;
+        };
+        let distance = (x.abs_diff(held.start.0)).max(y.abs_diff(held.start.1));
+        if !held.dragging && distance < threshold {
+            return;
+        }
+        held.dragging = true;
+        self.pending.push_back(MouseEvent::Drag {
+            button: held.button,
+            start: held.start,
+            x,
+            y,
+        });
+    }
+}
+
+/// Returns the logical button of the physical button of a raw event, or `None`
+/// if the button is disabled.
+fn logical_button(conn: &mut Connection, physical: u32) -> Result<Option<u8>, ConnectionError> {
+    let map = &conn.pointer_mapping()?.map;
+    let button = match usize::try_from(physical)
+        .ok()
+        .and_then(|b| b.checked_sub(1))
+    {
+        Some(index) => map.get(index).copied().unwrap_or(physical as u8),
+        None => 0,
+    };
+    Ok((button != 0).then_some(button))
+}
+
+/// Returns the delta and direction of the wheel buttons 4 to 7.
+fn scroll(button: u8) -> Option<(i32, bool)> {
+    match button {
+        4 => Some((-1, false)),
+        5 => Some((1, false)),
+        6 => Some((-1, true)),
+        7 => Some((1, true)),
+        _ => None,
+    }
+}
+
+/// Whether an event is a raw motion event of the extension.
+fn is_raw_motion(event: &Event, opcode: u8) -> bool {
+    matches!(event, Event::Generic(generic)
+        if generic.extension == opcode && generic.event_type == xinput::event_type::RAW_MOTION)
+}
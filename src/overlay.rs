@@ -0,0 +1,165 @@
+//! Windows drawn over everything else, for on-screen annotations and region
+//! selection.
+//!
+//! An [Overlay] is an override-redirect window, which the window manager does
+//! not decorate, move or focus. When the screen has a 32-bit TrueColor visual
+//! the overlay uses it with a colormap of its own, so that with a compositing
+//! manager its pixels can be translucent: its background is fully transparent
+//! and drawing sets the alpha with the color. An overlay can also be
+//! click-through, with an empty SHAPE input region letting the pointer events
+//! go to the windows below.
+//!
+//! ```no_run
+//! use mousetrap::connection::Connection;
+//! use mousetrap::overlay::Overlay;
+//! use mousetrap::protocol::Rectangle;
+//!
+//! let mut conn = Connection::init().unwrap();
+//! let geometry = Rectangle {
+//!     x: 100,
+//!     y: 100,
+//!     width: 400,
+//!     height: 300,
+//! };
+//! let overlay = Overlay::create(&mut conn, geometry, true).unwrap();
+//! println!("translucent: {}", overlay.is_argb());
+//! overlay.map(&mut conn).unwrap();
+//! ```
+
+use crate::connection::Connection;
+use crate::errors::ConnectionError;
+use crate::protocol::{ColorMap, EventMask, Rectangle, Screen, VisualClass, VisualId, Window};
+use crate::request::{ColormapAlloc, CreateWindow, WindowAttributes, WindowClass};
+
+/// Returns a 32-bit TrueColor visual of a screen, whose pixels have 8 bits of
+/// alpha besides red, green and blue.
+pub fn argb_visual(screen: &Screen) -> Option<VisualId> {
+    screen
+        .allowed_depths
+        .iter()
+        .filter(|depth| depth.depth == 32)
+        .flat_map(|depth| &depth.visuals)
+        .find(|visual| visual.class == VisualClass::TRUE_COLOR)
+        .map(|visual| visual.visual_id)
+}
+
+/// An override-redirect window over the other windows of the default screen.
+#[derive(Debug)]
+pub struct Overlay {
+    window: Window,
+    /// The colormap created for the 32-bit visual, if the overlay uses one.
+    colormap: Option<ColorMap>,
+    visual: VisualId,
+    depth: u8,
+    geometry: Rectangle,
+}
+
+impl Overlay {
+    /// Creates an unmapped overlay at `geometry`, in root window coordinates.
+    /// It uses a 32-bit visual if the screen has one, and the root visual
+    /// otherwise. If `click_through`, its input region is emptied, which
+    /// requires SHAPE 1.1.
+    ///
+    /// The overlay selects Exposure events.
+    pub fn create(
+        conn: &mut Connection,
+        geometry: Rectangle,
+        click_through: bool,
+    ) -> Result<Overlay, ConnectionError> {
+        let screen = conn.default_screen();
+        let root = screen.root;
+        let (visual, depth, colormap) = match argb_visual(screen) {
+            Some(visual) => {
+                let colormap = conn.create_colormap(ColormapAlloc::NONE, root, visual)?;
+                (visual, 32, Some(colormap))
+            }
+            None => (screen.root_visual, screen.root_depth, None),
+        };
+        let window = conn.generate_id()?;
+        // A window of another depth than its parent needs its own border and
+        // colormap, or creating it fails with a Match error.
+        let attributes = match colormap {
+            Some(colormap) => WindowAttributes {
+                background_pixel: Some(0),
+                border_pixel: Some(0),
+                colormap: Some(colormap),
+                ..Default::default()
+            },
+            None => WindowAttributes::default(),
+        };
+        conn.create_window(&CreateWindow {
+            depth,
+            window,
+            parent: root,
+            x: geometry.x,
+            y: geometry.y,
+            width: geometry.width,
+            height: geometry.height,
+            border_width: 0,
+            class: WindowClass::INPUT_OUTPUT,
+            visual,
+            attributes: WindowAttributes {
+                override_redirect: Some(true),
+                event_mask: Some(EventMask::EXPOSURE),
+                ..attributes
+            },
+        })?;
+        if click_through {
+            conn.shape_clear_input(window)?;
+        }
+        if let Some(error) = conn.sync()?.into_iter().next() {
+            return Err(ConnectionError::ProtocolError(error));
+        }
+        Ok(Overlay {
+            window,
+            colormap,
+            visual,
+            depth,
+            geometry,
+        })
+    }
+
+    /// Returns the window of the overlay, to draw on.
+    pub fn window(&self) -> Window {
+        self.window
+    }
+
+    /// Returns the visual of the window.
+    pub fn visual(&self) -> VisualId {
+        self.visual
+    }
+
+    /// Returns the depth of the window: 32 if it is translucent.
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    /// Returns whether the window uses a 32-bit visual with alpha.
+    pub fn is_argb(&self) -> bool {
+        self.colormap.is_some()
+    }
+
+    /// Returns the position and size the overlay was created with.
+    pub fn geometry(&self) -> Rectangle {
+        self.geometry
+    }
+
+    /// Shows the overlay.
+    pub fn map(&self, conn: &mut Connection) -> Result<u16, ConnectionError> {
+        conn.map_window(self.window)
+    }
+
+    /// Hides the overlay.
+    pub fn unmap(&self, conn: &mut Connection) -> Result<u16, ConnectionError> {
+        conn.unmap_window(self.window)
+    }
+
+    /// Destroys the window and frees its colormap.
+    pub fn destroy(self, conn: &mut Connection) -> Result<(), ConnectionError> {
+        conn.destroy_window(self.window)?;
+        if let Some(colormap) = self.colormap {
+            conn.free_colormap(colormap)?;
+        }
+        Ok(())
+    }
+}
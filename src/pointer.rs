@@ -0,0 +1,204 @@
+//! Following the pointer: its position and buttons as they change.
+//!
+//! [PointerTracker] yields a [PointerSample] each time the pointer moves or a
+//! button changes, anywhere on the screen. When the server supports XI2 it
+//! wakes up on the raw motion and button events of every device; otherwise it
+//! polls QueryPointer at a configurable rate. Both give the same samples.
+//!
+//! ```no_run
+//! use mousetrap::connection::Connection;
+//! use mousetrap::pointer::PointerTracker;
+//!
+//! let mut conn = Connection::init().unwrap();
+//! println!("{:?}", conn.pointer_position().unwrap());
+//! let mut tracker = PointerTracker::new(&mut conn).unwrap();
+//! loop {
+//!     let sample = tracker.next_sample(&mut conn).unwrap();
+//!     println!("{} {} {:#b}", sample.x, sample.y, sample.buttons);
+//! }
+//! ```
+
+use crate::connection::Connection;
+use crate::errors::ConnectionError;
+use crate::event::Event;
+use crate::xinput::{self, event_type, EventSelection, XInput, XiEventMask};
+use std::time::{Duration, Instant};
+
+/// Default interval between two queries when polling: 100 times a second.
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// The state of the pointer at some point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PointerSample {
+    /// The position, in root window coordinates of the default screen.
+    pub x: i16,
+    pub y: i16,
+    /// The buttons held down: bit 0 for button 1, up to bit 4 for button 5.
+    pub buttons: u8,
+    /// When the change was seen.
+    pub time: Instant,
+}
+
+/// Where the tracker learns that the pointer changed.
+#[derive(Debug, Clone, Copy)]
+enum Source {
+    /// Queries the pointer at every interval.
+    Poll,
+    /// Waits for the XI2 raw events of the extension with this major opcode.
+    RawEvents(u8),
+}
+
+/// Yields the changes of the pointer position and buttons.
+#[derive(Debug)]
+pub struct PointerTracker {
+    source: Source,
+    interval: Duration,
+    next_poll: Instant,
+    /// The position and buttons of the last sample.
+    last: Option<(i16, i16, u8)>,
+}
+
+impl PointerTracker {
+    /// Tracks the pointer through XI2 raw events if the server supports them,
+    /// polling otherwise.
+    ///
+    /// The raw events are selected on the default root window, replacing any XI2
+    /// selection of the connection on it.
+    pub fn new(conn: &mut Connection) -> Result<PointerTracker, ConnectionError> {
+        if conn.enable::<XInput>()?.is_none() {
+            return Ok(PointerTracker::polling(DEFAULT_POLL_INTERVAL));
+        }
+        let Some(info) = conn.extension(xinput::NAME)? else {
+            return Ok(PointerTracker::polling(DEFAULT_POLL_INTERVAL));
+        };
+        let root = conn.default_root();
+        let mask = XiEventMask::RAW_MOTION
+            | XiEventMask::RAW_BUTTON_PRESS
+            | XiEventMask::RAW_BUTTON_RELEASE;
+        conn.xinput_select_events(
+            root,
+            &[EventSelection::new(xinput::ALL_MASTER_DEVICES, mask)],
+        )?;
+        Ok(PointerTracker {
+            source: Source::RawEvents(info.major_opcode),
+            ..PointerTracker::polling(DEFAULT_POLL_INTERVAL)
+        })
+    }
+
+    /// Tracks the pointer by querying it every `interval`.
+    pub fn polling(interval: Duration) -> PointerTracker {
+        PointerTracker {
+            source: Source::Poll,
+            interval,
+            next_poll: Instant::now(),
+            last: None,
+        }
+    }
+
+    /// Sets the interval between two queries when polling.
+    pub fn set_interval(&mut self, interval: Duration) {
+        self.interval = interval;
+    }
+
+    /// Returns whether the tracker wakes up on XI2 raw events rather than polling.
+    pub fn uses_raw_events(&self) -> bool {
+        matches!(self.source, Source::RawEvents(_))
+    }
+
+    /// Returns the next change of the pointer, blocking until there is one. The
+    /// first call returns the current state.
+    pub fn next_sample(&mut self, conn: &mut Connection) -> Result<PointerSample, ConnectionError> {
+        loop {
+            if let Some(sample) = self.next_sample_until(conn, None)? {
+                return Ok(sample);
+            }
+        }
+    }
+
+    /// Returns the next change of the pointer, or `None` if there was none
+    /// within `timeout`.
+    pub fn next_sample_timeout(
+        &mut self,
+        conn: &mut Connection,
+        timeout: Duration,
+    ) -> Result<Option<PointerSample>, ConnectionError> {
+        self.next_sample_until(conn, Some(Instant::now() + timeout))
+    }
+
+    fn next_sample_until(
+        &mut self,
+        conn: &mut Connection,
+        deadline: Option<Instant>,
+    ) -> Result<Option<PointerSample>, ConnectionError> {
+        if self.last.is_none() {
+            return self.query(conn);
+        }
+        loop {
+            match self.source {
+                Source::Poll => {
+                    let now = Instant::now();
+                    let wake = self.next_poll;
+                    if let Some(deadline) = deadline.filter(|&deadline| deadline < wake) {
+                        std::thread::sleep(deadline.saturating_duration_since(now));
+                        return Ok(None);
+                    }
+                    std::thread::sleep(wake.saturating_duration_since(now));
+                    // Falling behind skips the missed polls rather than catching up.
+                    self.next_poll = wake.max(Instant::now()) + self.interval;
+                }
+                Source::RawEvents(opcode) => {
+                    let filter = |event: &Event| is_raw_pointer_event(event, opcode);
+                    match deadline {
+                        Some(deadline) => {
+                            let timeout = deadline.saturating_duration_since(Instant::now());
+                            if conn
+                                .wait_for_event_matching_timeout(filter, timeout)?
+                                .is_none()
+                            {
+                                return Ok(None);
+                            }
+                        }
+                        None => {
+                            conn.wait_for_event_matching(filter)?;
+                        }
+                    }
+                    // One query covers all the events already received.
+                    while conn.poll_for_event_matching(filter)?.is_some() {}
+                }
+            }
+            if let Some(sample) = self.query(conn)? {
+                return Ok(Some(sample));
+            }
+        }
+    }
+
+    /// Queries the pointer, returning a sample if it changed since the last one.
+    fn query(&mut self, conn: &mut Connection) -> Result<Option<PointerSample>, ConnectionError> {
+        let root = conn.default_root();
+        let reply = conn.query_pointer(root)?.reply(conn)?;
+        let state = (reply.root_x, reply.root_y, (reply.mask >> 8) as u8 & 0x1f);
+        if self.last == Some(state) {
+            return Ok(None);
+        }
+        self.last = Some(state);
+        let (x, y, buttons) = state;
+        Ok(Some(PointerSample {
+            x,
+            y,
+            buttons,
+            time: Instant::now(),
+        }))
+    }
+}
+
+/// Whether an event is a raw motion or button event of the extension.
+pub(crate) fn is_raw_pointer_event(event: &Event, opcode: u8) -> bool {
+    let Event::Generic(generic) = event else {
+        return false;
+    };
+    generic.extension == opcode
+        && matches!(
+            generic.event_type,
+            event_type::RAW_MOTION | event_type::RAW_BUTTON_PRESS | event_type::RAW_BUTTON_RELEASE
+        )
+}
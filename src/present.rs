@@ -0,0 +1,495 @@
+//! The Present extension: showing pixmaps in windows in step with the display.
+//!
+//! A pixmap is presented at a given media stream counter (MSC), the count of
+//! vertical blanks of the CRTC showing the window. The server reports with a
+//! CompleteNotify event when the pixmap was shown, along with the MSC and the
+//! time it happened, and with an IdleNotify event when the pixmap can be drawn
+//! into again.
+//!
+//! ```no_run
+//! use mousetrap::connection::Connection;
+//! use mousetrap::present::{self, EventMask, PresentEvent, PresentPixmap};
+//! use mousetrap::protocol::XId;
+//!
+//! let mut conn = Connection::init().unwrap();
+//! let (window, pixmap) = (XId(0x1200001), XId(0x1200002));
+//! conn.present_select_input(window, EventMask::COMPLETE_NOTIFY | EventMask::IDLE_NOTIFY)
+//!     .unwrap();
+//! conn.present_pixmap(&PresentPixmap::new(window, pixmap, 1)).unwrap();
+//!
+//! let major_opcode = conn.extension(present::NAME).unwrap().unwrap().major_opcode;
+//! loop {
+//!     let event = conn.wait_for_event().unwrap();
+//!     if let Some(PresentEvent::CompleteNotify(complete)) =
+//!         PresentEvent::from_event(&event, major_opcode)
+//!     {
+//!         println!("shown at msc {} ({} µs)", complete.msc, complete.ust);
+//!         break;
+//!     }
+//! }
+//! ```
+
+use crate::connection::Connection;
+use crate::cookie::{Cookie, Reply};
+use crate::errors::{ConnectionError, ParseError};
+use crate::event::Event;
+use crate::extension::{Extension, Version};
+use crate::protocol::{Window, XId};
+use crate::request::RequestBuilder;
+use crate::utils::{check_trailing_bytes, deserialize_into, skip};
+
+/// Name of the extension, as given to QueryExtension.
+pub const NAME: &str = "Present";
+
+/// Version of the extension implemented by this module.
+pub const VERSION: (u32, u32) = (1, 2);
+
+/// Minor opcodes of the Present requests.
+pub mod opcode {
+    pub const QUERY_VERSION: u8 = 0;
+    pub const PIXMAP: u8 = 1;
+    pub const NOTIFY_MSC: u8 = 2;
+    pub const SELECT_INPUT: u8 = 3;
+    pub const QUERY_CAPABILITIES: u8 = 4;
+}
+
+/// Names of the Present requests, indexed by minor opcode.
+pub const REQUEST_NAMES: &[&str] = &[
+    "QueryVersion",
+    "Pixmap",
+    "NotifyMSC",
+    "SelectInput",
+    "QueryCapabilities",
+];
+
+/// The extension defines no errors.
+pub const ERROR_NAMES: &[&str] = &[];
+
+/// Starts a request of the extension.
+fn request(conn: &mut Connection, minor_opcode: u8) -> Result<RequestBuilder, ConnectionError> {
+    let info = conn.require_extension(NAME, REQUEST_NAMES, ERROR_NAMES)?;
+    Ok(RequestBuilder::new(info.major_opcode, minor_opcode))
+}
+
+/// The Present extension, for [Connection::enable].
+pub struct Present;
+
+impl Extension for Present {
+    const NAME: &'static str = NAME;
+    const MIN_VERSION: Version = (1, 0);
+
+    fn negotiate(conn: &mut Connection) -> Result<Option<Version>, ConnectionError> {
+        let reply = conn
+            .present_query_version(VERSION.0, VERSION.1)?
+            .reply(conn)?;
+        Ok(Some((reply.major_version, reply.minor_version)))
+    }
+}
+
+/// Types of the Present events, sent as generic events.
+pub mod event_type {
+    pub const CONFIGURE_NOTIFY: u16 = 0;
+    pub const COMPLETE_NOTIFY: u16 = 1;
+    pub const IDLE_NOTIFY: u16 = 2;
+    pub const REDIRECT_NOTIFY: u16 = 3;
+}
+
+/// The events selected by [Connection::present_select_input].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct EventMask(pub u32);
+
+impl EventMask {
+    pub const CONFIGURE_NOTIFY: Self = Self(1);
+    pub const COMPLETE_NOTIFY: Self = Self(2);
+    pub const IDLE_NOTIFY: Self = Self(4);
+    pub const REDIRECT_NOTIFY: Self = Self(8);
+}
+
+impl std::ops::BitOr for EventMask {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+/// Options of [PresentPixmap].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Options(pub u32);
+
+impl Options {
+    /// Show the pixmap right away if the target MSC has passed, even if it tears.
+    pub const ASYNC: Self = Self(1);
+    /// Copy the pixmap rather than flipping to it.
+    pub const COPY: Self = Self(2);
+    /// The target is a time in microseconds rather than an MSC.
+    pub const UST: Self = Self(4);
+    pub const SUBOPTIMAL: Self = Self(8);
+}
+
+impl std::ops::BitOr for Options {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+/// What the server can do when presenting, see [Connection::present_query_capabilities].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Capabilities(pub u32);
+
+impl Capabilities {
+    pub const ASYNC: Self = Self(1);
+    pub const FENCE: Self = Self(2);
+    pub const UST: Self = Self(4);
+
+    /// Whether all the bits of `other` are set.
+    pub fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+/// Reply of the PresentQueryVersion request.
+#[derive(Debug, Clone)]
+pub struct QueryVersionReply {
+    pub major_version: u32,
+    pub minor_version: u32,
+}
+
+impl Reply for QueryVersionReply {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1                        unused
+        // 2  CARD16                sequence number
+        // 4  0                     reply length
+        // 4  CARD32                major-version
+        // 4  CARD32                minor-version
+        // 16                       unused
+        let rest = skip(bytes, 8)?;
+        let (major_version, rest) = deserialize_into::<u32>(rest)?;
+        let (minor_version, _) = deserialize_into::<u32>(rest)?;
+        Ok(QueryVersionReply {
+            major_version,
+            minor_version,
+        })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        check_trailing_bytes("present::QueryVersionReply", bytes, 32)
+    }
+}
+
+/// Reply of the PresentQueryCapabilities request.
+#[derive(Debug, Clone)]
+pub struct QueryCapabilitiesReply {
+    pub capabilities: Capabilities,
+}
+
+impl Reply for QueryCapabilitiesReply {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1                        unused
+        // 2  CARD16                sequence number
+        // 4  0                     reply length
+        // 4  CARD32                capabilities
+        // 20                       unused
+        let rest = skip(bytes, 8)?;
+        let (capabilities, _) = deserialize_into::<u32>(rest)?;
+        Ok(QueryCapabilitiesReply {
+            capabilities: Capabilities(capabilities),
+        })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        check_trailing_bytes("present::QueryCapabilitiesReply", bytes, 32)
+    }
+}
+
+/// The PresentPixmap request: shows a pixmap in a window at a given MSC.
+///
+/// The pixmap is shown at `target_msc`, or if that has passed, at the next MSC
+/// for which `msc % divisor == remainder`. With all three at 0, it is shown at
+/// the next vertical blank.
+#[derive(Debug, Clone)]
+pub struct PresentPixmap {
+    pub window: Window,
+    pub pixmap: XId,
+    /// Identifies the presentation in the events about it.
+    pub serial: u32,
+    /// The region of the pixmap with valid contents, 0 for all of it.
+    pub valid: XId,
+    /// The region of the pixmap that changed since the last presentation, 0 for
+    /// all of it.
+    pub update: XId,
+    pub x_off: i16,
+    pub y_off: i16,
+    /// The CRTC whose MSC is counted, 0 for the one the window is on.
+    pub target_crtc: u32,
+    /// A fence to wait on before presenting, 0 for none.
+    pub wait_fence: XId,
+    /// A fence triggered once the pixmap is idle, 0 for none.
+    pub idle_fence: XId,
+    pub options: Options,
+    pub target_msc: u64,
+    pub divisor: u64,
+    pub remainder: u64,
+}
+
+impl PresentPixmap {
+    /// Returns a request showing the whole pixmap at the next vertical blank.
+    pub fn new(window: Window, pixmap: XId, serial: u32) -> PresentPixmap {
+        PresentPixmap {
+            window,
+            pixmap,
+            serial,
+            valid: XId(0),
+            update: XId(0),
+            x_off: 0,
+            y_off: 0,
+            target_crtc: 0,
+            wait_fence: XId(0),
+            idle_fence: XId(0),
+            options: Options::default(),
+            target_msc: 0,
+            divisor: 0,
+            remainder: 0,
+        }
+    }
+
+    //   1 byte     Major opcode
+    //   1 byte     Minor opcode (1)
+    //   2 bytes    Request length (18)
+    //   4 bytes    Window
+    //   4 bytes    Pixmap
+    //   4 bytes    Serial
+    //   4 bytes    Valid region
+    //   4 bytes    Update region
+    //   2 bytes    X offset
+    //   2 bytes    Y offset
+    //   4 bytes    Target CRTC
+    //   4 bytes    Wait fence
+    //   4 bytes    Idle fence
+    //   4 bytes    Options
+    //   4 bytes    Unused
+    //   8 bytes    Target MSC
+    //   8 bytes    Divisor
+    //   8 bytes    Remainder
+    //
+    //  A list of windows to notify as well may follow; none is sent.
+    pub fn serialize(&self, major_opcode: u8) -> Vec<u8> {
+        RequestBuilder::new(major_opcode, opcode::PIXMAP)
+            .put(self.window)
+            .put(self.pixmap)
+            .put(self.serial)
+            .put(self.valid)
+            .put(self.update)
+            .put(self.x_off)
+            .put(self.y_off)
+            .put(self.target_crtc)
+            .put(self.wait_fence)
+            .put(self.idle_fence)
+            .put(self.options.0)
+            .pad(4)
+            .put(self.target_msc)
+            .put(self.divisor)
+            .put(self.remainder)
+            .finish()
+    }
+}
+
+/// A presentation completed: the pixmap was shown, or skipped, or the MSC of a
+/// [Connection::present_notify_msc] was reached.
+#[derive(Debug, Clone)]
+pub struct CompleteNotifyEvent {
+    /// PresentPixmap (0) or NotifyMSC (1).
+    pub kind: u8,
+    /// How the pixmap was shown: Copy (0), Flip (1), Skip (2) or
+    /// SuboptimalCopy (3).
+    pub mode: u8,
+    pub event_id: XId,
+    pub window: Window,
+    pub serial: u32,
+    /// When the presentation happened, in microseconds.
+    pub ust: u64,
+    /// The MSC at which the presentation happened.
+    pub msc: u64,
+}
+
+/// A presented pixmap is no longer used by the server.
+#[derive(Debug, Clone)]
+pub struct IdleNotifyEvent {
+    pub event_id: XId,
+    pub window: Window,
+    pub serial: u32,
+    pub pixmap: XId,
+    pub idle_fence: XId,
+}
+
+/// A Present event.
+#[derive(Debug, Clone)]
+pub enum PresentEvent {
+    CompleteNotify(CompleteNotifyEvent),
+    IdleNotify(IdleNotifyEvent),
+}
+
+impl PresentEvent {
+    /// Parses the event if `event` is a CompleteNotify or IdleNotify event.
+    /// `major_opcode` is the one of the extension, see [Connection::extension].
+    pub fn from_event(event: &Event, major_opcode: u8) -> Option<PresentEvent> {
+        let Event::Generic(generic) = event else {
+            return None;
+        };
+        if generic.extension != major_opcode {
+            return None;
+        }
+        match generic.event_type {
+            event_type::COMPLETE_NOTIFY => Self::parse_complete_notify(&generic.data),
+            event_type::IDLE_NOTIFY => Self::parse_idle_notify(&generic.data),
+            _ => None,
+        }
+    }
+
+    //   1 byte     Kind
+    //   1 byte     Mode
+    //   4 bytes    Event id
+    //   4 bytes    Window
+    //   4 bytes    Serial
+    //   8 bytes    UST
+    //   8 bytes    MSC
+    fn parse_complete_notify(data: &[u8]) -> Option<PresentEvent> {
+        let (kind, rest) = deserialize_into::<u8>(data).ok()?;
+        let (mode, rest) = deserialize_into::<u8>(rest).ok()?;
+        let (event_id, rest) = deserialize_into::<XId>(rest).ok()?;
+        let (window, rest) = deserialize_into::<Window>(rest).ok()?;
+        let (serial, rest) = deserialize_into::<u32>(rest).ok()?;
+        let (ust, rest) = deserialize_into::<u64>(rest).ok()?;
+        let (msc, _) = deserialize_into::<u64>(rest).ok()?;
+        Some(PresentEvent::CompleteNotify(CompleteNotifyEvent {
+            kind,
+            mode,
+            event_id,
+            window,
+            serial,
+            ust,
+            msc,
+        }))
+    }
+
+    //   2 bytes    Unused
+    //   4 bytes    Event id
+    //   4 bytes    Window
+    //   4 bytes    Serial
+    //   4 bytes    Pixmap
+    //   4 bytes    Idle fence
+    fn parse_idle_notify(data: &[u8]) -> Option<PresentEvent> {
+        let rest = skip(data, 2).ok()?;
+        let (event_id, rest) = deserialize_into::<XId>(rest).ok()?;
+        let (window, rest) = deserialize_into::<Window>(rest).ok()?;
+        let (serial, rest) = deserialize_into::<u32>(rest).ok()?;
+        let (pixmap, rest) = deserialize_into::<XId>(rest).ok()?;
+        let (idle_fence, _) = deserialize_into::<XId>(rest).ok()?;
+        Some(PresentEvent::IdleNotify(IdleNotifyEvent {
+            event_id,
+            window,
+            serial,
+            pixmap,
+            idle_fence,
+        }))
+    }
+}
+
+impl Connection {
+    /// Tells the server the version of the extension the client supports and
+    /// returns the version the server supports.
+    pub fn present_query_version(
+        &mut self,
+        major_version: u32,
+        minor_version: u32,
+    ) -> Result<Cookie<QueryVersionReply>, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (0)
+        //   2 bytes    Request length (3)
+        //   4 bytes    Client major version
+        //   4 bytes    Client minor version
+        let request = request(self, opcode::QUERY_VERSION)?
+            .put(major_version)
+            .put(minor_version)
+            .finish();
+        self.send_request_with_reply(&request)
+    }
+
+    /// Shows a pixmap in a window, see [PresentPixmap].
+    pub fn present_pixmap(&mut self, present: &PresentPixmap) -> Result<u16, ConnectionError> {
+        let info = self.require_extension(NAME, REQUEST_NAMES, ERROR_NAMES)?;
+        self.send_request(&present.serialize(info.major_opcode))
+    }
+
+    /// Asks for a CompleteNotify event with the given serial once the MSC of the
+    /// CRTC showing `window` reaches a target, as for [PresentPixmap].
+    pub fn present_notify_msc(
+        &mut self,
+        window: Window,
+        serial: u32,
+        target_msc: u64,
+        divisor: u64,
+        remainder: u64,
+    ) -> Result<u16, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (2)
+        //   2 bytes    Request length (10)
+        //   4 bytes    Window
+        //   4 bytes    Serial
+        //   4 bytes    Unused
+        //   8 bytes    Target MSC
+        //   8 bytes    Divisor
+        //   8 bytes    Remainder
+        let request = request(self, opcode::NOTIFY_MSC)?
+            .put(window)
+            .put(serial)
+            .pad(4)
+            .put(target_msc)
+            .put(divisor)
+            .put(remainder)
+            .finish();
+        self.send_request(&request)
+    }
+
+    /// Selects the Present events of a window and returns the id identifying the
+    /// selection in the events.
+    pub fn present_select_input(
+        &mut self,
+        window: Window,
+        mask: EventMask,
+    ) -> Result<XId, ConnectionError> {
+        let event_id = self.generate_id()?;
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (3)
+        //   2 bytes    Request length (4)
+        //   4 bytes    Event id
+        //   4 bytes    Window
+        //   4 bytes    Event mask
+        let request = request(self, opcode::SELECT_INPUT)?
+            .put(event_id)
+            .put(window)
+            .put(mask.0)
+            .finish();
+        self.send_request(&request)?;
+        Ok(event_id)
+    }
+
+    /// Returns what the server can do when presenting to a window or CRTC.
+    pub fn present_query_capabilities(
+        &mut self,
+        target: u32,
+    ) -> Result<Cookie<QueryCapabilitiesReply>, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (4)
+        //   2 bytes    Request length (2)
+        //   4 bytes    Target
+        let request = request(self, opcode::QUERY_CAPABILITIES)?
+            .put(target)
+            .finish();
+        self.send_request_with_reply(&request)
+    }
+}
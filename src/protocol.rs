@@ -1,10 +1,24 @@
+use std::borrow::Cow;
+use std::fmt;
+use std::ops::{BitOr, BitOrAssign};
+
 /// Order of bits within the bytes for a Bitmap image.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BitOrder {
     LeastSignificant,
     MostSignificant,
 }
 
-#[derive(Debug)]
+impl From<u8> for BitOrder {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => BitOrder::LeastSignificant,
+            _ => BitOrder::MostSignificant,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct Format {
     pub depth: u8,
     pub bits_per_pixel: u8,
@@ -16,98 +30,388 @@ pub const PROTOCOL_MINOR_VERSION: u16 = 0;
 
 /// Unique identifier used for various things inside x11,
 /// such as windows, pixmaps, fonts, [ColorMap]s and others.
-pub struct XId(u32);
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct XId(pub u32);
+
+/// A string as sent by the server: a STRING8 or the value of a property.
+///
+/// The bytes are kept as they are, since they are not necessarily UTF-8: the
+/// STRING type is Latin-1 and COMPOUND_TEXT uses ISO 2022 escapes.
+#[derive(Clone, PartialEq, Eq, Hash, Default)]
+pub struct XString(Vec<u8>);
+
+impl XString {
+    /// Returns the string, replacing bytes that are not valid UTF-8 with U+FFFD.
+    pub fn as_str_lossy(&self) -> Cow<'_, str> {
+        String::from_utf8_lossy(&self.0)
+    }
+
+    /// Returns the string if it is valid UTF-8.
+    pub fn to_str(&self) -> Option<&str> {
+        std::str::from_utf8(&self.0).ok()
+    }
+
+    /// Decodes the string as Latin-1, the encoding of the STRING type.
+    pub fn to_latin1(&self) -> String {
+        self.0.iter().map(|&b| char::from(b)).collect()
+    }
+
+    /// Returns the raw bytes of the string.
+    pub fn to_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Returns the raw bytes of the string, consuming it.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl From<Vec<u8>> for XString {
+    fn from(bytes: Vec<u8>) -> Self {
+        XString(bytes)
+    }
+}
+
+impl From<&[u8]> for XString {
+    fn from(bytes: &[u8]) -> Self {
+        XString(bytes.to_vec())
+    }
+}
+
+impl From<&str> for XString {
+    fn from(s: &str) -> Self {
+        XString(s.as_bytes().to_vec())
+    }
+}
+
+impl fmt::Display for XString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.as_str_lossy())
+    }
+}
+
+impl fmt::Debug for XString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self.as_str_lossy())
+    }
+}
+
+/// Identifier of a window.
+pub type Window = XId;
 
 /// Identifier for a [VisualType].
-type VisualId = u32;
+pub type VisualId = u32;
 
 /// A ColorMap consists of a set of entries defining color values.
-type ColorMap = u32;
+pub type ColorMap = u32;
 
 /// The numerical code of the key in a Keyboard.
-type KeyCode = u8;
+pub type KeyCode = u8;
+
+/// A symbol engraved on a key, such as `a`, `A` or `Return`.
+pub type Keysym = u32;
+
+/// Identifier of an interned string (see InternAtom).
+pub type Atom = u32;
+
+/// Server time in milliseconds.
+pub type Timestamp = u32;
+
+/// A [Timestamp] standing for the current server time.
+pub const CURRENT_TIME: Timestamp = 0;
+
+/// The atoms that are predefined by the core protocol.
+pub mod atom {
+    use super::Atom;
+
+    pub const NONE: Atom = 0;
+    pub const ANY: Atom = 0;
+    pub const PRIMARY: Atom = 1;
+    pub const SECONDARY: Atom = 2;
+    pub const ARC: Atom = 3;
+    pub const ATOM: Atom = 4;
+    pub const BITMAP: Atom = 5;
+    pub const CARDINAL: Atom = 6;
+    pub const COLORMAP: Atom = 7;
+    pub const CURSOR: Atom = 8;
+    pub const CUT_BUFFER0: Atom = 9;
+    pub const CUT_BUFFER1: Atom = 10;
+    pub const CUT_BUFFER2: Atom = 11;
+    pub const CUT_BUFFER3: Atom = 12;
+    pub const CUT_BUFFER4: Atom = 13;
+    pub const CUT_BUFFER5: Atom = 14;
+    pub const CUT_BUFFER6: Atom = 15;
+    pub const CUT_BUFFER7: Atom = 16;
+    pub const DRAWABLE: Atom = 17;
+    pub const FONT: Atom = 18;
+    pub const INTEGER: Atom = 19;
+    pub const PIXMAP: Atom = 20;
+    pub const POINT: Atom = 21;
+    pub const RECTANGLE: Atom = 22;
+    pub const RESOURCE_MANAGER: Atom = 23;
+    pub const RGB_COLOR_MAP: Atom = 24;
+    pub const RGB_BEST_MAP: Atom = 25;
+    pub const RGB_BLUE_MAP: Atom = 26;
+    pub const RGB_DEFAULT_MAP: Atom = 27;
+    pub const RGB_GRAY_MAP: Atom = 28;
+    pub const RGB_GREEN_MAP: Atom = 29;
+    pub const RGB_RED_MAP: Atom = 30;
+    pub const STRING: Atom = 31;
+    pub const VISUALID: Atom = 32;
+    pub const WINDOW: Atom = 33;
+    pub const WM_COMMAND: Atom = 34;
+    pub const WM_HINTS: Atom = 35;
+    pub const WM_CLIENT_MACHINE: Atom = 36;
+    pub const WM_ICON_NAME: Atom = 37;
+    pub const WM_ICON_SIZE: Atom = 38;
+    pub const WM_NAME: Atom = 39;
+    pub const WM_NORMAL_HINTS: Atom = 40;
+    pub const WM_SIZE_HINTS: Atom = 41;
+    pub const WM_ZOOM_HINTS: Atom = 42;
+    pub const MIN_SPACE: Atom = 43;
+    pub const NORM_SPACE: Atom = 44;
+    pub const MAX_SPACE: Atom = 45;
+    pub const END_SPACE: Atom = 46;
+    pub const SUPERSCRIPT_X: Atom = 47;
+    pub const SUPERSCRIPT_Y: Atom = 48;
+    pub const SUBSCRIPT_X: Atom = 49;
+    pub const SUBSCRIPT_Y: Atom = 50;
+    pub const UNDERLINE_POSITION: Atom = 51;
+    pub const UNDERLINE_THICKNESS: Atom = 52;
+    pub const STRIKEOUT_ASCENT: Atom = 53;
+    pub const STRIKEOUT_DESCENT: Atom = 54;
+    pub const ITALIC_ANGLE: Atom = 55;
+    pub const X_HEIGHT: Atom = 56;
+    pub const QUAD_WIDTH: Atom = 57;
+    pub const WEIGHT: Atom = 58;
+    pub const POINT_SIZE: Atom = 59;
+    pub const RESOLUTION: Atom = 60;
+    pub const COPYRIGHT: Atom = 61;
+    pub const NOTICE: Atom = 62;
+    pub const FONT_NAME: Atom = 63;
+    pub const FAMILY_NAME: Atom = 64;
+    pub const FULL_NAME: Atom = 65;
+    pub const CAP_HEIGHT: Atom = 66;
+    pub const WM_CLASS: Atom = 67;
+    pub const WM_TRANSIENT_FOR: Atom = 68;
+}
 
 /// Family represents the protocol/address family
 pub type Family = u16;
 
+/// A rectangle, e.g. an area of a window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Rectangle {
+    pub x: i16,
+    pub y: i16,
+    pub width: u16,
+    pub height: u16,
+}
+
+impl Rectangle {
+    /// Returns the smallest rectangle containing both `self` and `other`.
+    pub fn union(&self, other: &Rectangle) -> Rectangle {
+        let x = self.x.min(other.x);
+        let y = self.y.min(other.y);
+        let right = (i32::from(self.x) + i32::from(self.width))
+            .max(i32::from(other.x) + i32::from(other.width));
+        let bottom = (i32::from(self.y) + i32::from(self.height))
+            .max(i32::from(other.y) + i32::from(other.height));
+        Rectangle {
+            x,
+            y,
+            width: (right - i32::from(x)).clamp(0, u16::MAX.into()) as u16,
+            height: (bottom - i32::from(y)).clamp(0, u16::MAX.into()) as u16,
+        }
+    }
+
+    /// Returns the part of `self` inside `other`, or `None` if they do not overlap.
+    pub fn intersection(&self, other: &Rectangle) -> Option<Rectangle> {
+        let x = self.x.max(other.x);
+        let y = self.y.max(other.y);
+        let right = (i32::from(self.x) + i32::from(self.width))
+            .min(i32::from(other.x) + i32::from(other.width));
+        let bottom = (i32::from(self.y) + i32::from(self.height))
+            .min(i32::from(other.y) + i32::from(other.height));
+        if right <= i32::from(x) || bottom <= i32::from(y) {
+            return None;
+        }
+        Some(Rectangle {
+            x,
+            y,
+            width: (right - i32::from(x)) as u16,
+            height: (bottom - i32::from(y)) as u16,
+        })
+    }
+
+    /// Returns true if the point is inside the rectangle.
+    pub fn contains(&self, x: i16, y: i16) -> bool {
+        let (x, y) = (i32::from(x), i32::from(y));
+        x >= i32::from(self.x)
+            && y >= i32::from(self.y)
+            && x < i32::from(self.x) + i32::from(self.width)
+            && y < i32::from(self.y) + i32::from(self.height)
+    }
+}
+
+/// A set of events a client can select on a window.
+///
+/// Masks can be combined with `|`, e.g. `EventMask::KEY_PRESS | EventMask::BUTTON_PRESS`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct EventMask(pub u32);
+
+impl EventMask {
+    pub const NO_EVENT: Self = Self(0);
+    pub const KEY_PRESS: Self = Self(1 << 0);
+    pub const KEY_RELEASE: Self = Self(1 << 1);
+    pub const BUTTON_PRESS: Self = Self(1 << 2);
+    pub const BUTTON_RELEASE: Self = Self(1 << 3);
+    pub const ENTER_WINDOW: Self = Self(1 << 4);
+    pub const LEAVE_WINDOW: Self = Self(1 << 5);
+    pub const POINTER_MOTION: Self = Self(1 << 6);
+    pub const POINTER_MOTION_HINT: Self = Self(1 << 7);
+    pub const BUTTON1_MOTION: Self = Self(1 << 8);
+    pub const BUTTON2_MOTION: Self = Self(1 << 9);
+    pub const BUTTON3_MOTION: Self = Self(1 << 10);
+    pub const BUTTON4_MOTION: Self = Self(1 << 11);
+    pub const BUTTON5_MOTION: Self = Self(1 << 12);
+    pub const BUTTON_MOTION: Self = Self(1 << 13);
+    pub const KEYMAP_STATE: Self = Self(1 << 14);
+    pub const EXPOSURE: Self = Self(1 << 15);
+    pub const VISIBILITY_CHANGE: Self = Self(1 << 16);
+    pub const STRUCTURE_NOTIFY: Self = Self(1 << 17);
+    pub const RESIZE_REDIRECT: Self = Self(1 << 18);
+    pub const SUBSTRUCTURE_NOTIFY: Self = Self(1 << 19);
+    pub const SUBSTRUCTURE_REDIRECT: Self = Self(1 << 20);
+    pub const FOCUS_CHANGE: Self = Self(1 << 21);
+    pub const PROPERTY_CHANGE: Self = Self(1 << 22);
+    pub const COLORMAP_CHANGE: Self = Self(1 << 23);
+    pub const OWNER_GRAB_BUTTON: Self = Self(1 << 24);
+
+    /// Returns true if every event in `other` is also in `self`.
+    pub fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Returns true if `self` and `other` share at least one event.
+    pub fn intersects(self, other: Self) -> bool {
+        self.0 & other.0 != 0
+    }
+}
+
+impl BitOr for EventMask {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for EventMask {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
 /// In x11, a Screen represents a physical display where Windows can be rendered.
 /// So each struct fields in `Screen` represents various properties of the display.
+#[derive(Debug, Clone)]
 pub struct Screen {
     /// Id of the root window.
-    pub root: XId,
+    pub root: Window,
+    /// Default [ColorMap] of the Screen.
+    pub default_colormap: ColorMap,
+    /// The pixel values that correspond to white color on the screen.
+    pub white_pixel: u32,
+    // The pixel values that correspond to black color on the screen.
+    pub black_pixel: u32,
+    /// A set of input events that the root window is currently set to report.
+    /// This includes things like keyboard and mouse events.
+    pub current_input_masks: EventMask,
     /// Screen resolution width in pixels.
     pub width_in_px: u16,
     /// Screen resolution height in pixels.
-    height_in_px: u16,
+    pub height_in_px: u16,
     /// Screen width in millimeters (Physical, I guess).
-    width_in_mm: u16,
+    pub width_in_mm: u16,
     /// Screen height in millimeters (Physical, I guess).
-    height_in_mm: u16,
-    /// The color depths that the screen supports.
-    allowed_depths: Vec<Depth>,
-    /// Default color depth of the root window.
-    root_depth: u8,
-    /// Id of [VisualType] for the root window.
-    root_visual: VisualId,
-    /// Default [ColorMap] of the Screen.
-    default_colormap: ColorMap,
-    /// The pixel values that correspond to white color on the screen.
-    white_pixel: u32,
-    // The pixel values that correspond to black color on the screen.
-    black_pixel: u32,
+    pub height_in_mm: u16,
     /// The minimum number of color maps that can be installed on the screen simultaneously.
-    min_installed_maps: u16,
+    pub min_installed_maps: u16,
     /// The maximum number of color maps that can be installed on the screen simultaneously.
-    max_installed_maps: u16,
+    pub max_installed_maps: u16,
+    /// Id of [VisualType] for the root window.
+    pub root_visual: VisualId,
     /// [BackingStore]
-    backing_stores: BackingStore,
+    pub backing_stores: BackingStore,
     /// Indicating whether the screen supports "save-under" functionality.
     /// This feature allows windows to automatically save and restore the
     /// area under them when they are moved or resized.
-    save_unders: bool,
-    /// A set of input events that the root window is currently set to report.
-    /// This includes things like keyboard and mouse events.
-    current_input_masks: u16, // SETofEVENT
+    pub save_unders: bool,
+    /// Default color depth of the root window.
+    pub root_depth: u8,
+    /// The color depths that the screen supports.
+    pub allowed_depths: Vec<Depth>,
 }
 
 // I don't fully understand what a backing-store is. But,
 /// A backing-store of any of the `BackingStore` variants advises
 /// the server when to maintain the contents of obscured regions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BackingStore {
     Never,
     WhenMapped,
     Always,
-    // TODO: If necessary, implement methods to convert from
-    // and to the numeric representations (0, 1, 2) of these variants
-    // Or we could simply use u8 for this instead of the enum
 }
 
+impl From<u8> for BackingStore {
+    fn from(value: u8) -> Self {
+        match value {
+            1 => BackingStore::WhenMapped,
+            2 => BackingStore::Always,
+            _ => BackingStore::Never,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct Depth {
-    depth: u8,
-    visuals: Vec<VisualType>,
+    pub depth: u8,
+    pub visuals: Vec<VisualType>,
 }
 
-pub struct VisualClass(u8);
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VisualClass(pub u8);
 
 impl VisualClass {
     pub const STATIC_GRAY: Self = Self(0);
-    pub const STATIC_COLOR: Self = Self(1);
-    pub const TRUE_COLOR: Self = Self(2);
-    pub const GRAY_SCALE: Self = Self(3);
-    pub const PSEUDO_COLOR: Self = Self(4);
+    pub const GRAY_SCALE: Self = Self(1);
+    pub const STATIC_COLOR: Self = Self(2);
+    pub const PSEUDO_COLOR: Self = Self(3);
+    pub const TRUE_COLOR: Self = Self(4);
     pub const DIRECT_COLOR: Self = Self(5);
 }
 
 /// VisualType describes the format of the pixel data in a window or image
+#[derive(Debug, Clone)]
 pub struct VisualType {
     /// Unique Id of the visual type.
-    visual_id: VisualId,
-    class: VisualClass,
-    red_mask: u32,
-    green_mask: u32,
-    blue_mask: u32,
-    bits_per_rgb_value: u8,
-    colormap_entries: u16,
+    pub visual_id: VisualId,
+    pub class: VisualClass,
+    pub bits_per_rgb_value: u8,
+    pub colormap_entries: u16,
+    pub red_mask: u32,
+    pub green_mask: u32,
+    pub blue_mask: u32,
 }
 
 #[derive(Debug)]
@@ -124,47 +428,49 @@ pub struct ConnSetupRequest {
 }
 
 /// Represents the response received from the x11 server if the connection is accepted.
+#[derive(Debug, Clone)]
 pub struct ConnSetup {
-    success: u8,
+    pub success: u8,
     /// Major protocol version supported by the server.
-    protocol_major_version: u16,
+    pub protocol_major_version: u16,
     /// Minor protocol version supported by the  server.
-    protocol_minor_version: u16,
+    pub protocol_minor_version: u16,
     /// Vendor gives some identification of the owner of the server implementation.
-    vendor: String,
+    pub vendor: XString,
     /// Release number of the x11 server.
-    release_number: u32,
+    pub release_number: u32,
     /// Used by the client to generate resource IDs (like window IDs).
-    resource_id_base: u32,
+    pub resource_id_base: u32,
     /// Used by the client to generate resource IDs (like window IDs).
-    resource_id_mask: u32,
+    pub resource_id_mask: u32,
     /// Byte order of the image data.
-    image_byte_order: u8,
+    pub image_byte_order: u8,
     /// Alignment requirements for bitmap data.
-    bitmap_scanline_unit: u8,
-    bitmap_scanline_pad: u8,
+    pub bitmap_scanline_unit: u8,
+    pub bitmap_scanline_pad: u8,
     /// Bit order within a byte of bitmap data.
-    bitmap_bit_order: BitOrder,
+    pub bitmap_bit_order: BitOrder,
     /// A list of supported formats for pixmap images.
-    pixmap_formats: Vec<Format>,
+    pub pixmap_formats: Vec<Format>,
     /// Screen(s) managed by the server.
-    roots: Vec<Screen>,
+    pub roots: Vec<Screen>,
     /// Size of the server's motion event buffer.
-    motion_buffer_size: u32,
+    pub motion_buffer_size: u32,
     /// The maximum length of a request that can be sent to the server.
-    maximum_request_length: u16,
+    pub maximum_request_length: u16,
     /// The range of [KeyCode]s that are recognized by the server.
-    min_keycode: KeyCode,
-    max_keycode: KeyCode,
+    pub min_keycode: KeyCode,
+    pub max_keycode: KeyCode,
 }
 
 /// Represents the response received from the x11 server if the connection is refused.
+#[derive(Debug)]
 pub struct ConnFailed {
     /// The connection status
-    status: u8,
+    pub status: u8,
     /// Major and minor protocol version supported by the server.
-    protocol_major_version: u8,
-    protocol_minor_version: u8,
+    pub protocol_major_version: u16,
+    pub protocol_minor_version: u16,
     /// Reason of failure.
-    reason: String,
+    pub reason: XString,
 }
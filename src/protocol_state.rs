@@ -0,0 +1,1042 @@
+//! The state of the X protocol on a client connection, without any I/O.
+//!
+//! [ProtocolState] numbers the requests written to the server and sorts the bytes
+//! read from it into replies, errors and events. Whoever owns the socket feeds it:
+//! the blocking [crate::connection::Connection] after each read,
+//! [crate::aio::Connection] for every [crate::aio::AsyncIoDriver], so the async
+//! backends share all the parsing, [crate::shared::SharedConnection] from whichever
+//! thread reads, and the io_uring connection of `uring` from the completions of
+//! its reads.
+//!
+//! ```no_run
+//! # use mousetrap::protocol::ConnSetup;
+//! # use mousetrap::protocol_state::ProtocolState;
+//! # use mousetrap::request::GetInputFocus;
+//! # fn write(_: &[u8]) {}
+//! # fn read() -> Vec<u8> { Vec::new() }
+//! # fn example(setup: ConnSetup) -> Result<(), mousetrap::errors::ConnectionError> {
+//! let mut state = ProtocolState::new(setup, 0);
+//! let request = GetInputFocus.serialize();
+//! write(&request);
+//! let sequence = state.request_sent(&request, true);
+//! let reply = loop {
+//!     if let Some(reply) = state.take_response(sequence) {
+//!         break reply?;
+//!     }
+//!     state.bytes_received(&read())?;
+//! };
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::connection::{message_length, next_resource_id, ExtensionInfo};
+use crate::cookie::ParseMode;
+use crate::errors::{ConnectionError, ErrorCode, SerializeError, XError};
+use crate::event::{self, Event};
+use crate::ewmh;
+use crate::icccm;
+use crate::keyboard::KeyboardState;
+use crate::protocol::{ConnSetup, Screen, Timestamp, Window, XId};
+use crate::reply::QueryExtensionReply;
+use crate::request;
+#[cfg(feature = "trace")]
+use crate::trace::{self, Trace};
+use crate::utils::deserialize_into;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::io;
+use std::os::fd::OwnedFd;
+
+/// Events queued at most by default, see [ProtocolState::set_event_queue_limit].
+pub const DEFAULT_EVENT_QUEUE_LIMIT: usize = 1 << 16;
+
+/// The opcodes of a request that was sent but not necessarily processed yet.
+#[derive(Debug, Clone, Copy)]
+struct InFlightRequest {
+    sequence: u16,
+    /// Whether a cookie waits on the reply or error of the request.
+    checked: bool,
+    /// Whether the reply carries file descriptors, as many as its second byte says.
+    fd_reply: bool,
+    major_opcode: u8,
+    /// The second byte of the request, which is the minor opcode for extension requests.
+    minor_opcode: u8,
+}
+
+/// The state of a client connection. See the [module](self) documentation.
+pub struct ProtocolState {
+    /// The server's response to the connection setup.
+    setup: ConnSetup,
+    /// Sequence number of the last request sent to the server.
+    sequence_number: u16,
+    /// Bytes read from the server that do not form a complete message yet.
+    read_buffer: Vec<u8>,
+    /// Events read from the server that were not consumed yet.
+    event_queue: VecDeque<Event>,
+    /// Events queued at most, the oldest being dropped beyond.
+    event_queue_limit: usize,
+    /// Events dropped because the queue was full.
+    dropped_events: u64,
+    /// Replies and errors read from the server, by the sequence number of their request.
+    /// A few requests, such as RECORD's EnableContext, are answered with several replies.
+    responses: HashMap<u16, VecDeque<Result<Vec<u8>, XError>>>,
+    /// File descriptors received from the server and not attributed to a reply yet,
+    /// oldest first.
+    received_fds: VecDeque<OwnedFd>,
+    /// File descriptors passed with replies, by the sequence number of their request.
+    reply_fds: HashMap<u16, Vec<OwnedFd>>,
+    /// Sequence numbers of the requests passed to [ProtocolState::abandon]. Their
+    /// replies and errors are discarded as they arrive.
+    abandoned: HashSet<u16>,
+    /// Checked requests the server may still answer, oldest first. Unchecked
+    /// requests are not kept, as their errors carry the opcodes naming them, so
+    /// that a stream of them does not grow this.
+    in_flight: VecDeque<InFlightRequest>,
+    /// The codes of the extensions asked about, `None` for those the server does
+    /// not support.
+    extensions: HashMap<&'static str, Option<ExtensionInfo>>,
+    /// Extension name and request names (indexed by minor opcode) by major opcode.
+    extension_requests: HashMap<u8, (&'static str, &'static [&'static str])>,
+    /// Extension name and error names (indexed from the first error) by first error code.
+    extension_errors: BTreeMap<u8, (&'static str, &'static [&'static str])>,
+    /// Cached keyboard, modifier and pointer mappings, dropped by MappingNotify.
+    keyboard: KeyboardState,
+    /// The atoms of _NET_WM_PING messages if they are answered as they are read.
+    auto_pong: Option<(icccm::Atoms, ewmh::Atoms)>,
+    /// The window and timestamp of the pings read but not answered yet.
+    pings: VecDeque<(Window, Timestamp)>,
+    /// Index of the screen given in the display name.
+    screen_number: usize,
+    /// Length of the longest request the server accepts, in 4-byte units. Raised
+    /// by BIG-REQUESTS.
+    maximum_request_length: u32,
+    /// Whether BIG-REQUESTS is enabled.
+    big_requests: bool,
+    /// The last resource id handed out by `generate_id`, without the base.
+    last_resource_id: u32,
+    /// Called with the errors of requests nobody waits on.
+    error_handler: Option<Box<dyn Fn(XError) + Send>>,
+    /// Errors of unchecked requests collected between [ProtocolState::begin_sync]
+    /// and [ProtocolState::end_sync].
+    sync_errors: Option<Vec<XError>>,
+    /// Whether the server closed the connection or it broke.
+    disconnected: bool,
+    /// How strictly replies are parsed.
+    parse_mode: ParseMode,
+    /// What is logged of the traffic. See [ProtocolState::set_trace].
+    #[cfg(feature = "trace")]
+    trace: Trace,
+}
+
+impl ProtocolState {
+    /// Creates the state of a connection the server accepted with `setup`.
+    /// `screen_number` is the one given in the display name.
+    pub fn new(setup: ConnSetup, screen_number: usize) -> Self {
+        Self {
+            maximum_request_length: u32::from(setup.maximum_request_length),
+            setup,
+            sequence_number: 0,
+            read_buffer: Vec::new(),
+            event_queue: VecDeque::new(),
+            event_queue_limit: DEFAULT_EVENT_QUEUE_LIMIT,
+            dropped_events: 0,
+            responses: HashMap::new(),
+            received_fds: VecDeque::new(),
+            reply_fds: HashMap::new(),
+            abandoned: HashSet::new(),
+            in_flight: VecDeque::new(),
+            extensions: HashMap::new(),
+            extension_requests: HashMap::new(),
+            extension_errors: BTreeMap::new(),
+            keyboard: KeyboardState::default(),
+            auto_pong: None,
+            pings: VecDeque::new(),
+            screen_number,
+            big_requests: false,
+            last_resource_id: 0,
+            error_handler: None,
+            sync_errors: None,
+            disconnected: false,
+            parse_mode: ParseMode::default(),
+            #[cfg(feature = "trace")]
+            trace: Trace::Off,
+        }
+    }
+
+    /// Returns the server's response to the connection setup.
+    pub fn setup(&self) -> &ConnSetup {
+        &self.setup
+    }
+
+    /// Returns the screen given in the display name, or the first screen if the
+    /// server has no such screen.
+    pub fn default_screen(&self) -> &Screen {
+        &self.setup.roots[self.default_screen_number()]
+    }
+
+    /// Returns the number of the default screen, its index in the roots of the setup.
+    pub fn default_screen_number(&self) -> usize {
+        if self.screen_number < self.setup.roots.len() {
+            self.screen_number
+        } else {
+            0
+        }
+    }
+
+    /// Returns the root window of the default screen.
+    pub fn default_root(&self) -> Window {
+        self.default_screen().root
+    }
+
+    /// Allocates a new id for a resource such as a window or a pixmap.
+    pub fn generate_id(&mut self) -> Result<XId, ConnectionError> {
+        next_resource_id(&self.setup, &mut self.last_resource_id)
+    }
+
+    /// Sets how strictly replies are parsed.
+    pub fn set_parse_mode(&mut self, mode: ParseMode) {
+        self.parse_mode = mode;
+    }
+
+    /// Returns how strictly replies are parsed.
+    pub fn parse_mode(&self) -> ParseMode {
+        self.parse_mode
+    }
+
+    /// Sets the function called with the errors of unchecked requests. By default
+    /// these errors are logged as warnings through the `log` crate.
+    pub fn set_error_handler(&mut self, handler: Box<dyn Fn(XError) + Send>) {
+        self.error_handler = Some(handler);
+    }
+
+    /// Sets what is logged of the requests sent and the messages received, see
+    /// [crate::trace].
+    #[cfg(feature = "trace")]
+    pub fn set_trace(&mut self, trace: Trace) {
+        self.trace = trace;
+    }
+
+    /// Returns whether the connection is gone, see [ProtocolState::connection_lost].
+    pub fn is_disconnected(&self) -> bool {
+        self.disconnected
+    }
+
+    /// Returns the length of the longest request the server accepts, in bytes.
+    pub fn maximum_request_bytes(&self) -> usize {
+        self.maximum_request_length as usize * 4
+    }
+
+    /// Returns whether BIG-REQUESTS is enabled.
+    pub fn big_requests_enabled(&self) -> bool {
+        self.big_requests
+    }
+
+    /// Records that BIG-REQUESTS was enabled, the server accepting requests of up
+    /// to `maximum_request_length` 4-byte units from now on.
+    pub fn enable_big_requests(&mut self, maximum_request_length: u32) {
+        self.big_requests = true;
+        self.maximum_request_length = maximum_request_length;
+    }
+
+    /// Fails with [SerializeError::TooLong] if `request` is longer than the server
+    /// accepts. It must not be written then: the server would close the connection.
+    pub fn check_request_length(&self, request: &[u8]) -> Result<(), ConnectionError> {
+        if request.len() > self.maximum_request_bytes() {
+            return Err(ConnectionError::Serialize(SerializeError::TooLong {
+                field: "request",
+                length: request.len(),
+                max: self.maximum_request_bytes(),
+            }));
+        }
+        Ok(())
+    }
+
+    /// Records that a serialized request was written to the server, and returns its
+    /// sequence number. `checked` is whether a cookie waits on its reply or error.
+    pub fn request_sent(&mut self, request: &[u8], checked: bool) -> u16 {
+        self.record_request(request, checked, false)
+    }
+
+    /// Like [ProtocolState::request_sent], for a request whose reply carries file
+    /// descriptors. They are handed out by [ProtocolState::take_reply_fds].
+    pub fn request_with_fd_reply_sent(&mut self, request: &[u8]) -> u16 {
+        self.record_request(request, true, true)
+    }
+
+    fn record_request(&mut self, bytes: &[u8], checked: bool, fd_reply: bool) -> u16 {
+        self.sequence_number = self.sequence_number.wrapping_add(1);
+        // What is left for the sequence number answered a request sent 65536
+        // requests ago, whose cookie was dropped.
+        self.responses.remove(&self.sequence_number);
+        self.reply_fds.remove(&self.sequence_number);
+        self.abandoned.remove(&self.sequence_number);
+        while self
+            .in_flight
+            .front()
+            .is_some_and(|request| request.sequence == self.sequence_number)
+        {
+            self.in_flight.pop_front();
+        }
+        let request = InFlightRequest {
+            sequence: self.sequence_number,
+            checked,
+            fd_reply,
+            major_opcode: bytes.first().copied().unwrap_or_default(),
+            minor_opcode: bytes.get(1).copied().unwrap_or_default(),
+        };
+        log::trace!(
+            "sent {} (seq {}, {} bytes)",
+            self.describe_request(&request),
+            request.sequence,
+            bytes.len()
+        );
+        #[cfg(feature = "trace")]
+        self.trace_request(&request, bytes);
+        if checked {
+            self.in_flight.push_back(request);
+        }
+        self.sequence_number
+    }
+
+    /// Takes bytes read from the server and dispatches every message they complete
+    /// to the responses or the event queue. Returns whether any message was complete.
+    ///
+    /// A message that cannot be parsed is dropped and its error returned once the
+    /// messages following it were dispatched.
+    pub fn bytes_received(&mut self, bytes: &[u8]) -> Result<bool, ConnectionError> {
+        self.read_buffer.extend_from_slice(bytes);
+        // The messages are copied out one by one, and the bytes they took dropped
+        // from the buffer at once.
+        let mut offset = 0;
+        let mut result = Ok(false);
+        loop {
+            let length = match message_length(&self.read_buffer[offset..]) {
+                Ok(Some(length)) => length,
+                Ok(None) => break,
+                Err(err) => {
+                    // The end of the message cannot be found, so neither can the
+                    // next ones: the connection is unusable.
+                    self.read_buffer.clear();
+                    self.connection_lost(io::Error::from(io::ErrorKind::ConnectionAborted));
+                    return Err(err.into());
+                }
+            };
+            let message = self.read_buffer[offset..offset + length].to_vec();
+            offset += length;
+            match self.dispatch(message) {
+                Ok(()) => result = result.map(|_| true),
+                Err(err) => result = result.and(Err(err)),
+            }
+        }
+        self.read_buffer.drain(..offset);
+        result
+    }
+
+    /// Takes file descriptors read from the server along with bytes, before the
+    /// bytes are passed to [ProtocolState::bytes_received].
+    pub fn fds_received(&mut self, fds: impl IntoIterator<Item = OwnedFd>) {
+        self.received_fds.extend(fds);
+    }
+
+    /// Takes the reply or error to the request with the given sequence number, if
+    /// it was received.
+    pub fn take_response(&mut self, sequence: u16) -> Option<Result<Vec<u8>, ConnectionError>> {
+        let queue = self.responses.get_mut(&sequence)?;
+        let response = queue.pop_front();
+        if queue.is_empty() {
+            self.responses.remove(&sequence);
+        }
+        response.map(|response| response.map_err(ConnectionError::ProtocolError))
+    }
+
+    /// Takes the file descriptors passed with the reply to a request sent with
+    /// [ProtocolState::request_with_fd_reply_sent].
+    pub fn take_reply_fds(&mut self, sequence: u16) -> Vec<OwnedFd> {
+        self.reply_fds.remove(&sequence).unwrap_or_default()
+    }
+
+    /// Stops waiting on the reply to the request with the given sequence number,
+    /// e.g. after a timeout: whatever the server answers it with is discarded.
+    pub fn abandon(&mut self, sequence: u16) {
+        self.responses.remove(&sequence);
+        self.reply_fds.remove(&sequence);
+        let pending = self
+            .in_flight
+            .iter()
+            .any(|request| request.sequence == sequence);
+        if pending {
+            self.abandoned.insert(sequence);
+        }
+    }
+
+    /// Takes the oldest event received.
+    pub fn take_event(&mut self) -> Option<Event> {
+        self.event_queue.pop_front()
+    }
+
+    /// Takes the oldest event accepted by `filter`. The others stay queued, in order.
+    ///
+    /// The queue is searched from its start, so this takes longer the more events
+    /// are left in it, up to [ProtocolState::set_event_queue_limit].
+    pub fn take_event_matching<F>(&mut self, filter: F) -> Option<Event>
+    where
+        F: Fn(&Event) -> bool,
+    {
+        let position = self.event_queue.iter().position(filter)?;
+        self.event_queue.remove(position)
+    }
+
+    /// Returns whether events were received but not taken yet.
+    pub fn has_queued_events(&self) -> bool {
+        !self.event_queue.is_empty()
+    }
+
+    /// Sets how many events are queued at most, [DEFAULT_EVENT_QUEUE_LIMIT] by
+    /// default, so that a client which stopped taking events does not run out of
+    /// memory. When an event arrives at a full queue, the oldest event sent by the
+    /// server is dropped and counted by an [Event::EventsDropped] at the front of
+    /// the queue, which does not count towards the limit. [Event::Wakeup] and
+    /// [Event::ConnectionClosed] are never dropped.
+    pub fn set_event_queue_limit(&mut self, limit: usize) {
+        self.event_queue_limit = limit.max(1);
+        while self.queued_events() > self.event_queue_limit && self.drop_oldest_event() {}
+    }
+
+    /// Returns how many events were dropped because the queue was full.
+    pub fn dropped_events(&self) -> u64 {
+        self.dropped_events
+    }
+
+    /// Returns how many events count towards the limit of the queue.
+    fn queued_events(&self) -> usize {
+        let dropped = matches!(self.event_queue.front(), Some(Event::EventsDropped(_)));
+        self.event_queue.len() - usize::from(dropped)
+    }
+
+    /// Queues an event, making room for it if the queue is full.
+    fn queue_event(&mut self, event: Event) {
+        if is_from_server(&event) && self.queued_events() >= self.event_queue_limit {
+            self.drop_oldest_event();
+        }
+        self.event_queue.push_back(event);
+    }
+
+    /// Drops the oldest event sent by the server, and returns whether there was one.
+    fn drop_oldest_event(&mut self) -> bool {
+        let Some(position) = self.event_queue.iter().position(is_from_server) else {
+            return false;
+        };
+        self.event_queue.remove(position);
+        match self.event_queue.front_mut() {
+            Some(Event::EventsDropped(count)) => *count += 1,
+            _ => self.event_queue.push_front(Event::EventsDropped(1)),
+        }
+        self.dropped_events += 1;
+        // Every drop would flood the log, so only the 1st, 2nd, 4th... are logged.
+        if self.dropped_events.is_power_of_two() {
+            log::warn!(
+                "event queue full ({} events), {} events dropped so far",
+                self.event_queue_limit,
+                self.dropped_events
+            );
+        }
+        true
+    }
+
+    /// Queues [Event::Wakeup], unless one is queued already.
+    pub(crate) fn queue_wakeup(&mut self) {
+        if !self.event_queue.iter().any(|e| matches!(e, Event::Wakeup)) {
+            self.queue_event(Event::Wakeup);
+        }
+    }
+
+    /// Starts collecting the errors of unchecked requests instead of passing them
+    /// to the error handler, for a round trip that checks them.
+    pub fn begin_sync(&mut self) {
+        self.sync_errors = Some(Vec::new());
+    }
+
+    /// Returns the errors collected since [ProtocolState::begin_sync].
+    pub fn end_sync(&mut self) -> Vec<XError> {
+        self.sync_errors.take().unwrap_or_default()
+    }
+
+    /// Returns the codes of an extension the server was asked about, `None` inside
+    /// if it does not support it, or `None` if it was not asked yet. The backends
+    /// ask with QueryExtension and pass the reply to
+    /// [ProtocolState::extension_queried], so each extension is asked about once.
+    pub fn extension(&self, name: &str) -> Option<Option<ExtensionInfo>> {
+        self.extensions.get(name).copied()
+    }
+
+    /// Keeps the answer to the QueryExtension of `name` and returns the codes of
+    /// the extension, or `None` if the server does not support it.
+    pub fn extension_queried(
+        &mut self,
+        name: &'static str,
+        reply: &QueryExtensionReply,
+    ) -> Option<ExtensionInfo> {
+        let info = reply.present.then_some(ExtensionInfo {
+            major_opcode: reply.major_opcode,
+            first_event: reply.first_event,
+            first_error: reply.first_error,
+        });
+        self.extensions.insert(name, info);
+        info
+    }
+
+    /// Registers the names of the requests and errors of an extension about to be
+    /// used, indexed like the `REQUEST_NAMES` and `ERROR_NAMES` of the extension
+    /// modules, unless they were already.
+    pub fn register_extension(
+        &mut self,
+        name: &'static str,
+        info: ExtensionInfo,
+        request_names: &'static [&'static str],
+        error_names: &'static [&'static str],
+    ) {
+        if self.extension_requests.contains_key(&info.major_opcode) {
+            return;
+        }
+        self.register_extension_requests(info.major_opcode, name, request_names);
+        if !error_names.is_empty() {
+            self.register_extension_errors(info.first_error, name, error_names);
+        }
+    }
+
+    /// Registers the names of the requests of an extension, so errors caused by them
+    /// name the request, e.g. `BadMatch for RANDR:SetCrtcConfig`.
+    ///
+    /// `request_names` is indexed by minor opcode.
+    pub fn register_extension_requests(
+        &mut self,
+        major_opcode: u8,
+        extension_name: &'static str,
+        request_names: &'static [&'static str],
+    ) {
+        self.extension_requests
+            .insert(major_opcode, (extension_name, request_names));
+    }
+
+    /// Registers the names of the errors of an extension, so they are decoded to
+    /// e.g. `RANDR:BadOutput` rather than a bare error code.
+    ///
+    /// `first_error` is the one reported by QueryExtension and `error_names` is
+    /// indexed by the error code minus `first_error`.
+    pub fn register_extension_errors(
+        &mut self,
+        first_error: u8,
+        extension_name: &'static str,
+        error_names: &'static [&'static str],
+    ) {
+        self.extension_errors
+            .insert(first_error, (extension_name, error_names));
+    }
+
+    /// Returns the name of an extension error, as far as it is known.
+    fn error_name(&self, code: u8) -> Option<String> {
+        let (first_error, (extension, errors)) =
+            self.extension_errors.range(..=code).next_back()?;
+        let name = errors.get(usize::from(code - first_error))?;
+        Some(format!("{}:{}", extension, name))
+    }
+
+    /// Returns the name of a request, as far as it is known.
+    fn request_name(&self, major_opcode: u8, minor_opcode: u8) -> Option<String> {
+        if let Some((extension, requests)) = self.extension_requests.get(&major_opcode) {
+            // Unused minor opcodes have an empty name.
+            return Some(match requests.get(usize::from(minor_opcode)) {
+                Some(request) if !request.is_empty() => format!("{}:{}", extension, request),
+                _ => format!("{}:{}", extension, minor_opcode),
+            });
+        }
+        request::request_name(major_opcode).map(String::from)
+    }
+
+    /// Returns the name of a request, or its opcodes if the name is not known.
+    fn describe_request(&self, request: &InFlightRequest) -> String {
+        let (major_opcode, minor_opcode) = (request.major_opcode, request.minor_opcode);
+        self.request_name(major_opcode, minor_opcode)
+            .unwrap_or_else(|| format!("request {}.{}", major_opcode, minor_opcode))
+    }
+
+    /// Returns the cached keyboard, modifier and pointer mappings.
+    pub(crate) fn keyboard_mut(&mut self) -> &mut KeyboardState {
+        &mut self.keyboard
+    }
+
+    /// Sets the atoms of the _NET_WM_PING messages to answer as they are read, or
+    /// `None` to queue them like other events. See [ProtocolState::take_ping].
+    pub(crate) fn set_auto_pong(&mut self, atoms: Option<(icccm::Atoms, ewmh::Atoms)>) {
+        self.auto_pong = atoms;
+    }
+
+    /// Takes the window and timestamp of the oldest ping to answer.
+    pub(crate) fn take_ping(&mut self) -> Option<(Window, Timestamp)> {
+        self.pings.pop_front()
+    }
+
+    /// Converts an error of the socket, noting whether it means the connection is gone.
+    ///
+    /// On the first such error, [Event::ConnectionClosed] is queued and the requests
+    /// waiting on a reply are failed.
+    pub fn connection_lost(&mut self, err: io::Error) -> ConnectionError {
+        let closed = matches!(
+            err.kind(),
+            io::ErrorKind::UnexpectedEof
+                | io::ErrorKind::ConnectionReset
+                | io::ErrorKind::ConnectionAborted
+                | io::ErrorKind::BrokenPipe
+        );
+        if !closed {
+            return err.into();
+        }
+        if !self.disconnected {
+            self.disconnected = true;
+            self.in_flight.clear();
+            self.abandoned.clear();
+            self.queue_event(Event::ConnectionClosed);
+        }
+        ConnectionError::Disconnected
+    }
+
+    /// Forgets the in-flight requests that were sent before the request with the
+    /// given sequence number. The server processes requests in order, so once a
+    /// message about a request arrives, earlier requests can no longer fail.
+    fn retire_requests_before(&mut self, sequence: u16) {
+        // Compare by distance to the last sent request, to cope with wrapping sequence numbers.
+        let age = |s: u16| self.sequence_number.wrapping_sub(s);
+        let limit = age(sequence);
+        while self
+            .in_flight
+            .front()
+            .is_some_and(|r| age(r.sequence) > limit)
+        {
+            if let Some(request) = self.in_flight.pop_front() {
+                self.abandoned.remove(&request.sequence);
+            }
+        }
+    }
+
+    /// Parses an error message and attributes it to the request that caused it.
+    fn decode_error(&self, message: &[u8]) -> Result<XError, ConnectionError> {
+        let mut error = XError::from_bytes(message).map_err(|err| err.in_message(message))?;
+        let request = self
+            .in_flight
+            .iter()
+            .find(|request| request.sequence == error.sequence);
+        error.request_name = match request {
+            Some(request) => self.request_name(request.major_opcode, request.minor_opcode),
+            None => self.request_name(error.major_opcode, error.minor_opcode as u8),
+        };
+        if let ErrorCode::Other(code) = error.code {
+            error.error_name = self.error_name(code);
+        }
+        Ok(error)
+    }
+
+    /// Hands the error of an unchecked request to the error handler, or to a
+    /// running sync.
+    fn handle_unchecked_error(&mut self, error: XError) {
+        if let Some(errors) = &mut self.sync_errors {
+            errors.push(error);
+            return;
+        }
+        match &self.error_handler {
+            Some(handler) => handler(error),
+            None => log::warn!("X error in unchecked request: {}", error),
+        }
+    }
+
+    /// Stores a message read from the server where it will be looked for.
+    fn dispatch(&mut self, message: Vec<u8>) -> Result<(), ConnectionError> {
+        #[cfg(feature = "trace")]
+        self.trace_message(&message);
+        // Every message but KeymapNotify carries the sequence number of the last
+        // request the server processed.
+        if message[0] & 0x7f != event::code::KEYMAP_NOTIFY {
+            let (sequence, _) = deserialize_into::<u16>(&message[2..4])?;
+            self.retire_requests_before(sequence);
+        }
+        match message[0] {
+            // Errors (code 0) and replies (code 1) answer a request.
+            0 => {
+                let error = self.decode_error(&message)?;
+                log::trace!("received error {}", error);
+                if self.abandoned.contains(&error.sequence) {
+                    return Ok(());
+                }
+                let checked = self
+                    .in_flight
+                    .iter()
+                    .any(|request| request.sequence == error.sequence && request.checked);
+                if checked {
+                    self.responses
+                        .entry(error.sequence)
+                        .or_default()
+                        .push_back(Err(error));
+                } else {
+                    self.handle_unchecked_error(error);
+                }
+            }
+            1 => {
+                let (sequence, _) = deserialize_into::<u16>(&message[2..4])?;
+                if log::log_enabled!(log::Level::Trace) {
+                    let name = self
+                        .in_flight
+                        .iter()
+                        .find(|request| request.sequence == sequence)
+                        .map_or_else(
+                            || String::from("unknown request"),
+                            |request| self.describe_request(request),
+                        );
+                    log::trace!(
+                        "received reply to {} (seq {}, {} bytes)",
+                        name,
+                        sequence,
+                        message.len()
+                    );
+                }
+                let fd_reply = self
+                    .in_flight
+                    .iter()
+                    .any(|request| request.sequence == sequence && request.fd_reply);
+                if fd_reply {
+                    // The file descriptors arrive with the bytes of the reply.
+                    let count = usize::from(message[1]).min(self.received_fds.len());
+                    let fds = self.received_fds.drain(..count).collect();
+                    self.reply_fds.insert(sequence, fds);
+                }
+                // Nobody waits on the replies of unchecked requests. Kept, they
+                // would be handed to the cookie that reuses their sequence number.
+                let checked = self
+                    .in_flight
+                    .iter()
+                    .any(|request| request.sequence == sequence && request.checked);
+                if !checked || self.abandoned.contains(&sequence) {
+                    self.reply_fds.remove(&sequence);
+                    return Ok(());
+                }
+                self.responses
+                    .entry(sequence)
+                    .or_default()
+                    .push_back(Ok(message));
+            }
+            _ => {
+                let event = Event::from_bytes(&message).map_err(|err| err.in_message(&message))?;
+                // Cached mappings are refreshed lazily on their next use.
+                if let Event::MappingNotify(ref mapping_notify) = event {
+                    self.keyboard.invalidate(mapping_notify);
+                }
+                // Pings to answer are not queued, nobody else needs them.
+                if let Some((atoms, ewmh_atoms)) = &self.auto_pong {
+                    if let Some(icccm::ProtocolMessage::Ping { window, timestamp }) =
+                        icccm::ProtocolMessage::from_event(&event, atoms, ewmh_atoms)
+                    {
+                        self.pings.push_back((window, timestamp));
+                        return Ok(());
+                    }
+                }
+                self.queue_event(event);
+            }
+        }
+        Ok(())
+    }
+
+    /// Logs a request that was just sent.
+    #[cfg(feature = "trace")]
+    fn trace_request(&self, request: &InFlightRequest, bytes: &[u8]) {
+        if self.trace == Trace::Off || !log::log_enabled!(target: trace::TARGET, log::Level::Debug)
+        {
+            return;
+        }
+        self.trace_log(
+            format_args!(
+                "> seq {} {}, {} bytes",
+                request.sequence,
+                self.describe_request(request),
+                bytes.len()
+            ),
+            bytes,
+        );
+    }
+
+    /// Logs a message read from the server, before it is dispatched.
+    #[cfg(feature = "trace")]
+    fn trace_message(&self, message: &[u8]) {
+        if self.trace == Trace::Off || !log::log_enabled!(target: trace::TARGET, log::Level::Debug)
+        {
+            return;
+        }
+        match message[0] {
+            0 => match self.decode_error(message) {
+                Ok(error) => self.trace_log(format_args!("< error {}", error), message),
+                Err(err) => self.trace_log(format_args!("< error: {}", err), message),
+            },
+            1 => {
+                let Ok((sequence, _)) = deserialize_into::<u16>(&message[2..4]) else {
+                    return;
+                };
+                let name = self
+                    .in_flight
+                    .iter()
+                    .find(|request| request.sequence == sequence)
+                    .and_then(|request| {
+                        self.request_name(request.major_opcode, request.minor_opcode)
+                    })
+                    .unwrap_or_else(|| String::from("unknown request"));
+                self.trace_log(
+                    format_args!(
+                        "< seq {} reply to {}, {} bytes",
+                        sequence,
+                        name,
+                        message.len()
+                    ),
+                    message,
+                );
+            }
+            _ => match Event::from_bytes(message) {
+                Ok(event) => self.trace_log(format_args!("< {:?}", event), message),
+                Err(err) => self.trace_log(format_args!("< event: {}", err), message),
+            },
+        }
+    }
+
+    #[cfg(feature = "trace")]
+    fn trace_log(&self, summary: std::fmt::Arguments<'_>, bytes: &[u8]) {
+        match self.trace {
+            Trace::Off => {}
+            Trace::Summary => log::debug!(target: trace::TARGET, "{}", summary),
+            Trace::Hexdump => log::debug!(
+                target: trace::TARGET,
+                "{}\n{}",
+                summary,
+                trace::hexdump(bytes).trim_end()
+            ),
+        }
+    }
+}
+
+/// Returns whether an event was sent by the server, rather than queued by the
+/// connection itself. Only those are dropped when the queue is full.
+fn is_from_server(event: &Event) -> bool {
+    !matches!(
+        event,
+        Event::ConnectionClosed | Event::Wakeup | Event::EventsDropped(_)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keyboard::PointerMapping;
+    use crate::testing::{setup_bytes, MockServer};
+
+    fn state() -> ProtocolState {
+        let setup = setup_bytes(&MockServer::builder().screen(1920, 1080));
+        ProtocolState::new(ConnSetup::parse_into(&setup).unwrap(), 0)
+    }
+
+    /// Returns the bytes of an error answering the request with `sequence`.
+    fn error_message(code: u8, sequence: u16, major_opcode: u8, minor_opcode: u16) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        bytes[1] = code;
+        bytes[2..4].copy_from_slice(&sequence.to_ne_bytes());
+        bytes[8..10].copy_from_slice(&minor_opcode.to_ne_bytes());
+        bytes[10] = major_opcode;
+        bytes
+    }
+
+    #[test]
+    fn empty_request_is_numbered() {
+        let mut state = state();
+        assert_eq!(state.request_sent(&[], false), 1);
+        assert_eq!(state.request_sent(&[], true), 2);
+    }
+
+    #[test]
+    fn extensions_are_kept() {
+        let mut state = state();
+        assert_eq!(state.extension("RANDR"), None);
+        let mut reply = QueryExtensionReply {
+            present: true,
+            major_opcode: 140,
+            first_event: 89,
+            first_error: 147,
+        };
+        let info = state.extension_queried("RANDR", &reply).unwrap();
+        assert_eq!(state.extension("RANDR"), Some(Some(info)));
+        reply.present = false;
+        assert_eq!(state.extension_queried("XVideo", &reply), None);
+        assert_eq!(state.extension("XVideo"), Some(None));
+
+        // The names registered first stay.
+        state.register_extension("RANDR", info, &["QueryVersion"], &["BadOutput"]);
+        state.register_extension("RANDR", info, &[], &[]);
+        assert_eq!(
+            state.request_name(140, 0).as_deref(),
+            Some("RANDR:QueryVersion")
+        );
+        assert_eq!(state.error_name(147).as_deref(), Some("RANDR:BadOutput"));
+    }
+
+    #[test]
+    fn extension_errors_are_named() {
+        let mut state = state();
+        let info = ExtensionInfo {
+            major_opcode: 140,
+            first_event: 89,
+            first_error: 147,
+        };
+        let requests = &["QueryVersion", "", "SetScreenConfig"];
+        state.register_extension(
+            "RANDR",
+            info,
+            requests,
+            &["BadOutput", "BadCrtc", "BadMode"],
+        );
+        let sequence = state.request_sent(&[140, 2, 1, 0], true);
+        assert!(state
+            .bytes_received(&error_message(148, sequence, 140, 2))
+            .unwrap());
+        let Some(Err(ConnectionError::ProtocolError(error))) = state.take_response(sequence) else {
+            panic!("the error is not the response");
+        };
+        assert_eq!(
+            error.to_string(),
+            "RANDR:BadCrtc for RANDR:SetScreenConfig (seq 1)"
+        );
+
+        // Unused minor opcodes are numbered.
+        let sequence = state.request_sent(&[140, 1, 1, 0], true);
+        state
+            .bytes_received(&error_message(8, sequence, 140, 1))
+            .unwrap();
+        let Some(Err(ConnectionError::ProtocolError(error))) = state.take_response(sequence) else {
+            panic!("the error is not the response");
+        };
+        assert_eq!(error.request_name.as_deref(), Some("RANDR:1"));
+    }
+
+    #[test]
+    fn messages_split_across_reads() {
+        let mut state = state();
+        // 100 MapNotify events and half of another.
+        let mut bytes = Vec::new();
+        for window in 0..101u32 {
+            let mut event = [0u8; 32];
+            event[0] = event::code::MAP_NOTIFY;
+            event[8..12].copy_from_slice(&window.to_ne_bytes());
+            bytes.extend_from_slice(&event);
+        }
+        assert!(state.bytes_received(&bytes[..101 * 32 - 16]).unwrap());
+        assert_eq!(state.queued_events(), 100);
+        assert!(!state
+            .bytes_received(&bytes[101 * 32 - 16..101 * 32 - 1])
+            .unwrap());
+        assert!(state.bytes_received(&bytes[101 * 32 - 1..]).unwrap());
+        let windows: Vec<_> = std::iter::from_fn(|| state.take_event())
+            .map(|event| match event {
+                Event::MapNotify(e) => e.window.0,
+                event => panic!("expected MapNotify, got {:?}", event),
+            })
+            .collect();
+        assert_eq!(windows, (0..101).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn mapping_notify_drops_the_cached_mapping() {
+        let mut state = state();
+        state.keyboard_mut().pointer_mapping = Some(PointerMapping { map: vec![1, 2, 3] });
+        let mut event = [0u8; 32];
+        event[0] = event::code::MAPPING_NOTIFY;
+        // Pointer.
+        event[4] = 2;
+        state.bytes_received(&event).unwrap();
+        assert!(state.keyboard_mut().pointer_mapping.is_none());
+        assert!(matches!(state.take_event(), Some(Event::MappingNotify(_))));
+    }
+
+    #[test]
+    fn oversized_message_disconnects() {
+        let mut state = state();
+        let sequence = state.request_sent(&[43, 0, 1, 0], true);
+        let mut bytes = error_message(2, sequence, 43, 0).to_vec();
+        // A reply whose length is the largest there is.
+        let mut reply = [0u8; 32];
+        reply[0] = 1;
+        reply[4..8].copy_from_slice(&u32::MAX.to_ne_bytes());
+        bytes.extend_from_slice(&reply);
+        assert!(state.bytes_received(&bytes).is_err());
+        assert!(state.is_disconnected());
+        assert!(matches!(state.take_response(sequence), Some(Err(_))));
+    }
+
+    #[test]
+    fn unchecked_requests_are_not_kept() {
+        let mut state = state();
+        let mut sequence = 0;
+        // More than the sequence numbers, which wrap around.
+        for _ in 0..100_000 {
+            sequence = state.request_sent(&[8, 0, 2, 0, 1, 0, 0x40, 0], false);
+        }
+        assert!(state.in_flight.is_empty());
+        // Their errors are still named after the opcodes they carry.
+        state.begin_sync();
+        state
+            .bytes_received(&error_message(3, sequence, 8, 0))
+            .unwrap();
+        let errors = state.end_sync();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].request_name.as_deref(), Some("MapWindow"));
+
+        let checked = state.request_sent(&[43, 0, 1, 0], true);
+        assert_eq!(state.in_flight.len(), 1);
+        let mut reply = [0u8; 32];
+        reply[0] = 1;
+        reply[2..4].copy_from_slice(&checked.to_ne_bytes());
+        state.bytes_received(&reply).unwrap();
+        assert!(matches!(state.take_response(checked), Some(Ok(_))));
+    }
+
+    #[test]
+    fn full_event_queue_drops_the_oldest_events() {
+        let mut state = state();
+        state.set_event_queue_limit(3);
+        for window in 1..=5u32 {
+            // MapNotify of `window`.
+            let mut event = [0u8; 32];
+            event[0] = event::code::MAP_NOTIFY;
+            event[8..12].copy_from_slice(&window.to_ne_bytes());
+            state.bytes_received(&event).unwrap();
+        }
+        assert_eq!(state.dropped_events(), 2);
+        state.set_event_queue_limit(2);
+        assert_eq!(state.dropped_events(), 3);
+        assert!(matches!(state.take_event(), Some(Event::EventsDropped(3))));
+        let mut windows = Vec::new();
+        while let Some(Event::MapNotify(event)) = state.take_event() {
+            windows.push(event.window.0);
+        }
+        assert_eq!(windows, [4, 5]);
+    }
+
+    #[test]
+    fn full_event_queue_keeps_the_events_of_the_connection() {
+        let mut state = state();
+        state.set_event_queue_limit(1);
+        let mut event = [0u8; 32];
+        event[0] = event::code::MAP_NOTIFY;
+        state.bytes_received(&event).unwrap();
+        state.queue_wakeup();
+        state.connection_lost(io::ErrorKind::UnexpectedEof.into());
+        state.queue_event(Event::KeymapNotify(event::KeymapNotifyEvent {
+            keys: [0; 31],
+        }));
+        assert_eq!(state.dropped_events(), 1);
+        assert!(matches!(state.take_event(), Some(Event::EventsDropped(1))));
+        assert!(matches!(state.take_event(), Some(Event::Wakeup)));
+        assert!(matches!(state.take_event(), Some(Event::ConnectionClosed)));
+        assert!(matches!(state.take_event(), Some(Event::KeymapNotify(_))));
+        assert!(state.take_event().is_none());
+    }
+}
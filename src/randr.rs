@@ -0,0 +1,1871 @@
+//! The RandR extension: the outputs, CRTCs and modes making up a screen.
+//!
+//! An output is a connector a monitor can be plugged into. A CRTC scans out a
+//! rectangle of the screen in a mode to the outputs attached to it, so the CRTCs
+//! tell which part of the root window each monitor shows.
+//!
+//! Call [Connection::randr_query_version] before any other request.
+
+use crate::connection::Connection;
+use crate::cookie::{Cookie, Reply};
+use crate::errors::{ConnectionError, ParseError, SerializeError};
+use crate::event::Event;
+use crate::extension::{Extension, Version};
+use crate::protocol::{Atom, Timestamp, Window, XString};
+use crate::request::RequestBuilder;
+use crate::utils::{
+    check_trailing_bytes, deserialize_into, deserialize_into_string, deserialize_into_vec, padding,
+    skip,
+};
+
+/// Name of the extension, as given to QueryExtension.
+pub const NAME: &str = "RANDR";
+
+/// The newest version of the extension this module implements.
+pub const VERSION: (u32, u32) = (1, 6);
+
+/// Minor opcodes of the RandR requests.
+pub mod opcode {
+    pub const QUERY_VERSION: u8 = 0;
+    pub const SELECT_INPUT: u8 = 4;
+    pub const GET_SCREEN_SIZE_RANGE: u8 = 6;
+    pub const SET_SCREEN_SIZE: u8 = 7;
+    pub const GET_SCREEN_RESOURCES: u8 = 8;
+    pub const GET_OUTPUT_INFO: u8 = 9;
+    pub const GET_OUTPUT_PROPERTY: u8 = 15;
+    pub const GET_CRTC_INFO: u8 = 20;
+    pub const SET_CRTC_CONFIG: u8 = 21;
+    pub const GET_CRTC_GAMMA_SIZE: u8 = 22;
+    pub const GET_CRTC_GAMMA: u8 = 23;
+    pub const SET_CRTC_GAMMA: u8 = 24;
+    pub const GET_SCREEN_RESOURCES_CURRENT: u8 = 25;
+    pub const GET_OUTPUT_PRIMARY: u8 = 31;
+    pub const GET_MONITORS: u8 = 42;
+}
+
+/// Names of the RandR requests, indexed by minor opcode.
+pub const REQUEST_NAMES: &[&str] = &[
+    "QueryVersion",
+    "OldGetScreenInfo",
+    "SetScreenConfig",
+    "OldScreenChangeSelectInput",
+    "SelectInput",
+    "GetScreenInfo",
+    "GetScreenSizeRange",
+    "SetScreenSize",
+    "GetScreenResources",
+    "GetOutputInfo",
+    "ListOutputProperties",
+    "QueryOutputProperty",
+    "ConfigureOutputProperty",
+    "ChangeOutputProperty",
+    "DeleteOutputProperty",
+    "GetOutputProperty",
+    "CreateMode",
+    "DestroyMode",
+    "AddOutputMode",
+    "DeleteOutputMode",
+    "GetCrtcInfo",
+    "SetCrtcConfig",
+    "GetCrtcGammaSize",
+    "GetCrtcGamma",
+    "SetCrtcGamma",
+    "GetScreenResourcesCurrent",
+    "SetCrtcTransform",
+    "GetCrtcTransform",
+    "GetPanning",
+    "SetPanning",
+    "SetOutputPrimary",
+    "GetOutputPrimary",
+    "GetProviders",
+    "GetProviderInfo",
+    "SetProviderOffloadSink",
+    "SetProviderOutputSource",
+    "ListProviderProperties",
+    "QueryProviderProperty",
+    "ConfigureProviderProperty",
+    "ChangeProviderProperty",
+    "DeleteProviderProperty",
+    "GetProviderProperty",
+    "GetMonitors",
+    "SetMonitor",
+    "DeleteMonitor",
+    "CreateLease",
+    "FreeLease",
+];
+
+/// Names of the RandR errors, indexed from the first error.
+pub const ERROR_NAMES: &[&str] = &["BadOutput", "BadCrtc", "BadMode", "BadProvider"];
+
+/// Event codes of the RandR events, relative to the first event of the extension.
+pub mod event_code {
+    pub const SCREEN_CHANGE_NOTIFY: u8 = 0;
+    pub const NOTIFY: u8 = 1;
+}
+
+/// Subcodes of the RRNotify events.
+pub mod notify_subcode {
+    pub const CRTC_CHANGE: u8 = 0;
+    pub const OUTPUT_CHANGE: u8 = 1;
+    pub const OUTPUT_PROPERTY: u8 = 2;
+}
+
+/// The events selected by [Connection::randr_select_input].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct NotifyMask(pub u16);
+
+impl NotifyMask {
+    pub const SCREEN_CHANGE: Self = Self(1);
+    pub const CRTC_CHANGE: Self = Self(2);
+    pub const OUTPUT_CHANGE: Self = Self(4);
+    pub const OUTPUT_PROPERTY: Self = Self(8);
+    pub const PROVIDER_CHANGE: Self = Self(16);
+    pub const PROVIDER_PROPERTY: Self = Self(32);
+    pub const RESOURCE_CHANGE: Self = Self(64);
+}
+
+impl std::ops::BitOr for NotifyMask {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+/// An output, the connector a monitor is plugged into.
+pub type Output = u32;
+
+/// A CRTC, which scans out a part of the screen to its outputs.
+pub type Crtc = u32;
+
+/// A display mode: a resolution and its timings.
+pub type Mode = u32;
+
+/// The rotation and reflection of a CRTC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Rotation(pub u16);
+
+impl Rotation {
+    pub const ROTATE_0: Self = Self(1);
+    pub const ROTATE_90: Self = Self(2);
+    pub const ROTATE_180: Self = Self(4);
+    pub const ROTATE_270: Self = Self(8);
+    pub const REFLECT_X: Self = Self(16);
+    pub const REFLECT_Y: Self = Self(32);
+
+    /// Whether all the bits of `other` are set.
+    pub fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl std::ops::BitOr for Rotation {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+/// Whether a monitor is plugged into an output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutputConnection(pub u8);
+
+impl OutputConnection {
+    pub const CONNECTED: Self = Self(0);
+    pub const DISCONNECTED: Self = Self(1);
+    pub const UNKNOWN: Self = Self(2);
+}
+
+/// The outcome of a request changing the configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SetConfigStatus(pub u8);
+
+impl SetConfigStatus {
+    pub const SUCCESS: Self = Self(0);
+    /// The config timestamp is older than the last hardware probe: fetch the
+    /// screen resources again.
+    pub const INVALID_CONFIG_TIME: Self = Self(1);
+    /// The timestamp is older than the last configuration change.
+    pub const INVALID_TIME: Self = Self(2);
+    pub const FAILED: Self = Self(3);
+}
+
+/// Starts a request of the extension.
+fn request(conn: &mut Connection, minor_opcode: u8) -> Result<RequestBuilder, ConnectionError> {
+    let info = conn.require_extension(NAME, REQUEST_NAMES, ERROR_NAMES)?;
+    Ok(RequestBuilder::new(info.major_opcode, minor_opcode))
+}
+
+/// The RANDR extension, for [Connection::enable].
+pub struct Randr;
+
+impl Extension for Randr {
+    const NAME: &'static str = NAME;
+    const MIN_VERSION: Version = (1, 2);
+
+    fn negotiate(conn: &mut Connection) -> Result<Option<Version>, ConnectionError> {
+        let reply = conn
+            .randr_query_version(VERSION.0, VERSION.1)?
+            .reply(conn)?;
+        Ok(Some((reply.major_version, reply.minor_version)))
+    }
+}
+
+/// Reply of the RRQueryVersion request.
+#[derive(Debug, Clone)]
+pub struct QueryVersionReply {
+    pub major_version: u32,
+    pub minor_version: u32,
+}
+
+impl Reply for QueryVersionReply {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1                        unused
+        // 2  CARD16                sequence number
+        // 4  0                     reply length
+        // 4  CARD32                major-version
+        // 4  CARD32                minor-version
+        // 16                       unused
+        let rest = skip(bytes, 8)?;
+        let (major_version, rest) = deserialize_into::<u32>(rest)?;
+        let (minor_version, _) = deserialize_into::<u32>(rest)?;
+        Ok(QueryVersionReply {
+            major_version,
+            minor_version,
+        })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        check_trailing_bytes("randr::QueryVersionReply", bytes, 32)
+    }
+}
+
+/// A display mode with its timings.
+#[derive(Debug, Clone, Default)]
+pub struct ModeInfo {
+    pub id: Mode,
+    pub width: u16,
+    pub height: u16,
+    /// The pixel clock, in Hz.
+    pub dot_clock: u32,
+    pub hsync_start: u16,
+    pub hsync_end: u16,
+    pub htotal: u16,
+    pub hskew: u16,
+    pub vsync_start: u16,
+    pub vsync_end: u16,
+    pub vtotal: u16,
+    pub mode_flags: u32,
+    pub name: XString,
+}
+
+impl ModeInfo {
+    const INTERLACE: u32 = 0x10;
+    const DOUBLE_SCAN: u32 = 0x20;
+
+    /// Returns the refresh rate of the mode, in Hz, or 0 if its timings are unset.
+    pub fn refresh_rate(&self) -> f64 {
+        let mut vtotal = f64::from(self.vtotal);
+        if self.mode_flags & Self::DOUBLE_SCAN != 0 {
+            vtotal *= 2.0;
+        }
+        if self.mode_flags & Self::INTERLACE != 0 {
+            vtotal /= 2.0;
+        }
+        let total = f64::from(self.htotal) * vtotal;
+        if total == 0.0 {
+            return 0.0;
+        }
+        f64::from(self.dot_clock) / total
+    }
+
+    /// Parses a MODEINFO, without its name, and returns the length of the name.
+    fn parse(bytes: &[u8]) -> Result<(ModeInfo, u16, &[u8]), ParseError> {
+        // 4  CARD32                id
+        // 2  CARD16                width
+        // 2  CARD16                height
+        // 4  CARD32                dot clock
+        // 2  CARD16                h sync start
+        // 2  CARD16                h sync end
+        // 2  CARD16                h total
+        // 2  CARD16                h skew
+        // 2  CARD16                v sync start
+        // 2  CARD16                v sync end
+        // 2  CARD16                v total
+        // 2  CARD16                name length
+        // 4  SETofMODEFLAG         mode flags
+        let (id, rest) = deserialize_into::<u32>(bytes)?;
+        let (width, rest) = deserialize_into::<u16>(rest)?;
+        let (height, rest) = deserialize_into::<u16>(rest)?;
+        let (dot_clock, rest) = deserialize_into::<u32>(rest)?;
+        let (hsync_start, rest) = deserialize_into::<u16>(rest)?;
+        let (hsync_end, rest) = deserialize_into::<u16>(rest)?;
+        let (htotal, rest) = deserialize_into::<u16>(rest)?;
+        let (hskew, rest) = deserialize_into::<u16>(rest)?;
+        let (vsync_start, rest) = deserialize_into::<u16>(rest)?;
+        let (vsync_end, rest) = deserialize_into::<u16>(rest)?;
+        let (vtotal, rest) = deserialize_into::<u16>(rest)?;
+        let (name_length, rest) = deserialize_into::<u16>(rest)?;
+        let (mode_flags, rest) = deserialize_into::<u32>(rest)?;
+        let mode = ModeInfo {
+            id,
+            width,
+            height,
+            dot_clock,
+            hsync_start,
+            hsync_end,
+            htotal,
+            hskew,
+            vsync_start,
+            vsync_end,
+            vtotal,
+            mode_flags,
+            name: XString::default(),
+        };
+        Ok((mode, name_length, rest))
+    }
+}
+
+/// Reply of the GetScreenResources and GetScreenResourcesCurrent requests.
+#[derive(Debug, Clone)]
+pub struct ScreenResources {
+    /// When the configuration was last changed.
+    pub timestamp: Timestamp,
+    /// When the hardware was last probed. Requests changing the configuration
+    /// must pass it.
+    pub config_timestamp: Timestamp,
+    pub crtcs: Vec<Crtc>,
+    pub outputs: Vec<Output>,
+    pub modes: Vec<ModeInfo>,
+}
+
+impl ScreenResources {
+    /// Returns the mode with the given id.
+    pub fn mode(&self, id: Mode) -> Option<&ModeInfo> {
+        self.modes.iter().find(|mode| mode.id == id)
+    }
+}
+
+impl Reply for ScreenResources {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1                        unused
+        // 2  CARD16                sequence number
+        // 4  CARD32                reply length
+        // 4  TIMESTAMP             timestamp
+        // 4  TIMESTAMP             config-timestamp
+        // 2  CARD16                number of CRTCs (c)
+        // 2  CARD16                number of outputs (o)
+        // 2  CARD16                number of modes (m)
+        // 2  CARD16                length of mode names (b)
+        // 8                        unused
+        // 4c LISTofCRTC            crtcs
+        // 4o LISTofOUTPUT          outputs
+        // 32m LISTofMODEINFO       modeinfos
+        // b  STRING8               mode names
+        // p                        unused (p = pad(b))
+        let rest = skip(bytes, 8)?;
+        let (timestamp, rest) = deserialize_into::<Timestamp>(rest)?;
+        let (config_timestamp, rest) = deserialize_into::<Timestamp>(rest)?;
+        let (number_of_crtcs, rest) = deserialize_into::<u16>(rest)?;
+        let (number_of_outputs, rest) = deserialize_into::<u16>(rest)?;
+        let (number_of_modes, rest) = deserialize_into::<u16>(rest)?;
+        let (_, rest) = deserialize_into::<u16>(rest)?;
+        let rest = skip(rest, 8)?;
+        let (crtcs, rest) = deserialize_into_vec::<Crtc>(rest, number_of_crtcs.into())?;
+        let (outputs, mut rest) = deserialize_into_vec::<Output>(rest, number_of_outputs.into())?;
+        let mut modes = Vec::with_capacity(number_of_modes.into());
+        let mut name_lengths = Vec::with_capacity(number_of_modes.into());
+        for _ in 0..number_of_modes {
+            let (mode, name_length, remaining) = ModeInfo::parse(rest)?;
+            modes.push(mode);
+            name_lengths.push(name_length);
+            rest = remaining;
+        }
+        // The names of all modes follow each other without padding.
+        for (mode, name_length) in modes.iter_mut().zip(name_lengths) {
+            let (name, remaining) = deserialize_into_string(rest, name_length)?;
+            mode.name = name;
+            rest = remaining;
+        }
+        Ok(ScreenResources {
+            timestamp,
+            config_timestamp,
+            crtcs,
+            outputs,
+            modes,
+        })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        let names: usize = self.modes.iter().map(|mode| mode.name.len()).sum();
+        let length = 32
+            + 4 * self.crtcs.len()
+            + 4 * self.outputs.len()
+            + 32 * self.modes.len()
+            + names
+            + padding(names, 4);
+        check_trailing_bytes("randr::ScreenResources", bytes, length)
+    }
+}
+
+/// Reply of the GetOutputInfo request.
+#[derive(Debug, Clone)]
+pub struct OutputInfo {
+    /// The status of the request: Success (0) or InvalidConfigTime (1).
+    pub status: u8,
+    pub timestamp: Timestamp,
+    /// The CRTC driving the output, 0 if the output is disabled.
+    pub crtc: Crtc,
+    /// The physical size of the monitor, in millimeters.
+    pub mm_width: u32,
+    pub mm_height: u32,
+    pub connection: OutputConnection,
+    pub subpixel_order: u8,
+    /// The CRTCs able to drive the output.
+    pub crtcs: Vec<Crtc>,
+    /// The modes the monitor supports, the preferred ones first.
+    pub modes: Vec<Mode>,
+    pub number_of_preferred_modes: u16,
+    /// The outputs which can share a CRTC with this one.
+    pub clones: Vec<Output>,
+    /// The name of the connector, e.g. "HDMI-1".
+    pub name: XString,
+}
+
+impl OutputInfo {
+    /// Returns the modes the monitor prefers.
+    pub fn preferred_modes(&self) -> &[Mode] {
+        let count = usize::from(self.number_of_preferred_modes).min(self.modes.len());
+        &self.modes[..count]
+    }
+}
+
+impl Reply for OutputInfo {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1  RRCONFIGSTATUS        status
+        // 2  CARD16                sequence number
+        // 4  CARD32                reply length
+        // 4  TIMESTAMP             timestamp
+        // 4  CRTC                  current connected crtc
+        // 4  CARD32                width in millimeters
+        // 4  CARD32                height in millimeters
+        // 1  CONNECTION            connection
+        // 1  SUBPIXELORDER         subpixel-order
+        // 2  CARD16                number of CRTCs (c)
+        // 2  CARD16                number of modes (m)
+        // 2  CARD16                number of preferred modes
+        // 2  CARD16                number of clones (n)
+        // 2  CARD16                length of name (b)
+        // 4c LISTofCRTC            crtcs
+        // 4m LISTofMODE            modes
+        // 4n LISTofOUTPUT          clones
+        // b  STRING8               name
+        // p                        unused (p = pad(b))
+        let rest = skip(bytes, 1)?;
+        let (status, rest) = deserialize_into::<u8>(rest)?;
+        let rest = skip(rest, 6)?;
+        let (timestamp, rest) = deserialize_into::<Timestamp>(rest)?;
+        let (crtc, rest) = deserialize_into::<Crtc>(rest)?;
+        let (mm_width, rest) = deserialize_into::<u32>(rest)?;
+        let (mm_height, rest) = deserialize_into::<u32>(rest)?;
+        let (connection, rest) = deserialize_into::<u8>(rest)?;
+        let (subpixel_order, rest) = deserialize_into::<u8>(rest)?;
+        let (number_of_crtcs, rest) = deserialize_into::<u16>(rest)?;
+        let (number_of_modes, rest) = deserialize_into::<u16>(rest)?;
+        let (number_of_preferred_modes, rest) = deserialize_into::<u16>(rest)?;
+        let (number_of_clones, rest) = deserialize_into::<u16>(rest)?;
+        let (name_length, rest) = deserialize_into::<u16>(rest)?;
+        let (crtcs, rest) = deserialize_into_vec::<Crtc>(rest, number_of_crtcs.into())?;
+        let (modes, rest) = deserialize_into_vec::<Mode>(rest, number_of_modes.into())?;
+        let (clones, rest) = deserialize_into_vec::<Output>(rest, number_of_clones.into())?;
+        let (name, _) = deserialize_into_string(rest, name_length)?;
+        Ok(OutputInfo {
+            status,
+            timestamp,
+            crtc,
+            mm_width,
+            mm_height,
+            connection: OutputConnection(connection),
+            subpixel_order,
+            crtcs,
+            modes,
+            number_of_preferred_modes,
+            clones,
+            name,
+        })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        if self.connection.0 > 2 {
+            return Err(ParseError::InvalidValue {
+                type_name: "randr::OutputInfo",
+                field: "connection",
+                value: self.connection.0.into(),
+            });
+        }
+        let length = 36
+            + 4 * (self.crtcs.len() + self.modes.len() + self.clones.len())
+            + self.name.len()
+            + padding(self.name.len(), 4);
+        check_trailing_bytes("randr::OutputInfo", bytes, length)
+    }
+}
+
+/// Reply of the GetCrtcInfo request.
+#[derive(Debug, Clone)]
+pub struct CrtcInfo {
+    /// The status of the request: Success (0) or InvalidConfigTime (1).
+    pub status: u8,
+    pub timestamp: Timestamp,
+    /// The area of the screen the CRTC shows.
+    pub x: i16,
+    pub y: i16,
+    pub width: u16,
+    pub height: u16,
+    /// The current mode, 0 if the CRTC is disabled.
+    pub mode: Mode,
+    pub rotation: Rotation,
+    /// The rotations and reflections the CRTC supports.
+    pub rotations: Rotation,
+    /// The outputs the CRTC drives.
+    pub outputs: Vec<Output>,
+    /// The outputs the CRTC could drive.
+    pub possible_outputs: Vec<Output>,
+}
+
+impl CrtcInfo {
+    /// Whether the CRTC is enabled and shows the given point of the screen.
+    pub fn contains(&self, x: i16, y: i16) -> bool {
+        let (x, y) = (i32::from(x), i32::from(y));
+        let (left, top) = (i32::from(self.x), i32::from(self.y));
+        self.mode != 0
+            && x >= left
+            && y >= top
+            && x < left + i32::from(self.width)
+            && y < top + i32::from(self.height)
+    }
+}
+
+impl Reply for CrtcInfo {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1  RRCONFIGSTATUS        status
+        // 2  CARD16                sequence number
+        // 4  CARD32                reply length
+        // 4  TIMESTAMP             timestamp
+        // 2  INT16                 x
+        // 2  INT16                 y
+        // 2  CARD16                width
+        // 2  CARD16                height
+        // 4  MODE                  mode
+        // 2  ROTATION              current rotation and reflection
+        // 2  ROTATION              set of possible rotations
+        // 2  CARD16                number of outputs (o)
+        // 2  CARD16                number of possible outputs (p)
+        // 4o LISTofOUTPUT          outputs
+        // 4p LISTofOUTPUT          possible outputs
+        let rest = skip(bytes, 1)?;
+        let (status, rest) = deserialize_into::<u8>(rest)?;
+        let rest = skip(rest, 6)?;
+        let (timestamp, rest) = deserialize_into::<Timestamp>(rest)?;
+        let (x, rest) = deserialize_into::<i16>(rest)?;
+        let (y, rest) = deserialize_into::<i16>(rest)?;
+        let (width, rest) = deserialize_into::<u16>(rest)?;
+        let (height, rest) = deserialize_into::<u16>(rest)?;
+        let (mode, rest) = deserialize_into::<Mode>(rest)?;
+        let (rotation, rest) = deserialize_into::<u16>(rest)?;
+        let (rotations, rest) = deserialize_into::<u16>(rest)?;
+        let (number_of_outputs, rest) = deserialize_into::<u16>(rest)?;
+        let (number_of_possible_outputs, rest) = deserialize_into::<u16>(rest)?;
+        let (outputs, rest) = deserialize_into_vec::<Output>(rest, number_of_outputs.into())?;
+        let (possible_outputs, _) =
+            deserialize_into_vec::<Output>(rest, number_of_possible_outputs.into())?;
+        Ok(CrtcInfo {
+            status,
+            timestamp,
+            x,
+            y,
+            width,
+            height,
+            mode,
+            rotation: Rotation(rotation),
+            rotations: Rotation(rotations),
+            outputs,
+            possible_outputs,
+        })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        let length = 32 + 4 * (self.outputs.len() + self.possible_outputs.len());
+        check_trailing_bytes("randr::CrtcInfo", bytes, length)
+    }
+}
+
+/// Reply of the GetOutputProperty request.
+#[derive(Debug, Clone)]
+pub struct OutputProperty {
+    /// Whether `data` holds 8, 16 or 32 bit values, 0 if the property does not exist.
+    pub format: u8,
+    pub type_: Atom,
+    /// The number of bytes of the value after the returned part.
+    pub bytes_after: u32,
+    /// The raw data, `format / 8` bytes per item.
+    pub data: Vec<u8>,
+}
+
+impl OutputProperty {
+    /// Returns the items of the value, widened to 32 bits.
+    pub fn values(&self) -> Vec<u32> {
+        match self.format {
+            8 => self.data.iter().map(|&b| b.into()).collect(),
+            16 => self
+                .data
+                .chunks_exact(2)
+                .map(|c| u16::from_ne_bytes([c[0], c[1]]).into())
+                .collect(),
+            32 => self
+                .data
+                .chunks_exact(4)
+                .map(|c| u32::from_ne_bytes([c[0], c[1], c[2], c[3]]))
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl Reply for OutputProperty {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1  CARD8                 format
+        // 2  CARD16                sequence number
+        // 4  CARD32                reply length
+        // 4  ATOM                  type
+        // 4  CARD32                bytes-after
+        // 4  CARD32                length of value in format units (n)
+        // 12                       unused
+        // n*format/8 LISTofBYTE    data
+        // p                        unused
+        let rest = skip(bytes, 1)?;
+        let (format, rest) = deserialize_into::<u8>(rest)?;
+        let rest = skip(rest, 6)?;
+        let (type_, rest) = deserialize_into::<Atom>(rest)?;
+        let (bytes_after, rest) = deserialize_into::<u32>(rest)?;
+        let (items, rest) = deserialize_into::<u32>(rest)?;
+        let rest = skip(rest, 12)?;
+        let size = (items as usize)
+            .checked_mul(usize::from(format / 8))
+            .ok_or(ParseError::OverFlow {
+                type_name: "randr::OutputProperty",
+            })?;
+        let (data, _) = deserialize_into_vec::<u8>(rest, size)?;
+        Ok(OutputProperty {
+            format,
+            type_,
+            bytes_after,
+            data,
+        })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        if !matches!(self.format, 0 | 8 | 16 | 32) {
+            return Err(ParseError::InvalidValue {
+                type_name: "randr::OutputProperty",
+                field: "format",
+                value: self.format.into(),
+            });
+        }
+        let length = 32 + self.data.len() + padding(self.data.len(), 4);
+        check_trailing_bytes("randr::OutputProperty", bytes, length)
+    }
+}
+
+/// Reply of the GetScreenSizeRange request.
+#[derive(Debug, Clone)]
+pub struct ScreenSizeRange {
+    pub min_width: u16,
+    pub min_height: u16,
+    pub max_width: u16,
+    pub max_height: u16,
+}
+
+impl Reply for ScreenSizeRange {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1                        unused
+        // 2  CARD16                sequence number
+        // 4  0                     reply length
+        // 2  CARD16                min-width
+        // 2  CARD16                min-height
+        // 2  CARD16                max-width
+        // 2  CARD16                max-height
+        // 16                       unused
+        let rest = skip(bytes, 8)?;
+        let (min_width, rest) = deserialize_into::<u16>(rest)?;
+        let (min_height, rest) = deserialize_into::<u16>(rest)?;
+        let (max_width, rest) = deserialize_into::<u16>(rest)?;
+        let (max_height, _) = deserialize_into::<u16>(rest)?;
+        Ok(ScreenSizeRange {
+            min_width,
+            min_height,
+            max_width,
+            max_height,
+        })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        check_trailing_bytes("randr::ScreenSizeRange", bytes, 32)
+    }
+}
+
+/// Reply of the SetCrtcConfig request.
+#[derive(Debug, Clone)]
+pub struct SetCrtcConfigReply {
+    pub status: SetConfigStatus,
+    /// When the configuration was last changed.
+    pub timestamp: Timestamp,
+}
+
+impl Reply for SetCrtcConfigReply {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1  RRCONFIGSTATUS        status
+        // 2  CARD16                sequence number
+        // 4  0                     reply length
+        // 4  TIMESTAMP             new timestamp
+        // 20                       unused
+        let rest = skip(bytes, 1)?;
+        let (status, rest) = deserialize_into::<u8>(rest)?;
+        let rest = skip(rest, 6)?;
+        let (timestamp, _) = deserialize_into::<Timestamp>(rest)?;
+        Ok(SetCrtcConfigReply {
+            status: SetConfigStatus(status),
+            timestamp,
+        })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        if self.status.0 > 3 {
+            return Err(ParseError::InvalidValue {
+                type_name: "randr::SetCrtcConfigReply",
+                field: "status",
+                value: self.status.0.into(),
+            });
+        }
+        check_trailing_bytes("randr::SetCrtcConfigReply", bytes, 32)
+    }
+}
+
+/// The new configuration of a CRTC, see [Connection::randr_set_crtc_config].
+#[derive(Debug, Clone, Default)]
+pub struct CrtcConfig {
+    /// The position of the CRTC on the screen.
+    pub x: i16,
+    pub y: i16,
+    /// The mode to set, 0 to disable the CRTC.
+    pub mode: Mode,
+    pub rotation: Rotation,
+    /// The outputs the CRTC drives. Must be empty if `mode` is 0.
+    pub outputs: Vec<Output>,
+}
+
+/// Reply of the GetCrtcGammaSize request.
+#[derive(Debug, Clone)]
+pub struct GammaSizeReply {
+    /// The number of entries of each ramp of the gamma table.
+    pub size: u16,
+}
+
+impl Reply for GammaSizeReply {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1                        unused
+        // 2  CARD16                sequence number
+        // 4  0                     reply length
+        // 2  CARD16                size
+        // 22                       unused
+        let rest = skip(bytes, 8)?;
+        let (size, _) = deserialize_into::<u16>(rest)?;
+        Ok(GammaSizeReply { size })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        check_trailing_bytes("randr::GammaSizeReply", bytes, 32)
+    }
+}
+
+/// The gamma table of a CRTC: one ramp per color channel, all of the same size.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Gamma {
+    pub red: Vec<u16>,
+    pub green: Vec<u16>,
+    pub blue: Vec<u16>,
+}
+
+impl Gamma {
+    /// Returns a table of `size` entries applying the given gamma correction to
+    /// each channel, like `xrandr --gamma` does. 1.0 is the identity.
+    pub fn from_exponents(size: u16, red: f64, green: f64, blue: f64) -> Gamma {
+        let ramp = |exponent: f64| -> Vec<u16> {
+            let last = f64::from(size.saturating_sub(1).max(1));
+            (0..size)
+                .map(|i| ((f64::from(i) / last).powf(1.0 / exponent) * 65535.0) as u16)
+                .collect()
+        };
+        Gamma {
+            red: ramp(red),
+            green: ramp(green),
+            blue: ramp(blue),
+        }
+    }
+}
+
+impl Reply for Gamma {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1                        unused
+        // 2  CARD16                sequence number
+        // 4  (6n+p)/4              reply length
+        // 2  CARD16                size (n)
+        // 22                       unused
+        // 2n LISTofCARD16          red
+        // 2n LISTofCARD16          green
+        // 2n LISTofCARD16          blue
+        // p                        unused (p = pad(6n))
+        let rest = skip(bytes, 8)?;
+        let (size, rest) = deserialize_into::<u16>(rest)?;
+        let rest = skip(rest, 22)?;
+        let (red, rest) = deserialize_into_vec::<u16>(rest, size.into())?;
+        let (green, rest) = deserialize_into_vec::<u16>(rest, size.into())?;
+        let (blue, _) = deserialize_into_vec::<u16>(rest, size.into())?;
+        Ok(Gamma { red, green, blue })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        let ramps = 6 * self.red.len();
+        check_trailing_bytes("randr::Gamma", bytes, 32 + ramps + padding(ramps, 4))
+    }
+}
+
+/// Reply of the GetOutputPrimary request.
+#[derive(Debug, Clone)]
+pub struct OutputPrimaryReply {
+    /// The primary output, 0 if there is none.
+    pub output: Output,
+}
+
+impl Reply for OutputPrimaryReply {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1                        unused
+        // 2  CARD16                sequence number
+        // 4  0                     reply length
+        // 4  OUTPUT                output
+        // 20                       unused
+        let rest = skip(bytes, 8)?;
+        let (output, _) = deserialize_into::<Output>(rest)?;
+        Ok(OutputPrimaryReply { output })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        check_trailing_bytes("randr::OutputPrimaryReply", bytes, 32)
+    }
+}
+
+/// A monitor: a rectangle of the screen shown by one or more outputs.
+#[derive(Debug, Clone)]
+pub struct MonitorInfo {
+    /// The name, usually the name of the output, e.g. "HDMI-1".
+    pub name: Atom,
+    pub primary: bool,
+    /// Whether the server made the monitor for an output, rather than a client
+    /// with SetMonitor.
+    pub automatic: bool,
+    pub x: i16,
+    pub y: i16,
+    pub width: u16,
+    pub height: u16,
+    /// The physical size, in millimeters.
+    pub width_mm: u32,
+    pub height_mm: u32,
+    pub outputs: Vec<Output>,
+}
+
+/// Reply of the GetMonitors request.
+#[derive(Debug, Clone)]
+pub struct MonitorsReply {
+    pub timestamp: Timestamp,
+    pub monitors: Vec<MonitorInfo>,
+}
+
+impl Reply for MonitorsReply {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1                        unused
+        // 2  CARD16                sequence number
+        // 4  CARD32                reply length
+        // 4  TIMESTAMP             timestamp
+        // 4  CARD32                number of monitors (n)
+        // 4  CARD32                number of outputs (o)
+        // 12                       unused
+        // 24n+4o LISTofMONITORINFO monitors:
+        //   4  ATOM                name
+        //   1  BOOL                primary
+        //   1  BOOL                automatic
+        //   2  CARD16              number of outputs (m)
+        //   2  INT16               x
+        //   2  INT16               y
+        //   2  CARD16              width in pixels
+        //   2  CARD16              height in pixels
+        //   4  CARD32              width in millimeters
+        //   4  CARD32              height in millimeters
+        //   4m LISTofOUTPUT        outputs
+        let rest = skip(bytes, 8)?;
+        let (timestamp, rest) = deserialize_into::<Timestamp>(rest)?;
+        let (number_of_monitors, rest) = deserialize_into::<u32>(rest)?;
+        let mut rest = skip(rest, 16)?;
+        let mut monitors = Vec::new();
+        for _ in 0..number_of_monitors {
+            let (name, remaining) = deserialize_into::<Atom>(rest)?;
+            let (primary, remaining) = deserialize_into::<u8>(remaining)?;
+            let (automatic, remaining) = deserialize_into::<u8>(remaining)?;
+            let (number_of_outputs, remaining) = deserialize_into::<u16>(remaining)?;
+            let (x, remaining) = deserialize_into::<i16>(remaining)?;
+            let (y, remaining) = deserialize_into::<i16>(remaining)?;
+            let (width, remaining) = deserialize_into::<u16>(remaining)?;
+            let (height, remaining) = deserialize_into::<u16>(remaining)?;
+            let (width_mm, remaining) = deserialize_into::<u32>(remaining)?;
+            let (height_mm, remaining) = deserialize_into::<u32>(remaining)?;
+            let (outputs, remaining) =
+                deserialize_into_vec::<Output>(remaining, number_of_outputs.into())?;
+            monitors.push(MonitorInfo {
+                name,
+                primary: primary != 0,
+                automatic: automatic != 0,
+                x,
+                y,
+                width,
+                height,
+                width_mm,
+                height_mm,
+                outputs,
+            });
+            rest = remaining;
+        }
+        Ok(MonitorsReply {
+            timestamp,
+            monitors,
+        })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        let outputs: usize = self.monitors.iter().map(|m| m.outputs.len()).sum();
+        let length = 32 + 24 * self.monitors.len() + 4 * outputs;
+        check_trailing_bytes("randr::MonitorsReply", bytes, length)
+    }
+}
+
+/// The size or rotation of the screen changed.
+#[derive(Debug, Clone)]
+pub struct ScreenChangeNotifyEvent {
+    pub rotation: Rotation,
+    pub timestamp: Timestamp,
+    pub config_timestamp: Timestamp,
+    pub root: Window,
+    pub window: Window,
+    pub size_id: u16,
+    pub subpixel_order: u16,
+    /// The new size of the screen, in pixels and millimeters.
+    pub width: u16,
+    pub height: u16,
+    pub mm_width: u16,
+    pub mm_height: u16,
+}
+
+/// The configuration of a CRTC changed.
+#[derive(Debug, Clone)]
+pub struct CrtcChangeEvent {
+    pub timestamp: Timestamp,
+    pub window: Window,
+    pub crtc: Crtc,
+    /// The new mode, 0 if the CRTC was disabled.
+    pub mode: Mode,
+    pub rotation: Rotation,
+    pub x: i16,
+    pub y: i16,
+    pub width: u16,
+    pub height: u16,
+}
+
+/// An output changed, e.g. a monitor was plugged in or unplugged.
+#[derive(Debug, Clone)]
+pub struct OutputChangeEvent {
+    pub timestamp: Timestamp,
+    pub config_timestamp: Timestamp,
+    pub window: Window,
+    pub output: Output,
+    pub crtc: Crtc,
+    pub mode: Mode,
+    pub rotation: Rotation,
+    pub connection: OutputConnection,
+    pub subpixel_order: u8,
+}
+
+/// A property of an output changed.
+#[derive(Debug, Clone)]
+pub struct OutputPropertyEvent {
+    pub window: Window,
+    pub output: Output,
+    pub property: Atom,
+    pub timestamp: Timestamp,
+    /// Whether the property got a new value (0) or was deleted (1).
+    pub state: u8,
+}
+
+/// An event of the RandR extension.
+#[derive(Debug, Clone)]
+pub enum RandrEvent {
+    ScreenChangeNotify(ScreenChangeNotifyEvent),
+    CrtcChange(CrtcChangeEvent),
+    OutputChange(OutputChangeEvent),
+    OutputProperty(OutputPropertyEvent),
+}
+
+impl RandrEvent {
+    /// Parses the event if `event` is a RandR event. `first_event` is the one of
+    /// the extension, see [Connection::extension]. Events of the RandR versions
+    /// after 1.3, such as ProviderChange, are not parsed.
+    pub fn from_event(event: &Event, first_event: u8) -> Option<RandrEvent> {
+        let Event::Unknown(bytes) = event else {
+            return None;
+        };
+        let code = bytes.first()? & 0x7f;
+        if code == first_event.wrapping_add(event_code::SCREEN_CHANGE_NOTIFY) {
+            Self::parse_screen_change_notify(bytes)
+        } else if code == first_event.wrapping_add(event_code::NOTIFY) {
+            match *bytes.get(1)? {
+                notify_subcode::CRTC_CHANGE => Self::parse_crtc_change(bytes),
+                notify_subcode::OUTPUT_CHANGE => Self::parse_output_change(bytes),
+                notify_subcode::OUTPUT_PROPERTY => Self::parse_output_property(bytes),
+                _ => None,
+            }
+        } else {
+            None
+        }
+    }
+
+    //   1 byte     Code (first event + 0)
+    //   1 byte     Rotation
+    //   2 bytes    Sequence number
+    //   4 bytes    Timestamp
+    //   4 bytes    Config timestamp
+    //   4 bytes    Root
+    //   4 bytes    Request window
+    //   2 bytes    Size id
+    //   2 bytes    Subpixel order
+    //   2 bytes    Width in pixels
+    //   2 bytes    Height in pixels
+    //   2 bytes    Width in millimeters
+    //   2 bytes    Height in millimeters
+    fn parse_screen_change_notify(bytes: &[u8]) -> Option<RandrEvent> {
+        let rest = skip(bytes, 1).ok()?;
+        let (rotation, rest) = deserialize_into::<u8>(rest).ok()?;
+        let rest = skip(rest, 2).ok()?;
+        let (timestamp, rest) = deserialize_into::<Timestamp>(rest).ok()?;
+        let (config_timestamp, rest) = deserialize_into::<Timestamp>(rest).ok()?;
+        let (root, rest) = deserialize_into::<Window>(rest).ok()?;
+        let (window, rest) = deserialize_into::<Window>(rest).ok()?;
+        let (size_id, rest) = deserialize_into::<u16>(rest).ok()?;
+        let (subpixel_order, rest) = deserialize_into::<u16>(rest).ok()?;
+        let (width, rest) = deserialize_into::<u16>(rest).ok()?;
+        let (height, rest) = deserialize_into::<u16>(rest).ok()?;
+        let (mm_width, rest) = deserialize_into::<u16>(rest).ok()?;
+        let (mm_height, _) = deserialize_into::<u16>(rest).ok()?;
+        Some(RandrEvent::ScreenChangeNotify(ScreenChangeNotifyEvent {
+            rotation: Rotation(rotation.into()),
+            timestamp,
+            config_timestamp,
+            root,
+            window,
+            size_id,
+            subpixel_order,
+            width,
+            height,
+            mm_width,
+            mm_height,
+        }))
+    }
+
+    //   1 byte     Code (first event + 1)
+    //   1 byte     Subcode (0)
+    //   2 bytes    Sequence number
+    //   4 bytes    Timestamp
+    //   4 bytes    Window
+    //   4 bytes    Crtc
+    //   4 bytes    Mode
+    //   2 bytes    Rotation
+    //   2 bytes    Unused
+    //   2 bytes    X
+    //   2 bytes    Y
+    //   2 bytes    Width
+    //   2 bytes    Height
+    fn parse_crtc_change(bytes: &[u8]) -> Option<RandrEvent> {
+        let rest = skip(bytes, 4).ok()?;
+        let (timestamp, rest) = deserialize_into::<Timestamp>(rest).ok()?;
+        let (window, rest) = deserialize_into::<Window>(rest).ok()?;
+        let (crtc, rest) = deserialize_into::<Crtc>(rest).ok()?;
+        let (mode, rest) = deserialize_into::<Mode>(rest).ok()?;
+        let (rotation, rest) = deserialize_into::<u16>(rest).ok()?;
+        let rest = skip(rest, 2).ok()?;
+        let (x, rest) = deserialize_into::<i16>(rest).ok()?;
+        let (y, rest) = deserialize_into::<i16>(rest).ok()?;
+        let (width, rest) = deserialize_into::<u16>(rest).ok()?;
+        let (height, _) = deserialize_into::<u16>(rest).ok()?;
+        Some(RandrEvent::CrtcChange(CrtcChangeEvent {
+            timestamp,
+            window,
+            crtc,
+            mode,
+            rotation: Rotation(rotation),
+            x,
+            y,
+            width,
+            height,
+        }))
+    }
+
+    //   1 byte     Code (first event + 1)
+    //   1 byte     Subcode (1)
+    //   2 bytes    Sequence number
+    //   4 bytes    Timestamp
+    //   4 bytes    Config timestamp
+    //   4 bytes    Window
+    //   4 bytes    Output
+    //   4 bytes    Crtc
+    //   4 bytes    Mode
+    //   2 bytes    Rotation
+    //   1 byte     Connection
+    //   1 byte     Subpixel order
+    fn parse_output_change(bytes: &[u8]) -> Option<RandrEvent> {
+        let rest = skip(bytes, 4).ok()?;
+        let (timestamp, rest) = deserialize_into::<Timestamp>(rest).ok()?;
+        let (config_timestamp, rest) = deserialize_into::<Timestamp>(rest).ok()?;
+        let (window, rest) = deserialize_into::<Window>(rest).ok()?;
+        let (output, rest) = deserialize_into::<Output>(rest).ok()?;
+        let (crtc, rest) = deserialize_into::<Crtc>(rest).ok()?;
+        let (mode, rest) = deserialize_into::<Mode>(rest).ok()?;
+        let (rotation, rest) = deserialize_into::<u16>(rest).ok()?;
+        let (connection, rest) = deserialize_into::<u8>(rest).ok()?;
+        let (subpixel_order, _) = deserialize_into::<u8>(rest).ok()?;
+        Some(RandrEvent::OutputChange(OutputChangeEvent {
+            timestamp,
+            config_timestamp,
+            window,
+            output,
+            crtc,
+            mode,
+            rotation: Rotation(rotation),
+            connection: OutputConnection(connection),
+            subpixel_order,
+        }))
+    }
+
+    //   1 byte     Code (first event + 1)
+    //   1 byte     Subcode (2)
+    //   2 bytes    Sequence number
+    //   4 bytes    Window
+    //   4 bytes    Output
+    //   4 bytes    Property
+    //   4 bytes    Timestamp
+    //   1 byte     State
+    //   11 bytes   Unused
+    fn parse_output_property(bytes: &[u8]) -> Option<RandrEvent> {
+        let rest = skip(bytes, 4).ok()?;
+        let (window, rest) = deserialize_into::<Window>(rest).ok()?;
+        let (output, rest) = deserialize_into::<Output>(rest).ok()?;
+        let (property, rest) = deserialize_into::<Atom>(rest).ok()?;
+        let (timestamp, rest) = deserialize_into::<Timestamp>(rest).ok()?;
+        let (state, _) = deserialize_into::<u8>(rest).ok()?;
+        Some(RandrEvent::OutputProperty(OutputPropertyEvent {
+            window,
+            output,
+            property,
+            timestamp,
+            state,
+        }))
+    }
+}
+
+impl Connection {
+    /// Tells the server the version of the extension the client supports and
+    /// returns the version both support. Call it before any other RandR request.
+    pub fn randr_query_version(
+        &mut self,
+        major_version: u32,
+        minor_version: u32,
+    ) -> Result<Cookie<QueryVersionReply>, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (0)
+        //   2 bytes    Request length (3)
+        //   4 bytes    Client major version
+        //   4 bytes    Client minor version
+        let request = request(self, opcode::QUERY_VERSION)?
+            .put(major_version)
+            .put(minor_version)
+            .finish();
+        self.send_request_with_reply(&request)
+    }
+
+    /// Selects the RandR events `window` reports. The events arrive as
+    /// [Event::Unknown] and are parsed with [RandrEvent::from_event].
+    pub fn randr_select_input(
+        &mut self,
+        window: Window,
+        mask: NotifyMask,
+    ) -> Result<u16, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (4)
+        //   2 bytes    Request length (3)
+        //   4 bytes    Window
+        //   2 bytes    Enable
+        //   2 bytes    Unused
+        let request = request(self, opcode::SELECT_INPUT)?
+            .put(window)
+            .put(mask.0)
+            .pad(2)
+            .finish();
+        self.send_request(&request)
+    }
+
+    /// Returns the CRTCs, outputs and modes of the screen of `window`.
+    ///
+    /// The server probes the hardware for new monitors first, which may take
+    /// noticeable time. See [Connection::randr_get_screen_resources_current].
+    pub fn randr_get_screen_resources(
+        &mut self,
+        window: Window,
+    ) -> Result<Cookie<ScreenResources>, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (8)
+        //   2 bytes    Request length (2)
+        //   4 bytes    Window
+        let request = request(self, opcode::GET_SCREEN_RESOURCES)?
+            .put(window)
+            .finish();
+        self.send_request_with_reply(&request)
+    }
+
+    /// Returns the CRTCs, outputs and modes of the screen of `window` as the
+    /// server last saw them, without probing the hardware. Requires RandR 1.3.
+    pub fn randr_get_screen_resources_current(
+        &mut self,
+        window: Window,
+    ) -> Result<Cookie<ScreenResources>, ConnectionError> {
+        let request = request(self, opcode::GET_SCREEN_RESOURCES_CURRENT)?
+            .put(window)
+            .finish();
+        self.send_request_with_reply(&request)
+    }
+
+    /// Returns the state of an output. `config_timestamp` comes from [ScreenResources].
+    pub fn randr_get_output_info(
+        &mut self,
+        output: Output,
+        config_timestamp: Timestamp,
+    ) -> Result<Cookie<OutputInfo>, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (9)
+        //   2 bytes    Request length (3)
+        //   4 bytes    Output
+        //   4 bytes    Config timestamp
+        let request = request(self, opcode::GET_OUTPUT_INFO)?
+            .put(output)
+            .put(config_timestamp)
+            .finish();
+        self.send_request_with_reply(&request)
+    }
+
+    /// Returns the state of a CRTC. `config_timestamp` comes from [ScreenResources].
+    pub fn randr_get_crtc_info(
+        &mut self,
+        crtc: Crtc,
+        config_timestamp: Timestamp,
+    ) -> Result<Cookie<CrtcInfo>, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (20)
+        //   2 bytes    Request length (3)
+        //   4 bytes    Crtc
+        //   4 bytes    Config timestamp
+        let request = request(self, opcode::GET_CRTC_INFO)?
+            .put(crtc)
+            .put(config_timestamp)
+            .finish();
+        self.send_request_with_reply(&request)
+    }
+
+    /// Returns a property of an output, e.g. its EDID.
+    ///
+    /// `long_offset` and `long_length` are in 4-byte units. With `pending`, the
+    /// value to be applied at the next mode set is returned instead.
+    #[allow(clippy::too_many_arguments)]
+    pub fn randr_get_output_property(
+        &mut self,
+        output: Output,
+        property: Atom,
+        type_: Atom,
+        long_offset: u32,
+        long_length: u32,
+        delete: bool,
+        pending: bool,
+    ) -> Result<Cookie<OutputProperty>, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (15)
+        //   2 bytes    Request length (7)
+        //   4 bytes    Output
+        //   4 bytes    Property
+        //   4 bytes    Type
+        //   4 bytes    Long offset
+        //   4 bytes    Long length
+        //   1 byte     Delete
+        //   1 byte     Pending
+        //   2 bytes    Unused
+        let request = request(self, opcode::GET_OUTPUT_PROPERTY)?
+            .put(output)
+            .put(property)
+            .put(type_)
+            .put(long_offset)
+            .put(long_length)
+            .put(delete)
+            .put(pending)
+            .pad(2)
+            .finish();
+        self.send_request_with_reply(&request)
+    }
+
+    /// Returns the range of sizes the screen of `window` can be resized to.
+    pub fn randr_get_screen_size_range(
+        &mut self,
+        window: Window,
+    ) -> Result<Cookie<ScreenSizeRange>, ConnectionError> {
+        let request = request(self, opcode::GET_SCREEN_SIZE_RANGE)?
+            .put(window)
+            .finish();
+        self.send_request_with_reply(&request)
+    }
+
+    /// Resizes the screen of `window`. The CRTCs must fit in the new size, so
+    /// disable or move them first when shrinking the screen.
+    pub fn randr_set_screen_size(
+        &mut self,
+        window: Window,
+        width: u16,
+        height: u16,
+        mm_width: u32,
+        mm_height: u32,
+    ) -> Result<u16, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (7)
+        //   2 bytes    Request length (5)
+        //   4 bytes    Window
+        //   2 bytes    Width
+        //   2 bytes    Height
+        //   4 bytes    Width in millimeters
+        //   4 bytes    Height in millimeters
+        let request = request(self, opcode::SET_SCREEN_SIZE)?
+            .put(window)
+            .put(width)
+            .put(height)
+            .put(mm_width)
+            .put(mm_height)
+            .finish();
+        self.send_request(&request)
+    }
+
+    /// Sets the mode, position, rotation and outputs of a CRTC.
+    ///
+    /// `timestamp` is the one of the last [CrtcInfo] or [CrtcChangeEvent], and
+    /// `config_timestamp` the one of [ScreenResources]. Check the status of the
+    /// reply: the server refuses the change if either is outdated.
+    pub fn randr_set_crtc_config(
+        &mut self,
+        crtc: Crtc,
+        timestamp: Timestamp,
+        config_timestamp: Timestamp,
+        config: &CrtcConfig,
+    ) -> Result<Cookie<SetCrtcConfigReply>, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (21)
+        //   2 bytes    Request length (7+n)
+        //   4 bytes    Crtc
+        //   4 bytes    Timestamp
+        //   4 bytes    Config timestamp
+        //   2 bytes    X
+        //   2 bytes    Y
+        //   4 bytes    Mode
+        //   2 bytes    Rotation
+        //   2 bytes    Unused
+        //   4n bytes   Outputs
+        let mut request = request(self, opcode::SET_CRTC_CONFIG)?;
+        request
+            .put(crtc)
+            .put(timestamp)
+            .put(config_timestamp)
+            .put(config.x)
+            .put(config.y)
+            .put(config.mode)
+            .put(config.rotation.0)
+            .pad(2);
+        for &output in &config.outputs {
+            request.put(output);
+        }
+        self.send_request_with_reply(&request.finish())
+    }
+
+    /// Returns the primary output of the screen of `window`. Requires RandR 1.3.
+    pub fn randr_get_output_primary(
+        &mut self,
+        window: Window,
+    ) -> Result<Cookie<OutputPrimaryReply>, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (31)
+        //   2 bytes    Request length (2)
+        //   4 bytes    Window
+        let request = request(self, opcode::GET_OUTPUT_PRIMARY)?
+            .put(window)
+            .finish();
+        self.send_request_with_reply(&request)
+    }
+
+    /// Returns the monitors of the screen of `window`, only those shown by an
+    /// enabled output if `get_active`. Requires RandR 1.5.
+    pub fn randr_get_monitors(
+        &mut self,
+        window: Window,
+        get_active: bool,
+    ) -> Result<Cookie<MonitorsReply>, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (42)
+        //   2 bytes    Request length (3)
+        //   4 bytes    Window
+        //   1 byte     Get active
+        //   3 bytes    Unused
+        let request = request(self, opcode::GET_MONITORS)?
+            .put(window)
+            .put(get_active)
+            .pad(3)
+            .finish();
+        self.send_request_with_reply(&request)
+    }
+
+    /// Returns the number of entries of each ramp of the gamma table of a CRTC.
+    pub fn randr_get_crtc_gamma_size(
+        &mut self,
+        crtc: Crtc,
+    ) -> Result<Cookie<GammaSizeReply>, ConnectionError> {
+        let request = request(self, opcode::GET_CRTC_GAMMA_SIZE)?
+            .put(crtc)
+            .finish();
+        self.send_request_with_reply(&request)
+    }
+
+    /// Returns the gamma table of a CRTC.
+    pub fn randr_get_crtc_gamma(&mut self, crtc: Crtc) -> Result<Cookie<Gamma>, ConnectionError> {
+        let request = request(self, opcode::GET_CRTC_GAMMA)?.put(crtc).finish();
+        self.send_request_with_reply(&request)
+    }
+
+    /// Sets the gamma table of a CRTC. The ramps must have the size returned by
+    /// [Connection::randr_get_crtc_gamma_size].
+    pub fn randr_set_crtc_gamma(
+        &mut self,
+        crtc: Crtc,
+        gamma: &Gamma,
+    ) -> Result<u16, ConnectionError> {
+        let size = gamma.red.len();
+        for ramp in [&gamma.green, &gamma.blue] {
+            if ramp.len() != size {
+                return Err(SerializeError::LengthMismatch {
+                    field: "gamma ramp",
+                    length: ramp.len(),
+                    expected: size,
+                }
+                .into());
+            }
+        }
+        let size = u16::try_from(size).map_err(|_| SerializeError::TooLong {
+            field: "gamma ramp",
+            length: size,
+            max: u16::MAX.into(),
+        })?;
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (24)
+        //   2 bytes    Request length (3+(6n+p)/4)
+        //   4 bytes    Crtc
+        //   2 bytes    Size (n)
+        //   2 bytes    Unused
+        //   2n bytes   Red
+        //   2n bytes   Green
+        //   2n bytes   Blue
+        //   p bytes    Unused (p = pad(6n))
+        let mut request = request(self, opcode::SET_CRTC_GAMMA)?;
+        request.put(crtc).put(size).pad(2);
+        for &value in gamma.red.iter().chain(&gamma.green).chain(&gamma.blue) {
+            request.put(value);
+        }
+        self.send_request(&request.finish())
+    }
+
+    /// Returns the enabled CRTCs of the screen of `window`.
+    pub fn randr_active_crtcs(
+        &mut self,
+        window: Window,
+    ) -> Result<Vec<(Crtc, CrtcInfo)>, ConnectionError> {
+        let resources = self
+            .randr_get_screen_resources_current(window)?
+            .reply(self)?;
+        let cookies = resources
+            .crtcs
+            .iter()
+            .map(|&crtc| {
+                let cookie = self.randr_get_crtc_info(crtc, resources.config_timestamp)?;
+                Ok((crtc, cookie))
+            })
+            .collect::<Result<Vec<_>, ConnectionError>>()?;
+        let mut crtcs = Vec::with_capacity(cookies.len());
+        for (crtc, cookie) in cookies {
+            let info = cookie.reply(self)?;
+            if info.mode != 0 {
+                crtcs.push((crtc, info));
+            }
+        }
+        Ok(crtcs)
+    }
+
+    /// Returns the enabled CRTC showing the given point of the root window of
+    /// `window`, i.e. the monitor the pointer is on for root coordinates.
+    pub fn randr_crtc_at(
+        &mut self,
+        window: Window,
+        x: i16,
+        y: i16,
+    ) -> Result<Option<(Crtc, CrtcInfo)>, ConnectionError> {
+        let crtcs = self.randr_active_crtcs(window)?;
+        Ok(crtcs.into_iter().find(|(_, info)| info.contains(x, y)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cookie::{parse_reply, ParseMode};
+    use crate::protocol::XId;
+    use crate::testing::MessageBuilder;
+
+    /// Writes a MODEINFO of the given timings.
+    fn put_mode(message: &mut MessageBuilder, id: Mode, size: (u16, u16), name: &str) {
+        // 1920x1080 at 60 Hz (CEA-861), 1280x720 at 60 Hz otherwise.
+        let (dot_clock, htotal, vtotal) = match size {
+            (1920, 1080) => (148_500_000u32, 2200u16, 1125u16),
+            _ => (74_250_000, 1650, 750),
+        };
+        message
+            .put(id)
+            .put(size.0)
+            .put(size.1)
+            .put(dot_clock)
+            .put(size.0 + 88)
+            .put(size.0 + 132)
+            .put(htotal)
+            .put(0u16)
+            .put(size.1 + 4)
+            .put(size.1 + 9)
+            .put(vtotal)
+            .put(name.len() as u16)
+            // +HSync +VSync.
+            .put(0x5u32);
+    }
+
+    #[test]
+    fn screen_resources() {
+        // GetScreenResourcesCurrent of a CRTC, two outputs and two modes.
+        let mut message = MessageBuilder::default();
+        message
+            .put(0x0001_2345u32)
+            .put(0x0001_2000u32)
+            .put(1u16)
+            .put(2u16)
+            .put(2u16)
+            .put(17u16)
+            .pad(8)
+            .put(0x3fu32)
+            .put(0x40u32)
+            .put(0x41u32);
+        put_mode(&mut message, 0x42, (1920, 1080), "1920x1080");
+        put_mode(&mut message, 0x43, (1280, 720), "1280x720");
+        // The names follow each other, padded together.
+        let bytes = message.put_bytes(b"1920x10801280x720").pad(3).reply(0);
+        assert_eq!(bytes.len(), 128);
+
+        let resources: ScreenResources = parse_reply(&bytes, ParseMode::Strict).unwrap();
+        assert_eq!(resources.timestamp, 0x0001_2345);
+        assert_eq!(resources.config_timestamp, 0x0001_2000);
+        assert_eq!(resources.crtcs, [0x3f]);
+        assert_eq!(resources.outputs, [0x40, 0x41]);
+        assert_eq!(resources.modes.len(), 2);
+        let mode = resources.mode(0x42).unwrap();
+        assert_eq!(mode.name.to_string(), "1920x1080");
+        assert_eq!((mode.width, mode.height), (1920, 1080));
+        assert_eq!((mode.hsync_start, mode.vsync_end), (2008, 1089));
+        assert_eq!(mode.refresh_rate(), 60.0);
+        let mode = resources.mode(0x43).unwrap();
+        assert_eq!(mode.name.to_string(), "1280x720");
+        assert_eq!(mode.refresh_rate(), 60.0);
+        assert!(resources.mode(0x44).is_none());
+
+        // A name longer than the bytes left.
+        let mut truncated = bytes.clone();
+        truncated.truncate(128 - 4);
+        assert!(ScreenResources::from_bytes(&truncated).is_err());
+        // Bytes past the padding of the names.
+        let mut long = bytes;
+        long.extend_from_slice(&[0; 4]);
+        assert!(parse_reply::<ScreenResources>(&long, ParseMode::Strict).is_err());
+        assert!(parse_reply::<ScreenResources>(&long, ParseMode::Lenient).is_ok());
+    }
+
+    #[test]
+    fn output_info() {
+        let bytes = MessageBuilder::default()
+            .put(0x0001_2345u32)
+            .put(0x3fu32)
+            .put(530u32)
+            .put(300u32)
+            .put(OutputConnection::CONNECTED.0)
+            .put(0u8)
+            .put(1u16)
+            .put(2u16)
+            .put(1u16)
+            .put(1u16)
+            .put(6u16)
+            .put(0x3fu32)
+            .put(0x42u32)
+            .put(0x43u32)
+            .put(0x41u32)
+            .put_bytes(b"HDMI-1")
+            .pad(2)
+            .reply(0);
+        assert_eq!(bytes.len(), 60);
+
+        let info: OutputInfo = parse_reply(&bytes, ParseMode::Strict).unwrap();
+        assert_eq!(info.status, 0);
+        assert_eq!(info.crtc, 0x3f);
+        assert_eq!((info.mm_width, info.mm_height), (530, 300));
+        assert_eq!(info.connection, OutputConnection::CONNECTED);
+        assert_eq!(info.crtcs, [0x3f]);
+        assert_eq!(info.modes, [0x42, 0x43]);
+        assert_eq!(info.preferred_modes(), [0x42]);
+        assert_eq!(info.clones, [0x41]);
+        assert_eq!(info.name.to_string(), "HDMI-1");
+
+        // A connection beyond Unknown.
+        let mut invalid = bytes;
+        invalid[24] = 3;
+        assert!(parse_reply::<OutputInfo>(&invalid, ParseMode::Strict).is_err());
+    }
+
+    #[test]
+    fn crtc_info() {
+        let bytes = MessageBuilder::default()
+            .put(0x0001_2345u32)
+            .put(1920i16)
+            .put(0i16)
+            .put(1280u16)
+            .put(720u16)
+            .put(0x43u32)
+            .put(Rotation::ROTATE_90.0)
+            .put(0x3fu16)
+            .put(1u16)
+            .put(2u16)
+            .put(0x41u32)
+            .put(0x40u32)
+            .put(0x41u32)
+            .reply(0);
+
+        let info: CrtcInfo = parse_reply(&bytes, ParseMode::Strict).unwrap();
+        assert_eq!(
+            (info.x, info.y, info.width, info.height),
+            (1920, 0, 1280, 720)
+        );
+        assert_eq!(info.mode, 0x43);
+        assert_eq!(info.rotation, Rotation::ROTATE_90);
+        assert_eq!(info.rotations, Rotation(0x3f));
+        assert_eq!(info.outputs, [0x41]);
+        assert_eq!(info.possible_outputs, [0x40, 0x41]);
+        assert!(info.contains(1920, 0));
+        assert!(info.contains(3199, 719));
+        assert!(!info.contains(1919, 0));
+        assert!(!info.contains(3200, 0));
+    }
+
+    #[test]
+    fn monitors() {
+        // A primary monitor and one on its left.
+        let bytes = MessageBuilder::default()
+            .put(0x0001_2345u32)
+            .put(2u32)
+            .put(2u32)
+            .pad(12)
+            .put(0x1c0u32)
+            .put(true)
+            .put(true)
+            .put(1u16)
+            .put(0i16)
+            .put(0i16)
+            .put(1920u16)
+            .put(1080u16)
+            .put(530u32)
+            .put(300u32)
+            .put(0x40u32)
+            .put(0x1c1u32)
+            .put(false)
+            .put(true)
+            .put(1u16)
+            .put(-1280i16)
+            .put(0i16)
+            .put(1280u16)
+            .put(720u16)
+            .put(290u32)
+            .put(170u32)
+            .put(0x41u32)
+            .reply(0);
+        assert_eq!(bytes.len(), 88);
+
+        let reply: MonitorsReply = parse_reply(&bytes, ParseMode::Strict).unwrap();
+        assert_eq!(reply.timestamp, 0x0001_2345);
+        let [first, second] = &reply.monitors[..] else {
+            panic!("expected two monitors, got {:?}", reply.monitors);
+        };
+        assert_eq!(first.name, 0x1c0);
+        assert!(first.primary && first.automatic);
+        assert_eq!(
+            (first.x, first.y, first.width, first.height),
+            (0, 0, 1920, 1080)
+        );
+        assert_eq!((first.width_mm, first.height_mm), (530, 300));
+        assert_eq!(first.outputs, [0x40]);
+        assert!(!second.primary);
+        assert_eq!((second.x, second.width), (-1280, 1280));
+        assert_eq!(second.outputs, [0x41]);
+
+        // One monitor fewer than announced.
+        assert!(MonitorsReply::from_bytes(&bytes[..60]).is_err());
+    }
+
+    #[test]
+    fn events() {
+        const FIRST_EVENT: u8 = 89;
+        let parse = |bytes: Vec<u8>| {
+            let event = Event::from_bytes(&bytes).unwrap();
+            RandrEvent::from_event(&event, FIRST_EVENT)
+        };
+
+        let screen_change = MessageBuilder::default()
+            .put(Rotation::ROTATE_0.0 as u8)
+            .put(7u16)
+            .put(0x0001_2345u32)
+            .put(0x0001_2000u32)
+            .put(0x4a3u32)
+            .put(0x0040_0001u32)
+            .put(0u16)
+            .put(0u16)
+            .put(3200u16)
+            .put(1080u16)
+            .put(847u16)
+            .put(285u16)
+            .event(FIRST_EVENT);
+        let Some(RandrEvent::ScreenChangeNotify(event)) = parse(screen_change.clone()) else {
+            panic!("expected ScreenChangeNotify");
+        };
+        assert_eq!(event.rotation, Rotation::ROTATE_0);
+        assert_eq!(event.root, XId(0x4a3));
+        assert_eq!(event.window, XId(0x0040_0001));
+        assert_eq!((event.width, event.height), (3200, 1080));
+        assert_eq!((event.mm_width, event.mm_height), (847, 285));
+
+        // Sent with SendEvent.
+        let mut sent = screen_change;
+        sent[0] |= 0x80;
+        assert!(matches!(
+            parse(sent),
+            Some(RandrEvent::ScreenChangeNotify(_))
+        ));
+
+        let crtc_change = MessageBuilder::default()
+            .put(notify_subcode::CRTC_CHANGE)
+            .put(7u16)
+            .put(0x0001_2345u32)
+            .put(0x0040_0001u32)
+            .put(0x3fu32)
+            .put(0x43u32)
+            .put(Rotation::ROTATE_270.0)
+            .pad(2)
+            .put(-1280i16)
+            .put(0i16)
+            .put(720u16)
+            .put(1280u16)
+            .event(FIRST_EVENT + 1);
+        let Some(RandrEvent::CrtcChange(event)) = parse(crtc_change) else {
+            panic!("expected CrtcChange");
+        };
+        assert_eq!((event.crtc, event.mode), (0x3f, 0x43));
+        assert_eq!(event.rotation, Rotation::ROTATE_270);
+        assert_eq!(
+            (event.x, event.y, event.width, event.height),
+            (-1280, 0, 720, 1280)
+        );
+
+        let output_change = MessageBuilder::default()
+            .put(notify_subcode::OUTPUT_CHANGE)
+            .put(7u16)
+            .put(0x0001_2345u32)
+            .put(0x0001_2000u32)
+            .put(0x0040_0001u32)
+            .put(0x41u32)
+            .put(0u32)
+            .put(0u32)
+            .put(Rotation::ROTATE_0.0)
+            .put(OutputConnection::DISCONNECTED.0)
+            .put(0u8)
+            .event(FIRST_EVENT + 1);
+        let Some(RandrEvent::OutputChange(event)) = parse(output_change) else {
+            panic!("expected OutputChange");
+        };
+        assert_eq!((event.output, event.crtc, event.mode), (0x41, 0, 0));
+        assert_eq!(event.connection, OutputConnection::DISCONNECTED);
+
+        let output_property = MessageBuilder::default()
+            .put(notify_subcode::OUTPUT_PROPERTY)
+            .put(7u16)
+            .put(0x0040_0001u32)
+            .put(0x41u32)
+            .put(0x1d0u32)
+            .put(0x0001_2345u32)
+            .put(1u8)
+            .event(FIRST_EVENT + 1);
+        let Some(RandrEvent::OutputProperty(event)) = parse(output_property) else {
+            panic!("expected OutputProperty");
+        };
+        assert_eq!(
+            (event.output, event.property, event.state),
+            (0x41, 0x1d0, 1)
+        );
+
+        // Another subcode, and an event of another extension.
+        let provider_change = MessageBuilder::default().put(3u8).event(FIRST_EVENT + 1);
+        assert!(parse(provider_change).is_none());
+        assert!(parse(MessageBuilder::default().event(FIRST_EVENT + 2)).is_none());
+    }
+}
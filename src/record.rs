@@ -0,0 +1,471 @@
+//! The RECORD extension: a copy of the protocol other clients exchange with the
+//! server, notably the input events the server processes.
+//!
+//! A context, created with [Connection::record_create_context], says which
+//! clients and which parts of the protocol to record. The recorded data then
+//! arrives as a stream of replies to EnableContext, which keeps the connection
+//! busy until the context is disabled. A [Recording] therefore runs on a second,
+//! dedicated connection, while the context is created and disabled on the
+//! first one.
+//!
+//! ```no_run
+//! use mousetrap::connection::Connection;
+//! use mousetrap::event::code;
+//! use mousetrap::record::{ClientSpec, Range, Recording};
+//!
+//! let mut conn = Connection::init().unwrap();
+//! let range = Range {
+//!     device_events: (code::KEY_PRESS, code::MOTION_NOTIFY),
+//!     ..Range::default()
+//! };
+//! let context = conn
+//!     .record_create_context(0, &[ClientSpec::ALL_CLIENTS], &[range])
+//!     .unwrap();
+//! conn.sync().unwrap();
+//!
+//! for data in Recording::init(context).unwrap() {
+//!     for event in data.unwrap().events() {
+//!         println!("{:?}", event);
+//!     }
+//! }
+//! ```
+
+use crate::connection::Connection;
+use crate::cookie::{Cookie, Reply};
+use crate::errors::{ConnectionError, ParseError};
+use crate::event::{self, Event, EVENT_SIZE};
+use crate::extension::{Extension, Version};
+use crate::protocol::{Timestamp, XId};
+use crate::request::RequestBuilder;
+use crate::utils::{check_trailing_bytes, deserialize_into, deserialize_into_vec, skip};
+
+/// Name of the extension, as given to QueryExtension.
+pub const NAME: &str = "RECORD";
+
+/// Version of the extension implemented by this module.
+pub const VERSION: (u16, u16) = (1, 13);
+
+/// Minor opcodes of the RECORD requests.
+pub mod opcode {
+    pub const QUERY_VERSION: u8 = 0;
+    pub const CREATE_CONTEXT: u8 = 1;
+    pub const ENABLE_CONTEXT: u8 = 5;
+    pub const DISABLE_CONTEXT: u8 = 6;
+    pub const FREE_CONTEXT: u8 = 7;
+}
+
+/// Names of the RECORD requests, indexed by minor opcode.
+pub const REQUEST_NAMES: &[&str] = &[
+    "QueryVersion",
+    "CreateContext",
+    "RegisterClients",
+    "UnregisterClients",
+    "GetContext",
+    "EnableContext",
+    "DisableContext",
+    "FreeContext",
+];
+
+/// Names of the RECORD errors, indexed from the first error of the extension.
+pub const ERROR_NAMES: &[&str] = &["BadContext"];
+
+/// Starts a request of the extension.
+fn request(conn: &mut Connection, minor_opcode: u8) -> Result<RequestBuilder, ConnectionError> {
+    let info = conn.require_extension(NAME, REQUEST_NAMES, ERROR_NAMES)?;
+    Ok(RequestBuilder::new(info.major_opcode, minor_opcode))
+}
+
+/// The RECORD extension, for [Connection::enable].
+pub struct Record;
+
+impl Extension for Record {
+    const NAME: &'static str = NAME;
+    const MIN_VERSION: Version = (1, 13);
+
+    fn negotiate(conn: &mut Connection) -> Result<Option<Version>, ConnectionError> {
+        let reply = conn
+            .record_query_version(VERSION.0, VERSION.1)?
+            .reply(conn)?;
+        Ok(Some((
+            reply.major_version.into(),
+            reply.minor_version.into(),
+        )))
+    }
+}
+
+/// The clients a context records.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClientSpec(pub u32);
+
+impl ClientSpec {
+    /// The clients connected when the context is created.
+    pub const CURRENT_CLIENTS: Self = Self(1);
+    /// The clients that connect after the context is created.
+    pub const FUTURE_CLIENTS: Self = Self(2);
+    /// Both current and future clients.
+    pub const ALL_CLIENTS: Self = Self(3);
+
+    /// The client owning the given resource, e.g. a window.
+    pub fn owner_of(resource: XId) -> Self {
+        Self(resource.0)
+    }
+}
+
+/// Flags of the data that precedes every recorded protocol element, given when
+/// creating a context.
+pub mod element_header {
+    /// The server time precedes the elements sent by the server.
+    pub const FROM_SERVER_TIME: u8 = 1;
+    /// The server time precedes the elements sent by clients.
+    pub const FROM_CLIENT_TIME: u8 = 2;
+    /// The sequence number precedes the elements sent by clients.
+    pub const FROM_CLIENT_SEQUENCE: u8 = 4;
+}
+
+/// The parts of the protocol a context records. Each pair is an inclusive range
+/// of codes, and `(0, 0)` records nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Range {
+    /// Major opcodes of core requests.
+    pub core_requests: (u8, u8),
+    /// Major opcodes of the requests whose core replies are recorded.
+    pub core_replies: (u8, u8),
+    /// Major opcodes of extensions, and minor opcodes of their requests.
+    pub ext_requests: ((u8, u8), (u16, u16)),
+    /// Major opcodes of extensions, and minor opcodes of the requests whose
+    /// replies are recorded.
+    pub ext_replies: ((u8, u8), (u16, u16)),
+    /// Codes of the events delivered to the recorded clients.
+    pub delivered_events: (u8, u8),
+    /// Codes of the input events the server processes, whichever client they reach.
+    pub device_events: (u8, u8),
+    /// Codes of errors.
+    pub errors: (u8, u8),
+    /// Whether to record the connection setup of clients.
+    pub client_started: bool,
+    /// Whether to record clients disconnecting.
+    pub client_died: bool,
+}
+
+impl Range {
+    fn serialize_into(&self, request: &mut RequestBuilder) {
+        let ((first_major, last_major), (first_minor, last_minor)) = self.ext_requests;
+        let ((first_reply_major, last_reply_major), (first_reply_minor, last_reply_minor)) =
+            self.ext_replies;
+        request
+            .put(self.core_requests.0)
+            .put(self.core_requests.1)
+            .put(self.core_replies.0)
+            .put(self.core_replies.1)
+            .put(first_major)
+            .put(last_major)
+            .put(first_minor)
+            .put(last_minor)
+            .put(first_reply_major)
+            .put(last_reply_major)
+            .put(first_reply_minor)
+            .put(last_reply_minor)
+            .put(self.delivered_events.0)
+            .put(self.delivered_events.1)
+            .put(self.device_events.0)
+            .put(self.device_events.1)
+            .put(self.errors.0)
+            .put(self.errors.1)
+            .put(self.client_started as u8)
+            .put(self.client_died as u8);
+    }
+}
+
+/// Reply of the RecordQueryVersion request.
+#[derive(Debug, Clone)]
+pub struct QueryVersionReply {
+    pub major_version: u16,
+    pub minor_version: u16,
+}
+
+impl Reply for QueryVersionReply {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1                        unused
+        // 2  CARD16                sequence number
+        // 4  0                     reply length
+        // 2  CARD16                major-version
+        // 2  CARD16                minor-version
+        // 20                       unused
+        let rest = skip(bytes, 8)?;
+        let (major_version, rest) = deserialize_into::<u16>(rest)?;
+        let (minor_version, _) = deserialize_into::<u16>(rest)?;
+        Ok(QueryVersionReply {
+            major_version,
+            minor_version,
+        })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        check_trailing_bytes("record::QueryVersionReply", bytes, 32)
+    }
+}
+
+/// What a reply of EnableContext carries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Category(pub u8);
+
+impl Category {
+    /// Protocol elements sent by the server.
+    pub const FROM_SERVER: Self = Self(0);
+    /// Requests sent by a client.
+    pub const FROM_CLIENT: Self = Self(1);
+    /// The connection setup of a client.
+    pub const CLIENT_STARTED: Self = Self(2);
+    /// A client disconnected.
+    pub const CLIENT_DIED: Self = Self(3);
+    /// The context is enabled, the first reply.
+    pub const START_OF_DATA: Self = Self(4);
+    /// The context was disabled, the last reply.
+    pub const END_OF_DATA: Self = Self(5);
+}
+
+/// A reply of the RecordEnableContext request: some recorded protocol.
+#[derive(Debug, Clone)]
+pub struct InterceptedData {
+    pub category: Category,
+    /// The [element_header] flags of the context.
+    pub element_header: u8,
+    /// Whether the recorded client uses the other byte order, which the
+    /// recorded data is in.
+    pub client_swapped: bool,
+    /// The resource id base of the recorded client, 0 for the server.
+    pub id_base: u32,
+    pub server_time: Timestamp,
+    /// The sequence number of the last request of the recorded client.
+    pub recorded_sequence: u32,
+    /// The recorded protocol elements, each preceded by the data the element
+    /// header asks for.
+    pub data: Vec<u8>,
+}
+
+impl InterceptedData {
+    /// Returns the events among the data sent by the server. Replies and errors
+    /// are skipped, as is the data of clients using the other byte order.
+    pub fn events(&self) -> Vec<Event> {
+        if self.category != Category::FROM_SERVER || self.client_swapped {
+            return Vec::new();
+        }
+        let time_length = if self.element_header & element_header::FROM_SERVER_TIME != 0 {
+            4
+        } else {
+            0
+        };
+        let mut events = Vec::new();
+        let mut rest = &self.data[..];
+        while rest.len() >= time_length + EVENT_SIZE {
+            let element = &rest[time_length..];
+            // Replies and generic events are longer than 32 bytes, see Connection::next_message.
+            let length = match element[0] & 0x7f {
+                1 | event::code::GENERIC_EVENT => {
+                    let additional_length = deserialize_into::<u32>(&element[4..8])
+                        .map_or(0, |(length, _)| length as usize);
+                    EVENT_SIZE + additional_length * 4
+                }
+                _ => EVENT_SIZE,
+            };
+            let Some(element) = element.get(..length) else {
+                break;
+            };
+            if element[0] > 1 {
+                if let Ok(event) = Event::from_bytes(element) {
+                    events.push(event);
+                }
+            }
+            rest = &rest[time_length + length..];
+        }
+        events
+    }
+}
+
+impl Reply for InterceptedData {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1  CARD8                 category
+        // 2  CARD16                sequence number
+        // 4  n                     reply length
+        // 1  CARD8                 element-header
+        // 1  BOOL                  client-swapped
+        // 2                        unused
+        // 4  CARD32                id-base
+        // 4  TIMESTAMP             server-time
+        // 4  CARD32                recorded-sequence-number
+        // 8                        unused
+        // 4n BYTE                  data
+        let rest = skip(bytes, 1)?;
+        let (category, rest) = deserialize_into::<u8>(rest)?;
+        let rest = skip(rest, 2)?;
+        let (length, rest) = deserialize_into::<u32>(rest)?;
+        let (element_header, rest) = deserialize_into::<u8>(rest)?;
+        let (client_swapped, rest) = deserialize_into::<u8>(rest)?;
+        let rest = skip(rest, 2)?;
+        let (id_base, rest) = deserialize_into::<u32>(rest)?;
+        let (server_time, rest) = deserialize_into::<Timestamp>(rest)?;
+        let (recorded_sequence, rest) = deserialize_into::<u32>(rest)?;
+        let rest = skip(rest, 8)?;
+        let (data, _) = deserialize_into_vec::<u8>(rest, length as usize * 4)?;
+        Ok(InterceptedData {
+            category: Category(category),
+            element_header,
+            client_swapped: client_swapped != 0,
+            id_base,
+            server_time,
+            recorded_sequence,
+            data,
+        })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        check_trailing_bytes("record::InterceptedData", bytes, 32 + self.data.len())
+    }
+}
+
+/// The stream of data recorded by an enabled context, on its dedicated connection.
+///
+/// Iterating yields the data until the context is disabled with
+/// [Connection::record_disable_context] on another connection.
+pub struct Recording {
+    conn: Connection,
+    /// Sequence number of the EnableContext request.
+    sequence: u16,
+    /// Whether the last reply, of category [Category::END_OF_DATA], arrived.
+    finished: bool,
+}
+
+impl Recording {
+    /// Opens a connection to the display named by DISPLAY and enables the context
+    /// on it. The context must have been created on that display; see
+    /// [Recording::enable] for another one.
+    pub fn init(context: XId) -> Result<Recording, ConnectionError> {
+        Recording::enable(Connection::init()?, context)
+    }
+
+    /// Enables the context on a connection, which is then reserved for the recording.
+    pub fn enable(mut conn: Connection, context: XId) -> Result<Recording, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (5)
+        //   2 bytes    Request length (2)
+        //   4 bytes    Context
+        let request = request(&mut conn, opcode::ENABLE_CONTEXT)?
+            .put(context)
+            .finish();
+        let cookie: Cookie<InterceptedData> = conn.send_request_with_reply(&request)?;
+        Ok(Recording {
+            conn,
+            sequence: cookie.sequence,
+            finished: false,
+        })
+    }
+
+    /// Blocks until the next recorded data arrives. Returns `None` once the
+    /// context was disabled.
+    pub fn next_data(&mut self) -> Result<Option<InterceptedData>, ConnectionError> {
+        if self.finished {
+            return Ok(None);
+        }
+        let data = Cookie::<InterceptedData>::new(self.sequence).reply(&mut self.conn)?;
+        if data.category == Category::END_OF_DATA {
+            self.finished = true;
+            return Ok(None);
+        }
+        Ok(Some(data))
+    }
+
+    /// Returns the dedicated connection, once the recording is finished.
+    pub fn into_connection(self) -> Connection {
+        self.conn
+    }
+}
+
+impl Iterator for Recording {
+    type Item = Result<InterceptedData, ConnectionError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_data().transpose()
+    }
+}
+
+impl Connection {
+    /// Tells the server the version of the extension the client supports and
+    /// returns the version the server supports.
+    pub fn record_query_version(
+        &mut self,
+        major_version: u16,
+        minor_version: u16,
+    ) -> Result<Cookie<QueryVersionReply>, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (0)
+        //   2 bytes    Request length (2)
+        //   2 bytes    Client major version
+        //   2 bytes    Client minor version
+        let request = request(self, opcode::QUERY_VERSION)?
+            .put(major_version)
+            .put(minor_version)
+            .finish();
+        self.send_request_with_reply(&request)
+    }
+
+    /// Creates a context recording the given ranges of the protocol for the given
+    /// clients. `header` is a combination of [element_header] flags.
+    pub fn record_create_context(
+        &mut self,
+        header: u8,
+        clients: &[ClientSpec],
+        ranges: &[Range],
+    ) -> Result<XId, ConnectionError> {
+        let context = self.generate_id()?;
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (1)
+        //   2 bytes    Request length (5+m+6n)
+        //   4 bytes    Context
+        //   1 byte     Element header
+        //   3 bytes    Unused
+        //   4 bytes    Number of clients (m)
+        //   4 bytes    Number of ranges (n)
+        //   4m bytes   Clients
+        //   24n bytes  Ranges
+        let mut request = request(self, opcode::CREATE_CONTEXT)?;
+        request
+            .put(context)
+            .put(header)
+            .pad(3)
+            .put(clients.len() as u32)
+            .put(ranges.len() as u32);
+        for client in clients {
+            request.put(client.0);
+        }
+        for range in ranges {
+            range.serialize_into(&mut request);
+        }
+        self.send_request(&request.finish())?;
+        Ok(context)
+    }
+
+    /// Stops the recording of a context, ending the stream of its [Recording].
+    /// Must be sent on another connection than the recording one.
+    pub fn record_disable_context(&mut self, context: XId) -> Result<u16, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (6)
+        //   2 bytes    Request length (2)
+        //   4 bytes    Context
+        let request = request(self, opcode::DISABLE_CONTEXT)?
+            .put(context)
+            .finish();
+        self.send_request(&request)
+    }
+
+    /// Destroys a context, disabling it first if needed.
+    pub fn record_free_context(&mut self, context: XId) -> Result<u16, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (7)
+        //   2 bytes    Request length (2)
+        //   4 bytes    Context
+        let request = request(self, opcode::FREE_CONTEXT)?.put(context).finish();
+        self.send_request(&request)
+    }
+}
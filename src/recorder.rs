@@ -0,0 +1,419 @@
+//! Recording the keyboard and the mouse, and playing the recordings back.
+//!
+//! [Recorder] captures the key, button and motion events the server processes,
+//! whichever window they reach, through the RECORD extension. They are kept
+//! with their times in an [InputLog], which saves to a compact binary format
+//! and replays through XTEST with the original timing, the way macro tools do.
+//!
+//! ```no_run
+//! use mousetrap::connection::Connection;
+//! use mousetrap::recorder::{InputAction, InputLog, Recorder};
+//!
+//! let mut conn = Connection::init().unwrap();
+//! let mut recorder = Recorder::start(&mut conn).unwrap();
+//! // Record until Escape (keycode 9 on most layouts) is pressed.
+//! while let Some(event) = recorder.next_event().unwrap() {
+//!     if event.action == InputAction::KeyPress(9) {
+//!         break;
+//!     }
+//! }
+//! let log = recorder.stop(&mut conn).unwrap();
+//! std::fs::write("macro.log", log.to_bytes()).unwrap();
+//!
+//! let log = InputLog::from_bytes(&std::fs::read("macro.log").unwrap()).unwrap();
+//! log.replay(&mut conn, 1.0).unwrap();
+//! ```
+
+use crate::connection::Connection;
+use crate::errors::{ConnectionError, ParseError};
+use crate::event::{code, Event};
+use crate::protocol::{KeyCode, Timestamp, XId};
+use crate::record::{ClientSpec, Range, Recording};
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// The first bytes of a log in the binary format.
+const MAGIC: &[u8; 4] = b"MTIL";
+
+/// Version of the binary format.
+const FORMAT_VERSION: u8 = 1;
+
+/// Size of an event in the binary format.
+const EVENT_LENGTH: usize = 10;
+
+/// A key, button or motion, as XTEST can fake it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputAction {
+    KeyPress(KeyCode),
+    KeyRelease(KeyCode),
+    ButtonPress(u8),
+    ButtonRelease(u8),
+    /// The pointer moved to a position of the root window.
+    Motion {
+        x: i16,
+        y: i16,
+    },
+}
+
+impl InputAction {
+    /// Returns the action of a key, button or motion event.
+    pub fn from_event(event: &Event) -> Option<InputAction> {
+        let action = match event {
+            Event::KeyPress(e) => InputAction::KeyPress(e.detail),
+            Event::KeyRelease(e) => InputAction::KeyRelease(e.detail),
+            Event::ButtonPress(e) => InputAction::ButtonPress(e.detail),
+            Event::ButtonRelease(e) => InputAction::ButtonRelease(e.detail),
+            Event::MotionNotify(e) => InputAction::Motion {
+                x: e.root_x,
+                y: e.root_y,
+            },
+            _ => return None,
+        };
+        Some(action)
+    }
+
+    /// Fakes the action through XTEST.
+    pub fn inject(&self, conn: &mut Connection) -> Result<u16, ConnectionError> {
+        match *self {
+            InputAction::KeyPress(keycode) => conn.xtest_fake_key(keycode, true),
+            InputAction::KeyRelease(keycode) => conn.xtest_fake_key(keycode, false),
+            InputAction::ButtonPress(button) => conn.xtest_fake_button(button, true),
+            InputAction::ButtonRelease(button) => conn.xtest_fake_button(button, false),
+            InputAction::Motion { x, y } => conn.xtest_fake_motion(x, y, false),
+        }
+    }
+
+    fn code(&self) -> u8 {
+        match self {
+            InputAction::KeyPress(_) => code::KEY_PRESS,
+            InputAction::KeyRelease(_) => code::KEY_RELEASE,
+            InputAction::ButtonPress(_) => code::BUTTON_PRESS,
+            InputAction::ButtonRelease(_) => code::BUTTON_RELEASE,
+            InputAction::Motion { .. } => code::MOTION_NOTIFY,
+        }
+    }
+}
+
+/// An action and when it happened.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecordedEvent {
+    /// Milliseconds since the first event of the log.
+    pub time: u32,
+    pub action: InputAction,
+}
+
+/// A sequence of recorded actions.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InputLog {
+    pub events: Vec<RecordedEvent>,
+}
+
+impl InputLog {
+    pub fn new() -> InputLog {
+        InputLog::default()
+    }
+
+    /// Returns how long the log takes to replay at normal speed.
+    pub fn duration(&self) -> Duration {
+        let time = self.events.last().map_or(0, |event| event.time);
+        Duration::from_millis(time.into())
+    }
+
+    /// Encodes the log in the binary format, little-endian whatever the machine.
+    //
+    //   4 bytes    "MTIL"
+    //   1 byte     Format version (1)
+    //   3 bytes    Unused
+    //   4 bytes    Number of events (n)
+    //   10n bytes  Events:
+    //     4 bytes    Time in milliseconds
+    //     1 byte     Event code (KeyPress to MotionNotify)
+    //     1 byte     Keycode or button, 0 for motions
+    //     2 bytes    Root x, 0 for keys and buttons
+    //     2 bytes    Root y, 0 for keys and buttons
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(12 + EVENT_LENGTH * self.events.len());
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&[FORMAT_VERSION, 0, 0, 0]);
+        bytes.extend_from_slice(&(self.events.len() as u32).to_le_bytes());
+        for event in &self.events {
+            let (detail, x, y) = match event.action {
+                InputAction::KeyPress(detail)
+                | InputAction::KeyRelease(detail)
+                | InputAction::ButtonPress(detail)
+                | InputAction::ButtonRelease(detail) => (detail, 0, 0),
+                InputAction::Motion { x, y } => (0, x, y),
+            };
+            bytes.extend_from_slice(&event.time.to_le_bytes());
+            bytes.extend_from_slice(&[event.action.code(), detail]);
+            bytes.extend_from_slice(&x.to_le_bytes());
+            bytes.extend_from_slice(&y.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Decodes a log encoded by [InputLog::to_bytes].
+    pub fn from_bytes(bytes: &[u8]) -> Result<InputLog, ParseError> {
+        let invalid = |field, value| ParseError::InvalidValue {
+            type_name: "InputLog",
+            field,
+            value,
+        };
+        let header = bytes
+            .get(..12)
+            .ok_or_else(|| ParseError::not_enough_data("InputLog", 12, bytes.len()))?;
+        if &header[..4] != MAGIC {
+            let magic = u32::from_le_bytes([header[0], header[1], header[2], header[3]]);
+            return Err(invalid("magic", magic));
+        }
+        if header[4] != FORMAT_VERSION {
+            return Err(invalid("version", header[4].into()));
+        }
+        let count = u32::from_le_bytes([header[8], header[9], header[10], header[11]]) as usize;
+        let length = count
+            .checked_mul(EVENT_LENGTH)
+            .ok_or(ParseError::OverFlow {
+                type_name: "InputLog",
+            })?;
+        let data = bytes
+            .get(12..12 + length)
+            .ok_or_else(|| ParseError::not_enough_data("InputLog", 12 + length, bytes.len()))?;
+        let mut events = Vec::with_capacity(count);
+        for event in data.chunks_exact(EVENT_LENGTH) {
+            let time = u32::from_le_bytes([event[0], event[1], event[2], event[3]]);
+            let detail = event[5];
+            let action = match event[4] {
+                code::KEY_PRESS => InputAction::KeyPress(detail),
+                code::KEY_RELEASE => InputAction::KeyRelease(detail),
+                code::BUTTON_PRESS => InputAction::ButtonPress(detail),
+                code::BUTTON_RELEASE => InputAction::ButtonRelease(detail),
+                code::MOTION_NOTIFY => InputAction::Motion {
+                    x: i16::from_le_bytes([event[6], event[7]]),
+                    y: i16::from_le_bytes([event[8], event[9]]),
+                },
+                code => return Err(invalid("event code", code.into())),
+            };
+            events.push(RecordedEvent { time, action });
+        }
+        Ok(InputLog { events })
+    }
+
+    /// Replays the log through XTEST, blocking until it is done. `speed` scales
+    /// the timing: 2.0 replays twice as fast. It must be positive.
+    pub fn replay(&self, conn: &mut Connection, speed: f64) -> Result<(), ConnectionError> {
+        let start = Instant::now();
+        for event in &self.events {
+            let at = Duration::from_millis(event.time.into()).div_f64(speed);
+            if let Some(wait) = at.checked_sub(start.elapsed()) {
+                std::thread::sleep(wait);
+            }
+            event.action.inject(conn)?;
+        }
+        if let Some(error) = conn.sync()?.into_iter().next() {
+            return Err(ConnectionError::ProtocolError(error));
+        }
+        Ok(())
+    }
+}
+
+/// Captures the input events of every device into an [InputLog].
+///
+/// The recording runs on a dedicated connection; the connection given to
+/// [Recorder::start] and [Recorder::stop] creates and disables the RECORD
+/// context.
+pub struct Recorder {
+    context: XId,
+    recording: Recording,
+    log: InputLog,
+    /// The server time of the first event.
+    first_time: Option<Timestamp>,
+    /// The events of the last recorded data not returned yet.
+    pending: VecDeque<RecordedEvent>,
+}
+
+impl Recorder {
+    /// Starts recording the key, button and motion events, on a connection opened
+    /// to the display named by DISPLAY. `conn` must be connected to that display
+    /// too; see [Recorder::start_with] for another one.
+    pub fn start(conn: &mut Connection) -> Result<Recorder, ConnectionError> {
+        Recorder::start_with(conn, Connection::init()?)
+    }
+
+    /// Starts recording the key, button and motion events. The context is created
+    /// on `conn` and the events are read from `data_conn`, a connection to the
+    /// same display that is reserved for the recording.
+    pub fn start_with(
+        conn: &mut Connection,
+        data_conn: Connection,
+    ) -> Result<Recorder, ConnectionError> {
+        let range = Range {
+            device_events: (code::KEY_PRESS, code::MOTION_NOTIFY),
+            ..Range::default()
+        };
+        let context = conn.record_create_context(0, &[ClientSpec::ALL_CLIENTS], &[range])?;
+        if let Some(error) = conn.sync()?.into_iter().next() {
+            return Err(ConnectionError::ProtocolError(error));
+        }
+        Ok(Recorder {
+            context,
+            recording: Recording::enable(data_conn, context)?,
+            log: InputLog::new(),
+            first_time: None,
+            pending: VecDeque::new(),
+        })
+    }
+
+    /// Returns the RECORD context, which another thread can disable with
+    /// [Connection::record_disable_context] to end the recording.
+    pub fn context(&self) -> XId {
+        self.context
+    }
+
+    /// Blocks until the next event is recorded, adds it to the log and returns
+    /// it. Returns `None` once the context is disabled.
+    pub fn next_event(&mut self) -> Result<Option<RecordedEvent>, ConnectionError> {
+        while self.pending.is_empty() {
+            let Some(data) = self.recording.next_data()? else {
+                return Ok(None);
+            };
+            for event in data.events() {
+                let (Some(action), Some(time)) =
+                    (InputAction::from_event(&event), event_time(&event))
+                else {
+                    continue;
+                };
+                let first_time = *self.first_time.get_or_insert(time);
+                let event = RecordedEvent {
+                    time: time.wrapping_sub(first_time),
+                    action,
+                };
+                self.log.events.push(event);
+                self.pending.push_back(event);
+            }
+        }
+        Ok(self.pending.pop_front())
+    }
+
+    /// Returns the events recorded so far.
+    pub fn log(&self) -> &InputLog {
+        &self.log
+    }
+
+    /// Stops recording and returns the log, with the events recorded since the
+    /// last call to [Recorder::next_event].
+    pub fn stop(mut self, conn: &mut Connection) -> Result<InputLog, ConnectionError> {
+        conn.record_disable_context(self.context)?;
+        while self.next_event()?.is_some() {}
+        conn.record_free_context(self.context)?;
+        Ok(self.log)
+    }
+}
+
+/// Returns the time of a key, button or motion event.
+fn event_time(event: &Event) -> Option<Timestamp> {
+    match event {
+        Event::KeyPress(e)
+        | Event::KeyRelease(e)
+        | Event::ButtonPress(e)
+        | Event::ButtonRelease(e)
+        | Event::MotionNotify(e) => Some(e.time),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Ctrl+click with a drag in between.
+    fn log() -> InputLog {
+        let events = [
+            (0, InputAction::KeyPress(37)),
+            (120, InputAction::Motion { x: 640, y: -12 }),
+            (250, InputAction::ButtonPress(1)),
+            (400, InputAction::Motion { x: -3, y: 1079 }),
+            (410, InputAction::ButtonRelease(1)),
+            (70_000, InputAction::KeyRelease(37)),
+        ];
+        InputLog {
+            events: events
+                .into_iter()
+                .map(|(time, action)| RecordedEvent { time, action })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let log = log();
+        let bytes = log.to_bytes();
+        assert_eq!(bytes.len(), 12 + 6 * EVENT_LENGTH);
+        assert_eq!(bytes[..12], *b"MTIL\x01\0\0\0\x06\0\0\0");
+        // The first motion: time, code, detail, x and y, little-endian.
+        assert_eq!(bytes[22..32], [120, 0, 0, 0, 6, 0, 0x80, 0x02, 0xf4, 0xff]);
+        assert_eq!(InputLog::from_bytes(&bytes).unwrap(), log);
+        assert_eq!(log.duration(), Duration::from_secs(70));
+
+        let empty = InputLog::new().to_bytes();
+        assert_eq!(empty.len(), 12);
+        assert!(InputLog::from_bytes(&empty).unwrap().events.is_empty());
+    }
+
+    #[test]
+    fn truncated() {
+        let bytes = log().to_bytes();
+        assert!(matches!(
+            InputLog::from_bytes(&bytes[..8]),
+            Err(ParseError::NotEnoughData {
+                needed: 12,
+                available: 8,
+                ..
+            })
+        ));
+        // The last event cut short.
+        assert!(matches!(
+            InputLog::from_bytes(&bytes[..bytes.len() - 1]),
+            Err(ParseError::NotEnoughData {
+                needed: 72,
+                available: 71,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn invalid() {
+        let bytes = log().to_bytes();
+        let mut wrong = bytes.clone();
+        wrong[..4].copy_from_slice(b"MTIK");
+        assert!(matches!(
+            InputLog::from_bytes(&wrong),
+            Err(ParseError::InvalidValue {
+                field: "magic",
+                value: 0x4b49_544d,
+                ..
+            })
+        ));
+        let mut wrong = bytes.clone();
+        wrong[4] = 2;
+        assert!(matches!(
+            InputLog::from_bytes(&wrong),
+            Err(ParseError::InvalidValue {
+                field: "version",
+                value: 2,
+                ..
+            })
+        ));
+        // An Expose in place of the first event.
+        let mut wrong = bytes;
+        wrong[16] = code::EXPOSE;
+        assert!(matches!(
+            InputLog::from_bytes(&wrong),
+            Err(ParseError::InvalidValue {
+                field: "event code",
+                value: 12,
+                ..
+            })
+        ));
+    }
+}
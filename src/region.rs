@@ -0,0 +1,278 @@
+//! Letting the user drag out a rectangle of the screen, the way screenshot
+//! tools such as `maim -s` and `scrot -s` do, or click a window, the way xprop
+//! and xwininfo do.
+//!
+//! [select_region] grabs the pointer and the keyboard with a crosshair cursor
+//! and shows a rubber band, an [Overlay] shaped as the frame of the selection,
+//! while the user drags with the first button. Any other button or Escape
+//! cancels. [select_window] grabs them the same way and waits for a click.
+//!
+//! ```no_run
+//! use mousetrap::connection::Connection;
+//! use mousetrap::region::select_region;
+//!
+//! let mut conn = Connection::init().unwrap();
+//! match select_region(&mut conn).unwrap() {
+//!     Some(region) => println!("{}x{}+{}+{}", region.width, region.height, region.x, region.y),
+//!     None => println!("cancelled"),
+//! }
+//! ```
+
+use crate::connection::Connection;
+use crate::errors::ConnectionError;
+use crate::event::Event;
+use crate::keysym::XK_Escape;
+use crate::overlay::Overlay;
+use crate::protocol::{EventMask, Rectangle, Window, XId, CURRENT_TIME};
+use crate::reply::GrabStatus;
+use crate::request::{cursor_font, GrabKeyboard, GrabMode, GrabPointer, WindowAttributes};
+use crate::shape::{Kind, Operation, Ordering};
+use std::time::Duration;
+
+/// Width in pixels of the frame drawn around the selection.
+const FRAME_WIDTH: u16 = 2;
+
+/// How many times the grabs are attempted before giving up. Right after a
+/// hotkey the window manager often still grabs the keyboard.
+const GRAB_ATTEMPTS: u32 = 50;
+
+/// Interval between two grab attempts.
+const GRAB_RETRY_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Lets the user select a rectangle of the default screen by dragging with the
+/// first button, and returns it in root window coordinates. Returns `None` if
+/// the user cancelled with another button or Escape. A click without dragging
+/// selects an empty rectangle at the pointer.
+///
+/// Fails with [ConnectionError::GrabFailed] if another client keeps the
+/// pointer or the keyboard grabbed for about a second.
+pub fn select_region(conn: &mut Connection) -> Result<Option<Rectangle>, ConnectionError> {
+    let root = conn.default_root();
+    let cursor = conn.create_font_cursor(cursor_font::CROSSHAIR)?;
+    let result = grab(conn, root, cursor).and_then(|()| {
+        let result = drag(conn, root);
+        conn.ungrab_pointer(CURRENT_TIME)?;
+        conn.ungrab_keyboard(CURRENT_TIME)?;
+        result
+    });
+    conn.free_cursor(cursor)?;
+    if let Some(error) = conn.sync()?.into_iter().next() {
+        return Err(ConnectionError::ProtocolError(error));
+    }
+    result
+}
+
+/// Lets the user pick a window by clicking it with the first button, and returns
+/// its client window, see [Connection::find_client_window]. Clicking the
+/// background picks the root window. Returns `None` if the user cancelled with
+/// another button or Escape.
+///
+/// Fails with [ConnectionError::GrabFailed] like [select_region].
+pub fn select_window(conn: &mut Connection) -> Result<Option<Window>, ConnectionError> {
+    let root = conn.default_root();
+    let cursor = conn.create_font_cursor(cursor_font::CROSSHAIR)?;
+    let result = grab(conn, root, cursor).and_then(|()| {
+        let result = click(conn, root);
+        conn.ungrab_pointer(CURRENT_TIME)?;
+        conn.ungrab_keyboard(CURRENT_TIME)?;
+        result
+    });
+    conn.free_cursor(cursor)?;
+    if let Some(error) = conn.sync()?.into_iter().next() {
+        return Err(ConnectionError::ProtocolError(error));
+    }
+    match result? {
+        Some(window) => conn.find_client_window(window).map(Some),
+        None => Ok(None),
+    }
+}
+
+/// Follows the grabbed events until a click, and returns the child of the root
+/// window that was clicked.
+fn click(conn: &mut Connection, root: Window) -> Result<Option<Window>, ConnectionError> {
+    let escape = conn
+        .keysym_to_keycode(XK_Escape)?
+        .map(|(keycode, _)| keycode);
+    let mut pressed = None;
+    loop {
+        let event = conn.wait_for_event_matching(|event| {
+            matches!(event, Event::ButtonPress(e) | Event::ButtonRelease(e) if e.event == root)
+                || matches!(event, Event::KeyPress(e) if e.event == root)
+        })?;
+        match (event, pressed) {
+            (Event::ButtonPress(press), None) if press.detail == 1 => {
+                pressed = Some(if press.child == XId(0) {
+                    root
+                } else {
+                    press.child
+                });
+            }
+            (Event::ButtonPress(_), _) => return Ok(None),
+            // The grab lasts until the release, so the click does not reach the
+            // window.
+            (Event::ButtonRelease(release), Some(window)) if release.detail == 1 => {
+                return Ok(Some(window));
+            }
+            (Event::KeyPress(key), _) if Some(key.detail) == escape => return Ok(None),
+            _ => {}
+        }
+    }
+}
+
+/// Grabs the pointer and the keyboard, retrying while another client grabs them.
+fn grab(conn: &mut Connection, root: Window, cursor: XId) -> Result<(), ConnectionError> {
+    let mut attempt = 1;
+    loop {
+        let pointer = conn.grab_pointer(&GrabPointer {
+            owner_events: false,
+            grab_window: root,
+            event_mask: EventMask::BUTTON_PRESS
+                | EventMask::BUTTON_RELEASE
+                | EventMask::POINTER_MOTION,
+            pointer_mode: GrabMode::ASYNC,
+            keyboard_mode: GrabMode::ASYNC,
+            confine_to: XId(0),
+            cursor,
+            time: CURRENT_TIME,
+        })?;
+        let keyboard = conn.grab_keyboard(&GrabKeyboard {
+            owner_events: false,
+            grab_window: root,
+            time: CURRENT_TIME,
+            pointer_mode: GrabMode::ASYNC,
+            keyboard_mode: GrabMode::ASYNC,
+        })?;
+        let pointer = pointer.reply(conn)?.status;
+        let keyboard = keyboard.reply(conn)?.status;
+        if pointer == GrabStatus::SUCCESS && keyboard == GrabStatus::SUCCESS {
+            return Ok(());
+        }
+        if pointer == GrabStatus::SUCCESS {
+            conn.ungrab_pointer(CURRENT_TIME)?;
+        }
+        if keyboard == GrabStatus::SUCCESS {
+            conn.ungrab_keyboard(CURRENT_TIME)?;
+        }
+        if attempt == GRAB_ATTEMPTS {
+            let status = if pointer == GrabStatus::SUCCESS {
+                keyboard
+            } else {
+                pointer
+            };
+            return Err(ConnectionError::GrabFailed(status));
+        }
+        attempt += 1;
+        std::thread::sleep(GRAB_RETRY_INTERVAL);
+    }
+}
+
+/// Follows the grabbed events until the selection ends, showing the rubber band.
+fn drag(conn: &mut Connection, root: Window) -> Result<Option<Rectangle>, ConnectionError> {
+    let screen = conn.default_screen();
+    let root_rectangle = Rectangle {
+        x: 0,
+        y: 0,
+        width: screen.width_in_px,
+        height: screen.height_in_px,
+    };
+    let white_pixel = screen.white_pixel;
+    let escape = conn
+        .keysym_to_keycode(XK_Escape)?
+        .map(|(keycode, _)| keycode);
+    let overlay = Overlay::create(conn, root_rectangle, true)?;
+    // An ARGB pixel needs its alpha to be opaque.
+    let pixel = if overlay.is_argb() {
+        0xffff_ffff
+    } else {
+        white_pixel
+    };
+    conn.change_window_attributes(
+        overlay.window(),
+        WindowAttributes {
+            background_pixel: Some(pixel),
+            ..Default::default()
+        },
+    )?;
+    let is_grabbed_event = |event: &Event| {
+        matches!(event,
+            Event::ButtonPress(e) | Event::ButtonRelease(e) | Event::MotionNotify(e)
+                if e.event == root)
+            || matches!(event, Event::KeyPress(e) if e.event == root)
+    };
+    let mut start = None;
+    let result = loop {
+        let event = conn.wait_for_event_matching(is_grabbed_event)?;
+        match (event, start) {
+            (Event::ButtonPress(press), None) if press.detail == 1 => {
+                let position = (press.root_x, press.root_y);
+                start = Some(position);
+                set_frame(conn, &overlay, selection(position, position))?;
+                overlay.map(conn)?;
+            }
+            (Event::ButtonPress(_), _) => break None,
+            (Event::MotionNotify(mut motion), Some(start)) => {
+                // Only the last position of the motions already received matters.
+                while let Some(Event::MotionNotify(next)) = conn.poll_for_event_matching(
+                    |event| matches!(event, Event::MotionNotify(e) if e.event == root),
+                )? {
+                    motion = next;
+                }
+                let position = (motion.root_x, motion.root_y);
+                set_frame(conn, &overlay, selection(start, position))?;
+            }
+            (Event::ButtonRelease(release), Some(start)) if release.detail == 1 => {
+                break Some(selection(start, (release.root_x, release.root_y)));
+            }
+            (Event::KeyPress(key), _) if Some(key.detail) == escape => break None,
+            _ => {}
+        }
+    };
+    overlay.destroy(conn)?;
+    Ok(result)
+}
+
+/// Returns the rectangle between two corners.
+fn selection(start: (i16, i16), end: (i16, i16)) -> Rectangle {
+    Rectangle {
+        x: start.0.min(end.0),
+        y: start.1.min(end.1),
+        width: start.0.abs_diff(end.0),
+        height: start.1.abs_diff(end.1),
+    }
+}
+
+/// Shapes the overlay as the frame around a selection.
+fn set_frame(
+    conn: &mut Connection,
+    overlay: &Overlay,
+    selection: Rectangle,
+) -> Result<u16, ConnectionError> {
+    // The frame surrounds the selected pixels.
+    let (x, y) = (
+        selection.x - FRAME_WIDTH as i16,
+        selection.y - FRAME_WIDTH as i16,
+    );
+    let width = selection.width + 2 * FRAME_WIDTH;
+    let height = selection.height + 2 * FRAME_WIDTH;
+    let side = |x, y, width, height| Rectangle {
+        x,
+        y,
+        width,
+        height,
+    };
+    let frame = [
+        side(x, y, width, FRAME_WIDTH),
+        side(x, y + (height - FRAME_WIDTH) as i16, width, FRAME_WIDTH),
+        side(x, y, FRAME_WIDTH, height),
+        side(x + (width - FRAME_WIDTH) as i16, y, FRAME_WIDTH, height),
+    ];
+    conn.shape_rectangles(
+        Operation::SET,
+        Kind::BOUNDING,
+        Ordering::UNSORTED,
+        overlay.window(),
+        0,
+        0,
+        &frame,
+    )
+}
@@ -0,0 +1,764 @@
+//! The RENDER extension: alpha-blended compositing of pictures.
+//!
+//! A picture wraps a drawable with a [PictFormatInfo] telling how its pixels
+//! hold color and alpha. Pictures are combined with the Porter-Duff operators
+//! of [PictOp]. The formats the server supports are listed by
+//! [Connection::render_query_pict_formats].
+//!
+//! Call [Connection::render_query_version] before any other request.
+
+use crate::connection::Connection;
+use crate::cookie::{Cookie, Reply};
+use crate::errors::{ConnectionError, ParseError};
+use crate::extension::{Extension, Version};
+use crate::protocol::{Atom, ColorMap, Rectangle, VisualId, XId};
+use crate::request::RequestBuilder;
+use crate::utils::{
+    check_trailing_bytes, deserialize_into, deserialize_into_vec, skip, Output, Serialize,
+};
+
+/// Name of the extension, as given to QueryExtension.
+pub const NAME: &str = "RENDER";
+
+/// The newest version of the extension this module implements.
+pub const VERSION: (u32, u32) = (0, 11);
+
+/// Minor opcodes of the RENDER requests.
+pub mod opcode {
+    pub const QUERY_VERSION: u8 = 0;
+    pub const QUERY_PICT_FORMATS: u8 = 1;
+    pub const CREATE_PICTURE: u8 = 4;
+    pub const CHANGE_PICTURE: u8 = 5;
+    pub const FREE_PICTURE: u8 = 7;
+    pub const COMPOSITE: u8 = 8;
+    pub const FILL_RECTANGLES: u8 = 26;
+    pub const CREATE_CURSOR: u8 = 27;
+    pub const CREATE_ANIM_CURSOR: u8 = 31;
+    pub const CREATE_SOLID_FILL: u8 = 33;
+}
+
+/// Names of the RENDER requests, indexed by minor opcode.
+pub const REQUEST_NAMES: &[&str] = &[
+    "QueryVersion",
+    "QueryPictFormats",
+    "QueryPictIndexValues",
+    "QueryDithers",
+    "CreatePicture",
+    "ChangePicture",
+    "SetPictureClipRectangles",
+    "FreePicture",
+    "Composite",
+    "Scale",
+    "Trapezoids",
+    "Triangles",
+    "TriStrip",
+    "TriFan",
+    "ColorTrapezoids",
+    "ColorTriangles",
+    "Transform",
+    "CreateGlyphSet",
+    "ReferenceGlyphSet",
+    "FreeGlyphSet",
+    "AddGlyphs",
+    "AddGlyphsFromPicture",
+    "FreeGlyphs",
+    "CompositeGlyphs8",
+    "CompositeGlyphs16",
+    "CompositeGlyphs32",
+    "FillRectangles",
+    "CreateCursor",
+    "SetPictureTransform",
+    "QueryFilters",
+    "SetPictureFilter",
+    "CreateAnimCursor",
+    "AddTraps",
+    "CreateSolidFill",
+    "CreateLinearGradient",
+    "CreateRadialGradient",
+    "CreateConicalGradient",
+];
+
+/// Names of the RENDER errors, indexed from the first error.
+pub const ERROR_NAMES: &[&str] = &[
+    "BadPictFormat",
+    "BadPicture",
+    "BadPictOp",
+    "BadGlyphSet",
+    "BadGlyph",
+];
+
+/// A picture format, as listed by [Connection::render_query_pict_formats].
+pub type PictFormat = u32;
+
+/// Whether the pixels of a format are indices into a colormap or hold the
+/// channels directly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PictType(pub u8);
+
+impl PictType {
+    pub const INDEXED: Self = Self(0);
+    pub const DIRECT: Self = Self(1);
+}
+
+/// A compositing operator. `Over` blends the source onto the destination.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PictOp(pub u8);
+
+impl PictOp {
+    pub const CLEAR: Self = Self(0);
+    pub const SRC: Self = Self(1);
+    pub const DST: Self = Self(2);
+    pub const OVER: Self = Self(3);
+    pub const OVER_REVERSE: Self = Self(4);
+    pub const IN: Self = Self(5);
+    pub const IN_REVERSE: Self = Self(6);
+    pub const OUT: Self = Self(7);
+    pub const OUT_REVERSE: Self = Self(8);
+    pub const ATOP: Self = Self(9);
+    pub const ATOP_REVERSE: Self = Self(10);
+    pub const XOR: Self = Self(11);
+    pub const ADD: Self = Self(12);
+    pub const SATURATE: Self = Self(13);
+}
+
+/// How a picture is repeated outside of its drawable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Repeat(pub u8);
+
+impl Repeat {
+    pub const NONE: Self = Self(0);
+    pub const NORMAL: Self = Self(1);
+    pub const PAD: Self = Self(2);
+    pub const REFLECT: Self = Self(3);
+}
+
+/// A color with alpha, each channel from 0 to 0xffff.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Color {
+    pub red: u16,
+    pub green: u16,
+    pub blue: u16,
+    pub alpha: u16,
+}
+
+impl Color {
+    /// Returns the color of the given 8-bit channels.
+    pub fn from_rgba8(red: u8, green: u8, blue: u8, alpha: u8) -> Color {
+        // Multiplying by 0x101 maps 0xff to 0xffff.
+        Color {
+            red: u16::from(red) * 0x101,
+            green: u16::from(green) * 0x101,
+            blue: u16::from(blue) * 0x101,
+            alpha: u16::from(alpha) * 0x101,
+        }
+    }
+}
+
+impl Serialize for Color {
+    fn serialize_into<O: Output + ?Sized>(&self, buf: &mut O) {
+        self.red.serialize_into(buf);
+        self.green.serialize_into(buf);
+        self.blue.serialize_into(buf);
+        self.alpha.serialize_into(buf);
+    }
+}
+
+/// Where the channels of a direct format are in a pixel: each channel is
+/// `(pixel >> shift) & mask`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DirectFormat {
+    pub red_shift: u16,
+    pub red_mask: u16,
+    pub green_shift: u16,
+    pub green_mask: u16,
+    pub blue_shift: u16,
+    pub blue_mask: u16,
+    pub alpha_shift: u16,
+    pub alpha_mask: u16,
+}
+
+/// A picture format the server supports.
+#[derive(Debug, Clone)]
+pub struct PictFormatInfo {
+    pub id: PictFormat,
+    pub type_: PictType,
+    pub depth: u8,
+    pub direct: DirectFormat,
+    /// The colormap of an indexed format.
+    pub colormap: ColorMap,
+}
+
+/// The formats commonly used for drawing, which every server supports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StandardFormat {
+    /// 32 bits per pixel with 8 bits of alpha, red, green and blue.
+    Argb32,
+    /// 24 bits of red, green and blue, without alpha.
+    Rgb24,
+    /// 8 bits of alpha only, the usual mask format.
+    A8,
+    A4,
+    A1,
+}
+
+impl StandardFormat {
+    /// Returns the depth and the channels of the format.
+    fn layout(self) -> (u8, DirectFormat) {
+        let argb = |alpha_mask| DirectFormat {
+            red_shift: 16,
+            red_mask: 0xff,
+            green_shift: 8,
+            green_mask: 0xff,
+            blue_shift: 0,
+            blue_mask: 0xff,
+            alpha_shift: 24,
+            alpha_mask,
+        };
+        let alpha = |alpha_mask| DirectFormat {
+            alpha_mask,
+            ..Default::default()
+        };
+        match self {
+            StandardFormat::Argb32 => (32, argb(0xff)),
+            StandardFormat::Rgb24 => (
+                24,
+                DirectFormat {
+                    alpha_shift: 0,
+                    ..argb(0)
+                },
+            ),
+            StandardFormat::A8 => (8, alpha(0xff)),
+            StandardFormat::A4 => (4, alpha(0xf)),
+            StandardFormat::A1 => (1, alpha(0x1)),
+        }
+    }
+}
+
+/// A visual and the format of its pixels.
+#[derive(Debug, Clone)]
+pub struct PictVisual {
+    pub visual: VisualId,
+    pub format: PictFormat,
+}
+
+/// The visuals of a depth of a screen.
+#[derive(Debug, Clone)]
+pub struct PictDepth {
+    pub depth: u8,
+    pub visuals: Vec<PictVisual>,
+}
+
+/// The formats of the visuals of a screen.
+#[derive(Debug, Clone)]
+pub struct PictScreen {
+    pub depths: Vec<PictDepth>,
+    /// The format used for windows whose visual has no format, unused since 0.1.
+    pub fallback: PictFormat,
+}
+
+/// Reply of the QueryPictFormats request: the registry of the formats the
+/// server supports.
+#[derive(Debug, Clone)]
+pub struct PictFormats {
+    pub formats: Vec<PictFormatInfo>,
+    /// The visuals of each screen, in the order of the screens of the setup.
+    pub screens: Vec<PictScreen>,
+    /// The subpixel order of each screen, since 0.6.
+    pub subpixels: Vec<u32>,
+}
+
+impl PictFormats {
+    /// Returns the format with the given id.
+    pub fn format(&self, id: PictFormat) -> Option<&PictFormatInfo> {
+        self.formats.iter().find(|format| format.id == id)
+    }
+
+    /// Returns one of the standard formats.
+    pub fn find_standard(&self, standard: StandardFormat) -> Option<&PictFormatInfo> {
+        let (depth, direct) = standard.layout();
+        self.formats.iter().find(|format| {
+            format.type_ == PictType::DIRECT && format.depth == depth && format.direct == direct
+        })
+    }
+
+    /// Returns the format of the pixels of a visual, e.g. the one of a window
+    /// to create a picture for it.
+    pub fn find_visual_format(&self, visual: VisualId) -> Option<&PictFormatInfo> {
+        let format = self
+            .screens
+            .iter()
+            .flat_map(|screen| &screen.depths)
+            .flat_map(|depth| &depth.visuals)
+            .find(|v| v.visual == visual)?
+            .format;
+        self.format(format)
+    }
+
+    /// Parses a PICTFORMINFO.
+    fn parse_format(bytes: &[u8]) -> Result<(PictFormatInfo, &[u8]), ParseError> {
+        // 4  PICTFORMAT            id
+        // 1  CARD8                 type
+        // 1  CARD8                 depth
+        // 2                        unused
+        // 16 DIRECTFORMAT          direct
+        // 4  COLORMAP              colormap
+        let (id, rest) = deserialize_into::<PictFormat>(bytes)?;
+        let (type_, rest) = deserialize_into::<u8>(rest)?;
+        let (depth, rest) = deserialize_into::<u8>(rest)?;
+        let rest = skip(rest, 2)?;
+        let (channels, rest) = deserialize_into_vec::<u16>(rest, 8)?;
+        let (colormap, rest) = deserialize_into::<ColorMap>(rest)?;
+        let direct = DirectFormat {
+            red_shift: channels[0],
+            red_mask: channels[1],
+            green_shift: channels[2],
+            green_mask: channels[3],
+            blue_shift: channels[4],
+            blue_mask: channels[5],
+            alpha_shift: channels[6],
+            alpha_mask: channels[7],
+        };
+        let format = PictFormatInfo {
+            id,
+            type_: PictType(type_),
+            depth,
+            direct,
+            colormap,
+        };
+        Ok((format, rest))
+    }
+
+    /// Parses a PICTSCREEN and its depths.
+    fn parse_screen(bytes: &[u8]) -> Result<(PictScreen, &[u8]), ParseError> {
+        // 4  CARD32                number of depths (d)
+        // 4  PICTFORMAT            fallback
+        // LISTofPICTDEPTH          depths
+        //
+        // PICTDEPTH:
+        // 1  CARD8                 depth
+        // 1                        unused
+        // 2  CARD16                number of visuals (v)
+        // 4                        unused
+        // 8v LISTofPICTVISUAL      visuals (VISUALID, PICTFORMAT)
+        let (number_of_depths, rest) = deserialize_into::<u32>(bytes)?;
+        let (fallback, mut rest) = deserialize_into::<PictFormat>(rest)?;
+        let mut depths = Vec::new();
+        for _ in 0..number_of_depths {
+            let (depth, remaining) = deserialize_into::<u8>(rest)?;
+            let remaining = skip(remaining, 1)?;
+            let (number_of_visuals, remaining) = deserialize_into::<u16>(remaining)?;
+            let remaining = skip(remaining, 4)?;
+            let (ids, remaining) =
+                deserialize_into_vec::<u32>(remaining, 2 * usize::from(number_of_visuals))?;
+            let visuals = ids
+                .chunks_exact(2)
+                .map(|pair| PictVisual {
+                    visual: pair[0],
+                    format: pair[1],
+                })
+                .collect();
+            depths.push(PictDepth { depth, visuals });
+            rest = remaining;
+        }
+        Ok((PictScreen { depths, fallback }, rest))
+    }
+}
+
+impl Reply for PictFormats {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1                        unused
+        // 2  CARD16                sequence number
+        // 4  CARD32                reply length
+        // 4  CARD32                number of formats (f)
+        // 4  CARD32                number of screens (s)
+        // 4  CARD32                number of depths
+        // 4  CARD32                number of visuals
+        // 4  CARD32                number of subpixels (n)
+        // 4                        unused
+        // 28f LISTofPICTFORMINFO   formats
+        // LISTofPICTSCREEN         screens
+        // 4n LISTofSUBPIXEL        subpixels
+        let rest = skip(bytes, 8)?;
+        let (number_of_formats, rest) = deserialize_into::<u32>(rest)?;
+        let (number_of_screens, rest) = deserialize_into::<u32>(rest)?;
+        let rest = skip(rest, 8)?;
+        let (number_of_subpixels, rest) = deserialize_into::<u32>(rest)?;
+        let mut rest = skip(rest, 4)?;
+        let mut formats = Vec::new();
+        for _ in 0..number_of_formats {
+            let (format, remaining) = Self::parse_format(rest)?;
+            formats.push(format);
+            rest = remaining;
+        }
+        let mut screens = Vec::new();
+        for _ in 0..number_of_screens {
+            let (screen, remaining) = Self::parse_screen(rest)?;
+            screens.push(screen);
+            rest = remaining;
+        }
+        let (subpixels, _) = deserialize_into_vec::<u32>(rest, number_of_subpixels as usize)?;
+        Ok(PictFormats {
+            formats,
+            screens,
+            subpixels,
+        })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        let screens: usize = self
+            .screens
+            .iter()
+            .map(|screen| {
+                8 + screen
+                    .depths
+                    .iter()
+                    .map(|depth| 8 + 8 * depth.visuals.len())
+                    .sum::<usize>()
+            })
+            .sum();
+        let length = 32 + 28 * self.formats.len() + screens + 4 * self.subpixels.len();
+        check_trailing_bytes("render::PictFormats", bytes, length)
+    }
+}
+
+/// Reply of the RenderQueryVersion request.
+#[derive(Debug, Clone)]
+pub struct QueryVersionReply {
+    pub major_version: u32,
+    pub minor_version: u32,
+}
+
+impl Reply for QueryVersionReply {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1                        unused
+        // 2  CARD16                sequence number
+        // 4  0                     reply length
+        // 4  CARD32                major-version
+        // 4  CARD32                minor-version
+        // 16                       unused
+        let rest = skip(bytes, 8)?;
+        let (major_version, rest) = deserialize_into::<u32>(rest)?;
+        let (minor_version, _) = deserialize_into::<u32>(rest)?;
+        Ok(QueryVersionReply {
+            major_version,
+            minor_version,
+        })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        check_trailing_bytes("render::QueryVersionReply", bytes, 32)
+    }
+}
+
+/// Attributes of a picture. Only the attributes that are `Some` are sent to the server.
+#[derive(Debug, Clone, Default)]
+pub struct PictureAttributes {
+    pub repeat: Option<Repeat>,
+    pub alpha_map: Option<XId>,
+    pub alpha_x_origin: Option<i16>,
+    pub alpha_y_origin: Option<i16>,
+    pub clip_x_origin: Option<i16>,
+    pub clip_y_origin: Option<i16>,
+    pub clip_mask: Option<XId>,
+    pub graphics_exposures: Option<bool>,
+    /// ClipByChildren (0) or IncludeInferiors (1).
+    pub subwindow_mode: Option<u8>,
+    /// Sharp (0) or Smooth (1).
+    pub poly_edge: Option<u8>,
+    /// Precise (0) or Imprecise (1).
+    pub poly_mode: Option<u8>,
+    pub dither: Option<Atom>,
+    /// Whether each channel of a mask picture applies to the same channel of
+    /// the source, e.g. for subpixel text.
+    pub component_alpha: Option<bool>,
+}
+
+impl PictureAttributes {
+    /// Appends the value-mask followed by the LISTofVALUE of the attributes.
+    pub fn serialize_into(&self, request: &mut RequestBuilder) {
+        // The values are sent as 4-byte values, in the order of their bit in the
+        // value-mask. INT16 values are sign extended.
+        let values: [Option<u32>; 13] = [
+            self.repeat.map(|v| v.0.into()),
+            self.alpha_map.map(|v| v.0),
+            self.alpha_x_origin.map(|v| i32::from(v) as u32),
+            self.alpha_y_origin.map(|v| i32::from(v) as u32),
+            self.clip_x_origin.map(|v| i32::from(v) as u32),
+            self.clip_y_origin.map(|v| i32::from(v) as u32),
+            self.clip_mask.map(|v| v.0),
+            self.graphics_exposures.map(u32::from),
+            self.subwindow_mode.map(u32::from),
+            self.poly_edge.map(u32::from),
+            self.poly_mode.map(u32::from),
+            self.dither,
+            self.component_alpha.map(u32::from),
+        ];
+        let mut value_mask = 0u32;
+        for (bit, value) in values.iter().enumerate() {
+            if value.is_some() {
+                value_mask |= 1 << bit;
+            }
+        }
+        request.put(value_mask);
+        for value in values.into_iter().flatten() {
+            request.put(value);
+        }
+    }
+}
+
+/// Combines a source picture, through an optional mask, with a destination picture.
+#[derive(Debug, Clone)]
+pub struct Composite {
+    pub op: PictOp,
+    pub src: XId,
+    /// The alpha of the mask scales the source, 0 for no mask.
+    pub mask: XId,
+    pub dst: XId,
+    pub src_x: i16,
+    pub src_y: i16,
+    pub mask_x: i16,
+    pub mask_y: i16,
+    pub dst_x: i16,
+    pub dst_y: i16,
+    pub width: u16,
+    pub height: u16,
+}
+
+impl Composite {
+    /// Converts the request to x11 raw bytes
+    //
+    //   1 byte     Major opcode
+    //   1 byte     Minor opcode (8)
+    //   2 bytes    Request length (9)
+    //   1 byte     Op
+    //   3 bytes    Unused
+    //   4 bytes    Source
+    //   4 bytes    Mask
+    //   4 bytes    Destination
+    //   2 bytes    Source x
+    //   2 bytes    Source y
+    //   2 bytes    Mask x
+    //   2 bytes    Mask y
+    //   2 bytes    Destination x
+    //   2 bytes    Destination y
+    //   2 bytes    Width
+    //   2 bytes    Height
+    pub fn serialize(&self, major_opcode: u8) -> Vec<u8> {
+        RequestBuilder::new(major_opcode, opcode::COMPOSITE)
+            .put(self.op.0)
+            .pad(3)
+            .put(self.src)
+            .put(self.mask)
+            .put(self.dst)
+            .put(self.src_x)
+            .put(self.src_y)
+            .put(self.mask_x)
+            .put(self.mask_y)
+            .put(self.dst_x)
+            .put(self.dst_y)
+            .put(self.width)
+            .put(self.height)
+            .finish()
+    }
+}
+
+/// A frame of an animated cursor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AnimCursorElement {
+    pub cursor: XId,
+    /// How long the frame is shown, in milliseconds.
+    pub delay: u32,
+}
+
+/// Starts a request of the extension.
+fn request(conn: &mut Connection, minor_opcode: u8) -> Result<RequestBuilder, ConnectionError> {
+    let info = conn.require_extension(NAME, REQUEST_NAMES, ERROR_NAMES)?;
+    Ok(RequestBuilder::new(info.major_opcode, minor_opcode))
+}
+
+/// The RENDER extension, for [Connection::enable].
+pub struct Render;
+
+impl Extension for Render {
+    const NAME: &'static str = NAME;
+    const MIN_VERSION: Version = (0, 1);
+
+    fn negotiate(conn: &mut Connection) -> Result<Option<Version>, ConnectionError> {
+        let reply = conn
+            .render_query_version(VERSION.0, VERSION.1)?
+            .reply(conn)?;
+        Ok(Some((reply.major_version, reply.minor_version)))
+    }
+}
+
+impl Connection {
+    /// Tells the server the version of the extension the client supports and
+    /// returns the version both support. Call it before any other RENDER request.
+    pub fn render_query_version(
+        &mut self,
+        major_version: u32,
+        minor_version: u32,
+    ) -> Result<Cookie<QueryVersionReply>, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (0)
+        //   2 bytes    Request length (3)
+        //   4 bytes    Client major version
+        //   4 bytes    Client minor version
+        let request = request(self, opcode::QUERY_VERSION)?
+            .put(major_version)
+            .put(minor_version)
+            .finish();
+        self.send_request_with_reply(&request)
+    }
+
+    /// Returns the picture formats the server supports and the formats of the visuals.
+    pub fn render_query_pict_formats(&mut self) -> Result<Cookie<PictFormats>, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (1)
+        //   2 bytes    Request length (1)
+        let request = request(self, opcode::QUERY_PICT_FORMATS)?.finish();
+        self.send_request_with_reply(&request)
+    }
+
+    /// Creates a picture for a drawable and returns its id. `format` must have
+    /// the depth of the drawable.
+    pub fn render_create_picture(
+        &mut self,
+        drawable: XId,
+        format: PictFormat,
+        attributes: &PictureAttributes,
+    ) -> Result<XId, ConnectionError> {
+        let picture = self.generate_id()?;
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (4)
+        //   2 bytes    Request length (5+n)
+        //   4 bytes    Picture
+        //   4 bytes    Drawable
+        //   4 bytes    Format
+        //   4 bytes    Value mask
+        //   4n bytes   Values
+        let mut request = request(self, opcode::CREATE_PICTURE)?;
+        request.put(picture).put(drawable).put(format);
+        attributes.serialize_into(&mut request);
+        self.send_request(&request.finish())?;
+        Ok(picture)
+    }
+
+    /// Changes the attributes of a picture.
+    pub fn render_change_picture(
+        &mut self,
+        picture: XId,
+        attributes: &PictureAttributes,
+    ) -> Result<u16, ConnectionError> {
+        let mut request = request(self, opcode::CHANGE_PICTURE)?;
+        request.put(picture);
+        attributes.serialize_into(&mut request);
+        self.send_request(&request.finish())
+    }
+
+    pub fn render_free_picture(&mut self, picture: XId) -> Result<u16, ConnectionError> {
+        let request = request(self, opcode::FREE_PICTURE)?.put(picture).finish();
+        self.send_request(&request)
+    }
+
+    pub fn render_composite(&mut self, request: &Composite) -> Result<u16, ConnectionError> {
+        let major_opcode = self
+            .require_extension(NAME, REQUEST_NAMES, ERROR_NAMES)?
+            .major_opcode;
+        self.send_request(&request.serialize(major_opcode))
+    }
+
+    /// Combines a color with the given rectangles of a picture.
+    pub fn render_fill_rectangles(
+        &mut self,
+        op: PictOp,
+        dst: XId,
+        color: Color,
+        rectangles: &[Rectangle],
+    ) -> Result<u16, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (26)
+        //   2 bytes    Request length (5+2n)
+        //   1 byte     Op
+        //   3 bytes    Unused
+        //   4 bytes    Destination
+        //   8 bytes    Color
+        //   8n bytes   Rectangles
+        let mut request = request(self, opcode::FILL_RECTANGLES)?;
+        request.put(op.0).pad(3).put(dst).put(color);
+        for &rectangle in rectangles {
+            request.put(rectangle);
+        }
+        self.send_request(&request.finish())
+    }
+
+    /// Creates a picture of infinite size filled with one color and returns its id.
+    /// It is the usual source to blend a color with [Connection::render_composite].
+    pub fn render_create_solid_fill(&mut self, color: Color) -> Result<XId, ConnectionError> {
+        let picture = self.generate_id()?;
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (33)
+        //   2 bytes    Request length (4)
+        //   4 bytes    Picture
+        //   8 bytes    Color
+        let request = request(self, opcode::CREATE_SOLID_FILL)?
+            .put(picture)
+            .put(color)
+            .finish();
+        self.send_request(&request)?;
+        Ok(picture)
+    }
+
+    /// Creates a cursor from the image of a picture and returns its id.
+    ///
+    /// The picture is usually of the ARGB32 format, which gives full-color
+    /// cursors with alpha. `x` and `y` are the hotspot, which must be inside the
+    /// picture. The picture may be freed afterwards. Set the cursor of a window
+    /// with [crate::request::WindowAttributes::cursor]. Requires RENDER 0.5.
+    pub fn render_create_cursor(
+        &mut self,
+        source: XId,
+        x: u16,
+        y: u16,
+    ) -> Result<XId, ConnectionError> {
+        let cursor = self.generate_id()?;
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (27)
+        //   2 bytes    Request length (4)
+        //   4 bytes    Cursor
+        //   4 bytes    Source
+        //   2 bytes    X
+        //   2 bytes    Y
+        let request = request(self, opcode::CREATE_CURSOR)?
+            .put(cursor)
+            .put(source)
+            .put(x)
+            .put(y)
+            .finish();
+        self.send_request(&request)?;
+        Ok(cursor)
+    }
+
+    /// Creates a cursor cycling through the given cursors and returns its id.
+    /// The frames may be freed afterwards with [Connection::free_cursor]. Requires RENDER 0.8.
+    pub fn render_create_anim_cursor(
+        &mut self,
+        frames: &[AnimCursorElement],
+    ) -> Result<XId, ConnectionError> {
+        let cursor = self.generate_id()?;
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (31)
+        //   2 bytes    Request length (2+2n)
+        //   4 bytes    Cursor
+        //   8n bytes   Frames (CURSOR, CARD32 delay)
+        let mut request = request(self, opcode::CREATE_ANIM_CURSOR)?;
+        request.put(cursor);
+        for frame in frames {
+            request.put(frame.cursor).put(frame.delay);
+        }
+        self.send_request(&request.finish())?;
+        Ok(cursor)
+    }
+}
@@ -0,0 +1,701 @@
+use crate::cookie::Reply;
+use crate::errors::ParseError;
+use crate::protocol::{Atom, ColorMap, VisualId, Window, XString};
+use crate::utils::{
+    check_trailing_bytes, deserialize_into, deserialize_into_string, deserialize_into_vec, padding,
+    skip,
+};
+
+/// Reply of the InternAtom request.
+#[derive(Debug, Clone)]
+pub struct InternAtomReply {
+    /// The atom, or [crate::protocol::atom::NONE] if it does not exist and
+    /// `only_if_exists` was set.
+    pub atom: Atom,
+}
+
+impl Reply for InternAtomReply {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1                        unused
+        // 2  CARD16                sequence number
+        // 4  0                     reply length
+        // 4  ATOM                  atom
+        // 20                       unused
+        let rest = skip(bytes, 8)?;
+        let (atom, _) = deserialize_into::<Atom>(rest)?;
+        Ok(InternAtomReply { atom })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        check_trailing_bytes("InternAtomReply", bytes, 32)
+    }
+}
+
+/// Reply of the GetAtomName request.
+#[derive(Debug, Clone)]
+pub struct AtomNameReply {
+    pub name: XString,
+}
+
+impl Reply for AtomNameReply {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1                        unused
+        // 2  CARD16                sequence number
+        // 4  (n+p)/4               reply length
+        // 2  n                     length of name
+        // 22                       unused
+        // n  STRING8               name
+        // p                        unused, p=pad(n)
+        let rest = skip(bytes, 8)?;
+        let (length, rest) = deserialize_into::<u16>(rest)?;
+        let rest = skip(rest, 22)?;
+        let (name, _) = deserialize_into_string(rest, length)?;
+        Ok(AtomNameReply { name })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        let length = 32 + self.name.len();
+        check_trailing_bytes("AtomNameReply", bytes, length + padding(length, 4))
+    }
+}
+
+/// Reply of the GetProperty request.
+#[derive(Debug, Clone)]
+pub struct GetPropertyReply {
+    /// Whether the value holds 8, 16 or 32 bit values, 0 if the property does
+    /// not exist.
+    pub format: u8,
+    /// The actual type of the property, [crate::protocol::atom::NONE] if it does
+    /// not exist.
+    pub type_: Atom,
+    /// Number of bytes of the value left after the part that was returned.
+    pub bytes_after: u32,
+    /// The returned part of the value, empty if the type did not match.
+    pub value: Vec<u8>,
+}
+
+impl GetPropertyReply {
+    /// Whether the property exists.
+    pub fn exists(&self) -> bool {
+        self.type_ != crate::protocol::atom::NONE
+    }
+
+    /// Returns the value as 32-bit values, or `None` if its format is not 32.
+    pub fn value32(&self) -> Option<Vec<u32>> {
+        if self.format != 32 {
+            return None;
+        }
+        let values = self.value.chunks_exact(4);
+        Some(
+            values
+                .map(|v| u32::from_ne_bytes([v[0], v[1], v[2], v[3]]))
+                .collect(),
+        )
+    }
+
+    /// Returns the value as 16-bit values, or `None` if its format is not 16.
+    pub fn value16(&self) -> Option<Vec<u16>> {
+        if self.format != 16 {
+            return None;
+        }
+        let values = self.value.chunks_exact(2);
+        Some(values.map(|v| u16::from_ne_bytes([v[0], v[1]])).collect())
+    }
+
+    /// Returns the value as bytes, or `None` if its format is not 8.
+    pub fn value8(&self) -> Option<&[u8]> {
+        (self.format == 8).then_some(&self.value[..])
+    }
+}
+
+impl Reply for GetPropertyReply {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1  CARD8                 format
+        // 2  CARD16                sequence number
+        // 4  (n+p)/4               reply length
+        // 4  ATOM                  type
+        // 4  CARD32                bytes-after
+        // 4  CARD32                length of value in format units
+        // 12                       unused
+        // n  LISTofBYTE            value
+        // p                        unused, p=pad(n)
+        let rest = skip(bytes, 1)?;
+        let (format, rest) = deserialize_into::<u8>(rest)?;
+        let rest = skip(rest, 6)?;
+        let (type_, rest) = deserialize_into::<Atom>(rest)?;
+        let (bytes_after, rest) = deserialize_into::<u32>(rest)?;
+        let (units, rest) = deserialize_into::<u32>(rest)?;
+        let rest = skip(rest, 12)?;
+        let length = usize::try_from(units)
+            .ok()
+            .and_then(|units| units.checked_mul(usize::from(format / 8)))
+            .ok_or(ParseError::OverFlow {
+                type_name: "GetPropertyReply",
+            })?;
+        let (value, _) = deserialize_into_vec::<u8>(rest, length)?;
+        Ok(GetPropertyReply {
+            format,
+            type_,
+            bytes_after,
+            value,
+        })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        if ![0, 8, 16, 32].contains(&self.format) {
+            return Err(ParseError::InvalidValue {
+                type_name: "GetPropertyReply",
+                field: "format",
+                value: self.format.into(),
+            });
+        }
+        let length = 32 + self.value.len();
+        check_trailing_bytes("GetPropertyReply", bytes, length + padding(length, 4))
+    }
+}
+
+/// Reply of the GetSelectionOwner request.
+#[derive(Debug, Clone)]
+pub struct SelectionOwnerReply {
+    /// The owner, or [crate::protocol::atom::NONE] if the selection has none.
+    pub owner: Window,
+}
+
+impl Reply for SelectionOwnerReply {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1                        unused
+        // 2  CARD16                sequence number
+        // 4  0                     reply length
+        // 4  WINDOW                owner
+        // 20                       unused
+        let rest = skip(bytes, 8)?;
+        let (owner, _) = deserialize_into::<Window>(rest)?;
+        Ok(SelectionOwnerReply { owner })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        check_trailing_bytes("SelectionOwnerReply", bytes, 32)
+    }
+}
+
+/// Reply of the GrabPointer and GrabKeyboard requests.
+#[derive(Debug, Clone)]
+pub struct GrabReply {
+    pub status: GrabStatus,
+}
+
+/// Whether a grab succeeded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GrabStatus(pub u8);
+
+impl GrabStatus {
+    pub const SUCCESS: Self = Self(0);
+    /// Another client grabs the device.
+    pub const ALREADY_GRABBED: Self = Self(1);
+    pub const INVALID_TIME: Self = Self(2);
+    /// The grab window or the confine-to window is not viewable.
+    pub const NOT_VIEWABLE: Self = Self(3);
+    /// Another client froze the device with a synchronous grab.
+    pub const FROZEN: Self = Self(4);
+}
+
+impl Reply for GrabReply {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1                        status
+        // 2  CARD16                sequence number
+        // 4  0                     reply length
+        // 24                       unused
+        let rest = skip(bytes, 1)?;
+        let (status, _) = deserialize_into::<u8>(rest)?;
+        Ok(GrabReply {
+            status: GrabStatus(status),
+        })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        check_trailing_bytes("GrabReply", bytes, 32)
+    }
+}
+
+/// Reply of the GetImage request.
+#[derive(Debug, Clone)]
+pub struct GetImageReply {
+    pub depth: u8,
+    /// The visual of the window, None for a pixmap.
+    pub visual: VisualId,
+    /// The scanlines, each padded to the scanline pad of the format of `depth`.
+    pub data: Vec<u8>,
+}
+
+impl Reply for GetImageReply {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1  CARD8                 depth
+        // 2  CARD16                sequence number
+        // 4  (n+p)/4               reply length
+        // 4  VISUALID              visual
+        // 20                       unused
+        // n  LISTofBYTE            data
+        // p                        unused, p=pad(n)
+        let rest = skip(bytes, 1)?;
+        let (depth, rest) = deserialize_into::<u8>(rest)?;
+        let rest = skip(rest, 2)?;
+        let (length, rest) = deserialize_into::<u32>(rest)?;
+        let (visual, rest) = deserialize_into::<VisualId>(rest)?;
+        let rest = skip(rest, 20)?;
+        let length = usize::try_from(length)
+            .ok()
+            .and_then(|length| length.checked_mul(4))
+            .ok_or(ParseError::OverFlow {
+                type_name: "GetImageReply",
+            })?;
+        let (data, _) = deserialize_into_vec::<u8>(rest, length)?;
+        Ok(GetImageReply {
+            depth,
+            visual,
+            data,
+        })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        check_trailing_bytes("GetImageReply", bytes, 32 + self.data.len())
+    }
+}
+
+/// Reply of the GetWindowAttributes request.
+#[derive(Debug, Clone)]
+pub struct WindowAttributesReply {
+    pub backing_store: u8,
+    pub visual: VisualId,
+    /// InputOutput (1) or InputOnly (2), see [crate::request::WindowClass].
+    pub class: u16,
+    pub bit_gravity: u8,
+    pub win_gravity: u8,
+    pub backing_planes: u32,
+    pub backing_pixel: u32,
+    pub save_under: bool,
+    pub map_is_installed: bool,
+    /// Unmapped (0), Unviewable (1) or Viewable (2), see [map_state].
+    pub map_state: u8,
+    pub override_redirect: bool,
+    pub colormap: ColorMap,
+    /// The events selected by every client.
+    pub all_event_masks: u32,
+    /// The events selected by this client.
+    pub your_event_mask: u32,
+    pub do_not_propagate_mask: u16,
+}
+
+/// The values of [WindowAttributesReply::map_state].
+pub mod map_state {
+    pub const UNMAPPED: u8 = 0;
+    /// Mapped, but an ancestor is not.
+    pub const UNVIEWABLE: u8 = 1;
+    pub const VIEWABLE: u8 = 2;
+}
+
+impl Reply for WindowAttributesReply {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1                        backing-store
+        // 2  CARD16                sequence number
+        // 4  3                     reply length
+        // 4  VISUALID              visual
+        // 2                        class
+        // 1  BITGRAVITY            bit-gravity
+        // 1  WINGRAVITY            win-gravity
+        // 4  CARD32                backing-planes
+        // 4  CARD32                backing-pixel
+        // 1  BOOL                  save-under
+        // 1  BOOL                  map-is-installed
+        // 1                        map-state
+        // 1  BOOL                  override-redirect
+        // 4  COLORMAP              colormap
+        // 4  SETofEVENT            all-event-masks
+        // 4  SETofEVENT            your-event-mask
+        // 2  SETofDEVICEEVENT      do-not-propagate-mask
+        // 2                        unused
+        let rest = skip(bytes, 1)?;
+        let (backing_store, rest) = deserialize_into::<u8>(rest)?;
+        let rest = skip(rest, 6)?;
+        let (visual, rest) = deserialize_into::<VisualId>(rest)?;
+        let (class, rest) = deserialize_into::<u16>(rest)?;
+        let (bit_gravity, rest) = deserialize_into::<u8>(rest)?;
+        let (win_gravity, rest) = deserialize_into::<u8>(rest)?;
+        let (backing_planes, rest) = deserialize_into::<u32>(rest)?;
+        let (backing_pixel, rest) = deserialize_into::<u32>(rest)?;
+        let (save_under, rest) = deserialize_into::<bool>(rest)?;
+        let (map_is_installed, rest) = deserialize_into::<bool>(rest)?;
+        let (map_state, rest) = deserialize_into::<u8>(rest)?;
+        let (override_redirect, rest) = deserialize_into::<bool>(rest)?;
+        let (colormap, rest) = deserialize_into::<ColorMap>(rest)?;
+        let (all_event_masks, rest) = deserialize_into::<u32>(rest)?;
+        let (your_event_mask, rest) = deserialize_into::<u32>(rest)?;
+        let (do_not_propagate_mask, _) = deserialize_into::<u16>(rest)?;
+        Ok(WindowAttributesReply {
+            backing_store,
+            visual,
+            class,
+            bit_gravity,
+            win_gravity,
+            backing_planes,
+            backing_pixel,
+            save_under,
+            map_is_installed,
+            map_state,
+            override_redirect,
+            colormap,
+            all_event_masks,
+            your_event_mask,
+            do_not_propagate_mask,
+        })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        if self.map_state > map_state::VIEWABLE {
+            return Err(ParseError::InvalidValue {
+                type_name: "WindowAttributesReply",
+                field: "map-state",
+                value: self.map_state.into(),
+            });
+        }
+        check_trailing_bytes("WindowAttributesReply", bytes, 44)
+    }
+}
+
+/// Reply of the GetGeometry request.
+#[derive(Debug, Clone)]
+pub struct GeometryReply {
+    pub depth: u8,
+    pub root: Window,
+    /// The position of the outer corner, relative to the parent.
+    pub x: i16,
+    pub y: i16,
+    /// The inside size, without the border.
+    pub width: u16,
+    pub height: u16,
+    pub border_width: u16,
+}
+
+impl Reply for GeometryReply {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1  CARD8                 depth
+        // 2  CARD16                sequence number
+        // 4  0                     reply length
+        // 4  WINDOW                root
+        // 2  INT16                 x
+        // 2  INT16                 y
+        // 2  CARD16                width
+        // 2  CARD16                height
+        // 2  CARD16                border-width
+        // 10                       unused
+        let rest = skip(bytes, 1)?;
+        let (depth, rest) = deserialize_into::<u8>(rest)?;
+        let rest = skip(rest, 6)?;
+        let (root, rest) = deserialize_into::<Window>(rest)?;
+        let (x, rest) = deserialize_into::<i16>(rest)?;
+        let (y, rest) = deserialize_into::<i16>(rest)?;
+        let (width, rest) = deserialize_into::<u16>(rest)?;
+        let (height, rest) = deserialize_into::<u16>(rest)?;
+        let (border_width, _) = deserialize_into::<u16>(rest)?;
+        Ok(GeometryReply {
+            depth,
+            root,
+            x,
+            y,
+            width,
+            height,
+            border_width,
+        })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        check_trailing_bytes("GeometryReply", bytes, 32)
+    }
+}
+
+/// Reply of the QueryTree request.
+#[derive(Debug, Clone)]
+pub struct QueryTreeReply {
+    pub root: Window,
+    /// The parent, or None for a root window.
+    pub parent: Window,
+    /// The children, in stacking order from bottom to top.
+    pub children: Vec<Window>,
+}
+
+impl Reply for QueryTreeReply {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1                        unused
+        // 2  CARD16                sequence number
+        // 4  n                     reply length
+        // 4  WINDOW                root
+        // 4  WINDOW                parent
+        // 2  n                     number of WINDOWs in children
+        // 14                       unused
+        // 4n LISTofWINDOW          children
+        let rest = skip(bytes, 8)?;
+        let (root, rest) = deserialize_into::<Window>(rest)?;
+        let (parent, rest) = deserialize_into::<Window>(rest)?;
+        let (count, rest) = deserialize_into::<u16>(rest)?;
+        let rest = skip(rest, 14)?;
+        let (children, _) = deserialize_into_vec::<Window>(rest, count.into())?;
+        Ok(QueryTreeReply {
+            root,
+            parent,
+            children,
+        })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        check_trailing_bytes("QueryTreeReply", bytes, 32 + 4 * self.children.len())
+    }
+}
+
+/// Reply of the ListProperties request.
+#[derive(Debug, Clone)]
+pub struct ListPropertiesReply {
+    pub atoms: Vec<Atom>,
+}
+
+impl Reply for ListPropertiesReply {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1                        unused
+        // 2  CARD16                sequence number
+        // 4  n                     reply length
+        // 2  n                     number of ATOMs in atoms
+        // 22                       unused
+        // 4n LISTofATOM            atoms
+        let rest = skip(bytes, 8)?;
+        let (count, rest) = deserialize_into::<u16>(rest)?;
+        let rest = skip(rest, 22)?;
+        let (atoms, _) = deserialize_into_vec::<Atom>(rest, count.into())?;
+        Ok(ListPropertiesReply { atoms })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        check_trailing_bytes("ListPropertiesReply", bytes, 32 + 4 * self.atoms.len())
+    }
+}
+
+/// Reply of the TranslateCoordinates request.
+#[derive(Debug, Clone)]
+pub struct TranslateCoordinatesReply {
+    /// Whether both windows are on the same screen. If not, the other fields
+    /// are zero.
+    pub same_screen: bool,
+    /// The child of the destination window containing the position, or None.
+    pub child: Window,
+    pub dst_x: i16,
+    pub dst_y: i16,
+}
+
+impl Reply for TranslateCoordinatesReply {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1  BOOL                  same-screen
+        // 2  CARD16                sequence number
+        // 4  0                     reply length
+        // 4  WINDOW                child
+        // 2  INT16                 dst-x
+        // 2  INT16                 dst-y
+        // 16                       unused
+        let rest = skip(bytes, 1)?;
+        let (same_screen, rest) = deserialize_into::<bool>(rest)?;
+        let rest = skip(rest, 6)?;
+        let (child, rest) = deserialize_into::<Window>(rest)?;
+        let (dst_x, rest) = deserialize_into::<i16>(rest)?;
+        let (dst_y, _) = deserialize_into::<i16>(rest)?;
+        Ok(TranslateCoordinatesReply {
+            same_screen,
+            child,
+            dst_x,
+            dst_y,
+        })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        check_trailing_bytes("TranslateCoordinatesReply", bytes, 32)
+    }
+}
+
+/// Reply of the QueryPointer request.
+#[derive(Debug, Clone)]
+pub struct QueryPointerReply {
+    /// Whether the pointer is on the screen of the queried window. If not,
+    /// `child` is None and the window coordinates are 0.
+    pub same_screen: bool,
+    /// The root window the pointer is on.
+    pub root: Window,
+    /// The child of the queried window containing the pointer, or None.
+    pub child: Window,
+    pub root_x: i16,
+    pub root_y: i16,
+    pub win_x: i16,
+    pub win_y: i16,
+    /// State of the modifier keys and pointer buttons.
+    pub mask: u16,
+}
+
+impl Reply for QueryPointerReply {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1  BOOL                  same-screen
+        // 2  CARD16                sequence number
+        // 4  0                     reply length
+        // 4  WINDOW                root
+        // 4  WINDOW                child
+        // 2  INT16                 root-x
+        // 2  INT16                 root-y
+        // 2  INT16                 win-x
+        // 2  INT16                 win-y
+        // 2  SETofKEYBUTMASK       mask
+        // 6                        unused
+        let rest = skip(bytes, 1)?;
+        let (same_screen, rest) = deserialize_into::<u8>(rest)?;
+        let rest = skip(rest, 6)?;
+        let (root, rest) = deserialize_into::<Window>(rest)?;
+        let (child, rest) = deserialize_into::<Window>(rest)?;
+        let (root_x, rest) = deserialize_into::<i16>(rest)?;
+        let (root_y, rest) = deserialize_into::<i16>(rest)?;
+        let (win_x, rest) = deserialize_into::<i16>(rest)?;
+        let (win_y, rest) = deserialize_into::<i16>(rest)?;
+        let (mask, _) = deserialize_into::<u16>(rest)?;
+        Ok(QueryPointerReply {
+            same_screen: same_screen != 0,
+            root,
+            child,
+            root_x,
+            root_y,
+            win_x,
+            win_y,
+            mask,
+        })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        check_trailing_bytes("QueryPointerReply", bytes, 32)
+    }
+}
+
+/// Reply of the GetInputFocus request.
+#[derive(Debug, Clone)]
+pub struct InputFocusReply {
+    /// Where the focus reverts to if the focus window becomes unviewable:
+    /// None (0), PointerRoot (1) or Parent (2).
+    pub revert_to: u8,
+    /// The focus window, or None (0) or PointerRoot (1).
+    pub focus: Window,
+}
+
+impl Reply for InputFocusReply {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1                        revert-to
+        // 2  CARD16                sequence number
+        // 4  0                     reply length
+        // 4  WINDOW                focus
+        // 20                       unused
+        let rest = skip(bytes, 1)?;
+        let (revert_to, rest) = deserialize_into::<u8>(rest)?;
+        let rest = skip(rest, 6)?;
+        let (focus, _) = deserialize_into::<Window>(rest)?;
+        Ok(InputFocusReply { revert_to, focus })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        if self.revert_to > 2 {
+            return Err(ParseError::InvalidValue {
+                type_name: "InputFocusReply",
+                field: "revert-to",
+                value: self.revert_to.into(),
+            });
+        }
+        check_trailing_bytes("InputFocusReply", bytes, 32)
+    }
+}
+
+/// Reply of the QueryExtension request.
+#[derive(Debug, Clone)]
+pub struct QueryExtensionReply {
+    pub present: bool,
+    /// The major opcode of the requests of the extension.
+    pub major_opcode: u8,
+    /// The code of the first event of the extension, zero if it has no events.
+    pub first_event: u8,
+    /// The code of the first error of the extension, zero if it has no errors.
+    pub first_error: u8,
+}
+
+impl Reply for QueryExtensionReply {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1                        unused
+        // 2  CARD16                sequence number
+        // 4  0                     reply length
+        // 1  BOOL                  present
+        // 1  CARD8                 major-opcode
+        // 1  CARD8                 first-event
+        // 1  CARD8                 first-error
+        // 20                       unused
+        let rest = skip(bytes, 8)?;
+        let (present, rest) = deserialize_into::<bool>(rest)?;
+        let (major_opcode, rest) = deserialize_into::<u8>(rest)?;
+        let (first_event, rest) = deserialize_into::<u8>(rest)?;
+        let (first_error, _) = deserialize_into::<u8>(rest)?;
+        Ok(QueryExtensionReply {
+            present,
+            major_opcode,
+            first_event,
+            first_error,
+        })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        check_trailing_bytes("QueryExtensionReply", bytes, 32)
+    }
+}
+
+/// Reply of the ListExtensions request.
+#[derive(Debug, Clone)]
+pub struct ListExtensionsReply {
+    pub names: Vec<XString>,
+}
+
+impl Reply for ListExtensionsReply {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1  CARD8                 number of STRs in names
+        // 2  CARD16                sequence number
+        // 4  (n+p)/4               reply length
+        // 24                       unused
+        // n  LISTofSTR             names
+        // p                        unused, p=pad(n)
+        //
+        // Each STR is a 1-byte length followed by that many bytes.
+        let rest = skip(bytes, 1)?;
+        let (count, rest) = deserialize_into::<u8>(rest)?;
+        let mut rest = skip(rest, 30)?;
+        let mut names = Vec::with_capacity(count.into());
+        for _ in 0..count {
+            let (length, tail) = deserialize_into::<u8>(rest)?;
+            let (name, tail) = deserialize_into_string(tail, length.into())?;
+            names.push(name);
+            rest = tail;
+        }
+        Ok(ListExtensionsReply { names })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        let length = 32 + self.names.iter().map(|name| 1 + name.len()).sum::<usize>();
+        check_trailing_bytes("ListExtensionsReply", bytes, length + padding(length, 4))
+    }
+}
@@ -0,0 +1,1838 @@
+use crate::protocol::{Atom, ColorMap, EventMask, KeyCode, Timestamp, VisualId, Window, XId};
+use crate::utils::{padding, Output, Serialize};
+use std::fmt;
+use std::ops::Deref;
+
+/// Major opcodes of the core protocol requests.
+pub mod opcode {
+    pub const CREATE_WINDOW: u8 = 1;
+    pub const CHANGE_WINDOW_ATTRIBUTES: u8 = 2;
+    pub const GET_WINDOW_ATTRIBUTES: u8 = 3;
+    pub const DESTROY_WINDOW: u8 = 4;
+    pub const MAP_WINDOW: u8 = 8;
+    pub const UNMAP_WINDOW: u8 = 10;
+    pub const GET_GEOMETRY: u8 = 14;
+    pub const QUERY_TREE: u8 = 15;
+    pub const INTERN_ATOM: u8 = 16;
+    pub const GET_ATOM_NAME: u8 = 17;
+    pub const CHANGE_PROPERTY: u8 = 18;
+    pub const DELETE_PROPERTY: u8 = 19;
+    pub const GET_PROPERTY: u8 = 20;
+    pub const LIST_PROPERTIES: u8 = 21;
+    pub const SET_SELECTION_OWNER: u8 = 22;
+    pub const GET_SELECTION_OWNER: u8 = 23;
+    pub const CONVERT_SELECTION: u8 = 24;
+    pub const SEND_EVENT: u8 = 25;
+    pub const GRAB_POINTER: u8 = 26;
+    pub const UNGRAB_POINTER: u8 = 27;
+    pub const GRAB_BUTTON: u8 = 28;
+    pub const UNGRAB_BUTTON: u8 = 29;
+    pub const GRAB_KEYBOARD: u8 = 31;
+    pub const UNGRAB_KEYBOARD: u8 = 32;
+    pub const ALLOW_EVENTS: u8 = 35;
+    pub const QUERY_POINTER: u8 = 38;
+    pub const TRANSLATE_COORDINATES: u8 = 40;
+    pub const GET_INPUT_FOCUS: u8 = 43;
+    pub const OPEN_FONT: u8 = 45;
+    pub const CLOSE_FONT: u8 = 46;
+    pub const CREATE_PIXMAP: u8 = 53;
+    pub const FREE_PIXMAP: u8 = 54;
+    pub const CREATE_GC: u8 = 55;
+    pub const FREE_GC: u8 = 60;
+    pub const CLEAR_AREA: u8 = 61;
+    pub const PUT_IMAGE: u8 = 72;
+    pub const GET_IMAGE: u8 = 73;
+    pub const CREATE_COLORMAP: u8 = 78;
+    pub const FREE_COLORMAP: u8 = 79;
+    pub const CREATE_GLYPH_CURSOR: u8 = 94;
+    pub const FREE_CURSOR: u8 = 95;
+    pub const SET_CLOSE_DOWN_MODE: u8 = 112;
+    pub const KILL_CLIENT: u8 = 113;
+    pub const QUERY_EXTENSION: u8 = 98;
+    pub const LIST_EXTENSIONS: u8 = 99;
+    pub const GET_KEYBOARD_MAPPING: u8 = 101;
+    pub const GET_POINTER_MAPPING: u8 = 117;
+    pub const GET_MODIFIER_MAPPING: u8 = 119;
+}
+
+/// Names of the core requests, indexed by major opcode minus one.
+const REQUEST_NAMES: [&str; 119] = [
+    "CreateWindow",
+    "ChangeWindowAttributes",
+    "GetWindowAttributes",
+    "DestroyWindow",
+    "DestroySubwindows",
+    "ChangeSaveSet",
+    "ReparentWindow",
+    "MapWindow",
+    "MapSubwindows",
+    "UnmapWindow",
+    "UnmapSubwindows",
+    "ConfigureWindow",
+    "CirculateWindow",
+    "GetGeometry",
+    "QueryTree",
+    "InternAtom",
+    "GetAtomName",
+    "ChangeProperty",
+    "DeleteProperty",
+    "GetProperty",
+    "ListProperties",
+    "SetSelectionOwner",
+    "GetSelectionOwner",
+    "ConvertSelection",
+    "SendEvent",
+    "GrabPointer",
+    "UngrabPointer",
+    "GrabButton",
+    "UngrabButton",
+    "ChangeActivePointerGrab",
+    "GrabKeyboard",
+    "UngrabKeyboard",
+    "GrabKey",
+    "UngrabKey",
+    "AllowEvents",
+    "GrabServer",
+    "UngrabServer",
+    "QueryPointer",
+    "GetMotionEvents",
+    "TranslateCoordinates",
+    "WarpPointer",
+    "SetInputFocus",
+    "GetInputFocus",
+    "QueryKeymap",
+    "OpenFont",
+    "CloseFont",
+    "QueryFont",
+    "QueryTextExtents",
+    "ListFonts",
+    "ListFontsWithInfo",
+    "SetFontPath",
+    "GetFontPath",
+    "CreatePixmap",
+    "FreePixmap",
+    "CreateGC",
+    "ChangeGC",
+    "CopyGC",
+    "SetDashes",
+    "SetClipRectangles",
+    "FreeGC",
+    "ClearArea",
+    "CopyArea",
+    "CopyPlane",
+    "PolyPoint",
+    "PolyLine",
+    "PolySegment",
+    "PolyRectangle",
+    "PolyArc",
+    "FillPoly",
+    "PolyFillRectangle",
+    "PolyFillArc",
+    "PutImage",
+    "GetImage",
+    "PolyText8",
+    "PolyText16",
+    "ImageText8",
+    "ImageText16",
+    "CreateColormap",
+    "FreeColormap",
+    "CopyColormapAndFree",
+    "InstallColormap",
+    "UninstallColormap",
+    "ListInstalledColormaps",
+    "AllocColor",
+    "AllocNamedColor",
+    "AllocColorCells",
+    "AllocColorPlanes",
+    "FreeColors",
+    "StoreColors",
+    "StoreNamedColor",
+    "QueryColors",
+    "LookupColor",
+    "CreateCursor",
+    "CreateGlyphCursor",
+    "FreeCursor",
+    "RecolorCursor",
+    "QueryBestSize",
+    "QueryExtension",
+    "ListExtensions",
+    "ChangeKeyboardMapping",
+    "GetKeyboardMapping",
+    "ChangeKeyboardControl",
+    "GetKeyboardControl",
+    "Bell",
+    "ChangePointerControl",
+    "GetPointerControl",
+    "SetScreenSaver",
+    "GetScreenSaver",
+    "ChangeHosts",
+    "ListHosts",
+    "SetAccessControl",
+    "SetCloseDownMode",
+    "KillClient",
+    "RotateProperties",
+    "ForceScreenSaver",
+    "SetPointerMapping",
+    "GetPointerMapping",
+    "SetModifierMapping",
+    "GetModifierMapping",
+];
+
+/// Returns the name of a core request, e.g. `ConfigureWindow` for 12.
+pub fn request_name(major_opcode: u8) -> Option<&'static str> {
+    match major_opcode {
+        127 => Some("NoOperation"),
+        _ => REQUEST_NAMES
+            .get(usize::from(major_opcode).checked_sub(1)?)
+            .copied(),
+    }
+}
+
+/// Helps building the bytes of a request.
+///
+/// Every request starts with a 4 byte header:
+///   1 byte     Major opcode
+///   1 byte     Request specific data (the minor opcode for extensions)
+///   2 bytes    Length of the request in 4-byte units, including the header
+/// followed by the request body, padded to a 4-byte boundary.
+pub struct RequestBuilder {
+    buf: Vec<u8>,
+}
+
+impl RequestBuilder {
+    /// Starts a request with the given major opcode and the request specific header byte.
+    pub fn new(opcode: u8, data: u8) -> Self {
+        Self {
+            buf: vec![opcode, data, 0, 0],
+        }
+    }
+
+    /// Appends a value to the request body.
+    pub fn put<T: Serialize>(&mut self, value: T) -> &mut Self {
+        value.serialize_into(&mut self.buf);
+        self
+    }
+
+    /// Appends raw bytes to the request body.
+    pub fn put_bytes(&mut self, bytes: &[u8]) -> &mut Self {
+        self.buf.extend_from_slice(bytes);
+        self
+    }
+
+    /// Appends `n` unused bytes to the request body.
+    pub fn pad(&mut self, n: usize) -> &mut Self {
+        self.buf.resize(self.buf.len() + n, 0);
+        self
+    }
+
+    /// Pads the request to a 4-byte boundary, fills in the length field and
+    /// returns the bytes of the request.
+    ///
+    /// A request of more than 65535 units does not fit the length field. It is
+    /// given the long length of BIG-REQUESTS instead: a length field of 0,
+    /// followed by the 4-byte length, which counts itself. The connections enable
+    /// the extension before sending such a request, and fail with
+    /// [SerializeError::TooLong](crate::errors::SerializeError::TooLong) if the
+    /// server does not support it or the request is still too long.
+    pub fn finish(&mut self) -> Vec<u8> {
+        let pad = padding(self.buf.len(), 4);
+        self.pad(pad);
+        let length = self.buf.len() / 4;
+        match u16::try_from(length) {
+            Ok(length) => self.buf[2..4].copy_from_slice(&length.to_ne_bytes()),
+            Err(_) => {
+                let length = u32::try_from(length + 1).unwrap_or(u32::MAX);
+                self.buf.splice(4..4, length.to_ne_bytes());
+            }
+        }
+        std::mem::take(&mut self.buf)
+    }
+}
+
+/// Builds a request of at most [SmallRequest::CAPACITY] bytes on the stack, like
+/// [RequestBuilder] does on the heap.
+///
+/// The fixed-size core requests are serialized this way. The request dereferences
+/// to its bytes, so serializing it does not allocate. The blocking connection
+/// writes the slice straight to its socket, while the io_uring connection of
+/// `uring` copies it into its output buffer, written with its next submission.
+#[derive(Clone, Copy)]
+pub struct SmallRequest {
+    buf: [u8; SmallRequest::CAPACITY],
+    len: usize,
+}
+
+impl SmallRequest {
+    /// The size of the largest request that fits, in bytes.
+    pub const CAPACITY: usize = 32;
+
+    /// Starts a request with the given major opcode and the request specific header byte.
+    pub fn new(opcode: u8, data: u8) -> Self {
+        let mut buf = [0; Self::CAPACITY];
+        buf[0] = opcode;
+        buf[1] = data;
+        Self { buf, len: 4 }
+    }
+
+    /// Appends a value to the request body.
+    ///
+    /// Panics if the request would exceed [SmallRequest::CAPACITY].
+    pub fn put<T: Serialize>(&mut self, value: T) -> &mut Self {
+        value.serialize_into(self);
+        self
+    }
+
+    /// Appends `n` unused bytes to the request body.
+    ///
+    /// Panics if the request would exceed [SmallRequest::CAPACITY].
+    pub fn pad(&mut self, n: usize) -> &mut Self {
+        self.grow(n);
+        self
+    }
+
+    /// Pads the request to a 4-byte boundary, fills in the length field and
+    /// returns the request.
+    pub fn finish(&mut self) -> SmallRequest {
+        self.pad(padding(self.len, 4));
+        let length = (self.len / 4) as u16;
+        self.buf[2..4].copy_from_slice(&length.to_ne_bytes());
+        *self
+    }
+
+    /// Extends the request by `n` bytes, zeroed, and returns them.
+    fn grow(&mut self, n: usize) -> &mut [u8] {
+        let start = self.len;
+        assert!(
+            start + n <= Self::CAPACITY,
+            "request larger than SmallRequest::CAPACITY"
+        );
+        self.len += n;
+        &mut self.buf[start..self.len]
+    }
+}
+
+impl Output for SmallRequest {
+    fn put_slice(&mut self, bytes: &[u8]) {
+        self.grow(bytes.len()).copy_from_slice(bytes);
+    }
+}
+
+impl Deref for SmallRequest {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.buf[..self.len]
+    }
+}
+
+impl fmt::Debug for SmallRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("SmallRequest").field(&&self[..]).finish()
+    }
+}
+
+/// Attributes of a window. Only the attributes that are `Some` are sent to the server.
+#[derive(Debug, Clone, Default)]
+pub struct WindowAttributes {
+    pub background_pixmap: Option<XId>,
+    pub background_pixel: Option<u32>,
+    pub border_pixmap: Option<XId>,
+    pub border_pixel: Option<u32>,
+    pub bit_gravity: Option<u8>,
+    pub win_gravity: Option<u8>,
+    pub backing_store: Option<u8>,
+    pub backing_planes: Option<u32>,
+    pub backing_pixel: Option<u32>,
+    pub override_redirect: Option<bool>,
+    pub save_under: Option<bool>,
+    pub event_mask: Option<EventMask>,
+    pub do_not_propagate_mask: Option<EventMask>,
+    pub colormap: Option<ColorMap>,
+    pub cursor: Option<XId>,
+}
+
+impl WindowAttributes {
+    /// Appends the value-mask followed by the LISTofVALUE of the attributes.
+    pub fn serialize_into(&self, request: &mut RequestBuilder) {
+        // The values are sent as 4-byte values, in the order of their bit in the value-mask.
+        let values: [Option<u32>; 15] = [
+            self.background_pixmap.map(|v| v.0),
+            self.background_pixel,
+            self.border_pixmap.map(|v| v.0),
+            self.border_pixel,
+            self.bit_gravity.map(u32::from),
+            self.win_gravity.map(u32::from),
+            self.backing_store.map(u32::from),
+            self.backing_planes,
+            self.backing_pixel,
+            self.override_redirect.map(u32::from),
+            self.save_under.map(u32::from),
+            self.event_mask.map(|v| v.0),
+            self.do_not_propagate_mask.map(|v| v.0),
+            self.colormap,
+            self.cursor.map(|v| v.0),
+        ];
+        let mut value_mask = 0u32;
+        for (bit, value) in values.iter().enumerate() {
+            if value.is_some() {
+                value_mask |= 1 << bit;
+            }
+        }
+        request.put(value_mask);
+        for value in values.into_iter().flatten() {
+            request.put(value);
+        }
+    }
+}
+
+/// Class of a window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WindowClass(pub u16);
+
+impl WindowClass {
+    pub const COPY_FROM_PARENT: Self = Self(0);
+    pub const INPUT_OUTPUT: Self = Self(1);
+    /// Windows that are never drawn, used for receiving input or events only.
+    pub const INPUT_ONLY: Self = Self(2);
+}
+
+/// Creates an unmapped window.
+#[derive(Debug, Clone)]
+pub struct CreateWindow {
+    /// Depth of the window. 0 copies the depth of the parent.
+    pub depth: u8,
+    /// Id for the window, see [crate::connection::Connection::generate_id].
+    pub window: Window,
+    pub parent: Window,
+    pub x: i16,
+    pub y: i16,
+    pub width: u16,
+    pub height: u16,
+    pub border_width: u16,
+    pub class: WindowClass,
+    /// Visual of the window. 0 copies the visual of the parent.
+    pub visual: VisualId,
+    pub attributes: WindowAttributes,
+}
+
+impl CreateWindow {
+    /// Converts the request to x11 raw bytes
+    //
+    //   1 byte     Opcode (1)
+    //   1 byte     Depth
+    //   2 bytes    Request length (8+n)
+    //   4 bytes    Window id
+    //   4 bytes    Parent
+    //   2 bytes    X
+    //   2 bytes    Y
+    //   2 bytes    Width
+    //   2 bytes    Height
+    //   2 bytes    Border width
+    //   2 bytes    Class
+    //   4 bytes    Visual
+    //   4 bytes    Value-mask
+    //   4n bytes   LISTofVALUE
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut request = RequestBuilder::new(opcode::CREATE_WINDOW, self.depth);
+        request
+            .put(self.window)
+            .put(self.parent)
+            .put(self.x)
+            .put(self.y)
+            .put(self.width)
+            .put(self.height)
+            .put(self.border_width)
+            .put(self.class.0)
+            .put(self.visual);
+        self.attributes.serialize_into(&mut request);
+        request.finish()
+    }
+}
+
+/// Destroys a window and its children.
+#[derive(Debug, Clone)]
+pub struct DestroyWindow {
+    pub window: Window,
+}
+
+impl DestroyWindow {
+    /// Converts the request to x11 raw bytes
+    //
+    //   1 byte     Opcode (4)
+    //   1 byte     Unused
+    //   2 bytes    Request length (2)
+    //   4 bytes    Window
+    pub fn serialize(&self) -> SmallRequest {
+        SmallRequest::new(opcode::DESTROY_WINDOW, 0)
+            .put(self.window)
+            .finish()
+    }
+}
+
+/// Maps a window, which makes it visible once its ancestors are mapped too.
+#[derive(Debug, Clone)]
+pub struct MapWindow {
+    pub window: Window,
+}
+
+impl MapWindow {
+    /// Converts the request to x11 raw bytes
+    //
+    //   1 byte     Opcode (8)
+    //   1 byte     Unused
+    //   2 bytes    Request length (2)
+    //   4 bytes    Window
+    pub fn serialize(&self) -> SmallRequest {
+        SmallRequest::new(opcode::MAP_WINDOW, 0)
+            .put(self.window)
+            .finish()
+    }
+}
+
+/// Unmaps a window, hiding it and its children.
+#[derive(Debug, Clone)]
+pub struct UnmapWindow {
+    pub window: Window,
+}
+
+impl UnmapWindow {
+    /// Converts the request to x11 raw bytes
+    //
+    //   1 byte     Opcode (10)
+    //   1 byte     Unused
+    //   2 bytes    Request length (2)
+    //   4 bytes    Window
+    pub fn serialize(&self) -> SmallRequest {
+        SmallRequest::new(opcode::UNMAP_WINDOW, 0)
+            .put(self.window)
+            .finish()
+    }
+}
+
+/// Changes the attributes of a window.
+#[derive(Debug, Clone)]
+pub struct ChangeWindowAttributes {
+    pub window: Window,
+    pub attributes: WindowAttributes,
+}
+
+impl ChangeWindowAttributes {
+    /// Converts the request to x11 raw bytes
+    //
+    //   1 byte     Opcode (2)
+    //   1 byte     Unused
+    //   2 bytes    Request length (3+n)
+    //   4 bytes    Window
+    //   4 bytes    Value-mask
+    //   4n bytes   LISTofVALUE
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut request = RequestBuilder::new(opcode::CHANGE_WINDOW_ATTRIBUTES, 0);
+        request.put(self.window);
+        self.attributes.serialize_into(&mut request);
+        request.finish()
+    }
+}
+
+/// Returns the keysyms of a range of keycodes.
+#[derive(Debug, Clone)]
+pub struct GetKeyboardMapping {
+    pub first_keycode: KeyCode,
+    pub count: u8,
+}
+
+impl GetKeyboardMapping {
+    /// Converts the request to x11 raw bytes
+    //
+    //   1 byte     Opcode (101)
+    //   1 byte     Unused
+    //   2 bytes    Request length (2)
+    //   1 byte     First keycode
+    //   1 byte     Count
+    //   2 bytes    Unused
+    pub fn serialize(&self) -> SmallRequest {
+        SmallRequest::new(opcode::GET_KEYBOARD_MAPPING, 0)
+            .put(self.first_keycode)
+            .put(self.count)
+            .finish()
+    }
+}
+
+/// Returns whether the server supports an extension, and its opcode, first event
+/// and first error codes.
+#[derive(Debug, Clone)]
+pub struct QueryExtension<'a> {
+    pub name: &'a [u8],
+}
+
+impl QueryExtension<'_> {
+    /// Converts the request to x11 raw bytes
+    //
+    //   1 byte     Opcode (98)
+    //   1 byte     Unused
+    //   2 bytes    Request length (2+(n+p)/4)
+    //   2 bytes    Length of name (n)
+    //   2 bytes    Unused
+    //   n bytes    Name
+    //   p bytes    Unused (p = pad(n))
+    pub fn serialize(&self) -> Vec<u8> {
+        RequestBuilder::new(opcode::QUERY_EXTENSION, 0)
+            .put(self.name.len() as u16)
+            .pad(2)
+            .put_bytes(self.name)
+            .finish()
+    }
+}
+
+/// Returns the names of the extensions the server supports.
+#[derive(Debug, Clone)]
+pub struct ListExtensions;
+
+impl ListExtensions {
+    /// Converts the request to x11 raw bytes
+    //
+    //   1 byte     Opcode (99)
+    //   1 byte     Unused
+    //   2 bytes    Request length (1)
+    pub fn serialize(&self) -> SmallRequest {
+        SmallRequest::new(opcode::LIST_EXTENSIONS, 0).finish()
+    }
+}
+
+/// Creates a pixmap of the given depth on the screen of `drawable`.
+#[derive(Debug, Clone)]
+pub struct CreatePixmap {
+    pub depth: u8,
+    /// Id for the pixmap, see [crate::connection::Connection::generate_id].
+    pub pixmap: XId,
+    pub drawable: XId,
+    pub width: u16,
+    pub height: u16,
+}
+
+impl CreatePixmap {
+    /// Converts the request to x11 raw bytes
+    //
+    //   1 byte     Opcode (53)
+    //   1 byte     Depth
+    //   2 bytes    Request length (4)
+    //   4 bytes    Pixmap id
+    //   4 bytes    Drawable
+    //   2 bytes    Width
+    //   2 bytes    Height
+    pub fn serialize(&self) -> SmallRequest {
+        SmallRequest::new(opcode::CREATE_PIXMAP, self.depth)
+            .put(self.pixmap)
+            .put(self.drawable)
+            .put(self.width)
+            .put(self.height)
+            .finish()
+    }
+}
+
+/// Frees a pixmap once no resource refers to it anymore.
+#[derive(Debug, Clone)]
+pub struct FreePixmap {
+    pub pixmap: XId,
+}
+
+impl FreePixmap {
+    /// Converts the request to x11 raw bytes
+    //
+    //   1 byte     Opcode (54)
+    //   1 byte     Unused
+    //   2 bytes    Request length (2)
+    //   4 bytes    Pixmap
+    pub fn serialize(&self) -> SmallRequest {
+        SmallRequest::new(opcode::FREE_PIXMAP, 0)
+            .put(self.pixmap)
+            .finish()
+    }
+}
+
+/// Opens a server font, e.g. `cursor` for the glyphs of the standard cursors.
+#[derive(Debug, Clone)]
+pub struct OpenFont<'a> {
+    /// Id for the font, see [crate::connection::Connection::generate_id].
+    pub font: XId,
+    pub name: &'a [u8],
+}
+
+impl OpenFont<'_> {
+    /// Converts the request to x11 raw bytes
+    //
+    //   1 byte     Opcode (45)
+    //   1 byte     Unused
+    //   2 bytes    Request length (3+(n+p)/4)
+    //   4 bytes    Font id
+    //   2 bytes    Length of name (n)
+    //   2 bytes    Unused
+    //   n bytes    Name
+    //   p bytes    Unused (p = pad(n))
+    pub fn serialize(&self) -> Vec<u8> {
+        RequestBuilder::new(opcode::OPEN_FONT, 0)
+            .put(self.font)
+            .put(self.name.len() as u16)
+            .pad(2)
+            .put_bytes(self.name)
+            .finish()
+    }
+}
+
+/// Closes a font once no resource refers to it anymore.
+#[derive(Debug, Clone)]
+pub struct CloseFont {
+    pub font: XId,
+}
+
+impl CloseFont {
+    /// Converts the request to x11 raw bytes
+    //
+    //   1 byte     Opcode (46)
+    //   1 byte     Unused
+    //   2 bytes    Request length (2)
+    //   4 bytes    Font
+    pub fn serialize(&self) -> SmallRequest {
+        SmallRequest::new(opcode::CLOSE_FONT, 0)
+            .put(self.font)
+            .finish()
+    }
+}
+
+/// Glyphs of the `cursor` font, for [crate::connection::Connection::create_font_cursor].
+/// The glyph after each one is its mask.
+pub mod cursor_font {
+    pub const X_CURSOR: u16 = 0;
+    pub const ARROW: u16 = 2;
+    pub const CROSS: u16 = 30;
+    pub const CROSSHAIR: u16 = 34;
+    pub const FLEUR: u16 = 52;
+    pub const HAND1: u16 = 58;
+    pub const HAND2: u16 = 60;
+    pub const LEFT_PTR: u16 = 68;
+    pub const QUESTION_ARROW: u16 = 92;
+    pub const TCROSS: u16 = 130;
+    pub const WATCH: u16 = 150;
+    pub const XTERM: u16 = 152;
+}
+
+/// Creates a cursor from glyphs of fonts, usually the `cursor` font.
+#[derive(Debug, Clone)]
+pub struct CreateGlyphCursor {
+    /// Id for the cursor, see [crate::connection::Connection::generate_id].
+    pub cursor: XId,
+    pub source_font: XId,
+    /// The font of the mask, or None for a cursor without mask.
+    pub mask_font: XId,
+    pub source_char: u16,
+    /// The glyph of the mask, by convention the one after the source glyph in
+    /// the `cursor` font.
+    pub mask_char: u16,
+    /// The 16-bit red, green and blue of the foreground.
+    pub foreground: [u16; 3],
+    pub background: [u16; 3],
+}
+
+impl CreateGlyphCursor {
+    /// Converts the request to x11 raw bytes
+    //
+    //   1 byte     Opcode (94)
+    //   1 byte     Unused
+    //   2 bytes    Request length (8)
+    //   4 bytes    Cursor id
+    //   4 bytes    Source font
+    //   4 bytes    Mask font
+    //   2 bytes    Source char
+    //   2 bytes    Mask char
+    //   2 bytes    Foreground red
+    //   2 bytes    Foreground green
+    //   2 bytes    Foreground blue
+    //   2 bytes    Background red
+    //   2 bytes    Background green
+    //   2 bytes    Background blue
+    pub fn serialize(&self) -> SmallRequest {
+        let mut request = SmallRequest::new(opcode::CREATE_GLYPH_CURSOR, 0);
+        request
+            .put(self.cursor)
+            .put(self.source_font)
+            .put(self.mask_font)
+            .put(self.source_char)
+            .put(self.mask_char);
+        for value in self.foreground.into_iter().chain(self.background) {
+            request.put(value);
+        }
+        request.finish()
+    }
+}
+
+/// Frees a cursor once no window uses it anymore.
+#[derive(Debug, Clone)]
+pub struct FreeCursor {
+    pub cursor: XId,
+}
+
+impl FreeCursor {
+    /// Converts the request to x11 raw bytes
+    //
+    //   1 byte     Opcode (95)
+    //   1 byte     Unused
+    //   2 bytes    Request length (2)
+    //   4 bytes    Cursor
+    pub fn serialize(&self) -> SmallRequest {
+        SmallRequest::new(opcode::FREE_CURSOR, 0)
+            .put(self.cursor)
+            .finish()
+    }
+}
+
+/// Whether the entries of a new colormap are allocated to the client.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ColormapAlloc(pub u8);
+
+impl ColormapAlloc {
+    /// No entry is allocated, as needed for static visuals such as TrueColor.
+    pub const NONE: Self = Self(0);
+    pub const ALL: Self = Self(1);
+}
+
+/// Creates a colormap for a visual of the screen of `window`.
+#[derive(Debug, Clone)]
+pub struct CreateColormap {
+    pub alloc: ColormapAlloc,
+    /// Id for the colormap, see [crate::connection::Connection::generate_id].
+    pub colormap: ColorMap,
+    pub window: Window,
+    pub visual: VisualId,
+}
+
+impl CreateColormap {
+    /// Converts the request to x11 raw bytes
+    //
+    //   1 byte     Opcode (78)
+    //   1 byte     Alloc
+    //   2 bytes    Request length (4)
+    //   4 bytes    Colormap id
+    //   4 bytes    Window
+    //   4 bytes    Visual
+    pub fn serialize(&self) -> SmallRequest {
+        SmallRequest::new(opcode::CREATE_COLORMAP, self.alloc.0)
+            .put(self.colormap)
+            .put(self.window)
+            .put(self.visual)
+            .finish()
+    }
+}
+
+/// Frees a colormap. Windows using it keep it until they are destroyed.
+#[derive(Debug, Clone)]
+pub struct FreeColormap {
+    pub colormap: ColorMap,
+}
+
+impl FreeColormap {
+    /// Converts the request to x11 raw bytes
+    //
+    //   1 byte     Opcode (79)
+    //   1 byte     Unused
+    //   2 bytes    Request length (2)
+    //   4 bytes    Colormap
+    pub fn serialize(&self) -> SmallRequest {
+        SmallRequest::new(opcode::FREE_COLORMAP, 0)
+            .put(self.colormap)
+            .finish()
+    }
+}
+
+/// Values of a graphics context. Only the values that are `Some` are sent to the server.
+#[derive(Debug, Clone, Default)]
+pub struct GcValues {
+    pub function: Option<u8>,
+    pub plane_mask: Option<u32>,
+    pub foreground: Option<u32>,
+    pub background: Option<u32>,
+    pub line_width: Option<u16>,
+    pub line_style: Option<u8>,
+    pub cap_style: Option<u8>,
+    pub join_style: Option<u8>,
+    pub fill_style: Option<u8>,
+    pub fill_rule: Option<u8>,
+    pub tile: Option<XId>,
+    pub stipple: Option<XId>,
+    pub tile_stipple_x_origin: Option<i16>,
+    pub tile_stipple_y_origin: Option<i16>,
+    pub font: Option<XId>,
+    /// 0 clips drawing by the children of the drawable, 1 draws over them.
+    pub subwindow_mode: Option<u8>,
+    pub graphics_exposures: Option<bool>,
+    pub clip_x_origin: Option<i16>,
+    pub clip_y_origin: Option<i16>,
+    pub clip_mask: Option<XId>,
+    pub dash_offset: Option<u16>,
+    pub dashes: Option<u8>,
+    pub arc_mode: Option<u8>,
+}
+
+impl GcValues {
+    /// Appends the value-mask followed by the LISTofVALUE of the values.
+    pub fn serialize_into(&self, request: &mut RequestBuilder) {
+        // The values are sent as 4-byte values, in the order of their bit in the value-mask.
+        let values: [Option<u32>; 23] = [
+            self.function.map(u32::from),
+            self.plane_mask,
+            self.foreground,
+            self.background,
+            self.line_width.map(u32::from),
+            self.line_style.map(u32::from),
+            self.cap_style.map(u32::from),
+            self.join_style.map(u32::from),
+            self.fill_style.map(u32::from),
+            self.fill_rule.map(u32::from),
+            self.tile.map(|v| v.0),
+            self.stipple.map(|v| v.0),
+            self.tile_stipple_x_origin.map(|v| v as u32),
+            self.tile_stipple_y_origin.map(|v| v as u32),
+            self.font.map(|v| v.0),
+            self.subwindow_mode.map(u32::from),
+            self.graphics_exposures.map(u32::from),
+            self.clip_x_origin.map(|v| v as u32),
+            self.clip_y_origin.map(|v| v as u32),
+            self.clip_mask.map(|v| v.0),
+            self.dash_offset.map(u32::from),
+            self.dashes.map(u32::from),
+            self.arc_mode.map(u32::from),
+        ];
+        let mut value_mask = 0u32;
+        for (bit, value) in values.iter().enumerate() {
+            if value.is_some() {
+                value_mask |= 1 << bit;
+            }
+        }
+        request.put(value_mask);
+        for value in values.into_iter().flatten() {
+            request.put(value);
+        }
+    }
+}
+
+/// Creates a graphics context for drawing on drawables of the screen and depth
+/// of `drawable`.
+#[derive(Debug, Clone)]
+pub struct CreateGc {
+    /// Id for the graphics context, see [crate::connection::Connection::generate_id].
+    pub gc: XId,
+    pub drawable: XId,
+    pub values: GcValues,
+}
+
+impl CreateGc {
+    /// Converts the request to x11 raw bytes
+    //
+    //   1 byte     Opcode (55)
+    //   1 byte     Unused
+    //   2 bytes    Request length (4+n)
+    //   4 bytes    Graphics context id
+    //   4 bytes    Drawable
+    //   4 bytes    Value-mask
+    //   4n bytes   LISTofVALUE
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut request = RequestBuilder::new(opcode::CREATE_GC, 0);
+        request.put(self.gc).put(self.drawable);
+        self.values.serialize_into(&mut request);
+        request.finish()
+    }
+}
+
+/// Frees a graphics context.
+#[derive(Debug, Clone)]
+pub struct FreeGc {
+    pub gc: XId,
+}
+
+impl FreeGc {
+    /// Converts the request to x11 raw bytes
+    //
+    //   1 byte     Opcode (60)
+    //   1 byte     Unused
+    //   2 bytes    Request length (2)
+    //   4 bytes    Graphics context
+    pub fn serialize(&self) -> SmallRequest {
+        SmallRequest::new(opcode::FREE_GC, 0).put(self.gc).finish()
+    }
+}
+
+/// Paints an area of a window with its background. A width or height of 0
+/// extends the area to the edge of the window.
+#[derive(Debug, Clone)]
+pub struct ClearArea {
+    /// Whether Expose events are generated for the visible parts of the area.
+    pub exposures: bool,
+    pub window: Window,
+    pub x: i16,
+    pub y: i16,
+    pub width: u16,
+    pub height: u16,
+}
+
+impl ClearArea {
+    /// Converts the request to x11 raw bytes
+    //
+    //   1 byte     Opcode (61)
+    //   1 byte     Exposures
+    //   2 bytes    Request length (4)
+    //   4 bytes    Window
+    //   2 bytes    X
+    //   2 bytes    Y
+    //   2 bytes    Width
+    //   2 bytes    Height
+    pub fn serialize(&self) -> SmallRequest {
+        SmallRequest::new(opcode::CLEAR_AREA, self.exposures.into())
+            .put(self.window)
+            .put(self.x)
+            .put(self.y)
+            .put(self.width)
+            .put(self.height)
+            .finish()
+    }
+}
+
+/// What happens to the resources of a client when its connection closes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CloseDownMode(pub u8);
+
+impl CloseDownMode {
+    pub const DESTROY: Self = Self(0);
+    /// The resources live until the server resets, or a client kills them with KillClient.
+    pub const RETAIN_PERMANENT: Self = Self(1);
+    pub const RETAIN_TEMPORARY: Self = Self(2);
+}
+
+/// Sets what happens to the resources of this client when its connection closes.
+#[derive(Debug, Clone)]
+pub struct SetCloseDownMode {
+    pub mode: CloseDownMode,
+}
+
+impl SetCloseDownMode {
+    /// Converts the request to x11 raw bytes
+    //
+    //   1 byte     Opcode (112)
+    //   1 byte     Mode
+    //   2 bytes    Request length (1)
+    pub fn serialize(&self) -> SmallRequest {
+        SmallRequest::new(opcode::SET_CLOSE_DOWN_MODE, self.mode.0).finish()
+    }
+}
+
+/// The resource of [KillClient] that kills every client whose resources were
+/// retained with [CloseDownMode::RETAIN_TEMPORARY].
+pub const ALL_TEMPORARY: u32 = 0;
+
+/// Closes the connection of the client that created a resource, or destroys
+/// its resources if the connection is already closed.
+#[derive(Debug, Clone)]
+pub struct KillClient {
+    pub resource: u32,
+}
+
+impl KillClient {
+    /// Converts the request to x11 raw bytes
+    //
+    //   1 byte     Opcode (113)
+    //   1 byte     Unused
+    //   2 bytes    Request length (2)
+    //   4 bytes    Resource
+    pub fn serialize(&self) -> SmallRequest {
+        SmallRequest::new(opcode::KILL_CLIENT, 0)
+            .put(self.resource)
+            .finish()
+    }
+}
+
+/// Returns the attributes of a window, e.g. its class and whether it is mapped.
+#[derive(Debug, Clone)]
+pub struct GetWindowAttributes {
+    pub window: Window,
+}
+
+impl GetWindowAttributes {
+    /// Converts the request to x11 raw bytes
+    //
+    //   1 byte     Opcode (3)
+    //   1 byte     Unused
+    //   2 bytes    Request length (2)
+    //   4 bytes    Window
+    pub fn serialize(&self) -> SmallRequest {
+        SmallRequest::new(opcode::GET_WINDOW_ATTRIBUTES, 0)
+            .put(self.window)
+            .finish()
+    }
+}
+
+/// Returns the position, size and depth of a window or pixmap.
+#[derive(Debug, Clone)]
+pub struct GetGeometry {
+    pub drawable: XId,
+}
+
+impl GetGeometry {
+    /// Converts the request to x11 raw bytes
+    //
+    //   1 byte     Opcode (14)
+    //   1 byte     Unused
+    //   2 bytes    Request length (2)
+    //   4 bytes    Drawable
+    pub fn serialize(&self) -> SmallRequest {
+        SmallRequest::new(opcode::GET_GEOMETRY, 0)
+            .put(self.drawable)
+            .finish()
+    }
+}
+
+/// Returns the root, the parent and the children of a window.
+#[derive(Debug, Clone)]
+pub struct QueryTree {
+    pub window: Window,
+}
+
+impl QueryTree {
+    /// Converts the request to x11 raw bytes
+    //
+    //   1 byte     Opcode (15)
+    //   1 byte     Unused
+    //   2 bytes    Request length (2)
+    //   4 bytes    Window
+    pub fn serialize(&self) -> SmallRequest {
+        SmallRequest::new(opcode::QUERY_TREE, 0)
+            .put(self.window)
+            .finish()
+    }
+}
+
+/// Converts a position relative to a window into one relative to another, and
+/// returns the child of the destination containing it.
+#[derive(Debug, Clone)]
+pub struct TranslateCoordinates {
+    pub src_window: Window,
+    pub dst_window: Window,
+    pub src_x: i16,
+    pub src_y: i16,
+}
+
+impl TranslateCoordinates {
+    /// Converts the request to x11 raw bytes
+    //
+    //   1 byte     Opcode (40)
+    //   1 byte     Unused
+    //   2 bytes    Request length (4)
+    //   4 bytes    Source window
+    //   4 bytes    Destination window
+    //   2 bytes    Source x
+    //   2 bytes    Source y
+    pub fn serialize(&self) -> SmallRequest {
+        SmallRequest::new(opcode::TRANSLATE_COORDINATES, 0)
+            .put(self.src_window)
+            .put(self.dst_window)
+            .put(self.src_x)
+            .put(self.src_y)
+            .finish()
+    }
+}
+
+/// The button of GrabButton and UngrabButton matching any button.
+pub const ANY_BUTTON: u8 = 0;
+
+/// The modifiers of the grab requests matching any combination of modifiers.
+pub const ANY_MODIFIER: u16 = 0x8000;
+
+/// How the events of a device are processed while it is grabbed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GrabMode(pub u8);
+
+impl GrabMode {
+    /// Events are frozen until the grabbing client calls AllowEvents.
+    pub const SYNC: Self = Self(0);
+    pub const ASYNC: Self = Self(1);
+}
+
+/// Actively grabs the pointer, so that its events are reported to this client
+/// only.
+#[derive(Debug, Clone)]
+pub struct GrabPointer {
+    pub owner_events: bool,
+    pub grab_window: Window,
+    /// The pointer events reported during the grab.
+    pub event_mask: EventMask,
+    pub pointer_mode: GrabMode,
+    pub keyboard_mode: GrabMode,
+    /// The window to confine the pointer to, or None.
+    pub confine_to: Window,
+    /// The cursor during the grab, or None.
+    pub cursor: XId,
+    /// The time of the event that caused the grab, or [crate::protocol::CURRENT_TIME].
+    pub time: Timestamp,
+}
+
+impl GrabPointer {
+    /// Converts the request to x11 raw bytes
+    //
+    //   1 byte     Opcode (26)
+    //   1 byte     Owner events
+    //   2 bytes    Request length (6)
+    //   4 bytes    Grab window
+    //   2 bytes    Event mask
+    //   1 byte     Pointer mode
+    //   1 byte     Keyboard mode
+    //   4 bytes    Confine to
+    //   4 bytes    Cursor
+    //   4 bytes    Time
+    pub fn serialize(&self) -> SmallRequest {
+        SmallRequest::new(opcode::GRAB_POINTER, u8::from(self.owner_events))
+            .put(self.grab_window)
+            .put(self.event_mask.0 as u16)
+            .put(self.pointer_mode.0)
+            .put(self.keyboard_mode.0)
+            .put(self.confine_to)
+            .put(self.cursor)
+            .put(self.time)
+            .finish()
+    }
+}
+
+/// Releases an active grab of the pointer.
+#[derive(Debug, Clone)]
+pub struct UngrabPointer {
+    pub time: Timestamp,
+}
+
+impl UngrabPointer {
+    /// Converts the request to x11 raw bytes
+    //
+    //   1 byte     Opcode (27)
+    //   1 byte     Unused
+    //   2 bytes    Request length (2)
+    //   4 bytes    Time
+    pub fn serialize(&self) -> SmallRequest {
+        SmallRequest::new(opcode::UNGRAB_POINTER, 0)
+            .put(self.time)
+            .finish()
+    }
+}
+
+/// Passively grabs the pointer when a button is pressed over a window.
+#[derive(Debug, Clone)]
+pub struct GrabButton {
+    pub owner_events: bool,
+    pub grab_window: Window,
+    /// The pointer events reported during the grab.
+    pub event_mask: EventMask,
+    pub pointer_mode: GrabMode,
+    pub keyboard_mode: GrabMode,
+    /// The window to confine the pointer to, or None.
+    pub confine_to: Window,
+    /// The cursor during the grab, or None.
+    pub cursor: XId,
+    /// The button, or [ANY_BUTTON].
+    pub button: u8,
+    /// The modifiers, or [ANY_MODIFIER].
+    pub modifiers: u16,
+}
+
+impl GrabButton {
+    /// Converts the request to x11 raw bytes
+    //
+    //   1 byte     Opcode (28)
+    //   1 byte     Owner events
+    //   2 bytes    Request length (6)
+    //   4 bytes    Grab window
+    //   2 bytes    Event mask
+    //   1 byte     Pointer mode
+    //   1 byte     Keyboard mode
+    //   4 bytes    Confine to
+    //   4 bytes    Cursor
+    //   1 byte     Button
+    //   1 byte     Unused
+    //   2 bytes    Modifiers
+    pub fn serialize(&self) -> SmallRequest {
+        SmallRequest::new(opcode::GRAB_BUTTON, u8::from(self.owner_events))
+            .put(self.grab_window)
+            .put(self.event_mask.0 as u16)
+            .put(self.pointer_mode.0)
+            .put(self.keyboard_mode.0)
+            .put(self.confine_to)
+            .put(self.cursor)
+            .put(self.button)
+            .pad(1)
+            .put(self.modifiers)
+            .finish()
+    }
+}
+
+/// Releases a passive grab of GrabButton.
+#[derive(Debug, Clone)]
+pub struct UngrabButton {
+    pub button: u8,
+    pub grab_window: Window,
+    pub modifiers: u16,
+}
+
+impl UngrabButton {
+    /// Converts the request to x11 raw bytes
+    //
+    //   1 byte     Opcode (29)
+    //   1 byte     Button
+    //   2 bytes    Request length (3)
+    //   4 bytes    Grab window
+    //   2 bytes    Modifiers
+    //   2 bytes    Unused
+    pub fn serialize(&self) -> SmallRequest {
+        SmallRequest::new(opcode::UNGRAB_BUTTON, self.button)
+            .put(self.grab_window)
+            .put(self.modifiers)
+            .pad(2)
+            .finish()
+    }
+}
+
+/// Actively grabs the keyboard, so that its events are reported to this client
+/// only.
+#[derive(Debug, Clone)]
+pub struct GrabKeyboard {
+    pub owner_events: bool,
+    pub grab_window: Window,
+    /// The time of the event that caused the grab, or [crate::protocol::CURRENT_TIME].
+    pub time: Timestamp,
+    pub pointer_mode: GrabMode,
+    pub keyboard_mode: GrabMode,
+}
+
+impl GrabKeyboard {
+    /// Converts the request to x11 raw bytes
+    //
+    //   1 byte     Opcode (31)
+    //   1 byte     Owner events
+    //   2 bytes    Request length (4)
+    //   4 bytes    Grab window
+    //   4 bytes    Time
+    //   1 byte     Pointer mode
+    //   1 byte     Keyboard mode
+    //   2 bytes    Unused
+    pub fn serialize(&self) -> SmallRequest {
+        SmallRequest::new(opcode::GRAB_KEYBOARD, u8::from(self.owner_events))
+            .put(self.grab_window)
+            .put(self.time)
+            .put(self.pointer_mode.0)
+            .put(self.keyboard_mode.0)
+            .pad(2)
+            .finish()
+    }
+}
+
+/// Releases an active grab of the keyboard.
+#[derive(Debug, Clone)]
+pub struct UngrabKeyboard {
+    pub time: Timestamp,
+}
+
+impl UngrabKeyboard {
+    /// Converts the request to x11 raw bytes
+    //
+    //   1 byte     Opcode (32)
+    //   1 byte     Unused
+    //   2 bytes    Request length (2)
+    //   4 bytes    Time
+    pub fn serialize(&self) -> SmallRequest {
+        SmallRequest::new(opcode::UNGRAB_KEYBOARD, 0)
+            .put(self.time)
+            .finish()
+    }
+}
+
+/// What AllowEvents does with the events frozen by a synchronous grab.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AllowMode(pub u8);
+
+impl AllowMode {
+    pub const ASYNC_POINTER: Self = Self(0);
+    pub const SYNC_POINTER: Self = Self(1);
+    /// Releases the pointer grab and processes the event again as if the passive
+    /// grab that caused it did not exist.
+    pub const REPLAY_POINTER: Self = Self(2);
+    pub const ASYNC_KEYBOARD: Self = Self(3);
+    pub const SYNC_KEYBOARD: Self = Self(4);
+    pub const REPLAY_KEYBOARD: Self = Self(5);
+    pub const ASYNC_BOTH: Self = Self(6);
+    pub const SYNC_BOTH: Self = Self(7);
+}
+
+/// Releases the events frozen by a synchronous grab.
+#[derive(Debug, Clone)]
+pub struct AllowEvents {
+    pub mode: AllowMode,
+    pub time: Timestamp,
+}
+
+impl AllowEvents {
+    /// Converts the request to x11 raw bytes
+    //
+    //   1 byte     Opcode (35)
+    //   1 byte     Mode
+    //   2 bytes    Request length (2)
+    //   4 bytes    Time
+    pub fn serialize(&self) -> SmallRequest {
+        SmallRequest::new(opcode::ALLOW_EVENTS, self.mode.0)
+            .put(self.time)
+            .finish()
+    }
+}
+
+/// Returns the position of the pointer and the state of the modifiers and
+/// buttons.
+#[derive(Debug, Clone)]
+pub struct QueryPointer {
+    pub window: Window,
+}
+
+impl QueryPointer {
+    /// Converts the request to x11 raw bytes
+    //
+    //   1 byte     Opcode (38)
+    //   1 byte     Unused
+    //   2 bytes    Request length (2)
+    //   4 bytes    Window
+    pub fn serialize(&self) -> SmallRequest {
+        SmallRequest::new(opcode::QUERY_POINTER, 0)
+            .put(self.window)
+            .finish()
+    }
+}
+
+/// Returns the window that has the input focus.
+#[derive(Debug, Clone)]
+pub struct GetInputFocus;
+
+impl GetInputFocus {
+    /// Converts the request to x11 raw bytes
+    //
+    //   1 byte     Opcode (43)
+    //   1 byte     Unused
+    //   2 bytes    Request length (1)
+    pub fn serialize(&self) -> SmallRequest {
+        SmallRequest::new(opcode::GET_INPUT_FOCUS, 0).finish()
+    }
+}
+
+/// Returns the keycodes bound to the modifiers.
+#[derive(Debug, Clone)]
+pub struct GetModifierMapping;
+
+impl GetModifierMapping {
+    /// Converts the request to x11 raw bytes
+    //
+    //   1 byte     Opcode (119)
+    //   1 byte     Unused
+    //   2 bytes    Request length (1)
+    pub fn serialize(&self) -> SmallRequest {
+        SmallRequest::new(opcode::GET_MODIFIER_MAPPING, 0).finish()
+    }
+}
+
+/// Returns the mapping of the pointer buttons.
+#[derive(Debug, Clone)]
+pub struct GetPointerMapping;
+
+impl GetPointerMapping {
+    /// Converts the request to x11 raw bytes
+    //
+    //   1 byte     Opcode (117)
+    //   1 byte     Unused
+    //   2 bytes    Request length (1)
+    pub fn serialize(&self) -> SmallRequest {
+        SmallRequest::new(opcode::GET_POINTER_MAPPING, 0).finish()
+    }
+}
+
+/// Sends an event to a window.
+#[derive(Debug, Clone)]
+pub struct SendEvent {
+    pub propagate: bool,
+    pub destination: Window,
+    pub event_mask: EventMask,
+    /// The 32 bytes of the event.
+    pub event: [u8; 32],
+}
+
+impl SendEvent {
+    /// Converts the request to x11 raw bytes
+    //
+    //   1 byte     Opcode (25)
+    //   1 byte     Propagate
+    //   2 bytes    Request length (11)
+    //   4 bytes    Destination
+    //   4 bytes    Event mask
+    //   32 bytes   Event
+    pub fn serialize(&self) -> Vec<u8> {
+        RequestBuilder::new(opcode::SEND_EVENT, u8::from(self.propagate))
+            .put(self.destination)
+            .put(self.event_mask.0)
+            .put_bytes(&self.event)
+            .finish()
+    }
+}
+
+/// Returns the atom of a name, creating it unless `only_if_exists` is set.
+#[derive(Debug, Clone)]
+pub struct InternAtom<'a> {
+    pub only_if_exists: bool,
+    pub name: &'a [u8],
+}
+
+impl InternAtom<'_> {
+    /// Converts the request to x11 raw bytes
+    //
+    //   1 byte     Opcode (16)
+    //   1 byte     Only if exists
+    //   2 bytes    Request length (2+(n+p)/4)
+    //   2 bytes    Length of name (n)
+    //   2 bytes    Unused
+    //   n bytes    Name
+    //   p bytes    Unused (p = pad(n))
+    pub fn serialize(&self) -> Vec<u8> {
+        RequestBuilder::new(opcode::INTERN_ATOM, u8::from(self.only_if_exists))
+            .put(self.name.len() as u16)
+            .pad(2)
+            .put_bytes(self.name)
+            .finish()
+    }
+}
+
+/// Returns the name of an atom.
+#[derive(Debug, Clone)]
+pub struct GetAtomName {
+    pub atom: Atom,
+}
+
+impl GetAtomName {
+    /// Converts the request to x11 raw bytes
+    //
+    //   1 byte     Opcode (17)
+    //   1 byte     Unused
+    //   2 bytes    Request length (2)
+    //   4 bytes    Atom
+    pub fn serialize(&self) -> SmallRequest {
+        SmallRequest::new(opcode::GET_ATOM_NAME, 0)
+            .put(self.atom)
+            .finish()
+    }
+}
+
+/// How ChangeProperty combines the new data with the current value of the property.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PropMode(pub u8);
+
+impl PropMode {
+    pub const REPLACE: Self = Self(0);
+    pub const PREPEND: Self = Self(1);
+    pub const APPEND: Self = Self(2);
+}
+
+/// Removes a property from a window.
+#[derive(Debug, Clone)]
+pub struct DeleteProperty {
+    pub window: Window,
+    pub property: Atom,
+}
+
+impl DeleteProperty {
+    /// Converts the request to x11 raw bytes
+    //
+    //   1 byte     Opcode (19)
+    //   1 byte     Unused
+    //   2 bytes    Request length (3)
+    //   4 bytes    Window
+    //   4 bytes    Property
+    pub fn serialize(&self) -> SmallRequest {
+        SmallRequest::new(opcode::DELETE_PROPERTY, 0)
+            .put(self.window)
+            .put(self.property)
+            .finish()
+    }
+}
+
+/// Returns the properties of a window.
+#[derive(Debug, Clone)]
+pub struct ListProperties {
+    pub window: Window,
+}
+
+impl ListProperties {
+    /// Converts the request to x11 raw bytes
+    //
+    //   1 byte     Opcode (21)
+    //   1 byte     Unused
+    //   2 bytes    Request length (2)
+    //   4 bytes    Window
+    pub fn serialize(&self) -> SmallRequest {
+        SmallRequest::new(opcode::LIST_PROPERTIES, 0)
+            .put(self.window)
+            .finish()
+    }
+}
+
+/// Returns part of the value of a property of a window.
+#[derive(Debug, Clone)]
+pub struct GetProperty {
+    /// Whether to delete the property once it has been read entirely.
+    pub delete: bool,
+    pub window: Window,
+    pub property: Atom,
+    /// The expected type, or [crate::protocol::atom::ANY].
+    pub type_: Atom,
+    /// Where to start reading, in 4-byte units.
+    pub long_offset: u32,
+    /// How much to read at most, in 4-byte units.
+    pub long_length: u32,
+}
+
+impl GetProperty {
+    /// Converts the request to x11 raw bytes
+    //
+    //   1 byte     Opcode (20)
+    //   1 byte     Delete
+    //   2 bytes    Request length (6)
+    //   4 bytes    Window
+    //   4 bytes    Property
+    //   4 bytes    Type
+    //   4 bytes    Long offset
+    //   4 bytes    Long length
+    pub fn serialize(&self) -> SmallRequest {
+        SmallRequest::new(opcode::GET_PROPERTY, u8::from(self.delete))
+            .put(self.window)
+            .put(self.property)
+            .put(self.type_)
+            .put(self.long_offset)
+            .put(self.long_length)
+            .finish()
+    }
+}
+
+/// Makes a window the owner of a selection.
+#[derive(Debug, Clone)]
+pub struct SetSelectionOwner {
+    /// The new owner, or [crate::protocol::atom::NONE] to disown the selection.
+    pub owner: Window,
+    pub selection: Atom,
+    pub time: Timestamp,
+}
+
+impl SetSelectionOwner {
+    /// Converts the request to x11 raw bytes
+    //
+    //   1 byte     Opcode (22)
+    //   1 byte     Unused
+    //   2 bytes    Request length (4)
+    //   4 bytes    Owner
+    //   4 bytes    Selection
+    //   4 bytes    Time
+    pub fn serialize(&self) -> SmallRequest {
+        SmallRequest::new(opcode::SET_SELECTION_OWNER, 0)
+            .put(self.owner)
+            .put(self.selection)
+            .put(self.time)
+            .finish()
+    }
+}
+
+/// Returns the owner of a selection.
+#[derive(Debug, Clone)]
+pub struct GetSelectionOwner {
+    pub selection: Atom,
+}
+
+impl GetSelectionOwner {
+    /// Converts the request to x11 raw bytes
+    //
+    //   1 byte     Opcode (23)
+    //   1 byte     Unused
+    //   2 bytes    Request length (2)
+    //   4 bytes    Selection
+    pub fn serialize(&self) -> SmallRequest {
+        SmallRequest::new(opcode::GET_SELECTION_OWNER, 0)
+            .put(self.selection)
+            .finish()
+    }
+}
+
+/// Asks the owner of a selection to convert it to a target type and store the
+/// result in a property of the requestor.
+#[derive(Debug, Clone)]
+pub struct ConvertSelection {
+    pub requestor: Window,
+    pub selection: Atom,
+    pub target: Atom,
+    /// The property to store the result in, or [crate::protocol::atom::NONE] to
+    /// let the owner choose (obsolete).
+    pub property: Atom,
+    pub time: Timestamp,
+}
+
+impl ConvertSelection {
+    /// Converts the request to x11 raw bytes
+    //
+    //   1 byte     Opcode (24)
+    //   1 byte     Unused
+    //   2 bytes    Request length (6)
+    //   4 bytes    Requestor
+    //   4 bytes    Selection
+    //   4 bytes    Target
+    //   4 bytes    Property
+    //   4 bytes    Time
+    pub fn serialize(&self) -> SmallRequest {
+        SmallRequest::new(opcode::CONVERT_SELECTION, 0)
+            .put(self.requestor)
+            .put(self.selection)
+            .put(self.target)
+            .put(self.property)
+            .put(self.time)
+            .finish()
+    }
+}
+
+/// The layout of the pixels of an image sent with PutImage or read with GetImage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImageFormat(pub u8);
+
+impl ImageFormat {
+    pub const XY_BITMAP: Self = Self(0);
+    pub const XY_PIXMAP: Self = Self(1);
+    pub const Z_PIXMAP: Self = Self(2);
+}
+
+/// Writes an image to a drawable.
+#[derive(Debug, Clone)]
+pub struct PutImage<'a> {
+    pub format: ImageFormat,
+    pub drawable: XId,
+    pub gc: XId,
+    pub width: u16,
+    pub height: u16,
+    pub dst_x: i16,
+    pub dst_y: i16,
+    /// Number of bits to skip at the start of each scanline, for the XY formats.
+    pub left_pad: u8,
+    pub depth: u8,
+    /// The scanlines, each padded to the scanline pad of the format of `depth`.
+    pub data: &'a [u8],
+}
+
+impl PutImage<'_> {
+    /// Size of the request without the data.
+    pub const HEADER_LENGTH: usize = 24;
+
+    /// Converts the request to x11 raw bytes
+    //
+    //   1 byte     Opcode (72)
+    //   1 byte     Format
+    //   2 bytes    Request length (6+(n+p)/4)
+    //   4 bytes    Drawable
+    //   4 bytes    GC
+    //   2 bytes    Width
+    //   2 bytes    Height
+    //   2 bytes    Destination x
+    //   2 bytes    Destination y
+    //   1 byte     Left pad
+    //   1 byte     Depth
+    //   2 bytes    Unused
+    //   n bytes    Data
+    //   p bytes    Unused (p = pad(n))
+    pub fn serialize(&self) -> Vec<u8> {
+        RequestBuilder::new(opcode::PUT_IMAGE, self.format.0)
+            .put(self.drawable)
+            .put(self.gc)
+            .put(self.width)
+            .put(self.height)
+            .put(self.dst_x)
+            .put(self.dst_y)
+            .put(self.left_pad)
+            .put(self.depth)
+            .pad(2)
+            .put_bytes(self.data)
+            .finish()
+    }
+}
+
+/// Reads the contents of a rectangle of a drawable.
+#[derive(Debug, Clone)]
+pub struct GetImage {
+    pub format: ImageFormat,
+    pub drawable: XId,
+    pub x: i16,
+    pub y: i16,
+    pub width: u16,
+    pub height: u16,
+    /// The planes to read; the others are zero in the image.
+    pub plane_mask: u32,
+}
+
+impl GetImage {
+    /// Converts the request to x11 raw bytes
+    //
+    //   1 byte     Opcode (73)
+    //   1 byte     Format
+    //   2 bytes    Request length (5)
+    //   4 bytes    Drawable
+    //   2 bytes    X
+    //   2 bytes    Y
+    //   2 bytes    Width
+    //   2 bytes    Height
+    //   4 bytes    Plane mask
+    pub fn serialize(&self) -> SmallRequest {
+        SmallRequest::new(opcode::GET_IMAGE, self.format.0)
+            .put(self.drawable)
+            .put(self.x)
+            .put(self.y)
+            .put(self.width)
+            .put(self.height)
+            .put(self.plane_mask)
+            .finish()
+    }
+}
+
+/// Changes a property of a window.
+#[derive(Debug, Clone)]
+pub struct ChangeProperty<'a> {
+    pub mode: PropMode,
+    pub window: Window,
+    pub property: Atom,
+    pub type_: Atom,
+    /// Whether `data` holds 8, 16 or 32 bit values.
+    pub format: u8,
+    /// The raw data; its length must be a multiple of `format / 8`.
+    pub data: &'a [u8],
+}
+
+impl ChangeProperty<'_> {
+    /// Converts the request to x11 raw bytes
+    //
+    //   1 byte     Opcode (18)
+    //   1 byte     Mode
+    //   2 bytes    Request length (6+(n+p)/4)
+    //   4 bytes    Window
+    //   4 bytes    Property
+    //   4 bytes    Type
+    //   1 byte     Format
+    //   3 bytes    Unused
+    //   4 bytes    Length of data in format units
+    //   n bytes    Data
+    //   p bytes    Unused (p = pad(n))
+    pub fn serialize(&self) -> Vec<u8> {
+        let units = self.data.len() / usize::from((self.format / 8).max(1));
+        RequestBuilder::new(opcode::CHANGE_PROPERTY, self.mode.0)
+            .put(self.window)
+            .put(self.property)
+            .put(self.type_)
+            .put(self.format)
+            .pad(3)
+            .put(units as u32)
+            .put_bytes(self.data)
+            .finish()
+    }
+}
@@ -0,0 +1,233 @@
+//! The MIT-SCREEN-SAVER extension: the state of the screen saver and how long
+//! the user has been idle.
+//!
+//! The server counts the time since the last input of any device, which is
+//! what auto-lock tools and presence indicators poll. A client showing a video
+//! suspends the screen saver meanwhile with [Connection::screensaver_suspend].
+//!
+//! ```no_run
+//! use mousetrap::connection::Connection;
+//! use mousetrap::screensaver::ScreenSaver;
+//!
+//! let mut conn = Connection::init().unwrap();
+//! conn.enable::<ScreenSaver>().unwrap();
+//! let root = conn.default_root();
+//! let info = conn.screensaver_query_info(root).unwrap().reply(&mut conn).unwrap();
+//! println!("idle for {} ms", info.ms_since_user_input);
+//! ```
+
+use crate::connection::Connection;
+use crate::cookie::{Cookie, Reply};
+use crate::errors::{ConnectionError, ParseError};
+use crate::extension::{Extension, Version};
+use crate::protocol::Window;
+use crate::request::RequestBuilder;
+use crate::utils::{check_trailing_bytes, deserialize_into, skip};
+
+/// Name of the extension, as given to QueryExtension.
+pub const NAME: &str = "MIT-SCREEN-SAVER";
+
+/// Version of the extension implemented by this module.
+pub const VERSION: (u8, u8) = (1, 1);
+
+/// Minor opcodes of the MIT-SCREEN-SAVER requests.
+pub mod opcode {
+    pub const QUERY_VERSION: u8 = 0;
+    pub const QUERY_INFO: u8 = 1;
+    pub const SUSPEND: u8 = 5;
+}
+
+/// Names of the MIT-SCREEN-SAVER requests, indexed by minor opcode.
+pub const REQUEST_NAMES: &[&str] = &[
+    "QueryVersion",
+    "QueryInfo",
+    "SelectInput",
+    "SetAttributes",
+    "UnsetAttributes",
+    "Suspend",
+];
+
+/// The extension defines no errors.
+pub const ERROR_NAMES: &[&str] = &[];
+
+/// Starts a request of the extension.
+fn request(conn: &mut Connection, minor_opcode: u8) -> Result<RequestBuilder, ConnectionError> {
+    let info = conn.require_extension(NAME, REQUEST_NAMES, ERROR_NAMES)?;
+    Ok(RequestBuilder::new(info.major_opcode, minor_opcode))
+}
+
+/// The MIT-SCREEN-SAVER extension, for [Connection::enable].
+pub struct ScreenSaver;
+
+impl Extension for ScreenSaver {
+    const NAME: &'static str = NAME;
+    const MIN_VERSION: Version = (1, 0);
+
+    fn negotiate(conn: &mut Connection) -> Result<Option<Version>, ConnectionError> {
+        let reply = conn
+            .screensaver_query_version(VERSION.0, VERSION.1)?
+            .reply(conn)?;
+        Ok(Some((
+            reply.major_version.into(),
+            reply.minor_version.into(),
+        )))
+    }
+}
+
+/// The state of the screen saver.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SaverState(pub u8);
+
+impl SaverState {
+    pub const OFF: Self = Self(0);
+    pub const ON: Self = Self(1);
+    /// The screen saver is on and cycles its image.
+    pub const CYCLE: Self = Self(2);
+    /// The timeout is 0, so the screen saver never starts.
+    pub const DISABLED: Self = Self(3);
+}
+
+/// What the screen saver shows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SaverKind(pub u8);
+
+impl SaverKind {
+    /// The screen is blanked.
+    pub const BLANKED: Self = Self(0);
+    /// The server draws its own screen saver.
+    pub const INTERNAL: Self = Self(1);
+    /// A client draws into the screen saver window.
+    pub const EXTERNAL: Self = Self(2);
+}
+
+/// Reply of the ScreenSaverQueryVersion request.
+#[derive(Debug, Clone)]
+pub struct QueryVersionReply {
+    pub major_version: u16,
+    pub minor_version: u16,
+}
+
+impl Reply for QueryVersionReply {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1                        unused
+        // 2  CARD16                sequence number
+        // 4  0                     reply length
+        // 2  CARD16                server major version
+        // 2  CARD16                server minor version
+        // 20                       unused
+        let rest = skip(bytes, 8)?;
+        let (major_version, rest) = deserialize_into::<u16>(rest)?;
+        let (minor_version, _) = deserialize_into::<u16>(rest)?;
+        Ok(QueryVersionReply {
+            major_version,
+            minor_version,
+        })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        check_trailing_bytes("screensaver::QueryVersionReply", bytes, 32)
+    }
+}
+
+/// Reply of the ScreenSaverQueryInfo request.
+#[derive(Debug, Clone)]
+pub struct QueryInfoReply {
+    pub state: SaverState,
+    /// The window the screen saver is drawn into.
+    pub saver_window: Window,
+    /// Milliseconds until the screen saver starts if it is off, or since it
+    /// started if it is on. 0 if it is disabled.
+    pub ms_until_server: u32,
+    /// Milliseconds since the last input of any device.
+    pub ms_since_user_input: u32,
+    /// The screen saver events selected by this client.
+    pub event_mask: u32,
+    pub kind: SaverKind,
+}
+
+impl Reply for QueryInfoReply {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1  CARD8                 state
+        // 2  CARD16                sequence number
+        // 4  0                     reply length
+        // 4  WINDOW                saver window
+        // 4  CARD32                milliseconds until saver
+        // 4  CARD32                milliseconds since user input
+        // 4  CARD32                event mask
+        // 1  BYTE                  kind
+        // 7                        unused
+        let rest = skip(bytes, 1)?;
+        let (state, rest) = deserialize_into::<u8>(rest)?;
+        let rest = skip(rest, 6)?;
+        let (saver_window, rest) = deserialize_into::<Window>(rest)?;
+        let (ms_until_server, rest) = deserialize_into::<u32>(rest)?;
+        let (ms_since_user_input, rest) = deserialize_into::<u32>(rest)?;
+        let (event_mask, rest) = deserialize_into::<u32>(rest)?;
+        let (kind, _) = deserialize_into::<u8>(rest)?;
+        Ok(QueryInfoReply {
+            state: SaverState(state),
+            saver_window,
+            ms_until_server,
+            ms_since_user_input,
+            event_mask,
+            kind: SaverKind(kind),
+        })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        check_trailing_bytes("screensaver::QueryInfoReply", bytes, 32)
+    }
+}
+
+impl Connection {
+    /// Tells the server the version of the extension the client supports and
+    /// returns the version the server supports.
+    pub fn screensaver_query_version(
+        &mut self,
+        major_version: u8,
+        minor_version: u8,
+    ) -> Result<Cookie<QueryVersionReply>, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (0)
+        //   2 bytes    Request length (2)
+        //   1 byte     Client major version
+        //   1 byte     Client minor version
+        //   2 bytes    Unused
+        let request = request(self, opcode::QUERY_VERSION)?
+            .put(major_version)
+            .put(minor_version)
+            .pad(2)
+            .finish();
+        self.send_request_with_reply(&request)
+    }
+
+    /// Returns the state of the screen saver of the screen of `drawable`, and
+    /// how long the user has been idle.
+    pub fn screensaver_query_info(
+        &mut self,
+        drawable: Window,
+    ) -> Result<Cookie<QueryInfoReply>, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (1)
+        //   2 bytes    Request length (2)
+        //   4 bytes    Drawable
+        let request = request(self, opcode::QUERY_INFO)?.put(drawable).finish();
+        self.send_request_with_reply(&request)
+    }
+
+    /// Keeps the screen saver and DPMS from starting while `suspend` is set, as
+    /// video players do. The server counts the suspensions of each client, and
+    /// ends those of a client when it disconnects. Requires version 1.1.
+    pub fn screensaver_suspend(&mut self, suspend: bool) -> Result<u16, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (5)
+        //   2 bytes    Request length (2)
+        //   4 bytes    Suspend
+        let request = request(self, opcode::SUSPEND)?
+            .put(u32::from(suspend))
+            .finish();
+        self.send_request(&request)
+    }
+}
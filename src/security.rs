@@ -0,0 +1,328 @@
+//! The SECURITY extension: authorizations for untrusted clients.
+//!
+//! A trusted client can have the server generate an authorization cookie and
+//! give it to another program, e.g. a sandboxed one. Clients connecting with it
+//! are untrusted: they cannot see or act on the resources of trusted clients.
+//! The cookie expires once no client has used it for the timeout, or when it is
+//! revoked.
+//!
+//! ```no_run
+//! use mousetrap::auth::XAuthEntry;
+//! use mousetrap::connection::Connection;
+//!
+//! let mut conn = Connection::init().unwrap();
+//! let (_, cookie) = conn.security_generate_untrusted_cookie(60).unwrap();
+//! let entry = XAuthEntry {
+//!     family: 256,
+//!     address: b"myhost".to_vec(),
+//!     display_number: b"0".to_vec(),
+//!     authorization_protocol_name: b"MIT-MAGIC-COOKIE-1".to_vec(),
+//!     authorization_protocol_data: cookie,
+//! };
+//! let mut file = std::fs::File::create("/tmp/sandbox.Xauthority").unwrap();
+//! entry.write_to(&mut file).unwrap();
+//! ```
+
+use crate::connection::Connection;
+use crate::cookie::{Cookie, Reply};
+use crate::errors::{ConnectionError, ParseError, SerializeError};
+use crate::event::Event;
+use crate::extension::{Extension, Version};
+use crate::protocol::XId;
+use crate::request::RequestBuilder;
+use crate::utils::{check_trailing_bytes, deserialize_into, deserialize_into_vec, padding, skip};
+
+/// Name of the extension, as given to QueryExtension.
+pub const NAME: &str = "SECURITY";
+
+/// Version of the extension implemented by this module.
+pub const VERSION: (u16, u16) = (1, 0);
+
+/// Minor opcodes of the SECURITY requests.
+pub mod opcode {
+    pub const QUERY_VERSION: u8 = 0;
+    pub const GENERATE_AUTHORIZATION: u8 = 1;
+    pub const REVOKE_AUTHORIZATION: u8 = 2;
+}
+
+/// Names of the SECURITY requests, indexed by minor opcode.
+pub const REQUEST_NAMES: &[&str] = &[
+    "QueryVersion",
+    "GenerateAuthorization",
+    "RevokeAuthorization",
+];
+
+/// Names of the SECURITY errors, indexed by error code.
+pub const ERROR_NAMES: &[&str] = &["BadAuthorization", "BadAuthorizationProtocol"];
+
+/// Starts a request of the extension.
+fn request(conn: &mut Connection, minor_opcode: u8) -> Result<RequestBuilder, ConnectionError> {
+    let info = conn.require_extension(NAME, REQUEST_NAMES, ERROR_NAMES)?;
+    Ok(RequestBuilder::new(info.major_opcode, minor_opcode))
+}
+
+/// The SECURITY extension, for [Connection::enable].
+pub struct Security;
+
+impl Extension for Security {
+    const NAME: &'static str = NAME;
+    const MIN_VERSION: Version = (1, 0);
+
+    fn negotiate(conn: &mut Connection) -> Result<Option<Version>, ConnectionError> {
+        let reply = conn
+            .security_query_version(VERSION.0, VERSION.1)?
+            .reply(conn)?;
+        Ok(Some((
+            reply.major_version.into(),
+            reply.minor_version.into(),
+        )))
+    }
+}
+
+/// Event codes of the extension, relative to its first event.
+pub mod event_code {
+    pub const AUTHORIZATION_REVOKED: u8 = 0;
+}
+
+/// The authorization protocol of the cookies generated by
+/// [Connection::security_generate_untrusted_cookie].
+pub const MIT_MAGIC_COOKIE: &str = "MIT-MAGIC-COOKIE-1";
+
+/// Whether the clients connecting with an authorization are trusted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TrustLevel(pub u32);
+
+impl TrustLevel {
+    pub const TRUSTED: Self = Self(0);
+    pub const UNTRUSTED: Self = Self(1);
+}
+
+/// Attributes of a generated authorization. Only the attributes that are `Some`
+/// are sent to the server, which uses defaults for the others.
+#[derive(Debug, Clone, Default)]
+pub struct AuthorizationAttributes {
+    /// Seconds the authorization lasts once no client uses it, 60 by default.
+    /// 0 means it never expires.
+    pub timeout: Option<u32>,
+    /// Untrusted by default.
+    pub trust_level: Option<TrustLevel>,
+    /// The application group of the clients, see the XC-APPGROUP extension.
+    pub group: Option<XId>,
+    /// Whether the generating client is sent AuthorizationRevoked events, 1 to
+    /// select them.
+    pub event_mask: Option<u32>,
+}
+
+impl AuthorizationAttributes {
+    /// Returns the value-mask and the LISTofVALUE of the attributes.
+    fn values(&self) -> (u32, Vec<u32>) {
+        // The values are sent as 4-byte values, in the order of their bit in the value-mask.
+        let values: [Option<u32>; 4] = [
+            self.timeout,
+            self.trust_level.map(|v| v.0),
+            self.group.map(|v| v.0),
+            self.event_mask,
+        ];
+        let mut value_mask = 0u32;
+        for (bit, value) in values.iter().enumerate() {
+            if value.is_some() {
+                value_mask |= 1 << bit;
+            }
+        }
+        (value_mask, values.into_iter().flatten().collect())
+    }
+}
+
+/// Reply of the SecurityQueryVersion request.
+#[derive(Debug, Clone)]
+pub struct QueryVersionReply {
+    pub major_version: u16,
+    pub minor_version: u16,
+}
+
+impl Reply for QueryVersionReply {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1                        unused
+        // 2  CARD16                sequence number
+        // 4  0                     reply length
+        // 2  CARD16                server-major-version
+        // 2  CARD16                server-minor-version
+        // 20                       unused
+        let rest = skip(bytes, 8)?;
+        let (major_version, rest) = deserialize_into::<u16>(rest)?;
+        let (minor_version, _) = deserialize_into::<u16>(rest)?;
+        Ok(QueryVersionReply {
+            major_version,
+            minor_version,
+        })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        check_trailing_bytes("security::QueryVersionReply", bytes, 32)
+    }
+}
+
+/// Reply of the SecurityGenerateAuthorization request.
+#[derive(Debug, Clone)]
+pub struct GenerateAuthorizationReply {
+    /// Identifies the authorization, to revoke it.
+    pub authorization_id: XId,
+    /// The data of the authorization, e.g. the cookie, if the server generated it.
+    pub data: Vec<u8>,
+}
+
+impl Reply for GenerateAuthorizationReply {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1                        unused
+        // 2  CARD16                sequence number
+        // 4  (n+p)/4               reply length
+        // 4  AUTHID                authorization-id
+        // 2  n                     data length
+        // 18                       unused
+        // n  LISTofBYTE            authorization-data
+        // p                        unused, p=pad(n)
+        let rest = skip(bytes, 8)?;
+        let (authorization_id, rest) = deserialize_into::<XId>(rest)?;
+        let (length, rest) = deserialize_into::<u16>(rest)?;
+        let rest = skip(rest, 18)?;
+        let (data, _) = deserialize_into_vec::<u8>(rest, length.into())?;
+        Ok(GenerateAuthorizationReply {
+            authorization_id,
+            data,
+        })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        let length = 32 + self.data.len() + padding(self.data.len(), 4);
+        check_trailing_bytes("security::GenerateAuthorizationReply", bytes, length)
+    }
+}
+
+/// An authorization generated by this client was revoked or expired. Selected
+/// with [AuthorizationAttributes::event_mask].
+#[derive(Debug, Clone)]
+pub struct AuthorizationRevokedEvent {
+    pub authorization_id: XId,
+}
+
+impl AuthorizationRevokedEvent {
+    /// Parses the event if `event` is an AuthorizationRevoked event. `first_event`
+    /// is the one of the extension, see [Connection::extension].
+    //
+    //   1 byte     Code (first event + 0)
+    //   1 byte     Unused
+    //   2 bytes    Sequence number
+    //   4 bytes    Authorization id
+    //   24 bytes   Unused
+    pub fn from_event(event: &Event, first_event: u8) -> Option<AuthorizationRevokedEvent> {
+        let Event::Unknown(bytes) = event else {
+            return None;
+        };
+        if bytes.first()? & 0x7f != first_event.wrapping_add(event_code::AUTHORIZATION_REVOKED) {
+            return None;
+        }
+        let rest = skip(bytes, 4).ok()?;
+        let (authorization_id, _) = deserialize_into::<XId>(rest).ok()?;
+        Some(AuthorizationRevokedEvent { authorization_id })
+    }
+}
+
+impl Connection {
+    /// Tells the server the version of the extension the client supports and
+    /// returns the version the server supports.
+    pub fn security_query_version(
+        &mut self,
+        major_version: u16,
+        minor_version: u16,
+    ) -> Result<Cookie<QueryVersionReply>, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (0)
+        //   2 bytes    Request length (2)
+        //   2 bytes    Client major version
+        //   2 bytes    Client minor version
+        let request = request(self, opcode::QUERY_VERSION)?
+            .put(major_version)
+            .put(minor_version)
+            .finish();
+        self.send_request_with_reply(&request)
+    }
+
+    /// Adds an authorization for the protocol `protocol` to the server. The server
+    /// generates the data of the authorization if `data` is empty.
+    pub fn security_generate_authorization(
+        &mut self,
+        protocol: &str,
+        data: &[u8],
+        attributes: &AuthorizationAttributes,
+    ) -> Result<Cookie<GenerateAuthorizationReply>, ConnectionError> {
+        let protocol_length =
+            u16::try_from(protocol.len()).map_err(|_| SerializeError::TooLong {
+                field: "authorization protocol",
+                length: protocol.len(),
+                max: u16::MAX.into(),
+            })?;
+        let data_length = u16::try_from(data.len()).map_err(|_| SerializeError::TooLong {
+            field: "authorization data",
+            length: data.len(),
+            max: u16::MAX.into(),
+        })?;
+        let (value_mask, values) = attributes.values();
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (1)
+        //   2 bytes    Request length (3+(n+p+d+q)/4+v)
+        //   2 bytes    Protocol name length (n)
+        //   2 bytes    Data length (d)
+        //   4 bytes    Value mask
+        //   n+p bytes  Protocol name, padded
+        //   d+q bytes  Data, padded
+        //   4v bytes   Values
+        let mut request = request(self, opcode::GENERATE_AUTHORIZATION)?;
+        request
+            .put(protocol_length)
+            .put(data_length)
+            .put(value_mask)
+            .put_bytes(protocol.as_bytes())
+            .pad(padding(protocol.len(), 4))
+            .put_bytes(data)
+            .pad(padding(data.len(), 4));
+        for value in values {
+            request.put(value);
+        }
+        self.send_request_with_reply(&request.finish())
+    }
+
+    /// Has the server generate an untrusted MIT-MAGIC-COOKIE-1 cookie lasting
+    /// `timeout` seconds once unused, and returns its id and data.
+    pub fn security_generate_untrusted_cookie(
+        &mut self,
+        timeout: u32,
+    ) -> Result<(XId, Vec<u8>), ConnectionError> {
+        let attributes = AuthorizationAttributes {
+            timeout: Some(timeout),
+            trust_level: Some(TrustLevel::UNTRUSTED),
+            ..Default::default()
+        };
+        let reply = self
+            .security_generate_authorization(MIT_MAGIC_COOKIE, &[], &attributes)?
+            .reply(self)?;
+        Ok((reply.authorization_id, reply.data))
+    }
+
+    /// Removes an authorization from the server and closes the connections of the
+    /// clients that used it.
+    pub fn security_revoke_authorization(
+        &mut self,
+        authorization_id: XId,
+    ) -> Result<u16, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (2)
+        //   2 bytes    Request length (2)
+        //   4 bytes    Authorization id
+        let request = request(self, opcode::REVOKE_AUTHORIZATION)?
+            .put(authorization_id)
+            .finish();
+        self.send_request(&request)
+    }
+}
@@ -0,0 +1,393 @@
+//! The SHAPE extension: windows that are not rectangular.
+//!
+//! A window has three shapes. The bounding shape is the area the window covers
+//! on screen, the clip shape the area its contents are drawn in, and the input
+//! shape the area that receives pointer events. An empty input shape makes the
+//! window click-through, which suits overlays.
+//!
+//! ```no_run
+//! use mousetrap::connection::Connection;
+//! use mousetrap::protocol::XId;
+//!
+//! let mut conn = Connection::init().unwrap();
+//! let overlay = XId(0x1200001);
+//! conn.shape_clear_input(overlay).unwrap();
+//! conn.sync().unwrap();
+//! ```
+
+use crate::connection::Connection;
+use crate::cookie::{Cookie, Reply};
+use crate::errors::{ConnectionError, ParseError};
+use crate::event::Event;
+use crate::extension::{Extension, Version};
+use crate::protocol::{Rectangle, Timestamp, Window, XId};
+use crate::request::RequestBuilder;
+use crate::utils::{check_trailing_bytes, deserialize_into, skip};
+
+/// Name of the extension, as given to QueryExtension.
+pub const NAME: &str = "SHAPE";
+
+/// Version of the extension implemented by this module.
+pub const VERSION: (u16, u16) = (1, 1);
+
+/// Minor opcodes of the SHAPE requests.
+pub mod opcode {
+    pub const QUERY_VERSION: u8 = 0;
+    pub const RECTANGLES: u8 = 1;
+    pub const MASK: u8 = 2;
+    pub const COMBINE: u8 = 3;
+    pub const OFFSET: u8 = 4;
+    pub const SELECT_INPUT: u8 = 6;
+}
+
+/// Names of the SHAPE requests, indexed by minor opcode.
+pub const REQUEST_NAMES: &[&str] = &[
+    "QueryVersion",
+    "Rectangles",
+    "Mask",
+    "Combine",
+    "Offset",
+    "QueryExtents",
+    "SelectInput",
+    "InputSelected",
+    "GetRectangles",
+];
+
+/// The extension defines no errors.
+pub const ERROR_NAMES: &[&str] = &[];
+
+/// Starts a request of the extension.
+fn request(conn: &mut Connection, minor_opcode: u8) -> Result<RequestBuilder, ConnectionError> {
+    let info = conn.require_extension(NAME, REQUEST_NAMES, ERROR_NAMES)?;
+    Ok(RequestBuilder::new(info.major_opcode, minor_opcode))
+}
+
+/// The SHAPE extension, for [Connection::enable].
+pub struct Shape;
+
+impl Extension for Shape {
+    const NAME: &'static str = NAME;
+    const MIN_VERSION: Version = (1, 0);
+
+    fn negotiate(conn: &mut Connection) -> Result<Option<Version>, ConnectionError> {
+        let reply = conn.shape_query_version()?.reply(conn)?;
+        Ok(Some((
+            reply.major_version.into(),
+            reply.minor_version.into(),
+        )))
+    }
+}
+
+/// Event codes of the extension, relative to its first event.
+pub mod event_code {
+    pub const NOTIFY: u8 = 0;
+}
+
+/// One of the shapes of a window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Kind(pub u8);
+
+impl Kind {
+    pub const BOUNDING: Self = Self(0);
+    pub const CLIP: Self = Self(1);
+    /// Requires SHAPE 1.1.
+    pub const INPUT: Self = Self(2);
+}
+
+/// How a new shape is combined with the current shape of a window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Operation(pub u8);
+
+impl Operation {
+    /// The new shape replaces the current one.
+    pub const SET: Self = Self(0);
+    pub const UNION: Self = Self(1);
+    pub const INTERSECT: Self = Self(2);
+    /// The new shape is removed from the current one.
+    pub const SUBTRACT: Self = Self(3);
+    /// The current shape is removed from the new one.
+    pub const INVERT: Self = Self(4);
+}
+
+/// How the rectangles given to [Connection::shape_rectangles] are sorted, which
+/// saves the server from sorting them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ordering(pub u8);
+
+impl Ordering {
+    pub const UNSORTED: Self = Self(0);
+    pub const Y_SORTED: Self = Self(1);
+    pub const YX_SORTED: Self = Self(2);
+    pub const YX_BANDED: Self = Self(3);
+}
+
+/// Reply of the ShapeQueryVersion request.
+#[derive(Debug, Clone)]
+pub struct QueryVersionReply {
+    pub major_version: u16,
+    pub minor_version: u16,
+}
+
+impl Reply for QueryVersionReply {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1                        unused
+        // 2  CARD16                sequence number
+        // 4  0                     reply length
+        // 2  CARD16                major-version
+        // 2  CARD16                minor-version
+        // 20                       unused
+        let rest = skip(bytes, 8)?;
+        let (major_version, rest) = deserialize_into::<u16>(rest)?;
+        let (minor_version, _) = deserialize_into::<u16>(rest)?;
+        Ok(QueryVersionReply {
+            major_version,
+            minor_version,
+        })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        check_trailing_bytes("shape::QueryVersionReply", bytes, 32)
+    }
+}
+
+/// A shape of a window changed. Selected with [Connection::shape_select_input].
+#[derive(Debug, Clone)]
+pub struct ShapeNotifyEvent {
+    pub kind: Kind,
+    pub window: Window,
+    /// The extents of the new shape, relative to the window.
+    pub extents: Rectangle,
+    pub time: Timestamp,
+    /// Whether the window has a shape of this kind, rather than the default
+    /// rectangular one.
+    pub shaped: bool,
+}
+
+impl ShapeNotifyEvent {
+    /// Parses the event if `event` is a shape notification. `first_event` is the
+    /// one of the extension, see [Connection::extension].
+    //
+    //   1 byte     Code (first event + 0)
+    //   1 byte     Kind
+    //   2 bytes    Sequence number
+    //   4 bytes    Window
+    //   2 bytes    X
+    //   2 bytes    Y
+    //   2 bytes    Width
+    //   2 bytes    Height
+    //   4 bytes    Time
+    //   1 byte     Shaped
+    //   11 bytes   Unused
+    pub fn from_event(event: &Event, first_event: u8) -> Option<ShapeNotifyEvent> {
+        let Event::Unknown(bytes) = event else {
+            return None;
+        };
+        if bytes.first()? & 0x7f != first_event.wrapping_add(event_code::NOTIFY) {
+            return None;
+        }
+        let rest = skip(bytes, 1).ok()?;
+        let (kind, rest) = deserialize_into::<u8>(rest).ok()?;
+        let rest = skip(rest, 2).ok()?;
+        let (window, rest) = deserialize_into::<Window>(rest).ok()?;
+        let (x, rest) = deserialize_into::<i16>(rest).ok()?;
+        let (y, rest) = deserialize_into::<i16>(rest).ok()?;
+        let (width, rest) = deserialize_into::<u16>(rest).ok()?;
+        let (height, rest) = deserialize_into::<u16>(rest).ok()?;
+        let (time, rest) = deserialize_into::<Timestamp>(rest).ok()?;
+        let (shaped, _) = deserialize_into::<u8>(rest).ok()?;
+        Some(ShapeNotifyEvent {
+            kind: Kind(kind),
+            window,
+            extents: Rectangle {
+                x,
+                y,
+                width,
+                height,
+            },
+            time,
+            shaped: shaped != 0,
+        })
+    }
+}
+
+impl Connection {
+    /// Returns the version of the extension the server supports.
+    pub fn shape_query_version(&mut self) -> Result<Cookie<QueryVersionReply>, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (0)
+        //   2 bytes    Request length (1)
+        let request = request(self, opcode::QUERY_VERSION)?.finish();
+        self.send_request_with_reply(&request)
+    }
+
+    /// Combines a shape of a window with a list of rectangles, offset by
+    /// (`x`, `y`).
+    #[allow(clippy::too_many_arguments)]
+    pub fn shape_rectangles(
+        &mut self,
+        operation: Operation,
+        kind: Kind,
+        ordering: Ordering,
+        window: Window,
+        x: i16,
+        y: i16,
+        rectangles: &[Rectangle],
+    ) -> Result<u16, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (1)
+        //   2 bytes    Request length (4+2n)
+        //   1 byte     Operation
+        //   1 byte     Destination kind
+        //   1 byte     Ordering
+        //   1 byte     Unused
+        //   4 bytes    Destination window
+        //   2 bytes    X offset
+        //   2 bytes    Y offset
+        //   8n bytes   Rectangles
+        let mut request = request(self, opcode::RECTANGLES)?;
+        request
+            .put(operation.0)
+            .put(kind.0)
+            .put(ordering.0)
+            .pad(1)
+            .put(window)
+            .put(x)
+            .put(y);
+        for rectangle in rectangles {
+            request.put(*rectangle);
+        }
+        self.send_request(&request.finish())
+    }
+
+    /// Combines a shape of a window with the set bits of a 1-bit deep pixmap,
+    /// offset by (`x`, `y`). A `bitmap` of 0 stands for the whole window, which
+    /// with [Operation::SET] removes the shape.
+    pub fn shape_mask(
+        &mut self,
+        operation: Operation,
+        kind: Kind,
+        window: Window,
+        x: i16,
+        y: i16,
+        bitmap: XId,
+    ) -> Result<u16, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (2)
+        //   2 bytes    Request length (5)
+        //   1 byte     Operation
+        //   1 byte     Destination kind
+        //   2 bytes    Unused
+        //   4 bytes    Destination window
+        //   2 bytes    X offset
+        //   2 bytes    Y offset
+        //   4 bytes    Source bitmap
+        let request = request(self, opcode::MASK)?
+            .put(operation.0)
+            .put(kind.0)
+            .pad(2)
+            .put(window)
+            .put(x)
+            .put(y)
+            .put(bitmap)
+            .finish();
+        self.send_request(&request)
+    }
+
+    /// Combines a shape of a window with a shape of another window, offset by
+    /// (`x`, `y`).
+    #[allow(clippy::too_many_arguments)]
+    pub fn shape_combine(
+        &mut self,
+        operation: Operation,
+        kind: Kind,
+        source_kind: Kind,
+        window: Window,
+        x: i16,
+        y: i16,
+        source: Window,
+    ) -> Result<u16, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (3)
+        //   2 bytes    Request length (5)
+        //   1 byte     Operation
+        //   1 byte     Destination kind
+        //   1 byte     Source kind
+        //   1 byte     Unused
+        //   4 bytes    Destination window
+        //   2 bytes    X offset
+        //   2 bytes    Y offset
+        //   4 bytes    Source window
+        let request = request(self, opcode::COMBINE)?
+            .put(operation.0)
+            .put(kind.0)
+            .put(source_kind.0)
+            .pad(1)
+            .put(window)
+            .put(x)
+            .put(y)
+            .put(source)
+            .finish();
+        self.send_request(&request)
+    }
+
+    /// Moves a shape of a window by (`x`, `y`).
+    pub fn shape_offset(
+        &mut self,
+        kind: Kind,
+        window: Window,
+        x: i16,
+        y: i16,
+    ) -> Result<u16, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (4)
+        //   2 bytes    Request length (3)
+        //   1 byte     Destination kind
+        //   3 bytes    Unused
+        //   4 bytes    Destination window
+        //   2 bytes    X offset
+        //   2 bytes    Y offset
+        let request = request(self, opcode::OFFSET)?
+            .put(kind.0)
+            .pad(3)
+            .put(window)
+            .put(x)
+            .put(y)
+            .finish();
+        self.send_request(&request)
+    }
+
+    /// Selects whether ShapeNotify events are sent when the shapes of `window` change.
+    pub fn shape_select_input(
+        &mut self,
+        window: Window,
+        enable: bool,
+    ) -> Result<u16, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (6)
+        //   2 bytes    Request length (3)
+        //   4 bytes    Window
+        //   1 byte     Enable
+        //   3 bytes    Unused
+        let request = request(self, opcode::SELECT_INPUT)?
+            .put(window)
+            .put(enable as u8)
+            .pad(3)
+            .finish();
+        self.send_request(&request)
+    }
+
+    /// Empties the input shape of a window, so that pointer events go through it
+    /// to the windows below. Requires SHAPE 1.1.
+    pub fn shape_clear_input(&mut self, window: Window) -> Result<u16, ConnectionError> {
+        self.shape_rectangles(
+            Operation::SET,
+            Kind::INPUT,
+            Ordering::UNSORTED,
+            window,
+            0,
+            0,
+            &[],
+        )
+    }
+}
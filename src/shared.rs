@@ -0,0 +1,439 @@
+//! A connection that can be shared between threads.
+//!
+//! [SharedConnection] is `Send + Sync` and its methods take `&self`, so it can be
+//! put in an `Arc` and used by several threads at once. It follows the thread
+//! model of xcb:
+//!
+//! - Requests are written under a lock, whole, and numbered in the order they are
+//!   written.
+//! - There is a single reader at a time. A thread waiting on a reply or an event
+//!   that nobody reads for becomes the reader; it routes every message it reads
+//!   to the thread waiting on it and wakes the others, and the next thread still
+//!   waiting takes over when it got what it waited for.
+//!
+//! The blocking [crate::connection::Connection] stays the one with the full API;
+//! this one has the basics, like the async [crate::aio::Connection], and uses the
+//! same [ProtocolState] to parse what the server sends.
+//!
+//! # Extensions
+//!
+//! The requests of the extension modules, like [crate::randr], are methods of the
+//! blocking connection only. Here, [SharedConnection::require_extension] returns
+//! the major opcode of an extension and registers the names of its requests and
+//! errors, which the modules export, and the requests are built with
+//! [crate::request::RequestBuilder]:
+//!
+//! ```no_run
+//! # use mousetrap::randr;
+//! # use mousetrap::request::RequestBuilder;
+//! # use mousetrap::shared::SharedConnection;
+//! # let conn = SharedConnection::init().unwrap();
+//! let info = conn
+//!     .require_extension(randr::NAME, randr::REQUEST_NAMES, randr::ERROR_NAMES)
+//!     .unwrap();
+//! // RRSelectInput: window and event mask.
+//! let request = RequestBuilder::new(info.major_opcode, 4)
+//!     .put(conn.default_root().0)
+//!     .put(1u16)
+//!     .pad(2)
+//!     .finish();
+//! conn.send_request(&request).unwrap();
+//! ```
+//!
+//! The threaded and io_uring connections have the same `require_extension`.
+//!
+//! # Ordering
+//!
+//! The server processes requests in the order they were written. Within a thread,
+//! that is the order of the calls; between threads, a request sent after another
+//! thread's `send_request` returned is processed after that one. Events are
+//! delivered in the order the server sent them, each to one caller. The error of
+//! an unchecked request goes to the error handler, which is called from whichever
+//! thread was reading at the time.
+//!
+//! ```no_run
+//! # use mousetrap::shared::SharedConnection;
+//! # use mousetrap::reply::InternAtomReply;
+//! # use mousetrap::request::InternAtom;
+//! use std::sync::Arc;
+//! use std::thread;
+//!
+//! let conn = Arc::new(SharedConnection::init().unwrap());
+//! let events = thread::spawn({
+//!     let conn = conn.clone();
+//!     move || {
+//!         while let Ok(event) = conn.wait_for_event() {
+//!             println!("{:?}", event);
+//!         }
+//!     }
+//! });
+//! let request = InternAtom {
+//!     only_if_exists: false,
+//!     name: b"_NET_WM_NAME",
+//! };
+//! let cookie = conn
+//!     .send_request_with_reply::<InternAtomReply>(&request.serialize())
+//!     .unwrap();
+//! println!("atom: {:?}", cookie.reply(&conn).unwrap().atom);
+//! # events.join().unwrap();
+//! ```
+
+use crate::connection::{parse_conf, ExtensionInfo, Stream};
+use crate::cookie::{parse_reply, ParseMode, Reply};
+use crate::errors::{ConnectionError, XError};
+use crate::event::Event;
+use crate::protocol::{ConnSetup, Screen, Window, XId};
+use crate::protocol_state::ProtocolState;
+use crate::reply::{InputFocusReply, QueryExtensionReply};
+use crate::request::{GetInputFocus, QueryExtension};
+use std::env;
+use std::io;
+use std::marker::PhantomData;
+use std::sync::{Condvar, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+/// The protocol state, along with who reads from the server.
+struct Shared {
+    protocol: ProtocolState,
+    /// Whether a thread is reading from the server.
+    reading: bool,
+}
+
+/// A connection that can be shared between threads. See the [module](self)
+/// documentation.
+pub struct SharedConnection {
+    /// The server's response to the connection setup.
+    setup: ConnSetup,
+    /// Index of the screen given in the display name.
+    screen_number: usize,
+    /// The socket requests are written to. Locked while a request is written.
+    writer: Mutex<Stream>,
+    /// The socket messages are read from. Only locked by the reading thread.
+    reader: Mutex<Stream>,
+    /// Sequence numbers, buffered messages, replies and events.
+    shared: Mutex<Shared>,
+    /// Notified when the reading thread dispatched what it read or stopped reading.
+    dispatched: Condvar,
+}
+
+/// A handle to the reply of a request sent through a [SharedConnection].
+#[derive(Debug)]
+#[must_use = "the reply should be retrieved, otherwise it is kept in memory"]
+pub struct Cookie<R> {
+    /// Sequence number of the request.
+    pub sequence: u16,
+    reply: PhantomData<R>,
+}
+
+impl<R: Reply> Cookie<R> {
+    pub fn new(sequence: u16) -> Self {
+        Self {
+            sequence,
+            reply: PhantomData,
+        }
+    }
+
+    /// Blocks until the reply arrives and parses it.
+    pub fn reply(self, conn: &SharedConnection) -> Result<R, ConnectionError> {
+        let bytes = conn.wait_for_reply(self.sequence)?;
+        parse_reply(&bytes, conn.parse_mode())
+    }
+}
+
+/// Locks a mutex, ignoring that a thread panicked while holding it: the protected
+/// state is consistent between statements.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+impl SharedConnection {
+    /// Connects to the display named by `DISPLAY`.
+    pub fn init() -> Result<Self, ConnectionError> {
+        let display_name = env::var("DISPLAY").map_err(|_| ConnectionError::DisplayNotSet)?;
+        let conf = parse_conf(display_name);
+        let stream = Stream::open(&conf)?;
+        Self::with_stream(stream, conf.display_number, conf.screen_number)
+    }
+
+    pub(crate) fn with_stream(
+        mut stream: Stream,
+        display_number: u8,
+        screen_number: usize,
+    ) -> Result<Self, ConnectionError> {
+        let setup = stream.authenticate(display_number)?;
+        let reader = stream.try_clone()?;
+
+        Ok(SharedConnection {
+            setup: setup.clone(),
+            screen_number,
+            writer: Mutex::new(stream),
+            reader: Mutex::new(reader),
+            shared: Mutex::new(Shared {
+                protocol: ProtocolState::new(setup, screen_number),
+                reading: false,
+            }),
+            dispatched: Condvar::new(),
+        })
+    }
+
+    /// Returns the server's response to the connection setup.
+    pub fn setup(&self) -> &ConnSetup {
+        &self.setup
+    }
+
+    /// Returns the screen given in the display name, or the first screen if the
+    /// server has no such screen.
+    pub fn default_screen(&self) -> &Screen {
+        let roots = &self.setup.roots;
+        roots.get(self.screen_number).unwrap_or(&roots[0])
+    }
+
+    /// Returns the root window of the default screen.
+    pub fn default_root(&self) -> Window {
+        self.default_screen().root
+    }
+
+    /// Allocates a new id for a resource such as a window or a pixmap.
+    pub fn generate_id(&self) -> Result<XId, ConnectionError> {
+        lock(&self.shared).protocol.generate_id()
+    }
+
+    /// Sets how strictly replies are parsed.
+    pub fn set_parse_mode(&self, mode: ParseMode) {
+        lock(&self.shared).protocol.set_parse_mode(mode);
+    }
+
+    /// Returns how strictly replies are parsed.
+    pub fn parse_mode(&self) -> ParseMode {
+        lock(&self.shared).protocol.parse_mode()
+    }
+
+    /// Sets the function called with the errors of unchecked requests. By default
+    /// these errors are logged as warnings through the `log` crate.
+    ///
+    /// It is called with the connection's state locked, so it must not use the
+    /// connection.
+    pub fn set_error_handler(&self, handler: Box<dyn Fn(XError) + Send>) {
+        lock(&self.shared).protocol.set_error_handler(handler);
+    }
+
+    /// Sends a serialized request to the server and returns its sequence number.
+    ///
+    /// The request is unchecked: an error caused by it is passed to the error
+    /// handler.
+    pub fn send_request(&self, request: &[u8]) -> Result<u16, ConnectionError> {
+        self.send(request, false)
+    }
+
+    /// Sends a serialized request that has a reply and returns the cookie of the reply.
+    /// An error caused by the request is returned by [Cookie::reply].
+    pub fn send_request_with_reply<R: Reply>(
+        &self,
+        request: &[u8],
+    ) -> Result<Cookie<R>, ConnectionError> {
+        Ok(Cookie::new(self.send(request, true)?))
+    }
+
+    fn send(&self, request: &[u8], checked: bool) -> Result<u16, ConnectionError> {
+        let mut writer = lock(&self.writer);
+        // The request is numbered before it is written, so its reply or error can
+        // not be read before the request is known.
+        let sequence = {
+            let mut shared = lock(&self.shared);
+            if shared.protocol.is_disconnected() {
+                return Err(ConnectionError::Disconnected);
+            }
+            shared.protocol.check_request_length(request)?;
+            shared.protocol.request_sent(request, checked)
+        };
+        if let Err(err) = writer.write_all(request) {
+            let err = lock(&self.shared).protocol.connection_lost(err);
+            self.dispatched.notify_all();
+            return Err(err);
+        }
+        Ok(sequence)
+    }
+
+    /// Waits until the server processed every request sent so far and returns the
+    /// errors caused by unchecked requests that were not read from the server yet.
+    ///
+    /// Errors read by other threads in the meantime are collected too, so only one
+    /// thread should sync at a time.
+    pub fn sync(&self) -> Result<Vec<XError>, ConnectionError> {
+        let cookie: Cookie<InputFocusReply> =
+            self.send_request_with_reply(&GetInputFocus.serialize())?;
+        lock(&self.shared).protocol.begin_sync();
+        let result = cookie.reply(self);
+        let errors = lock(&self.shared).protocol.end_sync();
+        result.map(|_| errors)
+    }
+
+    /// Returns the codes of the extension `name`, or `None` if the server does not
+    /// support it. The server is only asked the first time.
+    pub fn extension(&self, name: &'static str) -> Result<Option<ExtensionInfo>, ConnectionError> {
+        if let Some(info) = lock(&self.shared).protocol.extension(name) {
+            return Ok(info);
+        }
+        let request = QueryExtension {
+            name: name.as_bytes(),
+        };
+        let reply = self
+            .send_request_with_reply::<QueryExtensionReply>(&request.serialize())?
+            .reply(self)?;
+        Ok(lock(&self.shared).protocol.extension_queried(name, &reply))
+    }
+
+    /// Returns the codes of an extension, failing if the server does not support
+    /// it, and registers the names of its requests and errors, see
+    /// [ProtocolState::register_extension].
+    pub fn require_extension(
+        &self,
+        name: &'static str,
+        request_names: &'static [&'static str],
+        error_names: &'static [&'static str],
+    ) -> Result<ExtensionInfo, ConnectionError> {
+        let info = self
+            .extension(name)?
+            .ok_or(ConnectionError::ExtensionNotSupported(name))?;
+        lock(&self.shared)
+            .protocol
+            .register_extension(name, info, request_names, error_names);
+        Ok(info)
+    }
+
+    /// Blocks until the reply to the request with the given sequence number arrives
+    /// and returns its bytes.
+    pub fn wait_for_reply(&self, sequence: u16) -> Result<Vec<u8>, ConnectionError> {
+        self.wait(|protocol| protocol.take_response(sequence), None)?
+            .ok_or(ConnectionError::Disconnected)?
+    }
+
+    /// Returns the next event, blocking until one arrives.
+    pub fn wait_for_event(&self) -> Result<Event, ConnectionError> {
+        self.wait(ProtocolState::take_event, None)?
+            .ok_or(ConnectionError::Disconnected)
+    }
+
+    /// Returns the next event, blocking until one arrives or `timeout` elapsed, in
+    /// which case `None` is returned.
+    pub fn wait_for_event_timeout(
+        &self,
+        timeout: Duration,
+    ) -> Result<Option<Event>, ConnectionError> {
+        self.wait(ProtocolState::take_event, Some(Instant::now() + timeout))
+    }
+
+    /// Returns the next event if one is available, without blocking.
+    pub fn poll_for_event(&self) -> Result<Option<Event>, ConnectionError> {
+        self.wait(ProtocolState::take_event, Some(Instant::now()))
+    }
+
+    /// Waits until `take` finds what it looks for, reading from the server unless
+    /// another thread does, at most until `deadline`.
+    fn wait<T>(
+        &self,
+        mut take: impl FnMut(&mut ProtocolState) -> Option<T>,
+        deadline: Option<Instant>,
+    ) -> Result<Option<T>, ConnectionError> {
+        let mut shared = lock(&self.shared);
+        loop {
+            if let Some(value) = take(&mut shared.protocol) {
+                return Ok(Some(value));
+            }
+            if shared.protocol.is_disconnected() {
+                return Err(ConnectionError::Disconnected);
+            }
+            let remaining =
+                deadline.map(|deadline| deadline.saturating_duration_since(Instant::now()));
+            if shared.reading {
+                // Another thread reads, and wakes us when it dispatched something.
+                shared = match remaining {
+                    None => self
+                        .dispatched
+                        .wait(shared)
+                        .unwrap_or_else(PoisonError::into_inner),
+                    Some(remaining) if remaining.is_zero() => return Ok(None),
+                    Some(remaining) => {
+                        self.dispatched
+                            .wait_timeout(shared, remaining)
+                            .unwrap_or_else(PoisonError::into_inner)
+                            .0
+                    }
+                };
+                continue;
+            }
+
+            // Read without holding the state, so other threads can send meanwhile.
+            shared.reading = true;
+            drop(shared);
+            let result = self.read(remaining);
+            shared = lock(&self.shared);
+            shared.reading = false;
+            self.dispatched.notify_all();
+            match result {
+                Ok(Some(bytes)) => {
+                    shared.protocol.bytes_received(&bytes)?;
+                }
+                Ok(None) => return Ok(take(&mut shared.protocol)),
+                Err(err) => {
+                    let err = shared.protocol.connection_lost(err);
+                    // What was read before the connection closed is still delivered.
+                    return take(&mut shared.protocol).map(Some).ok_or(err);
+                }
+            }
+        }
+    }
+
+    /// Reads what the server sent, blocking until something arrives or `timeout`
+    /// elapsed, in which case `None` is returned.
+    fn read(&self, timeout: Option<Duration>) -> io::Result<Option<Vec<u8>>> {
+        let mut reader = lock(&self.reader);
+        if let Some(timeout) = timeout {
+            if !reader.wait_readable(timeout)? {
+                return Ok(None);
+            }
+        }
+        let mut buf = vec![0u8; 4096];
+        loop {
+            match reader.read(&mut buf) {
+                Ok(0) => return Err(io::Error::from(io::ErrorKind::UnexpectedEof)),
+                Ok(n) => {
+                    buf.truncate(n);
+                    return Ok(Some(buf));
+                }
+                Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::randr;
+    use crate::request::RequestBuilder;
+    use crate::testing::randr_server;
+
+    #[test]
+    fn extension_errors_are_named_for_every_thread() {
+        let server = randr_server();
+        let stream = Stream::connect_unix_socket(&server.path().to_string_lossy()).unwrap();
+        let conn = SharedConnection::with_stream(stream, 0, 0).unwrap();
+        let info = conn
+            .require_extension(randr::NAME, randr::REQUEST_NAMES, randr::ERROR_NAMES)
+            .unwrap();
+        let request = RequestBuilder::new(info.major_opcode, 2).finish();
+        let cookie = std::thread::scope(|scope| {
+            scope
+                .spawn(|| conn.send_request_with_reply::<InputFocusReply>(&request))
+                .join()
+                .unwrap()
+        });
+        let Err(ConnectionError::ProtocolError(error)) = cookie.unwrap().reply(&conn) else {
+            panic!("expected the error of the request");
+        };
+        assert_eq!(error.error_name.as_deref(), Some("RANDR:BadCrtc"));
+        assert_eq!(error.request_name.as_deref(), Some("RANDR:SetScreenConfig"));
+    }
+}
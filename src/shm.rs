@@ -0,0 +1,705 @@
+//! The MIT-SHM extension: images transferred through shared memory.
+//!
+//! PutImage and GetImage send every pixel through the socket. With MIT-SHM the
+//! client and the server map the same memory segment instead, so capturing the
+//! screen costs a request and a memcpy at most, which makes high frame rates possible.
+//! This only works with a local server.
+//!
+//! Segments are either System V segments ([ShmSegment]) or file descriptors passed
+//! over the unix socket ([FdSegment]), which sandboxes are more likely to allow.
+//!
+//! ```no_run
+//! # use mousetrap::connection::Connection;
+//! # use mousetrap::request::ImageFormat;
+//! # use mousetrap::shm::{GetImage, ShmSegment};
+//! let mut conn = Connection::init().unwrap();
+//! let screen = conn.default_screen();
+//! let (root, width, height) = (screen.root, screen.width_in_px, screen.height_in_px);
+//! let size = usize::from(width) * usize::from(height) * 4;
+//! let segment = ShmSegment::new(size).unwrap();
+//! let shmseg = conn.shm_attach(&segment, false).unwrap();
+//! let reply = conn
+//!     .shm_get_image(&GetImage {
+//!         drawable: root,
+//!         x: 0,
+//!         y: 0,
+//!         width,
+//!         height,
+//!         plane_mask: !0,
+//!         format: ImageFormat::Z_PIXMAP,
+//!         shmseg,
+//!         offset: 0,
+//!     })
+//!     .unwrap()
+//!     .reply(&mut conn)
+//!     .unwrap();
+//! let pixels = &segment.as_slice()[..reply.size as usize];
+//! # let _ = pixels;
+//! ```
+
+use crate::connection::Connection;
+use crate::cookie::{Cookie, Reply};
+use crate::errors::{ConnectionError, ParseError};
+use crate::event::Event;
+use crate::extension::{Extension, Version};
+use crate::protocol::{VisualId, XId};
+use crate::request::{ImageFormat, RequestBuilder};
+use crate::utils::{check_trailing_bytes, deserialize_into, skip};
+use std::io;
+use std::os::fd::{AsFd, AsRawFd, BorrowedFd, FromRawFd, OwnedFd};
+use std::ptr;
+
+/// Name of the extension, as given to QueryExtension.
+pub const NAME: &str = "MIT-SHM";
+
+/// Minor opcodes of the MIT-SHM requests.
+pub mod opcode {
+    pub const QUERY_VERSION: u8 = 0;
+    pub const ATTACH: u8 = 1;
+    pub const DETACH: u8 = 2;
+    pub const PUT_IMAGE: u8 = 3;
+    pub const GET_IMAGE: u8 = 4;
+    pub const CREATE_PIXMAP: u8 = 5;
+    pub const ATTACH_FD: u8 = 6;
+    pub const CREATE_SEGMENT: u8 = 7;
+}
+
+/// Names of the MIT-SHM requests, indexed by minor opcode.
+pub const REQUEST_NAMES: &[&str] = &[
+    "QueryVersion",
+    "Attach",
+    "Detach",
+    "PutImage",
+    "GetImage",
+    "CreatePixmap",
+    "AttachFd",
+    "CreateSegment",
+];
+
+/// Names of the MIT-SHM errors, indexed from the first error.
+pub const ERROR_NAMES: &[&str] = &["BadShmSeg"];
+
+/// Returns the major opcode of the extension.
+fn major_opcode(conn: &mut Connection) -> Result<u8, ConnectionError> {
+    conn.require_extension(NAME, REQUEST_NAMES, ERROR_NAMES)
+        .map(|info| info.major_opcode)
+}
+
+/// The MIT-SHM extension, for [Connection::enable].
+pub struct Shm;
+
+impl Extension for Shm {
+    const NAME: &'static str = NAME;
+    const MIN_VERSION: Version = (1, 0);
+
+    fn negotiate(conn: &mut Connection) -> Result<Option<Version>, ConnectionError> {
+        let reply = conn.shm_query_version()?.reply(conn)?;
+        Ok(Some((
+            reply.major_version.into(),
+            reply.minor_version.into(),
+        )))
+    }
+}
+
+/// A System V shared memory segment mapped into this process.
+///
+/// The segment is removed when it is dropped. Detach it from the server first.
+pub struct ShmSegment {
+    shmid: i32,
+    addr: *mut u8,
+    size: usize,
+}
+
+// The segment owns its mapping, like a Vec owns its buffer.
+unsafe impl Send for ShmSegment {}
+
+impl ShmSegment {
+    /// Creates a segment of `size` bytes, readable and writable by the user only.
+    pub fn new(size: usize) -> io::Result<Self> {
+        let shmid = unsafe { libc::shmget(libc::IPC_PRIVATE, size, libc::IPC_CREAT | 0o600) };
+        if shmid == -1 {
+            return Err(io::Error::last_os_error());
+        }
+        let addr = unsafe { libc::shmat(shmid, ptr::null(), 0) };
+        if addr as isize == -1 {
+            let err = io::Error::last_os_error();
+            unsafe { libc::shmctl(shmid, libc::IPC_RMID, ptr::null_mut()) };
+            return Err(err);
+        }
+        Ok(ShmSegment {
+            shmid,
+            addr: addr.cast(),
+            size,
+        })
+    }
+
+    /// The System V id of the segment, passed to the server by ShmAttach.
+    pub fn shmid(&self) -> u32 {
+        self.shmid as u32
+    }
+
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    /// Returns the contents of the segment.
+    ///
+    /// The server writes to the segment while it processes a ShmGetImage, so
+    /// only read it after the reply arrived.
+    pub fn as_slice(&self) -> &[u8] {
+        unsafe { std::slice::from_raw_parts(self.addr, self.size) }
+    }
+
+    /// Returns the contents of the segment for writing an image to be sent with
+    /// ShmPutImage.
+    pub fn as_mut_slice(&mut self) -> &mut [u8] {
+        unsafe { std::slice::from_raw_parts_mut(self.addr, self.size) }
+    }
+}
+
+impl Drop for ShmSegment {
+    fn drop(&mut self) {
+        unsafe {
+            libc::shmdt(self.addr.cast());
+            libc::shmctl(self.shmid, libc::IPC_RMID, ptr::null_mut());
+        }
+    }
+}
+
+/// A shared memory segment backed by a file descriptor, such as a memfd.
+///
+/// Attached with ShmAttachFd, or created by the server with ShmCreateSegment.
+/// Unlike System V segments, these are released once both sides dropped them.
+pub struct FdSegment {
+    fd: OwnedFd,
+    addr: *mut u8,
+    size: usize,
+}
+
+// The segment owns its mapping, like a Vec owns its buffer.
+unsafe impl Send for FdSegment {}
+
+impl FdSegment {
+    /// Creates an anonymous memfd segment of `size` bytes.
+    pub fn new(size: usize) -> io::Result<Self> {
+        let name = c"mousetrap-shm";
+        let fd = unsafe { libc::memfd_create(name.as_ptr(), libc::MFD_CLOEXEC) };
+        if fd == -1 {
+            return Err(io::Error::last_os_error());
+        }
+        let fd = unsafe { OwnedFd::from_raw_fd(fd) };
+        let length = libc::off_t::try_from(size)
+            .map_err(|_| io::Error::from(io::ErrorKind::InvalidInput))?;
+        if unsafe { libc::ftruncate(fd.as_raw_fd(), length) } == -1 {
+            return Err(io::Error::last_os_error());
+        }
+        Self::from_fd(fd, size)
+    }
+
+    /// Maps `size` bytes of the file behind `fd`, e.g. one returned by ShmCreateSegment.
+    pub fn from_fd(fd: OwnedFd, size: usize) -> io::Result<Self> {
+        let addr = unsafe {
+            libc::mmap(
+                ptr::null_mut(),
+                size,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_SHARED,
+                fd.as_raw_fd(),
+                0,
+            )
+        };
+        if addr == libc::MAP_FAILED {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(FdSegment {
+            fd,
+            addr: addr.cast(),
+            size,
+        })
+    }
+
+    /// The file descriptor of the segment, passed to the server by ShmAttachFd.
+    pub fn as_fd(&self) -> BorrowedFd<'_> {
+        self.fd.as_fd()
+    }
+
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    /// Returns the contents of the segment. See [ShmSegment::as_slice].
+    pub fn as_slice(&self) -> &[u8] {
+        unsafe { std::slice::from_raw_parts(self.addr, self.size) }
+    }
+
+    /// Returns the contents of the segment for writing.
+    pub fn as_mut_slice(&mut self) -> &mut [u8] {
+        unsafe { std::slice::from_raw_parts_mut(self.addr, self.size) }
+    }
+}
+
+impl Drop for FdSegment {
+    fn drop(&mut self) {
+        unsafe { libc::munmap(self.addr.cast(), self.size) };
+    }
+}
+
+/// Reply of the ShmCreateSegment request. The file descriptor of the segment is
+/// passed along with it.
+#[derive(Debug, Clone)]
+pub struct CreateSegmentReply {
+    /// Number of file descriptors passed with the reply, always 1.
+    pub nfd: u8,
+}
+
+impl Reply for CreateSegmentReply {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1  1                     nfd
+        // 2  CARD16                sequence number
+        // 4  0                     reply length
+        // 24                       unused
+        let rest = skip(bytes, 1)?;
+        let (nfd, _) = deserialize_into::<u8>(rest)?;
+        Ok(CreateSegmentReply { nfd })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        if self.nfd != 1 {
+            return Err(ParseError::InvalidValue {
+                type_name: "shm::CreateSegmentReply",
+                field: "nfd",
+                value: self.nfd.into(),
+            });
+        }
+        check_trailing_bytes("shm::CreateSegmentReply", bytes, 32)
+    }
+}
+
+/// Reply of the ShmQueryVersion request.
+#[derive(Debug, Clone)]
+pub struct QueryVersionReply {
+    /// Whether ShmCreatePixmap is supported.
+    pub shared_pixmaps: bool,
+    pub major_version: u16,
+    pub minor_version: u16,
+    /// The user and group the server accesses segments as.
+    pub uid: u16,
+    pub gid: u16,
+    /// The format of shared pixmaps, if they are supported.
+    pub pixmap_format: ImageFormat,
+}
+
+impl Reply for QueryVersionReply {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1  BOOL                  shared-pixmaps
+        // 2  CARD16                sequence number
+        // 4  0                     reply length
+        // 2  CARD16                major-version
+        // 2  CARD16                minor-version
+        // 2  CARD16                uid
+        // 2  CARD16                gid
+        // 1  CARD8                 pixmap-format
+        // 15                       unused
+        let rest = skip(bytes, 1)?;
+        let (shared_pixmaps, rest) = deserialize_into::<bool>(rest)?;
+        let rest = skip(rest, 6)?;
+        let (major_version, rest) = deserialize_into::<u16>(rest)?;
+        let (minor_version, rest) = deserialize_into::<u16>(rest)?;
+        let (uid, rest) = deserialize_into::<u16>(rest)?;
+        let (gid, rest) = deserialize_into::<u16>(rest)?;
+        let (pixmap_format, _) = deserialize_into::<u8>(rest)?;
+        Ok(QueryVersionReply {
+            shared_pixmaps,
+            major_version,
+            minor_version,
+            uid,
+            gid,
+            pixmap_format: ImageFormat(pixmap_format),
+        })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        check_trailing_bytes("shm::QueryVersionReply", bytes, 32)
+    }
+}
+
+/// Writes an image from a shared memory segment to a drawable.
+#[derive(Debug, Clone)]
+pub struct PutImage {
+    pub drawable: XId,
+    pub gc: XId,
+    /// Size of the whole image in the segment.
+    pub total_width: u16,
+    pub total_height: u16,
+    /// The part of the image to write.
+    pub src_x: u16,
+    pub src_y: u16,
+    pub src_width: u16,
+    pub src_height: u16,
+    /// Where to write the image in the drawable.
+    pub dst_x: i16,
+    pub dst_y: i16,
+    pub depth: u8,
+    pub format: ImageFormat,
+    /// Whether the server sends a [CompletionEvent] once it is done with the segment.
+    pub send_event: bool,
+    pub shmseg: XId,
+    /// Offset of the image in the segment.
+    pub offset: u32,
+}
+
+impl PutImage {
+    /// Converts the request to x11 raw bytes
+    //
+    //   1 byte     Major opcode
+    //   1 byte     Minor opcode (3)
+    //   2 bytes    Request length (10)
+    //   4 bytes    Drawable
+    //   4 bytes    GC
+    //   2 bytes    Total width
+    //   2 bytes    Total height
+    //   2 bytes    Source x
+    //   2 bytes    Source y
+    //   2 bytes    Source width
+    //   2 bytes    Source height
+    //   2 bytes    Destination x
+    //   2 bytes    Destination y
+    //   1 byte     Depth
+    //   1 byte     Format
+    //   1 byte     Send event
+    //   1 byte     Unused
+    //   4 bytes    Segment
+    //   4 bytes    Offset
+    pub fn serialize(&self, major_opcode: u8) -> Vec<u8> {
+        RequestBuilder::new(major_opcode, opcode::PUT_IMAGE)
+            .put(self.drawable)
+            .put(self.gc)
+            .put(self.total_width)
+            .put(self.total_height)
+            .put(self.src_x)
+            .put(self.src_y)
+            .put(self.src_width)
+            .put(self.src_height)
+            .put(self.dst_x)
+            .put(self.dst_y)
+            .put(self.depth)
+            .put(self.format.0)
+            .put(self.send_event)
+            .pad(1)
+            .put(self.shmseg)
+            .put(self.offset)
+            .finish()
+    }
+}
+
+/// Reads the contents of a drawable into a shared memory segment.
+#[derive(Debug, Clone)]
+pub struct GetImage {
+    pub drawable: XId,
+    pub x: i16,
+    pub y: i16,
+    pub width: u16,
+    pub height: u16,
+    pub plane_mask: u32,
+    pub format: ImageFormat,
+    pub shmseg: XId,
+    /// Offset in the segment to write the image to.
+    pub offset: u32,
+}
+
+impl GetImage {
+    /// Converts the request to x11 raw bytes
+    //
+    //   1 byte     Major opcode
+    //   1 byte     Minor opcode (4)
+    //   2 bytes    Request length (8)
+    //   4 bytes    Drawable
+    //   2 bytes    X
+    //   2 bytes    Y
+    //   2 bytes    Width
+    //   2 bytes    Height
+    //   4 bytes    Plane mask
+    //   1 byte     Format
+    //   3 bytes    Unused
+    //   4 bytes    Segment
+    //   4 bytes    Offset
+    pub fn serialize(&self, major_opcode: u8) -> Vec<u8> {
+        RequestBuilder::new(major_opcode, opcode::GET_IMAGE)
+            .put(self.drawable)
+            .put(self.x)
+            .put(self.y)
+            .put(self.width)
+            .put(self.height)
+            .put(self.plane_mask)
+            .put(self.format.0)
+            .pad(3)
+            .put(self.shmseg)
+            .put(self.offset)
+            .finish()
+    }
+}
+
+/// Reply of the ShmGetImage request. The image itself is in the segment.
+#[derive(Debug, Clone)]
+pub struct GetImageReply {
+    pub depth: u8,
+    pub visual: VisualId,
+    /// Number of bytes written to the segment.
+    pub size: u32,
+}
+
+impl Reply for GetImageReply {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1  CARD8                 depth
+        // 2  CARD16                sequence number
+        // 4  0                     reply length
+        // 4  VISUALID              visual
+        // 4  CARD32                size
+        // 16                       unused
+        let rest = skip(bytes, 1)?;
+        let (depth, rest) = deserialize_into::<u8>(rest)?;
+        let rest = skip(rest, 6)?;
+        let (visual, rest) = deserialize_into::<u32>(rest)?;
+        let (size, _) = deserialize_into::<u32>(rest)?;
+        Ok(GetImageReply {
+            depth,
+            visual,
+            size,
+        })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        check_trailing_bytes("shm::GetImageReply", bytes, 32)
+    }
+}
+
+/// Creates a pixmap whose pixels are stored in a shared memory segment.
+#[derive(Debug, Clone)]
+pub struct CreatePixmap {
+    pub pixmap: XId,
+    /// A drawable on the screen to create the pixmap for.
+    pub drawable: XId,
+    pub width: u16,
+    pub height: u16,
+    pub depth: u8,
+    pub shmseg: XId,
+    pub offset: u32,
+}
+
+impl CreatePixmap {
+    /// Converts the request to x11 raw bytes
+    //
+    //   1 byte     Major opcode
+    //   1 byte     Minor opcode (5)
+    //   2 bytes    Request length (7)
+    //   4 bytes    Pixmap
+    //   4 bytes    Drawable
+    //   2 bytes    Width
+    //   2 bytes    Height
+    //   1 byte     Depth
+    //   3 bytes    Unused
+    //   4 bytes    Segment
+    //   4 bytes    Offset
+    pub fn serialize(&self, major_opcode: u8) -> Vec<u8> {
+        RequestBuilder::new(major_opcode, opcode::CREATE_PIXMAP)
+            .put(self.pixmap)
+            .put(self.drawable)
+            .put(self.width)
+            .put(self.height)
+            .put(self.depth)
+            .pad(3)
+            .put(self.shmseg)
+            .put(self.offset)
+            .finish()
+    }
+}
+
+/// Sent when the server is done with the segment of a ShmPutImage that had
+/// `send_event` set, so the segment can be reused.
+#[derive(Debug, Clone)]
+pub struct CompletionEvent {
+    pub drawable: XId,
+    pub minor_event: u16,
+    pub major_event: u8,
+    pub shmseg: XId,
+    pub offset: u32,
+}
+
+impl CompletionEvent {
+    /// Parses the event if `event` is a completion event. `first_event` is the one
+    /// of the extension, see [Connection::extension].
+    //
+    //   1 byte     Code (first event)
+    //   1 byte     Unused
+    //   2 bytes    Sequence number
+    //   4 bytes    Drawable
+    //   2 bytes    Minor event
+    //   1 byte     Major event
+    //   1 byte     Unused
+    //   4 bytes    Segment
+    //   4 bytes    Offset
+    //   12 bytes   Unused
+    pub fn from_event(event: &Event, first_event: u8) -> Option<CompletionEvent> {
+        let Event::Unknown(bytes) = event else {
+            return None;
+        };
+        if bytes.first()? & 0x7f != first_event {
+            return None;
+        }
+        let rest = skip(bytes, 4).ok()?;
+        let (drawable, rest) = deserialize_into::<XId>(rest).ok()?;
+        let (minor_event, rest) = deserialize_into::<u16>(rest).ok()?;
+        let (major_event, rest) = deserialize_into::<u8>(rest).ok()?;
+        let rest = skip(rest, 1).ok()?;
+        let (shmseg, rest) = deserialize_into::<XId>(rest).ok()?;
+        let (offset, _) = deserialize_into::<u32>(rest).ok()?;
+        Some(CompletionEvent {
+            drawable,
+            minor_event,
+            major_event,
+            shmseg,
+            offset,
+        })
+    }
+}
+
+impl Connection {
+    /// Returns the version of the extension the server supports.
+    pub fn shm_query_version(&mut self) -> Result<Cookie<QueryVersionReply>, ConnectionError> {
+        let major_opcode = major_opcode(self)?;
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (0)
+        //   2 bytes    Request length (1)
+        let request = RequestBuilder::new(major_opcode, opcode::QUERY_VERSION).finish();
+        self.send_request_with_reply(&request)
+    }
+
+    /// Attaches a segment to the server and returns the id it is known by in
+    /// the other MIT-SHM requests.
+    pub fn shm_attach(
+        &mut self,
+        segment: &ShmSegment,
+        read_only: bool,
+    ) -> Result<XId, ConnectionError> {
+        let major_opcode = major_opcode(self)?;
+        let shmseg = self.generate_id()?;
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (1)
+        //   2 bytes    Request length (4)
+        //   4 bytes    Segment
+        //   4 bytes    Shmid
+        //   1 byte     Read only
+        //   3 bytes    Unused
+        let request = RequestBuilder::new(major_opcode, opcode::ATTACH)
+            .put(shmseg)
+            .put(segment.shmid())
+            .put(read_only)
+            .pad(3)
+            .finish();
+        self.send_request(&request)?;
+        Ok(shmseg)
+    }
+
+    /// Detaches a segment from the server.
+    pub fn shm_detach(&mut self, shmseg: XId) -> Result<u16, ConnectionError> {
+        let major_opcode = major_opcode(self)?;
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (2)
+        //   2 bytes    Request length (2)
+        //   4 bytes    Segment
+        let request = RequestBuilder::new(major_opcode, opcode::DETACH)
+            .put(shmseg)
+            .finish();
+        self.send_request(&request)
+    }
+
+    /// Attaches a file descriptor backed segment to the server and returns the
+    /// id it is known by. Requires MIT-SHM 1.2 and a unix socket.
+    pub fn shm_attach_fd(
+        &mut self,
+        segment: &FdSegment,
+        read_only: bool,
+    ) -> Result<XId, ConnectionError> {
+        let major_opcode = major_opcode(self)?;
+        let shmseg = self.generate_id()?;
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (6)
+        //   2 bytes    Request length (3)
+        //   4 bytes    Segment
+        //   1 byte     Read only
+        //   3 bytes    Unused
+        //   (1 file descriptor passed as ancillary data)
+        let request = RequestBuilder::new(major_opcode, opcode::ATTACH_FD)
+            .put(shmseg)
+            .put(read_only)
+            .pad(3)
+            .finish();
+        self.send_request_with_fds(&request, &[segment.as_fd()])?;
+        Ok(shmseg)
+    }
+
+    /// Has the server create a segment of `size` bytes, attached to it, and maps it
+    /// into this process. Returns the id of the segment along with the mapping.
+    /// Requires MIT-SHM 1.2 and a unix socket.
+    pub fn shm_create_segment(
+        &mut self,
+        size: u32,
+        read_only: bool,
+    ) -> Result<(XId, FdSegment), ConnectionError> {
+        let major_opcode = major_opcode(self)?;
+        let shmseg = self.generate_id()?;
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (7)
+        //   2 bytes    Request length (4)
+        //   4 bytes    Segment
+        //   4 bytes    Size
+        //   1 byte     Read only
+        //   3 bytes    Unused
+        let request = RequestBuilder::new(major_opcode, opcode::CREATE_SEGMENT)
+            .put(shmseg)
+            .put(size)
+            .put(read_only)
+            .pad(3)
+            .finish();
+        let cookie: Cookie<CreateSegmentReply> = self.send_request_with_fd_reply(&request, &[])?;
+        let (_, fds) = cookie.reply_with_fds(self)?;
+        let fd = fds
+            .into_iter()
+            .next()
+            .ok_or(ConnectionError::InvalidResponseFromServer)?;
+        let segment = FdSegment::from_fd(fd, size as usize)?;
+        Ok((shmseg, segment))
+    }
+
+    /// Writes an image from a segment to a drawable.
+    pub fn shm_put_image(&mut self, request: &PutImage) -> Result<u16, ConnectionError> {
+        let major_opcode = major_opcode(self)?;
+        self.send_request(&request.serialize(major_opcode))
+    }
+
+    /// Reads the contents of a drawable into a segment.
+    pub fn shm_get_image(
+        &mut self,
+        request: &GetImage,
+    ) -> Result<Cookie<GetImageReply>, ConnectionError> {
+        let major_opcode = major_opcode(self)?;
+        self.send_request_with_reply(&request.serialize(major_opcode))
+    }
+
+    /// Creates a pixmap backed by a segment.
+    pub fn shm_create_pixmap(&mut self, request: &CreatePixmap) -> Result<u16, ConnectionError> {
+        let major_opcode = major_opcode(self)?;
+        self.send_request(&request.serialize(major_opcode))
+    }
+}
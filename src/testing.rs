@@ -0,0 +1,657 @@
+//! A scripted X server, to test clients end to end without a display.
+//!
+//! [MockServer] listens on a temporary unix socket, answers the connection
+//! setup with the vendor, screens and pixmap formats it was built with, and
+//! records every request it receives so tests can assert on them. Requests are
+//! answered by the handlers given to [MockServerBuilder::on]; the others get
+//! no answer, except GetInputFocus, QueryExtension and ListExtensions which
+//! have defaults so that [Connection::sync] and [Connection::extension] work,
+//! the latter reporting the extensions given to [MockServerBuilder::extension].
+//!
+//! ```
+//! use mousetrap::request::opcode;
+//! use mousetrap::testing::{MockServer, Response};
+//! use std::time::Duration;
+//!
+//! let server = MockServer::builder()
+//!     .vendor("Mock")
+//!     .screen(1024, 768)
+//!     .on(opcode::GET_GEOMETRY, |request| {
+//!         // Root, x, y, width, height and border width of the drawable.
+//!         let mut body = request.bytes[4..8].to_vec();
+//!         for value in [0u16, 0, 300, 200, 1] {
+//!             body.extend_from_slice(&value.to_ne_bytes());
+//!         }
+//!         Response::Reply { data: 24, body }
+//!     })
+//!     .start()
+//!     .unwrap();
+//!
+//! let mut conn = server.connect().unwrap();
+//! assert_eq!(conn.setup().vendor.to_string(), "Mock");
+//! let root = conn.default_root();
+//! let geometry = conn.get_geometry(root).unwrap().reply(&mut conn).unwrap();
+//! assert_eq!((geometry.width, geometry.height), (300, 200));
+//!
+//! conn.map_window(root).unwrap();
+//! let request = server
+//!     .wait_for_request(opcode::MAP_WINDOW, Duration::from_secs(5))
+//!     .unwrap();
+//! assert_eq!(request.bytes[4..8], root.0.to_ne_bytes());
+//! ```
+//!
+//! The server speaks the byte order of the machine, as the client does.
+
+#[cfg(feature = "async-std")]
+use crate::aio::{AsyncIoDriver, AsyncStdConnection, AsyncStdDriver};
+use crate::connection::{Connection, ExtensionInfo, Stream};
+use crate::errors::ConnectionError;
+use crate::protocol::Format;
+use crate::request::opcode;
+#[cfg(all(target_os = "linux", feature = "io-uring"))]
+use crate::uring::UringConnection;
+#[cfg(test)]
+use crate::utils::Serialize;
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::io::{self, BufReader, Read, Write};
+use std::net::Shutdown;
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// Id of the root window of the first screen, the next screens counting up.
+pub const FIRST_ROOT: u32 = 0x100;
+
+/// Id of the TrueColor visual of the root windows.
+pub const ROOT_VISUAL: u32 = 0x21;
+
+/// Id of the default colormap of the screens.
+pub const DEFAULT_COLORMAP: u32 = 0x20;
+
+/// Numbers the sockets of the servers of the process.
+static NEXT_SERVER: AtomicUsize = AtomicUsize::new(0);
+
+/// What the server answers to a request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Response {
+    /// No answer, as for most requests.
+    None,
+    /// A reply. `data` is its second byte and `body` what follows the length,
+    /// padded with zeros to the 32 bytes of a reply at least. The sequence
+    /// number and the length are filled in.
+    Reply { data: u8, body: Vec<u8> },
+    /// An error with the given code and bad value.
+    Error { code: u8, bad_value: u32 },
+}
+
+/// A request the server received.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReceivedRequest {
+    pub major_opcode: u8,
+    /// The second byte, the minor opcode of extension requests.
+    pub data: u8,
+    pub sequence: u16,
+    /// The whole request, header included.
+    pub bytes: Vec<u8>,
+}
+
+/// The size and depth of a screen of the server.
+#[derive(Debug, Clone, Copy)]
+struct ScreenSize {
+    width: u16,
+    height: u16,
+    depth: u8,
+}
+
+type Handler = Box<dyn Fn(&ReceivedRequest) -> Response + Send>;
+
+/// Configures a [MockServer]. See [MockServer::builder].
+pub struct MockServerBuilder {
+    vendor: String,
+    release_number: u32,
+    /// The smallest and largest keycodes of the setup.
+    keycodes: (u8, u8),
+    screens: Vec<ScreenSize>,
+    pixmap_formats: Vec<Format>,
+    handlers: HashMap<u8, Handler>,
+    keep_requests: bool,
+    /// The extensions QueryExtension reports present, by name.
+    extensions: HashMap<String, ExtensionInfo>,
+}
+
+impl MockServerBuilder {
+    /// Sets the vendor string of the setup, "mousetrap mock" by default.
+    pub fn vendor(mut self, vendor: &str) -> Self {
+        self.vendor = vendor.to_owned();
+        self
+    }
+
+    /// Sets the release number of the setup.
+    pub fn release_number(mut self, release_number: u32) -> Self {
+        self.release_number = release_number;
+        self
+    }
+
+    /// Sets the range of keycodes of the setup, 8 to 255 by default. The
+    /// server does not check that `min` is at most `max`.
+    pub fn keycodes(mut self, min: u8, max: u8) -> Self {
+        self.keycodes = (min, max);
+        self
+    }
+
+    /// Adds a screen of depth 24. Without any, the server has a single screen
+    /// of 1920x1080.
+    pub fn screen(self, width: u16, height: u16) -> Self {
+        self.screen_with_depth(width, height, 24)
+    }
+
+    /// Adds a screen whose root window has a TrueColor visual of `depth` bits,
+    /// a third of them per channel, e.g. 30 for 10 bits per channel. The
+    /// pixmap format of the depth must be added too.
+    pub fn screen_with_depth(mut self, width: u16, height: u16, depth: u8) -> Self {
+        self.screens.push(ScreenSize {
+            width,
+            height,
+            depth,
+        });
+        self
+    }
+
+    /// Adds a pixmap format. Without any, the server has the formats of depth
+    /// 1, 24 and 32 of common servers.
+    pub fn pixmap_format(mut self, format: Format) -> Self {
+        self.pixmap_formats.push(format);
+        self
+    }
+
+    /// Answers the requests of a major opcode with `handler`, called on the
+    /// thread of the server.
+    pub fn on<F>(mut self, major_opcode: u8, handler: F) -> Self
+    where
+        F: Fn(&ReceivedRequest) -> Response + Send + 'static,
+    {
+        self.handlers.insert(major_opcode, Box::new(handler));
+        self
+    }
+
+    /// Reports an extension present, with the given codes, to QueryExtension.
+    /// The requests with its major opcode are still answered by the handler
+    /// given to [MockServerBuilder::on], if any.
+    pub fn extension(mut self, name: &str, info: ExtensionInfo) -> Self {
+        self.extensions.insert(name.to_owned(), info);
+        self
+    }
+
+    /// Stops keeping the requests received, so that a client sending many, such
+    /// as a benchmark, does not fill the memory. [MockServer::requests] is then
+    /// empty and [MockServer::wait_for_request] finds nothing.
+    pub fn discard_requests(mut self) -> Self {
+        self.keep_requests = false;
+        self
+    }
+
+    /// Starts listening, on a thread which serves one client at a time.
+    pub fn start(mut self) -> io::Result<MockServer> {
+        if self.screens.is_empty() {
+            self.screens.push(ScreenSize {
+                width: 1920,
+                height: 1080,
+                depth: 24,
+            });
+        }
+        if self.pixmap_formats.is_empty() {
+            for (depth, bits_per_pixel) in [(1, 1), (24, 32), (32, 32)] {
+                self.pixmap_formats.push(Format {
+                    depth,
+                    bits_per_pixel,
+                    scanline_pad: 32,
+                });
+            }
+        }
+        let path = env::temp_dir().join(format!(
+            "mousetrap-mock-{}-{}",
+            process::id(),
+            NEXT_SERVER.fetch_add(1, Ordering::Relaxed)
+        ));
+        let _ = fs::remove_file(&path);
+        let listener = UnixListener::bind(&path)?;
+        let shared = Arc::new(Shared::default());
+        let thread = {
+            let shared = Arc::clone(&shared);
+            thread::spawn(move || serve(listener, self, &shared))
+        };
+        Ok(MockServer {
+            path,
+            shared,
+            thread: Some(thread),
+        })
+    }
+}
+
+/// What the server shares with its handle.
+#[derive(Default)]
+struct Shared {
+    stop: AtomicBool,
+    requests: Mutex<Vec<ReceivedRequest>>,
+    /// Notified with each request.
+    received: Condvar,
+    /// The current client and the sequence number of its last request, for
+    /// the events sent by [MockServer::send_event].
+    client: Mutex<Option<(UnixStream, u16)>>,
+}
+
+/// An X server answering from a script. Dropping it stops it.
+pub struct MockServer {
+    path: PathBuf,
+    shared: Arc<Shared>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl MockServer {
+    /// Starts configuring a server.
+    pub fn builder() -> MockServerBuilder {
+        MockServerBuilder {
+            vendor: String::from("mousetrap mock"),
+            release_number: 1,
+            keycodes: (8, 255),
+            screens: Vec::new(),
+            pixmap_formats: Vec::new(),
+            handlers: HashMap::new(),
+            extensions: HashMap::new(),
+            keep_requests: true,
+        }
+    }
+
+    /// Returns the path of the socket the server listens on.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Connects a client to the server.
+    pub fn connect(&self) -> Result<Connection, ConnectionError> {
+        let stream = Stream::connect_unix_socket(&self.path.to_string_lossy())?;
+        Connection::with_stream(stream, 0, 0)
+    }
+
+    /// Connects a client driven by io_uring to the server.
+    #[cfg(all(target_os = "linux", feature = "io-uring"))]
+    pub fn connect_uring(&self) -> Result<UringConnection, ConnectionError> {
+        let stream = Stream::connect_unix_socket(&self.path.to_string_lossy())?;
+        UringConnection::with_stream(stream, 0, 0)
+    }
+
+    /// Connects an async client, driven by async-io, to the server.
+    #[cfg(feature = "async-std")]
+    pub async fn connect_async_std(&self) -> Result<AsyncStdConnection, ConnectionError> {
+        let driver = AsyncStdDriver::connect_unix(&self.path.to_string_lossy()).await?;
+        AsyncStdConnection::with_driver(driver, 0, 0).await
+    }
+
+    /// Returns the requests received so far, oldest first.
+    pub fn requests(&self) -> Vec<ReceivedRequest> {
+        lock(&self.shared.requests).clone()
+    }
+
+    /// Returns the first request received with `major_opcode`, waiting at
+    /// most `timeout` for one.
+    pub fn wait_for_request(&self, major_opcode: u8, timeout: Duration) -> Option<ReceivedRequest> {
+        self.wait_for_requests(major_opcode, 1, timeout)
+            .map(|mut requests| requests.swap_remove(0))
+    }
+
+    /// Returns the requests received with `major_opcode`, oldest first, once
+    /// there are at least `count`, waiting at most `timeout` for them.
+    pub fn wait_for_requests(
+        &self,
+        major_opcode: u8,
+        count: usize,
+        timeout: Duration,
+    ) -> Option<Vec<ReceivedRequest>> {
+        let deadline = Instant::now() + timeout;
+        let mut requests = lock(&self.shared.requests);
+        loop {
+            let found: Vec<_> = requests
+                .iter()
+                .filter(|request| request.major_opcode == major_opcode)
+                .cloned()
+                .collect();
+            if found.len() >= count.max(1) {
+                return Some(found);
+            }
+            let left = deadline.checked_duration_since(Instant::now())?;
+            requests = match self.shared.received.wait_timeout(requests, left) {
+                Ok((requests, _)) => requests,
+                Err(poisoned) => poisoned.into_inner().0,
+            };
+        }
+    }
+
+    /// Sends an event to the current client, with the sequence number of its
+    /// last request filled in.
+    pub fn send_event(&self, mut event: [u8; 32]) -> io::Result<()> {
+        let mut client = lock(&self.shared.client);
+        let (stream, sequence) = client
+            .as_mut()
+            .ok_or_else(|| io::Error::from(io::ErrorKind::NotConnected))?;
+        event[2..4].copy_from_slice(&sequence.to_ne_bytes());
+        stream.write_all(&event)
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.shared.stop.store(true, Ordering::Relaxed);
+        if let Some((stream, _)) = lock(&self.shared.client).as_ref() {
+            let _ = stream.shutdown(Shutdown::Both);
+        }
+        // Ends the wait for a client.
+        let _ = UnixStream::connect(&self.path);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn serve(listener: UnixListener, config: MockServerBuilder, shared: &Shared) {
+    for stream in listener.incoming() {
+        if shared.stop.load(Ordering::Relaxed) {
+            return;
+        }
+        let Ok(stream) = stream else {
+            continue;
+        };
+        // The client is gone once reading or writing fails.
+        let _ = serve_client(stream, &config, shared);
+        *lock(&shared.client) = None;
+    }
+}
+
+fn serve_client(
+    mut stream: UnixStream,
+    config: &MockServerBuilder,
+    shared: &Shared,
+) -> io::Result<()> {
+    //   1 byte     Byte order
+    //   1 byte     Unused
+    //   2 bytes    Protocol major version
+    //   2 bytes    Protocol minor version
+    //   2 bytes    Length of the authorization protocol name (n)
+    //   2 bytes    Length of the authorization protocol data (d)
+    //   2 bytes    Unused
+    //   n+p+d+q    Name and data, each padded to 4 bytes
+    let mut header = [0u8; 12];
+    stream.read_exact(&mut header)?;
+    let name_length = usize::from(u16::from_ne_bytes([header[6], header[7]]));
+    let data_length = usize::from(u16::from_ne_bytes([header[8], header[9]]));
+    let mut auth = vec![0u8; name_length.next_multiple_of(4) + data_length.next_multiple_of(4)];
+    stream.read_exact(&mut auth)?;
+    stream.write_all(&setup_bytes(config))?;
+    *lock(&shared.client) = Some((stream.try_clone()?, 0));
+
+    let mut stream = BufReader::new(stream);
+    let mut sequence = 0u16;
+    loop {
+        let mut header = [0u8; 4];
+        stream.read_exact(&mut header)?;
+        let mut length = usize::from(u16::from_ne_bytes([header[2], header[3]])) * 4;
+        let mut bytes = header.to_vec();
+        // A length of 0 announces the 4-byte length of BIG-REQUESTS.
+        if length == 0 {
+            let mut big_length = [0u8; 4];
+            stream.read_exact(&mut big_length)?;
+            bytes.extend_from_slice(&big_length);
+            length = u32::from_ne_bytes(big_length) as usize * 4;
+        }
+        let start = bytes.len();
+        bytes.resize(length.max(start), 0);
+        stream.read_exact(&mut bytes[start..])?;
+        sequence = sequence.wrapping_add(1);
+        let request = ReceivedRequest {
+            major_opcode: header[0],
+            data: header[1],
+            sequence,
+            bytes,
+        };
+
+        let response = match config.handlers.get(&request.major_opcode) {
+            Some(handler) => handler(&request),
+            None => default_response(config, &request),
+        };
+        {
+            let mut client = lock(&shared.client);
+            if let Some((_, last)) = client.as_mut() {
+                *last = sequence;
+            }
+            let bytes = response_bytes(&request, response);
+            if let Some((stream, _)) = client.as_mut() {
+                stream.write_all(&bytes)?;
+            }
+        }
+        if config.keep_requests {
+            lock(&shared.requests).push(request);
+        }
+        shared.received.notify_all();
+    }
+}
+
+/// Answers the requests a client sends on its own, e.g. in [Connection::sync].
+fn default_response(config: &MockServerBuilder, request: &ReceivedRequest) -> Response {
+    match request.major_opcode {
+        // PointerRoot, with RevertToPointerRoot.
+        opcode::GET_INPUT_FOCUS => Response::Reply {
+            data: 1,
+            body: 1u32.to_ne_bytes().to_vec(),
+        },
+        // The codes of the extensions given to MockServerBuilder::extension,
+        // the others being absent.
+        opcode::QUERY_EXTENSION => {
+            let length = usize::from(u16::from_ne_bytes([request.bytes[4], request.bytes[5]]));
+            let name = request.bytes.get(8..8 + length).unwrap_or_default();
+            let info = String::from_utf8(name.to_vec())
+                .ok()
+                .and_then(|name| config.extensions.get(&name));
+            let body = match info {
+                Some(info) => vec![1, info.major_opcode, info.first_event, info.first_error],
+                None => Vec::new(),
+            };
+            Response::Reply { data: 0, body }
+        }
+        // Their names, each preceded by its length.
+        opcode::LIST_EXTENSIONS => {
+            let mut body = Vec::new();
+            for name in config.extensions.keys() {
+                body.push(name.len() as u8);
+                body.extend_from_slice(name.as_bytes());
+            }
+            Response::Reply {
+                data: config.extensions.len() as u8,
+                body,
+            }
+        }
+        _ => Response::None,
+    }
+}
+
+/// Returns a reply, its body padded to at least 24 bytes and to a 4-byte boundary.
+fn reply_bytes(sequence: u16, data: u8, mut body: Vec<u8>) -> Vec<u8> {
+    body.resize(body.len().max(24).next_multiple_of(4), 0);
+    let length = (body.len() as u32 - 24) / 4;
+    let sequence = sequence.to_ne_bytes();
+    let mut bytes = vec![1, data, sequence[0], sequence[1]];
+    bytes.extend_from_slice(&length.to_ne_bytes());
+    bytes.extend_from_slice(&body);
+    bytes
+}
+
+/// Starts a server with the RANDR extension, at major opcode 140 and with its
+/// errors from 147, failing every RANDR request with BadCrtc.
+#[cfg(test)]
+pub(crate) fn randr_server() -> MockServer {
+    let info = ExtensionInfo {
+        major_opcode: 140,
+        first_event: 89,
+        first_error: 147,
+    };
+    MockServer::builder()
+        .extension(crate::randr::NAME, info)
+        .on(info.major_opcode, |_| Response::Error {
+            code: 148,
+            bad_value: 0,
+        })
+        .start()
+        .unwrap()
+}
+
+/// Writes a message the way the server sends it, in the byte order of the
+/// machine, for the tests of the parsers.
+#[cfg(test)]
+#[derive(Default)]
+pub(crate) struct MessageBuilder {
+    bytes: Vec<u8>,
+}
+
+#[cfg(test)]
+impl MessageBuilder {
+    pub(crate) fn put<T: Serialize>(&mut self, value: T) -> &mut Self {
+        value.serialize_into(&mut self.bytes);
+        self
+    }
+
+    pub(crate) fn put_bytes(&mut self, bytes: &[u8]) -> &mut Self {
+        self.bytes.extend_from_slice(bytes);
+        self
+    }
+
+    pub(crate) fn pad(&mut self, n: usize) -> &mut Self {
+        self.bytes.resize(self.bytes.len() + n, 0);
+        self
+    }
+
+    /// Returns a reply with the given second byte, of which the bytes written
+    /// follow the length field.
+    pub(crate) fn reply(&mut self, data: u8) -> Vec<u8> {
+        reply_bytes(1, data, std::mem::take(&mut self.bytes))
+    }
+
+    /// Returns an event with the given code, of which the bytes written follow
+    /// the code, padded to 32 bytes.
+    pub(crate) fn event(&mut self, code: u8) -> Vec<u8> {
+        let mut bytes = vec![code];
+        bytes.append(&mut self.bytes);
+        bytes.resize(bytes.len().max(32), 0);
+        bytes
+    }
+}
+
+fn response_bytes(request: &ReceivedRequest, response: Response) -> Vec<u8> {
+    let sequence = request.sequence.to_ne_bytes();
+    match response {
+        Response::None => Vec::new(),
+        Response::Reply { data, body } => reply_bytes(request.sequence, data, body),
+        Response::Error { code, bad_value } => {
+            //   1 byte     0 (Error)
+            //   1 byte     Code
+            //   2 bytes    Sequence number
+            //   4 bytes    Bad value
+            //   2 bytes    Minor opcode
+            //   1 byte     Major opcode
+            //   21 bytes   Unused
+            let mut bytes = vec![0, code, sequence[0], sequence[1]];
+            bytes.extend_from_slice(&bad_value.to_ne_bytes());
+            bytes.extend_from_slice(&u16::from(request.data).to_ne_bytes());
+            bytes.push(request.major_opcode);
+            bytes.resize(32, 0);
+            bytes
+        }
+    }
+}
+
+/// Serializes the server's answer to the connection setup.
+pub(crate) fn setup_bytes(config: &MockServerBuilder) -> Vec<u8> {
+    let vendor = config.vendor.as_bytes();
+    let mut data = Vec::new();
+    data.extend_from_slice(&config.release_number.to_ne_bytes());
+    // Resource id base and mask.
+    data.extend_from_slice(&0x0040_0000u32.to_ne_bytes());
+    data.extend_from_slice(&0x001f_ffffu32.to_ne_bytes());
+    // Motion buffer size.
+    data.extend_from_slice(&256u32.to_ne_bytes());
+    data.extend_from_slice(&(vendor.len() as u16).to_ne_bytes());
+    // Maximum request length.
+    data.extend_from_slice(&u16::MAX.to_ne_bytes());
+    data.push(config.screens.len() as u8);
+    data.push(config.pixmap_formats.len() as u8);
+    // Image byte order (LSBFirst or MSBFirst like the machine), bitmap bit
+    // order, scanline unit and pad, and the range of keycodes.
+    let byte_order = u8::from(cfg!(target_endian = "big"));
+    let (min_keycode, max_keycode) = config.keycodes;
+    data.extend_from_slice(&[byte_order, 0, 32, 32, min_keycode, max_keycode, 0, 0, 0, 0]);
+    data.extend_from_slice(vendor);
+    data.resize(data.len().next_multiple_of(4), 0);
+    for format in &config.pixmap_formats {
+        data.extend_from_slice(&[format.depth, format.bits_per_pixel, format.scanline_pad]);
+        data.extend_from_slice(&[0; 5]);
+    }
+    for (i, screen) in config.screens.iter().enumerate() {
+        data.extend_from_slice(&(FIRST_ROOT + i as u32).to_ne_bytes());
+        data.extend_from_slice(&DEFAULT_COLORMAP.to_ne_bytes());
+        // White and black pixels, current input masks.
+        data.extend_from_slice(&0x00ff_ffffu32.to_ne_bytes());
+        data.extend_from_slice(&0u32.to_ne_bytes());
+        data.extend_from_slice(&0u32.to_ne_bytes());
+        // Size in pixels and millimetres, at 96 dpi.
+        let mm = |pixels: u16| (u32::from(pixels) * 254 / 960) as u16;
+        for value in [
+            screen.width,
+            screen.height,
+            mm(screen.width),
+            mm(screen.height),
+        ] {
+            data.extend_from_slice(&value.to_ne_bytes());
+        }
+        // Minimum and maximum installed maps.
+        data.extend_from_slice(&1u16.to_ne_bytes());
+        data.extend_from_slice(&1u16.to_ne_bytes());
+        data.extend_from_slice(&ROOT_VISUAL.to_ne_bytes());
+        // Backing stores, save unders, root depth and number of depths.
+        data.extend_from_slice(&[0, 0, screen.depth, 1]);
+        // The depth of the root window with a TrueColor visual.
+        data.extend_from_slice(&[screen.depth, 0]);
+        data.extend_from_slice(&1u16.to_ne_bytes());
+        data.extend_from_slice(&[0; 4]);
+        data.extend_from_slice(&ROOT_VISUAL.to_ne_bytes());
+        let bits = screen.depth / 3;
+        data.extend_from_slice(&[4, bits]);
+        data.extend_from_slice(&(1u16 << bits).to_ne_bytes());
+        let channel = (1u32 << bits) - 1;
+        for mask in [channel << (2 * bits), channel << bits, channel] {
+            data.extend_from_slice(&mask.to_ne_bytes());
+        }
+        data.extend_from_slice(&[0; 4]);
+    }
+
+    //   1 byte     1 (Success)
+    //   1 byte     Unused
+    //   2 bytes    Protocol major version
+    //   2 bytes    Protocol minor version
+    //   2 bytes    Length in 4-byte units of the data
+    let mut bytes = vec![1, 0];
+    bytes.extend_from_slice(&11u16.to_ne_bytes());
+    bytes.extend_from_slice(&0u16.to_ne_bytes());
+    bytes.extend_from_slice(&((data.len() / 4) as u16).to_ne_bytes());
+    bytes.extend_from_slice(&data);
+    bytes
+}
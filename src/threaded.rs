@@ -0,0 +1,448 @@
+//! A connection whose socket is read by a thread of its own. Requires the
+//! `reader-thread` feature.
+//!
+//! The reader thread sorts what the server sends as soon as it arrives: the reply
+//! to a request goes to the channel of its [Cookie], and events go to a bounded
+//! channel. Unlike [crate::shared::SharedConnection], where messages are only read
+//! while a thread waits on one, the socket is read even while every thread is busy,
+//! and a thread slow to call [Cookie::reply] holds up no other reply.
+//!
+//! If the event channel is full, the reader thread waits until it has room, which
+//! also holds up the replies behind those events. Size it for the bursts of events
+//! expected, or consume the events on a thread of their own. As replies and events
+//! go to separate channels, an event sent before a reply may be received after it.
+//!
+//! ```no_run
+//! # use mousetrap::threaded::ThreadedConnection;
+//! # use mousetrap::reply::InternAtomReply;
+//! # use mousetrap::request::InternAtom;
+//! let conn = ThreadedConnection::init(1024).unwrap();
+//! let events = conn.events().clone();
+//! std::thread::spawn(move || {
+//!     for event in events {
+//!         println!("{:?}", event);
+//!     }
+//! });
+//! let request = InternAtom {
+//!     only_if_exists: false,
+//!     name: b"_NET_WM_NAME",
+//! };
+//! let cookie = conn
+//!     .send_request_with_reply::<InternAtomReply>(&request.serialize())
+//!     .unwrap();
+//! println!("atom: {:?}", cookie.reply(&conn).unwrap().atom);
+//! ```
+
+use crate::connection::{parse_conf, ExtensionInfo, Stream};
+use crate::cookie::{parse_reply, ParseMode, Reply};
+use crate::errors::{ConnectionError, XError};
+use crate::event::Event;
+use crate::protocol::{ConnSetup, Screen, Window, XId};
+use crate::protocol_state::ProtocolState;
+use crate::reply::{InputFocusReply, QueryExtensionReply};
+use crate::request::{GetInputFocus, QueryExtension};
+use crossbeam_channel::{bounded, Receiver, RecvTimeoutError, Sender, TryRecvError};
+use std::collections::HashMap;
+use std::env;
+use std::io;
+use std::marker::PhantomData;
+use std::net::Shutdown;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::thread;
+use std::time::Duration;
+
+/// The reply to a request, or the error it caused.
+type Response = Result<Vec<u8>, ConnectionError>;
+
+/// The protocol state, along with where the replies go.
+struct Router {
+    protocol: ProtocolState,
+    /// Channels of the cookies waiting on a reply, by sequence number.
+    waiters: HashMap<u16, Sender<Response>>,
+}
+
+impl Router {
+    /// Sends the replies that arrived to the cookies waiting on them.
+    fn forward_replies(&mut self) {
+        let protocol = &mut self.protocol;
+        self.waiters
+            .retain(|&sequence, waiter| match protocol.take_response(sequence) {
+                Some(response) => {
+                    // The cookie may have been dropped without waiting.
+                    let _ = waiter.send(response);
+                    false
+                }
+                None => true,
+            });
+    }
+}
+
+/// A connection read by a thread of its own. See the [module](self) documentation.
+pub struct ThreadedConnection {
+    /// The server's response to the connection setup.
+    setup: ConnSetup,
+    /// Index of the screen given in the display name.
+    screen_number: usize,
+    /// The socket requests are written to. Locked while a request is written.
+    writer: Mutex<Stream>,
+    /// Shared with the reader thread.
+    router: Arc<Mutex<Router>>,
+    /// Events, along with the messages the reader thread could not parse.
+    events: Receiver<Result<Event, ConnectionError>>,
+}
+
+/// A handle to the reply of a request sent through a [ThreadedConnection].
+#[derive(Debug)]
+#[must_use = "the reply should be retrieved, otherwise it is kept in memory"]
+pub struct Cookie<R> {
+    /// Sequence number of the request.
+    pub sequence: u16,
+    response: Receiver<Response>,
+    reply: PhantomData<R>,
+}
+
+impl<R: Reply> Cookie<R> {
+    /// Blocks until the reply arrives and parses it.
+    pub fn reply(self, conn: &ThreadedConnection) -> Result<R, ConnectionError> {
+        let bytes = self
+            .response
+            .recv()
+            .map_err(|_| ConnectionError::Disconnected)??;
+        parse_reply(&bytes, conn.parse_mode())
+    }
+}
+
+/// Locks a mutex, ignoring that a thread panicked while holding it: the protected
+/// state is consistent between statements.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Reads from the server until the connection is closed or the connection and
+/// its event channel are dropped.
+fn read_loop(
+    mut stream: Stream,
+    router: Arc<Mutex<Router>>,
+    events: Sender<Result<Event, ConnectionError>>,
+) {
+    let mut buf = vec![0u8; 65536];
+    loop {
+        let read = match stream.read(&mut buf) {
+            Ok(0) => Err(io::Error::from(io::ErrorKind::UnexpectedEof)),
+            Ok(n) => Ok(n),
+            Err(ref e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => Err(e),
+        };
+
+        let mut received = Vec::new();
+        let disconnected = {
+            let mut router = lock(&router);
+            match read {
+                Ok(n) => {
+                    let mut bytes = &buf[..n];
+                    // A message that can not be parsed is skipped; the ones after
+                    // it are still dispatched.
+                    while let Err(err) = router.protocol.bytes_received(bytes) {
+                        received.push(Err(err));
+                        bytes = &[];
+                    }
+                }
+                Err(err) => {
+                    // Nothing more can be read either way, so an error that does not
+                    // close the connection is reported and the connection closed.
+                    let err = router.protocol.connection_lost(err);
+                    if !matches!(err, ConnectionError::Disconnected) {
+                        received.push(Err(err));
+                        let closed = io::Error::from(io::ErrorKind::ConnectionAborted);
+                        router.protocol.connection_lost(closed);
+                    }
+                }
+            }
+            router.forward_replies();
+            while let Some(event) = router.protocol.take_event() {
+                received.push(Ok(event));
+            }
+            let disconnected = router.protocol.is_disconnected();
+            if disconnected {
+                // The cookies still waiting fail with Disconnected.
+                router.waiters.clear();
+            }
+            disconnected
+        };
+
+        // Sent without the lock, as this waits while the channel is full.
+        for event in received {
+            if events.send(event).is_err() {
+                return;
+            }
+        }
+        if disconnected {
+            return;
+        }
+    }
+}
+
+impl ThreadedConnection {
+    /// Connects to the display named by `DISPLAY` and starts the reader thread.
+    /// At most `event_capacity` events wait in the event channel.
+    pub fn init(event_capacity: usize) -> Result<Self, ConnectionError> {
+        let display_name = env::var("DISPLAY").map_err(|_| ConnectionError::DisplayNotSet)?;
+        let conf = parse_conf(display_name);
+        let stream = Stream::open(&conf)?;
+        Self::with_stream(
+            stream,
+            conf.display_number,
+            conf.screen_number,
+            event_capacity,
+        )
+    }
+
+    pub(crate) fn with_stream(
+        mut stream: Stream,
+        display_number: u8,
+        screen_number: usize,
+        event_capacity: usize,
+    ) -> Result<Self, ConnectionError> {
+        let setup = stream.authenticate(display_number)?;
+        let reader = stream.try_clone()?;
+
+        let router = Arc::new(Mutex::new(Router {
+            protocol: ProtocolState::new(setup.clone(), screen_number),
+            waiters: HashMap::new(),
+        }));
+        let (sender, events) = bounded(event_capacity);
+        thread::Builder::new()
+            .name(String::from("mousetrap-reader"))
+            .spawn({
+                let router = router.clone();
+                move || read_loop(reader, router, sender)
+            })?;
+
+        Ok(ThreadedConnection {
+            setup,
+            screen_number,
+            writer: Mutex::new(stream),
+            router,
+            events,
+        })
+    }
+
+    /// Returns the server's response to the connection setup.
+    pub fn setup(&self) -> &ConnSetup {
+        &self.setup
+    }
+
+    /// Returns the screen given in the display name, or the first screen if the
+    /// server has no such screen.
+    pub fn default_screen(&self) -> &Screen {
+        let roots = &self.setup.roots;
+        roots.get(self.screen_number).unwrap_or(&roots[0])
+    }
+
+    /// Returns the root window of the default screen.
+    pub fn default_root(&self) -> Window {
+        self.default_screen().root
+    }
+
+    /// Allocates a new id for a resource such as a window or a pixmap.
+    pub fn generate_id(&self) -> Result<XId, ConnectionError> {
+        lock(&self.router).protocol.generate_id()
+    }
+
+    /// Sets how strictly replies are parsed.
+    pub fn set_parse_mode(&self, mode: ParseMode) {
+        lock(&self.router).protocol.set_parse_mode(mode);
+    }
+
+    /// Returns how strictly replies are parsed.
+    pub fn parse_mode(&self) -> ParseMode {
+        lock(&self.router).protocol.parse_mode()
+    }
+
+    /// Sets the function called with the errors of unchecked requests. By default
+    /// these errors are logged as warnings through the `log` crate.
+    ///
+    /// It is called on the reader thread with the connection's state locked, so it
+    /// must not use the connection.
+    pub fn set_error_handler(&self, handler: Box<dyn Fn(XError) + Send>) {
+        lock(&self.router).protocol.set_error_handler(handler);
+    }
+
+    /// Sends a serialized request to the server and returns its sequence number.
+    ///
+    /// The request is unchecked: an error caused by it is passed to the error
+    /// handler.
+    pub fn send_request(&self, request: &[u8]) -> Result<u16, ConnectionError> {
+        self.send(request, None)
+    }
+
+    /// Sends a serialized request that has a reply and returns the cookie of the reply.
+    /// An error caused by the request is returned by [Cookie::reply].
+    pub fn send_request_with_reply<R: Reply>(
+        &self,
+        request: &[u8],
+    ) -> Result<Cookie<R>, ConnectionError> {
+        let (waiter, response) = bounded(1);
+        let sequence = self.send(request, Some(waiter))?;
+        Ok(Cookie {
+            sequence,
+            response,
+            reply: PhantomData,
+        })
+    }
+
+    fn send(
+        &self,
+        request: &[u8],
+        waiter: Option<Sender<Response>>,
+    ) -> Result<u16, ConnectionError> {
+        let mut writer = lock(&self.writer);
+        // The request is numbered before it is written, so the reader thread knows
+        // it before its reply or error arrives.
+        let sequence = {
+            let mut router = lock(&self.router);
+            if router.protocol.is_disconnected() {
+                return Err(ConnectionError::Disconnected);
+            }
+            router.protocol.check_request_length(request)?;
+            let sequence = router.protocol.request_sent(request, waiter.is_some());
+            if let Some(waiter) = waiter {
+                router.waiters.insert(sequence, waiter);
+            }
+            sequence
+        };
+        if let Err(err) = writer.write_all(request) {
+            return Err(lock(&self.router).protocol.connection_lost(err));
+        }
+        Ok(sequence)
+    }
+
+    /// Waits until the server processed every request sent so far and returns the
+    /// errors caused by unchecked requests that the reader thread did not pass to
+    /// the error handler yet. Only one thread should sync at a time.
+    pub fn sync(&self) -> Result<Vec<XError>, ConnectionError> {
+        lock(&self.router).protocol.begin_sync();
+        let result = self
+            .send_request_with_reply::<InputFocusReply>(&GetInputFocus.serialize())
+            .and_then(|cookie| cookie.reply(self));
+        let errors = lock(&self.router).protocol.end_sync();
+        result.map(|_| errors)
+    }
+
+    /// Returns the codes of the extension `name`, or `None` if the server does not
+    /// support it. The server is only asked the first time.
+    pub fn extension(&self, name: &'static str) -> Result<Option<ExtensionInfo>, ConnectionError> {
+        if let Some(info) = lock(&self.router).protocol.extension(name) {
+            return Ok(info);
+        }
+        let request = QueryExtension {
+            name: name.as_bytes(),
+        };
+        let reply = self
+            .send_request_with_reply::<QueryExtensionReply>(&request.serialize())?
+            .reply(self)?;
+        Ok(lock(&self.router).protocol.extension_queried(name, &reply))
+    }
+
+    /// Returns the codes of an extension, failing if the server does not support
+    /// it, and registers the names of its requests and errors, see
+    /// [ProtocolState::register_extension].
+    pub fn require_extension(
+        &self,
+        name: &'static str,
+        request_names: &'static [&'static str],
+        error_names: &'static [&'static str],
+    ) -> Result<ExtensionInfo, ConnectionError> {
+        let info = self
+            .extension(name)?
+            .ok_or(ConnectionError::ExtensionNotSupported(name))?;
+        lock(&self.router)
+            .protocol
+            .register_extension(name, info, request_names, error_names);
+        Ok(info)
+    }
+
+    /// Returns the channel of the events, e.g. to wait on it along with other
+    /// channels with `crossbeam_channel::select!`. It disconnects after
+    /// [Event::ConnectionClosed].
+    pub fn events(&self) -> &Receiver<Result<Event, ConnectionError>> {
+        &self.events
+    }
+
+    /// Returns the next event, blocking until one arrives.
+    pub fn wait_for_event(&self) -> Result<Event, ConnectionError> {
+        self.events
+            .recv()
+            .map_err(|_| ConnectionError::Disconnected)?
+    }
+
+    /// Returns the next event, blocking until one arrives or `timeout` elapsed, in
+    /// which case `None` is returned.
+    pub fn wait_for_event_timeout(
+        &self,
+        timeout: Duration,
+    ) -> Result<Option<Event>, ConnectionError> {
+        match self.events.recv_timeout(timeout) {
+            Ok(event) => event.map(Some),
+            Err(RecvTimeoutError::Timeout) => Ok(None),
+            Err(RecvTimeoutError::Disconnected) => Err(ConnectionError::Disconnected),
+        }
+    }
+
+    /// Returns the next event if one is available, without blocking.
+    pub fn poll_for_event(&self) -> Result<Option<Event>, ConnectionError> {
+        match self.events.try_recv() {
+            Ok(event) => event.map(Some),
+            Err(TryRecvError::Empty) => Ok(None),
+            Err(TryRecvError::Disconnected) => Err(ConnectionError::Disconnected),
+        }
+    }
+}
+
+impl Drop for ThreadedConnection {
+    /// Closes the socket, which ends the reader thread.
+    fn drop(&mut self) {
+        let _ = lock(&self.writer).shutdown(Shutdown::Both);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::randr;
+    use crate::request::{opcode, RequestBuilder};
+    use crate::testing::randr_server;
+
+    #[test]
+    fn extension_errors_reach_the_error_handler_or_sync() {
+        let server = randr_server();
+        let stream = Stream::connect_unix_socket(&server.path().to_string_lossy()).unwrap();
+        let conn = ThreadedConnection::with_stream(stream, 0, 0, 16).unwrap();
+        let info = conn
+            .require_extension(randr::NAME, randr::REQUEST_NAMES, randr::ERROR_NAMES)
+            .unwrap();
+        // The reader thread may read the error before the sync starts.
+        let (sender, receiver) = std::sync::mpsc::channel();
+        conn.set_error_handler(Box::new(move |error| {
+            let _ = sender.send(error);
+        }));
+        conn.send_request(&RequestBuilder::new(info.major_opcode, 2).finish())
+            .unwrap();
+        let mut errors = conn.sync().unwrap();
+        errors.extend(receiver.try_iter());
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].error_name.as_deref(), Some("RANDR:BadCrtc"));
+        // Asked about once, from whichever thread.
+        std::thread::scope(|scope| {
+            scope.spawn(|| conn.extension(randr::NAME).unwrap());
+        });
+        assert_eq!(
+            server
+                .requests()
+                .iter()
+                .filter(|request| request.major_opcode == opcode::QUERY_EXTENSION)
+                .count(),
+            1
+        );
+    }
+}
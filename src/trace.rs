@@ -0,0 +1,76 @@
+//! Logging of the traffic of a connection, to debug protocol issues without a
+//! packet capture.
+//!
+//! Once enabled with [Connection::set_trace], every request sent is logged
+//! with its name, sequence number and length, and every message read with a
+//! decoded summary: the request a reply answers, the error with its request,
+//! or the event. The messages go through the `log` crate at the debug level,
+//! with the target [TARGET], so the application picks where they end up and
+//! can filter them out.
+//!
+//! ```no_run
+//! use mousetrap::connection::Connection;
+//! use mousetrap::trace::Trace;
+//!
+//! let mut conn = Connection::init().unwrap();
+//! conn.set_trace(Trace::Hexdump);
+//! conn.sync().unwrap();
+//! ```
+//!
+//! The other connections do not trace their traffic. Drivers of a
+//! [ProtocolState] enable it with [ProtocolState::set_trace].
+//!
+//! With or without the `trace` feature, every connection logs a line per request
+//! sent and per reply or error read, at the trace level with the target
+//! `mousetrap::protocol_state`: the request's name, sequence number and length.
+
+#[cfg(doc)]
+use crate::connection::Connection;
+#[cfg(doc)]
+use crate::protocol_state::ProtocolState;
+use std::fmt::Write;
+
+/// The target of the logged messages.
+pub const TARGET: &str = "mousetrap::trace";
+
+/// What a connection logs of its traffic.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Trace {
+    /// Nothing is logged.
+    #[default]
+    Off,
+    /// A line per request and per message.
+    Summary,
+    /// A line per request and per message, followed by its bytes.
+    Hexdump,
+}
+
+/// Formats bytes as lines of 16, with their offset and the printable ones
+/// aside, in the manner of `hexdump -C`.
+pub fn hexdump(bytes: &[u8]) -> String {
+    let mut dump = String::with_capacity(bytes.len() / 16 * 78 + 78);
+    for (line, chunk) in bytes.chunks(16).enumerate() {
+        let _ = write!(dump, "{:08x} ", line * 16);
+        for i in 0..16 {
+            if i == 8 {
+                dump.push(' ');
+            }
+            match chunk.get(i) {
+                Some(byte) => {
+                    let _ = write!(dump, " {:02x}", byte);
+                }
+                None => dump.push_str("   "),
+            }
+        }
+        dump.push_str("  |");
+        dump.extend(chunk.iter().map(|&byte| {
+            if byte.is_ascii_graphic() || byte == b' ' {
+                char::from(byte)
+            } else {
+                '.'
+            }
+        }));
+        dump.push_str("|\n");
+    }
+    dump
+}
@@ -0,0 +1,297 @@
+//! Carrying the protocol over something else than a socket, to record the
+//! traffic of a session and play it back in tests.
+//!
+//! A [Transport] stands in for the socket of a [Connection]. The
+//! [RecordingTransport] passes the bytes on to a real socket, writing both
+//! directions to a file. The [ReplayTransport] answers from such a file
+//! without any server, so the handshake, the replies and the events of a
+//! recorded session are parsed again the same way, e.g. in regression tests.
+//!
+//! ```no_run
+//! use mousetrap::connection::Connection;
+//!
+//! // With a server running.
+//! let mut conn = Connection::init_recording("session.mtr").unwrap();
+//! let root = conn.default_root();
+//! conn.get_geometry(root).unwrap().reply(&mut conn).unwrap();
+//! drop(conn);
+//!
+//! // Later, without one.
+//! let mut conn = Connection::init_replay("session.mtr").unwrap();
+//! let root = conn.default_root();
+//! let geometry = conn.get_geometry(root).unwrap().reply(&mut conn).unwrap();
+//! println!("{}x{}", geometry.width, geometry.height);
+//! ```
+//!
+//! The replayed client must send the requests of the recording in the same
+//! order, as the server's bytes are handed out once the client sent the bytes
+//! that preceded them.
+
+use crate::connection::{parse_conf, Connection, Stream};
+use crate::errors::{ConnectionError, ParseError};
+use crate::waker;
+use std::collections::VecDeque;
+use std::env;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::os::fd::{AsFd, AsRawFd, BorrowedFd, OwnedFd};
+use std::path::Path;
+
+/// The first bytes of a recording.
+const MAGIC: &[u8; 4] = b"MTTR";
+
+/// Version of the format of recordings.
+const FORMAT_VERSION: u8 = 1;
+
+/// Direction of the bytes of a chunk of a recording.
+mod direction {
+    pub const TO_SERVER: u8 = 0;
+    pub const FROM_SERVER: u8 = 1;
+}
+
+/// A byte stream to an X server, in place of the socket of a [Stream].
+pub trait Transport: AsFd + Send {
+    /// Reads the bytes available, blocking unless non-blocking mode is on.
+    /// Returns 0 once the server closed the stream.
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize>;
+
+    /// Writes some of `data`, returning how much.
+    fn write(&mut self, data: &[u8]) -> io::Result<usize>;
+
+    /// Moves the transport into or out of non-blocking mode, where reads fail
+    /// with [io::ErrorKind::WouldBlock] instead of waiting. The descriptor
+    /// returned by `as_fd` polls readable when a read would not block.
+    fn set_nonblocking(&mut self, non_blocking: bool) -> io::Result<()>;
+
+    /// Writes some of `data`, passing `fds` along with the first byte written.
+    /// Transports which cannot pass file descriptors fail.
+    fn write_with_fds(&mut self, data: &[u8], fds: &[BorrowedFd<'_>]) -> io::Result<usize> {
+        if !fds.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "the transport cannot pass file descriptors",
+            ));
+        }
+        self.write(data)
+    }
+
+    /// Removes and returns the `n` oldest file descriptors received with the
+    /// bytes read, fewer if less were received.
+    fn take_fds(&mut self, n: usize) -> Vec<OwnedFd> {
+        let _ = n;
+        Vec::new()
+    }
+}
+
+/// Passes the bytes on to a stream, writing them to a file on the way.
+///
+/// The file starts with a header, followed by a chunk per read or write:
+//
+//   4 bytes    "MTTR"
+//   1 byte     Format version (1)
+//   3 bytes    Unused
+//   chunks:
+//     1 byte     0 if sent to the server, 1 if received from it
+//     3 bytes    Unused
+//     4 bytes    Length in bytes (n), little-endian
+//     n bytes    Bytes
+pub struct RecordingTransport {
+    stream: Stream,
+    file: File,
+}
+
+impl RecordingTransport {
+    /// Starts recording the bytes exchanged over `stream` into `file`, which
+    /// should be empty.
+    pub fn new(stream: Stream, mut file: File) -> io::Result<Self> {
+        file.write_all(MAGIC)?;
+        file.write_all(&[FORMAT_VERSION, 0, 0, 0])?;
+        Ok(RecordingTransport { stream, file })
+    }
+
+    fn record(&mut self, direction: u8, bytes: &[u8]) -> io::Result<()> {
+        let mut chunk = Vec::with_capacity(8 + bytes.len());
+        chunk.extend_from_slice(&[direction, 0, 0, 0]);
+        chunk.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+        chunk.extend_from_slice(bytes);
+        self.file.write_all(&chunk)
+    }
+}
+
+impl Transport for RecordingTransport {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.stream.read(buf)?;
+        self.record(direction::FROM_SERVER, &buf[..n])?;
+        Ok(n)
+    }
+
+    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+        self.write_with_fds(data, &[])
+    }
+
+    fn set_nonblocking(&mut self, non_blocking: bool) -> io::Result<()> {
+        self.stream.set_nonblocking(non_blocking)
+    }
+
+    fn write_with_fds(&mut self, data: &[u8], fds: &[BorrowedFd<'_>]) -> io::Result<usize> {
+        let n = self.stream.write_with_fds(data, fds)?;
+        self.record(direction::TO_SERVER, &data[..n])?;
+        Ok(n)
+    }
+
+    fn take_fds(&mut self, n: usize) -> Vec<OwnedFd> {
+        self.stream.take_fds(n)
+    }
+}
+
+impl AsFd for RecordingTransport {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.stream.as_fd()
+    }
+}
+
+/// Answers with the bytes a [RecordingTransport] received from the server.
+///
+/// The bytes the server sent after the client's n-th byte are readable once the
+/// client wrote n bytes. What the client writes is counted, not compared with
+/// the recording: the authentication data differs from one machine to another.
+/// The stream ends with the recording.
+pub struct ReplayTransport {
+    /// The server's chunks, each with the number of bytes the client sent
+    /// before it.
+    chunks: VecDeque<(u64, Vec<u8>)>,
+    /// How much of the first chunk was read.
+    offset: usize,
+    /// How many bytes the client wrote.
+    written: u64,
+    non_blocking: bool,
+    /// A pipe kept readable, for event loops to poll: reads never wait.
+    readable: (OwnedFd, OwnedFd),
+}
+
+impl ReplayTransport {
+    /// Reads a recording made by [RecordingTransport].
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, ConnectionError> {
+        Self::from_bytes(&fs::read(path)?)
+    }
+
+    /// Decodes a recording made by [RecordingTransport].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ConnectionError> {
+        let chunks = parse_chunks(bytes)?;
+        let readable = waker::pipe()?;
+        let byte = 1u8;
+        unsafe { libc::write(readable.1.as_raw_fd(), (&byte as *const u8).cast(), 1) };
+        Ok(ReplayTransport {
+            chunks,
+            offset: 0,
+            written: 0,
+            non_blocking: false,
+            readable,
+        })
+    }
+}
+
+/// Returns the server's chunks of a recording, each with the number of bytes
+/// the client sent before it.
+fn parse_chunks(bytes: &[u8]) -> Result<VecDeque<(u64, Vec<u8>)>, ParseError> {
+    let invalid = |field, value| ParseError::InvalidValue {
+        type_name: "ReplayTransport",
+        field,
+        value,
+    };
+    let header = bytes
+        .get(..8)
+        .ok_or_else(|| ParseError::not_enough_data("ReplayTransport", 8, bytes.len()))?;
+    if &header[..4] != MAGIC {
+        let magic = u32::from_le_bytes([header[0], header[1], header[2], header[3]]);
+        return Err(invalid("magic", magic));
+    }
+    if header[4] != FORMAT_VERSION {
+        return Err(invalid("version", header[4].into()));
+    }
+    let mut chunks = VecDeque::new();
+    let mut sent = 0;
+    let mut rest = &bytes[8..];
+    while !rest.is_empty() {
+        let chunk_header = rest
+            .get(..8)
+            .ok_or_else(|| ParseError::not_enough_data("ReplayTransport", 8, rest.len()))?;
+        let length = u32::from_le_bytes([
+            chunk_header[4],
+            chunk_header[5],
+            chunk_header[6],
+            chunk_header[7],
+        ]) as usize;
+        let data = rest.get(8..8 + length).ok_or_else(|| {
+            ParseError::not_enough_data("ReplayTransport", 8 + length, rest.len())
+        })?;
+        match chunk_header[0] {
+            direction::TO_SERVER => sent += length as u64,
+            direction::FROM_SERVER => chunks.push_back((sent, data.to_vec())),
+            direction => return Err(invalid("direction", direction.into())),
+        }
+        rest = &rest[8 + length..];
+    }
+    Ok(chunks)
+}
+
+impl Transport for ReplayTransport {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let Some((sent, chunk)) = self.chunks.front() else {
+            return Ok(0);
+        };
+        if *sent > self.written {
+            if self.non_blocking {
+                return Err(io::Error::from(io::ErrorKind::WouldBlock));
+            }
+            // Nothing else could ever arrive.
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "the client waits for bytes the server only sent after more requests",
+            ));
+        }
+        let n = buf.len().min(chunk.len() - self.offset);
+        buf[..n].copy_from_slice(&chunk[self.offset..self.offset + n]);
+        self.offset += n;
+        if self.offset == chunk.len() {
+            self.chunks.pop_front();
+            self.offset = 0;
+        }
+        Ok(n)
+    }
+
+    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+        self.written += data.len() as u64;
+        Ok(data.len())
+    }
+
+    fn set_nonblocking(&mut self, non_blocking: bool) -> io::Result<()> {
+        self.non_blocking = non_blocking;
+        Ok(())
+    }
+}
+
+impl AsFd for ReplayTransport {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.readable.0.as_fd()
+    }
+}
+
+impl Connection {
+    /// Connects to the display named by DISPLAY like [Connection::init], and
+    /// records the bytes exchanged with the server to `path`, for
+    /// [Connection::init_replay].
+    pub fn init_recording(path: impl AsRef<Path>) -> Result<Self, ConnectionError> {
+        let display_name = env::var("DISPLAY").map_err(|_| ConnectionError::DisplayNotSet)?;
+        let conf = parse_conf(display_name);
+        let stream = Stream::open(&conf)?;
+        let transport = RecordingTransport::new(stream, File::create(path)?)?;
+        Connection::from_transport(transport, conf.display_number, conf.screen_number)
+    }
+
+    /// Plays back a session recorded by [Connection::init_recording], without
+    /// a server.
+    pub fn init_replay(path: impl AsRef<Path>) -> Result<Self, ConnectionError> {
+        Connection::from_transport(ReplayTransport::open(path)?, 0, 0)
+    }
+}
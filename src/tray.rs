@@ -0,0 +1,419 @@
+//! Icons in the system tray, the freedesktop protocol indicator apps use.
+//!
+//! The tray of a screen owns the `_NET_SYSTEM_TRAY_S<screen>` selection. An
+//! icon asks it to dock a window with a `_NET_SYSTEM_TRAY_OPCODE` message, and
+//! the tray then embeds the window with XEmbed: it reparents it, tells it with
+//! `_XEMBED` messages when it is embedded or focused, and maps it as the
+//! `_XEMBED_INFO` property of the window asks.
+//!
+//! [TrayIcon] docks a window, follows the tray going away and coming back,
+//! docking again then, and reports the XEmbed messages.
+//!
+//! ```no_run
+//! use mousetrap::connection::Connection;
+//! use mousetrap::tray::{TrayEvent, TrayIcon};
+//!
+//! let mut conn = Connection::init().unwrap();
+//! # let window = conn.default_root();
+//! // A window created for the icon, of the visual of the tray if it has one.
+//! let mut icon = TrayIcon::new(&mut conn, window).unwrap();
+//! if !icon.dock(&mut conn).unwrap() {
+//!     println!("no tray yet, docking once one starts");
+//! }
+//! loop {
+//!     let event = conn.wait_for_event().unwrap();
+//!     match icon.handle_event(&mut conn, &event).unwrap() {
+//!         Some(TrayEvent::Embedded { embedder, .. }) => println!("embedded in {:?}", embedder),
+//!         Some(TrayEvent::ManagerGone) => println!("the tray exited"),
+//!         _ => {}
+//!     }
+//! }
+//! ```
+
+use crate::connection::Connection;
+use crate::errors::ConnectionError;
+use crate::event::{ClientMessageEvent, Event};
+use crate::protocol::{atom, Atom, EventMask, VisualId, Window, XId, CURRENT_TIME};
+use crate::request::PropMode;
+
+/// Version of the XEmbed protocol implemented by this module.
+pub const XEMBED_VERSION: u32 = 0;
+
+/// The opcodes of `_NET_SYSTEM_TRAY_OPCODE` messages.
+pub mod opcode {
+    pub const REQUEST_DOCK: u32 = 0;
+    pub const BEGIN_MESSAGE: u32 = 1;
+    pub const CANCEL_MESSAGE: u32 = 2;
+}
+
+/// The messages of XEmbed, sent with the `_XEMBED` type.
+pub mod xembed {
+    pub const EMBEDDED_NOTIFY: u32 = 0;
+    pub const WINDOW_ACTIVATE: u32 = 1;
+    pub const WINDOW_DEACTIVATE: u32 = 2;
+    pub const REQUEST_FOCUS: u32 = 3;
+    pub const FOCUS_IN: u32 = 4;
+    pub const FOCUS_OUT: u32 = 5;
+    pub const FOCUS_NEXT: u32 = 6;
+    pub const FOCUS_PREV: u32 = 7;
+    pub const MODALITY_ON: u32 = 10;
+    pub const MODALITY_OFF: u32 = 11;
+
+    /// The flag of `_XEMBED_INFO` asking the embedder to map the window.
+    pub const MAPPED: u32 = 1 << 0;
+}
+
+/// Names of the atoms of the protocols, in the order of the fields of [Atoms]
+/// after the selection.
+const ATOM_NAMES: [&str; 6] = [
+    "_NET_SYSTEM_TRAY_OPCODE",
+    "_NET_SYSTEM_TRAY_ORIENTATION",
+    "_NET_SYSTEM_TRAY_VISUAL",
+    "MANAGER",
+    "_XEMBED",
+    "_XEMBED_INFO",
+];
+
+/// The atoms of the protocols.
+#[derive(Debug, Clone, Copy)]
+struct Atoms {
+    /// The `_NET_SYSTEM_TRAY_S<screen>` selection.
+    selection: Atom,
+    opcode: Atom,
+    orientation: Atom,
+    visual: Atom,
+    manager: Atom,
+    xembed: Atom,
+    xembed_info: Atom,
+}
+
+/// How the tray lays out its icons.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    Horizontal,
+    Vertical,
+}
+
+/// What happened to a docked icon.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TrayEvent {
+    /// A tray started, and the icon was docked in it if it was docked before.
+    ManagerAppeared {
+        manager: Window,
+    },
+    /// The tray exited. The icon is docked again in the next one.
+    ManagerGone,
+    /// The tray embedded the window in `embedder`, speaking XEmbed `version`.
+    Embedded {
+        embedder: Window,
+        version: u32,
+    },
+    /// The window was reparented out of the tray.
+    Unembedded,
+    /// The toplevel window of the tray gained or lost the focus.
+    WindowActivate,
+    WindowDeactivate,
+    /// The window got the focus; `detail` tells where in it, see XEmbed.
+    FocusIn {
+        detail: u32,
+    },
+    FocusOut,
+    /// Another XEmbed message.
+    Xembed {
+        message: u32,
+        detail: u32,
+        data1: u32,
+        data2: u32,
+    },
+}
+
+/// A window docked in the system tray of the default screen.
+#[derive(Debug)]
+pub struct TrayIcon {
+    window: Window,
+    root: Window,
+    atoms: Atoms,
+    /// The window owning the tray selection, if a tray runs.
+    manager: Option<Window>,
+    /// The window the tray embedded the icon in.
+    embedder: Option<Window>,
+    /// Whether the icon asked to be docked, so it docks in new trays.
+    docking: bool,
+}
+
+impl TrayIcon {
+    /// Prepares `window` to be docked: sets its `_XEMBED_INFO` asking to be
+    /// mapped, and looks for the tray. StructureNotify is added to the events
+    /// the connection selects on `window` and the root window, to follow the
+    /// embedding and the trays starting.
+    pub fn new(conn: &mut Connection, window: Window) -> Result<TrayIcon, ConnectionError> {
+        let root = conn.default_root();
+        let selection = format!("_NET_SYSTEM_TRAY_S{}", conn.default_screen_number());
+        let mut cookies = Vec::with_capacity(ATOM_NAMES.len() + 1);
+        for name in [selection.as_str()].iter().chain(&ATOM_NAMES) {
+            cookies.push(conn.intern_atom(false, name)?);
+        }
+        let mut atoms = Vec::with_capacity(cookies.len());
+        for cookie in cookies {
+            atoms.push(cookie.reply(conn)?.atom);
+        }
+        // The fields are evaluated in order, so each takes the next atom.
+        let mut atoms = atoms.into_iter();
+        let mut next = || atoms.next().unwrap_or(atom::NONE);
+        let atoms = Atoms {
+            selection: next(),
+            opcode: next(),
+            orientation: next(),
+            visual: next(),
+            manager: next(),
+            xembed: next(),
+            xembed_info: next(),
+        };
+        for target in [root, window] {
+            let attributes = conn.get_window_attributes(target)?.reply(conn)?;
+            let mask = EventMask(attributes.your_event_mask) | EventMask::STRUCTURE_NOTIFY;
+            conn.select_input(target, mask)?;
+        }
+        let mut icon = TrayIcon {
+            window,
+            root,
+            atoms,
+            manager: None,
+            embedder: None,
+            docking: false,
+        };
+        icon.set_mapped(conn, true)?;
+        icon.find_manager(conn)?;
+        Ok(icon)
+    }
+
+    /// Returns the docked window.
+    pub fn window(&self) -> Window {
+        self.window
+    }
+
+    /// Returns the window owning the tray, if a tray runs.
+    pub fn manager(&self) -> Option<Window> {
+        self.manager
+    }
+
+    /// Returns the window the tray embedded the icon in, once it did.
+    pub fn embedder(&self) -> Option<Window> {
+        self.embedder
+    }
+
+    /// Asks the tray to dock the window. Returns `false` if no tray runs, in
+    /// which case the window is docked once one starts, as it is when the tray
+    /// restarts.
+    pub fn dock(&mut self, conn: &mut Connection) -> Result<bool, ConnectionError> {
+        self.docking = true;
+        let Some(manager) = self.manager else {
+            return Ok(false);
+        };
+        self.send_opcode(conn, manager, opcode::REQUEST_DOCK, [self.window.0, 0, 0])?;
+        Ok(true)
+    }
+
+    /// Sets whether the tray should show the window, through the XEMBED_MAPPED
+    /// flag of its `_XEMBED_INFO`.
+    pub fn set_mapped(&self, conn: &mut Connection, mapped: bool) -> Result<u16, ConnectionError> {
+        let flags = if mapped { xembed::MAPPED } else { 0 };
+        let info: Vec<u8> = [XEMBED_VERSION, flags]
+            .iter()
+            .flat_map(|value| value.to_ne_bytes())
+            .collect();
+        conn.change_property(
+            PropMode::REPLACE,
+            self.window,
+            self.atoms.xembed_info,
+            self.atoms.xembed_info,
+            32,
+            &info,
+        )
+    }
+
+    /// Returns how the tray lays out its icons, if it tells.
+    pub fn orientation(
+        &self,
+        conn: &mut Connection,
+    ) -> Result<Option<Orientation>, ConnectionError> {
+        let value = self.manager_property(conn, self.atoms.orientation, atom::CARDINAL)?;
+        Ok(value.map(|value| match value {
+            1 => Orientation::Vertical,
+            _ => Orientation::Horizontal,
+        }))
+    }
+
+    /// Returns the visual the tray wants the windows of the icons to use, if it
+    /// tells, usually a 32-bit one for translucent icons.
+    pub fn visual(&self, conn: &mut Connection) -> Result<Option<VisualId>, ConnectionError> {
+        self.manager_property(conn, self.atoms.visual, atom::VISUALID)
+    }
+
+    /// Asks the embedder to give the window the focus.
+    pub fn request_focus(&self, conn: &mut Connection) -> Result<(), ConnectionError> {
+        if let Some(embedder) = self.embedder {
+            self.send_xembed(conn, embedder, xembed::REQUEST_FOCUS, [0, 0, 0])?;
+        }
+        Ok(())
+    }
+
+    /// Handles the events of the tray and of XEmbed, docking again in a tray
+    /// that starts. Returns what happened, or `None` if the event is not one
+    /// of them.
+    pub fn handle_event(
+        &mut self,
+        conn: &mut Connection,
+        event: &Event,
+    ) -> Result<Option<TrayEvent>, ConnectionError> {
+        match event {
+            Event::ClientMessage(message)
+                if message.window == self.root
+                    && message.message_type == self.atoms.manager
+                    && message.format == 32 =>
+            {
+                // data[0] is the time, data[1] the selection and data[2] its owner.
+                let data = message.data32();
+                if data[1] != self.atoms.selection {
+                    return Ok(None);
+                }
+                let manager = XId(data[2]);
+                self.set_manager(conn, Some(manager))?;
+                if self.docking {
+                    self.dock(conn)?;
+                }
+                Ok(Some(TrayEvent::ManagerAppeared { manager }))
+            }
+            Event::ClientMessage(message)
+                if message.window == self.window
+                    && message.message_type == self.atoms.xembed
+                    && message.format == 32 =>
+            {
+                Ok(Some(self.xembed(message)))
+            }
+            Event::DestroyNotify(e) if Some(e.window) == self.manager => {
+                self.manager = None;
+                self.embedder = None;
+                Ok(Some(TrayEvent::ManagerGone))
+            }
+            Event::ReparentNotify(e)
+                if e.window == self.window
+                    && self.embedder.is_some()
+                    && Some(e.parent) != self.embedder =>
+            {
+                self.embedder = None;
+                Ok(Some(TrayEvent::Unembedded))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Handles an `_XEMBED` message sent to the window.
+    //
+    //   data[0]    Timestamp
+    //   data[1]    Message
+    //   data[2]    Detail
+    //   data[3]    Data 1
+    //   data[4]    Data 2
+    fn xembed(&mut self, message: &ClientMessageEvent) -> TrayEvent {
+        let [_, message, detail, data1, data2] = message.data32();
+        match message {
+            xembed::EMBEDDED_NOTIFY => {
+                let embedder = XId(data1);
+                self.embedder = Some(embedder);
+                TrayEvent::Embedded {
+                    embedder,
+                    version: data2,
+                }
+            }
+            xembed::WINDOW_ACTIVATE => TrayEvent::WindowActivate,
+            xembed::WINDOW_DEACTIVATE => TrayEvent::WindowDeactivate,
+            xembed::FOCUS_IN => TrayEvent::FocusIn { detail },
+            xembed::FOCUS_OUT => TrayEvent::FocusOut,
+            _ => TrayEvent::Xembed {
+                message,
+                detail,
+                data1,
+                data2,
+            },
+        }
+    }
+
+    /// Looks up the owner of the tray selection.
+    fn find_manager(&mut self, conn: &mut Connection) -> Result<(), ConnectionError> {
+        let owner = conn
+            .get_selection_owner(self.atoms.selection)?
+            .reply(conn)?
+            .owner;
+        let manager = (owner != XId(0)).then_some(owner);
+        self.set_manager(conn, manager)
+    }
+
+    /// Follows a new tray, selecting StructureNotify on it to learn when it exits.
+    fn set_manager(
+        &mut self,
+        conn: &mut Connection,
+        manager: Option<Window>,
+    ) -> Result<(), ConnectionError> {
+        self.manager = manager;
+        self.embedder = None;
+        if let Some(manager) = manager {
+            conn.select_input(manager, EventMask::STRUCTURE_NOTIFY)?;
+        }
+        Ok(())
+    }
+
+    /// Reads a 32-bit property of the tray.
+    fn manager_property(
+        &self,
+        conn: &mut Connection,
+        property: Atom,
+        type_: Atom,
+    ) -> Result<Option<u32>, ConnectionError> {
+        let Some(manager) = self.manager else {
+            return Ok(None);
+        };
+        let reply = conn
+            .get_property(false, manager, property, type_, 0, 1)?
+            .reply(conn)?;
+        Ok(reply.value32().and_then(|value| value.first().copied()))
+    }
+
+    /// Sends a `_NET_SYSTEM_TRAY_OPCODE` message to the tray.
+    //
+    //   data[0]    Timestamp
+    //   data[1]    Opcode
+    //   data[2..5] Opcode data, for REQUEST_DOCK the window
+    fn send_opcode(
+        &self,
+        conn: &mut Connection,
+        manager: Window,
+        opcode: u32,
+        data: [u32; 3],
+    ) -> Result<u16, ConnectionError> {
+        let [a, b, c] = data;
+        let message = ClientMessageEvent::new_format32(
+            manager,
+            self.atoms.opcode,
+            [CURRENT_TIME, opcode, a, b, c],
+        );
+        conn.send_event(false, manager, EventMask::NO_EVENT, message.serialize())
+    }
+
+    /// Sends an `_XEMBED` message to the embedder.
+    fn send_xembed(
+        &self,
+        conn: &mut Connection,
+        embedder: Window,
+        message: u32,
+        data: [u32; 3],
+    ) -> Result<u16, ConnectionError> {
+        let [detail, data1, data2] = data;
+        let message = ClientMessageEvent::new_format32(
+            embedder,
+            self.atoms.xembed,
+            [CURRENT_TIME, message, detail, data1, data2],
+        );
+        conn.send_event(false, embedder, EventMask::NO_EVENT, message.serialize())
+    }
+}
@@ -0,0 +1,390 @@
+//! A connection doing its I/O through io_uring, for high-throughput workloads such
+//! as screen capture. Linux only; requires the `io-uring` feature.
+//!
+//! Requests are buffered as they are sent and written when a reply or an event is
+//! waited on, or on [UringConnection::flush]. The write of the buffered requests and
+//! the read of what the server answers are submitted together, so a round trip costs
+//! a single system call however many requests it carries. In a capture loop, the
+//! `ShmGetImage` or `ShmPutImage` requests of a frame and the replies and
+//! [crate::shm::CompletionEvent]s of the previous one thus share one submission.
+//!
+//! ```no_run
+//! # use mousetrap::reply::InputFocusReply;
+//! # use mousetrap::request::GetInputFocus;
+//! # use mousetrap::uring::UringConnection;
+//! let mut conn = UringConnection::init().unwrap();
+//! let cookies = (0..100)
+//!     .map(|_| conn.send_request_with_reply::<InputFocusReply>(&GetInputFocus.serialize()))
+//!     .collect::<Result<Vec<_>, _>>()
+//!     .unwrap();
+//! // The 100 requests are written along with the first read.
+//! for cookie in cookies {
+//!     println!("{:?}", cookie.reply(&mut conn).unwrap().focus);
+//! }
+//! ```
+
+use crate::connection::{parse_conf, ExtensionInfo, Stream};
+use crate::cookie::{parse_reply, ParseMode, Reply};
+use crate::errors::{ConnectionError, XError};
+use crate::event::Event;
+use crate::protocol::{ConnSetup, Screen, Window, XId};
+use crate::protocol_state::ProtocolState;
+use crate::reply::{InputFocusReply, QueryExtensionReply};
+use crate::request::{GetInputFocus, QueryExtension};
+use io_uring::{opcode, squeue, types, IoUring};
+use std::env;
+use std::io;
+use std::marker::PhantomData;
+use std::os::fd::AsRawFd;
+
+/// `user_data` of the write of the buffered requests.
+const WRITE: u64 = 0;
+/// `user_data` of the read from the server.
+const READ: u64 = 1;
+
+/// Size of the buffer the server's messages are read into.
+const READ_BUFFER_SIZE: usize = 65536;
+
+/// Whether a submission reads from the server, and how.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ReadMode {
+    /// Only the buffered requests are written.
+    None,
+    /// Waits until the server sent something.
+    Wait,
+    /// Reads what is available without waiting.
+    Poll,
+}
+
+/// A connection doing its I/O through io_uring. See the [module](self) documentation.
+pub struct UringConnection {
+    ring: IoUring,
+    /// The socket, owned here so it outlives every submission.
+    stream: Stream,
+    /// Sequence numbers, buffered messages, replies and events.
+    state: ProtocolState,
+    /// Requests sent but not written yet.
+    output: Vec<u8>,
+    /// Buffer the server's messages are read into.
+    input: Box<[u8]>,
+}
+
+/// A handle to the reply of a request sent through a [UringConnection].
+#[derive(Debug)]
+#[must_use = "the reply should be retrieved, otherwise it is kept in memory"]
+pub struct Cookie<R> {
+    /// Sequence number of the request.
+    pub sequence: u16,
+    reply: PhantomData<R>,
+}
+
+impl<R: Reply> Cookie<R> {
+    pub fn new(sequence: u16) -> Self {
+        Self {
+            sequence,
+            reply: PhantomData,
+        }
+    }
+
+    /// Writes the buffered requests, then blocks until the reply arrives and parses it.
+    pub fn reply(self, conn: &mut UringConnection) -> Result<R, ConnectionError> {
+        let bytes = conn.wait_for_reply(self.sequence)?;
+        parse_reply(&bytes, conn.parse_mode())
+    }
+}
+
+impl UringConnection {
+    /// Connects to the display named by `DISPLAY`, through its unix socket or, if
+    /// that fails, over TCP.
+    pub fn init() -> Result<Self, ConnectionError> {
+        let display_name = env::var("DISPLAY").map_err(|_| ConnectionError::DisplayNotSet)?;
+        let conf = parse_conf(display_name);
+        let stream = Stream::open(&conf)?;
+        Self::with_stream(stream, conf.display_number, conf.screen_number)
+    }
+
+    pub(crate) fn with_stream(
+        mut stream: Stream,
+        display_number: u8,
+        screen_number: usize,
+    ) -> Result<Self, ConnectionError> {
+        let setup = stream.authenticate(display_number)?;
+        // A write and a read are submitted at most.
+        let ring = IoUring::new(2)?;
+
+        Ok(UringConnection {
+            ring,
+            stream,
+            state: ProtocolState::new(setup, screen_number),
+            output: Vec::new(),
+            input: vec![0u8; READ_BUFFER_SIZE].into_boxed_slice(),
+        })
+    }
+
+    /// Returns the server's response to the connection setup.
+    pub fn setup(&self) -> &ConnSetup {
+        self.state.setup()
+    }
+
+    /// Returns the screen given in the display name, or the first screen if the
+    /// server has no such screen.
+    pub fn default_screen(&self) -> &Screen {
+        self.state.default_screen()
+    }
+
+    /// Returns the root window of the default screen.
+    pub fn default_root(&self) -> Window {
+        self.state.default_root()
+    }
+
+    /// Allocates a new id for a resource such as a window or a pixmap.
+    pub fn generate_id(&mut self) -> Result<XId, ConnectionError> {
+        self.state.generate_id()
+    }
+
+    /// Sets how strictly replies are parsed.
+    pub fn set_parse_mode(&mut self, mode: ParseMode) {
+        self.state.set_parse_mode(mode);
+    }
+
+    /// Returns how strictly replies are parsed.
+    pub fn parse_mode(&self) -> ParseMode {
+        self.state.parse_mode()
+    }
+
+    /// Sets the function called with the errors of unchecked requests. By default
+    /// these errors are logged as warnings through the `log` crate.
+    pub fn set_error_handler(&mut self, handler: Box<dyn Fn(XError) + Send>) {
+        self.state.set_error_handler(handler);
+    }
+
+    /// Buffers a serialized request and returns its sequence number.
+    ///
+    /// The request is unchecked: an error caused by it is passed to the error
+    /// handler.
+    pub fn send_request(&mut self, request: &[u8]) -> Result<u16, ConnectionError> {
+        self.send(request, false)
+    }
+
+    /// Buffers a serialized request that has a reply and returns the cookie of the
+    /// reply. An error caused by the request is returned by [Cookie::reply].
+    pub fn send_request_with_reply<R: Reply>(
+        &mut self,
+        request: &[u8],
+    ) -> Result<Cookie<R>, ConnectionError> {
+        Ok(Cookie::new(self.send(request, true)?))
+    }
+
+    fn send(&mut self, request: &[u8], checked: bool) -> Result<u16, ConnectionError> {
+        if self.state.is_disconnected() {
+            return Err(ConnectionError::Disconnected);
+        }
+        self.state.check_request_length(request)?;
+        self.output.extend_from_slice(request);
+        Ok(self.state.request_sent(request, checked))
+    }
+
+    /// Writes the buffered requests.
+    pub fn flush(&mut self) -> Result<(), ConnectionError> {
+        self.submit(ReadMode::None)
+    }
+
+    /// Waits until the server processed every request sent so far and returns the
+    /// errors caused by unchecked requests that were not read from the server yet.
+    pub fn sync(&mut self) -> Result<Vec<XError>, ConnectionError> {
+        let cookie: Cookie<InputFocusReply> =
+            self.send_request_with_reply(&GetInputFocus.serialize())?;
+        self.state.begin_sync();
+        let result = cookie.reply(self);
+        let errors = self.state.end_sync();
+        result.map(|_| errors)
+    }
+
+    /// Returns the codes of the extension `name`, or `None` if the server does not
+    /// support it. The server is only asked the first time.
+    pub fn extension(
+        &mut self,
+        name: &'static str,
+    ) -> Result<Option<ExtensionInfo>, ConnectionError> {
+        if let Some(info) = self.state.extension(name) {
+            return Ok(info);
+        }
+        let request = QueryExtension {
+            name: name.as_bytes(),
+        };
+        let reply = self
+            .send_request_with_reply::<QueryExtensionReply>(&request.serialize())?
+            .reply(self)?;
+        Ok(self.state.extension_queried(name, &reply))
+    }
+
+    /// Returns the codes of an extension, failing if the server does not support
+    /// it, and registers the names of its requests and errors, see
+    /// [ProtocolState::register_extension].
+    pub fn require_extension(
+        &mut self,
+        name: &'static str,
+        request_names: &'static [&'static str],
+        error_names: &'static [&'static str],
+    ) -> Result<ExtensionInfo, ConnectionError> {
+        let info = self
+            .extension(name)?
+            .ok_or(ConnectionError::ExtensionNotSupported(name))?;
+        self.state
+            .register_extension(name, info, request_names, error_names);
+        Ok(info)
+    }
+
+    /// Writes the buffered requests, then blocks until the reply to the request with
+    /// the given sequence number arrives and returns its bytes.
+    pub fn wait_for_reply(&mut self, sequence: u16) -> Result<Vec<u8>, ConnectionError> {
+        loop {
+            if let Some(response) = self.state.take_response(sequence) {
+                return response;
+            }
+            self.submit(ReadMode::Wait)?;
+        }
+    }
+
+    /// Writes the buffered requests, then returns the next event, blocking until one
+    /// arrives.
+    pub fn wait_for_event(&mut self) -> Result<Event, ConnectionError> {
+        loop {
+            if let Some(event) = self.state.take_event() {
+                return Ok(event);
+            }
+            if let Err(err) = self.submit(ReadMode::Wait) {
+                // The events read before the connection closed are still delivered.
+                return self.state.take_event().ok_or(err);
+            }
+        }
+    }
+
+    /// Writes the buffered requests, then returns the next event if one is
+    /// available, without blocking.
+    pub fn poll_for_event(&mut self) -> Result<Option<Event>, ConnectionError> {
+        if let Some(event) = self.state.take_event() {
+            return Ok(Some(event));
+        }
+        if let Err(err) = self.submit(ReadMode::Poll) {
+            return self.state.take_event().map(Some).ok_or(err);
+        }
+        Ok(self.state.take_event())
+    }
+
+    /// Submits the write of the buffered requests, linked to a read if `read` asks
+    /// for one, and waits for both to complete.
+    fn submit(&mut self, read: ReadMode) -> Result<(), ConnectionError> {
+        if self.state.is_disconnected() {
+            return Err(ConnectionError::Disconnected);
+        }
+        let fd = types::Fd(self.stream.as_raw_fd());
+        let mut read = read;
+        loop {
+            let mut entries = Vec::with_capacity(2);
+            if !self.output.is_empty() {
+                // A short write breaks the link, so the read only starts once
+                // every request was written.
+                let write = opcode::Send::new(fd, self.output.as_ptr(), self.output.len() as u32)
+                    .flags(libc::MSG_WAITALL | libc::MSG_NOSIGNAL)
+                    .build()
+                    .user_data(WRITE);
+                entries.push(if read == ReadMode::None {
+                    write
+                } else {
+                    write.flags(squeue::Flags::IO_LINK)
+                });
+            }
+            if read != ReadMode::None {
+                let flags = if read == ReadMode::Poll {
+                    libc::MSG_DONTWAIT
+                } else {
+                    0
+                };
+                let input = &mut self.input;
+                entries.push(
+                    opcode::Recv::new(fd, input.as_mut_ptr(), input.len() as u32)
+                        .flags(flags)
+                        .build()
+                        .user_data(READ),
+                );
+            }
+            if entries.is_empty() {
+                return Ok(());
+            }
+
+            // SAFETY: the buffers outlive the operations, as this waits for all of
+            // them to complete before the buffers are touched again.
+            unsafe {
+                self.ring
+                    .submission()
+                    .push_multiple(&entries)
+                    .expect("the submission queue is empty");
+            }
+            let mut completions = Vec::with_capacity(entries.len());
+            while completions.len() < entries.len() {
+                match self.ring.submit_and_wait(entries.len() - completions.len()) {
+                    Ok(_) => {}
+                    Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+                    Err(e) => return Err(e.into()),
+                }
+                completions.extend(self.ring.completion());
+            }
+
+            for completion in completions {
+                let result = completion.result();
+                if result < 0 && -result != libc::ECANCELED && -result != libc::EAGAIN {
+                    let err = io::Error::from_raw_os_error(-result);
+                    return Err(self.state.connection_lost(err));
+                }
+                match completion.user_data() {
+                    WRITE if result >= 0 => {
+                        self.output.drain(..result as usize);
+                    }
+                    READ if result == 0 => {
+                        let err = io::Error::from(io::ErrorKind::UnexpectedEof);
+                        return Err(self.state.connection_lost(err));
+                    }
+                    READ if result > 0 => {
+                        self.state.bytes_received(&self.input[..result as usize])?;
+                        // Enough was read, even if the write has to be resubmitted.
+                        read = ReadMode::None;
+                    }
+                    _ => {}
+                }
+            }
+            // Otherwise the write was short and the read cancelled, or nothing was
+            // read yet.
+            if self.output.is_empty() && read != ReadMode::Wait {
+                return Ok(());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::randr;
+    use crate::request::RequestBuilder;
+    use crate::testing::randr_server;
+
+    #[test]
+    fn extension_errors_of_buffered_requests_are_named() {
+        let server = randr_server();
+        let stream = Stream::connect_unix_socket(&server.path().to_string_lossy()).unwrap();
+        let mut conn = UringConnection::with_stream(stream, 0, 0).unwrap();
+        let info = conn
+            .require_extension(randr::NAME, randr::REQUEST_NAMES, randr::ERROR_NAMES)
+            .unwrap();
+        // Both written along with the GetInputFocus of the sync.
+        let request = RequestBuilder::new(info.major_opcode, 2).finish();
+        conn.send_request(&request).unwrap();
+        conn.send_request(&request).unwrap();
+        let errors = conn.sync().unwrap();
+        assert_eq!(errors.len(), 2);
+        assert!(errors
+            .iter()
+            .all(|error| error.error_name.as_deref() == Some("RANDR:BadCrtc")));
+    }
+}
@@ -1,7 +1,11 @@
 use crate::{
     errors::ParseError,
-    protocol::{Format, Screen},
+    protocol::{
+        BackingStore, Depth, EventMask, Format, Rectangle, Screen, VisualClass, VisualType, XId,
+        XString,
+    },
 };
+use std::any::type_name;
 
 /// Parses a given byte slice into a value of type `T` and returns the parsed value along with the remaining slice.
 ///
@@ -15,24 +19,57 @@ pub fn deserialize_into<T: Deserialize>(bytes: &[u8]) -> Result<(T, &[u8]), Pars
     // Extract the relevant bytes for deserializing.
     let result = match bytes.get(..size) {
         Some(b) => b,
-        None => return Err(ParseError::NotEnoughData),
+        None => {
+            return Err(ParseError::not_enough_data(
+                type_name::<T>(),
+                size,
+                bytes.len(),
+            ))
+        }
     };
     Ok((T::deserialize(result)?, &bytes[size..]))
 }
 
-/// Parses a given byte slice into a String and returns the parsed value along with the remaining slice.
+/// Parses a given byte slice into an [XString] and returns the parsed value along with the remaining slice.
 ///
 /// If the input slice contains not enough bytes to parse,
 /// it returns a `ParseError::NotEnoughData` error.
-pub fn deserialize_into_string(bytes: &[u8], length: u16) -> Result<(String, &[u8]), ParseError> {
+pub fn deserialize_into_string(bytes: &[u8], length: u16) -> Result<(XString, &[u8]), ParseError> {
     if bytes.len() < length.into() {
-        return Err(ParseError::NotEnoughData);
+        return Err(ParseError::not_enough_data(
+            "STRING8",
+            length.into(),
+            bytes.len(),
+        ));
     }
 
     // Extract the relevant bytes for deserializing.
     let (result, rest) = bytes.split_at(length.into());
 
-    Ok((String::from_utf8_lossy(result).to_string(), rest))
+    Ok((XString::from(result), rest))
+}
+
+/// Returns a `ParseError::TrailingBytes` error if `bytes` is longer than `length`,
+/// the size of the message its contents describe.
+pub fn check_trailing_bytes(
+    type_name: &'static str,
+    bytes: &[u8],
+    length: usize,
+) -> Result<(), ParseError> {
+    match bytes.len().checked_sub(length) {
+        Some(count) if count > 0 => Err(ParseError::TrailingBytes { type_name, count }),
+        _ => Ok(()),
+    }
+}
+
+/// Skips `n` unused bytes from the start of a byte slice and returns the remaining slice.
+///
+/// If the input slice contains less than `n` bytes,
+/// it returns a `ParseError::NotEnoughData` error.
+pub fn skip(bytes: &[u8], n: usize) -> Result<&[u8], ParseError> {
+    bytes
+        .get(n..)
+        .ok_or(ParseError::not_enough_data("unused bytes", n, bytes.len()))
 }
 
 /// A trait for parsing an instance of a type from a byte slice.
@@ -43,43 +80,60 @@ pub trait Deserialize {
         Self: Sized;
 }
 
+/// Converts `bytes` into an array of the size of `T`, the type being parsed.
+fn exact_bytes<T, const N: usize>(bytes: &[u8]) -> Result<[u8; N], ParseError> {
+    bytes.try_into().map_err(|_| ParseError::InvalidLength {
+        type_name: type_name::<T>(),
+        expected: N,
+        actual: bytes.len(),
+    })
+}
+
 impl Deserialize for u8 {
     fn deserialize(bytes: &[u8]) -> Result<Self, ParseError> {
-        if bytes.len() != 1 {
-            return Err(ParseError::OutOfBound);
-        }
-        let result: [u8; 1] = bytes.try_into().map_err(|_| ParseError::Failed)?;
-        Ok(u8::from_ne_bytes(result))
+        Ok(u8::from_ne_bytes(exact_bytes::<u8, 1>(bytes)?))
     }
 }
 
 impl Deserialize for u16 {
     fn deserialize(bytes: &[u8]) -> Result<Self, ParseError> {
-        if bytes.len() != 2 {
-            return Err(ParseError::OutOfBound);
-        }
-        let result = bytes.try_into().map_err(|_| ParseError::Failed)?;
-        Ok(u16::from_ne_bytes(result))
+        Ok(u16::from_ne_bytes(exact_bytes::<u16, 2>(bytes)?))
+    }
+}
+
+impl Deserialize for i16 {
+    fn deserialize(bytes: &[u8]) -> Result<Self, ParseError> {
+        Ok(i16::from_ne_bytes(exact_bytes::<i16, 2>(bytes)?))
+    }
+}
+
+impl Deserialize for bool {
+    fn deserialize(bytes: &[u8]) -> Result<Self, ParseError> {
+        Ok(<u8 as Deserialize>::deserialize(bytes)? != 0)
     }
 }
 
 impl Deserialize for u32 {
     fn deserialize(bytes: &[u8]) -> Result<Self, ParseError> {
-        if bytes.len() != 4 {
-            return Err(ParseError::InvalidLength);
-        }
-        let result: [u8; 4] = bytes.try_into().map_err(|_| ParseError::Failed)?;
-        Ok(u32::from_ne_bytes(result))
+        Ok(u32::from_ne_bytes(exact_bytes::<u32, 4>(bytes)?))
     }
 }
 
 impl Deserialize for u64 {
     fn deserialize(bytes: &[u8]) -> Result<Self, ParseError> {
-        if bytes.len() != 8 {
-            return Err(ParseError::InvalidLength);
-        }
-        let result: [u8; 8] = bytes.try_into().map_err(|_| ParseError::Failed)?;
-        Ok(u64::from_ne_bytes(result))
+        Ok(u64::from_ne_bytes(exact_bytes::<u64, 8>(bytes)?))
+    }
+}
+
+impl Deserialize for i32 {
+    fn deserialize(bytes: &[u8]) -> Result<Self, ParseError> {
+        Ok(i32::from_ne_bytes(exact_bytes::<i32, 4>(bytes)?))
+    }
+}
+
+impl Deserialize for XId {
+    fn deserialize(bytes: &[u8]) -> Result<Self, ParseError> {
+        Ok(XId(<u32 as Deserialize>::deserialize(bytes)?))
     }
 }
 
@@ -90,26 +144,36 @@ pub fn deserialize_into_vec<T: DeserializeList>(
 ) -> Result<(Vec<T>, &[u8]), ParseError> {
     // Calculate length of slice to split: element_size*n
     let element_size = T::size();
-    let tot_length: usize = element_size
-        .checked_mul(n.into())
-        .ok_or(ParseError::OverFlow)?;
+    let tot_length: usize = element_size.checked_mul(n).ok_or(ParseError::OverFlow {
+        type_name: type_name::<T>(),
+    })?;
 
     if bytes.len() < tot_length {
-        return Err(ParseError::NotEnoughData);
+        return Err(ParseError::not_enough_data(
+            type_name::<T>(),
+            tot_length,
+            bytes.len(),
+        ));
     }
 
-    let mut formats: Vec<T> = Vec::with_capacity(n.into());
+    let mut formats: Vec<T> = Vec::with_capacity(n);
 
     // Split the slice by the calculated length.
-    let (result, rest) = bytes.split_at(tot_length.into());
+    let (result, rest) = bytes.split_at(tot_length);
 
     // Iterate and deserialize each format within the LISTofFORMAT
-    let mut start = 0 as usize;
+    let mut start = 0_usize;
     let mut end = element_size;
     for _ in 0..n {
         let slice: &[u8] = match result.get(start..end) {
             Some(b) => b,
-            None => return Err(ParseError::NotEnoughData),
+            None => {
+                return Err(ParseError::not_enough_data(
+                    type_name::<T>(),
+                    element_size,
+                    result.len().saturating_sub(start),
+                ))
+            }
         };
         start += element_size;
         end += element_size;
@@ -119,6 +183,82 @@ pub fn deserialize_into_vec<T: DeserializeList>(
     Ok((formats, rest))
 }
 
+/// Where values are written as x11 bytes: a `Vec<u8>`, or the fixed-size buffer
+/// of a [crate::request::SmallRequest].
+pub trait Output {
+    /// Appends bytes.
+    fn put_slice(&mut self, bytes: &[u8]);
+}
+
+impl Output for Vec<u8> {
+    fn put_slice(&mut self, bytes: &[u8]) {
+        self.extend_from_slice(bytes);
+    }
+}
+
+/// A trait for writing a value as x11 bytes.
+pub trait Serialize {
+    /// Appends the bytes of the value to `buf`.
+    fn serialize_into<O: Output + ?Sized>(&self, buf: &mut O);
+}
+
+impl Serialize for u8 {
+    fn serialize_into<O: Output + ?Sized>(&self, buf: &mut O) {
+        buf.put_slice(&[*self]);
+    }
+}
+
+impl Serialize for bool {
+    fn serialize_into<O: Output + ?Sized>(&self, buf: &mut O) {
+        buf.put_slice(&[u8::from(*self)]);
+    }
+}
+
+impl Serialize for u16 {
+    fn serialize_into<O: Output + ?Sized>(&self, buf: &mut O) {
+        buf.put_slice(&self.to_ne_bytes());
+    }
+}
+
+impl Serialize for i16 {
+    fn serialize_into<O: Output + ?Sized>(&self, buf: &mut O) {
+        buf.put_slice(&self.to_ne_bytes());
+    }
+}
+
+impl Serialize for u32 {
+    fn serialize_into<O: Output + ?Sized>(&self, buf: &mut O) {
+        buf.put_slice(&self.to_ne_bytes());
+    }
+}
+
+impl Serialize for u64 {
+    fn serialize_into<O: Output + ?Sized>(&self, buf: &mut O) {
+        buf.put_slice(&self.to_ne_bytes());
+    }
+}
+
+impl Serialize for i32 {
+    fn serialize_into<O: Output + ?Sized>(&self, buf: &mut O) {
+        buf.put_slice(&self.to_ne_bytes());
+    }
+}
+
+impl Serialize for XId {
+    fn serialize_into<O: Output + ?Sized>(&self, buf: &mut O) {
+        self.0.serialize_into(buf);
+    }
+}
+
+impl Serialize for Rectangle {
+    fn serialize_into<O: Output + ?Sized>(&self, buf: &mut O) {
+        self.x.serialize_into(buf);
+        self.y.serialize_into(buf);
+        self.width.serialize_into(buf);
+        self.height.serialize_into(buf);
+    }
+}
+
 /// Returns the raw bytes of a T
 pub fn byte_raw_slice<T>(v: &T) -> &[u8] {
     let p: *const T = v;
@@ -134,6 +274,69 @@ pub trait DeserializeList {
     fn size() -> usize;
 }
 
+impl DeserializeList for u8 {
+    fn deserialize(bytes: &[u8]) -> Result<Self, ParseError> {
+        <u8 as Deserialize>::deserialize(bytes)
+    }
+
+    fn size() -> usize {
+        1
+    }
+}
+
+impl DeserializeList for u16 {
+    fn deserialize(bytes: &[u8]) -> Result<Self, ParseError> {
+        <u16 as Deserialize>::deserialize(bytes)
+    }
+
+    fn size() -> usize {
+        2
+    }
+}
+
+impl DeserializeList for u32 {
+    fn deserialize(bytes: &[u8]) -> Result<Self, ParseError> {
+        <u32 as Deserialize>::deserialize(bytes)
+    }
+
+    fn size() -> usize {
+        4
+    }
+}
+
+impl DeserializeList for XId {
+    fn deserialize(bytes: &[u8]) -> Result<Self, ParseError> {
+        <XId as Deserialize>::deserialize(bytes)
+    }
+
+    fn size() -> usize {
+        4
+    }
+}
+
+impl DeserializeList for Rectangle {
+    fn deserialize(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 2  INT16                 x
+        // 2  INT16                 y
+        // 2  CARD16                width
+        // 2  CARD16                height
+        let (x, rest) = deserialize_into::<i16>(bytes)?;
+        let (y, rest) = deserialize_into::<i16>(rest)?;
+        let (width, rest) = deserialize_into::<u16>(rest)?;
+        let (height, _) = deserialize_into::<u16>(rest)?;
+        Ok(Rectangle {
+            x,
+            y,
+            width,
+            height,
+        })
+    }
+
+    fn size() -> usize {
+        8
+    }
+}
+
 impl DeserializeList for Format {
     fn deserialize(bytes: &[u8]) -> Result<Self, ParseError>
     where
@@ -146,7 +349,7 @@ impl DeserializeList for Format {
         // 5                unused
         let (depth, rest) = deserialize_into::<u8>(bytes)?;
         let (bits_per_pixel, rest) = deserialize_into::<u8>(rest)?;
-        let (scanline_pad, rest) = deserialize_into::<u8>(rest)?;
+        let (scanline_pad, _) = deserialize_into::<u8>(rest)?;
         Ok(Format {
             depth,
             bits_per_pixel,
@@ -160,26 +363,152 @@ impl DeserializeList for Format {
     }
 }
 
-impl DeserializeList for Screen {
+impl DeserializeList for VisualType {
     fn deserialize(bytes: &[u8]) -> Result<Self, ParseError>
     where
         Self: Sized,
     {
-        todo!()
+        // Each item in the LISTofVISUALTYPE will have this byte format
+        // 4  VISUALID      visual-id
+        // 1                class
+        // 1  CARD8         bits-per-rgb-value
+        // 2  CARD16        colormap-entries
+        // 4  CARD32        red-mask
+        // 4  CARD32        green-mask
+        // 4  CARD32        blue-mask
+        // 4                unused
+        let (visual_id, rest) = deserialize_into::<u32>(bytes)?;
+        let (class, rest) = deserialize_into::<u8>(rest)?;
+        let (bits_per_rgb_value, rest) = deserialize_into::<u8>(rest)?;
+        let (colormap_entries, rest) = deserialize_into::<u16>(rest)?;
+        let (red_mask, rest) = deserialize_into::<u32>(rest)?;
+        let (green_mask, rest) = deserialize_into::<u32>(rest)?;
+        let (blue_mask, _) = deserialize_into::<u32>(rest)?;
+        Ok(VisualType {
+            visual_id,
+            class: VisualClass(class),
+            bits_per_rgb_value,
+            colormap_entries,
+            red_mask,
+            green_mask,
+            blue_mask,
+        })
     }
 
+    /// Each visual type within the LISTofVISUALTYPE takes up 24 bytes of space.
     fn size() -> usize {
-        4 // TODO: this is not the actual value
+        24
     }
 }
 
+/// A trait for parsing items of a list whose size is not fixed, such as
+/// the LISTofSCREEN and LISTofDEPTH in the connection setup.
+pub trait DeserializeVariable {
+    /// Parses one item from the start of `bytes` and returns it along with the remaining slice.
+    fn deserialize(bytes: &[u8]) -> Result<(Self, &[u8]), ParseError>
+    where
+        Self: Sized;
+}
+
+/// Deserialize `n` variable sized items from a chunk of bytes into a vector of T
+pub fn deserialize_into_variable_vec<T: DeserializeVariable>(
+    bytes: &[u8],
+    n: usize,
+) -> Result<(Vec<T>, &[u8]), ParseError> {
+    let mut items: Vec<T> = Vec::new();
+    let mut rest = bytes;
+    for _ in 0..n {
+        let (item, r) = T::deserialize(rest)?;
+        items.push(item);
+        rest = r;
+    }
+    Ok((items, rest))
+}
+
+impl DeserializeVariable for Depth {
+    fn deserialize(bytes: &[u8]) -> Result<(Self, &[u8]), ParseError> {
+        // 1  CARD8                 depth
+        // 1                        unused
+        // 2  n                     number of VISUALTYPES in visuals
+        // 4                        unused
+        // 24n LISTofVISUALTYPE     visuals
+        let (depth, rest) = deserialize_into::<u8>(bytes)?;
+        let rest = skip(rest, 1)?;
+        let (number_of_visuals, rest) = deserialize_into::<u16>(rest)?;
+        let rest = skip(rest, 4)?;
+        let (visuals, rest) = deserialize_into_vec::<VisualType>(rest, number_of_visuals.into())?;
+        Ok((Depth { depth, visuals }, rest))
+    }
+}
+
+impl DeserializeVariable for Screen {
+    fn deserialize(bytes: &[u8]) -> Result<(Self, &[u8]), ParseError> {
+        // 4  WINDOW                root
+        // 4  COLORMAP              default-colormap
+        // 4  CARD32                white-pixel
+        // 4  CARD32                black-pixel
+        // 4  SETofEVENT            current-input-masks
+        // 2  CARD16                width-in-pixels
+        // 2  CARD16                height-in-pixels
+        // 2  CARD16                width-in-millimeters
+        // 2  CARD16                height-in-millimeters
+        // 2  CARD16                min-installed-maps
+        // 2  CARD16                max-installed-maps
+        // 4  VISUALID              root-visual
+        // 1                        backing-stores
+        // 1  BOOL                  save-unders
+        // 1  CARD8                 root-depth
+        // 1  CARD8                 number of DEPTHs in allowed-depths
+        // n  LISTofDEPTH           allowed-depths
+        let (root, rest) = deserialize_into::<XId>(bytes)?;
+        let (default_colormap, rest) = deserialize_into::<u32>(rest)?;
+        let (white_pixel, rest) = deserialize_into::<u32>(rest)?;
+        let (black_pixel, rest) = deserialize_into::<u32>(rest)?;
+        let (current_input_masks, rest) = deserialize_into::<u32>(rest)?;
+        let (width_in_px, rest) = deserialize_into::<u16>(rest)?;
+        let (height_in_px, rest) = deserialize_into::<u16>(rest)?;
+        let (width_in_mm, rest) = deserialize_into::<u16>(rest)?;
+        let (height_in_mm, rest) = deserialize_into::<u16>(rest)?;
+        let (min_installed_maps, rest) = deserialize_into::<u16>(rest)?;
+        let (max_installed_maps, rest) = deserialize_into::<u16>(rest)?;
+        let (root_visual, rest) = deserialize_into::<u32>(rest)?;
+        let (backing_stores, rest) = deserialize_into::<u8>(rest)?;
+        let (save_unders, rest) = deserialize_into::<bool>(rest)?;
+        let (root_depth, rest) = deserialize_into::<u8>(rest)?;
+        let (number_of_depths, rest) = deserialize_into::<u8>(rest)?;
+        let (allowed_depths, rest) =
+            deserialize_into_variable_vec::<Depth>(rest, number_of_depths.into())?;
+        Ok((
+            Screen {
+                root,
+                default_colormap,
+                white_pixel,
+                black_pixel,
+                current_input_masks: EventMask(current_input_masks),
+                width_in_px,
+                height_in_px,
+                width_in_mm,
+                height_in_mm,
+                min_installed_maps,
+                max_installed_maps,
+                root_visual,
+                backing_stores: BackingStore::from(backing_stores),
+                save_unders,
+                root_depth,
+                allowed_depths,
+            },
+            rest,
+        ))
+    }
+}
+
+/// Returns the number of unused bytes needed to align `length` to the specified boundary.
+pub fn padding(length: usize, boundary: usize) -> usize {
+    (boundary - (length % boundary)) % boundary
+}
+
 /// Trims from the start of a byte slice to ensure alignment to
 /// the specified boundary, based on a given length.
-pub fn trim_by_padding(slice: &[u8], length: u16, boundary: u16) -> &[u8] {
-    let p = length % boundary;
-    if p > 0 {
-        &slice[p.into()..]
-    } else {
-        slice
-    }
+pub fn trim_by_padding(slice: &[u8], length: u16, boundary: u16) -> Result<&[u8], ParseError> {
+    skip(slice, padding(length.into(), boundary.into()))
 }
@@ -0,0 +1,93 @@
+//! Interrupting a blocking wait of a connection, e.g. to stop a listener thread.
+//!
+//! ```no_run
+//! # use mousetrap::connection::Connection;
+//! # use mousetrap::event::Event;
+//! let mut conn = Connection::init().unwrap();
+//! let waker = conn.create_waker().unwrap();
+//! let listener = std::thread::spawn(move || loop {
+//!     match conn.wait_for_event().unwrap() {
+//!         Event::Wakeup | Event::ConnectionClosed => break,
+//!         event => println!("{:?}", event),
+//!     }
+//! });
+//! waker.wake();
+//! listener.join().unwrap();
+//! ```
+
+use std::io;
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// A handle that makes a blocking wait of a [crate::connection::Connection] return
+/// [crate::event::Event::Wakeup]. See [crate::connection::Connection::create_waker].
+///
+/// It is a self-pipe: waking writes a byte the connection polls for along with its
+/// socket.
+#[derive(Debug, Clone)]
+pub struct Waker {
+    /// The write end of the pipe.
+    fd: Arc<OwnedFd>,
+}
+
+impl Waker {
+    pub(crate) fn new(fd: Arc<OwnedFd>) -> Self {
+        Waker { fd }
+    }
+
+    /// Makes the current blocking wait of the connection return, or the next one if
+    /// it is not waiting. Wakeups that were not seen yet are merged into one.
+    ///
+    /// It only calls `write(2)`, so it may be called from any thread and from a
+    /// signal handler.
+    pub fn wake(&self) {
+        let byte = 1u8;
+        // The pipe is non-blocking: if it is full, a wakeup is pending anyway.
+        unsafe { libc::write(self.fd.as_raw_fd(), (&byte as *const u8).cast(), 1) };
+    }
+}
+
+/// Creates a non-blocking pipe, returning its read and write ends.
+pub(crate) fn pipe() -> io::Result<(OwnedFd, OwnedFd)> {
+    let mut fds = [0; 2];
+    if unsafe { libc::pipe2(fds.as_mut_ptr(), libc::O_CLOEXEC | libc::O_NONBLOCK) } < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(unsafe { (OwnedFd::from_raw_fd(fds[0]), OwnedFd::from_raw_fd(fds[1])) })
+}
+
+/// Reads everything written to the read end of a pipe.
+pub(crate) fn drain(fd: &OwnedFd) {
+    let mut buf = [0u8; 64];
+    while unsafe { libc::read(fd.as_raw_fd(), buf.as_mut_ptr().cast(), buf.len()) } > 0 {}
+}
+
+/// Waits until one of `fds` is readable, at most `timeout` or forever if `None`.
+/// Returns how many are; 0 if it timed out or a signal interrupted the wait.
+pub(crate) fn poll_readable(
+    fds: &mut [libc::pollfd],
+    timeout: Option<Duration>,
+) -> io::Result<usize> {
+    // Rounded up, so waiting less than a millisecond does not spin.
+    let millis = match timeout {
+        Some(timeout) => {
+            let millis = timeout.as_nanos().div_ceil(1_000_000);
+            libc::c_int::try_from(millis).unwrap_or(libc::c_int::MAX)
+        }
+        None => -1,
+    };
+    for pollfd in fds.iter_mut() {
+        pollfd.events = libc::POLLIN;
+        pollfd.revents = 0;
+    }
+    let n = unsafe { libc::poll(fds.as_mut_ptr(), fds.len() as libc::nfds_t, millis) };
+    if n < 0 {
+        let err = io::Error::last_os_error();
+        if err.kind() == io::ErrorKind::Interrupted {
+            return Ok(0);
+        }
+        return Err(err);
+    }
+    Ok(n as usize)
+}
@@ -0,0 +1,375 @@
+//! Setting the desktop background, the way `feh --bg-fill` and Esetroot do.
+//!
+//! [set] scales an image to the root window, draws it to a pixmap and makes the
+//! pixmap the background of the root window. The pixmap is also published in
+//! the _XROOTPMAP_ID and ESETROOT_PMAP_ID properties of the root window, where
+//! compositors and pseudo-transparent terminals find the wallpaper to draw it
+//! themselves.
+//!
+//! The pixmap must outlive the connection that creates it, so it is created on
+//! a dedicated connection whose resources the server retains once it closes.
+//! The next client setting the wallpaper frees them with KillClient.
+//!
+//! ```no_run
+//! use mousetrap::wallpaper::{self, Placement, RgbImage};
+//!
+//! // A 2x1 image: a red pixel and a blue one.
+//! let data = [0xff, 0, 0, 0, 0, 0xff];
+//! let image = RgbImage {
+//!     width: 2,
+//!     height: 1,
+//!     data: &data,
+//! };
+//! wallpaper::set(&image, Placement::Fill).unwrap();
+//! ```
+
+use crate::connection::Connection;
+use crate::errors::{ConnectionError, SerializeError};
+use crate::protocol::{atom, Screen, VisualClass, XId};
+use crate::request::{CloseDownMode, GcValues, ImageFormat, PropMode, PutImage, WindowAttributes};
+
+/// An image with 8 bits of red, green and blue per pixel.
+#[derive(Debug, Clone, Copy)]
+pub struct RgbImage<'a> {
+    pub width: u32,
+    pub height: u32,
+    /// The rows from top to bottom, without padding, of 3 bytes per pixel: red,
+    /// green and blue.
+    pub data: &'a [u8],
+}
+
+/// How the image is laid out on the screen. The parts of the screen it does
+/// not cover are black.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Placement {
+    /// Scaled to cover the whole screen keeping its aspect ratio, cropping the
+    /// edges that do not fit, like `feh --bg-fill`.
+    Fill,
+    /// Scaled to the size of the screen, ignoring its aspect ratio, like
+    /// `feh --bg-scale`.
+    Scale,
+    /// Scaled to fit in the screen keeping its aspect ratio, like `feh --bg-max`.
+    Max,
+    /// At its size in the middle of the screen, like `feh --bg-center`.
+    Center,
+    /// Repeated from the top left corner, like `feh --bg-tile`.
+    Tile,
+}
+
+/// Sets the wallpaper of the default screen of the display in `DISPLAY`, and
+/// returns the pixmap holding it.
+pub fn set(image: &RgbImage, placement: Placement) -> Result<XId, ConnectionError> {
+    set_with(Connection::init()?, image, placement)
+}
+
+/// Sets the wallpaper of the default screen through `conn`, and returns the
+/// pixmap holding it.
+///
+/// Every resource of `conn` is retained when it closes, which it does on return.
+pub fn set_with(
+    mut conn: Connection,
+    image: &RgbImage,
+    placement: Placement,
+) -> Result<XId, ConnectionError> {
+    let expected = image.width as usize * image.height as usize * 3;
+    if image.data.len() != expected {
+        return Err(SerializeError::LengthMismatch {
+            field: "image data",
+            length: image.data.len(),
+            expected,
+        }
+        .into());
+    }
+    let screen = conn.default_screen();
+    let (root, depth) = (screen.root, screen.root_depth);
+    let (width, height) = (screen.width_in_px, screen.height_in_px);
+    let layout = PixelLayout::new(&conn, screen)?;
+    let background = screen.black_pixel;
+
+    let xrootpmap_id = conn.intern_atom(false, "_XROOTPMAP_ID")?;
+    let esetroot_pmap_id = conn.intern_atom(false, "ESETROOT_PMAP_ID")?;
+    let (xrootpmap_id, esetroot_pmap_id) = (
+        xrootpmap_id.reply(&mut conn)?.atom,
+        esetroot_pmap_id.reply(&mut conn)?.atom,
+    );
+    // Read the previous pixmaps while the image is sent.
+    let previous = conn.get_property(false, root, xrootpmap_id, atom::PIXMAP, 0, 1)?;
+    let previous_esetroot = conn.get_property(false, root, esetroot_pmap_id, atom::PIXMAP, 0, 1)?;
+
+    let pixmap = conn.create_pixmap(depth, root, width, height)?;
+    let gc = conn.create_gc(pixmap, GcValues::default())?;
+    let area = Area::new(image, placement, width, height);
+    let max_length = usize::from(conn.setup().maximum_request_length) * 4;
+    let rows_per_strip = ((max_length - PutImage::HEADER_LENGTH) / layout.stride(width)).max(1);
+    let mut y = 0;
+    while y < height {
+        let rows = (usize::from(height - y)).min(rows_per_strip) as u16;
+        let mut data = Vec::with_capacity(layout.stride(width) * usize::from(rows));
+        for row in y..y + rows {
+            let start = data.len();
+            for x in 0..width {
+                let pixel = match area.source(image, x, row) {
+                    Some(offset) => layout.pixel(&image.data[offset..offset + 3]),
+                    None => background,
+                };
+                layout.put(&mut data, pixel);
+            }
+            data.resize(start + layout.stride(width), 0);
+        }
+        conn.put_image(&PutImage {
+            format: ImageFormat::Z_PIXMAP,
+            drawable: pixmap,
+            gc,
+            width,
+            height: rows,
+            dst_x: 0,
+            dst_y: y as i16,
+            left_pad: 0,
+            depth,
+            data: &data,
+        })?;
+        y += rows;
+    }
+    conn.free_gc(gc)?;
+
+    conn.change_window_attributes(
+        root,
+        WindowAttributes {
+            background_pixmap: Some(pixmap),
+            ..Default::default()
+        },
+    )?;
+    conn.clear_area(false, root, 0, 0, 0, 0)?;
+    if let Some(error) = conn.sync()?.into_iter().next() {
+        return Err(ConnectionError::ProtocolError(error));
+    }
+
+    // The previous pixmap was retained for the wallpaper if both properties
+    // name it; otherwise it belongs to a client that may still need it.
+    let previous = previous.reply(&mut conn)?.value32();
+    let previous_esetroot = previous_esetroot.reply(&mut conn)?.value32();
+    if let (Some(&[previous]), Some(&[previous_esetroot])) =
+        (previous.as_deref(), previous_esetroot.as_deref())
+    {
+        if previous == previous_esetroot && previous != 0 {
+            conn.kill_client(previous)?;
+            // The client is gone already if the properties are stale.
+            conn.sync()?;
+        }
+    }
+    for property in [xrootpmap_id, esetroot_pmap_id] {
+        conn.change_property(
+            PropMode::REPLACE,
+            root,
+            property,
+            atom::PIXMAP,
+            32,
+            &pixmap.0.to_ne_bytes(),
+        )?;
+    }
+    conn.set_close_down_mode(CloseDownMode::RETAIN_PERMANENT)?;
+    if let Some(error) = conn.sync()?.into_iter().next() {
+        return Err(ConnectionError::ProtocolError(error));
+    }
+    Ok(pixmap)
+}
+
+impl Connection {
+    /// Returns the pixmap of the wallpaper of the default screen, as published in
+    /// the _XROOTPMAP_ID property of the root window.
+    pub fn wallpaper_pixmap(&mut self) -> Result<Option<XId>, ConnectionError> {
+        let root = self.default_root();
+        let property = self.intern_atom(false, "_XROOTPMAP_ID")?.reply(self)?.atom;
+        let reply = self
+            .get_property(false, root, property, atom::PIXMAP, 0, 1)?
+            .reply(self)?;
+        Ok(match reply.value32().as_deref() {
+            Some(&[pixmap]) if pixmap != 0 => Some(XId(pixmap)),
+            _ => None,
+        })
+    }
+}
+
+/// Where the image lands on the screen.
+struct Area {
+    placement: Placement,
+    x: i64,
+    y: i64,
+    width: i64,
+    height: i64,
+}
+
+impl Area {
+    fn new(image: &RgbImage, placement: Placement, width: u16, height: u16) -> Area {
+        let (screen_width, screen_height) = (i64::from(width), i64::from(height));
+        let (image_width, image_height) = (i64::from(image.width), i64::from(image.height));
+        let (area_width, area_height) = match placement {
+            Placement::Scale => (screen_width, screen_height),
+            Placement::Center | Placement::Tile => (image_width, image_height),
+            Placement::Fill | Placement::Max if image_width == 0 || image_height == 0 => (0, 0),
+            Placement::Fill | Placement::Max => {
+                // Compare the ratios of the screen and the image without rounding.
+                let wider = screen_width * image_height > screen_height * image_width;
+                if wider == (placement == Placement::Fill) {
+                    (screen_width, image_height * screen_width / image_width)
+                } else {
+                    (image_width * screen_height / image_height, screen_height)
+                }
+            }
+        };
+        let (x, y) = match placement {
+            Placement::Tile => (0, 0),
+            _ => (
+                (screen_width - area_width) / 2,
+                (screen_height - area_height) / 2,
+            ),
+        };
+        Area {
+            placement,
+            x,
+            y,
+            width: area_width,
+            height: area_height,
+        }
+    }
+
+    /// Returns the offset in the image data of the pixel drawn at a position of
+    /// the screen, picking the nearest one when scaling, or `None` if the image
+    /// does not cover the position.
+    fn source(&self, image: &RgbImage, x: u16, y: u16) -> Option<usize> {
+        if self.width <= 0 || self.height <= 0 {
+            return None;
+        }
+        let (x, y) = (i64::from(x) - self.x, i64::from(y) - self.y);
+        let (x, y) = match self.placement {
+            Placement::Tile => (x % self.width, y % self.height),
+            _ if x < 0 || y < 0 || x >= self.width || y >= self.height => return None,
+            _ => (x, y),
+        };
+        let source_x = x * i64::from(image.width) / self.width;
+        let source_y = y * i64::from(image.height) / self.height;
+        Some((source_y as usize * image.width as usize + source_x as usize) * 3)
+    }
+}
+
+/// How the pixels of the root window are stored in a ZPixmap image.
+pub(crate) struct PixelLayout {
+    red_mask: u32,
+    green_mask: u32,
+    blue_mask: u32,
+    pub(crate) bytes_per_pixel: usize,
+    scanline_pad: usize,
+    lsb_first: bool,
+}
+
+impl PixelLayout {
+    pub(crate) fn new(conn: &Connection, screen: &Screen) -> Result<PixelLayout, ConnectionError> {
+        let unsupported = ConnectionError::UnsupportedVisual(screen.root_visual);
+        let visual = screen
+            .allowed_depths
+            .iter()
+            .flat_map(|depth| &depth.visuals)
+            .find(|visual| visual.visual_id == screen.root_visual)
+            .filter(|visual| {
+                matches!(
+                    visual.class,
+                    VisualClass::TRUE_COLOR | VisualClass::DIRECT_COLOR
+                )
+            });
+        let format = conn
+            .setup()
+            .pixmap_formats
+            .iter()
+            .find(|format| format.depth == screen.root_depth);
+        let (Some(visual), Some(format)) = (visual, format) else {
+            return Err(unsupported);
+        };
+        if !matches!(format.bits_per_pixel, 16 | 24 | 32) {
+            return Err(unsupported);
+        }
+        Ok(PixelLayout {
+            red_mask: visual.red_mask,
+            green_mask: visual.green_mask,
+            blue_mask: visual.blue_mask,
+            bytes_per_pixel: usize::from(format.bits_per_pixel / 8),
+            scanline_pad: usize::from(format.scanline_pad / 8).max(1),
+            lsb_first: conn.setup().image_byte_order == 0,
+        })
+    }
+
+    /// Returns the length of a row of `width` pixels, with its padding.
+    pub(crate) fn stride(&self, width: u16) -> usize {
+        let length = usize::from(width) * self.bytes_per_pixel;
+        length.div_ceil(self.scanline_pad) * self.scanline_pad
+    }
+
+    /// Returns the pixel value of a red, green and blue color.
+    fn pixel(&self, rgb: &[u8]) -> u32 {
+        channel(rgb[0], self.red_mask)
+            | channel(rgb[1], self.green_mask)
+            | channel(rgb[2], self.blue_mask)
+    }
+
+    /// Appends a pixel in the image byte order of the server.
+    fn put(&self, data: &mut Vec<u8>, pixel: u32) {
+        let bytes = pixel.to_le_bytes();
+        let bytes = &bytes[..self.bytes_per_pixel];
+        if self.lsb_first {
+            data.extend_from_slice(bytes);
+        } else {
+            data.extend(bytes.iter().rev());
+        }
+    }
+
+    /// Reads the pixel at the start of `data`, in the image byte order of the server.
+    pub(crate) fn get(&self, data: &[u8]) -> u32 {
+        let mut bytes = [0; 4];
+        bytes[..self.bytes_per_pixel].copy_from_slice(&data[..self.bytes_per_pixel]);
+        if !self.lsb_first {
+            bytes[..self.bytes_per_pixel].reverse();
+        }
+        u32::from_le_bytes(bytes)
+    }
+
+    /// Returns the red, green and blue of a pixel value.
+    pub(crate) fn rgb(&self, pixel: u32) -> [u8; 3] {
+        [
+            channel_value(pixel, self.red_mask),
+            channel_value(pixel, self.green_mask),
+            channel_value(pixel, self.blue_mask),
+        ]
+    }
+}
+
+/// Scales an 8-bit channel to the bits of its mask.
+fn channel(value: u8, mask: u32) -> u32 {
+    if mask == 0 {
+        return 0;
+    }
+    let shift = mask.trailing_zeros();
+    let bits = (mask >> shift).count_ones();
+    let value = u32::from(value);
+    let scaled = if bits <= 8 {
+        value >> (8 - bits)
+    } else {
+        value << (bits - 8)
+    };
+    (scaled << shift) & mask
+}
+
+/// Scales the bits of a channel of a pixel to 8 bits.
+fn channel_value(pixel: u32, mask: u32) -> u8 {
+    if mask == 0 {
+        return 0;
+    }
+    let shift = mask.trailing_zeros();
+    let bits = (mask >> shift).count_ones();
+    let value = (pixel & mask) >> shift;
+    let scaled = if bits >= 8 {
+        value >> (bits - 8)
+    } else {
+        // Scale so that the largest value becomes 255.
+        value * 255 / ((1 << bits) - 1)
+    };
+    scaled as u8
+}
@@ -0,0 +1,75 @@
+//! What tools like xprop and xwininfo show about a window, in one round trip.
+//!
+//! ```no_run
+//! use mousetrap::connection::Connection;
+//! use mousetrap::window_info::WindowInfo;
+//!
+//! let mut conn = Connection::init().unwrap();
+//! if let Some(window) = conn.ewmh_active_window().unwrap() {
+//!     let info = WindowInfo::query(&mut conn, window).unwrap();
+//!     println!("{:?} {:?} {:?}", info.title, info.class, info.pid);
+//! }
+//! ```
+
+use crate::connection::Connection;
+use crate::errors::ConnectionError;
+use crate::icccm::{self, WmClass};
+use crate::protocol::{atom, Rectangle, Window};
+
+/// The title, class, process and geometry of a window.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WindowInfo {
+    pub window: Window,
+    /// _NET_WM_NAME if set, WM_NAME otherwise.
+    pub title: Option<String>,
+    /// WM_CLASS.
+    pub class: Option<WmClass>,
+    /// _NET_WM_PID.
+    pub pid: Option<u32>,
+    /// The position of the inside corner relative to the root window, and the
+    /// inside size.
+    pub geometry: Rectangle,
+    pub border_width: u16,
+}
+
+impl WindowInfo {
+    /// Queries the information of a window. Every request is sent before the
+    /// first reply is read, so this takes a single round trip once the EWMH
+    /// atoms are interned.
+    pub fn query(conn: &mut Connection, window: Window) -> Result<WindowInfo, ConnectionError> {
+        let atoms = conn.ewmh_atoms()?;
+        let net_wm_name =
+            conn.get_property(false, window, atoms.wm_name, atoms.utf8_string, 0, u32::MAX)?;
+        let wm_name = conn.get_property(false, window, atom::WM_NAME, atom::ANY, 0, u32::MAX)?;
+        let wm_class =
+            conn.get_property(false, window, atom::WM_CLASS, atom::STRING, 0, u32::MAX)?;
+        let pid = conn.get_property(false, window, atoms.wm_pid, atom::CARDINAL, 0, 1)?;
+        let geometry = conn.get_geometry(window)?;
+        let root = conn.default_root();
+        let position = conn.translate_coordinates(window, root, 0, 0)?;
+
+        let net_wm_name = net_wm_name.reply(conn)?;
+        let wm_name = wm_name.reply(conn)?;
+        let title = icccm::decode_text(&net_wm_name).or_else(|| icccm::decode_text(&wm_name));
+        let class = wm_class.reply(conn)?.value8().and_then(WmClass::decode);
+        let pid = pid
+            .reply(conn)?
+            .value32()
+            .and_then(|values| values.first().copied());
+        let geometry = geometry.reply(conn)?;
+        let position = position.reply(conn)?;
+        Ok(WindowInfo {
+            window,
+            title,
+            class,
+            pid,
+            geometry: Rectangle {
+                x: position.dst_x,
+                y: position.dst_y,
+                width: geometry.width,
+                height: geometry.height,
+            },
+            border_width: geometry.border_width,
+        })
+    }
+}
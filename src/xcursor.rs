@@ -0,0 +1,497 @@
+//! Cursors of the user's cursor theme, the way libXcursor loads them.
+//!
+//! A cursor theme is a directory of the icon search path, e.g.
+//! `/usr/share/icons/Adwaita`, holding a `cursors` directory with a file in the
+//! Xcursor format per cursor name. A theme missing a cursor takes it from the
+//! themes named by the `Inherits` key of its `index.theme`. Each file holds
+//! images of several nominal sizes; the ones closest to the size of the theme
+//! become a RENDER ARGB cursor, animated if there are several frames.
+//!
+//! ```no_run
+//! use mousetrap::connection::Connection;
+//! use mousetrap::request::cursor_font;
+//! use mousetrap::xcursor::CursorTheme;
+//!
+//! let mut conn = Connection::init().unwrap();
+//! let theme = CursorTheme::current(&mut conn).unwrap();
+//! // The core glyph serves when the theme has no such cursor.
+//! let cursor = match conn.load_cursor(&theme, "crosshair").unwrap() {
+//!     Some(cursor) => cursor,
+//!     None => conn.create_font_cursor(cursor_font::CROSSHAIR).unwrap(),
+//! };
+//! ```
+
+use crate::connection::Connection;
+use crate::errors::ConnectionError;
+use crate::protocol::XId;
+use crate::render::{self, AnimCursorElement, PictureAttributes, Render, StandardFormat};
+use crate::request::{GcValues, ImageFormat, PutImage};
+use std::env::var_os;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+/// The magic number starting an Xcursor file, "Xcur" in little-endian order.
+const MAGIC: u32 = 0x7275_6358;
+
+/// The type of the table of contents entries of images.
+const IMAGE_TYPE: u32 = 0xfffd_0002;
+
+/// The largest width and height of an image.
+const MAX_IMAGE_SIZE: u32 = 0x7fff;
+
+/// The theme used when none is configured, and as the last resort.
+const DEFAULT_THEME: &str = "default";
+
+/// How many themes deep the `Inherits` chain is followed, against cycles.
+const MAX_INHERIT_DEPTH: usize = 16;
+
+/// An image of a cursor file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CursorImage {
+    /// The nominal size the image was drawn for.
+    pub size: u32,
+    pub width: u16,
+    pub height: u16,
+    /// The hotspot, the point of the image at the pointer position.
+    pub xhot: u16,
+    pub yhot: u16,
+    /// How long the frame of an animated cursor is shown, in milliseconds.
+    pub delay: u32,
+    /// The rows from top to bottom of premultiplied ARGB pixels, with 8 bits
+    /// per channel from alpha in the high bits to blue in the low bits.
+    pub pixels: Vec<u32>,
+}
+
+/// Parses a file in the Xcursor format, and returns the images of the nominal
+/// size closest to `size`: one per frame, in the order of the animation.
+pub fn parse(bytes: &[u8], size: u32) -> io::Result<Vec<CursorImage>> {
+    // 4  CARD32   magic ("Xcur")
+    // 4  CARD32   header size
+    // 4  CARD32   version
+    // 4  CARD32   n, entries in the table of contents
+    // 12n         entries: type, subtype (the nominal size of images), position
+    if read_u32(bytes, 0)? != MAGIC {
+        return Err(invalid_data("not an Xcursor file"));
+    }
+    let header_size = read_u32(bytes, 4)? as usize;
+    let count = read_u32(bytes, 12)? as usize;
+    let mut entries = Vec::new();
+    for i in 0..count {
+        let offset = header_size + i * 12;
+        if read_u32(bytes, offset)? == IMAGE_TYPE {
+            entries.push((read_u32(bytes, offset + 4)?, read_u32(bytes, offset + 8)?));
+        }
+    }
+    let best = entries
+        .iter()
+        .map(|&(nominal, _)| nominal)
+        .min_by_key(|&nominal| nominal.abs_diff(size))
+        .ok_or_else(|| invalid_data("no images in the Xcursor file"))?;
+    entries
+        .into_iter()
+        .filter(|&(nominal, _)| nominal == best)
+        .map(|(_, position)| parse_image(bytes, position as usize))
+        .collect()
+}
+
+/// Parses the image chunk at `position`.
+fn parse_image(bytes: &[u8], position: usize) -> io::Result<CursorImage> {
+    // 4  CARD32   header size (36)
+    // 4  CARD32   type
+    // 4  CARD32   subtype, the nominal size
+    // 4  CARD32   version
+    // 4  CARD32   width
+    // 4  CARD32   height
+    // 4  CARD32   xhot
+    // 4  CARD32   yhot
+    // 4  CARD32   delay
+    // 4n          pixels
+    let field = |index: usize| read_u32(bytes, position + index * 4);
+    let header_size = field(0)? as usize;
+    if field(1)? != IMAGE_TYPE {
+        return Err(invalid_data(
+            "the table of contents does not point to an image",
+        ));
+    }
+    let (width, height) = (field(4)?, field(5)?);
+    let (xhot, yhot) = (field(6)?, field(7)?);
+    if width > MAX_IMAGE_SIZE || height > MAX_IMAGE_SIZE || xhot > width || yhot > height {
+        return Err(invalid_data("invalid Xcursor image size"));
+    }
+    let start = position + header_size;
+    let length = width as usize * height as usize * 4;
+    let data = bytes
+        .get(start..start + length)
+        .ok_or_else(|| invalid_data("truncated Xcursor image"))?;
+    Ok(CursorImage {
+        size: field(2)?,
+        width: width as u16,
+        height: height as u16,
+        xhot: xhot as u16,
+        yhot: yhot as u16,
+        delay: field(8)?,
+        pixels: data
+            .chunks_exact(4)
+            .map(|pixel| u32::from_le_bytes([pixel[0], pixel[1], pixel[2], pixel[3]]))
+            .collect(),
+    })
+}
+
+/// Reads a little-endian CARD32 of an Xcursor file.
+fn read_u32(bytes: &[u8], offset: usize) -> io::Result<u32> {
+    match bytes.get(offset..offset + 4) {
+        Some(value) => Ok(u32::from_le_bytes([value[0], value[1], value[2], value[3]])),
+        None => Err(invalid_data("truncated Xcursor file")),
+    }
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+/// Returns the directories themes are searched in: those of `XCURSOR_PATH`,
+/// separated by colons, or else the user's icon directories followed by the
+/// system ones.
+pub fn search_path() -> Vec<PathBuf> {
+    let home = var_os("HOME").map(PathBuf::from);
+    if let Some(path) = var_os("XCURSOR_PATH") {
+        return path
+            .to_string_lossy()
+            .split(':')
+            .filter(|dir| !dir.is_empty())
+            .map(|dir| match (dir.strip_prefix("~/"), &home) {
+                (Some(rest), Some(home)) => home.join(rest),
+                _ => PathBuf::from(dir),
+            })
+            .collect();
+    }
+    let mut path = Vec::new();
+    match var_os("XDG_DATA_HOME") {
+        Some(data) => path.push(PathBuf::from(data).join("icons")),
+        None => path.extend(home.iter().map(|home| home.join(".local/share/icons"))),
+    }
+    path.extend(home.iter().map(|home| home.join(".icons")));
+    path.push(PathBuf::from("/usr/share/icons"));
+    path.push(PathBuf::from("/usr/share/pixmaps"));
+    path
+}
+
+/// A cursor theme and the size its cursors are loaded at.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CursorTheme {
+    /// The name of the theme directory, e.g. "Adwaita".
+    pub name: String,
+    /// The nominal size of the cursors in pixels.
+    pub size: u32,
+    /// The directories the theme and the themes it inherits from are searched in.
+    pub path: Vec<PathBuf>,
+}
+
+impl CursorTheme {
+    /// Returns the theme of the given name, searched in [search_path].
+    pub fn new(name: &str, size: u32) -> CursorTheme {
+        CursorTheme {
+            name: name.to_owned(),
+            size,
+            path: search_path(),
+        }
+    }
+
+    /// Returns the theme configured for the user, the way libXcursor finds it:
+    /// the name comes from `XCURSOR_THEME` or the `Xcursor.theme` resource, and
+    /// the size from `XCURSOR_SIZE`, the `Xcursor.size` resource, 16 points at
+    /// `Xft.dpi`, or else a 48th of the smaller side of the default screen.
+    pub fn current(conn: &mut Connection) -> Result<CursorTheme, ConnectionError> {
+        let resources = conn.resources()?;
+        let env = |name| var_os(name).map(|value| value.to_string_lossy().into_owned());
+        let name = env("XCURSOR_THEME")
+            .filter(|name| !name.is_empty())
+            .or_else(|| resources.cursor_theme().map(str::to_owned))
+            .unwrap_or_else(|| DEFAULT_THEME.to_owned());
+        let screen = conn.default_screen();
+        let size = env("XCURSOR_SIZE")
+            .and_then(|size| size.trim().parse().ok())
+            .or_else(|| resources.cursor_size())
+            .or_else(|| resources.dpi().map(|dpi| (dpi * 16.0 / 72.0) as u32))
+            .filter(|&size| size > 0)
+            .unwrap_or_else(|| u32::from(screen.width_in_px.min(screen.height_in_px)) / 48);
+        Ok(CursorTheme::new(&name, size))
+    }
+
+    /// Returns the file of a cursor, e.g. "left_ptr", from the theme, the
+    /// themes it inherits from, or else the default theme.
+    pub fn find(&self, cursor: &str) -> Option<PathBuf> {
+        let mut visited = Vec::new();
+        self.find_in(&self.name, cursor, &mut visited)
+            .or_else(|| self.find_in(DEFAULT_THEME, cursor, &mut visited))
+    }
+
+    /// Searches a theme and the themes it inherits from, skipping the themes
+    /// already searched.
+    fn find_in(&self, theme: &str, cursor: &str, visited: &mut Vec<String>) -> Option<PathBuf> {
+        if visited.len() >= MAX_INHERIT_DEPTH || visited.iter().any(|name| name == theme) {
+            return None;
+        }
+        visited.push(theme.to_owned());
+        let found = self
+            .path
+            .iter()
+            .map(|dir| dir.join(theme).join("cursors").join(cursor))
+            .find(|file| file.is_file());
+        if found.is_some() {
+            return found;
+        }
+        // The first index.theme of the theme in the path tells what it inherits.
+        let index = self
+            .path
+            .iter()
+            .find_map(|dir| fs::read_to_string(dir.join(theme).join("index.theme")).ok())?;
+        inherits(&index)
+            .into_iter()
+            .find_map(|parent| self.find_in(&parent, cursor, visited))
+    }
+
+    /// Returns the images of a cursor closest to the size of the theme, or
+    /// `None` if the theme has no such cursor.
+    pub fn load_images(&self, cursor: &str) -> io::Result<Option<Vec<CursorImage>>> {
+        match self.find(cursor) {
+            Some(file) => Ok(Some(parse(&fs::read(file)?, self.size)?)),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Returns the themes of the `Inherits` key of an `index.theme` file.
+fn inherits(index: &str) -> Vec<String> {
+    index
+        .lines()
+        .filter_map(|line| {
+            let (key, value) = line.split_once('=')?;
+            (key.trim() == "Inherits").then_some(value)
+        })
+        .flat_map(|value| value.split([',', ';', ' ', '\t']))
+        .filter(|theme| !theme.is_empty())
+        .map(str::to_owned)
+        .collect()
+}
+
+impl Connection {
+    /// Loads a cursor, e.g. "left_ptr" or "crosshair", from a theme and returns
+    /// its id, or `None` if the theme has no such cursor. Requires RENDER 0.5,
+    /// and 0.8 for animated cursors, which are otherwise shown still.
+    pub fn load_cursor(
+        &mut self,
+        theme: &CursorTheme,
+        name: &str,
+    ) -> Result<Option<XId>, ConnectionError> {
+        match theme.load_images(name)? {
+            Some(images) => self.create_cursor_from_images(&images).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Creates a cursor from the frames of a cursor file and returns its id.
+    /// Requires RENDER 0.5, and 0.8 for several frames, of which only the first
+    /// is used otherwise.
+    pub fn create_cursor_from_images(
+        &mut self,
+        images: &[CursorImage],
+    ) -> Result<XId, ConnectionError> {
+        let version = match self.enable::<Render>()? {
+            Some(version) if version >= (0, 5) => version,
+            _ => return Err(ConnectionError::ExtensionNotSupported(render::NAME)),
+        };
+        let formats = self.render_query_pict_formats()?.reply(self)?;
+        let format = formats
+            .find_standard(StandardFormat::Argb32)
+            .ok_or(ConnectionError::ExtensionNotSupported(render::NAME))?
+            .id;
+        let images = if version >= (0, 8) {
+            images
+        } else {
+            &images[..images.len().min(1)]
+        };
+        let mut frames = Vec::with_capacity(images.len());
+        for image in images {
+            let cursor = self.create_image_cursor(image, format)?;
+            frames.push(AnimCursorElement {
+                cursor,
+                delay: image.delay,
+            });
+        }
+        match frames[..] {
+            [] => Err(ConnectionError::Io(invalid_data("no cursor images"))),
+            [frame] => Ok(frame.cursor),
+            _ => {
+                let cursor = self.render_create_anim_cursor(&frames)?;
+                for frame in frames {
+                    self.free_cursor(frame.cursor)?;
+                }
+                Ok(cursor)
+            }
+        }
+    }
+
+    /// Draws an image to an ARGB32 picture and creates a cursor from it.
+    fn create_image_cursor(
+        &mut self,
+        image: &CursorImage,
+        format: render::PictFormat,
+    ) -> Result<XId, ConnectionError> {
+        let root = self.default_root();
+        let (width, height) = (image.width.max(1), image.height.max(1));
+        let pixmap = self.create_pixmap(32, root, width, height)?;
+        let gc = self.create_gc(pixmap, GcValues::default())?;
+        let lsb_first = self.setup().image_byte_order == 0;
+        let stride = usize::from(image.width) * 4;
+        let max_length = usize::from(self.setup().maximum_request_length) * 4;
+        let rows_per_strip = ((max_length - PutImage::HEADER_LENGTH) / stride.max(1)).max(1);
+        for (strip, rows) in image
+            .pixels
+            .chunks(usize::from(image.width).max(1) * rows_per_strip)
+            .enumerate()
+        {
+            let data: Vec<u8> = rows
+                .iter()
+                .flat_map(|&pixel| {
+                    if lsb_first {
+                        pixel.to_le_bytes()
+                    } else {
+                        pixel.to_be_bytes()
+                    }
+                })
+                .collect();
+            self.put_image(&PutImage {
+                format: ImageFormat::Z_PIXMAP,
+                drawable: pixmap,
+                gc,
+                width: image.width,
+                height: (data.len() / stride) as u16,
+                dst_x: 0,
+                dst_y: (strip * rows_per_strip) as i16,
+                left_pad: 0,
+                depth: 32,
+                data: &data,
+            })?;
+        }
+        self.free_gc(gc)?;
+        let picture = self.render_create_picture(pixmap, format, &PictureAttributes::default())?;
+        self.free_pixmap(pixmap)?;
+        let cursor = self.render_create_cursor(
+            picture,
+            image.xhot.min(width - 1),
+            image.yhot.min(height - 1),
+        )?;
+        self.render_free_picture(picture)?;
+        Ok(cursor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process;
+
+    /// An image of the given size, filled with one pixel value.
+    fn image(size: u32, width: u16, height: u16, delay: u32, pixel: u32) -> CursorImage {
+        CursorImage {
+            size,
+            width,
+            height,
+            xhot: width / 2,
+            yhot: height / 2,
+            delay,
+            pixels: vec![pixel; usize::from(width) * usize::from(height)],
+        }
+    }
+
+    /// Serializes an Xcursor file of images.
+    fn file(images: &[CursorImage]) -> Vec<u8> {
+        let mut words = vec![MAGIC, 16, 0x1_0000, images.len() as u32];
+        let mut position = 16 + images.len() as u32 * 12;
+        for image in images {
+            words.extend_from_slice(&[IMAGE_TYPE, image.size, position]);
+            position += 36 + image.pixels.len() as u32 * 4;
+        }
+        for image in images {
+            words.extend_from_slice(&[36, IMAGE_TYPE, image.size, 1]);
+            for value in [image.width, image.height, image.xhot, image.yhot] {
+                words.push(u32::from(value));
+            }
+            words.push(image.delay);
+            words.extend_from_slice(&image.pixels);
+        }
+        words.iter().flat_map(|word| word.to_le_bytes()).collect()
+    }
+
+    #[test]
+    fn parse_picks_the_closest_size() {
+        let frames = [
+            image(24, 2, 2, 50, 0xff00_0000),
+            image(24, 2, 2, 60, 0xffff_ffff),
+        ];
+        let large = image(32, 3, 1, 0, 0x8000_0080);
+        let bytes = file(&[frames[0].clone(), frames[1].clone(), large.clone()]);
+        assert_eq!(parse(&bytes, 22).unwrap(), frames);
+        assert_eq!(parse(&bytes, 30).unwrap(), [large]);
+    }
+
+    #[test]
+    fn parse_rejects_invalid_files() {
+        let bytes = file(&[image(24, 2, 2, 0, 0)]);
+        let error = |bytes: &[u8]| parse(bytes, 24).unwrap_err().to_string();
+        assert_eq!(error(&bytes[..bytes.len() - 1]), "truncated Xcursor image");
+        assert_eq!(error(&bytes[..20]), "truncated Xcursor file");
+        assert_eq!(error(&bytes[4..]), "not an Xcursor file");
+        assert_eq!(error(&file(&[])), "no images in the Xcursor file");
+        let hotspot_outside = CursorImage {
+            xhot: 3,
+            ..image(24, 2, 2, 0, 0)
+        };
+        assert_eq!(
+            error(&file(&[hotspot_outside])),
+            "invalid Xcursor image size"
+        );
+        let mut too_wide = file(&[image(24, 1, 0, 0, 0)]);
+        // The width of the image, after the header and the table of contents.
+        too_wide[28 + 16..28 + 20].copy_from_slice(&(MAX_IMAGE_SIZE + 1).to_le_bytes());
+        assert_eq!(error(&too_wide), "invalid Xcursor image size");
+    }
+
+    #[test]
+    fn inherits_lists_the_parent_themes() {
+        let index = "[Icon Theme]\nName=Mine\nInherits = Adwaita, hicolor;core\n";
+        assert_eq!(inherits(index), ["Adwaita", "hicolor", "core"]);
+        assert!(inherits("[Icon Theme]\nName=Mine\n").is_empty());
+    }
+
+    #[test]
+    fn find_follows_the_inherits_chain() {
+        let dir = std::env::temp_dir().join(format!("mousetrap-xcursor-{}", process::id()));
+        let theme = |name: &str, inherits: &str, cursors: &[&str]| {
+            let cursors_dir = dir.join(name).join("cursors");
+            fs::create_dir_all(&cursors_dir).unwrap();
+            let index = format!("[Icon Theme]\nInherits={}\n", inherits);
+            fs::write(dir.join(name).join("index.theme"), index).unwrap();
+            for cursor in cursors {
+                fs::write(cursors_dir.join(cursor), b"").unwrap();
+            }
+        };
+        // Mine inherits from Base, which inherits back from Mine.
+        theme("Mine", "Base", &["left_ptr"]);
+        theme("Base", "Mine", &["crosshair"]);
+        theme(DEFAULT_THEME, "", &["watch"]);
+        let theme = CursorTheme {
+            name: String::from("Mine"),
+            size: 24,
+            path: vec![dir.clone()],
+        };
+        let found = |cursor| theme.find(cursor);
+        assert_eq!(found("left_ptr"), Some(dir.join("Mine/cursors/left_ptr")));
+        assert_eq!(found("crosshair"), Some(dir.join("Base/cursors/crosshair")));
+        assert_eq!(found("watch"), Some(dir.join("default/cursors/watch")));
+        assert_eq!(found("hand2"), None);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,318 @@
+//! The target side of XDND, the drag-and-drop protocol of X.
+//!
+//! A window accepting drops advertises the protocol with its XdndAware property.
+//! The source of a drag then sends it ClientMessages as the pointer enters,
+//! moves over and leaves it, and when the user drops. [DropTarget] answers them,
+//! accepting the drags offering one of the types it was created with, and fetches
+//! the dropped data through the XdndSelection selection.
+//!
+//! ```no_run
+//! use mousetrap::connection::Connection;
+//! use mousetrap::xdnd::{parse_uri_list, DropEvent, DropTarget};
+//!
+//! let mut conn = Connection::init().unwrap();
+//! let window = conn.default_root();
+//! let mut target = DropTarget::new(&mut conn, window, &["text/uri-list"]).unwrap();
+//! loop {
+//!     let event = conn.wait_for_event().unwrap();
+//!     if let Some(DropEvent::Drop { data: Some(data), .. }) =
+//!         target.handle_event(&mut conn, &event).unwrap()
+//!     {
+//!         println!("{:?}", parse_uri_list(&data.data));
+//!     }
+//! }
+//! ```
+
+use crate::clipboard::{Clipboard, SelectionData};
+use crate::connection::Connection;
+use crate::errors::ConnectionError;
+use crate::event::{ClientMessageEvent, Event};
+use crate::protocol::{atom, Atom, EventMask, Window, XId};
+use crate::request::PropMode;
+
+/// Version of the protocol implemented by this module.
+pub const VERSION: u32 = 5;
+
+/// Names of the atoms of the protocol, in the order of the fields of [Atoms].
+const ATOM_NAMES: [&str; 10] = [
+    "XdndAware",
+    "XdndEnter",
+    "XdndPosition",
+    "XdndStatus",
+    "XdndLeave",
+    "XdndDrop",
+    "XdndFinished",
+    "XdndSelection",
+    "XdndTypeList",
+    "XdndActionCopy",
+];
+
+/// The atoms of the protocol.
+#[derive(Debug, Clone, Copy)]
+struct Atoms {
+    aware: Atom,
+    enter: Atom,
+    position: Atom,
+    status: Atom,
+    leave: Atom,
+    drop: Atom,
+    finished: Atom,
+    selection: Atom,
+    type_list: Atom,
+    action_copy: Atom,
+}
+
+/// The drag in progress over the window.
+#[derive(Debug, Clone)]
+struct Drag {
+    source: Window,
+    /// The offered type the target chose, if any.
+    accepted: Option<Atom>,
+    /// The action the source last asked for.
+    action: Atom,
+    /// The last position of the pointer, in root window coordinates.
+    position: (i16, i16),
+}
+
+/// What happened to a drag over the window.
+#[derive(Debug, Clone)]
+pub enum DropEvent {
+    /// A drag entered the window, offering the data in `types`.
+    Enter { source: Window, types: Vec<Atom> },
+    /// The pointer moved over the window, at `x` and `y` in root window
+    /// coordinates. `accepted` tells whether the drag offers an accepted type.
+    Position { x: i16, y: i16, accepted: bool },
+    /// The drag left the window, or was cancelled.
+    Leave,
+    /// The user dropped at `x` and `y`, in root window coordinates. `data` is
+    /// `None` if the drag offered no accepted type or the source did not send it.
+    Drop {
+        x: i16,
+        y: i16,
+        data: Option<SelectionData>,
+    },
+}
+
+/// Accepts drops on a window.
+pub struct DropTarget {
+    window: Window,
+    atoms: Atoms,
+    /// The types the window accepts, most preferred first.
+    accepted_types: Vec<Atom>,
+    drag: Option<Drag>,
+    /// Fetches the dropped data.
+    clipboard: Clipboard,
+}
+
+impl DropTarget {
+    /// Makes `window` accept the drops of data in one of `types`, e.g.
+    /// `text/uri-list` for files or `text/plain;charset=utf-8`, most preferred
+    /// first.
+    pub fn new(
+        conn: &mut Connection,
+        window: Window,
+        types: &[&str],
+    ) -> Result<DropTarget, ConnectionError> {
+        let mut cookies = Vec::with_capacity(ATOM_NAMES.len() + types.len());
+        for name in ATOM_NAMES.iter().chain(types) {
+            cookies.push(conn.intern_atom(false, name)?);
+        }
+        let mut atoms = Vec::with_capacity(cookies.len());
+        for cookie in cookies {
+            atoms.push(cookie.reply(conn)?.atom);
+        }
+        let accepted_types = atoms.split_off(ATOM_NAMES.len());
+        // The fields are evaluated in order, so each takes the next atom.
+        let mut atoms = atoms.into_iter();
+        let mut next = || atoms.next().unwrap_or(atom::NONE);
+        let atoms = Atoms {
+            aware: next(),
+            enter: next(),
+            position: next(),
+            status: next(),
+            leave: next(),
+            drop: next(),
+            finished: next(),
+            selection: next(),
+            type_list: next(),
+            action_copy: next(),
+        };
+        conn.change_property(
+            PropMode::REPLACE,
+            window,
+            atoms.aware,
+            atom::ATOM,
+            32,
+            &VERSION.to_ne_bytes(),
+        )?;
+        Ok(DropTarget {
+            window,
+            atoms,
+            accepted_types,
+            drag: None,
+            clipboard: Clipboard::new(conn)?,
+        })
+    }
+
+    /// Returns the window accepting the drops.
+    pub fn window(&self) -> Window {
+        self.window
+    }
+
+    /// Handles the XDND messages sent to the window, answering the source. Returns
+    /// what happened, or `None` if the event is not one of them.
+    pub fn handle_event(
+        &mut self,
+        conn: &mut Connection,
+        event: &Event,
+    ) -> Result<Option<DropEvent>, ConnectionError> {
+        let Event::ClientMessage(message) = event else {
+            return Ok(None);
+        };
+        if message.window != self.window || message.format != 32 {
+            return Ok(None);
+        }
+        let data = message.data32();
+        let source = XId(data[0]);
+        let message_type = message.message_type;
+        if message_type == self.atoms.enter {
+            self.enter(conn, source, data).map(Some)
+        } else if message_type == self.atoms.position {
+            self.position(conn, source, data).map(Some)
+        } else if message_type == self.atoms.leave {
+            self.drag = None;
+            Ok(Some(DropEvent::Leave))
+        } else if message_type == self.atoms.drop {
+            self.drop(conn, source).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Handles XdndEnter: notes the types the source offers.
+    //
+    //   data[0]    Source window
+    //   data[1]    Version in the high byte; bit 0 if there are more than 3 types
+    //   data[2..5] The first 3 types, None if fewer
+    fn enter(
+        &mut self,
+        conn: &mut Connection,
+        source: Window,
+        data: [u32; 5],
+    ) -> Result<DropEvent, ConnectionError> {
+        let types = if data[1] & 1 != 0 {
+            conn.get_property(false, source, self.atoms.type_list, atom::ATOM, 0, u32::MAX)?
+                .reply(conn)?
+                .value32()
+                .unwrap_or_default()
+        } else {
+            data[2..]
+                .iter()
+                .copied()
+                .filter(|&t| t != atom::NONE)
+                .collect()
+        };
+        let accepted = self
+            .accepted_types
+            .iter()
+            .copied()
+            .find(|t| types.contains(t));
+        self.drag = Some(Drag {
+            source,
+            accepted,
+            action: self.atoms.action_copy,
+            position: (0, 0),
+        });
+        Ok(DropEvent::Enter { source, types })
+    }
+
+    /// Handles XdndPosition: answers whether a drop would be accepted.
+    //
+    //   data[0]    Source window
+    //   data[2]    Pointer position in root coordinates, x << 16 | y
+    //   data[3]    Timestamp
+    //   data[4]    Requested action
+    fn position(
+        &mut self,
+        conn: &mut Connection,
+        source: Window,
+        data: [u32; 5],
+    ) -> Result<DropEvent, ConnectionError> {
+        let x = (data[2] >> 16) as i16;
+        let y = data[2] as i16;
+        let Some(drag) = self.drag.as_mut().filter(|drag| drag.source == source) else {
+            return Ok(DropEvent::Position {
+                x,
+                y,
+                accepted: false,
+            });
+        };
+        drag.position = (x, y);
+        drag.action = data[4];
+        let accepted = drag.accepted.is_some();
+        // Bit 0: whether a drop would be accepted. Bit 1: send a position for
+        // every move, as no rectangle without positions is given.
+        let flags = u32::from(accepted) | 2;
+        let action = if accepted { drag.action } else { atom::NONE };
+        self.send(conn, source, self.atoms.status, [flags, 0, 0, action])?;
+        Ok(DropEvent::Position { x, y, accepted })
+    }
+
+    /// Handles XdndDrop: fetches the data and tells the source it is done with it.
+    fn drop(
+        &mut self,
+        conn: &mut Connection,
+        source: Window,
+    ) -> Result<DropEvent, ConnectionError> {
+        let Some(drag) = self.drag.take().filter(|drag| drag.source == source) else {
+            return Ok(DropEvent::Leave);
+        };
+        let data = match drag.accepted {
+            Some(target) => {
+                let data = self.clipboard.convert(conn, self.atoms.selection, target);
+                match data {
+                    Ok(data) => data,
+                    Err(ConnectionError::Timeout) => None,
+                    Err(err) => return Err(err),
+                }
+            }
+            None => None,
+        };
+        let accepted = data.is_some();
+        let action = if accepted { drag.action } else { atom::NONE };
+        self.send(
+            conn,
+            source,
+            self.atoms.finished,
+            [u32::from(accepted), action, 0, 0],
+        )?;
+        let (x, y) = drag.position;
+        Ok(DropEvent::Drop { x, y, data })
+    }
+
+    /// Sends a message to the source of the drag. The first value of every
+    /// message is the target window.
+    fn send(
+        &self,
+        conn: &mut Connection,
+        source: Window,
+        message_type: Atom,
+        data: [u32; 4],
+    ) -> Result<u16, ConnectionError> {
+        let [a, b, c, d] = data;
+        let message =
+            ClientMessageEvent::new_format32(source, message_type, [self.window.0, a, b, c, d]);
+        conn.send_event(false, source, EventMask::NO_EVENT, message.serialize())
+    }
+}
+
+/// Parses a text/uri-list: one URI per line, skipping the comments starting
+/// with `#`.
+pub fn parse_uri_list(data: &[u8]) -> Vec<String> {
+    String::from_utf8_lossy(data)
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_owned)
+        .collect()
+}
@@ -0,0 +1,701 @@
+//! The XFIXES extension: cursor images and notifications, selection
+//! notifications, regions and pointer barriers.
+//!
+//! Screen recorders use it to capture the cursor, which is not part of the
+//! screen contents, pointer confinement tools use its barriers, and clipboard
+//! managers learn from it when a selection changes owner.
+//!
+//! The server treats a client as an XFIXES 0.0 client until it called
+//! [Connection::xfixes_query_version], so call it before any other request.
+
+use crate::connection::Connection;
+use crate::cookie::{Cookie, Reply};
+use crate::errors::{ConnectionError, ParseError};
+use crate::event::Event;
+use crate::extension::{Extension, Version};
+use crate::protocol::{Atom, Rectangle, Timestamp, Window, XId};
+use crate::request::RequestBuilder;
+use crate::utils::{check_trailing_bytes, deserialize_into, deserialize_into_vec, skip};
+
+/// Name of the extension, as given to QueryExtension.
+pub const NAME: &str = "XFIXES";
+
+/// The newest version of the extension this module implements.
+pub const VERSION: (u32, u32) = (5, 0);
+
+/// Minor opcodes of the XFIXES requests.
+pub mod opcode {
+    pub const QUERY_VERSION: u8 = 0;
+    pub const SELECT_SELECTION_INPUT: u8 = 2;
+    pub const SELECT_CURSOR_INPUT: u8 = 3;
+    pub const GET_CURSOR_IMAGE: u8 = 4;
+    pub const CREATE_REGION: u8 = 5;
+    pub const CREATE_REGION_FROM_BITMAP: u8 = 6;
+    pub const CREATE_REGION_FROM_WINDOW: u8 = 7;
+    pub const DESTROY_REGION: u8 = 10;
+    pub const SET_REGION: u8 = 11;
+    pub const COPY_REGION: u8 = 12;
+    pub const UNION_REGION: u8 = 13;
+    pub const INTERSECT_REGION: u8 = 14;
+    pub const SUBTRACT_REGION: u8 = 15;
+    pub const TRANSLATE_REGION: u8 = 17;
+    pub const REGION_EXTENTS: u8 = 18;
+    pub const FETCH_REGION: u8 = 19;
+    pub const HIDE_CURSOR: u8 = 29;
+    pub const SHOW_CURSOR: u8 = 30;
+    pub const CREATE_POINTER_BARRIER: u8 = 31;
+    pub const DELETE_POINTER_BARRIER: u8 = 32;
+}
+
+/// Names of the XFIXES requests, indexed by minor opcode.
+pub const REQUEST_NAMES: &[&str] = &[
+    "QueryVersion",
+    "ChangeSaveSet",
+    "SelectSelectionInput",
+    "SelectCursorInput",
+    "GetCursorImage",
+    "CreateRegion",
+    "CreateRegionFromBitmap",
+    "CreateRegionFromWindow",
+    "CreateRegionFromGC",
+    "CreateRegionFromPicture",
+    "DestroyRegion",
+    "SetRegion",
+    "CopyRegion",
+    "UnionRegion",
+    "IntersectRegion",
+    "SubtractRegion",
+    "InvertRegion",
+    "TranslateRegion",
+    "RegionExtents",
+    "FetchRegion",
+    "SetGCClipRegion",
+    "SetWindowShapeRegion",
+    "SetPictureClipRegion",
+    "SetCursorName",
+    "GetCursorName",
+    "GetCursorImageAndName",
+    "ChangeCursor",
+    "ChangeCursorByName",
+    "ExpandRegion",
+    "HideCursor",
+    "ShowCursor",
+    "CreatePointerBarrier",
+    "DeletePointerBarrier",
+];
+
+/// Names of the XFIXES errors, indexed from the first error.
+pub const ERROR_NAMES: &[&str] = &["BadRegion"];
+
+/// Event codes of the XFIXES events, relative to the first event of the extension.
+pub mod event_code {
+    pub const SELECTION_NOTIFY: u8 = 0;
+    pub const CURSOR_NOTIFY: u8 = 1;
+}
+
+/// The events selected by SelectCursorInput.
+pub const CURSOR_NOTIFY_MASK: u32 = 1;
+
+/// The events selected by SelectSelectionInput: a client became the owner of
+/// the selection, the window owning it was destroyed, or its client closed.
+pub const SET_SELECTION_OWNER_NOTIFY_MASK: u32 = 1;
+pub const SELECTION_WINDOW_DESTROY_NOTIFY_MASK: u32 = 2;
+pub const SELECTION_CLIENT_CLOSE_NOTIFY_MASK: u32 = 4;
+
+/// The values of [SelectionNotifyEvent::subtype].
+pub mod selection_subtype {
+    pub const SET_SELECTION_OWNER: u8 = 0;
+    pub const SELECTION_WINDOW_DESTROY: u8 = 1;
+    pub const SELECTION_CLIENT_CLOSE: u8 = 2;
+}
+
+/// Starts a request of the extension.
+fn request(conn: &mut Connection, minor_opcode: u8) -> Result<RequestBuilder, ConnectionError> {
+    let info = conn.require_extension(NAME, REQUEST_NAMES, ERROR_NAMES)?;
+    Ok(RequestBuilder::new(info.major_opcode, minor_opcode))
+}
+
+/// The XFIXES extension, for [Connection::enable].
+pub struct XFixes;
+
+impl Extension for XFixes {
+    const NAME: &'static str = NAME;
+    const MIN_VERSION: Version = (2, 0);
+
+    fn negotiate(conn: &mut Connection) -> Result<Option<Version>, ConnectionError> {
+        let reply = conn
+            .xfixes_query_version(VERSION.0, VERSION.1)?
+            .reply(conn)?;
+        Ok(Some((reply.major_version, reply.minor_version)))
+    }
+}
+
+/// Reply of the XFixesQueryVersion request.
+#[derive(Debug, Clone)]
+pub struct QueryVersionReply {
+    pub major_version: u32,
+    pub minor_version: u32,
+}
+
+impl Reply for QueryVersionReply {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1                        unused
+        // 2  CARD16                sequence number
+        // 4  0                     reply length
+        // 4  CARD32                major-version
+        // 4  CARD32                minor-version
+        // 16                       unused
+        let rest = skip(bytes, 8)?;
+        let (major_version, rest) = deserialize_into::<u32>(rest)?;
+        let (minor_version, _) = deserialize_into::<u32>(rest)?;
+        Ok(QueryVersionReply {
+            major_version,
+            minor_version,
+        })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        check_trailing_bytes("xfixes::QueryVersionReply", bytes, 32)
+    }
+}
+
+/// The image of the current cursor, as returned by GetCursorImage.
+#[derive(Debug, Clone)]
+pub struct CursorImage {
+    /// Position of the hotspot on the screen.
+    pub x: i16,
+    pub y: i16,
+    pub width: u16,
+    pub height: u16,
+    /// Position of the hotspot in the image.
+    pub xhot: u16,
+    pub yhot: u16,
+    /// Changes whenever the cursor changes, see [CursorNotifyEvent].
+    pub cursor_serial: u32,
+    /// `width * height` pixels, row by row, as premultiplied ARGB.
+    pub pixels: Vec<u32>,
+}
+
+impl Reply for CursorImage {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1                        unused
+        // 2  CARD16                sequence number
+        // 4  CARD32                reply length
+        // 2  INT16                 x
+        // 2  INT16                 y
+        // 2  CARD16                width
+        // 2  CARD16                height
+        // 2  CARD16                xhot
+        // 2  CARD16                yhot
+        // 4  CARD32                cursor-serial
+        // 8                        unused
+        // 4n LISTofCARD32          cursor-image (n = width * height)
+        let rest = skip(bytes, 8)?;
+        let (x, rest) = deserialize_into::<i16>(rest)?;
+        let (y, rest) = deserialize_into::<i16>(rest)?;
+        let (width, rest) = deserialize_into::<u16>(rest)?;
+        let (height, rest) = deserialize_into::<u16>(rest)?;
+        let (xhot, rest) = deserialize_into::<u16>(rest)?;
+        let (yhot, rest) = deserialize_into::<u16>(rest)?;
+        let (cursor_serial, rest) = deserialize_into::<u32>(rest)?;
+        let rest = skip(rest, 8)?;
+        let (pixels, _) =
+            deserialize_into_vec::<u32>(rest, usize::from(width) * usize::from(height))?;
+        Ok(CursorImage {
+            x,
+            y,
+            width,
+            height,
+            xhot,
+            yhot,
+            cursor_serial,
+            pixels,
+        })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        check_trailing_bytes("xfixes::CursorImage", bytes, 32 + self.pixels.len() * 4)
+    }
+}
+
+/// Reply of the FetchRegion request.
+#[derive(Debug, Clone)]
+pub struct FetchRegionReply {
+    /// The bounding box of the region.
+    pub extents: Rectangle,
+    /// The rectangles making up the region.
+    pub rectangles: Vec<Rectangle>,
+}
+
+impl Reply for FetchRegionReply {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1                        unused
+        // 2  CARD16                sequence number
+        // 4  2n                    reply length
+        // 8  RECTANGLE             extents
+        // 16                       unused
+        // 8n LISTofRECTANGLE       rectangles
+        let rest = skip(bytes, 4)?;
+        let (length, rest) = deserialize_into::<u32>(rest)?;
+        let (extents, rest) = deserialize_into_vec::<Rectangle>(rest, 1)?;
+        let rest = skip(rest, 16)?;
+        let (rectangles, _) = deserialize_into_vec::<Rectangle>(rest, length as usize / 2)?;
+        Ok(FetchRegionReply {
+            extents: extents[0],
+            rectangles,
+        })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        check_trailing_bytes(
+            "xfixes::FetchRegionReply",
+            bytes,
+            32 + self.rectangles.len() * 8,
+        )
+    }
+}
+
+/// The sides of a pointer barrier the pointer may cross it from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct BarrierDirections(pub u32);
+
+impl BarrierDirections {
+    /// The barrier blocks the pointer from both sides.
+    pub const NONE: Self = Self(0);
+    pub const POSITIVE_X: Self = Self(1);
+    pub const POSITIVE_Y: Self = Self(2);
+    pub const NEGATIVE_X: Self = Self(4);
+    pub const NEGATIVE_Y: Self = Self(8);
+}
+
+impl std::ops::BitOr for BarrierDirections {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+/// A horizontal or vertical line the pointer cannot cross.
+#[derive(Debug, Clone, Default)]
+pub struct PointerBarrier {
+    /// A window on the screen of the barrier.
+    pub window: Window,
+    pub x1: u16,
+    pub y1: u16,
+    pub x2: u16,
+    pub y2: u16,
+    /// The directions the pointer may still cross the barrier in.
+    pub directions: BarrierDirections,
+    /// The XInput devices the barrier applies to. Empty for all devices.
+    pub devices: Vec<u16>,
+}
+
+/// The owner of a selection changed. Selected with the masks of
+/// [Connection::xfixes_select_selection_input].
+#[derive(Debug, Clone)]
+pub struct SelectionNotifyEvent {
+    /// What happened, see [selection_subtype].
+    pub subtype: u8,
+    pub window: Window,
+    /// The new owner, 0 if the selection has none anymore.
+    pub owner: Window,
+    pub selection: Atom,
+    pub timestamp: Timestamp,
+    /// When the owner took the selection.
+    pub selection_timestamp: Timestamp,
+}
+
+impl SelectionNotifyEvent {
+    /// Parses the event if `event` is a selection notification. `first_event` is
+    /// the one of the extension, see [Connection::extension].
+    //
+    //   1 byte     Code (first event + 0)
+    //   1 byte     Subtype
+    //   2 bytes    Sequence number
+    //   4 bytes    Window
+    //   4 bytes    Owner
+    //   4 bytes    Selection
+    //   4 bytes    Timestamp
+    //   4 bytes    Selection timestamp
+    //   8 bytes    Unused
+    pub fn from_event(event: &Event, first_event: u8) -> Option<SelectionNotifyEvent> {
+        let Event::Unknown(bytes) = event else {
+            return None;
+        };
+        if bytes.first()? & 0x7f != first_event.wrapping_add(event_code::SELECTION_NOTIFY) {
+            return None;
+        }
+        let rest = skip(bytes, 1).ok()?;
+        let (subtype, rest) = deserialize_into::<u8>(rest).ok()?;
+        let rest = skip(rest, 2).ok()?;
+        let (window, rest) = deserialize_into::<Window>(rest).ok()?;
+        let (owner, rest) = deserialize_into::<Window>(rest).ok()?;
+        let (selection, rest) = deserialize_into::<Atom>(rest).ok()?;
+        let (timestamp, rest) = deserialize_into::<Timestamp>(rest).ok()?;
+        let (selection_timestamp, _) = deserialize_into::<Timestamp>(rest).ok()?;
+        Some(SelectionNotifyEvent {
+            subtype,
+            window,
+            owner,
+            selection,
+            timestamp,
+            selection_timestamp,
+        })
+    }
+}
+
+/// The cursor changed. Selected with [CURSOR_NOTIFY_MASK].
+#[derive(Debug, Clone)]
+pub struct CursorNotifyEvent {
+    /// The subtype, 0 (DisplayCursor).
+    pub subtype: u8,
+    pub window: Window,
+    pub cursor_serial: u32,
+    pub timestamp: Timestamp,
+    /// The name of the cursor, or [crate::protocol::atom::NONE].
+    pub name: Atom,
+}
+
+impl CursorNotifyEvent {
+    /// Parses the event if `event` is a cursor notification. `first_event` is the
+    /// one of the extension, see [Connection::extension].
+    //
+    //   1 byte     Code (first event + 1)
+    //   1 byte     Subtype
+    //   2 bytes    Sequence number
+    //   4 bytes    Window
+    //   4 bytes    Cursor serial
+    //   4 bytes    Timestamp
+    //   4 bytes    Name
+    //   12 bytes   Unused
+    pub fn from_event(event: &Event, first_event: u8) -> Option<CursorNotifyEvent> {
+        let Event::Unknown(bytes) = event else {
+            return None;
+        };
+        if bytes.first()? & 0x7f != first_event.wrapping_add(event_code::CURSOR_NOTIFY) {
+            return None;
+        }
+        let rest = skip(bytes, 1).ok()?;
+        let (subtype, rest) = deserialize_into::<u8>(rest).ok()?;
+        let rest = skip(rest, 2).ok()?;
+        let (window, rest) = deserialize_into::<Window>(rest).ok()?;
+        let (cursor_serial, rest) = deserialize_into::<u32>(rest).ok()?;
+        let (timestamp, rest) = deserialize_into::<u32>(rest).ok()?;
+        let (name, _) = deserialize_into::<u32>(rest).ok()?;
+        Some(CursorNotifyEvent {
+            subtype,
+            window,
+            cursor_serial,
+            timestamp,
+            name,
+        })
+    }
+}
+
+impl Connection {
+    /// Tells the server the version of the extension the client supports and
+    /// returns the version both support. Call it before any other XFIXES request.
+    pub fn xfixes_query_version(
+        &mut self,
+        major_version: u32,
+        minor_version: u32,
+    ) -> Result<Cookie<QueryVersionReply>, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (0)
+        //   2 bytes    Request length (3)
+        //   4 bytes    Client major version
+        //   4 bytes    Client minor version
+        let request = request(self, opcode::QUERY_VERSION)?
+            .put(major_version)
+            .put(minor_version)
+            .finish();
+        self.send_request_with_reply(&request)
+    }
+
+    /// Selects the notifications about `selection` that `window` reports, e.g.
+    /// [SET_SELECTION_OWNER_NOTIFY_MASK]. The events arrive as [Event::Unknown]
+    /// and are parsed with [SelectionNotifyEvent::from_event].
+    pub fn xfixes_select_selection_input(
+        &mut self,
+        window: Window,
+        selection: Atom,
+        event_mask: u32,
+    ) -> Result<u16, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (2)
+        //   2 bytes    Request length (4)
+        //   4 bytes    Window
+        //   4 bytes    Selection
+        //   4 bytes    Event mask
+        let request = request(self, opcode::SELECT_SELECTION_INPUT)?
+            .put(window)
+            .put(selection)
+            .put(event_mask)
+            .finish();
+        self.send_request(&request)
+    }
+
+    /// Selects the cursor notifications `window` reports, e.g. [CURSOR_NOTIFY_MASK].
+    pub fn xfixes_select_cursor_input(
+        &mut self,
+        window: Window,
+        event_mask: u32,
+    ) -> Result<u16, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (3)
+        //   2 bytes    Request length (3)
+        //   4 bytes    Window
+        //   4 bytes    Event mask
+        let request = request(self, opcode::SELECT_CURSOR_INPUT)?
+            .put(window)
+            .put(event_mask)
+            .finish();
+        self.send_request(&request)
+    }
+
+    /// Returns the image of the current cursor.
+    pub fn xfixes_get_cursor_image(&mut self) -> Result<Cookie<CursorImage>, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (4)
+        //   2 bytes    Request length (1)
+        let request = request(self, opcode::GET_CURSOR_IMAGE)?.finish();
+        self.send_request_with_reply(&request)
+    }
+
+    /// Hides the cursor while it is in `window` or its children.
+    pub fn xfixes_hide_cursor(&mut self, window: Window) -> Result<u16, ConnectionError> {
+        let request = request(self, opcode::HIDE_CURSOR)?.put(window).finish();
+        self.send_request(&request)
+    }
+
+    /// Shows the cursor hidden by [Connection::xfixes_hide_cursor] again.
+    pub fn xfixes_show_cursor(&mut self, window: Window) -> Result<u16, ConnectionError> {
+        let request = request(self, opcode::SHOW_CURSOR)?.put(window).finish();
+        self.send_request(&request)
+    }
+
+    /// Creates a region made of the given rectangles and returns its id.
+    pub fn xfixes_create_region(
+        &mut self,
+        rectangles: &[Rectangle],
+    ) -> Result<XId, ConnectionError> {
+        let region = self.generate_id()?;
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (5)
+        //   2 bytes    Request length (2+2n)
+        //   4 bytes    Region
+        //   8n bytes   Rectangles
+        let mut request = request(self, opcode::CREATE_REGION)?;
+        request.put(region);
+        for rectangle in rectangles {
+            request.put(*rectangle);
+        }
+        self.send_request(&request.finish())?;
+        Ok(region)
+    }
+
+    /// Creates a region from the set bits of a 1-bit deep pixmap and returns its id.
+    pub fn xfixes_create_region_from_bitmap(
+        &mut self,
+        bitmap: XId,
+    ) -> Result<XId, ConnectionError> {
+        let region = self.generate_id()?;
+        let request = request(self, opcode::CREATE_REGION_FROM_BITMAP)?
+            .put(region)
+            .put(bitmap)
+            .finish();
+        self.send_request(&request)?;
+        Ok(region)
+    }
+
+    /// Creates a region from the bounding (kind 0) or clip (kind 1) shape of a
+    /// window and returns its id.
+    pub fn xfixes_create_region_from_window(
+        &mut self,
+        window: Window,
+        kind: u8,
+    ) -> Result<XId, ConnectionError> {
+        let region = self.generate_id()?;
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (7)
+        //   2 bytes    Request length (4)
+        //   4 bytes    Region
+        //   4 bytes    Window
+        //   1 byte     Kind
+        //   3 bytes    Unused
+        let request = request(self, opcode::CREATE_REGION_FROM_WINDOW)?
+            .put(region)
+            .put(window)
+            .put(kind)
+            .pad(3)
+            .finish();
+        self.send_request(&request)?;
+        Ok(region)
+    }
+
+    pub fn xfixes_destroy_region(&mut self, region: XId) -> Result<u16, ConnectionError> {
+        let request = request(self, opcode::DESTROY_REGION)?.put(region).finish();
+        self.send_request(&request)
+    }
+
+    /// Replaces the contents of a region with the given rectangles.
+    pub fn xfixes_set_region(
+        &mut self,
+        region: XId,
+        rectangles: &[Rectangle],
+    ) -> Result<u16, ConnectionError> {
+        let mut request = request(self, opcode::SET_REGION)?;
+        request.put(region);
+        for rectangle in rectangles {
+            request.put(*rectangle);
+        }
+        self.send_request(&request.finish())
+    }
+
+    pub fn xfixes_copy_region(
+        &mut self,
+        source: XId,
+        destination: XId,
+    ) -> Result<u16, ConnectionError> {
+        let request = request(self, opcode::COPY_REGION)?
+            .put(source)
+            .put(destination)
+            .finish();
+        self.send_request(&request)
+    }
+
+    /// Stores the union of two regions in `destination`.
+    pub fn xfixes_union_region(
+        &mut self,
+        source1: XId,
+        source2: XId,
+        destination: XId,
+    ) -> Result<u16, ConnectionError> {
+        self.xfixes_combine_region(opcode::UNION_REGION, source1, source2, destination)
+    }
+
+    /// Stores the intersection of two regions in `destination`.
+    pub fn xfixes_intersect_region(
+        &mut self,
+        source1: XId,
+        source2: XId,
+        destination: XId,
+    ) -> Result<u16, ConnectionError> {
+        self.xfixes_combine_region(opcode::INTERSECT_REGION, source1, source2, destination)
+    }
+
+    /// Stores `source1` minus `source2` in `destination`.
+    pub fn xfixes_subtract_region(
+        &mut self,
+        source1: XId,
+        source2: XId,
+        destination: XId,
+    ) -> Result<u16, ConnectionError> {
+        self.xfixes_combine_region(opcode::SUBTRACT_REGION, source1, source2, destination)
+    }
+
+    //   1 byte     Major opcode
+    //   1 byte     Minor opcode
+    //   2 bytes    Request length (4)
+    //   4 bytes    Source 1
+    //   4 bytes    Source 2
+    //   4 bytes    Destination
+    fn xfixes_combine_region(
+        &mut self,
+        minor_opcode: u8,
+        source1: XId,
+        source2: XId,
+        destination: XId,
+    ) -> Result<u16, ConnectionError> {
+        let request = request(self, minor_opcode)?
+            .put(source1)
+            .put(source2)
+            .put(destination)
+            .finish();
+        self.send_request(&request)
+    }
+
+    pub fn xfixes_translate_region(
+        &mut self,
+        region: XId,
+        dx: i16,
+        dy: i16,
+    ) -> Result<u16, ConnectionError> {
+        let request = request(self, opcode::TRANSLATE_REGION)?
+            .put(region)
+            .put(dx)
+            .put(dy)
+            .finish();
+        self.send_request(&request)
+    }
+
+    /// Stores the bounding box of `source` in `destination`.
+    pub fn xfixes_region_extents(
+        &mut self,
+        source: XId,
+        destination: XId,
+    ) -> Result<u16, ConnectionError> {
+        let request = request(self, opcode::REGION_EXTENTS)?
+            .put(source)
+            .put(destination)
+            .finish();
+        self.send_request(&request)
+    }
+
+    /// Returns the rectangles making up a region.
+    pub fn xfixes_fetch_region(
+        &mut self,
+        region: XId,
+    ) -> Result<Cookie<FetchRegionReply>, ConnectionError> {
+        let request = request(self, opcode::FETCH_REGION)?.put(region).finish();
+        self.send_request_with_reply(&request)
+    }
+
+    /// Creates a pointer barrier and returns its id. Requires XFIXES 5.0.
+    pub fn xfixes_create_pointer_barrier(
+        &mut self,
+        barrier: &PointerBarrier,
+    ) -> Result<XId, ConnectionError> {
+        let id = self.generate_id()?;
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (31)
+        //   2 bytes    Request length (7+(2n+p)/4)
+        //   4 bytes    Barrier
+        //   4 bytes    Window
+        //   2 bytes    X1
+        //   2 bytes    Y1
+        //   2 bytes    X2
+        //   2 bytes    Y2
+        //   4 bytes    Directions
+        //   2 bytes    Unused
+        //   2 bytes    Number of devices (n)
+        //   2n bytes   Devices
+        let mut request = request(self, opcode::CREATE_POINTER_BARRIER)?;
+        request
+            .put(id)
+            .put(barrier.window)
+            .put(barrier.x1)
+            .put(barrier.y1)
+            .put(barrier.x2)
+            .put(barrier.y2)
+            .put(barrier.directions.0)
+            .pad(2)
+            .put(barrier.devices.len() as u16);
+        for device in &barrier.devices {
+            request.put(*device);
+        }
+        self.send_request(&request.finish())?;
+        Ok(id)
+    }
+
+    pub fn xfixes_delete_pointer_barrier(&mut self, barrier: XId) -> Result<u16, ConnectionError> {
+        let request = request(self, opcode::DELETE_POINTER_BARRIER)?
+            .put(barrier)
+            .finish();
+        self.send_request(&request)
+    }
+}
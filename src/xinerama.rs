@@ -0,0 +1,207 @@
+//! The Xinerama extension: the monitors of a screen spanning several heads.
+//!
+//! Servers without RandR 1.2, or with a legacy multi-head setup, only describe
+//! their monitors through this extension. Prefer [crate::randr] when it is
+//! available.
+
+use crate::connection::Connection;
+use crate::cookie::{Cookie, Reply};
+use crate::errors::{ConnectionError, ParseError};
+use crate::extension::{Extension, Version};
+use crate::protocol::Rectangle;
+use crate::request::RequestBuilder;
+use crate::utils::{check_trailing_bytes, deserialize_into, deserialize_into_vec, skip};
+
+/// Name of the extension, as given to QueryExtension.
+pub const NAME: &str = "XINERAMA";
+
+/// Version of the extension implemented by this module.
+pub const VERSION: (u8, u8) = (1, 1);
+
+/// Minor opcodes of the Xinerama requests.
+pub mod opcode {
+    pub const QUERY_VERSION: u8 = 0;
+    pub const IS_ACTIVE: u8 = 4;
+    pub const QUERY_SCREENS: u8 = 5;
+}
+
+/// Names of the Xinerama requests, indexed by minor opcode.
+pub const REQUEST_NAMES: &[&str] = &[
+    "QueryVersion",
+    "GetState",
+    "GetScreenCount",
+    "GetScreenSize",
+    "IsActive",
+    "QueryScreens",
+];
+
+/// The extension defines no errors.
+pub const ERROR_NAMES: &[&str] = &[];
+
+/// Starts a request of the extension.
+fn request(conn: &mut Connection, minor_opcode: u8) -> Result<RequestBuilder, ConnectionError> {
+    let info = conn.require_extension(NAME, REQUEST_NAMES, ERROR_NAMES)?;
+    Ok(RequestBuilder::new(info.major_opcode, minor_opcode))
+}
+
+/// The XINERAMA extension, for [Connection::enable].
+pub struct Xinerama;
+
+impl Extension for Xinerama {
+    const NAME: &'static str = NAME;
+    const MIN_VERSION: Version = (1, 1);
+
+    fn negotiate(conn: &mut Connection) -> Result<Option<Version>, ConnectionError> {
+        let reply = conn
+            .xinerama_query_version(VERSION.0, VERSION.1)?
+            .reply(conn)?;
+        Ok(Some((
+            reply.major_version.into(),
+            reply.minor_version.into(),
+        )))
+    }
+}
+
+/// Reply of the XineramaQueryVersion request.
+#[derive(Debug, Clone)]
+pub struct QueryVersionReply {
+    pub major_version: u16,
+    pub minor_version: u16,
+}
+
+impl Reply for QueryVersionReply {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1                        unused
+        // 2  CARD16                sequence number
+        // 4  0                     reply length
+        // 2  CARD16                major-version
+        // 2  CARD16                minor-version
+        // 20                       unused
+        let rest = skip(bytes, 8)?;
+        let (major_version, rest) = deserialize_into::<u16>(rest)?;
+        let (minor_version, _) = deserialize_into::<u16>(rest)?;
+        Ok(QueryVersionReply {
+            major_version,
+            minor_version,
+        })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        check_trailing_bytes("xinerama::QueryVersionReply", bytes, 32)
+    }
+}
+
+/// Reply of the XineramaIsActive request.
+#[derive(Debug, Clone)]
+pub struct IsActiveReply {
+    /// Whether the screen spans several monitors through Xinerama.
+    pub active: bool,
+}
+
+impl Reply for IsActiveReply {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1                        unused
+        // 2  CARD16                sequence number
+        // 4  0                     reply length
+        // 4  CARD32                state
+        // 20                       unused
+        let rest = skip(bytes, 8)?;
+        let (state, _) = deserialize_into::<u32>(rest)?;
+        Ok(IsActiveReply { active: state != 0 })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        check_trailing_bytes("xinerama::IsActiveReply", bytes, 32)
+    }
+}
+
+/// Reply of the XineramaQueryScreens request.
+#[derive(Debug, Clone)]
+pub struct QueryScreensReply {
+    /// The area of the root window each monitor shows.
+    pub screens: Vec<Rectangle>,
+}
+
+impl QueryScreensReply {
+    /// Returns the index of the monitor showing the given point of the root window.
+    pub fn screen_at(&self, x: i16, y: i16) -> Option<usize> {
+        self.screens.iter().position(|screen| {
+            let (x, y) = (i32::from(x), i32::from(y));
+            let (left, top) = (i32::from(screen.x), i32::from(screen.y));
+            x >= left
+                && y >= top
+                && x < left + i32::from(screen.width)
+                && y < top + i32::from(screen.height)
+        })
+    }
+}
+
+impl Reply for QueryScreensReply {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1                        unused
+        // 2  CARD16                sequence number
+        // 4  2n                    reply length
+        // 4  CARD32                number of screens (n)
+        // 20                       unused
+        // 8n LISTofSCREENINFO      screens
+        let rest = skip(bytes, 8)?;
+        let (number_of_screens, rest) = deserialize_into::<u32>(rest)?;
+        let rest = skip(rest, 20)?;
+        let (screens, _) = deserialize_into_vec::<Rectangle>(rest, number_of_screens as usize)?;
+        Ok(QueryScreensReply { screens })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        check_trailing_bytes(
+            "xinerama::QueryScreensReply",
+            bytes,
+            32 + 8 * self.screens.len(),
+        )
+    }
+}
+
+impl Connection {
+    /// Tells the server the version of the extension the client supports and
+    /// returns the version the server supports.
+    pub fn xinerama_query_version(
+        &mut self,
+        major_version: u8,
+        minor_version: u8,
+    ) -> Result<Cookie<QueryVersionReply>, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (0)
+        //   2 bytes    Request length (2)
+        //   1 byte     Client major version
+        //   1 byte     Client minor version
+        //   2 bytes    Unused
+        let request = request(self, opcode::QUERY_VERSION)?
+            .put(major_version)
+            .put(minor_version)
+            .pad(2)
+            .finish();
+        self.send_request_with_reply(&request)
+    }
+
+    /// Returns whether Xinerama is active. The extension may be present while
+    /// the server only drives one monitor.
+    pub fn xinerama_is_active(&mut self) -> Result<Cookie<IsActiveReply>, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (4)
+        //   2 bytes    Request length (1)
+        let request = request(self, opcode::IS_ACTIVE)?.finish();
+        self.send_request_with_reply(&request)
+    }
+
+    /// Returns the area of the root window each monitor shows. The list is empty
+    /// if Xinerama is not active.
+    pub fn xinerama_query_screens(&mut self) -> Result<Cookie<QueryScreensReply>, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (5)
+        //   2 bytes    Request length (1)
+        let request = request(self, opcode::QUERY_SCREENS)?.finish();
+        self.send_request_with_reply(&request)
+    }
+}
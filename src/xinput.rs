@@ -0,0 +1,1085 @@
+//! The X Input extension, version 2 (XI2): input events per device.
+//!
+//! Raw events are what makes system-wide monitoring possible: selected on the
+//! root window, they report every key press, button press and motion of every
+//! device, whichever window has the focus, without grabbing anything.
+//!
+//! XI2 events arrive as [Event::Generic] and are parsed with [XiEvent::from_event].
+//! Call [Connection::xinput_query_version] with 2.0 or newer before any other
+//! XI2 request, the server refuses them otherwise.
+//!
+//! ```no_run
+//! use mousetrap::connection::Connection;
+//! use mousetrap::xinput::{self, EventSelection, XiEvent, XiEventMask};
+//!
+//! let mut conn = Connection::init().unwrap();
+//! conn.xinput_query_version(2, 2).unwrap().reply(&mut conn).unwrap();
+//! let root = conn.default_root();
+//! let mask = XiEventMask::RAW_MOTION | XiEventMask::RAW_BUTTON_PRESS;
+//! conn.xinput_select_events(root, &[EventSelection::new(xinput::ALL_MASTER_DEVICES, mask)])
+//!     .unwrap();
+//! let opcode = conn.extension(xinput::NAME).unwrap().unwrap().major_opcode;
+//! loop {
+//!     let event = conn.wait_for_event().unwrap();
+//!     if let Some(XiEvent::RawMotion(motion)) = XiEvent::from_event(&event, opcode) {
+//!         println!("{:?}", motion.valuators);
+//!     }
+//! }
+//! ```
+
+use crate::connection::Connection;
+use crate::cookie::{Cookie, Reply};
+use crate::errors::{ConnectionError, ParseError};
+use crate::event::Event;
+use crate::extension::{Extension, Version};
+use crate::protocol::{Timestamp, Window, XId};
+use crate::request::RequestBuilder;
+use crate::utils::{check_trailing_bytes, deserialize_into, deserialize_into_vec, skip};
+use std::collections::HashMap;
+
+/// Name of the extension, as given to QueryExtension.
+pub const NAME: &str = "XInputExtension";
+
+/// The newest version of XI2 this module implements.
+pub const VERSION: (u16, u16) = (2, 4);
+
+/// Minor opcodes of the XI2 requests.
+pub mod opcode {
+    pub const XI_WARP_POINTER: u8 = 41;
+    pub const XI_SELECT_EVENTS: u8 = 46;
+    pub const XI_QUERY_VERSION: u8 = 47;
+    pub const XI_ALLOW_EVENTS: u8 = 53;
+    pub const XI_BARRIER_RELEASE_POINTER: u8 = 61;
+}
+
+/// Names of the X Input requests, indexed by minor opcode.
+pub const REQUEST_NAMES: &[&str] = &[
+    "",
+    "GetExtensionVersion",
+    "ListInputDevices",
+    "OpenDevice",
+    "CloseDevice",
+    "SetDeviceMode",
+    "SelectExtensionEvent",
+    "GetSelectedExtensionEvents",
+    "ChangeDeviceDontPropagateList",
+    "GetDeviceDontPropagateList",
+    "GetDeviceMotionEvents",
+    "ChangeKeyboardDevice",
+    "ChangePointerDevice",
+    "GrabDevice",
+    "UngrabDevice",
+    "GrabDeviceKey",
+    "UngrabDeviceKey",
+    "GrabDeviceButton",
+    "UngrabDeviceButton",
+    "AllowDeviceEvents",
+    "GetDeviceFocus",
+    "SetDeviceFocus",
+    "GetFeedbackControl",
+    "ChangeFeedbackControl",
+    "GetDeviceKeyMapping",
+    "ChangeDeviceKeyMapping",
+    "GetDeviceModifierMapping",
+    "SetDeviceModifierMapping",
+    "GetDeviceButtonMapping",
+    "SetDeviceButtonMapping",
+    "QueryDeviceState",
+    "SendExtensionEvent",
+    "DeviceBell",
+    "SetDeviceValuators",
+    "GetDeviceControl",
+    "ChangeDeviceControl",
+    "ListDeviceProperties",
+    "ChangeDeviceProperty",
+    "DeleteDeviceProperty",
+    "GetDeviceProperty",
+    "XIQueryPointer",
+    "XIWarpPointer",
+    "XIChangeCursor",
+    "XIChangeHierarchy",
+    "XISetClientPointer",
+    "XIGetClientPointer",
+    "XISelectEvents",
+    "XIQueryVersion",
+    "XIQueryDevice",
+    "XISetFocus",
+    "XIGetFocus",
+    "XIGrabDevice",
+    "XIUngrabDevice",
+    "XIAllowEvents",
+    "XIPassiveGrabDevice",
+    "XIPassiveUngrabDevice",
+    "XIListProperties",
+    "XIChangeProperty",
+    "XIDeleteProperty",
+    "XIGetProperty",
+    "XIGetSelectedEvents",
+    "XIBarrierReleasePointer",
+];
+
+/// Names of the X Input errors, indexed from the first error.
+pub const ERROR_NAMES: &[&str] = &["BadDevice", "BadEvent", "BadMode", "DeviceBusy", "BadClass"];
+
+/// An input device, as numbered by the server.
+pub type DeviceId = u16;
+
+/// Stands for all devices, master and slave.
+pub const ALL_DEVICES: DeviceId = 0;
+
+/// Stands for the master devices, the virtual pointers and keyboards the
+/// physical (slave) devices are attached to.
+pub const ALL_MASTER_DEVICES: DeviceId = 1;
+
+/// Types of the XI2 events.
+pub mod event_type {
+    pub const DEVICE_CHANGED: u16 = 1;
+    pub const KEY_PRESS: u16 = 2;
+    pub const KEY_RELEASE: u16 = 3;
+    pub const BUTTON_PRESS: u16 = 4;
+    pub const BUTTON_RELEASE: u16 = 5;
+    pub const MOTION: u16 = 6;
+    pub const ENTER: u16 = 7;
+    pub const LEAVE: u16 = 8;
+    pub const FOCUS_IN: u16 = 9;
+    pub const FOCUS_OUT: u16 = 10;
+    pub const HIERARCHY_CHANGED: u16 = 11;
+    pub const PROPERTY: u16 = 12;
+    pub const RAW_KEY_PRESS: u16 = 13;
+    pub const RAW_KEY_RELEASE: u16 = 14;
+    pub const RAW_BUTTON_PRESS: u16 = 15;
+    pub const RAW_BUTTON_RELEASE: u16 = 16;
+    pub const RAW_MOTION: u16 = 17;
+    pub const TOUCH_BEGIN: u16 = 18;
+    pub const TOUCH_UPDATE: u16 = 19;
+    pub const TOUCH_END: u16 = 20;
+    pub const TOUCH_OWNERSHIP: u16 = 21;
+    pub const RAW_TOUCH_BEGIN: u16 = 22;
+    pub const RAW_TOUCH_UPDATE: u16 = 23;
+    pub const RAW_TOUCH_END: u16 = 24;
+    pub const BARRIER_HIT: u16 = 25;
+    pub const BARRIER_LEAVE: u16 = 26;
+    pub const GESTURE_PINCH_BEGIN: u16 = 27;
+    pub const GESTURE_PINCH_UPDATE: u16 = 28;
+    pub const GESTURE_PINCH_END: u16 = 29;
+    pub const GESTURE_SWIPE_BEGIN: u16 = 30;
+    pub const GESTURE_SWIPE_UPDATE: u16 = 31;
+    pub const GESTURE_SWIPE_END: u16 = 32;
+}
+
+/// A set of XI2 event types: bit `n` selects the events of type `n`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct XiEventMask(pub u64);
+
+impl XiEventMask {
+    pub const KEY_PRESS: Self = Self::of(event_type::KEY_PRESS);
+    pub const KEY_RELEASE: Self = Self::of(event_type::KEY_RELEASE);
+    pub const BUTTON_PRESS: Self = Self::of(event_type::BUTTON_PRESS);
+    pub const BUTTON_RELEASE: Self = Self::of(event_type::BUTTON_RELEASE);
+    pub const MOTION: Self = Self::of(event_type::MOTION);
+    pub const RAW_KEY_PRESS: Self = Self::of(event_type::RAW_KEY_PRESS);
+    pub const RAW_KEY_RELEASE: Self = Self::of(event_type::RAW_KEY_RELEASE);
+    pub const RAW_BUTTON_PRESS: Self = Self::of(event_type::RAW_BUTTON_PRESS);
+    pub const RAW_BUTTON_RELEASE: Self = Self::of(event_type::RAW_BUTTON_RELEASE);
+    pub const RAW_MOTION: Self = Self::of(event_type::RAW_MOTION);
+    /// Touch events must be selected all three together. Requires XI 2.2.
+    pub const TOUCH: Self = Self(
+        Self::of(event_type::TOUCH_BEGIN).0
+            | Self::of(event_type::TOUCH_UPDATE).0
+            | Self::of(event_type::TOUCH_END).0,
+    );
+    pub const TOUCH_OWNERSHIP: Self = Self::of(event_type::TOUCH_OWNERSHIP);
+    /// Pointer barrier events, selected on the window of the barrier. Requires XI 2.3.
+    pub const BARRIER_HIT: Self = Self::of(event_type::BARRIER_HIT);
+    pub const BARRIER_LEAVE: Self = Self::of(event_type::BARRIER_LEAVE);
+    /// Pinch gestures, whose three events must be selected together. Requires XI 2.4.
+    pub const GESTURE_PINCH: Self = Self(
+        Self::of(event_type::GESTURE_PINCH_BEGIN).0
+            | Self::of(event_type::GESTURE_PINCH_UPDATE).0
+            | Self::of(event_type::GESTURE_PINCH_END).0,
+    );
+    /// Swipe gestures, whose three events must be selected together. Requires XI 2.4.
+    pub const GESTURE_SWIPE: Self = Self(
+        Self::of(event_type::GESTURE_SWIPE_BEGIN).0
+            | Self::of(event_type::GESTURE_SWIPE_UPDATE).0
+            | Self::of(event_type::GESTURE_SWIPE_END).0,
+    );
+
+    /// Returns the mask selecting the events of the given type.
+    pub const fn of(event_type: u16) -> Self {
+        Self(1 << event_type)
+    }
+
+    /// Returns the mask as sent on the wire: a bit array in 4-byte units, bit
+    /// `n` being bit `n % 8` of byte `n / 8`.
+    fn to_bytes(self) -> Vec<u8> {
+        let units = if self.0 >> 32 == 0 { 1 } else { 2 };
+        self.0.to_le_bytes()[..units * 4].to_vec()
+    }
+}
+
+impl std::ops::BitOr for XiEventMask {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+/// The events to select for a device.
+#[derive(Debug, Clone)]
+pub struct EventSelection {
+    /// A device, or [ALL_DEVICES] or [ALL_MASTER_DEVICES].
+    pub device: DeviceId,
+    pub mask: XiEventMask,
+}
+
+impl EventSelection {
+    pub fn new(device: DeviceId, mask: XiEventMask) -> Self {
+        Self { device, mask }
+    }
+}
+
+/// Flags of device events.
+pub mod flags {
+    /// A key press repeated by the server.
+    pub const KEY_REPEAT: u32 = 1 << 16;
+    /// The touch ended, but the owner has not accepted or rejected it yet.
+    pub const TOUCH_PENDING_END: u32 = 1 << 16;
+    /// The touch also generates pointer events.
+    pub const TOUCH_EMULATING_POINTER: u32 = 1 << 17;
+    /// The gesture ended because it was cancelled, not completed.
+    pub const GESTURE_CANCELLED: u32 = 1;
+    /// The pointer went through the barrier after it was released.
+    pub const BARRIER_POINTER_RELEASED: u32 = 1;
+    /// The device is grabbed by another client.
+    pub const BARRIER_DEVICE_IS_GRABBED: u32 = 2;
+}
+
+/// How [Connection::xinput_allow_events] releases grabbed events or touches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EventMode(pub u8);
+
+impl EventMode {
+    pub const ASYNC_DEVICE: Self = Self(0);
+    pub const SYNC_DEVICE: Self = Self(1);
+    pub const REPLAY_DEVICE: Self = Self(2);
+    pub const ASYNC_PAIRED_DEVICE: Self = Self(3);
+    pub const ASYNC_PAIR: Self = Self(4);
+    pub const SYNC_PAIR: Self = Self(5);
+    /// The grabbing client takes the touch: other clients get a TouchEnd.
+    pub const ACCEPT_TOUCH: Self = Self(6);
+    /// The grabbing client passes the touch on to the next client.
+    pub const REJECT_TOUCH: Self = Self(7);
+}
+
+/// Starts a request of the extension.
+fn request(conn: &mut Connection, minor_opcode: u8) -> Result<RequestBuilder, ConnectionError> {
+    let info = conn.require_extension(NAME, REQUEST_NAMES, ERROR_NAMES)?;
+    Ok(RequestBuilder::new(info.major_opcode, minor_opcode))
+}
+
+/// The XInputExtension extension, for [Connection::enable].
+pub struct XInput;
+
+impl Extension for XInput {
+    const NAME: &'static str = NAME;
+    const MIN_VERSION: Version = (2, 0);
+
+    fn negotiate(conn: &mut Connection) -> Result<Option<Version>, ConnectionError> {
+        let reply = conn
+            .xinput_query_version(VERSION.0, VERSION.1)?
+            .reply(conn)?;
+        Ok(Some((
+            reply.major_version.into(),
+            reply.minor_version.into(),
+        )))
+    }
+}
+
+/// Reply of the XIQueryVersion request.
+#[derive(Debug, Clone)]
+pub struct QueryVersionReply {
+    pub major_version: u16,
+    pub minor_version: u16,
+}
+
+impl Reply for QueryVersionReply {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1                        unused
+        // 2  CARD16                sequence number
+        // 4  0                     reply length
+        // 2  CARD16                major-version
+        // 2  CARD16                minor-version
+        // 20                       unused
+        let rest = skip(bytes, 8)?;
+        let (major_version, rest) = deserialize_into::<u16>(rest)?;
+        let (minor_version, _) = deserialize_into::<u16>(rest)?;
+        Ok(QueryVersionReply {
+            major_version,
+            minor_version,
+        })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        check_trailing_bytes("xinput::QueryVersionReply", bytes, 32)
+    }
+}
+
+/// Converts an FP3232 fixed point number to a float.
+fn fp3232(integral: i32, frac: u32) -> f64 {
+    f64::from(integral) + f64::from(frac) / 4_294_967_296.0
+}
+
+/// Parses a list of `n` FP3232 numbers.
+fn deserialize_fp3232_list(bytes: &[u8], n: usize) -> Result<(Vec<f64>, &[u8]), ParseError> {
+    let (words, rest) = deserialize_into_vec::<u32>(bytes, 2 * n)?;
+    let values = words
+        .chunks_exact(2)
+        .map(|pair| fp3232(pair[0] as i32, pair[1]))
+        .collect();
+    Ok((values, rest))
+}
+
+/// Converts an FP1616 fixed point number to a float.
+fn fp1616(value: u32) -> f64 {
+    f64::from(value as i32) / 65536.0
+}
+
+/// Parses `n` FP1616 numbers.
+fn deserialize_fp1616_list(bytes: &[u8], n: usize) -> Result<(Vec<f64>, &[u8]), ParseError> {
+    let (words, rest) = deserialize_into_vec::<u32>(bytes, n)?;
+    Ok((words.into_iter().map(fp1616).collect(), rest))
+}
+
+/// Returns the numbers of the bits set in a valuator mask.
+fn mask_bits(mask: &[u8]) -> Vec<u16> {
+    (0..mask.len() * 8)
+        .filter(|&bit| mask[bit / 8] & (1 << (bit % 8)) != 0)
+        .map(|bit| bit as u16)
+        .collect()
+}
+
+/// The value of an axis of a device, e.g. the relative motion along x.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Valuator {
+    /// The number of the axis. Axes 0 and 1 are usually x and y.
+    pub number: u16,
+    /// The value after pointer acceleration.
+    pub value: f64,
+    /// The value as the device reported it.
+    pub raw_value: f64,
+}
+
+/// A raw event: the input of a device before the server applied pointer
+/// acceleration or delivered it to a window.
+#[derive(Debug, Clone)]
+pub struct RawEvent {
+    pub device: DeviceId,
+    pub time: Timestamp,
+    /// The keycode or the button, 0 for motion.
+    pub detail: u32,
+    /// The slave device that generated the event.
+    pub source: DeviceId,
+    pub flags: u32,
+    /// The axes that changed.
+    pub valuators: Vec<Valuator>,
+}
+
+impl RawEvent {
+    /// Parses the data of a raw event, following its event type.
+    //
+    //   2 bytes    Device
+    //   4 bytes    Time
+    //   4 bytes    Detail
+    //   2 bytes    Source device
+    //   2 bytes    Length of the valuator mask in 4-byte units (m)
+    //   4 bytes    Flags
+    //   4 bytes    Unused
+    //   4m bytes   Valuator mask
+    //   8n bytes   Values (FP3232, n = number of bits set in the mask)
+    //   8n bytes   Raw values (FP3232)
+    fn parse(data: &[u8]) -> Result<RawEvent, ParseError> {
+        let (device, rest) = deserialize_into::<u16>(data)?;
+        let (time, rest) = deserialize_into::<Timestamp>(rest)?;
+        let (detail, rest) = deserialize_into::<u32>(rest)?;
+        let (source, rest) = deserialize_into::<u16>(rest)?;
+        let (mask_length, rest) = deserialize_into::<u16>(rest)?;
+        let (flags, rest) = deserialize_into::<u32>(rest)?;
+        let rest = skip(rest, 4)?;
+        let (mask, rest) = deserialize_into_vec::<u8>(rest, 4 * usize::from(mask_length))?;
+        let numbers = mask_bits(&mask);
+        let (values, rest) = deserialize_fp3232_list(rest, numbers.len())?;
+        let (raw_values, _) = deserialize_fp3232_list(rest, numbers.len())?;
+        let valuators = numbers
+            .into_iter()
+            .zip(values.into_iter().zip(raw_values))
+            .map(|(number, (value, raw_value))| Valuator {
+                number,
+                value,
+                raw_value,
+            })
+            .collect();
+        Ok(RawEvent {
+            device,
+            time,
+            detail,
+            source,
+            flags,
+            valuators,
+        })
+    }
+
+    /// Returns the value of an axis if it changed.
+    pub fn valuator(&self, number: u16) -> Option<&Valuator> {
+        self.valuators.iter().find(|v| v.number == number)
+    }
+}
+
+/// The state of the modifiers: the bits of the modifier keys (Shift, Lock,
+/// Control, Mod1 to Mod5).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ModifierInfo {
+    pub base: u32,
+    pub latched: u32,
+    pub locked: u32,
+    pub effective: u32,
+}
+
+/// The state of the keyboard group (layout).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct GroupInfo {
+    pub base: u8,
+    pub latched: u8,
+    pub locked: u8,
+    pub effective: u8,
+}
+
+/// Parses the modifiers (16 bytes) and the group (4 bytes) of an event.
+fn deserialize_modifiers(bytes: &[u8]) -> Result<(ModifierInfo, GroupInfo, &[u8]), ParseError> {
+    let (mods, rest) = deserialize_into_vec::<u32>(bytes, 4)?;
+    let (group, rest) = deserialize_into_vec::<u8>(rest, 4)?;
+    let mods = ModifierInfo {
+        base: mods[0],
+        latched: mods[1],
+        locked: mods[2],
+        effective: mods[3],
+    };
+    let group = GroupInfo {
+        base: group[0],
+        latched: group[1],
+        locked: group[2],
+        effective: group[3],
+    };
+    Ok((mods, group, rest))
+}
+
+/// A key, button, motion or touch event delivered to a window.
+#[derive(Debug, Clone)]
+pub struct DeviceEvent {
+    pub device: DeviceId,
+    pub time: Timestamp,
+    /// The keycode, the button, or the touch id of touch events.
+    pub detail: u32,
+    pub root: Window,
+    pub event: Window,
+    pub child: Window,
+    /// The position, with subpixel precision, relative to the root window and
+    /// to the event window.
+    pub root_x: f64,
+    pub root_y: f64,
+    pub event_x: f64,
+    pub event_y: f64,
+    /// The slave device that generated the event.
+    pub source: DeviceId,
+    /// See [flags].
+    pub flags: u32,
+    /// The buttons held down, as their numbers.
+    pub buttons: Vec<u16>,
+    /// The axes of the device; touch events carry the touch position here too.
+    pub valuators: Vec<(u16, f64)>,
+    pub mods: ModifierInfo,
+    pub group: GroupInfo,
+}
+
+impl DeviceEvent {
+    /// Parses the data of a device event, following its event type.
+    //
+    //   2 bytes    Device
+    //   4 bytes    Time
+    //   4 bytes    Detail
+    //   4 bytes    Root
+    //   4 bytes    Event
+    //   4 bytes    Child
+    //   4 bytes    Root x (FP1616)
+    //   4 bytes    Root y (FP1616)
+    //   4 bytes    Event x (FP1616)
+    //   4 bytes    Event y (FP1616)
+    //   2 bytes    Length of the button mask in 4-byte units (b)
+    //   2 bytes    Length of the valuator mask in 4-byte units (m)
+    //   2 bytes    Source device
+    //   2 bytes    Unused
+    //   4 bytes    Flags
+    //   16 bytes   Modifiers
+    //   4 bytes    Group
+    //   4b bytes   Button mask
+    //   4m bytes   Valuator mask
+    //   8n bytes   Values (FP3232, n = number of bits set in the valuator mask)
+    fn parse(data: &[u8]) -> Result<DeviceEvent, ParseError> {
+        let (device, rest) = deserialize_into::<u16>(data)?;
+        let (time, rest) = deserialize_into::<Timestamp>(rest)?;
+        let (detail, rest) = deserialize_into::<u32>(rest)?;
+        let (root, rest) = deserialize_into::<Window>(rest)?;
+        let (event, rest) = deserialize_into::<Window>(rest)?;
+        let (child, rest) = deserialize_into::<Window>(rest)?;
+        let (position, rest) = deserialize_fp1616_list(rest, 4)?;
+        let (buttons_length, rest) = deserialize_into::<u16>(rest)?;
+        let (valuators_length, rest) = deserialize_into::<u16>(rest)?;
+        let (source, rest) = deserialize_into::<u16>(rest)?;
+        let rest = skip(rest, 2)?;
+        let (flags, rest) = deserialize_into::<u32>(rest)?;
+        let (mods, group, rest) = deserialize_modifiers(rest)?;
+        let (buttons, rest) = deserialize_into_vec::<u8>(rest, 4 * usize::from(buttons_length))?;
+        let (mask, rest) = deserialize_into_vec::<u8>(rest, 4 * usize::from(valuators_length))?;
+        let numbers = mask_bits(&mask);
+        let (values, _) = deserialize_fp3232_list(rest, numbers.len())?;
+        Ok(DeviceEvent {
+            device,
+            time,
+            detail,
+            root,
+            event,
+            child,
+            root_x: position[0],
+            root_y: position[1],
+            event_x: position[2],
+            event_y: position[3],
+            source,
+            flags,
+            buttons: mask_bits(&buttons),
+            valuators: numbers.into_iter().zip(values).collect(),
+            mods,
+            group,
+        })
+    }
+}
+
+/// Another client took or gave up a touch: sent to the clients owning it.
+#[derive(Debug, Clone)]
+pub struct TouchOwnershipEvent {
+    pub device: DeviceId,
+    pub time: Timestamp,
+    pub touch_id: u32,
+    pub root: Window,
+    pub event: Window,
+    pub child: Window,
+    pub source: DeviceId,
+    pub flags: u32,
+}
+
+impl TouchOwnershipEvent {
+    //   2 bytes    Device
+    //   4 bytes    Time
+    //   4 bytes    Touch id
+    //   4 bytes    Root
+    //   4 bytes    Event
+    //   4 bytes    Child
+    //   2 bytes    Source device
+    //   2 bytes    Unused
+    //   4 bytes    Flags
+    //   8 bytes    Unused
+    fn parse(data: &[u8]) -> Result<TouchOwnershipEvent, ParseError> {
+        let (device, rest) = deserialize_into::<u16>(data)?;
+        let (time, rest) = deserialize_into::<Timestamp>(rest)?;
+        let (touch_id, rest) = deserialize_into::<u32>(rest)?;
+        let (root, rest) = deserialize_into::<Window>(rest)?;
+        let (event, rest) = deserialize_into::<Window>(rest)?;
+        let (child, rest) = deserialize_into::<Window>(rest)?;
+        let (source, rest) = deserialize_into::<u16>(rest)?;
+        let rest = skip(rest, 2)?;
+        let (flags, _) = deserialize_into::<u32>(rest)?;
+        Ok(TouchOwnershipEvent {
+            device,
+            time,
+            touch_id,
+            root,
+            event,
+            child,
+            source,
+            flags,
+        })
+    }
+}
+
+/// A touchpad pinch or swipe gesture. Requires XI 2.4.
+#[derive(Debug, Clone)]
+pub struct GestureEvent {
+    pub device: DeviceId,
+    pub time: Timestamp,
+    /// The number of fingers.
+    pub fingers: u32,
+    pub root: Window,
+    pub event: Window,
+    pub child: Window,
+    pub root_x: f64,
+    pub root_y: f64,
+    pub event_x: f64,
+    pub event_y: f64,
+    /// The motion of the center of the fingers since the last event, with and
+    /// without pointer acceleration.
+    pub delta_x: f64,
+    pub delta_y: f64,
+    pub delta_unaccel_x: f64,
+    pub delta_unaccel_y: f64,
+    /// For pinches: the scale since the beginning of the gesture and the rotation
+    /// since the last event, in degrees. 1 and 0 for swipes.
+    pub scale: f64,
+    pub delta_angle: f64,
+    pub source: DeviceId,
+    pub mods: ModifierInfo,
+    pub group: GroupInfo,
+    /// See [flags::GESTURE_CANCELLED].
+    pub flags: u32,
+}
+
+impl GestureEvent {
+    //   2 bytes    Device
+    //   4 bytes    Time
+    //   4 bytes    Number of fingers
+    //   4 bytes    Root
+    //   4 bytes    Event
+    //   4 bytes    Child
+    //   32 bytes   Root x, root y, event x, event y, delta x, delta y,
+    //              unaccelerated delta x and y (FP1616)
+    //   8 bytes    Scale and delta angle (FP1616), pinch events only
+    //   2 bytes    Source device
+    //   2 bytes    Unused
+    //   16 bytes   Modifiers
+    //   4 bytes    Group
+    //   4 bytes    Flags
+    fn parse(data: &[u8], pinch: bool) -> Result<GestureEvent, ParseError> {
+        let (device, rest) = deserialize_into::<u16>(data)?;
+        let (time, rest) = deserialize_into::<Timestamp>(rest)?;
+        let (fingers, rest) = deserialize_into::<u32>(rest)?;
+        let (root, rest) = deserialize_into::<Window>(rest)?;
+        let (event, rest) = deserialize_into::<Window>(rest)?;
+        let (child, rest) = deserialize_into::<Window>(rest)?;
+        let (values, rest) = deserialize_fp1616_list(rest, if pinch { 10 } else { 8 })?;
+        let (source, rest) = deserialize_into::<u16>(rest)?;
+        let rest = skip(rest, 2)?;
+        let (mods, group, rest) = deserialize_modifiers(rest)?;
+        let (flags, _) = deserialize_into::<u32>(rest)?;
+        Ok(GestureEvent {
+            device,
+            time,
+            fingers,
+            root,
+            event,
+            child,
+            root_x: values[0],
+            root_y: values[1],
+            event_x: values[2],
+            event_y: values[3],
+            delta_x: values[4],
+            delta_y: values[5],
+            delta_unaccel_x: values[6],
+            delta_unaccel_y: values[7],
+            scale: values.get(8).copied().unwrap_or(1.0),
+            delta_angle: values.get(9).copied().unwrap_or(0.0),
+            source,
+            mods,
+            group,
+            flags,
+        })
+    }
+}
+
+/// The pointer pushed against a pointer barrier, or left it. Requires XI 2.3.
+#[derive(Debug, Clone)]
+pub struct BarrierEvent {
+    pub device: DeviceId,
+    pub time: Timestamp,
+    /// Identifies the series of events from the pointer hitting the barrier until
+    /// it leaves it. Pass it to [Connection::xinput_barrier_release_pointer].
+    pub event_id: u32,
+    pub root: Window,
+    pub event: Window,
+    pub barrier: XId,
+    /// The time since the last event of the series, in milliseconds.
+    pub dtime: u32,
+    /// See [flags::BARRIER_POINTER_RELEASED] and [flags::BARRIER_DEVICE_IS_GRABBED].
+    pub flags: u32,
+    pub source: DeviceId,
+    /// The position of the pointer, held at the barrier.
+    pub root_x: f64,
+    pub root_y: f64,
+    /// The motion the barrier blocked, without pointer acceleration.
+    pub dx: f64,
+    pub dy: f64,
+}
+
+impl BarrierEvent {
+    //   2 bytes    Device
+    //   4 bytes    Time
+    //   4 bytes    Event id
+    //   4 bytes    Root
+    //   4 bytes    Event
+    //   4 bytes    Barrier
+    //   4 bytes    Dtime
+    //   4 bytes    Flags
+    //   2 bytes    Source device
+    //   2 bytes    Unused
+    //   8 bytes    Root x and y (FP1616)
+    //   16 bytes   Dx and dy (FP3232)
+    fn parse(data: &[u8]) -> Result<BarrierEvent, ParseError> {
+        let (device, rest) = deserialize_into::<u16>(data)?;
+        let (time, rest) = deserialize_into::<Timestamp>(rest)?;
+        let (event_id, rest) = deserialize_into::<u32>(rest)?;
+        let (root, rest) = deserialize_into::<Window>(rest)?;
+        let (event, rest) = deserialize_into::<Window>(rest)?;
+        let (barrier, rest) = deserialize_into::<XId>(rest)?;
+        let (dtime, rest) = deserialize_into::<u32>(rest)?;
+        let (flags, rest) = deserialize_into::<u32>(rest)?;
+        let (source, rest) = deserialize_into::<u16>(rest)?;
+        let rest = skip(rest, 2)?;
+        let (position, rest) = deserialize_fp1616_list(rest, 2)?;
+        let (delta, _) = deserialize_fp3232_list(rest, 2)?;
+        Ok(BarrierEvent {
+            device,
+            time,
+            event_id,
+            root,
+            event,
+            barrier,
+            dtime,
+            flags,
+            source,
+            root_x: position[0],
+            root_y: position[1],
+            dx: delta[0],
+            dy: delta[1],
+        })
+    }
+}
+
+/// A barrier to let a pointer through, see [Connection::xinput_barrier_release_pointer].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BarrierRelease {
+    pub device: DeviceId,
+    pub barrier: XId,
+    /// The event id of the [BarrierEvent] of the hit.
+    pub event_id: u32,
+}
+
+/// An XI2 event.
+#[derive(Debug, Clone)]
+pub enum XiEvent {
+    KeyPress(DeviceEvent),
+    KeyRelease(DeviceEvent),
+    ButtonPress(DeviceEvent),
+    ButtonRelease(DeviceEvent),
+    Motion(DeviceEvent),
+    RawKeyPress(RawEvent),
+    RawKeyRelease(RawEvent),
+    RawButtonPress(RawEvent),
+    RawButtonRelease(RawEvent),
+    RawMotion(RawEvent),
+    TouchBegin(DeviceEvent),
+    TouchUpdate(DeviceEvent),
+    TouchEnd(DeviceEvent),
+    TouchOwnership(TouchOwnershipEvent),
+    GesturePinchBegin(GestureEvent),
+    GesturePinchUpdate(GestureEvent),
+    GesturePinchEnd(GestureEvent),
+    GestureSwipeBegin(GestureEvent),
+    GestureSwipeUpdate(GestureEvent),
+    GestureSwipeEnd(GestureEvent),
+    BarrierHit(BarrierEvent),
+    BarrierLeave(BarrierEvent),
+}
+
+impl XiEvent {
+    /// Parses the event if `event` is an XI2 event this module knows about.
+    /// `major_opcode` is the one of the extension, see [Connection::extension].
+    pub fn from_event(event: &Event, major_opcode: u8) -> Option<XiEvent> {
+        let Event::Generic(generic) = event else {
+            return None;
+        };
+        if generic.extension != major_opcode {
+            return None;
+        }
+        let data = &generic.data;
+        let event = match generic.event_type {
+            event_type::KEY_PRESS => XiEvent::KeyPress(DeviceEvent::parse(data).ok()?),
+            event_type::KEY_RELEASE => XiEvent::KeyRelease(DeviceEvent::parse(data).ok()?),
+            event_type::BUTTON_PRESS => XiEvent::ButtonPress(DeviceEvent::parse(data).ok()?),
+            event_type::BUTTON_RELEASE => XiEvent::ButtonRelease(DeviceEvent::parse(data).ok()?),
+            event_type::MOTION => XiEvent::Motion(DeviceEvent::parse(data).ok()?),
+            event_type::RAW_KEY_PRESS => XiEvent::RawKeyPress(RawEvent::parse(data).ok()?),
+            event_type::RAW_KEY_RELEASE => XiEvent::RawKeyRelease(RawEvent::parse(data).ok()?),
+            event_type::RAW_BUTTON_PRESS => XiEvent::RawButtonPress(RawEvent::parse(data).ok()?),
+            event_type::RAW_BUTTON_RELEASE => {
+                XiEvent::RawButtonRelease(RawEvent::parse(data).ok()?)
+            }
+            event_type::RAW_MOTION => XiEvent::RawMotion(RawEvent::parse(data).ok()?),
+            event_type::TOUCH_BEGIN => XiEvent::TouchBegin(DeviceEvent::parse(data).ok()?),
+            event_type::TOUCH_UPDATE => XiEvent::TouchUpdate(DeviceEvent::parse(data).ok()?),
+            event_type::TOUCH_END => XiEvent::TouchEnd(DeviceEvent::parse(data).ok()?),
+            event_type::TOUCH_OWNERSHIP => {
+                XiEvent::TouchOwnership(TouchOwnershipEvent::parse(data).ok()?)
+            }
+            event_type::GESTURE_PINCH_BEGIN => {
+                XiEvent::GesturePinchBegin(GestureEvent::parse(data, true).ok()?)
+            }
+            event_type::GESTURE_PINCH_UPDATE => {
+                XiEvent::GesturePinchUpdate(GestureEvent::parse(data, true).ok()?)
+            }
+            event_type::GESTURE_PINCH_END => {
+                XiEvent::GesturePinchEnd(GestureEvent::parse(data, true).ok()?)
+            }
+            event_type::GESTURE_SWIPE_BEGIN => {
+                XiEvent::GestureSwipeBegin(GestureEvent::parse(data, false).ok()?)
+            }
+            event_type::GESTURE_SWIPE_UPDATE => {
+                XiEvent::GestureSwipeUpdate(GestureEvent::parse(data, false).ok()?)
+            }
+            event_type::BARRIER_HIT => XiEvent::BarrierHit(BarrierEvent::parse(data).ok()?),
+            event_type::BARRIER_LEAVE => XiEvent::BarrierLeave(BarrierEvent::parse(data).ok()?),
+            event_type::GESTURE_SWIPE_END => {
+                XiEvent::GestureSwipeEnd(GestureEvent::parse(data, false).ok()?)
+            }
+            _ => return None,
+        };
+        Some(event)
+    }
+}
+
+/// A touch in progress.
+#[derive(Debug, Clone)]
+pub struct Touch {
+    pub device: DeviceId,
+    /// Where the touch began and where it is now, relative to the root window.
+    pub start: (f64, f64),
+    pub position: (f64, f64),
+    pub started_at: Timestamp,
+    /// Whether the touch also moves the pointer.
+    pub emulating_pointer: bool,
+}
+
+/// Keeps track of the touches in progress, by touch id.
+///
+/// Feed it every [XiEvent]; touch ids are only unique among the touches in
+/// progress of a device, so a touch is forgotten at its TouchEnd.
+#[derive(Debug, Clone, Default)]
+pub struct TouchTracker {
+    touches: HashMap<(DeviceId, u32), Touch>,
+}
+
+impl TouchTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Updates the touches with an event and returns the touch it is about.
+    /// At a TouchEnd, the touch is returned one last time.
+    pub fn update(&mut self, event: &XiEvent) -> Option<Touch> {
+        match event {
+            XiEvent::TouchBegin(e) => {
+                let touch = Touch {
+                    device: e.device,
+                    start: (e.root_x, e.root_y),
+                    position: (e.root_x, e.root_y),
+                    started_at: e.time,
+                    emulating_pointer: e.flags & flags::TOUCH_EMULATING_POINTER != 0,
+                };
+                self.touches.insert((e.device, e.detail), touch.clone());
+                Some(touch)
+            }
+            XiEvent::TouchUpdate(e) => {
+                let touch = self.touches.get_mut(&(e.device, e.detail))?;
+                touch.position = (e.root_x, e.root_y);
+                Some(touch.clone())
+            }
+            XiEvent::TouchEnd(e) => {
+                let mut touch = self.touches.remove(&(e.device, e.detail))?;
+                touch.position = (e.root_x, e.root_y);
+                Some(touch)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the touches in progress, with their ids.
+    pub fn active(&self) -> impl Iterator<Item = (u32, &Touch)> {
+        self.touches.iter().map(|((_, id), touch)| (*id, touch))
+    }
+
+    /// Returns the number of touches in progress.
+    pub fn len(&self) -> usize {
+        self.touches.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.touches.is_empty()
+    }
+}
+
+impl Connection {
+    /// Tells the server the version of XI2 the client supports and returns the
+    /// version both support. Call it before any other XI2 request.
+    pub fn xinput_query_version(
+        &mut self,
+        major_version: u16,
+        minor_version: u16,
+    ) -> Result<Cookie<QueryVersionReply>, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (47)
+        //   2 bytes    Request length (2)
+        //   2 bytes    Client major version
+        //   2 bytes    Client minor version
+        let request = request(self, opcode::XI_QUERY_VERSION)?
+            .put(major_version)
+            .put(minor_version)
+            .finish();
+        self.send_request_with_reply(&request)
+    }
+
+    /// Selects the XI2 events `window` reports, per device. A selection replaces
+    /// the previous one of the same device; an empty mask clears it.
+    ///
+    /// Raw events can only be selected on the root window.
+    pub fn xinput_select_events(
+        &mut self,
+        window: Window,
+        selections: &[EventSelection],
+    ) -> Result<u16, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (46)
+        //   2 bytes    Request length (3+n)
+        //   4 bytes    Window
+        //   2 bytes    Number of masks
+        //   2 bytes    Unused
+        //   n*4 bytes  Masks:
+        //     2 bytes    Device
+        //     2 bytes    Length of the mask in 4-byte units (m)
+        //     4m bytes   Mask
+        let mut request = request(self, opcode::XI_SELECT_EVENTS)?;
+        request.put(window).put(selections.len() as u16).pad(2);
+        for selection in selections {
+            let mask = selection.mask.to_bytes();
+            request
+                .put(selection.device)
+                .put((mask.len() / 4) as u16)
+                .put_bytes(&mask);
+        }
+        self.send_request(&request.finish())
+    }
+
+    /// Releases events frozen by a synchronous grab, or accepts or rejects a
+    /// touch grabbed by this client with [EventMode::ACCEPT_TOUCH] or
+    /// [EventMode::REJECT_TOUCH]. `touch_id` and `grab_window` are only used for
+    /// touches. Requires XI 2.2.
+    pub fn xinput_allow_events(
+        &mut self,
+        device: DeviceId,
+        event_mode: EventMode,
+        time: Timestamp,
+        touch_id: u32,
+        grab_window: Window,
+    ) -> Result<u16, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (53)
+        //   2 bytes    Request length (6)
+        //   4 bytes    Time
+        //   2 bytes    Device
+        //   1 byte     Event mode
+        //   1 byte     Unused
+        //   4 bytes    Touch id
+        //   4 bytes    Grab window
+        let request = request(self, opcode::XI_ALLOW_EVENTS)?
+            .put(time)
+            .put(device)
+            .put(event_mode.0)
+            .pad(1)
+            .put(touch_id)
+            .put(grab_window)
+            .finish();
+        self.send_request(&request)
+    }
+
+    /// Moves the pointer of a device to a position relative to `dst_window`, or
+    /// by an offset if `dst_window` is 0. Positions have subpixel precision.
+    ///
+    /// The move only happens if the pointer is in the given rectangle of
+    /// `src_window`, unless `src_window` is 0. A width or height of 0 extends the
+    /// rectangle to the edge of the window.
+    #[allow(clippy::too_many_arguments)]
+    pub fn xinput_warp_pointer(
+        &mut self,
+        device: DeviceId,
+        src_window: Window,
+        src_x: f64,
+        src_y: f64,
+        src_width: u16,
+        src_height: u16,
+        dst_window: Window,
+        dst_x: f64,
+        dst_y: f64,
+    ) -> Result<u16, ConnectionError> {
+        let fp1616 = |value: f64| (value * 65536.0).round() as i32;
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (41)
+        //   2 bytes    Request length (9)
+        //   4 bytes    Source window
+        //   4 bytes    Destination window
+        //   4 bytes    Source x (FP1616)
+        //   4 bytes    Source y (FP1616)
+        //   2 bytes    Source width
+        //   2 bytes    Source height
+        //   4 bytes    Destination x (FP1616)
+        //   4 bytes    Destination y (FP1616)
+        //   2 bytes    Device
+        //   2 bytes    Unused
+        let request = request(self, opcode::XI_WARP_POINTER)?
+            .put(src_window)
+            .put(dst_window)
+            .put(fp1616(src_x))
+            .put(fp1616(src_y))
+            .put(src_width)
+            .put(src_height)
+            .put(fp1616(dst_x))
+            .put(fp1616(dst_y))
+            .put(device)
+            .pad(2)
+            .finish();
+        self.send_request(&request)
+    }
+
+    /// Lets pointers through the barriers they are held at, until they leave
+    /// them. Requires XI 2.3.
+    pub fn xinput_barrier_release_pointer(
+        &mut self,
+        releases: &[BarrierRelease],
+    ) -> Result<u16, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (61)
+        //   2 bytes    Request length (2+3n)
+        //   4 bytes    Number of barriers (n)
+        //   12n bytes  Barriers:
+        //     2 bytes    Device
+        //     2 bytes    Unused
+        //     4 bytes    Barrier
+        //     4 bytes    Event id
+        let mut request = request(self, opcode::XI_BARRIER_RELEASE_POINTER)?;
+        request.put(releases.len() as u32);
+        for release in releases {
+            request
+                .put(release.device)
+                .pad(2)
+                .put(release.barrier)
+                .put(release.event_id);
+        }
+        self.send_request(&request.finish())
+    }
+}
@@ -0,0 +1,1787 @@
+//! The X Keyboard extension (XKB): keyboard layouts with several groups, key
+//! types and the full keyboard state.
+//!
+//! The core protocol can only describe two groups and guesses the shift level
+//! of a key from its keysyms. With XKB, the level of a key comes from its key
+//! type and the current modifiers, and the group from the keyboard state,
+//! which is how keys must be translated to match what the user sees.
+//!
+//! Each group holds a layout: [Connection::xkb_layouts] lists them and
+//! [Connection::xkb_lock_group] switches between them.
+//!
+//! ```no_run
+//! use mousetrap::connection::Connection;
+//! use mousetrap::xkb::{self, EventType, XkbEvent, USE_CORE_KEYBOARD};
+//!
+//! let mut conn = Connection::init().unwrap();
+//! let reply = conn.xkb_use_extension(1, 0).unwrap().reply(&mut conn).unwrap();
+//! assert!(reply.supported);
+//! let map = conn.xkb_get_map(USE_CORE_KEYBOARD).unwrap().reply(&mut conn).unwrap();
+//! let mut state = conn.xkb_get_state(USE_CORE_KEYBOARD).unwrap().reply(&mut conn).unwrap();
+//! conn.xkb_select_events(USE_CORE_KEYBOARD, EventType::STATE_NOTIFY, EventType::STATE_NOTIFY)
+//!     .unwrap();
+//!
+//! let first_event = conn.extension(xkb::NAME).unwrap().unwrap().first_event;
+//! loop {
+//!     let event = conn.wait_for_event().unwrap();
+//!     if let Some(XkbEvent::StateNotify(notify)) = XkbEvent::from_event(&event, first_event) {
+//!         state.update(&notify);
+//!         println!("group {}: {:?}", state.group, map.keysym(38, state.group, state.lookup_mods));
+//!     }
+//! }
+//! ```
+
+use crate::connection::Connection;
+use crate::cookie::{Cookie, Reply};
+use crate::errors::{ConnectionError, ParseError};
+use crate::event::Event;
+use crate::extension::{Extension, Version};
+use crate::protocol::{Atom, KeyCode, Keysym, Timestamp};
+use crate::request::RequestBuilder;
+use crate::utils::{check_trailing_bytes, deserialize_into, deserialize_into_vec, padding, skip};
+
+/// Name of the extension, as given to QueryExtension.
+pub const NAME: &str = "XKEYBOARD";
+
+/// Version of the extension implemented by this module.
+pub const VERSION: (u16, u16) = (1, 0);
+
+/// Minor opcodes of the XKB requests.
+pub mod opcode {
+    pub const USE_EXTENSION: u8 = 0;
+    pub const SELECT_EVENTS: u8 = 1;
+    pub const GET_STATE: u8 = 4;
+    pub const LATCH_LOCK_STATE: u8 = 5;
+    pub const GET_MAP: u8 = 8;
+    pub const GET_NAMES: u8 = 17;
+}
+
+/// Names of the XKB requests, indexed by minor opcode.
+pub const REQUEST_NAMES: &[&str] = &[
+    "UseExtension",
+    "SelectEvents",
+    "",
+    "Bell",
+    "GetState",
+    "LatchLockState",
+    "GetControls",
+    "SetControls",
+    "GetMap",
+    "SetMap",
+    "GetCompatMap",
+    "SetCompatMap",
+    "GetIndicatorState",
+    "GetIndicatorMap",
+    "SetIndicatorMap",
+    "GetNamedIndicator",
+    "SetNamedIndicator",
+    "GetNames",
+    "SetNames",
+    "GetGeometry",
+    "SetGeometry",
+    "PerClientFlags",
+    "ListComponents",
+    "GetKbdByName",
+    "GetDeviceInfo",
+    "SetDeviceInfo",
+];
+
+/// Names of the XKB errors, indexed from the first error of the extension.
+pub const ERROR_NAMES: &[&str] = &["Keyboard"];
+
+/// Starts a request of the extension.
+fn request(conn: &mut Connection, minor_opcode: u8) -> Result<RequestBuilder, ConnectionError> {
+    let info = conn.require_extension(NAME, REQUEST_NAMES, ERROR_NAMES)?;
+    Ok(RequestBuilder::new(info.major_opcode, minor_opcode))
+}
+
+/// The XKEYBOARD extension, for [Connection::enable].
+pub struct Xkb;
+
+impl Extension for Xkb {
+    const NAME: &'static str = NAME;
+    const MIN_VERSION: Version = (1, 0);
+
+    fn negotiate(conn: &mut Connection) -> Result<Option<Version>, ConnectionError> {
+        let reply = conn.xkb_use_extension(VERSION.0, VERSION.1)?.reply(conn)?;
+        Ok(reply
+            .supported
+            .then_some((reply.major_version.into(), reply.minor_version.into())))
+    }
+}
+
+/// A keyboard: the id of an input device, or [USE_CORE_KEYBOARD].
+pub type DeviceSpec = u16;
+
+/// The core keyboard, whichever device it is.
+pub const USE_CORE_KEYBOARD: DeviceSpec = 0x100;
+
+/// The XKB events, all sent with the first event code of the extension and
+/// told apart by their second byte.
+pub mod event_type {
+    pub const NEW_KEYBOARD_NOTIFY: u8 = 0;
+    pub const MAP_NOTIFY: u8 = 1;
+    pub const STATE_NOTIFY: u8 = 2;
+    pub const CONTROLS_NOTIFY: u8 = 3;
+    pub const INDICATOR_STATE_NOTIFY: u8 = 4;
+    pub const INDICATOR_MAP_NOTIFY: u8 = 5;
+    pub const NAMES_NOTIFY: u8 = 6;
+    pub const COMPAT_MAP_NOTIFY: u8 = 7;
+    pub const BELL_NOTIFY: u8 = 8;
+    pub const ACTION_MESSAGE: u8 = 9;
+    pub const ACCESS_X_NOTIFY: u8 = 10;
+    pub const EXTENSION_DEVICE_NOTIFY: u8 = 11;
+}
+
+/// A set of XKB events, see [Connection::xkb_select_events].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct EventType(pub u16);
+
+impl EventType {
+    pub const NEW_KEYBOARD_NOTIFY: Self = Self(1 << event_type::NEW_KEYBOARD_NOTIFY);
+    pub const MAP_NOTIFY: Self = Self(1 << event_type::MAP_NOTIFY);
+    pub const STATE_NOTIFY: Self = Self(1 << event_type::STATE_NOTIFY);
+    pub const CONTROLS_NOTIFY: Self = Self(1 << event_type::CONTROLS_NOTIFY);
+    pub const INDICATOR_STATE_NOTIFY: Self = Self(1 << event_type::INDICATOR_STATE_NOTIFY);
+    pub const INDICATOR_MAP_NOTIFY: Self = Self(1 << event_type::INDICATOR_MAP_NOTIFY);
+    pub const NAMES_NOTIFY: Self = Self(1 << event_type::NAMES_NOTIFY);
+    pub const COMPAT_MAP_NOTIFY: Self = Self(1 << event_type::COMPAT_MAP_NOTIFY);
+    pub const BELL_NOTIFY: Self = Self(1 << event_type::BELL_NOTIFY);
+    pub const ACTION_MESSAGE: Self = Self(1 << event_type::ACTION_MESSAGE);
+    pub const ACCESS_X_NOTIFY: Self = Self(1 << event_type::ACCESS_X_NOTIFY);
+    pub const EXTENSION_DEVICE_NOTIFY: Self = Self(1 << event_type::EXTENSION_DEVICE_NOTIFY);
+}
+
+impl std::ops::BitOr for EventType {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+/// The parts of a keyboard map, see [Connection::xkb_get_map].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MapPart(pub u16);
+
+impl MapPart {
+    pub const KEY_TYPES: Self = Self(1);
+    pub const KEY_SYMS: Self = Self(2);
+    pub const MODIFIER_MAP: Self = Self(4);
+    pub const EXPLICIT_COMPONENTS: Self = Self(8);
+    pub const KEY_ACTIONS: Self = Self(16);
+    pub const KEY_BEHAVIORS: Self = Self(32);
+    pub const VIRTUAL_MODS: Self = Self(64);
+    pub const VIRTUAL_MOD_MAP: Self = Self(128);
+    pub const ALL: Self = Self(0xff);
+
+    /// Returns whether all parts of `other` are in `self`.
+    pub fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl std::ops::BitOr for MapPart {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+/// Reply of the XkbUseExtension request.
+#[derive(Debug, Clone)]
+pub struct UseExtensionReply {
+    /// Whether the server supports the requested version. XKB requests fail
+    /// until a supported version was negotiated.
+    pub supported: bool,
+    pub major_version: u16,
+    pub minor_version: u16,
+}
+
+impl Reply for UseExtensionReply {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1  BOOL                  supported
+        // 2  CARD16                sequence number
+        // 4  0                     reply length
+        // 2  CARD16                serverMajor
+        // 2  CARD16                serverMinor
+        // 20                       unused
+        let rest = skip(bytes, 1)?;
+        let (supported, rest) = deserialize_into::<u8>(rest)?;
+        let rest = skip(rest, 6)?;
+        let (major_version, rest) = deserialize_into::<u16>(rest)?;
+        let (minor_version, _) = deserialize_into::<u16>(rest)?;
+        Ok(UseExtensionReply {
+            supported: supported != 0,
+            major_version,
+            minor_version,
+        })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        check_trailing_bytes("xkb::UseExtensionReply", bytes, 32)
+    }
+}
+
+/// The state of a keyboard: its modifiers and group. Returned by
+/// [Connection::xkb_get_state] and kept current with [State::update].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct State {
+    /// The effective modifiers: the base, latched and locked ones combined.
+    pub mods: u8,
+    /// The modifiers of the keys held down.
+    pub base_mods: u8,
+    /// The modifiers that apply to the next key press only.
+    pub latched_mods: u8,
+    /// The modifiers that stay until they are unlocked, e.g. Lock.
+    pub locked_mods: u8,
+    /// The effective group, i.e. the index of the active layout.
+    pub group: u8,
+    pub base_group: i16,
+    pub latched_group: i16,
+    pub locked_group: u8,
+    /// The modifiers and group as seen by clients not using XKB.
+    pub compat_state: u8,
+    /// The modifiers used to activate passive grabs.
+    pub grab_mods: u8,
+    pub compat_grab_mods: u8,
+    /// The modifiers used to translate keys.
+    pub lookup_mods: u8,
+    pub compat_lookup_mods: u8,
+    /// The pointer buttons held down, as in the state of core events.
+    pub pointer_buttons: u16,
+}
+
+impl State {
+    /// Applies a StateNotify event, so that the state stays current.
+    pub fn update(&mut self, event: &StateNotifyEvent) {
+        *self = event.state.clone();
+    }
+}
+
+impl Reply for State {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1  CARD8                 deviceID
+        // 2  CARD16                sequence number
+        // 4  0                     reply length
+        // 1  CARD8                 mods
+        // 1  CARD8                 baseMods
+        // 1  CARD8                 latchedMods
+        // 1  CARD8                 lockedMods
+        // 1  CARD8                 group
+        // 1  CARD8                 lockedGroup
+        // 2  INT16                 baseGroup
+        // 2  INT16                 latchedGroup
+        // 1  CARD8                 compatState
+        // 1  CARD8                 grabMods
+        // 1  CARD8                 compatGrabMods
+        // 1  CARD8                 lookupMods
+        // 1  CARD8                 compatLookupMods
+        // 1                        unused
+        // 2  CARD16                ptrBtnState
+        // 6                        unused
+        let rest = skip(bytes, 8)?;
+        let (mods, rest) = deserialize_into::<u8>(rest)?;
+        let (base_mods, rest) = deserialize_into::<u8>(rest)?;
+        let (latched_mods, rest) = deserialize_into::<u8>(rest)?;
+        let (locked_mods, rest) = deserialize_into::<u8>(rest)?;
+        let (group, rest) = deserialize_into::<u8>(rest)?;
+        let (locked_group, rest) = deserialize_into::<u8>(rest)?;
+        let (base_group, rest) = deserialize_into::<i16>(rest)?;
+        let (latched_group, rest) = deserialize_into::<i16>(rest)?;
+        let (compat_state, rest) = deserialize_into::<u8>(rest)?;
+        let (grab_mods, rest) = deserialize_into::<u8>(rest)?;
+        let (compat_grab_mods, rest) = deserialize_into::<u8>(rest)?;
+        let (lookup_mods, rest) = deserialize_into::<u8>(rest)?;
+        let (compat_lookup_mods, rest) = deserialize_into::<u8>(rest)?;
+        let rest = skip(rest, 1)?;
+        let (pointer_buttons, _) = deserialize_into::<u16>(rest)?;
+        Ok(State {
+            mods,
+            base_mods,
+            latched_mods,
+            locked_mods,
+            group,
+            base_group,
+            latched_group,
+            locked_group,
+            compat_state,
+            grab_mods,
+            compat_grab_mods,
+            lookup_mods,
+            compat_lookup_mods,
+            pointer_buttons,
+        })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        check_trailing_bytes("xkb::State", bytes, 32)
+    }
+}
+
+/// An entry of a key type: the level a combination of modifiers selects.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyTypeEntry {
+    /// Whether the entry is used. Entries with unbound virtual modifiers are not.
+    pub active: bool,
+    /// The modifiers, among those of the key type, that select the level.
+    pub mods: u8,
+    pub virtual_mods: u16,
+    pub level: u8,
+    /// The modifiers that are not consumed by the translation, if the key type
+    /// preserves modifiers.
+    pub preserve: Option<u8>,
+}
+
+/// How the modifiers select the level of the keys of a type, e.g. TWO_LEVEL
+/// where Shift selects the second level.
+#[derive(Debug, Clone)]
+pub struct KeyType {
+    /// The modifiers the key type looks at, virtual modifiers resolved.
+    pub mods: u8,
+    pub real_mods: u8,
+    pub virtual_mods: u16,
+    pub num_levels: u8,
+    pub entries: Vec<KeyTypeEntry>,
+}
+
+impl KeyType {
+    /// Returns the level selected by the given modifiers.
+    pub fn level(&self, mods: u8) -> u8 {
+        let mods = mods & self.mods;
+        self.entries
+            .iter()
+            .find(|entry| entry.active && entry.mods == mods)
+            .map_or(0, |entry| entry.level)
+    }
+
+    /// The size of the key type on the wire.
+    fn length(&self) -> usize {
+        let preserve = self.entries.iter().any(|entry| entry.preserve.is_some());
+        8 + self.entries.len() * if preserve { 12 } else { 8 }
+    }
+
+    //   1 byte     Modifiers mask
+    //   1 byte     Real modifiers
+    //   2 bytes    Virtual modifiers
+    //   1 byte     Number of levels
+    //   1 byte     Number of entries (n)
+    //   1 byte     Has preserve
+    //   1 byte     Unused
+    //   8n bytes   Entries:
+    //     1 byte     Active
+    //     1 byte     Modifiers mask
+    //     1 byte     Level
+    //     1 byte     Real modifiers
+    //     2 bytes    Virtual modifiers
+    //     2 bytes    Unused
+    //   4n bytes   Preserved modifiers, if any:
+    //     1 byte     Modifiers mask
+    //     1 byte     Real modifiers
+    //     2 bytes    Virtual modifiers
+    fn parse(bytes: &[u8]) -> Result<(KeyType, &[u8]), ParseError> {
+        let (mods, rest) = deserialize_into::<u8>(bytes)?;
+        let (real_mods, rest) = deserialize_into::<u8>(rest)?;
+        let (virtual_mods, rest) = deserialize_into::<u16>(rest)?;
+        let (num_levels, rest) = deserialize_into::<u8>(rest)?;
+        let (number_of_entries, rest) = deserialize_into::<u8>(rest)?;
+        let (has_preserve, rest) = deserialize_into::<u8>(rest)?;
+        let mut rest = skip(rest, 1)?;
+        let mut entries = Vec::with_capacity(number_of_entries.into());
+        for _ in 0..number_of_entries {
+            let (active, r) = deserialize_into::<u8>(rest)?;
+            let (entry_mods, r) = deserialize_into::<u8>(r)?;
+            let (level, r) = deserialize_into::<u8>(r)?;
+            let r = skip(r, 1)?;
+            let (entry_virtual_mods, r) = deserialize_into::<u16>(r)?;
+            rest = skip(r, 2)?;
+            entries.push(KeyTypeEntry {
+                active: active != 0,
+                mods: entry_mods,
+                virtual_mods: entry_virtual_mods,
+                level,
+                preserve: None,
+            });
+        }
+        if has_preserve != 0 {
+            for entry in &mut entries {
+                let (preserve, r) = deserialize_into::<u8>(rest)?;
+                rest = skip(r, 3)?;
+                entry.preserve = Some(preserve);
+            }
+        }
+        let key_type = KeyType {
+            mods,
+            real_mods,
+            virtual_mods,
+            num_levels,
+            entries,
+        };
+        Ok((key_type, rest))
+    }
+}
+
+/// The keysyms of a key, for each of its groups and levels.
+#[derive(Debug, Clone)]
+pub struct KeySymMap {
+    /// The index of the key type of each group.
+    pub key_types: [u8; 4],
+    /// The number of groups in the low 4 bits, and what happens to groups
+    /// beyond them in the high bits.
+    pub group_info: u8,
+    /// The number of keysyms of each group.
+    pub width: u8,
+    /// `width` keysyms for every group.
+    pub keysyms: Vec<Keysym>,
+}
+
+impl KeySymMap {
+    /// Returns the number of groups of the key.
+    pub fn groups(&self) -> u8 {
+        self.group_info & 0x0f
+    }
+
+    /// Returns the group of the key used for the given group of the keyboard,
+    /// which may be beyond the groups of the key.
+    pub fn effective_group(&self, group: u8) -> Option<u8> {
+        let groups = self.groups();
+        if groups == 0 {
+            return None;
+        }
+        if group < groups {
+            return Some(group);
+        }
+        Some(match self.group_info & 0xc0 {
+            // Clamp into range.
+            0x40 => groups - 1,
+            // Redirect into range.
+            0x80 => Some((self.group_info >> 4) & 0x03)
+                .filter(|&group| group < groups)
+                .unwrap_or(0),
+            // Wrap into range.
+            _ => group % groups,
+        })
+    }
+
+    //   4 bytes    Key type of each group
+    //   1 byte     Group info
+    //   1 byte     Width
+    //   2 bytes    Number of keysyms (n)
+    //   4n bytes   Keysyms
+    fn parse(bytes: &[u8]) -> Result<(KeySymMap, &[u8]), ParseError> {
+        let (key_types, rest) = deserialize_into_vec::<u8>(bytes, 4)?;
+        let (group_info, rest) = deserialize_into::<u8>(rest)?;
+        let (width, rest) = deserialize_into::<u8>(rest)?;
+        let (number_of_keysyms, rest) = deserialize_into::<u16>(rest)?;
+        let (keysyms, rest) = deserialize_into_vec::<Keysym>(rest, number_of_keysyms.into())?;
+        let map = KeySymMap {
+            key_types: [key_types[0], key_types[1], key_types[2], key_types[3]],
+            group_info,
+            width,
+            keysyms,
+        };
+        Ok((map, rest))
+    }
+}
+
+/// Reply of the XkbGetMap request: the key types, keysyms and modifier map of
+/// a keyboard.
+#[derive(Debug, Clone)]
+pub struct KeyMap {
+    pub device_id: u8,
+    pub min_keycode: KeyCode,
+    pub max_keycode: KeyCode,
+    /// The parts of the map in the reply.
+    pub present: MapPart,
+    pub types: Vec<KeyType>,
+    /// The keycode of the first entry in `keysyms`.
+    pub first_keysym: KeyCode,
+    pub keysyms: Vec<KeySymMap>,
+    /// The keys bound to real modifiers, with the modifiers.
+    pub modifier_map: Vec<(KeyCode, u8)>,
+}
+
+impl KeyMap {
+    /// Returns the keysyms of a key, or `None` if the key is not mapped.
+    pub fn key(&self, keycode: KeyCode) -> Option<&KeySymMap> {
+        let index = keycode.checked_sub(self.first_keysym)?;
+        self.keysyms.get(usize::from(index))
+    }
+
+    /// Translates a key in the given group and with the given modifiers, e.g.
+    /// the group and lookup modifiers of the [State].
+    pub fn keysym(&self, keycode: KeyCode, group: u8, mods: u8) -> Option<Keysym> {
+        let key = self.key(keycode)?;
+        let group = key.effective_group(group)?;
+        let key_type = self
+            .types
+            .get(usize::from(key.key_types[usize::from(group)]))?;
+        let level = key_type.level(mods);
+        if level >= key.width {
+            return None;
+        }
+        let index = usize::from(group) * usize::from(key.width) + usize::from(level);
+        key.keysyms
+            .get(index)
+            .copied()
+            .filter(|&keysym| keysym != 0)
+    }
+}
+
+impl Reply for KeyMap {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1  CARD8                 deviceID
+        // 2  CARD16                sequence number
+        // 4  CARD32                reply length
+        // 2                        unused
+        // 1  KEYCODE               minKeyCode
+        // 1  KEYCODE               maxKeyCode
+        // 2  CARD16                present
+        // 1  CARD8                 firstType
+        // 1  CARD8                 nTypes
+        // 1  CARD8                 totalTypes
+        // 1  KEYCODE               firstKeySym
+        // 2  CARD16                totalSyms
+        // 1  CARD8                 nKeySyms
+        // 1  KEYCODE               firstKeyAction
+        // 2  CARD16                totalActions
+        // 1  CARD8                 nKeyActions
+        // 1  KEYCODE               firstKeyBehavior
+        // 1  CARD8                 nKeyBehaviors
+        // 1  CARD8                 totalKeyBehaviors
+        // 1  KEYCODE               firstKeyExplicit
+        // 1  CARD8                 nKeyExplicit
+        // 1  CARD8                 totalKeyExplicit
+        // 1  KEYCODE               firstModMapKey
+        // 1  CARD8                 nModMapKeys
+        // 1  CARD8                 totalModMapKeys
+        // 1  KEYCODE               firstVModMapKey
+        // 1  CARD8                 nVModMapKeys
+        // 1  CARD8                 totalVModMapKeys
+        // 1                        unused
+        // 2  CARD16                virtualMods
+        //    LISTofKEYTYPE         types, if present
+        //    LISTofKEYSYMMAP       keysyms, if present
+        //    LISTofCARD8           actions count and list, if present, skipped
+        //    LISTofSETBEHAVIOR     behaviors, if present, skipped
+        //    LISTofCARD8           virtual modifiers, if present, skipped
+        //    LISTofSETEXPLICIT     explicit components, if present, skipped
+        //    LISTofKEYMODMAP       modifier map, if present
+        //    LISTofKEYVMODMAP      virtual modifier map, if present, skipped
+        let rest = skip(bytes, 1)?;
+        let (device_id, rest) = deserialize_into::<u8>(rest)?;
+        let rest = skip(rest, 8)?;
+        let (min_keycode, rest) = deserialize_into::<u8>(rest)?;
+        let (max_keycode, rest) = deserialize_into::<u8>(rest)?;
+        let (present, rest) = deserialize_into::<u16>(rest)?;
+        let present = MapPart(present);
+        let rest = skip(rest, 1)?;
+        let (number_of_types, rest) = deserialize_into::<u8>(rest)?;
+        let rest = skip(rest, 1)?;
+        let (first_keysym, rest) = deserialize_into::<u8>(rest)?;
+        let rest = skip(rest, 2)?;
+        let (number_of_keysyms, rest) = deserialize_into::<u8>(rest)?;
+        let rest = skip(rest, 1)?;
+        let (total_actions, rest) = deserialize_into::<u16>(rest)?;
+        let (number_of_actions, rest) = deserialize_into::<u8>(rest)?;
+        let rest = skip(rest, 2)?;
+        let (total_behaviors, rest) = deserialize_into::<u8>(rest)?;
+        let rest = skip(rest, 2)?;
+        let (total_explicit, rest) = deserialize_into::<u8>(rest)?;
+        let rest = skip(rest, 2)?;
+        let (total_modifier_map, rest) = deserialize_into::<u8>(rest)?;
+        let rest = skip(rest, 4)?;
+        let (virtual_mods, mut rest) = deserialize_into::<u16>(rest)?;
+
+        let mut types = Vec::new();
+        if present.contains(MapPart::KEY_TYPES) {
+            for _ in 0..number_of_types {
+                let (key_type, r) = KeyType::parse(rest)?;
+                types.push(key_type);
+                rest = r;
+            }
+        }
+        let mut keysyms = Vec::new();
+        if present.contains(MapPart::KEY_SYMS) {
+            for _ in 0..number_of_keysyms {
+                let (map, r) = KeySymMap::parse(rest)?;
+                keysyms.push(map);
+                rest = r;
+            }
+        }
+        if present.contains(MapPart::KEY_ACTIONS) {
+            let count = usize::from(number_of_actions);
+            rest = skip(
+                rest,
+                count + padding(count, 4) + 8 * usize::from(total_actions),
+            )?;
+        }
+        if present.contains(MapPart::KEY_BEHAVIORS) {
+            rest = skip(rest, 4 * usize::from(total_behaviors))?;
+        }
+        if present.contains(MapPart::VIRTUAL_MODS) {
+            let count = virtual_mods.count_ones() as usize;
+            rest = skip(rest, count + padding(count, 4))?;
+        }
+        if present.contains(MapPart::EXPLICIT_COMPONENTS) {
+            let length = 2 * usize::from(total_explicit);
+            rest = skip(rest, length + padding(length, 4))?;
+        }
+        let mut modifier_map = Vec::new();
+        if present.contains(MapPart::MODIFIER_MAP) {
+            for _ in 0..total_modifier_map {
+                let (keycode, r) = deserialize_into::<u8>(rest)?;
+                let (mods, r) = deserialize_into::<u8>(r)?;
+                modifier_map.push((keycode, mods));
+                rest = r;
+            }
+        }
+        Ok(KeyMap {
+            device_id,
+            min_keycode,
+            max_keycode,
+            present,
+            types,
+            first_keysym,
+            keysyms,
+            modifier_map,
+        })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        // Only the parts requested by Connection::xkb_get_map are accounted for.
+        let types: usize = self.types.iter().map(KeyType::length).sum();
+        let keysyms: usize = self
+            .keysyms
+            .iter()
+            .map(|map| 8 + 4 * map.keysyms.len())
+            .sum();
+        let modifier_map = 2 * self.modifier_map.len();
+        let length = 40 + types + keysyms + modifier_map + padding(modifier_map, 4);
+        check_trailing_bytes("xkb::KeyMap", bytes, length)
+    }
+}
+
+/// The names of a keyboard, see [Connection::xkb_get_names].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct NameDetail(pub u32);
+
+impl NameDetail {
+    pub const KEYCODES: Self = Self(1);
+    pub const GEOMETRY: Self = Self(2);
+    /// The symbols of the keymap, e.g. `pc+us+de:2+inet(evdev)`.
+    pub const SYMBOLS: Self = Self(4);
+    pub const PHYS_SYMBOLS: Self = Self(8);
+    pub const TYPES: Self = Self(16);
+    pub const COMPAT: Self = Self(32);
+    pub const KEY_TYPE_NAMES: Self = Self(64);
+    pub const KT_LEVEL_NAMES: Self = Self(128);
+    pub const INDICATOR_NAMES: Self = Self(256);
+    pub const KEY_NAMES: Self = Self(512);
+    pub const KEY_ALIASES: Self = Self(1024);
+    pub const VIRTUAL_MOD_NAMES: Self = Self(2048);
+    /// The names of the groups, e.g. `English (US)`.
+    pub const GROUP_NAMES: Self = Self(4096);
+    pub const RG_NAMES: Self = Self(8192);
+
+    /// Returns whether all names of `other` are in `self`.
+    pub fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl std::ops::BitOr for NameDetail {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+/// Reply of the XkbGetNames request. Only the names asked for are filled in.
+#[derive(Debug, Clone, Default)]
+pub struct Names {
+    pub device_id: u8,
+    /// The names in the reply.
+    pub which: NameDetail,
+    pub min_keycode: KeyCode,
+    pub max_keycode: KeyCode,
+    pub keycodes: Option<Atom>,
+    pub geometry: Option<Atom>,
+    pub symbols: Option<Atom>,
+    pub phys_symbols: Option<Atom>,
+    pub types: Option<Atom>,
+    pub compat: Option<Atom>,
+    /// The name of each key type.
+    pub type_names: Vec<Atom>,
+    /// The names of the levels of each key type.
+    pub level_names: Vec<Vec<Atom>>,
+    pub indicator_names: Vec<Atom>,
+    pub virtual_mod_names: Vec<Atom>,
+    /// The groups that have a name, with the name.
+    pub group_names: Vec<(u8, Atom)>,
+    /// The name of each key from the first keycode, e.g. `AE01`.
+    pub key_names: Vec<[u8; 4]>,
+    /// Alternative names of keys, as (name, alias).
+    pub key_aliases: Vec<([u8; 4], [u8; 4])>,
+    pub radio_group_names: Vec<Atom>,
+}
+
+impl Names {
+    /// Reads a list of atoms if the reply has the given names.
+    fn parse_atoms(
+        which: NameDetail,
+        detail: NameDetail,
+        bytes: &[u8],
+        count: usize,
+    ) -> Result<(Vec<Atom>, &[u8]), ParseError> {
+        if which.contains(detail) {
+            deserialize_into_vec::<Atom>(bytes, count)
+        } else {
+            Ok((Vec::new(), bytes))
+        }
+    }
+
+    /// Reads a 4-byte key name.
+    fn parse_key_name(bytes: &[u8]) -> Result<([u8; 4], &[u8]), ParseError> {
+        let (name, rest) = deserialize_into_vec::<u8>(bytes, 4)?;
+        Ok(([name[0], name[1], name[2], name[3]], rest))
+    }
+}
+
+impl Reply for Names {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1  CARD8                 deviceID
+        // 2  CARD16                sequence number
+        // 4  CARD32                reply length
+        // 4  CARD32                which
+        // 1  KEYCODE               minKeyCode
+        // 1  KEYCODE               maxKeyCode
+        // 1  CARD8                 nTypes
+        // 1  CARD8                 groupNames
+        // 2  CARD16                virtualMods
+        // 1  KEYCODE               firstKey
+        // 1  CARD8                 nKeys
+        // 4  CARD32                indicators
+        // 1  CARD8                 nRadioGroups
+        // 1  CARD8                 nKeyAliases
+        // 2  CARD16                nKTLevels
+        // 4                        unused
+        //    ATOM                  keycodes, geometry, symbols, physSymbols,
+        //                          types and compat names, each if present
+        //    LISTofATOM            key type names, if present
+        //    LISTofCARD8           levels of each key type, padded, if present
+        //    LISTofATOM            level names, if present
+        //    LISTofATOM            indicator names, if present
+        //    LISTofATOM            virtual modifier names, if present
+        //    LISTofATOM            group names, if present
+        //    LISTofKEYNAME         key names, if present
+        //    LISTofKEYALIAS        key aliases, if present
+        //    LISTofATOM            radio group names, if present
+        let rest = skip(bytes, 1)?;
+        let (device_id, rest) = deserialize_into::<u8>(rest)?;
+        let rest = skip(rest, 6)?;
+        let (which, rest) = deserialize_into::<u32>(rest)?;
+        let which = NameDetail(which);
+        let (min_keycode, rest) = deserialize_into::<u8>(rest)?;
+        let (max_keycode, rest) = deserialize_into::<u8>(rest)?;
+        let (number_of_types, rest) = deserialize_into::<u8>(rest)?;
+        let (group_mask, rest) = deserialize_into::<u8>(rest)?;
+        let (virtual_mods, rest) = deserialize_into::<u16>(rest)?;
+        let rest = skip(rest, 1)?;
+        let (number_of_keys, rest) = deserialize_into::<u8>(rest)?;
+        let (indicators, rest) = deserialize_into::<u32>(rest)?;
+        let (number_of_radio_groups, rest) = deserialize_into::<u8>(rest)?;
+        let (number_of_key_aliases, rest) = deserialize_into::<u8>(rest)?;
+        let mut rest = skip(rest, 6)?;
+
+        let single = |detail: NameDetail, rest: &mut &[u8]| -> Result<Option<Atom>, ParseError> {
+            if !which.contains(detail) {
+                return Ok(None);
+            }
+            let (atom, r) = deserialize_into::<Atom>(rest)?;
+            *rest = r;
+            Ok(Some(atom))
+        };
+        let keycodes = single(NameDetail::KEYCODES, &mut rest)?;
+        let geometry = single(NameDetail::GEOMETRY, &mut rest)?;
+        let symbols = single(NameDetail::SYMBOLS, &mut rest)?;
+        let phys_symbols = single(NameDetail::PHYS_SYMBOLS, &mut rest)?;
+        let types = single(NameDetail::TYPES, &mut rest)?;
+        let compat = single(NameDetail::COMPAT, &mut rest)?;
+
+        let (type_names, mut rest) = Names::parse_atoms(
+            which,
+            NameDetail::KEY_TYPE_NAMES,
+            rest,
+            number_of_types.into(),
+        )?;
+        let mut level_names = Vec::new();
+        if which.contains(NameDetail::KT_LEVEL_NAMES) {
+            let count = usize::from(number_of_types);
+            let (levels, r) = deserialize_into_vec::<u8>(rest, count)?;
+            rest = skip(r, padding(count, 4))?;
+            for levels in levels {
+                let (names, r) = deserialize_into_vec::<Atom>(rest, levels.into())?;
+                level_names.push(names);
+                rest = r;
+            }
+        }
+        let (indicator_names, rest) = Names::parse_atoms(
+            which,
+            NameDetail::INDICATOR_NAMES,
+            rest,
+            indicators.count_ones() as usize,
+        )?;
+        let (virtual_mod_names, rest) = Names::parse_atoms(
+            which,
+            NameDetail::VIRTUAL_MOD_NAMES,
+            rest,
+            virtual_mods.count_ones() as usize,
+        )?;
+        let (names, mut rest) = Names::parse_atoms(
+            which,
+            NameDetail::GROUP_NAMES,
+            rest,
+            group_mask.count_ones() as usize,
+        )?;
+        let groups = (0..4).filter(|group| group_mask & (1 << group) != 0);
+        let group_names = groups.zip(names).collect();
+        let mut key_names = Vec::new();
+        if which.contains(NameDetail::KEY_NAMES) {
+            for _ in 0..number_of_keys {
+                let (name, r) = Names::parse_key_name(rest)?;
+                key_names.push(name);
+                rest = r;
+            }
+        }
+        let mut key_aliases = Vec::new();
+        if which.contains(NameDetail::KEY_ALIASES) {
+            for _ in 0..number_of_key_aliases {
+                let (name, r) = Names::parse_key_name(rest)?;
+                let (alias, r) = Names::parse_key_name(r)?;
+                key_aliases.push((name, alias));
+                rest = r;
+            }
+        }
+        let (radio_group_names, _) = Names::parse_atoms(
+            which,
+            NameDetail::RG_NAMES,
+            rest,
+            number_of_radio_groups.into(),
+        )?;
+        Ok(Names {
+            device_id,
+            which,
+            min_keycode,
+            max_keycode,
+            keycodes,
+            geometry,
+            symbols,
+            phys_symbols,
+            types,
+            compat,
+            type_names,
+            level_names,
+            indicator_names,
+            virtual_mod_names,
+            group_names,
+            key_names,
+            key_aliases,
+            radio_group_names,
+        })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        let singles = [
+            self.keycodes,
+            self.geometry,
+            self.symbols,
+            self.phys_symbols,
+            self.types,
+            self.compat,
+        ];
+        let atoms = singles.iter().flatten().count()
+            + self.type_names.len()
+            + self.level_names.iter().map(Vec::len).sum::<usize>()
+            + self.indicator_names.len()
+            + self.virtual_mod_names.len()
+            + self.group_names.len()
+            + self.radio_group_names.len();
+        let levels = if self.which.contains(NameDetail::KT_LEVEL_NAMES) {
+            self.level_names.len() + padding(self.level_names.len(), 4)
+        } else {
+            0
+        };
+        let length =
+            32 + 4 * atoms + levels + 4 * self.key_names.len() + 8 * self.key_aliases.len();
+        check_trailing_bytes("xkb::Names", bytes, length)
+    }
+}
+
+/// Returns the layouts named in the symbols of a keymap (see [Names::symbols]),
+/// with their group. For `pc+us+de:2+inet(evdev)` these are `us` in group 0 and
+/// `de` in group 1. Variants are dropped, as is everything that is not a layout.
+pub fn layouts_from_symbols(symbols: &str) -> Vec<(u8, String)> {
+    // Symbols files holding options or models rather than layouts.
+    const NOT_LAYOUTS: &[&str] = &[
+        "pc",
+        "inet",
+        "group",
+        "level3",
+        "level5",
+        "ctrl",
+        "compose",
+        "terminate",
+        "capslock",
+        "altwin",
+        "eurosign",
+        "rupeesign",
+        "keypad",
+        "kpdl",
+        "nbsp",
+        "shift",
+        "srvr_ctrl",
+        "lv3",
+        "lv5",
+        "grp_led",
+        "mod_led",
+        "evdev",
+        "base",
+    ];
+    let mut layouts = Vec::new();
+    for part in symbols.split('+') {
+        let (part, group) = match part.split_once(':') {
+            Some((part, group)) => (part, group.parse::<u8>().ok()),
+            None => (part, None),
+        };
+        let name = part.split('(').next().unwrap_or_default();
+        if name.is_empty() || NOT_LAYOUTS.contains(&name) {
+            continue;
+        }
+        // Groups are numbered from 1 in the symbols, from 0 in the protocol.
+        let group = group.map_or(layouts.len() as u8, |group| group.saturating_sub(1));
+        layouts.push((group, name.to_string()));
+    }
+    layouts
+}
+
+/// A keyboard layout, i.e. a group of the keymap.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Layout {
+    pub group: u8,
+    /// The name of the group, e.g. `English (US)`.
+    pub name: String,
+    /// The layout in the symbols of the keymap, e.g. `us`, if it could be told.
+    pub short_name: Option<String>,
+}
+
+/// Changes to the latched and locked modifiers and group of a keyboard, see
+/// [Connection::xkb_latch_lock_state]. Only what is `Some` changes.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LatchLockState {
+    /// The modifiers to lock or unlock, and which of them to lock.
+    pub mod_locks: Option<(u8, u8)>,
+    pub group_lock: Option<u8>,
+    /// The modifiers to latch or unlatch, and which of them to latch.
+    pub mod_latches: Option<(u8, u8)>,
+    pub group_latch: Option<u16>,
+}
+
+/// The names of a keyboard changed, e.g. after the layouts were switched with
+/// setxkbmap.
+#[derive(Debug, Clone)]
+pub struct NamesNotifyEvent {
+    pub time: Timestamp,
+    pub device_id: u8,
+    /// The names that changed.
+    pub changed: NameDetail,
+    pub first_type: u8,
+    pub number_of_types: u8,
+    pub first_level_name: u8,
+    pub number_of_level_names: u8,
+    pub number_of_radio_groups: u8,
+    pub number_of_key_aliases: u8,
+    /// The groups whose name changed.
+    pub changed_group_names: u8,
+    pub changed_virtual_mods: u16,
+    pub first_key: KeyCode,
+    pub number_of_keys: u8,
+    pub changed_indicators: u32,
+}
+
+/// The keyboard state changed.
+#[derive(Debug, Clone)]
+pub struct StateNotifyEvent {
+    pub time: Timestamp,
+    pub device_id: u8,
+    /// The new state.
+    pub state: State,
+    /// Which components of the state changed.
+    pub changed: u16,
+    /// The key, event and request that caused the change, if any.
+    pub keycode: KeyCode,
+    pub event_type: u8,
+    pub request_major: u8,
+    pub request_minor: u8,
+}
+
+/// The keyboard map changed. Fetch the changed parts anew with
+/// [Connection::xkb_get_map].
+#[derive(Debug, Clone)]
+pub struct MapNotifyEvent {
+    pub time: Timestamp,
+    pub device_id: u8,
+    /// The parts of the map that changed.
+    pub changed: MapPart,
+    pub min_keycode: KeyCode,
+    pub max_keycode: KeyCode,
+    pub first_type: u8,
+    pub number_of_types: u8,
+    pub first_keysym: KeyCode,
+    pub number_of_keysyms: u8,
+    pub first_modifier_map_key: KeyCode,
+    pub number_of_modifier_map_keys: u8,
+    pub virtual_mods: u16,
+}
+
+/// An XKB event.
+#[derive(Debug, Clone)]
+pub enum XkbEvent {
+    MapNotify(MapNotifyEvent),
+    StateNotify(StateNotifyEvent),
+    NamesNotify(NamesNotifyEvent),
+}
+
+impl XkbEvent {
+    /// Parses the event if `event` is one of the XKB events this module knows.
+    /// `first_event` is the one of the extension, see [Connection::extension].
+    pub fn from_event(event: &Event, first_event: u8) -> Option<XkbEvent> {
+        let Event::Unknown(bytes) = event else {
+            return None;
+        };
+        if bytes.first()? & 0x7f != first_event {
+            return None;
+        }
+        match *bytes.get(1)? {
+            event_type::MAP_NOTIFY => Self::parse_map_notify(bytes),
+            event_type::STATE_NOTIFY => Self::parse_state_notify(bytes),
+            event_type::NAMES_NOTIFY => Self::parse_names_notify(bytes),
+            _ => None,
+        }
+    }
+
+    //   1 byte     Code (first event)
+    //   1 byte     XKB event type (1)
+    //   2 bytes    Sequence number
+    //   4 bytes    Time
+    //   1 byte     Device id
+    //   1 byte     Pointer button actions
+    //   2 bytes    Changed
+    //   1 byte     Min keycode
+    //   1 byte     Max keycode
+    //   1 byte     First type
+    //   1 byte     Number of types
+    //   1 byte     First keysym
+    //   1 byte     Number of keysyms
+    //   6 bytes    First and number of actions, behaviors and explicit components
+    //   1 byte     First modifier map key
+    //   1 byte     Number of modifier map keys
+    //   2 bytes    First and number of virtual modifier map keys
+    //   2 bytes    Virtual modifiers
+    //   2 bytes    Unused
+    fn parse_map_notify(bytes: &[u8]) -> Option<XkbEvent> {
+        let rest = skip(bytes, 4).ok()?;
+        let (time, rest) = deserialize_into::<Timestamp>(rest).ok()?;
+        let (device_id, rest) = deserialize_into::<u8>(rest).ok()?;
+        let rest = skip(rest, 1).ok()?;
+        let (changed, rest) = deserialize_into::<u16>(rest).ok()?;
+        let (min_keycode, rest) = deserialize_into::<u8>(rest).ok()?;
+        let (max_keycode, rest) = deserialize_into::<u8>(rest).ok()?;
+        let (first_type, rest) = deserialize_into::<u8>(rest).ok()?;
+        let (number_of_types, rest) = deserialize_into::<u8>(rest).ok()?;
+        let (first_keysym, rest) = deserialize_into::<u8>(rest).ok()?;
+        let (number_of_keysyms, rest) = deserialize_into::<u8>(rest).ok()?;
+        let rest = skip(rest, 6).ok()?;
+        let (first_modifier_map_key, rest) = deserialize_into::<u8>(rest).ok()?;
+        let (number_of_modifier_map_keys, rest) = deserialize_into::<u8>(rest).ok()?;
+        let rest = skip(rest, 2).ok()?;
+        let (virtual_mods, _) = deserialize_into::<u16>(rest).ok()?;
+        Some(XkbEvent::MapNotify(MapNotifyEvent {
+            time,
+            device_id,
+            changed: MapPart(changed),
+            min_keycode,
+            max_keycode,
+            first_type,
+            number_of_types,
+            first_keysym,
+            number_of_keysyms,
+            first_modifier_map_key,
+            number_of_modifier_map_keys,
+            virtual_mods,
+        }))
+    }
+
+    //   1 byte     Code (first event)
+    //   1 byte     XKB event type (2)
+    //   2 bytes    Sequence number
+    //   4 bytes    Time
+    //   1 byte     Device id
+    //   1 byte     Modifiers
+    //   1 byte     Base modifiers
+    //   1 byte     Latched modifiers
+    //   1 byte     Locked modifiers
+    //   1 byte     Group
+    //   2 bytes    Base group
+    //   2 bytes    Latched group
+    //   1 byte     Locked group
+    //   1 byte     Compatibility state
+    //   1 byte     Grab modifiers
+    //   1 byte     Compatibility grab modifiers
+    //   1 byte     Lookup modifiers
+    //   1 byte     Compatibility lookup modifiers
+    //   2 bytes    Pointer buttons
+    //   2 bytes    Changed
+    //   1 byte     Keycode
+    //   1 byte     Event type
+    //   1 byte     Request major opcode
+    //   1 byte     Request minor opcode
+    fn parse_state_notify(bytes: &[u8]) -> Option<XkbEvent> {
+        let rest = skip(bytes, 4).ok()?;
+        let (time, rest) = deserialize_into::<Timestamp>(rest).ok()?;
+        let (device_id, rest) = deserialize_into::<u8>(rest).ok()?;
+        let (mods, rest) = deserialize_into::<u8>(rest).ok()?;
+        let (base_mods, rest) = deserialize_into::<u8>(rest).ok()?;
+        let (latched_mods, rest) = deserialize_into::<u8>(rest).ok()?;
+        let (locked_mods, rest) = deserialize_into::<u8>(rest).ok()?;
+        let (group, rest) = deserialize_into::<u8>(rest).ok()?;
+        let (base_group, rest) = deserialize_into::<i16>(rest).ok()?;
+        let (latched_group, rest) = deserialize_into::<i16>(rest).ok()?;
+        let (locked_group, rest) = deserialize_into::<u8>(rest).ok()?;
+        let (compat_state, rest) = deserialize_into::<u8>(rest).ok()?;
+        let (grab_mods, rest) = deserialize_into::<u8>(rest).ok()?;
+        let (compat_grab_mods, rest) = deserialize_into::<u8>(rest).ok()?;
+        let (lookup_mods, rest) = deserialize_into::<u8>(rest).ok()?;
+        let (compat_lookup_mods, rest) = deserialize_into::<u8>(rest).ok()?;
+        let (pointer_buttons, rest) = deserialize_into::<u16>(rest).ok()?;
+        let (changed, rest) = deserialize_into::<u16>(rest).ok()?;
+        let (keycode, rest) = deserialize_into::<u8>(rest).ok()?;
+        let (event_type, rest) = deserialize_into::<u8>(rest).ok()?;
+        let (request_major, rest) = deserialize_into::<u8>(rest).ok()?;
+        let (request_minor, _) = deserialize_into::<u8>(rest).ok()?;
+        Some(XkbEvent::StateNotify(StateNotifyEvent {
+            time,
+            device_id,
+            state: State {
+                mods,
+                base_mods,
+                latched_mods,
+                locked_mods,
+                group,
+                base_group,
+                latched_group,
+                locked_group,
+                compat_state,
+                grab_mods,
+                compat_grab_mods,
+                lookup_mods,
+                compat_lookup_mods,
+                pointer_buttons,
+            },
+            changed,
+            keycode,
+            event_type,
+            request_major,
+            request_minor,
+        }))
+    }
+
+    //   1 byte     Code (first event)
+    //   1 byte     XKB event type (6)
+    //   2 bytes    Sequence number
+    //   4 bytes    Time
+    //   1 byte     Device id
+    //   1 byte     Unused
+    //   2 bytes    Changed
+    //   1 byte     First type
+    //   1 byte     Number of types
+    //   1 byte     First level name
+    //   1 byte     Number of level names
+    //   1 byte     Unused
+    //   1 byte     Number of radio groups
+    //   1 byte     Number of key aliases
+    //   1 byte     Changed group names
+    //   2 bytes    Changed virtual modifiers
+    //   1 byte     First key
+    //   1 byte     Number of keys
+    //   4 bytes    Changed indicators
+    //   4 bytes    Unused
+    fn parse_names_notify(bytes: &[u8]) -> Option<XkbEvent> {
+        let rest = skip(bytes, 4).ok()?;
+        let (time, rest) = deserialize_into::<Timestamp>(rest).ok()?;
+        let (device_id, rest) = deserialize_into::<u8>(rest).ok()?;
+        let rest = skip(rest, 1).ok()?;
+        let (changed, rest) = deserialize_into::<u16>(rest).ok()?;
+        let (first_type, rest) = deserialize_into::<u8>(rest).ok()?;
+        let (number_of_types, rest) = deserialize_into::<u8>(rest).ok()?;
+        let (first_level_name, rest) = deserialize_into::<u8>(rest).ok()?;
+        let (number_of_level_names, rest) = deserialize_into::<u8>(rest).ok()?;
+        let rest = skip(rest, 1).ok()?;
+        let (number_of_radio_groups, rest) = deserialize_into::<u8>(rest).ok()?;
+        let (number_of_key_aliases, rest) = deserialize_into::<u8>(rest).ok()?;
+        let (changed_group_names, rest) = deserialize_into::<u8>(rest).ok()?;
+        let (changed_virtual_mods, rest) = deserialize_into::<u16>(rest).ok()?;
+        let (first_key, rest) = deserialize_into::<u8>(rest).ok()?;
+        let (number_of_keys, rest) = deserialize_into::<u8>(rest).ok()?;
+        let (changed_indicators, _) = deserialize_into::<u32>(rest).ok()?;
+        Some(XkbEvent::NamesNotify(NamesNotifyEvent {
+            time,
+            device_id,
+            changed: NameDetail(changed.into()),
+            first_type,
+            number_of_types,
+            first_level_name,
+            number_of_level_names,
+            number_of_radio_groups,
+            number_of_key_aliases,
+            changed_group_names,
+            changed_virtual_mods,
+            first_key,
+            number_of_keys,
+            changed_indicators,
+        }))
+    }
+}
+
+impl Connection {
+    /// Negotiates the version of the extension. Must be sent before any other
+    /// XKB request.
+    pub fn xkb_use_extension(
+        &mut self,
+        major_version: u16,
+        minor_version: u16,
+    ) -> Result<Cookie<UseExtensionReply>, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (0)
+        //   2 bytes    Request length (2)
+        //   2 bytes    Wanted major version
+        //   2 bytes    Wanted minor version
+        let request = request(self, opcode::USE_EXTENSION)?
+            .put(major_version)
+            .put(minor_version)
+            .finish();
+        self.send_request_with_reply(&request)
+    }
+
+    /// Selects every variant of the events in `select` and deselects the other
+    /// events of `affect`. The events not in `affect` stay as they are.
+    pub fn xkb_select_events(
+        &mut self,
+        device: DeviceSpec,
+        affect: EventType,
+        select: EventType,
+    ) -> Result<u16, ConnectionError> {
+        let select = select.0 & affect.0;
+        let map = if select & EventType::MAP_NOTIFY.0 != 0 {
+            MapPart::ALL
+        } else {
+            MapPart::default()
+        };
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (1)
+        //   2 bytes    Request length (4)
+        //   2 bytes    Device
+        //   2 bytes    Affected events
+        //   2 bytes    Cleared events
+        //   2 bytes    Fully selected events
+        //   2 bytes    Affected map parts
+        //   2 bytes    Selected map parts
+        //
+        //  The details of the events that are neither cleared nor fully selected
+        //  would follow; there are none.
+        let request = request(self, opcode::SELECT_EVENTS)?
+            .put(device)
+            .put(affect.0)
+            .put(affect.0 & !select)
+            .put(select)
+            .put(MapPart::ALL.0)
+            .put(map.0)
+            .finish();
+        self.send_request(&request)
+    }
+
+    /// Returns the state of a keyboard.
+    pub fn xkb_get_state(&mut self, device: DeviceSpec) -> Result<Cookie<State>, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (4)
+        //   2 bytes    Request length (2)
+        //   2 bytes    Device
+        //   2 bytes    Unused
+        let request = request(self, opcode::GET_STATE)?
+            .put(device)
+            .pad(2)
+            .finish();
+        self.send_request_with_reply(&request)
+    }
+
+    /// Returns the key types, keysyms and modifier map of a keyboard.
+    pub fn xkb_get_map(&mut self, device: DeviceSpec) -> Result<Cookie<KeyMap>, ConnectionError> {
+        let full = MapPart::KEY_TYPES | MapPart::KEY_SYMS | MapPart::MODIFIER_MAP;
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (8)
+        //   2 bytes    Request length (7)
+        //   2 bytes    Device
+        //   2 bytes    Full parts
+        //   2 bytes    Partial parts
+        //   1 byte     First type
+        //   1 byte     Number of types
+        //   1 byte     First keysym
+        //   1 byte     Number of keysyms
+        //   1 byte     First key action
+        //   1 byte     Number of key actions
+        //   1 byte     First key behavior
+        //   1 byte     Number of key behaviors
+        //   2 bytes    Virtual modifiers
+        //   1 byte     First explicit key
+        //   1 byte     Number of explicit keys
+        //   1 byte     First modifier map key
+        //   1 byte     Number of modifier map keys
+        //   1 byte     First virtual modifier map key
+        //   1 byte     Number of virtual modifier map keys
+        //   2 bytes    Unused
+        //
+        //  The ranges only apply to the partial parts, of which there are none.
+        let request = request(self, opcode::GET_MAP)?
+            .put(device)
+            .put(full.0)
+            .put(0u16)
+            .pad(20)
+            .finish();
+        self.send_request_with_reply(&request)
+    }
+
+    /// Returns the given names of a keyboard.
+    pub fn xkb_get_names(
+        &mut self,
+        device: DeviceSpec,
+        which: NameDetail,
+    ) -> Result<Cookie<Names>, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (17)
+        //   2 bytes    Request length (3)
+        //   2 bytes    Device
+        //   2 bytes    Unused
+        //   4 bytes    Which
+        let request = request(self, opcode::GET_NAMES)?
+            .put(device)
+            .pad(2)
+            .put(which.0)
+            .finish();
+        self.send_request_with_reply(&request)
+    }
+
+    /// Latches or locks modifiers and groups of a keyboard, as if the keys doing
+    /// so were pressed.
+    pub fn xkb_latch_lock_state(
+        &mut self,
+        device: DeviceSpec,
+        state: &LatchLockState,
+    ) -> Result<u16, ConnectionError> {
+        let (affect_mod_locks, mod_locks) = state.mod_locks.unwrap_or_default();
+        let (affect_mod_latches, mod_latches) = state.mod_latches.unwrap_or_default();
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (5)
+        //   2 bytes    Request length (4)
+        //   2 bytes    Device
+        //   1 byte     Affected modifier locks
+        //   1 byte     Modifier locks
+        //   1 byte     Lock group
+        //   1 byte     Group lock
+        //   1 byte     Affected modifier latches
+        //   1 byte     Modifier latches
+        //   1 byte     Unused
+        //   1 byte     Latch group
+        //   2 bytes    Group latch
+        let request = request(self, opcode::LATCH_LOCK_STATE)?
+            .put(device)
+            .put(affect_mod_locks)
+            .put(mod_locks)
+            .put(state.group_lock.is_some() as u8)
+            .put(state.group_lock.unwrap_or_default())
+            .put(affect_mod_latches)
+            .put(mod_latches)
+            .pad(1)
+            .put(state.group_latch.is_some() as u8)
+            .put(state.group_latch.unwrap_or_default())
+            .finish();
+        self.send_request(&request)
+    }
+
+    /// Switches a keyboard to the layout of the given group.
+    pub fn xkb_lock_group(
+        &mut self,
+        device: DeviceSpec,
+        group: u8,
+    ) -> Result<u16, ConnectionError> {
+        let state = LatchLockState {
+            group_lock: Some(group),
+            ..Default::default()
+        };
+        self.xkb_latch_lock_state(device, &state)
+    }
+
+    /// Returns the layouts of a keyboard, one for each named group.
+    pub fn xkb_layouts(&mut self, device: DeviceSpec) -> Result<Vec<Layout>, ConnectionError> {
+        let names = self
+            .xkb_get_names(device, NameDetail::SYMBOLS | NameDetail::GROUP_NAMES)?
+            .reply(self)?;
+        // Send every GetAtomName before waiting on the first reply.
+        let symbols = names
+            .symbols
+            .map(|atom| self.get_atom_name(atom))
+            .transpose()?;
+        let cookies = names
+            .group_names
+            .iter()
+            .map(|&(group, atom)| Ok((group, self.get_atom_name(atom)?)))
+            .collect::<Result<Vec<_>, ConnectionError>>()?;
+        let short_names = match symbols {
+            Some(cookie) => layouts_from_symbols(&cookie.reply(self)?.name.as_str_lossy()),
+            None => Vec::new(),
+        };
+        cookies
+            .into_iter()
+            .map(|(group, cookie)| {
+                let name = cookie.reply(self)?.name.as_str_lossy().into_owned();
+                let short_name = short_names
+                    .iter()
+                    .find(|(layout_group, _)| *layout_group == group)
+                    .map(|(_, short_name)| short_name.clone());
+                Ok(Layout {
+                    group,
+                    name,
+                    short_name,
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the layout of the current group of a keyboard.
+    pub fn xkb_active_layout(
+        &mut self,
+        device: DeviceSpec,
+    ) -> Result<Option<Layout>, ConnectionError> {
+        let group = self.xkb_get_state(device)?.reply(self)?.group;
+        let layouts = self.xkb_layouts(device)?;
+        Ok(layouts.into_iter().find(|layout| layout.group == group))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cookie::{parse_reply, ParseMode};
+    use crate::testing::MessageBuilder;
+
+    const SHIFT: u8 = 0x01;
+    const LOCK: u8 = 0x02;
+
+    /// Writes a key type without preserved modifiers, of (modifiers, level) entries.
+    fn put_key_type(message: &mut MessageBuilder, mods: u8, levels: u8, entries: &[(u8, u8)]) {
+        message
+            .put(mods)
+            .put(mods)
+            .put(0u16)
+            .put(levels)
+            .put(entries.len() as u8)
+            .put(false)
+            .pad(1);
+        for &(mods, level) in entries {
+            message
+                .put(true)
+                .put(mods)
+                .put(level)
+                .put(mods)
+                .put(0u16)
+                .pad(2);
+        }
+    }
+
+    /// Writes the keysyms of a key whose groups all have the given type.
+    fn put_key(message: &mut MessageBuilder, key_type: u8, group_info: u8, keysyms: &[Keysym]) {
+        let groups = usize::from(group_info & 0x0f);
+        let width = keysyms.len().checked_div(groups).unwrap_or(0);
+        message
+            .put_bytes(&[key_type; 4])
+            .put(group_info)
+            .put(width as u8)
+            .put(keysyms.len() as u16);
+        for &keysym in keysyms {
+            message.put(keysym);
+        }
+    }
+
+    /// The GetMap reply of a keyboard with the `us` and `de` layouts, for the
+    /// keys Y (29), U (30) and Escape (9 in real keymaps, 31 here).
+    fn us_de_key_map() -> Vec<u8> {
+        let mut message = MessageBuilder::default();
+        let present = MapPart::KEY_TYPES | MapPart::KEY_SYMS | MapPart::MODIFIER_MAP;
+        message
+            .pad(2)
+            .put(8u8)
+            .put(255u8)
+            .put(present.0)
+            // Types: first, count and total.
+            .put_bytes(&[0, 4, 4])
+            // Keysyms: first, total and count.
+            .put(29u8)
+            .put(10u16)
+            .put(3u8)
+            // Actions, behaviors and explicit components: none.
+            .put_bytes(&[0; 10])
+            // Modifier map: first, count and total keys.
+            .put_bytes(&[50, 17, 2])
+            // Virtual modifier map: none.
+            .put_bytes(&[0, 0, 0])
+            .pad(1)
+            .put(0u16);
+        // ONE_LEVEL, TWO_LEVEL and ALPHABETIC.
+        put_key_type(&mut message, 0, 1, &[]);
+        put_key_type(&mut message, SHIFT, 2, &[(SHIFT, 1)]);
+        put_key_type(&mut message, SHIFT | LOCK, 2, &[(SHIFT, 1), (LOCK, 1)]);
+        // A type where Shift selects level 2, keeping Lock for the client.
+        message
+            .put(SHIFT | LOCK)
+            .put(SHIFT | LOCK)
+            .put(0u16)
+            .put(2u8)
+            .put(1u8)
+            .put(true)
+            .pad(1)
+            .put(true)
+            .put(SHIFT | LOCK)
+            .put(1u8)
+            .put(SHIFT | LOCK)
+            .put(0u16)
+            .pad(2)
+            .put(LOCK)
+            .put(LOCK)
+            .put(0u16);
+        // y/Y then z/Z, wrapping into range; u/U, clamping; Escape, redirecting
+        // into group 0.
+        put_key(&mut message, 2, 0x02, &[0x79, 0x59, 0x7a, 0x5a]);
+        put_key(&mut message, 2, 0x41, &[0x75, 0x55]);
+        put_key(&mut message, 0, 0x82, &[0xff1b, 0xff1b]);
+        // Shift_L (50) is Shift and Caps_Lock (66) is Lock.
+        message.put_bytes(&[50, SHIFT, 66, LOCK]);
+        message.reply(3)
+    }
+
+    #[test]
+    fn key_map() {
+        let bytes = us_de_key_map();
+        let map: KeyMap = parse_reply(&bytes, ParseMode::Strict).unwrap();
+        assert_eq!(map.device_id, 3);
+        assert_eq!((map.min_keycode, map.max_keycode), (8, 255));
+        assert_eq!(map.types.len(), 4);
+        assert_eq!(map.types[1].num_levels, 2);
+        assert_eq!(map.types[2].entries.len(), 2);
+        assert_eq!(map.types[3].entries[0].preserve, Some(LOCK));
+        assert_eq!(map.first_keysym, 29);
+        assert_eq!(map.keysyms.len(), 3);
+        assert_eq!(map.key(29).unwrap().groups(), 2);
+        assert!(map.key(28).is_none());
+        assert!(map.key(32).is_none());
+        assert_eq!(map.modifier_map, [(50, SHIFT), (66, LOCK)]);
+
+        // Bytes past the modifier map.
+        let mut long = bytes;
+        long.extend_from_slice(&[0; 4]);
+        assert!(parse_reply::<KeyMap>(&long, ParseMode::Strict).is_err());
+        // A key type cut short.
+        assert!(KeyMap::from_bytes(&long[..60]).is_err());
+    }
+
+    #[test]
+    fn key_types_select_levels() {
+        let map: KeyMap = parse_reply(&us_de_key_map(), ParseMode::Strict).unwrap();
+        let alphabetic = &map.types[2];
+        assert_eq!(alphabetic.level(0), 0);
+        assert_eq!(alphabetic.level(SHIFT), 1);
+        assert_eq!(alphabetic.level(LOCK), 1);
+        // Both modifiers match no entry, and Mod2 (NumLock) is ignored.
+        assert_eq!(alphabetic.level(SHIFT | LOCK), 0);
+        assert_eq!(alphabetic.level(LOCK | 0x10), 1);
+        assert_eq!(map.types[0].level(SHIFT), 0);
+    }
+
+    #[test]
+    fn groups_switch_the_keysyms() {
+        let map: KeyMap = parse_reply(&us_de_key_map(), ParseMode::Strict).unwrap();
+        // us, then de.
+        assert_eq!(map.keysym(29, 0, 0), Some(0x79));
+        assert_eq!(map.keysym(29, 0, SHIFT), Some(0x59));
+        assert_eq!(map.keysym(29, 1, 0), Some(0x7a));
+        assert_eq!(map.keysym(29, 1, LOCK), Some(0x5a));
+        // Groups beyond those of the key wrap, clamp or redirect.
+        assert_eq!(map.keysym(29, 2, 0), Some(0x79));
+        assert_eq!(map.keysym(29, 3, 0), Some(0x7a));
+        assert_eq!(map.keysym(30, 1, SHIFT), Some(0x55));
+        assert_eq!(map.keysym(30, 3, 0), Some(0x75));
+        assert_eq!(map.key(31).unwrap().effective_group(3), Some(0));
+        assert_eq!(map.keysym(31, 3, SHIFT), Some(0xff1b));
+        assert_eq!(map.keysym(32, 0, 0), None);
+    }
+
+    #[test]
+    fn state() {
+        // The de layout locked, Shift held and Caps Lock on.
+        let bytes = MessageBuilder::default()
+            .put(SHIFT | LOCK)
+            .put(SHIFT)
+            .put(0u8)
+            .put(LOCK)
+            .put(1u8)
+            .put(1u8)
+            .put(0i16)
+            .put(0i16)
+            .put(SHIFT | LOCK)
+            .put(SHIFT | LOCK)
+            .put(SHIFT | LOCK)
+            .put(SHIFT | LOCK)
+            .put(SHIFT | LOCK)
+            .pad(1)
+            .put(0x100u16)
+            .reply(3);
+        let state: State = parse_reply(&bytes, ParseMode::Strict).unwrap();
+        assert_eq!(state.mods, SHIFT | LOCK);
+        assert_eq!((state.base_mods, state.locked_mods), (SHIFT, LOCK));
+        assert_eq!((state.group, state.locked_group), (1, 1));
+        assert_eq!(state.lookup_mods, SHIFT | LOCK);
+        assert_eq!(state.pointer_buttons, 0x100);
+
+        let map: KeyMap = parse_reply(&us_de_key_map(), ParseMode::Strict).unwrap();
+        // Shift cancels Caps Lock on alphabetic keys.
+        assert_eq!(map.keysym(29, state.group, state.lookup_mods), Some(0x7a));
+        assert_eq!(map.keysym(29, state.group, LOCK), Some(0x5a));
+
+        // Switched back to us by a StateNotify.
+        const FIRST_EVENT: u8 = 85;
+        let event = MessageBuilder::default()
+            .put(event_type::STATE_NOTIFY)
+            .put(9u16)
+            .put(0x0001_2345u32)
+            .put(3u8)
+            .put(0u8)
+            .put(0u8)
+            .put(0u8)
+            .put(0u8)
+            .put(0u8)
+            .put(0i16)
+            .put(0i16)
+            .put(0u8)
+            .pad(5)
+            .put(0u16)
+            // Changed: the group lock and the effective group.
+            .put(0x50u16)
+            .put(0u8)
+            .put(0u8)
+            .put(130u8)
+            .put(opcode::LATCH_LOCK_STATE)
+            .event(FIRST_EVENT);
+        let event = Event::from_bytes(&event).unwrap();
+        let Some(XkbEvent::StateNotify(event)) = XkbEvent::from_event(&event, FIRST_EVENT) else {
+            panic!("expected StateNotify");
+        };
+        assert_eq!(event.changed, 0x50);
+        assert_eq!((event.request_major, event.request_minor), (130, 5));
+        let mut state = state;
+        state.update(&event);
+        assert_eq!(state, State::default());
+        assert_eq!(map.keysym(29, state.group, state.lookup_mods), Some(0x79));
+    }
+
+    #[test]
+    fn names() {
+        // The symbols, key type and level names, group names and key names.
+        let which = NameDetail::SYMBOLS
+            | NameDetail::KEY_TYPE_NAMES
+            | NameDetail::KT_LEVEL_NAMES
+            | NameDetail::GROUP_NAMES
+            | NameDetail::KEY_NAMES;
+        let mut message = MessageBuilder::default();
+        message
+            .put(which.0)
+            .put(8u8)
+            .put(255u8)
+            .put(3u8)
+            .put(0b0011u8)
+            // Virtual modifiers and indicators, whose names were not asked for.
+            .put(0x00ffu16)
+            .put(29u8)
+            .put(2u8)
+            .put(0x0000_0007u32)
+            .put(0u8)
+            .put(0u8)
+            .put(5u16)
+            .pad(4)
+            .put(0x1e0u32);
+        for atom in [0x1e1u32, 0x1e2, 0x1e3] {
+            message.put(atom);
+        }
+        // 1, 2 and 2 levels, padded to 4 bytes.
+        message.put_bytes(&[1, 2, 2]).pad(1);
+        for atom in [0x1e4u32, 0x1e4, 0x1e5, 0x1e4, 0x1e5, 0x1e6, 0x1e7] {
+            message.put(atom);
+        }
+        let bytes = message.put_bytes(b"AD06AD07").reply(3);
+
+        let names: Names = parse_reply(&bytes, ParseMode::Strict).unwrap();
+        assert_eq!(names.which, which);
+        assert_eq!(names.symbols, Some(0x1e0));
+        assert_eq!(names.keycodes, None);
+        assert_eq!(names.type_names, [0x1e1, 0x1e2, 0x1e3]);
+        assert_eq!(
+            names.level_names,
+            [vec![0x1e4], vec![0x1e4, 0x1e5], vec![0x1e4, 0x1e5]]
+        );
+        assert!(names.indicator_names.is_empty());
+        assert!(names.virtual_mod_names.is_empty());
+        assert_eq!(names.group_names, [(0, 0x1e6), (1, 0x1e7)]);
+        assert_eq!(names.key_names, [*b"AD06", *b"AD07"]);
+
+        // Only the group names of groups 1 and 3.
+        let bytes = MessageBuilder::default()
+            .put(NameDetail::GROUP_NAMES.0)
+            .put(8u8)
+            .put(255u8)
+            .put(0u8)
+            .put(0b1010u8)
+            .pad(16)
+            .put(0x1e6u32)
+            .put(0x1e7u32)
+            .reply(3);
+        let names: Names = parse_reply(&bytes, ParseMode::Strict).unwrap();
+        assert_eq!(names.symbols, None);
+        assert_eq!(names.group_names, [(1, 0x1e6), (3, 0x1e7)]);
+    }
+
+    #[test]
+    fn layouts() {
+        assert_eq!(
+            layouts_from_symbols("pc+us+de:2+inet(evdev)"),
+            [(0, "us".to_string()), (1, "de".to_string())]
+        );
+        assert_eq!(
+            layouts_from_symbols("pc+gb(extd)+ru:3+group(alt_shift_toggle)"),
+            [(0, "gb".to_string()), (2, "ru".to_string())]
+        );
+        assert!(layouts_from_symbols("").is_empty());
+    }
+}
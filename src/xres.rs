@@ -0,0 +1,380 @@
+//! The X-Resource extension: the clients of the server and what they use.
+//!
+//! Besides counting the resources each client holds, the extension tells which
+//! process a client is, which attributes a window to the program showing it.
+//!
+//! ```no_run
+//! use mousetrap::connection::Connection;
+//! use mousetrap::protocol::XId;
+//!
+//! let mut conn = Connection::init().unwrap();
+//! let window = XId(0x1200001);
+//! if let Some(pid) = conn.xres_client_pid(window).unwrap() {
+//!     println!("window {:?} belongs to process {}", window, pid);
+//! }
+//! ```
+
+use crate::connection::Connection;
+use crate::cookie::{Cookie, Reply};
+use crate::errors::{ConnectionError, ParseError};
+use crate::extension::{Extension, Version};
+use crate::protocol::{Atom, XId};
+use crate::request::RequestBuilder;
+use crate::utils::{check_trailing_bytes, deserialize_into, deserialize_into_vec, skip};
+
+/// Name of the extension, as given to QueryExtension.
+pub const NAME: &str = "X-Resource";
+
+/// Version of the extension implemented by this module.
+pub const VERSION: (u8, u8) = (1, 2);
+
+/// Minor opcodes of the X-Resource requests.
+pub mod opcode {
+    pub const QUERY_VERSION: u8 = 0;
+    pub const QUERY_CLIENTS: u8 = 1;
+    pub const QUERY_CLIENT_RESOURCES: u8 = 2;
+    pub const QUERY_CLIENT_IDS: u8 = 4;
+}
+
+/// Names of the X-Resource requests, indexed by minor opcode.
+pub const REQUEST_NAMES: &[&str] = &[
+    "QueryVersion",
+    "QueryClients",
+    "QueryClientResources",
+    "QueryClientPixmapBytes",
+    "QueryClientIds",
+    "QueryResourceBytes",
+];
+
+/// The extension defines no errors.
+pub const ERROR_NAMES: &[&str] = &[];
+
+/// Starts a request of the extension.
+fn request(conn: &mut Connection, minor_opcode: u8) -> Result<RequestBuilder, ConnectionError> {
+    let info = conn.require_extension(NAME, REQUEST_NAMES, ERROR_NAMES)?;
+    Ok(RequestBuilder::new(info.major_opcode, minor_opcode))
+}
+
+/// The X-Resource extension, for [Connection::enable].
+pub struct XRes;
+
+impl Extension for XRes {
+    const NAME: &'static str = NAME;
+    const MIN_VERSION: Version = (1, 0);
+
+    fn negotiate(conn: &mut Connection) -> Result<Option<Version>, ConnectionError> {
+        let reply = conn.xres_query_version(VERSION.0, VERSION.1)?.reply(conn)?;
+        Ok(Some((
+            reply.major_version.into(),
+            reply.minor_version.into(),
+        )))
+    }
+}
+
+/// The kinds of ids asked for with [Connection::xres_query_client_ids].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ClientIdMask(pub u32);
+
+impl ClientIdMask {
+    /// The resource id base of the client.
+    pub const CLIENT_XID: Self = Self(1);
+    /// The process id of a client connected on the same host.
+    pub const LOCAL_CLIENT_PID: Self = Self(2);
+
+    /// Whether all the bits of `other` are set.
+    pub fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl std::ops::BitOr for ClientIdMask {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+/// The ids of a client to look up. `client` is any resource of the client, or
+/// 0 for all clients.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClientIdSpec {
+    pub client: XId,
+    pub mask: ClientIdMask,
+}
+
+/// Reply of the XResQueryVersion request.
+#[derive(Debug, Clone)]
+pub struct QueryVersionReply {
+    pub major_version: u16,
+    pub minor_version: u16,
+}
+
+impl Reply for QueryVersionReply {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1                        unused
+        // 2  CARD16                sequence number
+        // 4  0                     reply length
+        // 2  CARD16                server-major
+        // 2  CARD16                server-minor
+        // 20                       unused
+        let rest = skip(bytes, 8)?;
+        let (major_version, rest) = deserialize_into::<u16>(rest)?;
+        let (minor_version, _) = deserialize_into::<u16>(rest)?;
+        Ok(QueryVersionReply {
+            major_version,
+            minor_version,
+        })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        check_trailing_bytes("xres::QueryVersionReply", bytes, 32)
+    }
+}
+
+/// A client of the server, known by the range of resource ids it allocates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Client {
+    pub resource_base: u32,
+    pub resource_mask: u32,
+}
+
+impl Client {
+    /// Returns whether the resource `id` was allocated by this client.
+    pub fn owns(&self, id: XId) -> bool {
+        id.0 & !self.resource_mask == self.resource_base
+    }
+}
+
+/// Reply of the XResQueryClients request.
+#[derive(Debug, Clone)]
+pub struct QueryClientsReply {
+    pub clients: Vec<Client>,
+}
+
+impl Reply for QueryClientsReply {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1                        unused
+        // 2  CARD16                sequence number
+        // 4  2n                    reply length
+        // 4  CARD32                n, number of clients
+        // 20                       unused
+        // 8n LISTofCLIENT          clients
+        //
+        // CLIENT
+        // 4  CARD32                resource-base
+        // 4  CARD32                resource-mask
+        let rest = skip(bytes, 8)?;
+        let (count, rest) = deserialize_into::<u32>(rest)?;
+        let mut rest = skip(rest, 20)?;
+        let mut clients = Vec::new();
+        for _ in 0..count {
+            let (resource_base, next) = deserialize_into::<u32>(rest)?;
+            let (resource_mask, next) = deserialize_into::<u32>(next)?;
+            clients.push(Client {
+                resource_base,
+                resource_mask,
+            });
+            rest = next;
+        }
+        Ok(QueryClientsReply { clients })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        let length = 32 + 8 * self.clients.len();
+        check_trailing_bytes("xres::QueryClientsReply", bytes, length)
+    }
+}
+
+/// How many resources of a type a client holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResourceCount {
+    /// The type of the resources, an atom such as `WINDOW` or `PIXMAP`.
+    pub resource_type: Atom,
+    pub count: u32,
+}
+
+/// Reply of the XResQueryClientResources request.
+#[derive(Debug, Clone)]
+pub struct QueryClientResourcesReply {
+    pub types: Vec<ResourceCount>,
+}
+
+impl Reply for QueryClientResourcesReply {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1                        unused
+        // 2  CARD16                sequence number
+        // 4  2n                    reply length
+        // 4  CARD32                n, number of types
+        // 20                       unused
+        // 8n LISTofTYPE            types
+        //
+        // TYPE
+        // 4  ATOM                  resource-type
+        // 4  CARD32                count
+        let rest = skip(bytes, 8)?;
+        let (count, rest) = deserialize_into::<u32>(rest)?;
+        let mut rest = skip(rest, 20)?;
+        let mut types = Vec::new();
+        for _ in 0..count {
+            let (resource_type, next) = deserialize_into::<Atom>(rest)?;
+            let (count, next) = deserialize_into::<u32>(next)?;
+            types.push(ResourceCount {
+                resource_type,
+                count,
+            });
+            rest = next;
+        }
+        Ok(QueryClientResourcesReply { types })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        let length = 32 + 8 * self.types.len();
+        check_trailing_bytes("xres::QueryClientResourcesReply", bytes, length)
+    }
+}
+
+/// An id of a client: its resource id base for [ClientIdMask::CLIENT_XID], or
+/// its process id for [ClientIdMask::LOCAL_CLIENT_PID].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientIdValue {
+    pub spec: ClientIdSpec,
+    pub value: Vec<u32>,
+}
+
+/// Reply of the XResQueryClientIds request.
+#[derive(Debug, Clone)]
+pub struct QueryClientIdsReply {
+    pub ids: Vec<ClientIdValue>,
+}
+
+impl Reply for QueryClientIdsReply {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1                        unused
+        // 2  CARD16                sequence number
+        // 4  n                     reply length
+        // 4  CARD32                number of ids
+        // 20                       unused
+        // 4n LISTofCLIENTIDVALUE   ids
+        //
+        // CLIENTIDVALUE
+        // 4  CARD32                client
+        // 4  CARD32                mask
+        // 4  CARD32                m, length of the value in bytes
+        // m  LISTofCARD32          value
+        let rest = skip(bytes, 8)?;
+        let (count, rest) = deserialize_into::<u32>(rest)?;
+        let mut rest = skip(rest, 20)?;
+        let mut ids = Vec::new();
+        for _ in 0..count {
+            let (client, next) = deserialize_into::<XId>(rest)?;
+            let (mask, next) = deserialize_into::<u32>(next)?;
+            let (length, next) = deserialize_into::<u32>(next)?;
+            let (value, next) = deserialize_into_vec::<u32>(next, length as usize / 4)?;
+            ids.push(ClientIdValue {
+                spec: ClientIdSpec {
+                    client,
+                    mask: ClientIdMask(mask),
+                },
+                value,
+            });
+            rest = next;
+        }
+        Ok(QueryClientIdsReply { ids })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        let length = 32
+            + self
+                .ids
+                .iter()
+                .map(|id| 12 + 4 * id.value.len())
+                .sum::<usize>();
+        check_trailing_bytes("xres::QueryClientIdsReply", bytes, length)
+    }
+}
+
+impl Connection {
+    /// Tells the server the version of the extension the client supports and
+    /// returns the version the server supports.
+    pub fn xres_query_version(
+        &mut self,
+        major_version: u8,
+        minor_version: u8,
+    ) -> Result<Cookie<QueryVersionReply>, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (0)
+        //   2 bytes    Request length (2)
+        //   1 byte     Client major version
+        //   1 byte     Client minor version
+        //   2 bytes    Unused
+        let request = request(self, opcode::QUERY_VERSION)?
+            .put(major_version)
+            .put(minor_version)
+            .pad(2)
+            .finish();
+        self.send_request_with_reply(&request)
+    }
+
+    /// Returns the clients connected to the server.
+    pub fn xres_query_clients(&mut self) -> Result<Cookie<QueryClientsReply>, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (1)
+        //   2 bytes    Request length (1)
+        let request = request(self, opcode::QUERY_CLIENTS)?.finish();
+        self.send_request_with_reply(&request)
+    }
+
+    /// Returns how many resources of each type the client owning the resource
+    /// `id` holds.
+    pub fn xres_query_client_resources(
+        &mut self,
+        id: XId,
+    ) -> Result<Cookie<QueryClientResourcesReply>, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (2)
+        //   2 bytes    Request length (2)
+        //   4 bytes    Resource id
+        let request = request(self, opcode::QUERY_CLIENT_RESOURCES)?
+            .put(id)
+            .finish();
+        self.send_request_with_reply(&request)
+    }
+
+    /// Returns the ids of clients. Requires X-Resource 1.2.
+    pub fn xres_query_client_ids(
+        &mut self,
+        specs: &[ClientIdSpec],
+    ) -> Result<Cookie<QueryClientIdsReply>, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (4)
+        //   2 bytes    Request length (2+2n)
+        //   4 bytes    Number of specs (n)
+        //   8n bytes   Specs: client and mask
+        let mut request = request(self, opcode::QUERY_CLIENT_IDS)?;
+        request.put(specs.len() as u32);
+        for spec in specs {
+            request.put(spec.client).put(spec.mask.0);
+        }
+        self.send_request_with_reply(&request.finish())
+    }
+
+    /// Returns the process id of the client owning the resource `id`, such as a
+    /// window, if it is connected on the same host. Requires X-Resource 1.2.
+    pub fn xres_client_pid(&mut self, id: XId) -> Result<Option<u32>, ConnectionError> {
+        let spec = ClientIdSpec {
+            client: id,
+            mask: ClientIdMask::LOCAL_CLIENT_PID,
+        };
+        let reply = self.xres_query_client_ids(&[spec])?.reply(self)?;
+        Ok(reply
+            .ids
+            .into_iter()
+            .find(|id| id.spec.mask.contains(ClientIdMask::LOCAL_CLIENT_PID))
+            .and_then(|id| id.value.first().copied()))
+    }
+}
@@ -0,0 +1,279 @@
+//! The X resources loaded with `xrdb`, such as `Xft.dpi`.
+//!
+//! `xrdb` stores the resource database in the RESOURCE_MANAGER property of the
+//! root window of the first screen. [Resources] parses its syntax and looks up
+//! values the way Xlib does, matching patterns like `*background` or
+//! `URxvt*font`.
+//!
+//! ```no_run
+//! use mousetrap::connection::Connection;
+//!
+//! let mut conn = Connection::init().unwrap();
+//! let resources = conn.resources().unwrap();
+//! println!("dpi: {:?}", resources.dpi());
+//! println!("cursor: {:?} {:?}", resources.cursor_theme(), resources.cursor_size());
+//! ```
+
+use crate::connection::Connection;
+use crate::errors::ConnectionError;
+use crate::protocol::atom;
+use std::collections::BTreeMap;
+
+/// A resource database: values by resource pattern, e.g. `Xft.dpi` or
+/// `*.foreground`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Resources {
+    /// The values by pattern, as written in the database.
+    pub entries: BTreeMap<String, String>,
+}
+
+impl Resources {
+    /// Parses a database in the syntax of `xrdb`: `pattern: value` lines, with
+    /// `!` comments, `\` line continuations and the `\n`, `\\` and `\ooo`
+    /// escapes. Lines that are not resources, such as preprocessor
+    /// directives, are skipped. A pattern given twice keeps its last value.
+    pub fn parse(text: &str) -> Resources {
+        let mut entries = BTreeMap::new();
+        let mut lines = text.lines();
+        while let Some(line) = lines.next() {
+            // A backslash at the end of a line continues it on the next one.
+            let mut line = line.to_owned();
+            while line.ends_with('\\') && !line.ends_with("\\\\") {
+                line.pop();
+                match lines.next() {
+                    Some(next) => line.push_str(next),
+                    None => break,
+                }
+            }
+            let line = line.trim_start();
+            if line.starts_with('!') || line.starts_with('#') {
+                continue;
+            }
+            let Some((pattern, value)) = line.split_once(':') else {
+                continue;
+            };
+            let pattern: String = pattern.chars().filter(|c| !c.is_whitespace()).collect();
+            if pattern.is_empty() {
+                continue;
+            }
+            let value = value.trim_start_matches([' ', '\t']);
+            entries.insert(pattern, unescape(value));
+        }
+        Resources { entries }
+    }
+
+    /// Returns the value of a fully qualified resource name such as `Xft.dpi`,
+    /// from the most specific pattern matching it, e.g. `Xft.dpi` before
+    /// `Xft*dpi` before `*dpi`.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        if let Some(value) = self.entries.get(name) {
+            return Some(value);
+        }
+        let name: Vec<&str> = name.split('.').collect();
+        self.entries
+            .iter()
+            .filter_map(|(pattern, value)| Some((precedence(pattern, &name)?, value)))
+            .max_by(|(a, _), (b, _)| a.cmp(b))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns `Xft.dpi`, the resolution fonts are rendered at.
+    pub fn dpi(&self) -> Option<f64> {
+        self.get("Xft.dpi")?.trim().parse().ok()
+    }
+
+    /// Returns `Xcursor.theme`, the name of the cursor theme.
+    pub fn cursor_theme(&self) -> Option<&str> {
+        self.get("Xcursor.theme").map(str::trim)
+    }
+
+    /// Returns `Xcursor.size`, the nominal size of the cursors in pixels.
+    pub fn cursor_size(&self) -> Option<u32> {
+        self.get("Xcursor.size")?.trim().parse().ok()
+    }
+}
+
+impl Connection {
+    /// Returns the resources loaded with `xrdb`, from the RESOURCE_MANAGER
+    /// property of the root window of the first screen. The database is empty
+    /// if none was loaded.
+    pub fn resources(&mut self) -> Result<Resources, ConnectionError> {
+        let root = self.setup().roots[0].root;
+        let reply = self
+            .get_property(
+                false,
+                root,
+                atom::RESOURCE_MANAGER,
+                atom::STRING,
+                0,
+                u32::MAX,
+            )?
+            .reply(self)?;
+        Ok(Resources::parse(&String::from_utf8_lossy(&reply.value)))
+    }
+}
+
+/// Replaces the escapes of a value. `\ooo` escapes give bytes rather than
+/// characters, so that a multibyte character may be escaped byte by byte: the
+/// result is decoded as UTF-8 once complete.
+fn unescape(value: &str) -> String {
+    let mut result = Vec::with_capacity(value.len());
+    let mut bytes = value.bytes().peekable();
+    while let Some(byte) = bytes.next() {
+        if byte != b'\\' {
+            result.push(byte);
+            continue;
+        }
+        match bytes.next() {
+            Some(b'n') => result.push(b'\n'),
+            Some(digit @ b'0'..=b'7') => {
+                // Three octal digits give a byte.
+                let mut code = digit - b'0';
+                for _ in 0..2 {
+                    match bytes.next_if(|byte| matches!(byte, b'0'..=b'7')) {
+                        Some(digit) => code = code.wrapping_mul(8) + (digit - b'0'),
+                        None => break,
+                    }
+                }
+                result.push(code);
+            }
+            Some(other) => result.push(other),
+            None => result.push(b'\\'),
+        }
+    }
+    String::from_utf8(result)
+        .unwrap_or_else(|err| String::from_utf8_lossy(err.as_bytes()).into_owned())
+}
+
+/// How well a pattern matches each component of a name, from the first one:
+/// 3 for the component named after a tight binding (`.`), 2 after a loose one
+/// (`*`), 1 for `?`, 0 for a component skipped by a loose binding. Returns
+/// `None` if the pattern does not match.
+///
+/// Xlib prefers the pattern that matches the first components best, which is
+/// the order of these lists.
+fn precedence(pattern: &str, name: &[&str]) -> Option<Vec<u8>> {
+    // The components of the pattern, each with whether a loose binding precedes it.
+    let mut components = Vec::new();
+    let mut loose = false;
+    let mut start = 0;
+    for (i, c) in pattern.char_indices().chain([(pattern.len(), '.')]) {
+        if c != '.' && c != '*' {
+            continue;
+        }
+        if i > start {
+            components.push((loose, &pattern[start..i]));
+            loose = false;
+        }
+        loose |= c == '*';
+        start = i + 1;
+    }
+    let mut scores = Vec::with_capacity(name.len());
+    matches(&components, name, &mut scores).then_some(scores)
+}
+
+/// Matches pattern components against name components, filling `scores` with
+/// the first match found. That match scores best: at each component, matching
+/// scores above skipping and is tried first.
+fn matches(components: &[(bool, &str)], name: &[&str], scores: &mut Vec<u8>) -> bool {
+    let Some(&(loose, part)) = components.first() else {
+        return name.is_empty();
+    };
+    let Some(&first) = name.first() else {
+        return false;
+    };
+    let length = scores.len();
+    if part == first || part == "?" {
+        scores.push(match (part == "?", loose) {
+            (true, _) => 1,
+            (false, true) => 2,
+            (false, false) => 3,
+        });
+        if matches(&components[1..], &name[1..], scores) {
+            return true;
+        }
+        scores.truncate(length);
+    }
+    if loose {
+        // Skip a component of the name.
+        scores.push(0);
+        if matches(components, &name[1..], scores) {
+            return true;
+        }
+        scores.truncate(length);
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes() {
+        let resources = Resources::parse(concat!(
+            "a.newline: one\\ntwo\n",
+            "a.backslash: C:\\\\dir\n",
+            "a.octal: \\101\\040b\n",
+            "a.utf8: caf\\303\\251\n",
+        ));
+        assert_eq!(resources.get("a.newline"), Some("one\ntwo"));
+        assert_eq!(resources.get("a.backslash"), Some("C:\\dir"));
+        assert_eq!(resources.get("a.octal"), Some("A b"));
+        assert_eq!(resources.get("a.utf8"), Some("café"));
+    }
+
+    #[test]
+    fn continuation_lines() {
+        let resources = Resources::parse(concat!(
+            "URxvt.keysym.C-c: \\\n",
+            "    copy\n",
+            "! comment: skipped\n",
+            "#ifdef COLOR\n",
+            "URxvt.path: C:\\\\\n",
+            "URxvt.font: xft:mono\n",
+        ));
+        // The whitespace after the colon is skipped once the lines are joined.
+        assert_eq!(resources.get("URxvt.keysym.C-c"), Some("copy"));
+        assert_eq!(resources.get("URxvt.path"), Some("C:\\"));
+        assert_eq!(resources.get("URxvt.font"), Some("xft:mono"));
+        assert_eq!(resources.entries.len(), 3);
+    }
+
+    #[test]
+    fn bindings() {
+        let resources = Resources::parse(concat!(
+            "*background: black\n",
+            "URxvt.?.font: mono\n",
+            "Xft.dpi: 96\n",
+        ));
+        assert_eq!(resources.get("URxvt.background"), Some("black"));
+        assert_eq!(
+            resources.get("URxvt.vt.scrollBar.background"),
+            Some("black")
+        );
+        assert_eq!(resources.get("URxvt.vt.font"), Some("mono"));
+        assert_eq!(resources.get("URxvt.font"), None);
+        assert_eq!(resources.get("Xft.dpi.extra"), None);
+        assert_eq!(resources.dpi(), Some(96.0));
+    }
+
+    #[test]
+    fn precedence_of_patterns() {
+        let resources = Resources::parse(concat!(
+            "*dpi: 1\n",
+            "Xft*dpi: 2\n",
+            "*Xft.dpi: 3\n",
+            "?.dpi: 4\n",
+            "App*Xft*dpi: 5\n",
+            "App.Xft*dpi: 6\n",
+        ));
+        // A tight binding beats a loose one, which beats `?`, which beats
+        // skipping the component. The first components weigh most.
+        assert_eq!(resources.get("Xft.dpi"), Some("2"));
+        assert_eq!(resources.get("Other.dpi"), Some("4"));
+        assert_eq!(resources.get("App.Xft.dpi"), Some("6"));
+        assert_eq!(resources.get("App.Sub.Xft.dpi"), Some("5"));
+        assert_eq!(resources.get("Xft.Sub.dpi"), Some("2"));
+    }
+}
@@ -0,0 +1,232 @@
+//! The XTEST extension: synthetic input, as if it came from the devices.
+//!
+//! Faked events go through the server like real ones: they move the pointer,
+//! activate grabs and reach whichever window has the focus, which makes the
+//! extension the backend of automation tools.
+//!
+//! ```no_run
+//! use mousetrap::connection::Connection;
+//!
+//! let mut conn = Connection::init().unwrap();
+//! conn.xtest_fake_motion(100, 200, false).unwrap();
+//! conn.xtest_fake_button(1, true).unwrap();
+//! conn.xtest_fake_button(1, false).unwrap();
+//! conn.sync().unwrap();
+//! ```
+
+use crate::connection::Connection;
+use crate::cookie::{Cookie, Reply};
+use crate::errors::{ConnectionError, ParseError};
+use crate::event::code;
+use crate::extension::{Extension, Version};
+use crate::protocol::{KeyCode, Timestamp, Window, XId};
+use crate::request::RequestBuilder;
+use crate::utils::{check_trailing_bytes, deserialize_into, skip};
+
+/// Name of the extension, as given to QueryExtension.
+pub const NAME: &str = "XTEST";
+
+/// Version of the extension implemented by this module.
+pub const VERSION: (u8, u16) = (2, 2);
+
+/// Minor opcodes of the XTEST requests.
+pub mod opcode {
+    pub const GET_VERSION: u8 = 0;
+    pub const FAKE_INPUT: u8 = 2;
+    pub const GRAB_CONTROL: u8 = 3;
+}
+
+/// Names of the XTEST requests, indexed by minor opcode.
+pub const REQUEST_NAMES: &[&str] = &["GetVersion", "CompareCursor", "FakeInput", "GrabControl"];
+
+/// The extension defines no errors.
+pub const ERROR_NAMES: &[&str] = &[];
+
+/// Starts a request of the extension.
+fn request(conn: &mut Connection, minor_opcode: u8) -> Result<RequestBuilder, ConnectionError> {
+    let info = conn.require_extension(NAME, REQUEST_NAMES, ERROR_NAMES)?;
+    Ok(RequestBuilder::new(info.major_opcode, minor_opcode))
+}
+
+/// The XTEST extension, for [Connection::enable].
+pub struct XTest;
+
+impl Extension for XTest {
+    const NAME: &'static str = NAME;
+    const MIN_VERSION: Version = (2, 0);
+
+    fn negotiate(conn: &mut Connection) -> Result<Option<Version>, ConnectionError> {
+        let reply = conn.xtest_get_version(VERSION.0, VERSION.1)?.reply(conn)?;
+        Ok(Some((
+            reply.major_version.into(),
+            reply.minor_version.into(),
+        )))
+    }
+}
+
+/// Reply of the XTestGetVersion request.
+#[derive(Debug, Clone)]
+pub struct GetVersionReply {
+    pub major_version: u8,
+    pub minor_version: u16,
+}
+
+impl Reply for GetVersionReply {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1  CARD8                 major-version
+        // 2  CARD16                sequence number
+        // 4  0                     reply length
+        // 2  CARD16                minor-version
+        // 22                       unused
+        let rest = skip(bytes, 1)?;
+        let (major_version, rest) = deserialize_into::<u8>(rest)?;
+        let rest = skip(rest, 6)?;
+        let (minor_version, _) = deserialize_into::<u16>(rest)?;
+        Ok(GetVersionReply {
+            major_version,
+            minor_version,
+        })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        check_trailing_bytes("xtest::GetVersionReply", bytes, 32)
+    }
+}
+
+/// A synthetic event, see [Connection::xtest_fake_input].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FakeInput {
+    /// One of [code::KEY_PRESS], [code::KEY_RELEASE], [code::BUTTON_PRESS],
+    /// [code::BUTTON_RELEASE] or [code::MOTION_NOTIFY].
+    pub event_type: u8,
+    /// The keycode, the button, or 1 for a relative motion and 0 for an absolute one.
+    pub detail: u8,
+    /// How long the server waits before it processes the event, in milliseconds.
+    pub delay: Timestamp,
+    /// The root window the pointer moves to, 0 for the root it is on. Only used
+    /// by absolute motions.
+    pub root: Window,
+    pub root_x: i16,
+    pub root_y: i16,
+}
+
+impl FakeInput {
+    /// Returns an event without delay, on the current root window.
+    pub fn new(event_type: u8, detail: u8) -> FakeInput {
+        FakeInput {
+            event_type,
+            detail,
+            delay: 0,
+            root: XId(0),
+            root_x: 0,
+            root_y: 0,
+        }
+    }
+}
+
+impl Connection {
+    /// Tells the server the version of the extension the client supports and
+    /// returns the version the server supports.
+    pub fn xtest_get_version(
+        &mut self,
+        major_version: u8,
+        minor_version: u16,
+    ) -> Result<Cookie<GetVersionReply>, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (0)
+        //   2 bytes    Request length (2)
+        //   1 byte     Client major version
+        //   1 byte     Unused
+        //   2 bytes    Client minor version
+        let request = request(self, opcode::GET_VERSION)?
+            .put(major_version)
+            .pad(1)
+            .put(minor_version)
+            .finish();
+        self.send_request_with_reply(&request)
+    }
+
+    /// Sends a synthetic event through the input devices of the server.
+    pub fn xtest_fake_input(&mut self, input: &FakeInput) -> Result<u16, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (2)
+        //   2 bytes    Request length (9)
+        //   1 byte     Type
+        //   1 byte     Detail
+        //   2 bytes    Unused
+        //   4 bytes    Delay
+        //   4 bytes    Root
+        //   8 bytes    Unused
+        //   2 bytes    Root x
+        //   2 bytes    Root y
+        //   7 bytes    Unused
+        //   1 byte     Device (core devices only)
+        let request = request(self, opcode::FAKE_INPUT)?
+            .put(input.event_type)
+            .put(input.detail)
+            .pad(2)
+            .put(input.delay)
+            .put(input.root)
+            .pad(8)
+            .put(input.root_x)
+            .put(input.root_y)
+            .pad(8)
+            .finish();
+        self.send_request(&request)
+    }
+
+    /// Presses or releases a key.
+    pub fn xtest_fake_key(
+        &mut self,
+        keycode: KeyCode,
+        press: bool,
+    ) -> Result<u16, ConnectionError> {
+        let event_type = if press {
+            code::KEY_PRESS
+        } else {
+            code::KEY_RELEASE
+        };
+        self.xtest_fake_input(&FakeInput::new(event_type, keycode))
+    }
+
+    /// Presses or releases a pointer button.
+    pub fn xtest_fake_button(&mut self, button: u8, press: bool) -> Result<u16, ConnectionError> {
+        let event_type = if press {
+            code::BUTTON_PRESS
+        } else {
+            code::BUTTON_RELEASE
+        };
+        self.xtest_fake_input(&FakeInput::new(event_type, button))
+    }
+
+    /// Moves the pointer to a position of the root window it is on, or by an
+    /// offset if `relative` is set.
+    pub fn xtest_fake_motion(
+        &mut self,
+        x: i16,
+        y: i16,
+        relative: bool,
+    ) -> Result<u16, ConnectionError> {
+        self.xtest_fake_input(&FakeInput {
+            root_x: x,
+            root_y: y,
+            ..FakeInput::new(code::MOTION_NOTIFY, relative as u8)
+        })
+    }
+
+    /// Makes the connection immune to server grabs when `impervious` is set, so
+    /// that faked input keeps flowing while another client grabs the server.
+    pub fn xtest_grab_control(&mut self, impervious: bool) -> Result<u16, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (3)
+        //   2 bytes    Request length (2)
+        //   1 byte     Impervious
+        //   3 bytes    Unused
+        let request = request(self, opcode::GRAB_CONTROL)?
+            .put(impervious as u8)
+            .pad(3)
+            .finish();
+        self.send_request(&request)
+    }
+}
@@ -0,0 +1,478 @@
+//! The XVideo extension: video and image output through the adaptors of the
+//! graphics card.
+//!
+//! An adaptor has a range of ports, each of which scales and converts images in
+//! YUV formats such as YUY2 or I420 while drawing them, which is how video was
+//! shown before compositing.
+//!
+//! ```no_run
+//! use mousetrap::connection::Connection;
+//! use mousetrap::protocol::XId;
+//! use mousetrap::xv::{self, AdaptorType, PutImage};
+//!
+//! let mut conn = Connection::init().unwrap();
+//! let (window, gc) = (XId(0x1200001), XId(0x1200002));
+//! let adaptors = conn.xv_query_adaptors(window).unwrap().reply(&mut conn).unwrap();
+//! let adaptor = adaptors
+//!     .adaptors
+//!     .iter()
+//!     .find(|adaptor| adaptor.adaptor_type.contains(AdaptorType::IMAGE))
+//!     .unwrap();
+//! let frame = vec![0x80; 320 * 240 * 2];
+//! let image = PutImage::new(adaptor.base_id, window, gc, xv::fourcc(*b"YUY2"), 320, 240);
+//! conn.xv_put_image(&image, &frame).unwrap();
+//! conn.sync().unwrap();
+//! ```
+
+use crate::connection::Connection;
+use crate::cookie::{Cookie, Reply};
+use crate::errors::{ConnectionError, ParseError, SerializeError};
+use crate::extension::{Extension, Version};
+use crate::protocol::{VisualId, Window, XId, XString};
+use crate::request::RequestBuilder;
+use crate::utils::{
+    check_trailing_bytes, deserialize_into, deserialize_into_string, deserialize_into_vec, padding,
+    skip,
+};
+
+/// Name of the extension, as given to QueryExtension.
+pub const NAME: &str = "XVideo";
+
+/// Version of the extension implemented by this module.
+pub const VERSION: (u16, u16) = (2, 2);
+
+/// Minor opcodes of the XVideo requests.
+pub mod opcode {
+    pub const QUERY_EXTENSION: u8 = 0;
+    pub const QUERY_ADAPTORS: u8 = 1;
+    pub const QUERY_PORT_ATTRIBUTES: u8 = 15;
+    pub const PUT_IMAGE: u8 = 18;
+}
+
+/// Names of the XVideo requests, indexed by minor opcode.
+pub const REQUEST_NAMES: &[&str] = &[
+    "QueryExtension",
+    "QueryAdaptors",
+    "QueryEncodings",
+    "GrabPort",
+    "UngrabPort",
+    "PutVideo",
+    "PutStill",
+    "GetVideo",
+    "GetStill",
+    "StopVideo",
+    "SelectVideoNotify",
+    "SelectPortNotify",
+    "QueryBestSize",
+    "SetPortAttribute",
+    "GetPortAttribute",
+    "QueryPortAttributes",
+    "ListImageFormats",
+    "QueryImageAttributes",
+    "PutImage",
+    "ShmPutImage",
+];
+
+/// Names of the XVideo errors, indexed by error code.
+pub const ERROR_NAMES: &[&str] = &["BadPort", "BadEncoding", "BadControl"];
+
+/// Starts a request of the extension.
+fn request(conn: &mut Connection, minor_opcode: u8) -> Result<RequestBuilder, ConnectionError> {
+    let info = conn.require_extension(NAME, REQUEST_NAMES, ERROR_NAMES)?;
+    Ok(RequestBuilder::new(info.major_opcode, minor_opcode))
+}
+
+/// The XVideo extension, for [Connection::enable].
+pub struct Xv;
+
+impl Extension for Xv {
+    const NAME: &'static str = NAME;
+    const MIN_VERSION: Version = (2, 0);
+
+    fn negotiate(conn: &mut Connection) -> Result<Option<Version>, ConnectionError> {
+        let reply = conn.xv_query_extension()?.reply(conn)?;
+        Ok(Some((
+            reply.major_version.into(),
+            reply.minor_version.into(),
+        )))
+    }
+}
+
+/// Returns the id of an image format from its four character code, e.g. `YUY2`.
+pub const fn fourcc(code: [u8; 4]) -> u32 {
+    u32::from_le_bytes(code)
+}
+
+/// What an adaptor does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct AdaptorType(pub u8);
+
+impl AdaptorType {
+    pub const INPUT: Self = Self(1);
+    pub const OUTPUT: Self = Self(2);
+    pub const VIDEO: Self = Self(4);
+    pub const STILL: Self = Self(8);
+    /// The ports of the adaptor take images, see [Connection::xv_put_image].
+    pub const IMAGE: Self = Self(16);
+
+    /// Whether all the bits of `other` are set.
+    pub fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+/// Flags of a port attribute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct AttributeFlags(pub u32);
+
+impl AttributeFlags {
+    pub const GETTABLE: Self = Self(1);
+    pub const SETTABLE: Self = Self(2);
+
+    /// Whether all the bits of `other` are set.
+    pub fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+/// Reply of the XvQueryExtension request.
+#[derive(Debug, Clone)]
+pub struct QueryExtensionReply {
+    pub major_version: u16,
+    pub minor_version: u16,
+}
+
+impl Reply for QueryExtensionReply {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1                        unused
+        // 2  CARD16                sequence number
+        // 4  0                     reply length
+        // 2  CARD16                major
+        // 2  CARD16                minor
+        // 20                       unused
+        let rest = skip(bytes, 8)?;
+        let (major_version, rest) = deserialize_into::<u16>(rest)?;
+        let (minor_version, _) = deserialize_into::<u16>(rest)?;
+        Ok(QueryExtensionReply {
+            major_version,
+            minor_version,
+        })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        check_trailing_bytes("xv::QueryExtensionReply", bytes, 32)
+    }
+}
+
+/// A visual and depth the ports of an adaptor can draw to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Format {
+    pub visual: VisualId,
+    pub depth: u8,
+}
+
+/// An adaptor and its ports.
+#[derive(Debug, Clone)]
+pub struct AdaptorInfo {
+    /// The first port of the adaptor.
+    pub base_id: XId,
+    /// The number of ports, whose ids follow `base_id`.
+    pub num_ports: u16,
+    pub adaptor_type: AdaptorType,
+    pub name: XString,
+    pub formats: Vec<Format>,
+}
+
+impl AdaptorInfo {
+    /// Returns the ids of the ports of the adaptor.
+    pub fn ports(&self) -> impl Iterator<Item = XId> {
+        let base = self.base_id.0;
+        (0..u32::from(self.num_ports)).map(move |i| XId(base + i))
+    }
+}
+
+/// Reply of the XvQueryAdaptors request.
+#[derive(Debug, Clone)]
+pub struct QueryAdaptorsReply {
+    pub adaptors: Vec<AdaptorInfo>,
+}
+
+impl Reply for QueryAdaptorsReply {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1                        unused
+        // 2  CARD16                sequence number
+        // 4  n                     reply length
+        // 2  CARD16                number of adaptors
+        // 22                       unused
+        // 4n LISTofADAPTORINFO     adaptors
+        //
+        // ADAPTORINFO
+        // 4  PORT                  base-id
+        // 2  m                     name length
+        // 2  CARD16                number of ports
+        // 2  f                     number of formats
+        // 1  CARD8                 type
+        // 1                        unused
+        // m  STRING8               name
+        // p                        unused, p=pad(m)
+        // 8f LISTofFORMAT          formats
+        //
+        // FORMAT
+        // 4  VISUALID              visual
+        // 1  CARD8                 depth
+        // 3                        unused
+        let rest = skip(bytes, 8)?;
+        let (count, rest) = deserialize_into::<u16>(rest)?;
+        let mut rest = skip(rest, 22)?;
+        let mut adaptors = Vec::new();
+        for _ in 0..count {
+            let (base_id, next) = deserialize_into::<XId>(rest)?;
+            let (name_length, next) = deserialize_into::<u16>(next)?;
+            let (num_ports, next) = deserialize_into::<u16>(next)?;
+            let (num_formats, next) = deserialize_into::<u16>(next)?;
+            let (adaptor_type, next) = deserialize_into::<u8>(next)?;
+            let next = skip(next, 1)?;
+            let (name, next) = deserialize_into_string(next, name_length)?;
+            let mut next = skip(next, padding(name_length.into(), 4))?;
+            let mut formats = Vec::new();
+            for _ in 0..num_formats {
+                let (visual, format) = deserialize_into::<VisualId>(next)?;
+                let (depth, format) = deserialize_into::<u8>(format)?;
+                formats.push(Format { visual, depth });
+                next = skip(format, 3)?;
+            }
+            adaptors.push(AdaptorInfo {
+                base_id,
+                num_ports,
+                adaptor_type: AdaptorType(adaptor_type),
+                name,
+                formats,
+            });
+            rest = next;
+        }
+        Ok(QueryAdaptorsReply { adaptors })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        let length = 32
+            + self
+                .adaptors
+                .iter()
+                .map(|adaptor| {
+                    let name = adaptor.name.len();
+                    12 + name + padding(name, 4) + 8 * adaptor.formats.len()
+                })
+                .sum::<usize>();
+        check_trailing_bytes("xv::QueryAdaptorsReply", bytes, length)
+    }
+}
+
+/// An attribute of a port, such as `XV_BRIGHTNESS` or `XV_COLORKEY`.
+#[derive(Debug, Clone)]
+pub struct AttributeInfo {
+    pub flags: AttributeFlags,
+    pub min: i32,
+    pub max: i32,
+    /// The name of the attribute, an atom name, without its terminating NUL.
+    pub name: XString,
+}
+
+/// Reply of the XvQueryPortAttributes request.
+#[derive(Debug, Clone)]
+pub struct QueryPortAttributesReply {
+    pub attributes: Vec<AttributeInfo>,
+}
+
+impl Reply for QueryPortAttributesReply {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        // 1  1                     Reply
+        // 1                        unused
+        // 2  CARD16                sequence number
+        // 4  n                     reply length
+        // 4  CARD32                number of attributes
+        // 4  CARD32                total size of the names
+        // 16                       unused
+        // 4n LISTofATTRIBUTEINFO   attributes
+        //
+        // ATTRIBUTEINFO
+        // 4  CARD32                flags
+        // 4  INT32                 min
+        // 4  INT32                 max
+        // 4  m                     name length, with the terminating NUL
+        // m  STRING8               name
+        // p                        unused, p=pad(m)
+        let rest = skip(bytes, 8)?;
+        let (count, rest) = deserialize_into::<u32>(rest)?;
+        let mut rest = skip(rest, 20)?;
+        let mut attributes = Vec::new();
+        for _ in 0..count {
+            let (flags, next) = deserialize_into::<u32>(rest)?;
+            let (min, next) = deserialize_into::<i32>(next)?;
+            let (max, next) = deserialize_into::<i32>(next)?;
+            let (size, next) = deserialize_into::<u32>(next)?;
+            let (mut name, next) = deserialize_into_vec::<u8>(next, size as usize)?;
+            while name.last() == Some(&0) {
+                name.pop();
+            }
+            attributes.push(AttributeInfo {
+                flags: AttributeFlags(flags),
+                min,
+                max,
+                name: XString::from(name),
+            });
+            rest = skip(next, padding(size as usize, 4))?;
+        }
+        Ok(QueryPortAttributesReply { attributes })
+    }
+
+    fn validate(&self, bytes: &[u8]) -> Result<(), ParseError> {
+        let length = 32
+            + self
+                .attributes
+                .iter()
+                .map(|attribute| {
+                    let size = attribute.name.len() + 1;
+                    16 + size + padding(size, 4)
+                })
+                .sum::<usize>();
+        check_trailing_bytes("xv::QueryPortAttributesReply", bytes, length)
+    }
+}
+
+/// Draws an image through a port, scaling the source area to the destination
+/// area.
+#[derive(Debug, Clone)]
+pub struct PutImage {
+    pub port: XId,
+    pub drawable: XId,
+    pub gc: XId,
+    /// The format of the image, see [fourcc].
+    pub id: u32,
+    /// The part of the image to draw.
+    pub src_x: i16,
+    pub src_y: i16,
+    pub src_width: u16,
+    pub src_height: u16,
+    /// Where to draw it in the drawable.
+    pub dst_x: i16,
+    pub dst_y: i16,
+    pub dst_width: u16,
+    pub dst_height: u16,
+    /// Size of the whole image.
+    pub width: u16,
+    pub height: u16,
+}
+
+impl PutImage {
+    /// Returns a request drawing the whole image at the top left corner of the
+    /// drawable, without scaling.
+    pub fn new(port: XId, drawable: XId, gc: XId, id: u32, width: u16, height: u16) -> Self {
+        PutImage {
+            port,
+            drawable,
+            gc,
+            id,
+            src_x: 0,
+            src_y: 0,
+            src_width: width,
+            src_height: height,
+            dst_x: 0,
+            dst_y: 0,
+            dst_width: width,
+            dst_height: height,
+            width,
+            height,
+        }
+    }
+}
+
+impl Connection {
+    /// Returns the version of the extension the server supports.
+    pub fn xv_query_extension(&mut self) -> Result<Cookie<QueryExtensionReply>, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (0)
+        //   2 bytes    Request length (1)
+        let request = request(self, opcode::QUERY_EXTENSION)?.finish();
+        self.send_request_with_reply(&request)
+    }
+
+    /// Returns the adaptors of the screen of `window`.
+    pub fn xv_query_adaptors(
+        &mut self,
+        window: Window,
+    ) -> Result<Cookie<QueryAdaptorsReply>, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (1)
+        //   2 bytes    Request length (2)
+        //   4 bytes    Window
+        let request = request(self, opcode::QUERY_ADAPTORS)?.put(window).finish();
+        self.send_request_with_reply(&request)
+    }
+
+    /// Returns the attributes of a port.
+    pub fn xv_query_port_attributes(
+        &mut self,
+        port: XId,
+    ) -> Result<Cookie<QueryPortAttributesReply>, ConnectionError> {
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (15)
+        //   2 bytes    Request length (2)
+        //   4 bytes    Port
+        let request = request(self, opcode::QUERY_PORT_ATTRIBUTES)?
+            .put(port)
+            .finish();
+        self.send_request_with_reply(&request)
+    }
+
+    /// Draws an image through a port. `data` holds the whole image, laid out as
+    /// given by XvQueryImageAttributes for its format and size.
+    pub fn xv_put_image(&mut self, image: &PutImage, data: &[u8]) -> Result<u16, ConnectionError> {
+        let max = usize::from(u16::MAX) * 4 - 40;
+        if data.len() > max {
+            return Err(SerializeError::TooLong {
+                field: "image data",
+                length: data.len(),
+                max,
+            }
+            .into());
+        }
+        //   1 byte     Major opcode
+        //   1 byte     Minor opcode (18)
+        //   2 bytes    Request length (10+(n+p)/4)
+        //   4 bytes    Port
+        //   4 bytes    Drawable
+        //   4 bytes    GC
+        //   4 bytes    Image format id
+        //   2 bytes    Source x
+        //   2 bytes    Source y
+        //   2 bytes    Source width
+        //   2 bytes    Source height
+        //   2 bytes    Destination x
+        //   2 bytes    Destination y
+        //   2 bytes    Destination width
+        //   2 bytes    Destination height
+        //   2 bytes    Width
+        //   2 bytes    Height
+        //   n+p bytes  Data, padded
+        let request = request(self, opcode::PUT_IMAGE)?
+            .put(image.port)
+            .put(image.drawable)
+            .put(image.gc)
+            .put(image.id)
+            .put(image.src_x)
+            .put(image.src_y)
+            .put(image.src_width)
+            .put(image.src_height)
+            .put(image.dst_x)
+            .put(image.dst_y)
+            .put(image.dst_width)
+            .put(image.dst_height)
+            .put(image.width)
+            .put(image.height)
+            .put_bytes(data)
+            .finish();
+        self.send_request(&request)
+    }
+}
@@ -0,0 +1,368 @@
+//! Selections against [MockServer], the test playing the other client: contents
+//! larger than a request are reassembled from the chunks of an INCR transfer,
+//! owners which stop answering time out, and large contents are served in
+//! chunks until the requestor is destroyed or stops asking for them.
+
+use mousetrap::clipboard::{Clipboard, Selection};
+use mousetrap::connection::Connection;
+use mousetrap::errors::ConnectionError;
+use mousetrap::request::opcode;
+use mousetrap::testing::{MockServer, ReceivedRequest, Response};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The atoms the server interns, by name. Other names get None.
+const ATOMS: &[(&str, u32)] = &[
+    ("CLIPBOARD", 0x150),
+    ("TARGETS", 0x151),
+    ("INCR", 0x152),
+    ("TIMESTAMP", 0x153),
+    ("UTF8_STRING", 0x154),
+    ("_MOUSETRAP_SELECTION", 0x155),
+    ("_MOUSETRAP_TIMESTAMP", 0x156),
+];
+
+const CLIPBOARD: u32 = 0x150;
+const INCR: u32 = 0x152;
+const UTF8_STRING: u32 = 0x154;
+
+/// A window and property of another client, requesting the selection.
+const REQUESTOR: u32 = 0x0060_0001;
+const REQUESTOR_PROPERTY: u32 = 0x160;
+
+/// Larger than the chunks of an INCR transfer, and not a multiple of them.
+const LARGE: usize = 200_000;
+
+/// The values of the property, in the order GetProperty returns them.
+type Values = Arc<Mutex<VecDeque<(u32, u8, Vec<u8>)>>>;
+
+/// Starts a server interning the [ATOMS], answering GetProperty with the next
+/// of `values` and keeping the owner of the selections.
+fn server(values: Values) -> MockServer {
+    let owner = Arc::new(AtomicU32::new(0));
+    let set_owner = Arc::clone(&owner);
+    MockServer::builder()
+        .on(opcode::INTERN_ATOM, |request| {
+            let length = usize::from(u16::from_ne_bytes([request.bytes[4], request.bytes[5]]));
+            let name = &request.bytes[8..8 + length];
+            let atom = ATOMS
+                .iter()
+                .find(|(atom_name, _)| atom_name.as_bytes() == name)
+                .map_or(0, |&(_, atom)| atom);
+            Response::Reply {
+                data: 0,
+                body: atom.to_ne_bytes().to_vec(),
+            }
+        })
+        .on(opcode::GET_PROPERTY, move |_| {
+            // A property which does not exist once the values run out.
+            let (type_, format, value) = values.lock().unwrap().pop_front().unwrap_or_default();
+            let units = value.len() / usize::from(format.max(8) / 8);
+            let mut body = type_.to_ne_bytes().to_vec();
+            body.extend_from_slice(&0u32.to_ne_bytes());
+            body.extend_from_slice(&(units as u32).to_ne_bytes());
+            body.resize(24, 0);
+            body.extend_from_slice(&value);
+            Response::Reply { data: format, body }
+        })
+        .on(opcode::SET_SELECTION_OWNER, move |request| {
+            set_owner.store(word(request, 4), Ordering::Relaxed);
+            Response::None
+        })
+        .on(opcode::GET_SELECTION_OWNER, move |_| Response::Reply {
+            data: 0,
+            body: owner.load(Ordering::Relaxed).to_ne_bytes().to_vec(),
+        })
+        .start()
+        .unwrap()
+}
+
+fn word(request: &ReceivedRequest, offset: usize) -> u32 {
+    u32::from_ne_bytes(request.bytes[offset..offset + 4].try_into().unwrap())
+}
+
+/// Returns the contents of [LARGE] bytes.
+fn large_value() -> Vec<u8> {
+    (0..LARGE).map(|i| (i % 251) as u8).collect()
+}
+
+/// Sends the SelectionNotify answering a ConvertSelection, with the property
+/// the contents were written to.
+fn send_selection_notify(server: &MockServer, request: &ReceivedRequest) {
+    // SelectionNotify: code, unused, sequence, time, requestor, selection,
+    // target, property.
+    let mut event = [0u8; 32];
+    event[0] = 31;
+    for (field, offset) in [(8, 4), (12, 8), (16, 12), (20, 16)] {
+        event[field..field + 4].copy_from_slice(&word(request, offset).to_ne_bytes());
+    }
+    server.send_event(event).unwrap();
+}
+
+/// Sends a PropertyNotify, of a new value (state 0) or of a deletion (state 1).
+fn send_property_notify(server: &MockServer, window: u32, atom: u32, state: u8) {
+    // PropertyNotify: code, unused, sequence, window, atom, time, state.
+    let mut event = [0u8; 32];
+    event[0] = 28;
+    event[4..8].copy_from_slice(&window.to_ne_bytes());
+    event[8..12].copy_from_slice(&atom.to_ne_bytes());
+    event[12..16].copy_from_slice(&1000u32.to_ne_bytes());
+    event[16] = state;
+    server.send_event(event).unwrap();
+}
+
+#[test]
+fn convert_reassembles_incr_transfers() {
+    let value = large_value();
+    let chunks: Vec<_> = value.chunks(65536).map(<[u8]>::to_vec).collect();
+    let mut values = VecDeque::from([(INCR, 32, (LARGE as u32).to_ne_bytes().to_vec())]);
+    values.extend(chunks.iter().map(|chunk| (UTF8_STRING, 8, chunk.clone())));
+    // The empty chunk ending the transfer.
+    values.push_back((UTF8_STRING, 8, Vec::new()));
+    let server = server(Arc::new(Mutex::new(values)));
+    let mut conn = server.connect().unwrap();
+    let clipboard = Clipboard::new(&mut conn).unwrap();
+
+    let data = thread::scope(|scope| {
+        scope.spawn(|| {
+            let request = server
+                .wait_for_request(opcode::CONVERT_SELECTION, TIMEOUT)
+                .unwrap();
+            let (window, property) = (word(&request, 4), word(&request, 16));
+            // The INCR property written before the notification.
+            send_property_notify(&server, window, property, 0);
+            send_selection_notify(&server, &request);
+            // Each chunk once the requestor deleted the previous one.
+            for read in 1..=chunks.len() + 1 {
+                server
+                    .wait_for_requests(opcode::GET_PROPERTY, read, TIMEOUT)
+                    .unwrap();
+                send_property_notify(&server, window, property, 1);
+                send_property_notify(&server, window, property, 0);
+            }
+        });
+        clipboard.convert(&mut conn, CLIPBOARD, UTF8_STRING)
+    });
+    let data = data.unwrap().unwrap();
+    assert_eq!(data.type_, UTF8_STRING);
+    assert_eq!(data.format, 8);
+    assert_eq!(data.data.len(), LARGE);
+    assert!(data.data == value);
+
+    // The INCR property and each chunk deleted as they are read.
+    let reads = server
+        .wait_for_requests(opcode::GET_PROPERTY, 1, TIMEOUT)
+        .unwrap();
+    assert_eq!(reads.len(), chunks.len() + 2);
+    assert!(reads.iter().all(|request| request.data == 1));
+}
+
+#[test]
+fn convert_times_out() {
+    let server = server(Arc::default());
+    let mut conn = server.connect().unwrap();
+    let mut clipboard = Clipboard::new(&mut conn).unwrap();
+    clipboard.set_timeout(Duration::from_millis(100));
+    // No SelectionNotify.
+    assert!(matches!(
+        clipboard.convert(&mut conn, CLIPBOARD, UTF8_STRING),
+        Err(ConnectionError::Timeout)
+    ));
+}
+
+#[test]
+fn convert_times_out_when_a_transfer_stalls() {
+    let values = VecDeque::from([
+        (INCR, 32, (LARGE as u32).to_ne_bytes().to_vec()),
+        (UTF8_STRING, 8, vec![7; 65536]),
+    ]);
+    let server = server(Arc::new(Mutex::new(values)));
+    let mut conn = server.connect().unwrap();
+    let mut clipboard = Clipboard::new(&mut conn).unwrap();
+    clipboard.set_timeout(Duration::from_millis(100));
+
+    let result = thread::scope(|scope| {
+        scope.spawn(|| {
+            let request = server
+                .wait_for_request(opcode::CONVERT_SELECTION, TIMEOUT)
+                .unwrap();
+            let (window, property) = (word(&request, 4), word(&request, 16));
+            send_selection_notify(&server, &request);
+            // The first chunk, then nothing.
+            server
+                .wait_for_request(opcode::GET_PROPERTY, TIMEOUT)
+                .unwrap();
+            send_property_notify(&server, window, property, 0);
+        });
+        clipboard.convert(&mut conn, CLIPBOARD, UTF8_STRING)
+    });
+    assert!(matches!(result, Err(ConnectionError::Timeout)));
+    assert_eq!(
+        server
+            .wait_for_requests(opcode::GET_PROPERTY, 2, TIMEOUT)
+            .unwrap()
+            .len(),
+        2
+    );
+}
+
+/// Owns the clipboard with `text`, answering the ChangeProperty through which
+/// the server time is found.
+fn set_text(server: &MockServer, conn: &mut Connection, clipboard: &mut Clipboard, text: &str) {
+    let owned = thread::scope(|scope| {
+        scope.spawn(|| {
+            let request = server
+                .wait_for_request(opcode::CHANGE_PROPERTY, TIMEOUT)
+                .unwrap();
+            send_property_notify(server, word(&request, 4), word(&request, 8), 0);
+        });
+        clipboard.set_text(conn, Selection::Clipboard, text)
+    });
+    assert!(owned.unwrap());
+}
+
+/// Sends the SelectionRequest of [REQUESTOR] for the clipboard as UTF8_STRING.
+fn send_selection_request(server: &MockServer, owner: u32) {
+    // SelectionRequest: code, unused, sequence, time, owner, requestor,
+    // selection, target, property.
+    let mut event = [0u8; 32];
+    event[0] = 30;
+    for (offset, value) in [
+        (4, 0),
+        (8, owner),
+        (12, REQUESTOR),
+        (16, CLIPBOARD),
+        (20, UTF8_STRING),
+        (24, REQUESTOR_PROPERTY),
+    ] {
+        event[offset..offset + 4].copy_from_slice(&value.to_ne_bytes());
+    }
+    server.send_event(event).unwrap();
+}
+
+/// Returns the ChangeProperty requests writing the property of [REQUESTOR].
+fn requestor_writes(server: &MockServer) -> Vec<ReceivedRequest> {
+    server
+        .requests()
+        .into_iter()
+        .filter(|request| {
+            request.major_opcode == opcode::CHANGE_PROPERTY
+                && word(request, 4) == REQUESTOR
+                && word(request, 8) == REQUESTOR_PROPERTY
+        })
+        .collect()
+}
+
+#[test]
+fn large_contents_are_served_in_chunks() {
+    let server = server(Arc::default());
+    let mut conn = server.connect().unwrap();
+    let mut clipboard = Clipboard::new(&mut conn).unwrap();
+    let text = String::from_utf8(large_value().iter().map(|b| b'a' + b % 26).collect()).unwrap();
+    set_text(&server, &mut conn, &mut clipboard, &text);
+
+    send_selection_request(&server, clipboard.window().0);
+    let event = conn.wait_for_event().unwrap();
+    assert!(clipboard.handle_event(&mut conn, &event).unwrap());
+    // The requestor deleting the property after reading each chunk.
+    let mut deletions = 0;
+    loop {
+        conn.sync().unwrap();
+        let writes = requestor_writes(&server);
+        if writes
+            .last()
+            .is_some_and(|request| request.bytes.len() == 24)
+        {
+            break;
+        }
+        assert!(deletions <= LARGE / 65536 + 1, "the transfer does not end");
+        send_property_notify(&server, REQUESTOR, REQUESTOR_PROPERTY, 1);
+        let event = conn.wait_for_event().unwrap();
+        assert!(clipboard.handle_event(&mut conn, &event).unwrap());
+        deletions += 1;
+    }
+
+    // ChangeProperty: mode, window, property, type, format, length, data.
+    let writes = requestor_writes(&server);
+    assert_eq!(word(&writes[0], 12), INCR);
+    assert_eq!(word(&writes[0], 24), LARGE as u32);
+    let mut served = Vec::new();
+    for request in &writes[1..] {
+        assert_eq!(word(request, 12), UTF8_STRING);
+        assert_eq!(request.bytes[16], 8);
+        let length = word(request, 20) as usize;
+        assert!(length <= 65536);
+        served.extend_from_slice(&request.bytes[24..24 + length]);
+    }
+    assert_eq!(writes.len(), LARGE.div_ceil(65536) + 2);
+    assert!(served == text.as_bytes());
+    // The requestor is told of the INCR transfer, and the transfer is over.
+    let notify = server
+        .wait_for_request(opcode::SEND_EVENT, TIMEOUT)
+        .unwrap();
+    assert_eq!(notify.bytes[12], 31);
+    assert!(clipboard.owns(Selection::Clipboard));
+}
+
+/// Owns the clipboard with large contents, starts serving them to [REQUESTOR]
+/// and gives the clipboard up, leaving only the transfer to serve.
+fn start_transfer(server: &MockServer, conn: &mut Connection, clipboard: &mut Clipboard) {
+    let text = "a".repeat(LARGE);
+    set_text(server, conn, clipboard, &text);
+    send_selection_request(server, clipboard.window().0);
+    assert!(clipboard.serve_event(conn).unwrap().is_none());
+    clipboard.release(conn, Selection::Clipboard).unwrap();
+    assert!(clipboard.is_serving());
+}
+
+/// Asserts that the last events selected on [REQUESTOR] are none.
+fn assert_requestor_released(server: &MockServer, conn: &mut Connection) {
+    conn.sync().unwrap();
+    // ChangeWindowAttributes: window, value mask, event mask.
+    let selected = server
+        .requests()
+        .into_iter()
+        .rev()
+        .find(|request| {
+            request.major_opcode == opcode::CHANGE_WINDOW_ATTRIBUTES
+                && word(request, 4) == REQUESTOR
+        })
+        .unwrap();
+    assert_eq!(word(&selected, 12), 0);
+}
+
+#[test]
+fn transfers_to_destroyed_windows_are_dropped() {
+    let server = server(Arc::default());
+    let mut conn = server.connect().unwrap();
+    let mut clipboard = Clipboard::new(&mut conn).unwrap();
+    start_transfer(&server, &mut conn, &mut clipboard);
+
+    // DestroyNotify: code, unused, sequence, event, window.
+    let mut event = [0u8; 32];
+    event[0] = 17;
+    event[4..8].copy_from_slice(&REQUESTOR.to_ne_bytes());
+    event[8..12].copy_from_slice(&REQUESTOR.to_ne_bytes());
+    server.send_event(event).unwrap();
+    assert!(clipboard.serve_event(&mut conn).unwrap().is_none());
+    assert!(!clipboard.is_serving());
+}
+
+#[test]
+fn stalled_transfers_time_out() {
+    let server = server(Arc::default());
+    let mut conn = server.connect().unwrap();
+    let mut clipboard = Clipboard::new(&mut conn).unwrap();
+    clipboard.set_timeout(Duration::from_millis(100));
+    start_transfer(&server, &mut conn, &mut clipboard);
+
+    // The requestor never deletes the INCR property.
+    assert!(clipboard.serve_event(&mut conn).unwrap().is_none());
+    assert!(!clipboard.is_serving());
+    assert_requestor_released(&server, &mut conn);
+}
@@ -0,0 +1,176 @@
+//! Replays the captures of `tests/corpus/` and checks what the parsers make of
+//! them against the values the server sent.
+//!
+//! A capture is the file written by `Connection::init_recording`. To add one,
+//! record a session against the server with the ignored `record` test, which
+//! makes the requests the tests replay:
+//!
+//! ```text
+//! DISPLAY=:1 MOUSETRAP_CAPTURE=xvfb-two-screens cargo test --test corpus -- --ignored record
+//! ```
+//!
+//! then add a test asserting on the replies, the golden values being those the
+//! server is known to send (its setup, `xdpyinfo`, `xwininfo -root`). The first chunk the
+//! client sent holds the authorization cookie: record with `XAUTHORITY`
+//! pointing to an empty file against a server started without access control
+//! (`Xvfb :1 -ac`), or zero the cookie before adding the capture.
+//!
+//! Captures keep the byte order of the machine that recorded them, the client
+//! having announced it in the setup. All of them are little-endian.
+//!
+//! | Capture                  | Server                                         |
+//! |--------------------------|------------------------------------------------|
+//! | `mock-two-screens.mtr`   | `mousetrap::testing::MockServer`, two screens |
+//! |                          | and a depth 30 pixmap format (synthetic)       |
+//! | `mock-depth-30.mtr`      | `mousetrap::testing::MockServer`, a depth 30   |
+//! |                          | root window and an image of it (synthetic)     |
+//!
+//! The corpus does not hold any capture of a real server yet, so it does not
+//! validate the parsers against real-world encodings: the synthetic captures
+//! only check that the parsers read back what `MockServer` encodes. Captures of
+//! Xorg, Xvfb (`-screen 0 1920x1080x24 -screen 1 1280x1024x30`), Xephyr and
+//! Xwayland are still to be recorded, each with its own test.
+#![cfg(target_endian = "little")]
+
+use mousetrap::capture::Capturer;
+use mousetrap::connection::Connection;
+use mousetrap::protocol::{Rectangle, XId};
+use std::env;
+use std::path::PathBuf;
+
+fn path(name: &str) -> PathBuf {
+    [env!("CARGO_MANIFEST_DIR"), "tests", "corpus", name]
+        .iter()
+        .collect()
+}
+
+fn replay(name: &str) -> Connection {
+    Connection::init_replay(path(name)).unwrap()
+}
+
+/// Records `tests/corpus/$MOUSETRAP_CAPTURE.mtr` against the server of `DISPLAY`.
+#[test]
+#[ignore = "records a capture from the server of DISPLAY"]
+fn record() {
+    let name = env::var("MOUSETRAP_CAPTURE").expect("MOUSETRAP_CAPTURE is not set");
+    let mut conn = Connection::init_recording(path(&format!("{}.mtr", name))).unwrap();
+    let root = conn.default_root();
+    conn.get_geometry(root).unwrap().reply(&mut conn).unwrap();
+    conn.intern_atom(false, "_NET_WM_NAME")
+        .unwrap()
+        .reply(&mut conn)
+        .unwrap();
+    let mut capturer = Capturer::new(&mut conn, false).unwrap();
+    let area = Rectangle {
+        x: 0,
+        y: 0,
+        width: 5,
+        height: 1,
+    };
+    capturer.capture_area(&mut conn, area).unwrap();
+    conn.sync().unwrap();
+}
+
+#[test]
+fn mock_two_screens() {
+    let mut conn = replay("mock-two-screens.mtr");
+    let setup = conn.setup();
+    assert_eq!(setup.vendor.to_string(), "mousetrap mock");
+    assert_eq!(
+        (setup.protocol_major_version, setup.protocol_minor_version),
+        (11, 0)
+    );
+    assert_eq!(setup.resource_id_base, 0x0040_0000);
+    assert_eq!(setup.resource_id_mask, 0x001f_ffff);
+    assert_eq!(setup.maximum_request_length, 0xffff);
+    assert_eq!((setup.min_keycode, setup.max_keycode), (8, 255));
+
+    let formats: Vec<_> = setup
+        .pixmap_formats
+        .iter()
+        .map(|format| (format.depth, format.bits_per_pixel, format.scanline_pad))
+        .collect();
+    assert_eq!(
+        formats,
+        [(1, 1, 32), (24, 32, 32), (30, 32, 32), (32, 32, 32)]
+    );
+
+    let screens: Vec<_> = setup
+        .roots
+        .iter()
+        .map(|screen| {
+            (
+                screen.root,
+                screen.width_in_px,
+                screen.height_in_px,
+                screen.root_depth,
+            )
+        })
+        .collect();
+    assert_eq!(
+        screens,
+        [(XId(0x100), 1920, 1080, 24), (XId(0x101), 1280, 1024, 24)]
+    );
+    let visual = &setup.roots[1].allowed_depths[0].visuals[0];
+    assert_eq!(visual.visual_id, setup.roots[1].root_visual);
+    assert_eq!(
+        (visual.red_mask, visual.green_mask, visual.blue_mask),
+        (0xff_0000, 0xff00, 0xff)
+    );
+
+    let root = conn.default_root();
+    let geometry = conn.get_geometry(root).unwrap().reply(&mut conn).unwrap();
+    assert_eq!(geometry.root, root);
+    assert_eq!((geometry.width, geometry.height), (1920, 1080));
+    let atom = conn
+        .intern_atom(false, "_NET_WM_NAME")
+        .unwrap()
+        .reply(&mut conn)
+        .unwrap()
+        .atom;
+    assert_eq!(atom, 0x150);
+    assert!(conn.sync().unwrap().is_empty());
+}
+
+#[test]
+fn mock_depth_30() {
+    let mut conn = replay("mock-depth-30.mtr");
+    let screen = conn.default_screen();
+    assert_eq!((screen.width_in_px, screen.height_in_px), (2560, 1440));
+    assert_eq!(screen.root_depth, 30);
+    let depth = &screen.allowed_depths[0];
+    assert_eq!(depth.depth, 30);
+    let visual = &depth.visuals[0];
+    assert_eq!(visual.visual_id, screen.root_visual);
+    assert_eq!(
+        (visual.bits_per_rgb_value, visual.colormap_entries),
+        (10, 1024)
+    );
+    assert_eq!(
+        (visual.red_mask, visual.green_mask, visual.blue_mask),
+        (0x3ff0_0000, 0x000f_fc00, 0x0000_03ff)
+    );
+
+    // The pixels are 0x3ff00000, 0x000ffc00, 0x000003ff, 0x20080200 and
+    // 0x1ff7fdff: 10 bits per channel, scaled down to 8.
+    let mut capturer = Capturer::new(&mut conn, false).unwrap();
+    assert!(!capturer.uses_shm());
+    let area = Rectangle {
+        x: 0,
+        y: 0,
+        width: 5,
+        height: 1,
+    };
+    let frame = capturer.capture_area(&mut conn, area).unwrap().unwrap();
+    assert_eq!(
+        frame.data,
+        [
+            0xff, 0, 0, 0xff, //
+            0, 0xff, 0, 0xff, //
+            0, 0, 0xff, 0xff, //
+            0x80, 0x80, 0x80, 0xff, //
+            0x7f, 0x7f, 0x7f, 0xff,
+        ]
+    );
+    assert!(conn.sync().unwrap().is_empty());
+}
@@ -0,0 +1,308 @@
+//! A connection against [MockServer]: replies, errors and events reach the
+//! client as a real server would send them, and the loss of the server ends
+//! the pending and later requests.
+
+use mousetrap::connection::ExtensionInfo;
+use mousetrap::errors::{ConnectionError, ErrorCode, SerializeError};
+use mousetrap::event::Event;
+use mousetrap::protocol::XId;
+#[cfg(feature = "async-std")]
+use mousetrap::reply::AtomNameReply;
+use mousetrap::reply::InputFocusReply;
+#[cfg(feature = "async-std")]
+use mousetrap::request::GetAtomName;
+use mousetrap::request::{opcode, GetInputFocus, PropMode};
+use mousetrap::testing::{MockServer, Response, FIRST_ROOT};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::thread;
+use std::time::Duration;
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Starts a server naming every atom "WM_NAME" and failing MapWindow with
+/// BadWindow.
+fn server() -> MockServer {
+    MockServer::builder()
+        .on(opcode::GET_ATOM_NAME, |_| {
+            let name = b"WM_NAME";
+            let mut body = (name.len() as u16).to_ne_bytes().to_vec();
+            body.resize(24, 0);
+            body.extend_from_slice(name);
+            Response::Reply { data: 0, body }
+        })
+        .on(opcode::MAP_WINDOW, |request| Response::Error {
+            code: 3,
+            bad_value: u32::from_ne_bytes(request.bytes[4..8].try_into().unwrap()),
+        })
+        .start()
+        .unwrap()
+}
+
+#[test]
+fn reply() {
+    let server = server();
+    let mut conn = server.connect().unwrap();
+    let cookie = conn.get_atom_name(39).unwrap();
+    assert_eq!(cookie.reply(&mut conn).unwrap().name.to_string(), "WM_NAME");
+    let request = server
+        .wait_for_request(opcode::GET_ATOM_NAME, TIMEOUT)
+        .unwrap();
+    assert_eq!(request.bytes[4..8], 39u32.to_ne_bytes());
+}
+
+#[test]
+fn error() {
+    let server = server();
+    let mut conn = server.connect().unwrap();
+    let sequence = conn.map_window(XId(0x0040_0001)).unwrap();
+    let errors = conn.sync().unwrap();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].code, ErrorCode::Window);
+    assert_eq!(errors[0].sequence, sequence);
+    assert_eq!(errors[0].bad_value, 0x0040_0001);
+    assert_eq!(errors[0].major_opcode, opcode::MAP_WINDOW);
+    assert_eq!(errors[0].request_name.as_deref(), Some("MapWindow"));
+}
+
+#[test]
+fn event() {
+    let server = server();
+    let mut conn = server.connect().unwrap();
+    conn.sync().unwrap();
+    // MapNotify: code, unused, sequence, event, window, override-redirect.
+    let mut event = [0u8; 32];
+    event[0] = 19;
+    event[4..8].copy_from_slice(&FIRST_ROOT.to_ne_bytes());
+    event[8..12].copy_from_slice(&0x0040_0001u32.to_ne_bytes());
+    event[12] = 1;
+    server.send_event(event).unwrap();
+    match conn.wait_for_event().unwrap() {
+        Event::MapNotify(event) => {
+            assert_eq!(event.event, XId(FIRST_ROOT));
+            assert_eq!(event.window, XId(0x0040_0001));
+            assert!(event.override_redirect);
+        }
+        event => panic!("expected MapNotify, got {:?}", event),
+    }
+}
+
+#[test]
+fn disconnect() {
+    let server = MockServer::builder().start().unwrap();
+    let mut conn = server.connect().unwrap();
+    // Never answered by the server.
+    let cookie = conn.get_atom_name(39).unwrap();
+    server
+        .wait_for_request(opcode::GET_ATOM_NAME, TIMEOUT)
+        .unwrap();
+    drop(server);
+    assert!(matches!(
+        cookie.reply(&mut conn),
+        Err(ConnectionError::Disconnected)
+    ));
+    assert!(matches!(conn.sync(), Err(ConnectionError::Disconnected)));
+    assert!(matches!(
+        conn.poll_for_event(),
+        Ok(Some(Event::ConnectionClosed))
+    ));
+}
+
+#[test]
+fn replies_nobody_waits_on_are_not_kept_past_a_wrap() {
+    // Each GetInputFocus is answered with the next focus window.
+    let next_focus = AtomicU32::new(0x3e8);
+    let server = MockServer::builder()
+        .discard_requests()
+        .on(opcode::GET_INPUT_FOCUS, move |_| Response::Reply {
+            data: 1,
+            body: next_focus
+                .fetch_add(1, Ordering::Relaxed)
+                .to_ne_bytes()
+                .to_vec(),
+        })
+        .start()
+        .unwrap();
+    let mut conn = server.connect().unwrap();
+    let request = GetInputFocus.serialize();
+    // Sent without a cookie, and with a cookie dropped.
+    let first = conn.send_request(&request).unwrap();
+    drop(
+        conn.send_request_with_reply::<InputFocusReply>(&request)
+            .unwrap(),
+    );
+    conn.sync().unwrap();
+    // NoOperation, until the sequence numbers come back round.
+    let mut sequence = first + 3;
+    while sequence != first.wrapping_sub(1) {
+        sequence = conn.send_request(&[127, 0, 1, 0]).unwrap();
+    }
+    for focus in [0x3eb, 0x3ec] {
+        let cookie = conn
+            .send_request_with_reply::<InputFocusReply>(&request)
+            .unwrap();
+        assert_eq!(cookie.reply(&mut conn).unwrap().focus, XId(focus));
+    }
+}
+
+/// Starts a server naming atom 1 "PRIMARY" after [SLOW_REPLY], and the others
+/// "WM_NAME" at once.
+fn slow_server() -> MockServer {
+    MockServer::builder()
+        .on(opcode::GET_ATOM_NAME, |request| {
+            let name: &[u8] = if request.bytes[4..8] == 1u32.to_ne_bytes() {
+                thread::sleep(SLOW_REPLY);
+                b"PRIMARY"
+            } else {
+                b"WM_NAME"
+            };
+            let mut body = (name.len() as u16).to_ne_bytes().to_vec();
+            body.resize(24, 0);
+            body.extend_from_slice(name);
+            Response::Reply { data: 0, body }
+        })
+        .start()
+        .unwrap()
+}
+
+/// How late the server answers, well past the timeouts of the tests.
+const SLOW_REPLY: Duration = Duration::from_millis(300);
+
+#[test]
+fn reply_timeout() {
+    let server = slow_server();
+    let mut conn = server.connect().unwrap();
+    let late = conn.get_atom_name(1).unwrap();
+    let reply = late.reply_timeout(&mut conn, Duration::from_millis(50));
+    assert!(reply.unwrap().is_none());
+    // The late reply arrives first, and is not taken for this one.
+    let cookie = conn.get_atom_name(39).unwrap();
+    let reply = cookie.reply_timeout(&mut conn, TIMEOUT).unwrap().unwrap();
+    assert_eq!(reply.name.to_string(), "WM_NAME");
+    assert!(conn.sync().unwrap().is_empty());
+}
+
+#[cfg(feature = "async-std")]
+#[test]
+fn async_reply_timeout() {
+    let server = slow_server();
+    async_io::block_on(async {
+        let mut conn = server.connect_async_std().await.unwrap();
+        let request = GetAtomName { atom: 1 }.serialize();
+        let late = conn
+            .send_request_with_reply::<AtomNameReply>(&request)
+            .await
+            .unwrap();
+        let reply = late
+            .reply_timeout(&mut conn, Duration::from_millis(50))
+            .await;
+        assert!(reply.unwrap().is_none());
+        let request = GetAtomName { atom: 39 }.serialize();
+        let cookie = conn
+            .send_request_with_reply::<AtomNameReply>(&request)
+            .await
+            .unwrap();
+        let reply = cookie
+            .reply_timeout(&mut conn, TIMEOUT)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(reply.name.to_string(), "WM_NAME");
+        assert!(conn.sync().await.unwrap().is_empty());
+    });
+}
+
+/// A property value too long for the 16-bit request length.
+const BIG_VALUE: usize = 300_000;
+
+#[test]
+fn long_requests_enable_big_requests() {
+    let info = ExtensionInfo {
+        major_opcode: 133,
+        first_event: 0,
+        first_error: 0,
+    };
+    let server = MockServer::builder()
+        .extension("BIG-REQUESTS", info)
+        .on(133, |_| Response::Reply {
+            data: 0,
+            body: 0x0040_0000u32.to_ne_bytes().to_vec(),
+        })
+        .start()
+        .unwrap();
+    let mut conn = server.connect().unwrap();
+    let value = vec![7u8; BIG_VALUE];
+    conn.change_property(PropMode::REPLACE, XId(FIRST_ROOT), 39, 31, 8, &value)
+        .unwrap();
+    let request = server
+        .wait_for_request(opcode::CHANGE_PROPERTY, TIMEOUT)
+        .unwrap();
+    // A length field of 0, then the long length, counting itself.
+    assert_eq!(request.bytes[2..4], [0, 0]);
+    let length = (24 + BIG_VALUE) / 4 + 1;
+    assert_eq!(request.bytes[4..8], (length as u32).to_ne_bytes());
+    assert_eq!(request.bytes.len(), length * 4);
+    assert_eq!(request.bytes[28..], value[..]);
+}
+
+#[test]
+fn long_requests_fail_without_big_requests() {
+    let server = server();
+    let mut conn = server.connect().unwrap();
+    let value = vec![7u8; BIG_VALUE];
+    let result = conn.change_property(PropMode::REPLACE, XId(FIRST_ROOT), 39, 31, 8, &value);
+    assert!(matches!(
+        result,
+        Err(ConnectionError::Serialize(SerializeError::TooLong {
+            field: "request",
+            ..
+        }))
+    ));
+    // Nothing was written, so the connection goes on.
+    assert_eq!(
+        conn.get_atom_name(39)
+            .unwrap()
+            .reply(&mut conn)
+            .unwrap()
+            .name
+            .to_string(),
+        "WM_NAME"
+    );
+}
+
+#[test]
+fn setup_with_reversed_keycodes() {
+    let server = MockServer::builder().keycodes(255, 8).start().unwrap();
+    let mut conn = server.connect().unwrap();
+    assert!(matches!(
+        conn.keyboard_mapping(),
+        Err(ConnectionError::InvalidResponseFromServer)
+    ));
+    // Nothing was asked of the server.
+    conn.sync().unwrap();
+    assert!(server
+        .requests()
+        .iter()
+        .all(|request| request.major_opcode != opcode::GET_KEYBOARD_MAPPING));
+}
+
+#[test]
+fn font_cursor_of_the_last_glyph() {
+    let server = server();
+    let mut conn = server.connect().unwrap();
+    // Its mask would be the glyph after it, which does not exist.
+    let error = conn.create_font_cursor(u16::MAX).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Request failed: Failed to serialize: The glyph cannot be 65535"
+    );
+    assert!(matches!(
+        error,
+        ConnectionError::Serialize(SerializeError::OutOfRange { field: "glyph", .. })
+    ));
+    // The font was not opened.
+    conn.sync().unwrap();
+    assert!(server
+        .requests()
+        .iter()
+        .all(|request| request.major_opcode != opcode::OPEN_FONT));
+}
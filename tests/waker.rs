@@ -0,0 +1,93 @@
+//! Wakeups arriving while the connection waits for a specific event: the wait
+//! goes on until the event arrives, and the wakeup stays queued for the next
+//! unfiltered wait.
+
+use mousetrap::clipboard::Clipboard;
+use mousetrap::connection::Connection;
+use mousetrap::event::Event;
+use mousetrap::request::opcode;
+use mousetrap::testing::{MockServer, ReceivedRequest, Response};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::thread;
+use std::time::Duration;
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Starts a server interning every name to a new atom.
+fn server() -> MockServer {
+    let next_atom = AtomicU32::new(0x150);
+    MockServer::builder()
+        .on(opcode::INTERN_ATOM, move |_| {
+            let atom = next_atom.fetch_add(1, Ordering::Relaxed);
+            Response::Reply {
+                data: 0,
+                body: atom.to_ne_bytes().to_vec(),
+            }
+        })
+        .start()
+        .unwrap()
+}
+
+fn word(request: &ReceivedRequest, offset: usize) -> [u8; 4] {
+    request.bytes[offset..offset + 4].try_into().unwrap()
+}
+
+/// Asserts that the wakeup is the only event left.
+fn assert_only_wakeup(conn: &mut Connection) {
+    assert!(matches!(conn.poll_for_event(), Ok(Some(Event::Wakeup))));
+    assert!(matches!(conn.poll_for_event(), Ok(None)));
+}
+
+#[test]
+fn wakeup_during_current_server_time() {
+    let server = server();
+    let mut conn = server.connect().unwrap();
+    let waker = conn.create_waker().unwrap();
+    let time = thread::scope(|scope| {
+        scope.spawn(|| {
+            let request = server
+                .wait_for_request(opcode::CHANGE_PROPERTY, TIMEOUT)
+                .unwrap();
+            waker.wake();
+            thread::sleep(Duration::from_millis(50));
+            // PropertyNotify: code, unused, sequence, window, atom, time, state.
+            let mut event = [0u8; 32];
+            event[0] = 28;
+            event[4..8].copy_from_slice(&word(&request, 4));
+            event[8..12].copy_from_slice(&word(&request, 8));
+            event[12..16].copy_from_slice(&1234u32.to_ne_bytes());
+            server.send_event(event).unwrap();
+        });
+        conn.current_server_time()
+    });
+    assert_eq!(time.unwrap(), 1234);
+    assert_only_wakeup(&mut conn);
+}
+
+#[test]
+fn wakeup_during_clipboard_convert() {
+    let server = server();
+    let mut conn = server.connect().unwrap();
+    let clipboard = Clipboard::new(&mut conn).unwrap();
+    let waker = conn.create_waker().unwrap();
+    let data = thread::scope(|scope| {
+        scope.spawn(|| {
+            let request = server
+                .wait_for_request(opcode::CONVERT_SELECTION, TIMEOUT)
+                .unwrap();
+            waker.wake();
+            thread::sleep(Duration::from_millis(50));
+            // SelectionNotify: code, unused, sequence, time, requestor,
+            // selection, target, property. A property of None is a refusal.
+            let mut event = [0u8; 32];
+            event[0] = 31;
+            event[8..12].copy_from_slice(&word(&request, 4));
+            event[12..16].copy_from_slice(&word(&request, 8));
+            event[16..20].copy_from_slice(&word(&request, 12));
+            server.send_event(event).unwrap();
+        });
+        clipboard.convert(&mut conn, 0x150, 0x151)
+    });
+    assert!(data.unwrap().is_none());
+    assert_only_wakeup(&mut conn);
+}